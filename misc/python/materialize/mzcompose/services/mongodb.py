@@ -0,0 +1,55 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+
+from materialize.mzcompose.service import (
+    Service,
+)
+
+
+class MongoDb(Service):
+    DEFAULT_VERSION = "7.0.14"
+    DEFAULT_REPLICA_SET = "rs0"
+
+    def __init__(
+        self,
+        name: str = "mongodb",
+        version: str = DEFAULT_VERSION,
+        port: int = 27017,
+        replica_set: str = DEFAULT_REPLICA_SET,
+    ) -> None:
+        # Change streams are only available on replica sets, so the server
+        # runs as the only member of one. The healthcheck initiates the replica
+        # set on first use and only passes once the member is writable.
+        initiate = (
+            f"try {{ rs.status() }} catch (e) {{ rs.initiate({{ _id: '{replica_set}', "
+            f"members: [{{ _id: 0, host: '{name}:{port}' }}] }}) }}; "
+            "quit(db.hello().isWritablePrimary ? 0 : 1)"
+        )
+
+        super().__init__(
+            name=name,
+            config={
+                "image": f"mongo:{version}",
+                "init": True,
+                "ports": [port],
+                "command": [
+                    "--replSet",
+                    replica_set,
+                    "--bind_ip_all",
+                    "--port",
+                    str(port),
+                ],
+                "healthcheck": {
+                    "test": ["CMD", "mongosh", "--quiet", "--eval", initiate],
+                    "interval": "1s",
+                    "start_period": "60s",
+                },
+            },
+        )
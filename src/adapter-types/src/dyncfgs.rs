@@ -137,6 +137,12 @@ pub const PERSIST_FAST_PATH_ORDER: Config<bool> = Config::new(
     "If set, send queries with a compatible literal constraint or ordering clause down the Persist fast path.",
 );
 
+pub const PEEK_RESULT_CACHE_MAX_BYTES_PER_CLUSTER: Config<usize> = Config::new(
+    "peek_result_cache_max_bytes_per_cluster",
+    0,
+    "The maximum number of bytes of peek results to cache per cluster, or 0 to disable the peek result cache.",
+);
+
 /// Adds the full set of all compute `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -158,4 +164,5 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&ENABLE_SELF_MANAGED_AUTH)
         .add(&CONSTRAINT_BASED_TIMESTAMP_SELECTION)
         .add(&PERSIST_FAST_PATH_ORDER)
+        .add(&PEEK_RESULT_CACHE_MAX_BYTES_PER_CLUSTER)
 }
@@ -3572,7 +3572,9 @@ mod tests {
                         | typ @ ScalarType::MzTimestamp
                         | typ @ ScalarType::List { .. }
                         | typ @ ScalarType::Map { .. }
-                        | typ @ ScalarType::MzAclItem => {
+                        | typ @ ScalarType::MzAclItem
+                        | typ @ ScalarType::HllSketch
+                        | typ @ ScalarType::QuantileSketch => {
                             panic!("{typ:?} type found in {full_name}");
                         }
                         ScalarType::AclItem
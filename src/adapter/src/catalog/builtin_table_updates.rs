@@ -328,11 +328,13 @@ impl CatalogState {
         updates.push(BuiltinTableUpdate::row(&*MZ_CLUSTERS, row, diff));
 
         if let ClusterVariant::Managed(managed_config) = &cluster.config.variant {
-            let row = match managed_config.schedule {
+            let row = match &managed_config.schedule {
                 ClusterSchedule::Manual => Row::pack_slice(&[
                     Datum::String(&id.to_string()),
                     Datum::String("manual"),
                     Datum::Null,
+                    Datum::Null,
+                    Datum::Null,
                 ]),
                 ClusterSchedule::Refresh {
                     hydration_time_estimate,
@@ -340,9 +342,18 @@ impl CatalogState {
                     Datum::String(&id.to_string()),
                     Datum::String("on-refresh"),
                     Datum::Interval(
-                        Interval::from_duration(&hydration_time_estimate)
+                        Interval::from_duration(hydration_time_estimate)
                             .expect("planning ensured that this is convertible back to Interval"),
                     ),
+                    Datum::Null,
+                    Datum::Null,
+                ]),
+                ClusterSchedule::Utilization { min_size, max_size } => Row::pack_slice(&[
+                    Datum::String(&id.to_string()),
+                    Datum::String("on-utilization"),
+                    Datum::Null,
+                    Datum::String(min_size),
+                    Datum::String(max_size),
                 ]),
             };
             updates.push(BuiltinTableUpdate::row(&*MZ_CLUSTER_SCHEDULES, row, diff));
@@ -598,9 +609,9 @@ impl CatalogState {
                                         diff,
                                     )
                                 }
-                                // Load generator sources don't have any special
-                                // updates.
-                                "load-generator" => vec![],
+                                // Load generator, MongoDB, and Kinesis sources don't
+                                // have any special updates.
+                                "load-generator" | "mongodb" | "kinesis" => vec![],
                                 "kafka" => {
                                     mz_ore::soft_assert_eq_no_log!(external_reference.len(), 1);
                                     let topic = external_reference[0].to_ast_string_simple();
@@ -729,9 +740,9 @@ impl CatalogState {
                                     diff,
                                 )
                             }
-                            // Load generator sources don't have any special
-                            // updates.
-                            "load-generator" => vec![],
+                            // Load generator and MongoDB sources don't have
+                            // any special updates.
+                            "load-generator" | "mongodb" => vec![],
                             s => unreachable!("{s} sources do not have subsources"),
                         }
                     }
@@ -1138,6 +1149,7 @@ impl CatalogState {
                     ConnectionDetails::Ssh { .. } => "ssh-tunnel",
                     ConnectionDetails::MySql { .. } => "mysql",
                     ConnectionDetails::SqlServer(_) => "sql-server",
+                    ConnectionDetails::MongoDb(_) => "mongodb",
                 }),
                 Datum::String(&owner_id.to_string()),
                 privileges,
@@ -1181,7 +1193,8 @@ impl CatalogState {
             ConnectionDetails::Csr(_)
             | ConnectionDetails::Postgres(_)
             | ConnectionDetails::MySql(_)
-            | ConnectionDetails::SqlServer(_) => (),
+            | ConnectionDetails::SqlServer(_)
+            | ConnectionDetails::MongoDb(_) => (),
         };
         updates
     }
@@ -1561,6 +1574,10 @@ impl CatalogState {
                     diff,
                 ));
             }
+            StorageSinkConnection::Iceberg(_)
+            | StorageSinkConnection::S3(_)
+            | StorageSinkConnection::Postgres(_)
+            | StorageSinkConnection::Http(_) => {}
         };
 
         let create_stmt = mz_sql::parse::parse(&sink.create_sql)
@@ -243,9 +243,10 @@ fn ast_rewrite_sources_to_tables(
     use mz_sql::ast::{
         CreateSourceConnection, CreateSourceStatement, CreateSubsourceOptionName,
         CreateSubsourceStatement, CreateTableFromSourceStatement, Ident,
-        KafkaSourceConfigOptionName, LoadGenerator, MySqlConfigOptionName, PgConfigOptionName,
-        RawItemName, TableFromSourceColumns, TableFromSourceOption, TableFromSourceOptionName,
-        UnresolvedItemName, Value, WithOptionValue,
+        KafkaSourceConfigOptionName, KinesisSourceConfigOptionName, LoadGenerator,
+        MySqlConfigOptionName, PgConfigOptionName, RawItemName, TableFromSourceColumns,
+        TableFromSourceOption, TableFromSourceOptionName, UnresolvedItemName, Value,
+        WithOptionValue,
     };
     use mz_storage_client::controller::StorageTxn;
     use mz_storage_types::sources::SourceExportStatementDetails;
@@ -506,6 +507,7 @@ fn ast_rewrite_sources_to_tables(
             Statement::CreateSource(CreateSourceStatement {
                 connection:
                     conn @ (CreateSourceConnection::Kafka { .. }
+                    | CreateSourceConnection::Kinesis { .. }
                     | CreateSourceConnection::LoadGenerator {
                         generator:
                             LoadGenerator::Clock
@@ -604,6 +606,9 @@ fn ast_rewrite_sources_to_tables(
                 let details = match &conn {
                     // For kafka sources this proto is currently empty.
                     CreateSourceConnection::Kafka { .. } => SourceExportStatementDetails::Kafka {},
+                    CreateSourceConnection::Kinesis { .. } => {
+                        SourceExportStatementDetails::Kinesis {}
+                    }
                     CreateSourceConnection::LoadGenerator { .. } => {
                         // Since these load generators are single-output we use the default output.
                         SourceExportStatementDetails::LoadGenerator {
@@ -634,6 +639,18 @@ fn ast_rewrite_sources_to_tables(
 
                         Some(UnresolvedItemName::qualified(&[Ident::new(topic)?]))
                     }
+                    CreateSourceConnection::Kinesis { options, .. } => {
+                        let stream_option = options
+                            .iter()
+                            .find(|o| matches!(o.name, KinesisSourceConfigOptionName::Stream))
+                            .expect("kinesis sources must have a stream");
+                        let stream = match &stream_option.value {
+                            Some(WithOptionValue::Value(Value::String(stream))) => stream,
+                            _ => unreachable!("stream must be a string"),
+                        };
+
+                        Some(UnresolvedItemName::qualified(&[Ident::new(stream)?]))
+                    }
                     CreateSourceConnection::LoadGenerator { generator, .. } => {
                         // Since these load generators are single-output the external reference
                         // uses the schema-name for both namespace and name.
@@ -757,6 +774,13 @@ fn ast_rewrite_sources_to_tables(
                 ..
             }) => (),
 
+            // MongoDB sources were introduced after the source table
+            // migration, so there is nothing to migrate.
+            Statement::CreateSource(CreateSourceStatement {
+                connection: CreateSourceConnection::MongoDb { .. },
+                ..
+            }) => (),
+
             #[expect(unreachable_patterns)]
             Statement::CreateSource(_) => {}
             _ => (),
@@ -929,6 +929,7 @@ fn add_new_remove_old_builtin_clusters_migration(
                         logging: default_logging_config(),
                         optimizer_feature_overrides: Default::default(),
                         schedule: Default::default(),
+                        auto_suspend_after: None,
                     }),
                     workload_class: None,
                 },
@@ -1815,6 +1815,8 @@ impl CatalogState {
             CatalogType::VarChar => CatalogType::VarChar,
             CatalogType::Int2Vector => CatalogType::Int2Vector,
             CatalogType::MzAclItem => CatalogType::MzAclItem,
+            CatalogType::HllSketch => CatalogType::HllSketch,
+            CatalogType::QuantileSketch => CatalogType::QuantileSketch,
         };
 
         BuiltinType {
@@ -22,7 +22,7 @@ use mz_adapter_types::dyncfgs::{
 };
 use mz_audit_log::{
     CreateOrDropClusterReplicaReasonV1, EventDetails, EventType, IdFullNameV1, IdNameV1,
-    ObjectType, SchedulingDecisionsWithReasonsV2, VersionedEvent, VersionedStorageUsage,
+    ObjectType, SchedulingDecisionsWithReasonsV3, VersionedEvent, VersionedStorageUsage,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_catalog::builtin::BuiltinLog;
@@ -296,7 +296,8 @@ pub enum ReplicaCreateDropReason {
     /// - CREATE/DROP CLUSTER REPLICA on an unmanaged cluster.
     Manual,
     /// The automated cluster scheduling initiated the replica create or drop, e.g., a
-    /// materialized view is needing a refresh on a SCHEDULE ON REFRESH cluster.
+    /// materialized view is needing a refresh on a SCHEDULE ON REFRESH cluster, or a
+    /// SCHEDULE ON UTILIZATION cluster is being resized.
     ClusterScheduling(Vec<SchedulingDecision>),
 }
 
@@ -305,7 +306,7 @@ impl ReplicaCreateDropReason {
         self,
     ) -> (
         CreateOrDropClusterReplicaReasonV1,
-        Option<SchedulingDecisionsWithReasonsV2>,
+        Option<SchedulingDecisionsWithReasonsV3>,
     ) {
         let (reason, scheduling_policies) = match self {
             ReplicaCreateDropReason::Manual => (CreateOrDropClusterReplicaReasonV1::Manual, None),
@@ -1009,8 +1010,8 @@ impl Catalog {
                 }) = &config.location
                 {
                     let (reason, scheduling_policies) = reason.into_audit_log();
-                    let details = EventDetails::CreateClusterReplicaV3(
-                        mz_audit_log::CreateClusterReplicaV3 {
+                    let details = EventDetails::CreateClusterReplicaV4(
+                        mz_audit_log::CreateClusterReplicaV4 {
                             cluster_id: cluster_id.to_string(),
                             cluster_name: cluster.name.clone(),
                             replica_id: Some(id.to_string()),
@@ -1527,7 +1528,7 @@ impl Catalog {
 
                     let (reason, scheduling_policies) = reason.into_audit_log();
                     let details =
-                        EventDetails::DropClusterReplicaV3(mz_audit_log::DropClusterReplicaV3 {
+                        EventDetails::DropClusterReplicaV4(mz_audit_log::DropClusterReplicaV4 {
                             cluster_id: cluster_id.to_string(),
                             cluster_name: cluster.name.clone(),
                             replica_id: Some(replica_id.to_string()),
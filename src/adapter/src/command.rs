@@ -644,8 +644,9 @@ impl ExecuteResponse {
             DropObjects => &[DroppedObject],
             DropOwned => &[DroppedOwned],
             PlanKind::EmptyQuery => &[ExecuteResponseKind::EmptyQuery],
-            ExplainPlan | ExplainPushdown | ExplainTimestamp | Select | ShowAllVariables
-            | ShowCreate | ShowColumns | ShowVariable | InspectShard | ExplainSinkSchema => &[
+            ExplainPlan | ExplainPushdown | ExplainAnalyze | ExplainTimestamp | Select
+            | ShowAllVariables | ShowCreate | ShowColumns | ShowVariable | InspectShard
+            | ExplainSinkSchema => &[
                 ExecuteResponseKind::CopyTo,
                 SendingRows,
                 SendingRowsImmediate,
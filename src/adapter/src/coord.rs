@@ -106,10 +106,11 @@ use mz_compute_client::as_of_selection;
 use mz_compute_client::controller::error::InstanceMissing;
 use mz_compute_types::ComputeInstanceId;
 use mz_compute_types::dataflows::DataflowDescription;
-use mz_compute_types::plan::Plan;
+use mz_compute_types::plan::{LirId, Plan};
 use mz_controller::ControllerConfig;
 use mz_controller::clusters::{ClusterConfig, ClusterEvent, ClusterStatus, ProcessId};
 use mz_controller_types::{ClusterId, ReplicaId, WatchSetId};
+use mz_expr::row::RowCollection;
 use mz_expr::{MapFilterProject, OptimizedMirRelationExpr, RowSetFinishing};
 use mz_license_keys::ValidatedLicenseKey;
 use mz_orchestrator::{OfflineReason, ServiceProcessMetrics};
@@ -128,7 +129,7 @@ use mz_ore::{
 };
 use mz_persist_client::batch::ProtoBatch;
 use mz_persist_client::usage::{ShardsUsageReferenced, StorageUsageClient};
-use mz_repr::explain::{ExplainConfig, ExplainFormat};
+use mz_repr::explain::{ExplainConfig, ExplainFormat, Measurements};
 use mz_repr::global_id::TransientIdGen;
 use mz_repr::optimize::OptimizerFeatures;
 use mz_repr::role_id::RoleId;
@@ -145,8 +146,8 @@ use mz_sql::plan::{
 };
 use mz_sql::session::user::User;
 use mz_sql::session::vars::SystemVars;
-use mz_sql_parser::ast::ExplainStage;
 use mz_sql_parser::ast::display::AstDisplay;
+use mz_sql_parser::ast::{ExplainAnalyzeProperty, ExplainStage};
 use mz_storage_client::client::TableData;
 use mz_storage_client::controller::{CollectionDescription, DataSource, ExportDescription};
 use mz_storage_types::connections::Connection as StorageConnection;
@@ -178,14 +179,16 @@ use crate::catalog::{BuiltinTableUpdate, Catalog, OpenCatalogResult};
 use crate::client::{Client, Handle};
 use crate::command::{Command, ExecuteResponse};
 use crate::config::{SynchronizedParameters, SystemParameterFrontend, SystemParameterSyncConfig};
+use crate::coord::admission::{PeekAdmission, PeekReservation};
 use crate::coord::appends::{
     BuiltinTableAppendNotify, DeferredOp, GroupCommitPermit, PendingWriteTxn,
 };
 use crate::coord::caught_up::CaughtUpCheckContext;
-use crate::coord::cluster_scheduling::SchedulingDecision;
+use crate::coord::cluster_scheduling::{AutoSuspendState, SchedulingDecision, UtilizationState};
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::introspection::IntrospectionSubscribe;
-use crate::coord::peek::PendingPeek;
+use crate::coord::peek::{ExplainAnalyzeDataflow, PendingPeek};
+use crate::coord::peek_result_cache::{PeekResultCache, PeekResultCacheKey};
 use crate::coord::statement_logging::{StatementLogging, StatementLoggingId};
 use crate::coord::timeline::{TimelineContext, TimelineState};
 use crate::coord::timestamp_selection::{TimestampContext, TimestampDetermination};
@@ -204,9 +207,11 @@ use crate::util::{ClientTransmitter, ResultExt};
 use crate::webhook::{WebhookAppenderInvalidator, WebhookConcurrencyLimiter};
 use crate::{AdapterNotice, ReadHolds, flags};
 
+pub(crate) mod admission;
 pub(crate) mod id_bundle;
 pub(crate) mod in_memory_oracle;
 pub(crate) mod peek;
+pub(crate) mod peek_result_cache;
 pub(crate) mod statement_logging;
 pub(crate) mod timeline;
 pub(crate) mod timestamp_selection;
@@ -318,6 +323,23 @@ pub enum Message {
         span: Span,
         stage: ClusterStage,
     },
+    /// The rows of a completed peek, to be added to the peek result cache.
+    PeekResultCacheInsert {
+        key: PeekResultCacheKey,
+        /// The collections the rows were read from.
+        inputs: CollectionIdBundle,
+        rows: RowCollection,
+    },
+    /// A slot on the cluster was released, which might let queued peeks be
+    /// admitted.
+    AdmitQueuedPeeks(ClusterId),
+    /// The dataflow installed to analyze a SELECT is no longer needed.
+    DropExplainAnalyzeDataflow {
+        cluster_id: ClusterId,
+        index_id: GlobalId,
+    },
+    /// A deferred DDL statement may have reached its deadline.
+    DeferredStatementTimeout,
     ExplainTimestampStageReady {
         ctx: ExecuteContext,
         span: Span,
@@ -385,12 +407,16 @@ impl Message {
             }
             Message::SecretStageReady { .. } => "secret_stage_ready",
             Message::ClusterStageReady { .. } => "cluster_stage_ready",
+            Message::PeekResultCacheInsert { .. } => "peek_result_cache_insert",
+            Message::AdmitQueuedPeeks(_) => "admit_queued_peeks",
+            Message::DropExplainAnalyzeDataflow { .. } => "drop_explain_analyze_dataflow",
             Message::DrainStatementLog => "drain_statement_log",
             Message::AlterConnectionValidationReady(..) => "alter_connection_validation_ready",
             Message::PrivateLinkVpcEndpointEvents(_) => "private_link_vpc_endpoint_events",
             Message::CheckSchedulingPolicies => "check_scheduling_policies",
             Message::SchedulingDecisions { .. } => "scheduling_decision",
             Message::DeferredStatementReady => "deferred_statement_ready",
+            Message::DeferredStatementTimeout => "deferred_statement_timeout",
         }
     }
 }
@@ -437,6 +463,13 @@ pub enum PeekStage {
     /// Final stage for an explain.
     ExplainPlan(PeekStageExplainPlan),
     ExplainPushdown(PeekStageExplainPushdown),
+    /// Installs the dataflow of a SELECT that is analyzed by an `EXPLAIN
+    /// ANALYZE`, and waits for it to hydrate.
+    ExplainAnalyzeDataflow(PeekStageExplainAnalyzeDataflow),
+    /// Issues the introspection query that measures an analyzed dataflow.
+    ExplainAnalyzeMeasure(PeekStageExplainAnalyzeMeasure),
+    /// Final stage for an `EXPLAIN ANALYZE`.
+    ExplainAnalyzeRender(PeekStageExplainAnalyzeRender),
     /// Preflight checks for a copy to operation.
     CopyToPreflight(PeekStageCopyTo),
     /// Final stage for a copy to which involves shipping the dataflow.
@@ -541,6 +574,12 @@ pub struct PeekStageFinish {
     insights_ctx: Option<Box<PlanInsightsContext>>,
     global_lir_plan: optimize::peek::GlobalLirPlan,
     optimization_finished_at: EpochMillis,
+    /// The slot reserved for the peek on its cluster, once it was admitted
+    /// from the admission queue.
+    reservation: Option<PeekReservation>,
+    /// When present, the peek reads the measurements of a dataflow analyzed by
+    /// an `EXPLAIN ANALYZE`.
+    explain_analyze: Option<Box<ExplainAnalyzeMeasureContext>>,
 }
 
 #[derive(Debug)]
@@ -568,6 +607,28 @@ pub struct PeekStageExplainPushdown {
     imports: BTreeMap<GlobalId, MapFilterProject>,
 }
 
+#[derive(Debug)]
+pub struct PeekStageExplainAnalyzeDataflow {
+    validity: PlanValidity,
+    determination: TimestampDetermination<mz_repr::Timestamp>,
+    cluster_id: ComputeInstanceId,
+    global_lir_plan: optimize::peek::GlobalLirPlan,
+    explain_ctx: ExplainAnalyzeContext,
+}
+
+#[derive(Debug)]
+pub struct PeekStageExplainAnalyzeMeasure {
+    validity: PlanValidity,
+    measure_ctx: ExplainAnalyzeMeasureContext,
+}
+
+#[derive(Debug)]
+pub struct PeekStageExplainAnalyzeRender {
+    validity: PlanValidity,
+    measure_ctx: ExplainAnalyzeMeasureContext,
+    measurements: BTreeMap<LirId, Measurements>,
+}
+
 #[derive(Debug)]
 pub enum CreateIndexStage {
     Optimize(CreateIndexOptimize),
@@ -717,6 +778,12 @@ pub enum ExplainContext {
     PlanInsightsNotice(OptimizerTrace),
     /// `EXPLAIN FILTER PUSHDOWN`
     Pushdown,
+    /// `EXPLAIN ANALYZE` of a SELECT, which installs the dataflow of the
+    /// SELECT to measure it.
+    Analyze(ExplainAnalyzeContext),
+    /// The introspection query that measures the dataflow analyzed by an
+    /// `EXPLAIN ANALYZE`.
+    AnalyzeMeasure(Box<ExplainAnalyzeMeasureContext>),
 }
 
 impl ExplainContext {
@@ -738,6 +805,8 @@ impl ExplainContext {
             ExplainContext::Plan(..) => false,
             ExplainContext::PlanInsightsNotice(..) => true,
             ExplainContext::Pushdown => false,
+            ExplainContext::Analyze(..) => true,
+            ExplainContext::AnalyzeMeasure(..) => true,
         }
     }
 
@@ -763,6 +832,25 @@ pub struct ExplainPlanContext {
     pub optimizer_trace: OptimizerTrace,
 }
 
+#[derive(Debug)]
+pub struct ExplainAnalyzeContext {
+    pub properties: Vec<ExplainAnalyzeProperty>,
+    pub skew: bool,
+    pub format: ExplainFormat,
+    pub config: ExplainConfig,
+}
+
+#[derive(Debug)]
+pub struct ExplainAnalyzeMeasureContext {
+    explain_ctx: ExplainAnalyzeContext,
+    /// The cluster that runs the analyzed dataflow.
+    cluster_id: ComputeInstanceId,
+    /// The physical plan of the analyzed dataflow.
+    dataflow: DataflowDescription<Plan>,
+    /// Drops the dataflow of an analyzed SELECT once it has been measured.
+    dataflow_guard: Option<ExplainAnalyzeDataflow>,
+}
+
 #[derive(Debug)]
 pub enum CreateMaterializedViewStage {
     Optimize(CreateMaterializedViewOptimize),
@@ -1664,6 +1752,11 @@ pub struct Coordinator {
     pending_peeks: BTreeMap<Uuid, PendingPeek>,
     /// A map from client connection ids to a set of all pending peeks for that client.
     client_pending_peeks: BTreeMap<ConnectionId, BTreeMap<Uuid, ClusterId>>,
+    /// Results of completed peeks, for answering identical peeks at the same
+    /// timestamp without sending them to a cluster.
+    peek_result_cache: PeekResultCache,
+    /// Admission state of peeks, by cluster and workload class.
+    peek_admission: PeekAdmission,
 
     /// A map from client connection ids to pending linearize read transaction.
     pending_linearize_read_txns: BTreeMap<ConnectionId, PendingReadTxn>,
@@ -1759,11 +1852,19 @@ pub struct Coordinator {
     /// Periodically asks cluster scheduling policies to make their decisions.
     check_cluster_scheduling_policies_interval: Interval,
 
-    /// This keeps the last On/Off decision for each cluster and each scheduling policy.
+    /// This keeps the last decision for each cluster and each scheduling policy.
     /// (Clusters that have been dropped or are otherwise out of scope for automatic scheduling are
     /// periodically cleaned up from this Map.)
     cluster_scheduling_decisions: BTreeMap<ClusterId, BTreeMap<&'static str, SchedulingDecision>>,
 
+    /// What the `SCHEDULE = ON UTILIZATION` policy remembers about each such cluster between its
+    /// runs, e.g., since when the utilization has been high, and in-progress resizes.
+    cluster_utilization_state: BTreeMap<ClusterId, UtilizationState>,
+
+    /// What the `AUTO SUSPEND AFTER` policy remembers about each such cluster, e.g., when it last
+    /// served a query, and which queries wait for it to be resumed.
+    cluster_auto_suspend_state: BTreeMap<ClusterId, AutoSuspendState>,
+
     /// When doing 0dt upgrades/in read-only mode, periodically ask all known
     /// clusters/collections whether they are caught up.
     caught_up_check_interval: Interval,
@@ -4259,6 +4360,8 @@ pub fn serve(
                     txn_read_holds: Default::default(),
                     pending_peeks: BTreeMap::new(),
                     client_pending_peeks: BTreeMap::new(),
+                    peek_result_cache: PeekResultCache::default(),
+                    peek_admission: PeekAdmission::default(),
                     pending_linearize_read_txns: BTreeMap::new(),
                     serialized_ddl: LockedVecDeque::new(),
                     active_compute_sinks: BTreeMap::new(),
@@ -4285,6 +4388,8 @@ pub fn serve(
                     pg_timestamp_oracle_config,
                     check_cluster_scheduling_policies_interval: check_scheduling_policies_interval,
                     cluster_scheduling_decisions: BTreeMap::new(),
+                    cluster_utilization_state: BTreeMap::new(),
+                    cluster_auto_suspend_state: BTreeMap::new(),
                     caught_up_check_interval: clusters_caught_up_check_interval,
                     caught_up_check: clusters_caught_up_check,
                     installed_watch_sets: BTreeMap::new(),
@@ -4514,6 +4619,8 @@ pub async fn load_remote_system_parameters(
 pub(crate) enum WatchSetResponse {
     StatementDependenciesReady(StatementLoggingId, StatementLifecycleEvent),
     AlterSinkReady(AlterSinkReadyContext),
+    /// The dataflow installed for an `EXPLAIN ANALYZE` has hydrated.
+    ExplainAnalyzeReady(oneshot::Sender<()>),
 }
 
 #[derive(Debug)]
@@ -4571,10 +4678,6 @@ impl<T> LockedVecDeque<T> {
         self.items.is_empty()
     }
 
-    pub fn push_back(&mut self, value: T) {
-        self.items.push_back(value)
-    }
-
     pub fn pop_front(&mut self) -> Option<T> {
         self.items.pop_front()
     }
@@ -4592,6 +4695,33 @@ impl<T> LockedVecDeque<T> {
 struct DeferredPlanStatement {
     ctx: ExecuteContext,
     ps: PlanStatement,
+    /// When the statement stops waiting for its turn and is canceled, if ever.
+    deadline: Option<Instant>,
+}
+
+impl LockedVecDeque<DeferredPlanStatement> {
+    /// Queues `deferred` behind all deferred statements of sessions whose
+    /// workload class has the same or a higher priority, and ahead of those
+    /// of lower priority.
+    fn push_by_workload_class(&mut self, deferred: DeferredPlanStatement) {
+        let class = deferred.ctx.session().vars().workload_class();
+        let idx = self
+            .items
+            .iter()
+            .position(|other| other.ctx.session().vars().workload_class() < class)
+            .unwrap_or(self.items.len());
+        self.items.insert(idx, deferred);
+    }
+
+    /// Removes and returns the deferred statements whose deadline is at or
+    /// before `now`.
+    fn take_expired(&mut self, now: Instant) -> Vec<DeferredPlanStatement> {
+        let (expired, items) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition(|deferred| deferred.deadline.is_some_and(|deadline| deadline <= now));
+        self.items = items;
+        expired
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,446 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Admission control for peeks and DDL statements, based on the workload class
+//! of the issuing session.
+//!
+//! Each cluster admits a limited number of concurrent peeks, overall and per
+//! workload class. A peek that would exceed a limit is queued until a running
+//! peek on the cluster completes. Queued peeks of higher priority workload
+//! classes are admitted first, and peeks of the same class are admitted in the
+//! order they were queued.
+//!
+//! A peek is counted against the limits of its cluster from the moment it is
+//! admitted until it is removed from the coordinator's pending peeks. Between
+//! the two, a queued peek holds a [`PeekReservation`], which releases its slot
+//! when dropped, whether the peek is issued, fails, or is canceled.
+//!
+//! DDL statements already execute one at a time, so they are not subject to
+//! concurrency limits. A DDL statement that has to wait for another one is
+//! queued ahead of the statements of lower priority workload classes, and is
+//! canceled if it waits for longer than the workload class queue timeout.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Weak};
+use std::time::Instant;
+
+use mz_controller_types::ClusterId;
+use mz_ore::cast::CastFrom;
+use mz_sql::session::vars::{SystemVars, WorkloadClass};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::coord::{Coordinator, DeferredPlanStatement, ExecuteContext, Message, PlanStatement};
+use crate::error::AdapterError;
+
+/// Concurrency limits for the peeks on a cluster. A limit of zero means no
+/// limit.
+#[derive(Clone, Debug)]
+pub struct AdmissionLimits {
+    /// The maximum number of concurrent peeks across all workload classes.
+    pub cluster: usize,
+    /// The maximum number of concurrent peeks of each workload class.
+    pub classes: BTreeMap<WorkloadClass, usize>,
+}
+
+impl AdmissionLimits {
+    pub fn from_system_vars(vars: &SystemVars) -> Self {
+        AdmissionLimits {
+            cluster: usize::cast_from(vars.max_concurrent_peeks_per_cluster()),
+            classes: WorkloadClass::ALL
+                .into_iter()
+                .map(|class| (class, usize::cast_from(vars.max_concurrent_peeks(class))))
+                .collect(),
+        }
+    }
+
+    /// Reports whether another peek of `class` can be admitted when `running`
+    /// peeks of each class are already admitted.
+    fn admits(&self, class: WorkloadClass, running: &BTreeMap<WorkloadClass, usize>) -> bool {
+        let within = |limit: usize, count: usize| limit == 0 || count < limit;
+        let class_limit = self.classes.get(&class).copied().unwrap_or(0);
+        within(self.cluster, running.values().sum())
+            && within(class_limit, running.get(&class).copied().unwrap_or(0))
+    }
+}
+
+/// A slot on a cluster, reserved for a queued peek when it is admitted and
+/// held until the peek is issued.
+///
+/// Dropping the reservation releases the slot and prompts the coordinator to
+/// admit further queued peeks.
+#[derive(Debug)]
+pub struct PeekReservation {
+    cluster_id: ClusterId,
+    /// Counted by [`PeekAdmission`] for as long as it is alive.
+    token: Option<Arc<()>>,
+    internal_cmd_tx: mpsc::UnboundedSender<Message>,
+}
+
+impl Drop for PeekReservation {
+    fn drop(&mut self) {
+        // Release the slot before notifying the coordinator, so that it is no
+        // longer counted when the message is handled.
+        drop(self.token.take());
+        // The coordinator is gone if sending fails, and nothing is left to
+        // admit.
+        let _ = self
+            .internal_cmd_tx
+            .send(Message::AdmitQueuedPeeks(self.cluster_id));
+    }
+}
+
+/// A peek waiting for admission.
+#[derive(Debug)]
+struct QueuedPeek {
+    tx: oneshot::Sender<PeekReservation>,
+}
+
+/// The admission state of the peeks on all clusters.
+#[derive(Debug, Default)]
+pub struct PeekAdmission {
+    /// Peeks waiting for admission, by cluster and workload class.
+    queued: BTreeMap<ClusterId, BTreeMap<WorkloadClass, VecDeque<QueuedPeek>>>,
+    /// The reservations of peeks that were admitted but not yet issued, by
+    /// cluster. Dropped reservations are pruned lazily.
+    reserved: BTreeMap<ClusterId, Vec<(WorkloadClass, Weak<()>)>>,
+}
+
+impl PeekAdmission {
+    /// Reports whether a new peek of `class` on `cluster_id` can be admitted
+    /// right away, given the peeks that are `running` on the cluster.
+    ///
+    /// A new peek is never admitted ahead of queued peeks of the same class.
+    pub fn try_admit(
+        &mut self,
+        cluster_id: ClusterId,
+        class: WorkloadClass,
+        running: BTreeMap<WorkloadClass, usize>,
+        limits: &AdmissionLimits,
+    ) -> bool {
+        let running = self.with_reserved(cluster_id, running);
+        let queue_is_empty = match self
+            .queued
+            .get_mut(&cluster_id)
+            .and_then(|queues| queues.get_mut(&class))
+        {
+            Some(queue) => {
+                // Waiters that have timed out or were canceled no longer count.
+                queue.retain(|peek| !peek.tx.is_closed());
+                queue.is_empty()
+            }
+            None => true,
+        };
+        queue_is_empty && limits.admits(class, &running)
+    }
+
+    /// Queues a peek of `class` on `cluster_id`. The returned receiver yields
+    /// the peek's reservation once it is admitted.
+    pub fn enqueue(
+        &mut self,
+        cluster_id: ClusterId,
+        class: WorkloadClass,
+    ) -> oneshot::Receiver<PeekReservation> {
+        let (tx, rx) = oneshot::channel();
+        self.queued
+            .entry(cluster_id)
+            .or_default()
+            .entry(class)
+            .or_default()
+            .push_back(QueuedPeek { tx });
+        rx
+    }
+
+    /// Admits as many queued peeks on `cluster_id` as the limits allow, given
+    /// the peeks that are `running` on the cluster, in order of priority.
+    ///
+    /// The reservations of admitted peeks report their release to
+    /// `internal_cmd_tx`.
+    pub fn admit_queued(
+        &mut self,
+        cluster_id: ClusterId,
+        running: BTreeMap<WorkloadClass, usize>,
+        limits: &AdmissionLimits,
+        internal_cmd_tx: &mpsc::UnboundedSender<Message>,
+    ) {
+        let mut running = self.with_reserved(cluster_id, running);
+        let Some(queues) = self.queued.get_mut(&cluster_id) else {
+            return;
+        };
+        for class in WorkloadClass::ALL {
+            let Some(queue) = queues.get_mut(&class) else {
+                continue;
+            };
+            while !queue.is_empty() && limits.admits(class, &running) {
+                let peek = queue.pop_front().expect("known to be non-empty");
+                if peek.tx.is_closed() {
+                    // The peek timed out or was canceled while waiting.
+                    continue;
+                }
+                let token = Arc::new(());
+                self.reserved
+                    .entry(cluster_id)
+                    .or_default()
+                    .push((class, Arc::downgrade(&token)));
+                let reservation = PeekReservation {
+                    cluster_id,
+                    token: Some(token),
+                    internal_cmd_tx: internal_cmd_tx.clone(),
+                };
+                // If the receiver went away in the meantime, the returned
+                // reservation is dropped right away and releases the slot.
+                let _ = peek.tx.send(reservation);
+                *running.entry(class).or_default() += 1;
+            }
+        }
+        queues.retain(|_, queue| !queue.is_empty());
+        if queues.is_empty() {
+            self.queued.remove(&cluster_id);
+        }
+    }
+
+    /// Drops all queued peeks on `cluster_id` and stops counting its
+    /// reservations.
+    ///
+    /// Queued peeks observe this as the closing of their receiver.
+    pub fn remove_cluster(&mut self, cluster_id: &ClusterId) {
+        self.queued.remove(cluster_id);
+        self.reserved.remove(cluster_id);
+    }
+
+    /// Adds the live reservations on `cluster_id` to the `running` peeks.
+    fn with_reserved(
+        &mut self,
+        cluster_id: ClusterId,
+        mut running: BTreeMap<WorkloadClass, usize>,
+    ) -> BTreeMap<WorkloadClass, usize> {
+        if let Some(reserved) = self.reserved.get_mut(&cluster_id) {
+            reserved.retain(|(_, token)| token.strong_count() > 0);
+            for (class, _) in reserved.iter() {
+                *running.entry(*class).or_default() += 1;
+            }
+            if reserved.is_empty() {
+                self.reserved.remove(&cluster_id);
+            }
+        }
+        running
+    }
+}
+
+impl Coordinator {
+    /// Reports whether a new peek of `class` on `cluster_id` can be admitted
+    /// right away.
+    pub(crate) fn try_admit_peek(&mut self, cluster_id: ClusterId, class: WorkloadClass) -> bool {
+        let running = self.running_peeks(cluster_id);
+        let limits = AdmissionLimits::from_system_vars(self.catalog().system_config());
+        self.peek_admission
+            .try_admit(cluster_id, class, running, &limits)
+    }
+
+    /// Admits as many queued peeks on `cluster_id` as its limits allow.
+    pub(crate) fn admit_queued_peeks(&mut self, cluster_id: ClusterId) {
+        let running = self.running_peeks(cluster_id);
+        let limits = AdmissionLimits::from_system_vars(self.catalog().system_config());
+        self.peek_admission
+            .admit_queued(cluster_id, running, &limits, &self.internal_cmd_tx);
+    }
+
+    /// Queues a DDL statement until it can take the DDL lock, ahead of the
+    /// statements of lower priority workload classes.
+    ///
+    /// The statement is canceled if it is still queued once the workload class
+    /// queue timeout has passed.
+    pub(super) fn defer_ddl(&mut self, ctx: ExecuteContext, ps: PlanStatement) {
+        let timeout = self
+            .catalog()
+            .system_config()
+            .workload_class_queue_timeout();
+        let deadline = Instant::now().checked_add(timeout);
+        self.serialized_ddl
+            .push_by_workload_class(DeferredPlanStatement { ctx, ps, deadline });
+        if deadline.is_some() {
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            mz_ore::task::spawn(|| "deferred statement timeout", async move {
+                tokio::time::sleep(timeout).await;
+                // The coordinator is gone if sending fails.
+                let _ = internal_cmd_tx.send(Message::DeferredStatementTimeout);
+            });
+        }
+    }
+
+    /// Cancels the deferred DDL statements that have waited past their
+    /// deadline.
+    pub(crate) fn cancel_expired_deferred_statements(&mut self) {
+        for deferred in self.serialized_ddl.take_expired(Instant::now()) {
+            let class = deferred.ctx.session().vars().workload_class();
+            deferred
+                .ctx
+                .retire(Err(AdapterError::WorkloadClassQueueTimeout(class)));
+        }
+    }
+
+    /// Returns the number of pending peeks on `cluster_id`, by workload class.
+    fn running_peeks(&self, cluster_id: ClusterId) -> BTreeMap<WorkloadClass, usize> {
+        let mut running = BTreeMap::new();
+        for peek in self.pending_peeks.values() {
+            if peek.cluster_id == cluster_id {
+                *running.entry(peek.workload_class).or_default() += 1;
+            }
+        }
+        running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(
+        cluster: usize,
+        interactive: usize,
+        standard: usize,
+        batch: usize,
+    ) -> AdmissionLimits {
+        AdmissionLimits {
+            cluster,
+            classes: [
+                (WorkloadClass::Interactive, interactive),
+                (WorkloadClass::Standard, standard),
+                (WorkloadClass::Batch, batch),
+            ]
+            .into(),
+        }
+    }
+
+    fn running(classes: &[(WorkloadClass, usize)]) -> BTreeMap<WorkloadClass, usize> {
+        classes.iter().copied().collect()
+    }
+
+    #[mz_ore::test]
+    fn test_try_admit() {
+        let mut admission = PeekAdmission::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let limits = limits(3, 0, 0, 1);
+
+        assert!(admission.try_admit(cluster, WorkloadClass::Batch, running(&[]), &limits));
+        assert!(!admission.try_admit(
+            cluster,
+            WorkloadClass::Batch,
+            running(&[(WorkloadClass::Batch, 1)]),
+            &limits
+        ));
+        assert!(admission.try_admit(
+            cluster,
+            WorkloadClass::Interactive,
+            running(&[(WorkloadClass::Batch, 1), (WorkloadClass::Standard, 1)]),
+            &limits
+        ));
+        assert!(!admission.try_admit(
+            cluster,
+            WorkloadClass::Interactive,
+            running(&[(WorkloadClass::Batch, 1), (WorkloadClass::Standard, 2)]),
+            &limits
+        ));
+
+        // New peeks queue behind waiting peeks of the same class.
+        let rx = admission.enqueue(cluster, WorkloadClass::Standard);
+        assert!(!admission.try_admit(cluster, WorkloadClass::Standard, running(&[]), &limits));
+        assert!(admission.try_admit(cluster, WorkloadClass::Interactive, running(&[]), &limits));
+        // Unless the waiting peeks have gone away.
+        drop(rx);
+        assert!(admission.try_admit(cluster, WorkloadClass::Standard, running(&[]), &limits));
+    }
+
+    #[mz_ore::test]
+    fn test_admit_queued_by_priority() {
+        let mut admission = PeekAdmission::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let limits = limits(1, 0, 0, 0);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let mut batch = admission.enqueue(cluster, WorkloadClass::Batch);
+        let mut standard = admission.enqueue(cluster, WorkloadClass::Standard);
+        let mut interactive = admission.enqueue(cluster, WorkloadClass::Interactive);
+
+        // A running peek blocks all queued peeks.
+        admission.admit_queued(cluster, running(&[(WorkloadClass::Batch, 1)]), &limits, &tx);
+        assert!(interactive.try_recv().is_err());
+
+        // Once it completes, the highest priority peek is admitted and holds
+        // a reservation until it is issued.
+        admission.admit_queued(cluster, running(&[]), &limits, &tx);
+        let reservation = interactive.try_recv().expect("admitted");
+        assert!(standard.try_recv().is_err());
+        admission.admit_queued(cluster, running(&[]), &limits, &tx);
+        assert!(standard.try_recv().is_err());
+
+        // Dropping the reservation releases the slot and notifies the
+        // coordinator.
+        drop(reservation);
+        assert!(matches!(rx.try_recv(), Ok(Message::AdmitQueuedPeeks(id)) if id == cluster));
+        admission.admit_queued(cluster, running(&[]), &limits, &tx);
+        let reservation = standard.try_recv().expect("admitted");
+        assert!(batch.try_recv().is_err());
+        drop(reservation);
+
+        // Canceled peeks are skipped.
+        drop(batch);
+        let mut other = admission.enqueue(cluster, WorkloadClass::Batch);
+        admission.admit_queued(cluster, running(&[]), &limits, &tx);
+        assert!(other.try_recv().is_ok());
+    }
+
+    #[mz_ore::test]
+    fn test_abandoned_reservation() {
+        let mut admission = PeekAdmission::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let limits = limits(1, 0, 0, 0);
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        let abandoned = admission.enqueue(cluster, WorkloadClass::Standard);
+        let mut next = admission.enqueue(cluster, WorkloadClass::Standard);
+        admission.admit_queued(cluster, running(&[]), &limits, &tx);
+        assert!(next.try_recv().is_err());
+
+        // A peek that goes away after it was admitted, e.g. because it timed
+        // out at the same time, doesn't keep its slot.
+        drop(abandoned);
+        admission.admit_queued(cluster, running(&[]), &limits, &tx);
+        assert!(next.try_recv().is_ok());
+    }
+
+    #[mz_ore::test]
+    fn test_class_limits() {
+        let mut admission = PeekAdmission::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let other_cluster = ClusterId::user(2).expect("valid id");
+        let limits = limits(0, 0, 0, 1);
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        let mut batch1 = admission.enqueue(cluster, WorkloadClass::Batch);
+        let mut batch2 = admission.enqueue(cluster, WorkloadClass::Batch);
+        let mut other = admission.enqueue(other_cluster, WorkloadClass::Batch);
+
+        admission.admit_queued(
+            cluster,
+            running(&[(WorkloadClass::Standard, 10)]),
+            &limits,
+            &tx,
+        );
+        let _reservation = batch1.try_recv().expect("admitted");
+        assert!(batch2.try_recv().is_err());
+        assert!(other.try_recv().is_err());
+
+        // Removing a cluster drops its waiters.
+        admission.remove_cluster(&cluster);
+        assert!(matches!(
+            batch2.try_recv(),
+            Err(oneshot::error::TryRecvError::Closed)
+        ));
+    }
+}
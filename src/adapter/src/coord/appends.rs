@@ -26,7 +26,9 @@ use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::{assert_none, instrument};
 use mz_repr::{CatalogItemId, Timestamp};
 use mz_sql::names::ResolvedIds;
-use mz_sql::plan::{ExplainPlanPlan, ExplainTimestampPlan, Explainee, ExplaineeStatement, Plan};
+use mz_sql::plan::{
+    ExplainAnalyzePlan, ExplainPlanPlan, ExplainTimestampPlan, Explainee, ExplaineeStatement, Plan,
+};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_storage_client::client::TableData;
 use mz_timestamp_oracle::WriteTimestamp;
@@ -945,6 +947,10 @@ pub(crate) fn waiting_on_startup_appends(
         Plan::ExplainPlan(ExplainPlanPlan {
             explainee: Explainee::Statement(ExplaineeStatement::Select { plan, .. }),
             ..
+        })
+        | Plan::ExplainAnalyze(ExplainAnalyzePlan {
+            explainee: Explainee::Statement(ExplaineeStatement::Select { plan, .. }),
+            ..
         }) => plan.source.depends_on(),
         Plan::ExplainTimestamp(ExplainTimestampPlan { raw_plan, .. }) => raw_plan.depends_on(),
         Plan::CreateConnection(_)
@@ -984,6 +990,7 @@ pub(crate) fn waiting_on_startup_appends(
         | Plan::CopyTo(_)
         | Plan::ExplainPlan(_)
         | Plan::ExplainPushdown(_)
+        | Plan::ExplainAnalyze(_)
         | Plan::ExplainSinkSchema(_)
         | Plan::Insert(_)
         | Plan::AlterNetworkPolicy(_)
@@ -7,40 +7,60 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use crate::AdapterError;
+use crate::catalog::ReplicaCreateDropReason;
 use crate::coord::{Coordinator, Message};
+use dec::OrderedDecimal;
+use futures::FutureExt;
+use futures::future::{self, BoxFuture};
 use itertools::Itertools;
-use mz_audit_log::SchedulingDecisionsWithReasonsV2;
+use maplit::btreeset;
+use mz_audit_log::SchedulingDecisionsWithReasonsV3;
+use mz_catalog::config::ClusterReplicaSizeMap;
 use mz_catalog::memory::objects::{CatalogItem, ClusterVariant, ClusterVariantManaged};
-use mz_controller_types::ClusterId;
-use mz_ore::collections::CollectionExt;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_orchestrator::{MemoryLimit, ServiceProcessMetrics};
 use mz_ore::{soft_assert_or_log, soft_panic_or_log};
 use mz_repr::adt::interval::Interval;
 use mz_repr::{GlobalId, TimestampManipulation};
 use mz_sql::catalog::CatalogCluster;
-use mz_sql::plan::{AlterClusterPlanStrategy, ClusterSchedule};
+use mz_sql::plan::{AlterClusterPlanStrategy, ClusterSchedule, OnTimeoutAction};
+use std::collections::BTreeSet;
 use std::time::{Duration, Instant};
+use tokio::sync::watch;
 use tracing::{debug, warn};
 
-const POLICIES: &[&str] = &[REFRESH_POLICY_NAME];
-
 const REFRESH_POLICY_NAME: &str = "refresh";
 
-/// A policy's decision for whether it wants a certain cluster to be On, along with its reason.
+const UTILIZATION_POLICY_NAME: &str = "utilization";
+
+const AUTO_SUSPEND_POLICY_NAME: &str = "auto_suspend";
+
+/// Returns the name of the policy that makes decisions for clusters with the given config, or
+/// `None` if such clusters are managed manually.
+fn scheduling_policy(config: &ClusterVariantManaged) -> Option<&'static str> {
+    match &config.schedule {
+        ClusterSchedule::Manual if config.auto_suspend_after.is_some() => {
+            Some(AUTO_SUSPEND_POLICY_NAME)
+        }
+        ClusterSchedule::Manual => None,
+        ClusterSchedule::Refresh { .. } => Some(REFRESH_POLICY_NAME),
+        ClusterSchedule::Utilization { .. } => Some(UTILIZATION_POLICY_NAME),
+    }
+}
+
+/// A policy's decision for whether it wants a certain cluster to be On, or which size it wants it
+/// to have, along with its reason.
 /// (Among the reasons there can be settings of the policy as well as other information about the
 /// state of the system.)
 #[derive(Clone, Debug)]
 pub enum SchedulingDecision {
     /// The reason for the refresh policy for wanting to turn a cluster On or Off.
     Refresh(RefreshDecision),
-}
-
-impl SchedulingDecision {
-    /// Extract the On/Off decision from the policy-specific structs.
-    pub fn cluster_on(&self) -> bool {
-        match &self {
-            SchedulingDecision::Refresh(RefreshDecision { cluster_on, .. }) => cluster_on.clone(),
-        }
-    }
+    /// The reason for the utilization policy for wanting a cluster to have a certain size.
+    Utilization(UtilizationDecision),
+    /// The reason for the auto-suspend policy for wanting to suspend or resume a cluster.
+    AutoSuspend(AutoSuspendDecision),
 }
 
 #[derive(Clone, Debug)]
@@ -57,14 +77,56 @@ pub struct RefreshDecision {
     hydration_time_estimate: Duration,
 }
 
+#[derive(Clone, Debug)]
+pub struct UtilizationDecision {
+    /// The size that the ON UTILIZATION policy wants a certain cluster to have.
+    target_size: String,
+    /// The size that the cluster had when the decision was made.
+    current_size: String,
+    /// The highest CPU utilization among the processes of the cluster's replicas, in percent of
+    /// the size's CPU limit. `None` if unknown.
+    cpu_percent: Option<u32>,
+    /// The highest memory utilization among the processes of the cluster's replicas, in percent
+    /// of the size's memory limit. `None` if unknown.
+    memory_percent: Option<u32>,
+    /// Whether all collections are hydrated on the cluster's replicas.
+    hydrated: bool,
+    /// The MIN SIZE setting of the cluster.
+    min_size: String,
+    /// The MAX SIZE setting of the cluster.
+    max_size: String,
+    /// Whether all collections are hydrated on the pending replicas of an in-progress resize.
+    /// `None` if the cluster has no pending replicas.
+    pending_replicas_hydrated: Option<bool>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AutoSuspendDecision {
+    /// Whether the AUTO SUSPEND AFTER policy wants a certain cluster to be On.
+    cluster_on: bool,
+    /// Objects on the cluster whose results have to be kept fresh even without queries, and
+    /// therefore should keep the cluster On.
+    objects_needing_freshness: Vec<GlobalId>,
+    /// How long the cluster has been without peeks and subscribes.
+    idle_for: Duration,
+    /// Whether a query on the suspended cluster asked for the cluster to be resumed.
+    resumed_for_query: bool,
+    /// The AUTO SUSPEND AFTER setting of the cluster.
+    auto_suspend_after: Duration,
+    /// Whether all collections are hydrated on the cluster's replicas. `None` if no query waits
+    /// for the cluster to be resumed, in which case we don't check.
+    hydrated: Option<bool>,
+}
+
 impl SchedulingDecision {
-    pub fn reasons_to_audit_log_reasons<'a, I>(reasons: I) -> SchedulingDecisionsWithReasonsV2
+    pub fn reasons_to_audit_log_reasons<'a, I>(reasons: I) -> SchedulingDecisionsWithReasonsV3
     where
         I: IntoIterator<Item = &'a SchedulingDecision>,
     {
-        SchedulingDecisionsWithReasonsV2 {
+        let reasons = reasons.into_iter().collect_vec();
+        SchedulingDecisionsWithReasonsV3 {
             on_refresh: reasons
-                .into_iter()
+                .iter()
                 .filter_map(|r| match r {
                     SchedulingDecision::Refresh(RefreshDecision {
                         cluster_on,
@@ -78,13 +140,7 @@ impl SchedulingDecision {
                                 || !objects_needing_compaction.is_empty(),
                             "`cluster_on = true` should have an explanation"
                         );
-                        let mut hydration_time_estimate_str = String::new();
-                        mz_repr::strconv::format_interval(
-                            &mut hydration_time_estimate_str,
-                            Interval::from_duration(hydration_time_estimate).expect(
-                                "planning ensured that this is convertible back to Interval",
-                            ),
-                        );
+                        let hydration_time_estimate_str = format_duration(*hydration_time_estimate);
                         Some(mz_audit_log::RefreshDecisionWithReasonV2 {
                             decision: (*cluster_on).into(),
                             objects_needing_refresh: objects_needing_refresh
@@ -98,18 +154,331 @@ impl SchedulingDecision {
                             hydration_time_estimate: hydration_time_estimate_str,
                         })
                     }
+                    SchedulingDecision::Utilization(_) | SchedulingDecision::AutoSuspend(_) => None,
+                })
+                // Each policy should have at most one opinion on each cluster.
+                .at_most_one()
+                .expect("at most one refresh decision"),
+            on_utilization: reasons
+                .iter()
+                .filter_map(|r| match r {
+                    SchedulingDecision::Refresh(_) | SchedulingDecision::AutoSuspend(_) => None,
+                    SchedulingDecision::Utilization(UtilizationDecision {
+                        target_size,
+                        current_size,
+                        cpu_percent,
+                        memory_percent,
+                        hydrated,
+                        min_size,
+                        max_size,
+                        pending_replicas_hydrated: _,
+                    }) => Some(mz_audit_log::UtilizationDecisionWithReasonV1 {
+                        target_size: target_size.clone(),
+                        current_size: current_size.clone(),
+                        cpu_percent: *cpu_percent,
+                        memory_percent: *memory_percent,
+                        hydrated: *hydrated,
+                        min_size: min_size.clone(),
+                        max_size: max_size.clone(),
+                    }),
                 })
-                .into_element(), // Each policy should have exactly one opinion on each cluster.
+                .at_most_one()
+                .expect("at most one utilization decision"),
+            on_auto_suspend: reasons
+                .iter()
+                .filter_map(|r| match r {
+                    SchedulingDecision::Refresh(_) | SchedulingDecision::Utilization(_) => None,
+                    SchedulingDecision::AutoSuspend(AutoSuspendDecision {
+                        cluster_on,
+                        objects_needing_freshness,
+                        idle_for,
+                        resumed_for_query,
+                        auto_suspend_after,
+                        hydrated: _,
+                    }) => Some(mz_audit_log::AutoSuspendDecisionWithReasonV1 {
+                        decision: (*cluster_on).into(),
+                        objects_needing_freshness: objects_needing_freshness
+                            .iter()
+                            .map(|id| id.to_string())
+                            .collect(),
+                        idle_for: format_duration(*idle_for),
+                        resumed_for_query: *resumed_for_query,
+                        auto_suspend_after: format_duration(*auto_suspend_after),
+                    }),
+                })
+                .at_most_one()
+                .expect("at most one auto-suspend decision"),
+        }
+    }
+}
+
+/// Formats `duration` like an `interval` for the audit log, dropping fractional microseconds.
+fn format_duration(duration: Duration) -> String {
+    let duration = Duration::new(duration.as_secs(), duration.subsec_micros() * 1000);
+    let mut s = String::new();
+    match Interval::from_duration(&duration) {
+        Ok(interval) => {
+            mz_repr::strconv::format_interval(&mut s, interval);
+        }
+        // Only absurdly long durations are not convertible.
+        Err(_) => s = format!("{duration:?}"),
+    }
+    s
+}
+
+/// Whether the utilization of a `SCHEDULE = ON UTILIZATION` cluster asks for a larger or a smaller
+/// size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UtilizationTrend {
+    /// The utilization has been above the scale-up threshold for long enough.
+    High,
+    /// The utilization has been below the scale-down threshold for long enough.
+    Low,
+    /// Neither, or the cluster was resized too recently.
+    Neutral,
+}
+
+/// The system variables that configure the `SCHEDULE = ON UTILIZATION` policy.
+#[derive(Clone, Debug)]
+struct UtilizationThresholds {
+    scale_up_percent: u32,
+    scale_down_percent: u32,
+    sustained_interval: Duration,
+    cooldown: Duration,
+}
+
+/// What the `SCHEDULE = ON UTILIZATION` policy remembers about a cluster between its runs.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct UtilizationState {
+    /// Since when the utilization has been continuously above the scale-up threshold.
+    high_since: Option<Instant>,
+    /// Since when the utilization has been continuously below the scale-down threshold.
+    low_since: Option<Instant>,
+    /// When the policy last finished or abandoned a resize of the cluster.
+    last_resize: Option<Instant>,
+    /// A resize that waits for its pending replicas to hydrate.
+    pending_resize: Option<PendingResize>,
+}
+
+/// A graceful resize started by the `SCHEDULE = ON UTILIZATION` policy.
+#[derive(Clone, Debug)]
+struct PendingResize {
+    /// The config to apply to the cluster once the pending replicas are hydrated.
+    new_config: ClusterVariantManaged,
+    /// When to give up on the resize and drop the pending replicas.
+    timeout_time: Instant,
+}
+
+impl UtilizationState {
+    /// Records the current utilization of the cluster, and returns whether it has been high or low
+    /// for long enough to resize the cluster.
+    fn observe(
+        &mut self,
+        now: Instant,
+        cpu_percent: Option<u32>,
+        memory_percent: Option<u32>,
+        thresholds: &UtilizationThresholds,
+    ) -> UtilizationTrend {
+        let known = [cpu_percent, memory_percent]
+            .into_iter()
+            .flatten()
+            .collect_vec();
+        // Any resource running out is a reason to scale up, but we only scale down if all of them
+        // have room to spare.
+        let high = known.iter().any(|p| *p >= thresholds.scale_up_percent);
+        let low = !known.is_empty() && known.iter().all(|p| *p <= thresholds.scale_down_percent);
+        self.high_since = high.then(|| self.high_since.unwrap_or(now));
+        self.low_since = low.then(|| self.low_since.unwrap_or(now));
+
+        let elapsed = |since: Instant| now.saturating_duration_since(since);
+        if self
+            .last_resize
+            .is_some_and(|last_resize| elapsed(last_resize) < thresholds.cooldown)
+        {
+            return UtilizationTrend::Neutral;
+        }
+        let sustained = |since: Option<Instant>| {
+            since.is_some_and(|s| elapsed(s) >= thresholds.sustained_interval)
+        };
+        if sustained(self.high_since) {
+            UtilizationTrend::High
+        } else if sustained(self.low_since) {
+            UtilizationTrend::Low
+        } else {
+            UtilizationTrend::Neutral
+        }
+    }
+
+    /// Records that a resize of the cluster has just finished or was abandoned.
+    fn resized(&mut self, now: Instant) {
+        self.high_since = None;
+        self.low_since = None;
+        self.last_resize = Some(now);
+    }
+}
+
+/// What `check_utilization_policy` collects about a cluster before making its decision on a
+/// background task.
+struct UtilizationInfo {
+    cluster_id: ClusterId,
+    current_size: String,
+    min_size: String,
+    max_size: String,
+    cpu_percent: Option<u32>,
+    memory_percent: Option<u32>,
+    trend: UtilizationTrend,
+    hydrated: BoxFuture<'static, bool>,
+    pending_replicas_hydrated: Option<BoxFuture<'static, bool>>,
+}
+
+/// Returns the names of the enabled replica sizes, ordered from smallest to largest.
+pub(crate) fn size_ladder(sizes: &ClusterReplicaSizeMap) -> Vec<String> {
+    sizes
+        .enabled_allocations()
+        .sorted_by_key(|(name, allocation)| {
+            (
+                allocation.scale,
+                allocation.cpu_limit,
+                allocation.memory_limit,
+                OrderedDecimal(allocation.credits_per_hour),
+                *name,
+            )
+        })
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Picks the size that a `SCHEDULE = ON UTILIZATION` cluster should have.
+///
+/// A cluster whose size is outside of `[min_size, max_size]` is moved to the nearest end of that
+/// range. Otherwise, the cluster moves by at most one step on `ladder`. We don't scale down a
+/// cluster that is not hydrated, because its utilization is not representative while it is still
+/// catching up. If a size is not on `ladder`, e.g., because it has been disabled since, the cluster
+/// keeps its current size.
+fn utilization_target_size(
+    ladder: &[String],
+    current_size: &str,
+    min_size: &str,
+    max_size: &str,
+    trend: UtilizationTrend,
+    hydrated: bool,
+) -> String {
+    let position = |size: &str| ladder.iter().position(|s| s == size);
+    let (Some(current), Some(min), Some(max)) = (
+        position(current_size),
+        position(min_size),
+        position(max_size),
+    ) else {
+        return current_size.to_string();
+    };
+    let target = if current < min {
+        min
+    } else if current > max {
+        max
+    } else {
+        match trend {
+            UtilizationTrend::High => std::cmp::min(current + 1, max),
+            UtilizationTrend::Low if hydrated => std::cmp::max(current.saturating_sub(1), min),
+            UtilizationTrend::Low | UtilizationTrend::Neutral => current,
+        }
+    };
+    ladder[target].clone()
+}
+
+/// Returns the highest of `values`, in percent of `limit`. Returns `None` if there is no limit or
+/// no known value.
+fn max_utilization_percent<I>(values: I, limit: Option<u64>) -> Option<u32>
+where
+    I: IntoIterator<Item = Option<u64>>,
+{
+    let limit = limit.filter(|limit| *limit > 0)?;
+    let max = values.into_iter().flatten().max()?;
+    let percent = u128::from(max) * 100 / u128::from(limit);
+    Some(u32::try_from(percent).unwrap_or(u32::MAX))
+}
+
+/// What the `AUTO SUSPEND AFTER` policy remembers about a cluster between its runs.
+#[derive(Debug)]
+pub(crate) struct AutoSuspendState {
+    /// When the cluster last served a peek or subscribe, or when we started tracking it.
+    last_activity: Instant,
+    /// The replication factor that the cluster had before the policy suspended it.
+    suspended_replication_factor: Option<u32>,
+    /// Set to `true` once the replicas of a cluster that was resumed for a query are hydrated.
+    /// `Some` while queries might be waiting for this.
+    resuming: Option<watch::Sender<bool>>,
+}
+
+impl AutoSuspendState {
+    fn new(now: Instant) -> Self {
+        AutoSuspendState {
+            last_activity: now,
+            suspended_replication_factor: None,
+            resuming: None,
         }
     }
+
+    /// Returns a future that resolves once the cluster's replicas are hydrated after a resume, or
+    /// `None` if the cluster is not being resumed. The future also resolves if the resume is
+    /// abandoned, e.g., because the cluster was dropped.
+    fn wait_for_resume(&self) -> Option<BoxFuture<'static, ()>> {
+        let mut rx = self.resuming.as_ref()?.subscribe();
+        Some(
+            async move {
+                let _ = rx.wait_for(|hydrated| *hydrated).await;
+            }
+            .boxed(),
+        )
+    }
+}
+
+/// What `check_auto_suspend_policy` collects about a cluster before making its decision on a
+/// background task.
+struct AutoSuspendInfo {
+    cluster_id: ClusterId,
+    objects_needing_freshness: Vec<GlobalId>,
+    idle_for: Duration,
+    auto_suspend_after: Duration,
+    hydrated: Option<BoxFuture<'static, bool>>,
+}
+
+/// Whether an object on an `AUTO SUSPEND AFTER` cluster has to be kept fresh even when nobody
+/// queries the cluster. Indexes don't, because only queries on their own cluster can read them,
+/// and such queries resume the cluster.
+fn needs_freshness(item: &CatalogItem) -> bool {
+    match item {
+        CatalogItem::Source(_)
+        | CatalogItem::MaterializedView(_)
+        | CatalogItem::Sink(_)
+        | CatalogItem::ContinualTask(_) => true,
+        CatalogItem::Table(_)
+        | CatalogItem::Log(_)
+        | CatalogItem::View(_)
+        | CatalogItem::Index(_)
+        | CatalogItem::Type(_)
+        | CatalogItem::Func(_)
+        | CatalogItem::Secret(_)
+        | CatalogItem::Connection(_) => false,
+    }
+}
+
+/// Whether the `AUTO SUSPEND AFTER` policy wants a cluster to be On: either something on the
+/// cluster needs to be kept fresh, or the cluster has served a query recently enough.
+fn auto_suspend_cluster_on(
+    objects_needing_freshness: &[GlobalId],
+    idle_for: Duration,
+    auto_suspend_after: Duration,
+) -> bool {
+    !objects_needing_freshness.is_empty() || idle_for < auto_suspend_after
 }
 
 impl Coordinator {
     #[mz_ore::instrument(level = "debug")]
     /// Call each scheduling policy.
     pub(crate) async fn check_scheduling_policies(&mut self) {
-        // (So far, we have only this one policy.)
         self.check_refresh_policy();
+        self.check_utilization_policy();
+        self.check_auto_suspend_policy();
     }
 
     /// Runs the `SCHEDULE = ON REFRESH` cluster scheduling policy, which makes cluster On/Off
@@ -128,9 +497,10 @@ impl Coordinator {
         let mut refresh_mv_infos = Vec::new();
         for cluster in self.catalog().clusters() {
             if let ClusterVariant::Managed(ref config) = cluster.config.variant {
-                match config.schedule {
-                    ClusterSchedule::Manual => {
-                        // Nothing to do, user manages this cluster manually.
+                match &config.schedule {
+                    ClusterSchedule::Manual | ClusterSchedule::Utilization { .. } => {
+                        // Nothing to do, user manages this cluster manually, or it's the
+                        // utilization policy's business.
                     }
                     ClusterSchedule::Refresh {
                         hydration_time_estimate,
@@ -156,7 +526,7 @@ impl Coordinator {
                             })
                             .collect_vec();
                         debug!(%cluster.id, ?refresh_mv_infos, "check_refresh_policy");
-                        refresh_mv_infos.push((cluster.id, hydration_time_estimate, mvs));
+                        refresh_mv_infos.push((cluster.id, *hydration_time_estimate, mvs));
                     }
                 }
             }
@@ -269,12 +639,405 @@ impl Coordinator {
             .observe((Instant::now() - start_time).as_secs_f64());
     }
 
+    /// Runs the `SCHEDULE = ON UTILIZATION` cluster scheduling policy, which picks a size for each
+    /// such cluster based on the sustained CPU and memory utilization of its replicas and on
+    /// whether they are hydrated, and sends `Message::SchedulingDecisions` with these decisions.
+    /// (Waits for the hydration checks on a background task.)
+    fn check_utilization_policy(&mut self) {
+        let start_time = Instant::now();
+
+        let catalog = self.owned_catalog();
+        let system_config = catalog.system_config();
+        let thresholds = UtilizationThresholds {
+            scale_up_percent: system_config.cluster_utilization_scale_up_threshold(),
+            scale_down_percent: system_config.cluster_utilization_scale_down_threshold(),
+            sustained_interval: system_config.cluster_utilization_sustained_interval(),
+            cooldown: system_config.cluster_utilization_cooldown(),
+        };
+        let ladder = size_ladder(catalog.cluster_replica_sizes());
+
+        // Collect information about ON UTILIZATION clusters:
+        // - cluster
+        // - current size, MIN SIZE, and MAX SIZE
+        // - current utilization, and whether it has been high or low for long enough
+        // - hydration checks for the replicas and for the pending replicas of a resize
+        let mut utilization_infos = Vec::new();
+        for cluster in catalog.clusters() {
+            let ClusterVariant::Managed(config) = &cluster.config.variant else {
+                continue;
+            };
+            let ClusterSchedule::Utilization { min_size, max_size } = &config.schedule else {
+                continue;
+            };
+            let (pending_replicas, replicas): (Vec<_>, Vec<_>) = cluster
+                .replicas()
+                .partition(|r| r.config.location.pending());
+            let pending_replicas = pending_replicas.iter().map(|r| r.replica_id).collect_vec();
+            let replicas = replicas.iter().map(|r| r.replica_id).collect_vec();
+
+            let (cpu_percent, memory_percent) = self.replica_utilization(&config.size, &replicas);
+            let trend = self
+                .cluster_utilization_state
+                .entry(cluster.id)
+                .or_default()
+                .observe(start_time, cpu_percent, memory_percent, &thresholds);
+            let hydrated = self.replicas_hydrated(cluster.id, replicas);
+            let pending_replicas_hydrated = if pending_replicas.is_empty() {
+                None
+            } else {
+                Some(self.replicas_hydrated(cluster.id, pending_replicas))
+            };
+            debug!(%cluster.id, ?cpu_percent, ?memory_percent, ?trend, "check_utilization_policy");
+            utilization_infos.push(UtilizationInfo {
+                cluster_id: cluster.id,
+                current_size: config.size.clone(),
+                min_size: min_size.clone(),
+                max_size: max_size.clone(),
+                cpu_percent,
+                memory_percent,
+                trend,
+                hydrated,
+                pending_replicas_hydrated,
+            });
+        }
+
+        // Spawn a background task that waits for the hydration checks, makes the size decisions,
+        // and sends a `Message::SchedulingDecisions` with these decisions.
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let check_scheduling_policies_seconds_cloned =
+            self.metrics.check_scheduling_policies_seconds.clone();
+        mz_ore::task::spawn(
+            || "utilization policy check hydration and make decisions",
+            async move {
+                let task_start_time = Instant::now();
+                let mut decisions = Vec::new();
+                for UtilizationInfo {
+                    cluster_id,
+                    current_size,
+                    min_size,
+                    max_size,
+                    cpu_percent,
+                    memory_percent,
+                    trend,
+                    hydrated,
+                    pending_replicas_hydrated,
+                } in utilization_infos
+                {
+                    let hydrated = hydrated.await;
+                    let pending_replicas_hydrated = match pending_replicas_hydrated {
+                        Some(pending_replicas_hydrated) => Some(pending_replicas_hydrated.await),
+                        None => None,
+                    };
+                    let target_size = utilization_target_size(
+                        &ladder,
+                        &current_size,
+                        &min_size,
+                        &max_size,
+                        trend,
+                        hydrated,
+                    );
+                    decisions.push((
+                        cluster_id,
+                        SchedulingDecision::Utilization(UtilizationDecision {
+                            target_size,
+                            current_size,
+                            cpu_percent,
+                            memory_percent,
+                            hydrated,
+                            min_size,
+                            max_size,
+                            pending_replicas_hydrated,
+                        }),
+                    ));
+                }
+                if let Err(e) = internal_cmd_tx.send(Message::SchedulingDecisions(vec![(
+                    UTILIZATION_POLICY_NAME,
+                    decisions,
+                )])) {
+                    // It is not an error for this task to be running after `internal_cmd_rx` is
+                    // dropped.
+                    warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                }
+                check_scheduling_policies_seconds_cloned
+                    .with_label_values(&[UTILIZATION_POLICY_NAME, "background"])
+                    .observe((Instant::now() - task_start_time).as_secs_f64());
+            },
+        );
+
+        self.metrics
+            .check_scheduling_policies_seconds
+            .with_label_values(&[UTILIZATION_POLICY_NAME, "main"])
+            .observe((Instant::now() - start_time).as_secs_f64());
+    }
+
+    /// Runs the `AUTO SUSPEND AFTER` cluster scheduling policy, which wants a cluster to be Off if
+    /// nothing on it needs to be kept fresh and it hasn't served peeks or subscribes for the
+    /// configured interval, and sends `Message::SchedulingDecisions` with these decisions. For
+    /// clusters that were resumed for a query, it also checks whether they are hydrated, so that
+    /// the waiting queries can proceed. (Waits for these hydration checks on a background task.)
+    fn check_auto_suspend_policy(&mut self) {
+        let start_time = Instant::now();
+
+        let catalog = self.owned_catalog();
+        // Ongoing peeks and subscribes keep their clusters active.
+        let active_clusters: BTreeSet<_> = self
+            .pending_peeks
+            .values()
+            .map(|peek| peek.cluster_id)
+            .chain(
+                self.active_compute_sinks
+                    .values()
+                    .map(|sink| sink.cluster_id()),
+            )
+            .collect();
+
+        // Collect information about AUTO SUSPEND AFTER clusters:
+        // - cluster
+        // - objects that need to be kept fresh
+        // - how long the cluster has been idle
+        // - hydration check, if queries wait for the cluster to be resumed
+        let mut auto_suspend_infos = Vec::new();
+        for cluster in catalog.clusters() {
+            let ClusterVariant::Managed(config) = &cluster.config.variant else {
+                continue;
+            };
+            if scheduling_policy(config) != Some(AUTO_SUSPEND_POLICY_NAME) {
+                continue;
+            }
+            let auto_suspend_after = config.auto_suspend_after.expect("checked above");
+            let state = self
+                .cluster_auto_suspend_state
+                .entry(cluster.id)
+                .or_insert_with(|| AutoSuspendState::new(start_time));
+            if active_clusters.contains(&cluster.id) {
+                state.last_activity = start_time;
+            }
+            let idle_for = start_time.saturating_duration_since(state.last_activity);
+            let resuming = state.resuming.is_some();
+
+            let objects_needing_freshness = cluster
+                .bound_objects()
+                .iter()
+                .map(|id| catalog.get_entry(id))
+                .filter(|entry| needs_freshness(entry.item()))
+                .map(|entry| entry.latest_global_id())
+                .collect_vec();
+            let hydrated = resuming.then(|| {
+                let replicas = cluster.replicas().map(|r| r.replica_id).collect_vec();
+                self.replicas_hydrated(cluster.id, replicas)
+            });
+            debug!(
+                %cluster.id, ?objects_needing_freshness, ?idle_for, %resuming,
+                "check_auto_suspend_policy"
+            );
+            auto_suspend_infos.push(AutoSuspendInfo {
+                cluster_id: cluster.id,
+                objects_needing_freshness,
+                idle_for,
+                auto_suspend_after,
+                hydrated,
+            });
+        }
+
+        // Spawn a background task that waits for the hydration checks, makes the On/Off
+        // decisions, and sends a `Message::SchedulingDecisions` with these decisions.
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let check_scheduling_policies_seconds_cloned =
+            self.metrics.check_scheduling_policies_seconds.clone();
+        mz_ore::task::spawn(
+            || "auto-suspend policy check hydration and make decisions",
+            async move {
+                let task_start_time = Instant::now();
+                let mut decisions = Vec::new();
+                for AutoSuspendInfo {
+                    cluster_id,
+                    objects_needing_freshness,
+                    idle_for,
+                    auto_suspend_after,
+                    hydrated,
+                } in auto_suspend_infos
+                {
+                    let hydrated = match hydrated {
+                        Some(hydrated) => Some(hydrated.await),
+                        None => None,
+                    };
+                    let cluster_on = auto_suspend_cluster_on(
+                        &objects_needing_freshness,
+                        idle_for,
+                        auto_suspend_after,
+                    );
+                    decisions.push((
+                        cluster_id,
+                        SchedulingDecision::AutoSuspend(AutoSuspendDecision {
+                            cluster_on,
+                            objects_needing_freshness,
+                            idle_for,
+                            resumed_for_query: false,
+                            auto_suspend_after,
+                            hydrated,
+                        }),
+                    ));
+                }
+                if let Err(e) = internal_cmd_tx.send(Message::SchedulingDecisions(vec![(
+                    AUTO_SUSPEND_POLICY_NAME,
+                    decisions,
+                )])) {
+                    // It is not an error for this task to be running after `internal_cmd_rx` is
+                    // dropped.
+                    warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                }
+                check_scheduling_policies_seconds_cloned
+                    .with_label_values(&[AUTO_SUSPEND_POLICY_NAME, "background"])
+                    .observe((Instant::now() - task_start_time).as_secs_f64());
+            },
+        );
+
+        self.metrics
+            .check_scheduling_policies_seconds
+            .with_label_values(&[AUTO_SUSPEND_POLICY_NAME, "main"])
+            .observe((Instant::now() - start_time).as_secs_f64());
+    }
+
+    /// Records that a query targets `cluster_id`. If the cluster was suspended by its `AUTO
+    /// SUSPEND AFTER` option, this resumes it. Returns a future that resolves once the resumed
+    /// cluster is hydrated, for the query to wait on, or `None` if the query doesn't need to wait.
+    pub(crate) async fn resume_cluster_for_query(
+        &mut self,
+        cluster_id: ClusterId,
+    ) -> Result<Option<BoxFuture<'static, ()>>, AdapterError> {
+        let Some(managed_config) = self.get_managed_cluster_config(cluster_id) else {
+            return Ok(None);
+        };
+        if scheduling_policy(&managed_config) != Some(AUTO_SUSPEND_POLICY_NAME) {
+            return Ok(None);
+        }
+        let auto_suspend_after = managed_config.auto_suspend_after.expect("checked above");
+        let now = Instant::now();
+        let state = self
+            .cluster_auto_suspend_state
+            .entry(cluster_id)
+            .or_insert_with(|| AutoSuspendState::new(now));
+        state.last_activity = now;
+
+        if managed_config.replication_factor == 0 {
+            let replication_factor = state.suspended_replication_factor.unwrap_or(1);
+            let mut new_config = self.catalog().get_cluster(cluster_id).config.clone();
+            let ClusterVariant::Managed(new_managed_config) = &mut new_config.variant else {
+                unreachable!("checked above");
+            };
+            new_managed_config.replication_factor = replication_factor;
+            let reason =
+                ReplicaCreateDropReason::ClusterScheduling(vec![SchedulingDecision::AutoSuspend(
+                    AutoSuspendDecision {
+                        cluster_on: true,
+                        objects_needing_freshness: Vec::new(),
+                        idle_for: Duration::ZERO,
+                        resumed_for_query: true,
+                        auto_suspend_after,
+                        hydrated: None,
+                    },
+                )]);
+            self.sequence_alter_cluster_managed_to_managed(
+                None,
+                cluster_id,
+                new_config,
+                reason,
+                AlterClusterPlanStrategy::None,
+            )
+            .await?;
+            let state = self
+                .cluster_auto_suspend_state
+                .get_mut(&cluster_id)
+                .expect("inserted above");
+            state.suspended_replication_factor = None;
+            state
+                .resuming
+                .get_or_insert_with(|| watch::channel(false).0);
+        }
+
+        Ok(self
+            .cluster_auto_suspend_state
+            .get(&cluster_id)
+            .and_then(|state| state.wait_for_resume()))
+    }
+
+    /// Returns the highest CPU and memory utilization among the processes of the given replicas,
+    /// in percent of the limits of `size`.
+    fn replica_utilization(
+        &self,
+        size: &str,
+        replica_ids: &[ReplicaId],
+    ) -> (Option<u32>, Option<u32>) {
+        let Ok(allocation) = self
+            .catalog()
+            .cluster_replica_sizes()
+            .get_allocation_by_name(size)
+        else {
+            return (None, None);
+        };
+        let processes = replica_ids
+            .iter()
+            .filter_map(|id| self.transient_replica_metadata.get(id))
+            .flatten()
+            .filter_map(|metadata| metadata.metrics.as_ref())
+            .flatten()
+            .collect_vec();
+        let cpu_percent = max_utilization_percent(
+            processes
+                .iter()
+                .map(|ServiceProcessMetrics { cpu_nano_cores, .. }| *cpu_nano_cores),
+            allocation.cpu_limit.map(|limit| limit.as_nanocpus()),
+        );
+        let memory_percent = max_utilization_percent(
+            processes
+                .iter()
+                .map(|ServiceProcessMetrics { memory_bytes, .. }| *memory_bytes),
+            allocation
+                .memory_limit
+                .map(|MemoryLimit(limit)| limit.as_u64()),
+        );
+        (cpu_percent, memory_percent)
+    }
+
+    /// Starts checking whether all collections are hydrated on the given replicas. The returned
+    /// future resolves to `false` if the check fails.
+    fn replicas_hydrated(
+        &self,
+        cluster_id: ClusterId,
+        replica_ids: Vec<ReplicaId>,
+    ) -> BoxFuture<'static, bool> {
+        if replica_ids.is_empty() {
+            return future::ready(true).boxed();
+        }
+        let storage_hydrated = self
+            .controller
+            .storage
+            .collections_hydrated_on_replicas(Some(replica_ids.clone()), &[].into())
+            .unwrap_or_else(|e| {
+                warn!(%cluster_id, "failed to check storage hydration: {e}");
+                false
+            });
+        match self.controller.compute.collections_hydrated_for_replicas(
+            cluster_id,
+            replica_ids,
+            [].into(),
+        ) {
+            Ok(compute_hydrated) => {
+                async move { storage_hydrated && compute_hydrated.await.unwrap_or(false) }.boxed()
+            }
+            Err(e) => {
+                warn!(%cluster_id, "failed to check compute hydration: {e}");
+                future::ready(false).boxed()
+            }
+        }
+    }
+
     /// Handles `SchedulingDecisions`:
     /// 1. Adds the newly made decisions to `cluster_scheduling_decisions`.
     /// 2. Cleans up old decisions that are for clusters no longer in scope of automated scheduling
-    ///   decisions.
-    /// 3. For each cluster, it sums up `cluster_scheduling_decisions`, checks the summed up decision
-    ///   against the cluster state, and turns cluster On/Off if needed.
+    ///   decisions, or that are from a policy that is no longer responsible for the cluster.
+    /// 3. For each cluster, it checks the decision of the responsible policy against the cluster
+    ///   state, and turns the cluster On/Off or resizes it if needed.
     #[mz_ore::instrument(level = "debug")]
     pub(crate) async fn handle_scheduling_decisions(
         &mut self,
@@ -295,7 +1058,9 @@ impl Coordinator {
         // 2. Clean up those clusters from `scheduling_decisions` that
         // - have been dropped, or
         // - were switched to unmanaged, or
-        // - were switched to `SCHEDULE = MANUAL`.
+        // - were switched to `SCHEDULE = MANUAL` without `AUTO SUSPEND AFTER`.
+        // Also clean up decisions of policies that are not responsible for a cluster anymore,
+        // e.g., because it was switched from `SCHEDULE = ON REFRESH` to `SCHEDULE = ON UTILIZATION`.
         for cluster_id in self
             .cluster_scheduling_decisions
             .keys()
@@ -313,74 +1078,114 @@ impl Coordinator {
                     );
                     self.cluster_scheduling_decisions.remove(&cluster_id);
                 }
-                Some(managed_config) => {
-                    if matches!(managed_config.schedule, ClusterSchedule::Manual) {
+                Some(managed_config) => match scheduling_policy(&managed_config) {
+                    None => {
                         debug!(
                             "handle_scheduling_decisions: \
                             Removing cluster {} from cluster_scheduling_decisions, \
-                            because schedule is Manual",
+                            because it is managed manually",
                             cluster_id
                         );
                         self.cluster_scheduling_decisions.remove(&cluster_id);
                     }
-                }
+                    Some(policy) => {
+                        let decisions = self
+                            .cluster_scheduling_decisions
+                            .get_mut(&cluster_id)
+                            .expect("iterating over the keys");
+                        decisions.retain(|policy_name, _| *policy_name == policy);
+                        if decisions.is_empty() {
+                            self.cluster_scheduling_decisions.remove(&cluster_id);
+                        }
+                    }
+                },
             }
         }
+        let catalog = &self.catalog;
+        self.cluster_utilization_state.retain(|cluster_id, _| {
+            catalog.try_get_cluster(*cluster_id).is_some_and(|cluster| {
+                match &cluster.config.variant {
+                    ClusterVariant::Managed(config) => {
+                        matches!(config.schedule, ClusterSchedule::Utilization { .. })
+                    }
+                    ClusterVariant::Unmanaged => false,
+                }
+            })
+        });
+        // Dropping the state of a cluster also releases the queries that wait for it to resume.
+        self.cluster_auto_suspend_state.retain(|cluster_id, _| {
+            catalog.try_get_cluster(*cluster_id).is_some_and(|cluster| {
+                match &cluster.config.variant {
+                    ClusterVariant::Managed(config) => {
+                        scheduling_policy(config) == Some(AUTO_SUSPEND_POLICY_NAME)
+                    }
+                    ClusterVariant::Unmanaged => false,
+                }
+            })
+        });
 
         // 3. Act on `scheduling_decisions` where needed.
         let mut altered_a_cluster = false;
         for (cluster_id, decisions) in self.cluster_scheduling_decisions.clone() {
-            // We touch a cluster only when all policies have made a decision about it. This is
-            // to ensure that after an envd restart all policies have a chance to run at least once
-            // before we turn off a cluster, to avoid spuriously turning off a cluster and possibly
-            // losing a hydrated state.
-            if POLICIES.iter().all(|policy| decisions.contains_key(policy)) {
-                // Check whether the cluster's state matches the needed state.
-                // If any policy says On, then we need a replica.
-                let needs_replica = decisions
-                    .values()
-                    .map(|decision| decision.cluster_on())
-                    .contains(&true);
-                let cluster_config = self.catalog().get_cluster(cluster_id).config.clone();
-                let mut new_config = cluster_config.clone();
-                let ClusterVariant::Managed(managed_config) = &mut new_config.variant else {
-                    panic!("cleaned up unmanaged clusters above");
-                };
-                let has_replica = managed_config.replication_factor > 0; // Is it On?
-                if needs_replica != has_replica {
-                    // Turn the cluster On or Off.
-                    altered_a_cluster = true;
-                    managed_config.replication_factor = if needs_replica { 1 } else { 0 };
-                    if let Err(e) = self
-                        .sequence_alter_cluster_managed_to_managed(
-                            None,
-                            cluster_id,
-                            new_config.clone(),
-                            crate::catalog::ReplicaCreateDropReason::ClusterScheduling(
-                                decisions.values().cloned().collect(),
-                            ),
-                            AlterClusterPlanStrategy::None,
-                        )
-                        .await
-                    {
-                        soft_panic_or_log!(
-                            "handle_scheduling_decisions couldn't alter cluster {}. \
-                             Old config: {:?}, \
-                             New config: {:?}, \
-                             Error: {}",
-                            cluster_id,
-                            cluster_config,
-                            new_config,
-                            e
-                        );
+            // We touch a cluster only when the responsible policy has made a decision about it
+            // (which the cleanup above ensures). This is to ensure that after an envd restart the
+            // policy has a chance to run at least once before we turn off or resize a cluster, to
+            // avoid spuriously turning off a cluster and possibly losing a hydrated state.
+            let reason =
+                ReplicaCreateDropReason::ClusterScheduling(decisions.values().cloned().collect());
+            let decision = decisions
+                .into_values()
+                .exactly_one()
+                .expect("cleaned up above");
+            match decision {
+                SchedulingDecision::Refresh(RefreshDecision {
+                    cluster_on: needs_replica,
+                    ..
+                }) => {
+                    // Check whether the cluster's state matches the needed state.
+                    let cluster_config = self.catalog().get_cluster(cluster_id).config.clone();
+                    let mut new_config = cluster_config.clone();
+                    let ClusterVariant::Managed(managed_config) = &mut new_config.variant else {
+                        panic!("cleaned up unmanaged clusters above");
+                    };
+                    let has_replica = managed_config.replication_factor > 0; // Is it On?
+                    if needs_replica != has_replica {
+                        // Turn the cluster On or Off.
+                        altered_a_cluster = true;
+                        managed_config.replication_factor = if needs_replica { 1 } else { 0 };
+                        if let Err(e) = self
+                            .sequence_alter_cluster_managed_to_managed(
+                                None,
+                                cluster_id,
+                                new_config.clone(),
+                                reason,
+                                AlterClusterPlanStrategy::None,
+                            )
+                            .await
+                        {
+                            soft_panic_or_log!(
+                                "handle_scheduling_decisions couldn't alter cluster {}. \
+                                 Old config: {:?}, \
+                                 New config: {:?}, \
+                                 Error: {}",
+                                cluster_id,
+                                cluster_config,
+                                new_config,
+                                e
+                            );
+                        }
                     }
                 }
-            } else {
-                debug!(
-                    "handle_scheduling_decisions: \
-                    Not all policies have made a decision about cluster {}. decisions: {:?}",
-                    cluster_id, decisions,
-                );
+                SchedulingDecision::Utilization(decision) => {
+                    altered_a_cluster |= self
+                        .apply_utilization_decision(cluster_id, &decision, reason)
+                        .await;
+                }
+                SchedulingDecision::AutoSuspend(decision) => {
+                    altered_a_cluster |= self
+                        .apply_auto_suspend_decision(cluster_id, &decision, reason)
+                        .await;
+                }
             }
         }
 
@@ -390,6 +1195,243 @@ impl Coordinator {
             .observe((Instant::now() - start_time).as_secs_f64());
     }
 
+    /// Acts on a decision of the `SCHEDULE = ON UTILIZATION` policy. Resizes are graceful: we
+    /// first create pending replicas of the new size next to the old ones, and only once the
+    /// pending replicas are hydrated, we drop the old replicas and promote the pending ones. If the
+    /// pending replicas don't hydrate within `cluster_utilization_resize_timeout`, we drop them
+    /// and keep the old replicas. Returns whether the cluster was altered.
+    async fn apply_utilization_decision(
+        &mut self,
+        cluster_id: ClusterId,
+        decision: &UtilizationDecision,
+        reason: ReplicaCreateDropReason,
+    ) -> bool {
+        let now = Instant::now();
+        let cluster = self.catalog().get_cluster(cluster_id);
+        let cluster_config = cluster.config.clone();
+        let has_pending_replicas = cluster.replicas().any(|r| r.config.location.pending());
+        let ClusterVariant::Managed(managed_config) = &cluster_config.variant else {
+            panic!("cleaned up unmanaged clusters above");
+        };
+
+        // Move forward an in-progress resize.
+        let state = self
+            .cluster_utilization_state
+            .entry(cluster_id)
+            .or_default();
+        if let Some(pending_resize) = state.pending_resize.take() {
+            if !has_pending_replicas {
+                // Somebody else has dropped the pending replicas in the meantime.
+                return false;
+            }
+            if decision.pending_replicas_hydrated == Some(true) {
+                state.resized(now);
+                if let Err(e) = self
+                    .finalize_cluster_reconfiguration(
+                        None,
+                        cluster_id,
+                        pending_resize.new_config.clone(),
+                        reason,
+                    )
+                    .await
+                {
+                    soft_panic_or_log!(
+                        "handle_scheduling_decisions couldn't finalize resizing cluster {}. \
+                         New config: {:?}, \
+                         Error: {}",
+                        cluster_id,
+                        pending_resize.new_config,
+                        e
+                    );
+                }
+                return true;
+            }
+            if now >= pending_resize.timeout_time {
+                warn!(
+                    %cluster_id,
+                    "abandoning resize, because the new replicas didn't hydrate in time"
+                );
+                state.resized(now);
+                if let Err(e) = self
+                    .drop_reconfiguration_replicas(btreeset! {cluster_id})
+                    .await
+                {
+                    soft_panic_or_log!(
+                        "handle_scheduling_decisions couldn't drop the pending replicas of \
+                         cluster {}. Error: {}",
+                        cluster_id,
+                        e
+                    );
+                }
+                return true;
+            }
+            state.pending_resize = Some(pending_resize);
+            return false;
+        }
+
+        if has_pending_replicas {
+            if self
+                .active_conns
+                .values()
+                .any(|conn| conn.pending_cluster_alters.contains(&cluster_id))
+            {
+                // A user-initiated graceful ALTER is in progress.
+                return false;
+            }
+            // Nobody is waiting for these pending replicas anymore, e.g., because envd restarted
+            // during a resize. Clean them up so that we can start over.
+            if let Err(e) = self
+                .drop_reconfiguration_replicas(btreeset! {cluster_id})
+                .await
+            {
+                soft_panic_or_log!(
+                    "handle_scheduling_decisions couldn't drop the pending replicas of \
+                     cluster {}. Error: {}",
+                    cluster_id,
+                    e
+                );
+            }
+            return true;
+        }
+
+        if decision.target_size == managed_config.size {
+            return false;
+        }
+
+        // Start a resize.
+        let mut new_config = cluster_config.clone();
+        let ClusterVariant::Managed(new_managed_config) = &mut new_config.variant else {
+            unreachable!("checked above");
+        };
+        new_managed_config.size.clone_from(&decision.target_size);
+        let new_managed_config = new_managed_config.clone();
+        // A cluster without replicas has nothing to hydrate, so we can simply change its size.
+        let graceful = managed_config.replication_factor > 0;
+        let timeout = self
+            .catalog()
+            .system_config()
+            .cluster_utilization_resize_timeout();
+        let strategy = if graceful {
+            AlterClusterPlanStrategy::UntilReady {
+                on_timeout: OnTimeoutAction::Rollback,
+                timeout,
+            }
+        } else {
+            AlterClusterPlanStrategy::None
+        };
+        match self
+            .sequence_alter_cluster_managed_to_managed(
+                None,
+                cluster_id,
+                new_config.clone(),
+                reason,
+                strategy,
+            )
+            .await
+        {
+            Ok(_) => {
+                let state = self
+                    .cluster_utilization_state
+                    .entry(cluster_id)
+                    .or_default();
+                if graceful {
+                    state.pending_resize = Some(PendingResize {
+                        new_config: new_managed_config,
+                        timeout_time: now + timeout,
+                    });
+                } else {
+                    state.resized(now);
+                }
+            }
+            Err(e) => {
+                soft_panic_or_log!(
+                    "handle_scheduling_decisions couldn't resize cluster {}. \
+                     Old config: {:?}, \
+                     New config: {:?}, \
+                     Error: {}",
+                    cluster_id,
+                    cluster_config,
+                    new_config,
+                    e
+                );
+            }
+        }
+        true
+    }
+
+    /// Acts on a decision of the `AUTO SUSPEND AFTER` policy. Suspending a cluster sets its
+    /// replication factor to 0, and remembers the old replication factor for when the cluster is
+    /// resumed. The policy itself only resumes a suspended cluster if something on it needs to be
+    /// kept fresh; otherwise, clusters are resumed by queries, see `resume_cluster_for_query`.
+    /// Returns whether the cluster was altered.
+    async fn apply_auto_suspend_decision(
+        &mut self,
+        cluster_id: ClusterId,
+        decision: &AutoSuspendDecision,
+        reason: ReplicaCreateDropReason,
+    ) -> bool {
+        let now = Instant::now();
+        let cluster_config = self.catalog().get_cluster(cluster_id).config.clone();
+        let ClusterVariant::Managed(managed_config) = &cluster_config.variant else {
+            panic!("cleaned up unmanaged clusters above");
+        };
+        let state = self
+            .cluster_auto_suspend_state
+            .entry(cluster_id)
+            .or_insert_with(|| AutoSuspendState::new(now));
+
+        if decision.hydrated == Some(true) {
+            if let Some(resuming) = state.resuming.take() {
+                // Let the queries that wait for the cluster proceed.
+                resuming.send_replace(true);
+            }
+        }
+
+        let has_replica = managed_config.replication_factor > 0; // Is it On?
+        let new_replication_factor = if decision.cluster_on {
+            if has_replica || decision.objects_needing_freshness.is_empty() {
+                return false;
+            }
+            state.suspended_replication_factor.take().unwrap_or(1)
+        } else {
+            // A query might have arrived since the policy made its decision.
+            let idle_for = now.saturating_duration_since(state.last_activity);
+            if !has_replica || state.resuming.is_some() || idle_for < decision.auto_suspend_after {
+                return false;
+            }
+            state.suspended_replication_factor = Some(managed_config.replication_factor);
+            0
+        };
+
+        let mut new_config = cluster_config.clone();
+        let ClusterVariant::Managed(new_managed_config) = &mut new_config.variant else {
+            unreachable!("checked above");
+        };
+        new_managed_config.replication_factor = new_replication_factor;
+        if let Err(e) = self
+            .sequence_alter_cluster_managed_to_managed(
+                None,
+                cluster_id,
+                new_config.clone(),
+                reason,
+                AlterClusterPlanStrategy::None,
+            )
+            .await
+        {
+            soft_panic_or_log!(
+                "handle_scheduling_decisions couldn't suspend or resume cluster {}. \
+                 Old config: {:?}, \
+                 New config: {:?}, \
+                 Error: {}",
+                cluster_id,
+                cluster_config,
+                new_config,
+                e
+            );
+        }
+        true
+    }
+
     /// Returns the managed config for a cluster. Returns None if the cluster doesn't exist or if
     /// it's an unmanaged cluster.
     fn get_managed_cluster_config(&self, cluster_id: ClusterId) -> Option<ClusterVariantManaged> {
@@ -401,3 +1443,171 @@ impl Coordinator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ladder() -> Vec<String> {
+        ["25cc", "50cc", "100cc", "200cc", "400cc"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    fn thresholds() -> UtilizationThresholds {
+        UtilizationThresholds {
+            scale_up_percent: 80,
+            scale_down_percent: 30,
+            sustained_interval: Duration::from_secs(60),
+            cooldown: Duration::from_secs(600),
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_utilization_target_size() {
+        use UtilizationTrend::*;
+        let ladder = ladder();
+        let target = |current, trend, hydrated| {
+            utilization_target_size(&ladder, current, "50cc", "200cc", trend, hydrated)
+        };
+
+        // Step by one, within the range.
+        assert_eq!(target("100cc", High, true), "200cc");
+        assert_eq!(target("100cc", Low, true), "50cc");
+        assert_eq!(target("100cc", Neutral, true), "100cc");
+        assert_eq!(target("200cc", High, true), "200cc");
+        assert_eq!(target("50cc", Low, true), "50cc");
+        // Don't scale down while hydrating, but do scale up.
+        assert_eq!(target("100cc", Low, false), "100cc");
+        assert_eq!(target("100cc", High, false), "200cc");
+        // Move into the range.
+        assert_eq!(target("25cc", Neutral, true), "50cc");
+        assert_eq!(target("400cc", Low, false), "200cc");
+        // Unknown sizes stay put.
+        assert_eq!(target("800cc", High, true), "800cc");
+        assert_eq!(
+            utilization_target_size(&ladder, "100cc", "1cc", "200cc", High, true),
+            "100cc"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_utilization_state_observe() {
+        let thresholds = thresholds();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut state = UtilizationState::default();
+
+        // High utilization of a single resource has to be sustained.
+        assert_eq!(
+            state.observe(at(0), Some(90), Some(10), &thresholds),
+            UtilizationTrend::Neutral
+        );
+        assert_eq!(
+            state.observe(at(30), Some(85), None, &thresholds),
+            UtilizationTrend::Neutral
+        );
+        assert_eq!(
+            state.observe(at(60), Some(95), Some(10), &thresholds),
+            UtilizationTrend::High
+        );
+
+        // A dip resets the clock.
+        assert_eq!(
+            state.observe(at(70), Some(50), Some(10), &thresholds),
+            UtilizationTrend::Neutral
+        );
+        assert_eq!(
+            state.observe(at(100), Some(90), Some(10), &thresholds),
+            UtilizationTrend::Neutral
+        );
+
+        // Low utilization needs all known resources to be low, and no metrics is not low.
+        let mut state = UtilizationState::default();
+        assert_eq!(
+            state.observe(at(0), None, None, &thresholds),
+            UtilizationTrend::Neutral
+        );
+        assert_eq!(
+            state.observe(at(0), Some(10), Some(50), &thresholds),
+            UtilizationTrend::Neutral
+        );
+        assert_eq!(
+            state.observe(at(10), Some(10), Some(20), &thresholds),
+            UtilizationTrend::Neutral
+        );
+        assert_eq!(
+            state.observe(at(70), Some(10), None, &thresholds),
+            UtilizationTrend::Low
+        );
+
+        // No resizes during the cooldown.
+        state.resized(at(70));
+        assert_eq!(
+            state.observe(at(200), Some(10), Some(10), &thresholds),
+            UtilizationTrend::Neutral
+        );
+        assert_eq!(
+            state.observe(at(670), Some(10), Some(10), &thresholds),
+            UtilizationTrend::Low
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_max_utilization_percent() {
+        assert_eq!(
+            max_utilization_percent([Some(50), None, Some(150)], Some(200)),
+            Some(75)
+        );
+        assert_eq!(max_utilization_percent([Some(300)], Some(200)), Some(150));
+        assert_eq!(max_utilization_percent([None, None], Some(200)), None);
+        assert_eq!(max_utilization_percent([Some(50)], None), None);
+        assert_eq!(max_utilization_percent([Some(50)], Some(0)), None);
+    }
+
+    #[mz_ore::test]
+    fn test_auto_suspend_cluster_on() {
+        let minute = Duration::from_secs(60);
+        assert!(auto_suspend_cluster_on(&[], Duration::ZERO, minute));
+        assert!(auto_suspend_cluster_on(&[], minute / 2, minute));
+        assert!(!auto_suspend_cluster_on(&[], minute, minute));
+        assert!(!auto_suspend_cluster_on(&[], minute * 10, minute));
+        // Objects that need to be kept fresh keep the cluster On regardless of queries.
+        assert!(auto_suspend_cluster_on(
+            &[GlobalId::User(1)],
+            minute * 10,
+            minute
+        ));
+    }
+
+    #[mz_ore::test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(3600)), "01:00:00");
+        // Fractional microseconds are dropped rather than failing the conversion.
+        assert_eq!(
+            format_duration(Duration::new(1, 500_000_999)),
+            format_duration(Duration::new(1, 500_000_000)),
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_auto_suspend_wait_for_resume() {
+        let mut state = AutoSuspendState::new(Instant::now());
+        assert!(state.wait_for_resume().is_none());
+
+        // Waiters are released once the cluster is hydrated.
+        let (tx, _) = watch::channel(false);
+        state.resuming = Some(tx);
+        let wait = state.wait_for_resume().expect("resuming");
+        state.resuming.take().expect("resuming").send_replace(true);
+        wait.await;
+
+        // Waiters are also released if the resume is abandoned.
+        let (tx, _) = watch::channel(false);
+        state.resuming = Some(tx);
+        let wait = state.wait_for_resume().expect("resuming");
+        state.resuming = None;
+        wait.await;
+    }
+}
@@ -62,7 +62,7 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 use crate::command::{AuthResponse, CatalogSnapshot, Command, ExecuteResponse, StartupResponse};
 use crate::coord::appends::PendingWriteTxn;
 use crate::coord::{
-    ConnMeta, Coordinator, DeferredPlanStatement, Message, PendingTxn, PlanStatement, PlanValidity,
+    ConnMeta, Coordinator, Message, PendingTxn, PlanStatement, PlanValidity,
     PurifiedStatementReady, validate_ip_with_policy_rules,
 };
 use crate::error::AdapterError;
@@ -701,6 +701,7 @@ impl Coordinator {
                     | Statement::Execute(_)
                     | Statement::ExplainPlan(_)
                     | Statement::ExplainPushdown(_)
+                    | Statement::ExplainAnalyze(_)
                     | Statement::ExplainTimestamp(_)
                     | Statement::ExplainSinkSchema(_)
                     | Statement::Fetch(_)
@@ -862,10 +863,7 @@ impl Coordinator {
                     )));
                     return;
                 }
-                self.serialized_ddl.push_back(DeferredPlanStatement {
-                    ctx,
-                    ps: PlanStatement::Statement { stmt, params },
-                });
+                self.defer_ddl(ctx, PlanStatement::Statement { stmt, params });
                 return;
             }
         }
@@ -710,6 +710,8 @@ impl Coordinator {
             if !clusters_to_drop.is_empty() {
                 for cluster_id in clusters_to_drop {
                     self.controller.drop_cluster(cluster_id);
+                    self.peek_result_cache.remove_cluster(&cluster_id);
+                    self.peek_admission.remove_cluster(&cluster_id);
                 }
             }
 
@@ -1463,7 +1465,8 @@ impl Coordinator {
                             }
                             ConnectionDetails::Csr(_)
                             | ConnectionDetails::Ssh { .. }
-                            | ConnectionDetails::Aws(_) => {}
+                            | ConnectionDetails::Aws(_)
+                            | ConnectionDetails::MongoDb(_) => {}
                         },
                         CatalogItem::Table(_) => {
                             new_tables += 1;
@@ -1643,7 +1646,8 @@ impl Coordinator {
                 ConnectionDetails::Kafka(_) => current_kafka_connections += 1,
                 ConnectionDetails::Csr(_)
                 | ConnectionDetails::Ssh { .. }
-                | ConnectionDetails::Aws(_) => {}
+                | ConnectionDetails::Aws(_)
+                | ConnectionDetails::MongoDb(_) => {}
             }
         }
         self.validate_resource_limit(
@@ -15,6 +15,7 @@ use std::time::{Duration, Instant};
 
 use futures::FutureExt;
 use maplit::btreemap;
+use mz_adapter_types::dyncfgs::PEEK_RESULT_CACHE_MAX_BYTES_PER_CLUSTER;
 use mz_catalog::memory::objects::ClusterReplicaProcessStatus;
 use mz_controller::ControllerResponse;
 use mz_controller::clusters::{ClusterEvent, ClusterStatus};
@@ -104,6 +105,8 @@ impl Coordinator {
             }
             Message::AdvanceTimelines => {
                 self.advance_timelines().boxed_local().await;
+                // Advancing the timelines downgrades read holds, which lets read frontiers advance.
+                self.invalidate_peek_result_cache();
             }
             Message::ClusterEvent(event) => self.message_cluster_event(event).boxed_local().await,
             Message::CancelPendingPeeks { conn_id } => {
@@ -177,6 +180,21 @@ impl Coordinator {
             Message::ClusterStageReady { ctx, span, stage } => {
                 self.sequence_staged(ctx, span, stage).boxed_local().await;
             }
+            Message::PeekResultCacheInsert { key, inputs, rows } => {
+                let max_bytes = PEEK_RESULT_CACHE_MAX_BYTES_PER_CLUSTER
+                    .get(self.catalog().system_config().dyncfgs());
+                self.peek_result_cache.insert(key, inputs, rows, max_bytes);
+            }
+            Message::AdmitQueuedPeeks(cluster_id) => {
+                self.admit_queued_peeks(cluster_id);
+            }
+            Message::DropExplainAnalyzeDataflow {
+                cluster_id,
+                index_id,
+            } => {
+                self.remove_compute_ids_from_timeline(vec![(cluster_id, index_id)]);
+                self.drop_indexes(vec![(cluster_id, index_id)]);
+            }
             Message::DrainStatementLog => {
                 self.drain_statement_log();
             }
@@ -204,6 +222,9 @@ impl Coordinator {
             Message::DeferredStatementReady => {
                 self.handle_deferred_statement().boxed_local().await;
             }
+            Message::DeferredStatementTimeout => {
+                self.cancel_expired_deferred_statements();
+            }
         }
     }
 
@@ -467,6 +488,10 @@ impl Coordinator {
                         WatchSetResponse::AlterSinkReady(ctx) => {
                             self.sequence_alter_sink_finish(ctx).await;
                         }
+                        WatchSetResponse::ExplainAnalyzeReady(tx) => {
+                            // The statement is gone if sending fails.
+                            let _ = tx.send(());
+                        }
                     }
                 }
             }
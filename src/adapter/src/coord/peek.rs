@@ -20,12 +20,14 @@ use differential_dataflow::consolidation::consolidate;
 use futures::TryFutureExt;
 use mz_adapter_types::compaction::CompactionWindow;
 use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::PEEK_RESULT_CACHE_MAX_BYTES_PER_CLUSTER;
 use mz_cluster_client::ReplicaId;
 use mz_compute_client::controller::PeekNotification;
 use mz_compute_client::protocol::command::PeekTarget;
 use mz_compute_client::protocol::response::PeekResponse;
 use mz_compute_types::ComputeInstanceId;
 use mz_compute_types::dataflows::{DataflowDescription, IndexImport};
+use mz_compute_types::sources::SourceInstanceArguments;
 use mz_controller_types::ClusterId;
 use mz_expr::explain::{HumanizedExplain, HumanizerMode, fmt_text_constant_rows};
 use mz_expr::row::RowCollection;
@@ -39,10 +41,14 @@ use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::explain::text::DisplayText;
 use mz_repr::explain::{CompactScalars, IndexUsageType, PlanRenderingContext, UsedIndexes};
 use mz_repr::{Diff, GlobalId, IntoRowIterator, RelationType, Row, RowIterator, preserves_order};
+use mz_sql::session::vars::WorkloadClass;
 use serde::{Deserialize, Serialize};
 use timely::progress::Timestamp;
 use uuid::Uuid;
 
+use crate::coord::Message;
+use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::peek_result_cache::PeekResultCacheKey;
 use crate::coord::timestamp_selection::TimestampDetermination;
 use crate::optimize::OptimizerError;
 use crate::statement_logging::{StatementEndedExecutionReason, StatementExecutionStrategy};
@@ -63,6 +69,8 @@ pub(crate) struct PendingPeek {
     pub(crate) ctx_extra: ExecuteContextExtra,
     /// Is this a fast-path peek, i.e. one that doesn't require a dataflow?
     pub(crate) is_fast_path: bool,
+    /// The workload class the peek was admitted in.
+    pub(crate) workload_class: WorkloadClass,
 }
 
 /// The response from a `Peek`, with row multiplicities represented in unary.
@@ -108,7 +116,30 @@ impl<T> PeekDataflowPlan<T> {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Ord, PartialOrd)]
+/// The dataflow of a SELECT that is analyzed by an `EXPLAIN ANALYZE`.
+///
+/// The dataflow is installed as a transient index, and is dropped when the
+/// guard is dropped, whether the analysis succeeds, fails, or is canceled.
+#[derive(Debug)]
+pub struct ExplainAnalyzeDataflow {
+    pub(crate) cluster_id: ClusterId,
+    pub(crate) index_id: GlobalId,
+    pub(crate) internal_cmd_tx: tokio::sync::mpsc::UnboundedSender<Message>,
+}
+
+impl Drop for ExplainAnalyzeDataflow {
+    fn drop(&mut self) {
+        // The coordinator is gone if sending fails, and the dataflow with it.
+        let _ = self
+            .internal_cmd_tx
+            .send(Message::DropExplainAnalyzeDataflow {
+                cluster_id: self.cluster_id,
+                index_id: self.index_id,
+            });
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub enum FastPathPlan {
     /// The view evaluates to a constant result that can be returned.
     ///
@@ -280,6 +311,97 @@ pub enum PeekPlan<T = mz_repr::Timestamp> {
     SlowPath(PeekDataflowPlan<T>),
 }
 
+/// The parts of a peek plan and its finishing that determine the results of
+/// the peek, which identify the results in the
+/// [`PeekResultCache`](crate::coord::peek_result_cache::PeekResultCache).
+///
+/// Transient ids, debug names, and frontiers of the dataflow of a slow-path
+/// plan are excluded, as they differ between otherwise identical peeks.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PeekResultCachePlan<T = mz_repr::Timestamp> {
+    FastPath(FastPathPlan, RowSetFinishing),
+    SlowPath {
+        source_imports: Vec<(GlobalId, SourceInstanceArguments, RelationType, bool)>,
+        index_imports: BTreeMap<GlobalId, IndexImport>,
+        plans: Vec<mz_compute_types::plan::Plan<T>>,
+        key: Vec<MirScalarExpr>,
+        permutation: Vec<usize>,
+        thinned_arity: usize,
+        finishing: RowSetFinishing,
+    },
+}
+
+impl<T: Clone> PeekPlan<T> {
+    /// Returns the parts of this plan that determine its results with
+    /// `finishing` applied, for use in a [`PeekResultCacheKey`], or `None` if
+    /// the results of this plan should not be cached.
+    pub fn result_cache_plan(&self, finishing: &RowSetFinishing) -> Option<PeekResultCachePlan<T>> {
+        match self {
+            PeekPlan::FastPath(FastPathPlan::Constant(..)) => None,
+            PeekPlan::FastPath(plan) => Some(PeekResultCachePlan::FastPath(
+                plan.clone(),
+                finishing.clone(),
+            )),
+            PeekPlan::SlowPath(PeekDataflowPlan {
+                desc,
+                id: _,
+                key,
+                permutation,
+                thinned_arity,
+            }) => {
+                let source_imports = desc
+                    .source_imports
+                    .iter()
+                    .map(|(id, (desc, monotonic, _upper))| {
+                        (*id, desc.arguments.clone(), desc.typ.clone(), *monotonic)
+                    })
+                    .collect();
+                let plans = desc
+                    .objects_to_build
+                    .iter()
+                    .map(|build| build.plan.clone())
+                    .collect();
+                Some(PeekResultCachePlan::SlowPath {
+                    source_imports,
+                    index_imports: desc.index_imports.clone(),
+                    plans,
+                    key: key.clone(),
+                    permutation: permutation.clone(),
+                    thinned_arity: *thinned_arity,
+                    finishing: finishing.clone(),
+                })
+            }
+        }
+    }
+}
+
+impl<T> PeekPlan<T> {
+    /// Returns the collections that this plan reads from when it runs on
+    /// `compute_instance`.
+    pub fn input_id_bundle(&self, compute_instance: ComputeInstanceId) -> CollectionIdBundle {
+        let mut id_bundle = CollectionIdBundle::default();
+        match self {
+            PeekPlan::FastPath(FastPathPlan::Constant(..)) => {}
+            PeekPlan::FastPath(FastPathPlan::PeekExisting(_coll_id, idx_id, ..)) => {
+                id_bundle
+                    .compute_ids
+                    .insert(compute_instance, BTreeSet::from([*idx_id]));
+            }
+            PeekPlan::FastPath(FastPathPlan::PeekPersist(coll_id, ..)) => {
+                id_bundle.storage_ids.insert(*coll_id);
+            }
+            PeekPlan::SlowPath(PeekDataflowPlan { desc, .. }) => {
+                id_bundle.storage_ids = desc.source_imports.keys().copied().collect();
+                id_bundle.compute_ids.insert(
+                    compute_instance,
+                    desc.index_imports.keys().copied().collect(),
+                );
+            }
+        }
+        id_bundle
+    }
+}
+
 /// Convert `mfp` to an executable, non-temporal plan.
 /// It should be non-temporal, as OneShot preparation populates `mz_now`.
 fn mfp_to_safe_plan(
@@ -517,6 +639,7 @@ impl crate::coord::Coordinator {
         target_replica: Option<ReplicaId>,
         max_result_size: u64,
         max_returned_query_size: Option<u64>,
+        workload_class: WorkloadClass,
     ) -> Result<crate::ExecuteResponse, AdapterError> {
         let PlannedPeek {
             plan: fast_path,
@@ -588,6 +711,66 @@ impl crate::coord::Coordinator {
             self.set_statement_execution_timestamp(id, timestamp)
         }
 
+        // Answer the peek from the result cache if an identical peek already
+        // ran on this cluster at the same timestamp. Peeks targeting a specific
+        // replica bypass the cache, as they are used to inspect that replica.
+        let result_cache_max_bytes =
+            PEEK_RESULT_CACHE_MAX_BYTES_PER_CLUSTER.get(self.catalog().system_config().dyncfgs());
+        if result_cache_max_bytes == 0 {
+            self.peek_result_cache.clear();
+        }
+        let result_cache_key = if result_cache_max_bytes > 0 && target_replica.is_none() {
+            fast_path
+                .result_cache_plan(&finishing)
+                .map(|plan| PeekResultCacheKey {
+                    cluster_id: compute_instance,
+                    plan,
+                    timestamp,
+                })
+        } else {
+            None
+        };
+        let result_cache_inputs = fast_path.input_id_bundle(compute_instance);
+        if let Some(key) = &result_cache_key {
+            let cluster_label = compute_instance.to_string();
+            if let Some(rows) = self.peek_result_cache.get(key) {
+                self.metrics
+                    .peek_result_cache_hits
+                    .with_label_values(&[&cluster_label])
+                    .inc();
+                let duration_histogram = self.metrics.row_set_finishing_seconds();
+                let (ret, reason) = match finishing.finish(
+                    rows,
+                    max_result_size,
+                    max_returned_query_size,
+                    &duration_histogram,
+                ) {
+                    Ok((rows, row_size_bytes)) => {
+                        let result_size = u64::cast_from(row_size_bytes);
+                        let rows_returned = u64::cast_from(rows.count());
+                        (
+                            Ok(Self::send_immediate_rows(rows)),
+                            StatementEndedExecutionReason::Success {
+                                result_size: Some(result_size),
+                                rows_returned: Some(rows_returned),
+                                execution_strategy: Some(StatementExecutionStrategy::ResultCache),
+                            },
+                        )
+                    }
+                    Err(error) => (
+                        Err(AdapterError::ResultSize(error.clone())),
+                        StatementEndedExecutionReason::Errored { error },
+                    ),
+                };
+                self.retire_execution(reason, std::mem::take(ctx_extra));
+                return ret;
+            }
+            self.metrics
+                .peek_result_cache_misses
+                .with_label_values(&[&cluster_label])
+                .inc();
+        }
+
         // The remaining cases are a peek into a maintained arrangement, or building a dataflow.
         // In both cases we will want to peek, and the main difference is that we might want to
         // build a dataflow and drop it once the peek is issued. The peeks are also constructed
@@ -699,6 +882,7 @@ impl crate::coord::Coordinator {
                 depends_on: source_ids,
                 ctx_extra: std::mem::take(ctx_extra),
                 is_fast_path,
+                workload_class,
             },
         );
         self.client_pending_peeks
@@ -722,12 +906,21 @@ impl crate::coord::Coordinator {
             )
             .unwrap_or_terminate("cannot fail to peek");
         let duration_histogram = self.metrics.row_set_finishing_seconds();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
 
         // Prepare the receiver to return as a response.
         let rows_rx = rows_rx.map_ok_or_else(
             |e| PeekResponseUnary::Error(e.to_string()),
             move |resp| match resp {
                 PeekResponse::Rows(rows) => {
+                    if let Some(key) = result_cache_key {
+                        // It is not an error for the coordinator to have shut down.
+                        let _ = internal_cmd_tx.send(Message::PeekResultCacheInsert {
+                            key,
+                            inputs: result_cache_inputs,
+                            rows: rows.clone(),
+                        });
+                    }
                     match finishing.finish(
                         rows,
                         max_result_size,
@@ -756,6 +949,30 @@ impl crate::coord::Coordinator {
         })
     }
 
+    /// Drops cached peek results that no peek can use anymore, because the
+    /// read frontier of one of their inputs has advanced past their timestamp,
+    /// or because one of their inputs no longer exists.
+    pub(crate) fn invalidate_peek_result_cache(&mut self) {
+        let controller = &self.controller;
+        self.peek_result_cache.retain(|inputs, timestamp| {
+            let storage_readable = inputs.storage_ids.iter().all(|id| {
+                controller
+                    .storage
+                    .collection_frontiers(*id)
+                    .is_ok_and(|(since, _upper)| since.less_equal(&timestamp))
+            });
+            let compute_readable = inputs.compute_ids.iter().all(|(instance, ids)| {
+                ids.iter().all(|id| {
+                    controller
+                        .compute
+                        .collection_frontiers(*id, Some(*instance))
+                        .is_ok_and(|frontiers| frontiers.read_frontier.less_equal(&timestamp))
+                })
+            });
+            storage_readable && compute_readable
+        });
+    }
+
     /// Cancel and remove all pending peeks that were initiated by the client with `conn_id`.
     #[mz_ore::instrument(level = "debug")]
     pub(crate) fn cancel_pending_peeks(&mut self, conn_id: &ConnectionId) {
@@ -787,9 +1004,13 @@ impl crate::coord::Coordinator {
                 .iter()
                 .filter_map(|(uuid, _)| self.pending_peeks.remove(uuid))
                 .collect::<Vec<_>>();
+            let clusters: BTreeSet<_> = peeks.iter().map(|peek| peek.cluster_id).collect();
             for peek in peeks {
                 self.retire_execution(StatementEndedExecutionReason::Canceled, peek.ctx_extra);
             }
+            for cluster_id in clusters {
+                self.admit_queued_peeks(cluster_id);
+            }
         }
     }
 
@@ -809,6 +1030,7 @@ impl crate::coord::Coordinator {
             depends_on: _,
             ctx_extra,
             is_fast_path,
+            workload_class: _,
         }) = self.remove_pending_peek(&uuid)
         {
             let reason = match notification {
@@ -849,6 +1071,7 @@ impl crate::coord::Coordinator {
             if uuids.is_empty() {
                 self.client_pending_peeks.remove(&pending_peek.conn_id);
             }
+            self.admit_queued_peeks(pending_peek.cluster_id);
         }
         pending_peek
     }
@@ -0,0 +1,458 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A cache of peek results, for answering repeated identical peeks without
+//! sending them to a cluster.
+//!
+//! Results are keyed by the cluster, the optimized peek plan (including its
+//! finishing), and the timestamp the peek was executed at. The result of a plan
+//! at a fixed timestamp never changes, so a cached result can be returned for
+//! as long as peeks keep choosing the same timestamp. Only the newest timestamp
+//! is retained for each plan: once a peek for the same plan chooses a later
+//! timestamp, the older result is dropped. Results are also dropped as soon as
+//! the read frontier of one of their inputs advances past their timestamp, as
+//! no peek can choose that timestamp anymore.
+//!
+//! Cached results are bucketed by a hash of their plan, but a result is only
+//! returned for a plan that is equal to the one it was cached for. Hashes can
+//! collide, and returning the result of a different plan would leak data the
+//! peeking session might not be allowed to read.
+
+use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use mz_controller_types::ClusterId;
+use mz_expr::row::RowCollection;
+use mz_repr::Timestamp;
+
+use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::peek::PeekResultCachePlan;
+
+/// Identifies the result of a peek in a [`PeekResultCache`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeekResultCacheKey<P = PeekResultCachePlan> {
+    /// The cluster the peek is executed on.
+    pub cluster_id: ClusterId,
+    /// The optimized peek plan and its finishing.
+    pub plan: P,
+    /// The timestamp the peek is executed at.
+    pub timestamp: Timestamp,
+}
+
+/// A per-cluster, size-limited cache of peek results.
+#[derive(Debug)]
+pub struct PeekResultCache<P = PeekResultCachePlan> {
+    clusters: BTreeMap<ClusterId, ClusterPeekResultCache<P>>,
+}
+
+impl<P> Default for PeekResultCache<P> {
+    fn default() -> Self {
+        Self {
+            clusters: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ClusterPeekResultCache<P> {
+    /// Cached results, by the hash of their plan.
+    entries: BTreeMap<u64, Vec<CacheEntry<P>>>,
+    /// Plan hashes, by the tick at which their entry was last used.
+    lru: BTreeMap<u64, u64>,
+    /// Source of ticks for `lru`.
+    clock: u64,
+    /// The sum of the sizes of all cached results.
+    bytes: usize,
+}
+
+impl<P> Default for ClusterPeekResultCache<P> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            lru: BTreeMap::new(),
+            clock: 0,
+            bytes: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry<P> {
+    plan: P,
+    timestamp: Timestamp,
+    /// The collections the result was read from.
+    inputs: CollectionIdBundle,
+    rows: RowCollection,
+    /// The tick at which the entry was last used, which also identifies the
+    /// entry within its cluster.
+    last_used: u64,
+}
+
+impl<P: Hash + Eq> PeekResultCache<P> {
+    /// Returns the cached result for `key`, if any.
+    ///
+    /// A cached result for the same plan at an earlier timestamp is stale and
+    /// gets evicted.
+    pub fn get(&mut self, key: &PeekResultCacheKey<P>) -> Option<RowCollection> {
+        let cluster = self.clusters.get_mut(&key.cluster_id)?;
+        let hash = plan_hash(&key.plan);
+        let entry = cluster.find(hash, &key.plan)?;
+        let (timestamp, last_used) = (entry.timestamp, entry.last_used);
+        if timestamp < key.timestamp {
+            cluster.remove(hash, last_used);
+            return None;
+        }
+        if timestamp > key.timestamp {
+            return None;
+        }
+
+        let tick = cluster.tick();
+        let entry = cluster
+            .entries
+            .get_mut(&hash)
+            .and_then(|bucket| bucket.iter_mut().find(|entry| entry.last_used == last_used))
+            .expect("entry known to exist");
+        cluster.lru.remove(&entry.last_used);
+        cluster.lru.insert(tick, hash);
+        entry.last_used = tick;
+        Some(entry.rows.clone())
+    }
+
+    /// Caches `rows`, read from the collections in `inputs`, as the result for
+    /// `key`, evicting the least recently used results of the cluster until its
+    /// cached results fit in `max_bytes`.
+    ///
+    /// Results for an earlier timestamp than the one already cached for the
+    /// same plan are not cached.
+    pub fn insert(
+        &mut self,
+        key: PeekResultCacheKey<P>,
+        inputs: CollectionIdBundle,
+        rows: RowCollection,
+        max_bytes: usize,
+    ) {
+        if rows.byte_len() > max_bytes {
+            return;
+        }
+
+        let cluster = self.clusters.entry(key.cluster_id).or_default();
+        let hash = plan_hash(&key.plan);
+        if let Some(entry) = cluster.find(hash, &key.plan) {
+            if entry.timestamp >= key.timestamp {
+                return;
+            }
+            let last_used = entry.last_used;
+            cluster.remove(hash, last_used);
+        }
+
+        let tick = cluster.tick();
+        cluster.bytes += rows.byte_len();
+        cluster.lru.insert(tick, hash);
+        cluster.entries.entry(hash).or_default().push(CacheEntry {
+            plan: key.plan,
+            timestamp: key.timestamp,
+            inputs,
+            rows,
+            last_used: tick,
+        });
+
+        while cluster.bytes > max_bytes {
+            let (&last_used, &hash) = cluster
+                .lru
+                .first_key_value()
+                .expect("cache known to be non-empty");
+            cluster.remove(hash, last_used);
+        }
+    }
+}
+
+impl<P> PeekResultCache<P> {
+    /// Drops the cached results for which `is_valid` returns false, given the
+    /// collections the result was read from and its timestamp.
+    pub fn retain<F>(&mut self, mut is_valid: F)
+    where
+        F: FnMut(&CollectionIdBundle, Timestamp) -> bool,
+    {
+        for cluster in self.clusters.values_mut() {
+            let invalid: Vec<_> = cluster
+                .entries
+                .iter()
+                .flat_map(|(hash, bucket)| bucket.iter().map(move |entry| (*hash, entry)))
+                .filter(|(_, entry)| !is_valid(&entry.inputs, entry.timestamp))
+                .map(|(hash, entry)| (hash, entry.last_used))
+                .collect();
+            for (hash, last_used) in invalid {
+                cluster.remove(hash, last_used);
+            }
+        }
+        self.clusters
+            .retain(|_, cluster| !cluster.entries.is_empty());
+    }
+
+    /// Drops all cached results for `cluster_id`.
+    pub fn remove_cluster(&mut self, cluster_id: &ClusterId) {
+        self.clusters.remove(cluster_id);
+    }
+
+    /// Drops all cached results.
+    pub fn clear(&mut self) {
+        self.clusters.clear();
+    }
+}
+
+impl<P: Eq> ClusterPeekResultCache<P> {
+    /// Returns the entry cached for `plan`, whose hash is `hash`.
+    fn find(&self, hash: u64, plan: &P) -> Option<&CacheEntry<P>> {
+        self.entries
+            .get(&hash)?
+            .iter()
+            .find(|entry| entry.plan == *plan)
+    }
+}
+
+impl<P> ClusterPeekResultCache<P> {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Removes the entry with plan hash `hash` that was last used at
+    /// `last_used`.
+    fn remove(&mut self, hash: u64, last_used: u64) {
+        let Some(bucket) = self.entries.get_mut(&hash) else {
+            return;
+        };
+        let Some(index) = bucket.iter().position(|entry| entry.last_used == last_used) else {
+            return;
+        };
+        let entry = bucket.swap_remove(index);
+        if bucket.is_empty() {
+            self.entries.remove(&hash);
+        }
+        self.lru.remove(&entry.last_used);
+        self.bytes -= entry.rows.byte_len();
+    }
+}
+
+/// Returns the hash of `plan` used to bucket cached results.
+///
+/// This is only used to narrow down the entries whose plans are compared, so it
+/// need not be resistant to deliberate collisions.
+fn plan_hash<P: Hash>(plan: &P) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    plan.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::num::NonZeroUsize;
+
+    use mz_repr::{Datum, GlobalId, Row};
+
+    use super::*;
+
+    fn key(cluster_id: ClusterId, plan: u64, timestamp: u64) -> PeekResultCacheKey<u64> {
+        PeekResultCacheKey {
+            cluster_id,
+            plan,
+            timestamp: timestamp.into(),
+        }
+    }
+
+    /// A plan whose hash collides with that of every other plan.
+    #[derive(Debug, PartialEq, Eq)]
+    struct CollidingPlan(u64);
+
+    impl Hash for CollidingPlan {
+        fn hash<H: Hasher>(&self, _state: &mut H) {}
+    }
+
+    fn inputs(id: GlobalId) -> CollectionIdBundle {
+        CollectionIdBundle {
+            storage_ids: BTreeSet::from([id]),
+            compute_ids: BTreeMap::new(),
+        }
+    }
+
+    fn rows(n: i64) -> RowCollection {
+        let rows = (0..n)
+            .map(|i| (Row::pack_slice(&[Datum::Int64(i)]), NonZeroUsize::MIN))
+            .collect();
+        RowCollection::new(rows, &[])
+    }
+
+    #[mz_ore::test]
+    fn test_hit_and_miss() {
+        let mut cache = PeekResultCache::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let other_cluster = ClusterId::user(2).expect("valid id");
+
+        assert!(cache.get(&key(cluster, 1, 1)).is_none());
+        cache.insert(
+            key(cluster, 1, 1),
+            inputs(GlobalId::User(1)),
+            rows(3),
+            1 << 20,
+        );
+
+        let hit = cache.get(&key(cluster, 1, 1)).expect("cached");
+        assert_eq!(hit.entries(), 3);
+        assert!(cache.get(&key(cluster, 2, 1)).is_none());
+        assert!(cache.get(&key(other_cluster, 1, 1)).is_none());
+        // A peek at an earlier timestamp cannot use the newer result.
+        assert!(cache.get(&key(cluster, 1, 0)).is_none());
+        assert!(cache.get(&key(cluster, 1, 1)).is_some());
+
+        cache.remove_cluster(&cluster);
+        assert!(cache.get(&key(cluster, 1, 1)).is_none());
+    }
+
+    #[mz_ore::test]
+    fn test_newer_timestamp_invalidates() {
+        let mut cache = PeekResultCache::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+
+        cache.insert(
+            key(cluster, 1, 1),
+            inputs(GlobalId::User(1)),
+            rows(3),
+            1 << 20,
+        );
+        assert!(cache.get(&key(cluster, 1, 2)).is_none());
+        assert!(cache.get(&key(cluster, 1, 1)).is_none());
+        assert_eq!(cache.clusters[&cluster].bytes, 0);
+
+        // Results for older timestamps don't replace newer ones.
+        cache.insert(
+            key(cluster, 1, 5),
+            inputs(GlobalId::User(1)),
+            rows(1),
+            1 << 20,
+        );
+        cache.insert(
+            key(cluster, 1, 4),
+            inputs(GlobalId::User(1)),
+            rows(2),
+            1 << 20,
+        );
+        assert_eq!(cache.get(&key(cluster, 1, 5)).expect("cached").entries(), 1);
+        assert!(cache.get(&key(cluster, 1, 4)).is_none());
+    }
+
+    #[mz_ore::test]
+    fn test_eviction() {
+        let mut cache = PeekResultCache::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let size = rows(10).byte_len();
+        let max_bytes = 2 * size;
+
+        cache.insert(
+            key(cluster, 1, 1),
+            inputs(GlobalId::User(1)),
+            rows(10),
+            max_bytes,
+        );
+        cache.insert(
+            key(cluster, 2, 1),
+            inputs(GlobalId::User(1)),
+            rows(10),
+            max_bytes,
+        );
+        // Using 1 makes 2 the least recently used result.
+        assert!(cache.get(&key(cluster, 1, 1)).is_some());
+        cache.insert(
+            key(cluster, 3, 1),
+            inputs(GlobalId::User(1)),
+            rows(10),
+            max_bytes,
+        );
+
+        assert!(cache.get(&key(cluster, 1, 1)).is_some());
+        assert!(cache.get(&key(cluster, 2, 1)).is_none());
+        assert!(cache.get(&key(cluster, 3, 1)).is_some());
+        assert_eq!(cache.clusters[&cluster].bytes, max_bytes);
+
+        // Results larger than the limit are not cached at all.
+        cache.insert(
+            key(cluster, 4, 1),
+            inputs(GlobalId::User(1)),
+            rows(30),
+            max_bytes,
+        );
+        assert!(cache.get(&key(cluster, 4, 1)).is_none());
+        assert!(cache.get(&key(cluster, 1, 1)).is_some());
+        assert!(cache.get(&key(cluster, 3, 1)).is_some());
+    }
+
+    #[mz_ore::test]
+    fn test_retain() {
+        let mut cache = PeekResultCache::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let other_cluster = ClusterId::user(2).expect("valid id");
+
+        cache.insert(
+            key(cluster, 1, 1),
+            inputs(GlobalId::User(1)),
+            rows(3),
+            1 << 20,
+        );
+        cache.insert(
+            key(cluster, 2, 5),
+            inputs(GlobalId::User(1)),
+            rows(3),
+            1 << 20,
+        );
+        cache.insert(
+            key(other_cluster, 1, 1),
+            inputs(GlobalId::User(2)),
+            rows(3),
+            1 << 20,
+        );
+
+        // The read frontier of `u1` advances to 2.
+        cache.retain(|inputs, timestamp| {
+            !inputs.storage_ids.contains(&GlobalId::User(1)) || timestamp >= 2.into()
+        });
+        assert!(cache.get(&key(cluster, 1, 1)).is_none());
+        assert!(cache.get(&key(cluster, 2, 5)).is_some());
+        assert!(cache.get(&key(other_cluster, 1, 1)).is_some());
+        assert_eq!(cache.clusters[&cluster].bytes, rows(3).byte_len());
+
+        cache.retain(|_, _| false);
+        assert!(cache.clusters.is_empty());
+    }
+
+    #[mz_ore::test]
+    fn test_hash_collision() {
+        let mut cache = PeekResultCache::default();
+        let cluster = ClusterId::user(1).expect("valid id");
+        let key = |plan, timestamp: u64| PeekResultCacheKey {
+            cluster_id: cluster,
+            plan: CollidingPlan(plan),
+            timestamp: timestamp.into(),
+        };
+
+        cache.insert(key(1, 1), inputs(GlobalId::User(1)), rows(1), 1 << 20);
+        // A different plan with the same hash must not see the result.
+        assert!(cache.get(&key(2, 1)).is_none());
+
+        cache.insert(key(2, 1), inputs(GlobalId::User(2)), rows(2), 1 << 20);
+        assert_eq!(cache.get(&key(1, 1)).expect("cached").entries(), 1);
+        assert_eq!(cache.get(&key(2, 1)).expect("cached").entries(), 2);
+
+        // Newer timestamps and invalidations only affect the matching plan.
+        assert!(cache.get(&key(1, 2)).is_none());
+        assert!(cache.get(&key(2, 1)).is_some());
+        cache.retain(|inputs, _| !inputs.storage_ids.contains(&GlobalId::User(2)));
+        assert!(cache.get(&key(2, 1)).is_none());
+        assert!(cache.clusters.is_empty());
+    }
+}
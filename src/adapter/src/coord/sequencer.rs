@@ -40,9 +40,7 @@ use crate::catalog::Catalog;
 use crate::command::{Command, ExecuteResponse, Response};
 use crate::coord::appends::{DeferredOp, DeferredPlan};
 use crate::coord::validity::PlanValidity;
-use crate::coord::{
-    Coordinator, DeferredPlanStatement, Message, PlanStatement, TargetCluster, catalog_serving,
-};
+use crate::coord::{Coordinator, Message, PlanStatement, TargetCluster, catalog_serving};
 use crate::error::AdapterError;
 use crate::notice::AdapterNotice;
 use crate::session::{EndTransactionAction, Session, TransactionOps, TransactionStatus, WriteOp};
@@ -176,6 +174,41 @@ impl Coordinator {
                 return ctx.retire(Err(e.into()));
             }
 
+            // Queries resume clusters that were suspended by their AUTO SUSPEND AFTER option, and
+            // wait until the cluster is hydrated.
+            if let Some(cluster_id) = target_cluster_id {
+                if matches!(
+                    plan,
+                    Plan::Select(_) | Plan::Subscribe(_) | Plan::CopyTo(_) | Plan::ShowColumns(_)
+                ) {
+                    let wait_future =
+                        return_if_err!(self.resume_cluster_for_query(cluster_id).await, ctx);
+                    if let Some(wait_future) = wait_future {
+                        let conn_id = ctx.session().conn_id();
+                        tracing::debug!(%conn_id, %cluster_id, "deferring plan for cluster resume");
+
+                        let role_metadata = ctx.session().role_metadata().clone();
+                        let validity = PlanValidity::new(
+                            self.catalog.transient_revision(),
+                            resolved_ids.items().copied().collect(),
+                            Some(cluster_id),
+                            None,
+                            role_metadata,
+                        );
+                        let deferred_plan = DeferredPlan {
+                            ctx,
+                            plan,
+                            validity,
+                            requires_locks: BTreeSet::default(),
+                        };
+                        let acquire_future = wait_future.map(|()| None);
+
+                        self.defer_op(acquire_future, DeferredOp::Plan(deferred_plan));
+                        return;
+                    }
+                }
+            }
+
             match plan {
                 Plan::CreateSource(plan) => {
                     let id_ts = self.get_catalog_write_ts().await;
@@ -353,10 +386,7 @@ impl Coordinator {
                                 "connections should have at most one lock guard"
                             );
                         } else {
-                            self.serialized_ddl.push_back(DeferredPlanStatement {
-                                ctx,
-                                ps: PlanStatement::Plan { plan, resolved_ids },
-                            });
+                            self.defer_ddl(ctx, PlanStatement::Plan { plan, resolved_ids });
                             return;
                         }
                     }
@@ -420,6 +450,10 @@ impl Coordinator {
                     self.sequence_explain_pushdown(ctx, plan, target_cluster)
                         .await;
                 }
+                Plan::ExplainAnalyze(plan) => {
+                    self.sequence_explain_analyze(ctx, plan, target_cluster)
+                        .await;
+                }
                 Plan::ExplainSinkSchema(plan) => {
                     let result = self.sequence_explain_schema(plan);
                     ctx.retire(result);
@@ -88,7 +88,7 @@ use mz_storage_client::controller::{CollectionDescription, DataSource, ExportDes
 use mz_storage_types::AlterCompatible;
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::controller::StorageError;
-use mz_storage_types::stats::RelationPartStats;
+use mz_storage_types::stats::{PartFilterResult, RelationPartStats};
 use mz_transform::EmptyStatisticsOracle;
 use mz_transform::dataflow::DataflowMetainfo;
 use mz_transform::notice::{OptimizerNoticeApi, OptimizerNoticeKind, RawOptimizerNotice};
@@ -125,6 +125,7 @@ mod create_continual_task;
 mod create_index;
 mod create_materialized_view;
 mod create_view;
+mod explain_analyze;
 mod explain_timestamp;
 mod peek;
 mod secret;
@@ -316,7 +317,9 @@ impl Coordinator {
                         GenericSourceConnection::Postgres(_)
                         | GenericSourceConnection::MySql(_)
                         | GenericSourceConnection::SqlServer(_)
+                        | GenericSourceConnection::MongoDb(_)
                         | GenericSourceConnection::Kafka(_)
+                        | GenericSourceConnection::Kinesis(_)
                         | GenericSourceConnection::LoadGenerator(_) => {
                             if let Some(cluster) = self.catalog().try_get_cluster(cluster_id) {
                                 let enable_multi_replica_sources = ENABLE_MULTI_REPLICA_SOURCES
@@ -2664,6 +2667,7 @@ impl Coordinator {
                 let mut total_parts = 0;
                 let mut selected_bytes = 0;
                 let mut selected_parts = 0;
+                let mut bloom_filtered_parts = 0;
                 for SnapshotPartStats {
                     encoded_size_bytes: bytes,
                     stats,
@@ -2672,8 +2676,8 @@ impl Coordinator {
                     let bytes = u64::cast_from(*bytes);
                     total_bytes += bytes;
                     total_parts += 1u64;
-                    let selected = match stats {
-                        None => true,
+                    let result = match stats {
+                        None => PartFilterResult::MayMatch,
                         Some(stats) => {
                             let stats = stats.decode();
                             let stats = RelationPartStats::new(
@@ -2682,13 +2686,19 @@ impl Coordinator {
                                 &relation_desc,
                                 &stats,
                             );
-                            stats.may_match_mfp(mz_now.clone(), &mfp)
+                            stats.filter_mfp(mz_now.clone(), &mfp)
                         }
                     };
 
-                    if selected {
-                        selected_bytes += bytes;
-                        selected_parts += 1u64;
+                    match result {
+                        PartFilterResult::MayMatch => {
+                            selected_bytes += bytes;
+                            selected_parts += 1u64;
+                        }
+                        PartFilterResult::FilteredByBloomFilters => {
+                            bloom_filtered_parts += 1u64;
+                        }
+                        PartFilterResult::FilteredByStats => {}
                     }
                 }
                 Ok(Row::pack_slice(&[
@@ -2697,6 +2707,7 @@ impl Coordinator {
                     selected_bytes.into(),
                     total_parts.into(),
                     selected_parts.into(),
+                    bloom_filtered_parts.into(),
                 ]))
             });
         }
@@ -3445,6 +3456,13 @@ impl Coordinator {
                 let session_var = session.vars().inspect(variable.name())?;
                 // Return early if it's not visible.
                 session_var.visible(session.user(), catalog.system_vars())?;
+                // Role defaults are how regular users get their workload
+                // class, so only superusers may change them.
+                if session_var.name() == vars::WORKLOAD_CLASS.name()
+                    && !session.user().is_superuser()
+                {
+                    return Err(VarError::RequiresSuperuser(vars::WORKLOAD_CLASS.name()).into());
+                }
 
                 // Emit a warning when deprecated variables are used.
                 // TODO(database-issues#8069) remove this after sufficient time has passed
@@ -4951,7 +4969,12 @@ impl Coordinator {
         // It is possible Message::DeferredStatementReady was sent but then a session cancellation
         // was processed, removing the single element from deferred_statements, so it is expected
         // that this is sometimes empty.
-        let Some(DeferredPlanStatement { ctx, ps }) = self.serialized_ddl.pop_front() else {
+        let Some(DeferredPlanStatement {
+            ctx,
+            ps,
+            deadline: _,
+        }) = self.serialized_ddl.pop_front()
+        else {
             return;
         };
         match ps {
@@ -30,7 +30,7 @@ use mz_sql::ast::{Ident, QualifiedReplica};
 use mz_sql::catalog::{CatalogCluster, CatalogClusterReplica, ObjectType};
 use mz_sql::plan::{
     self, AlterClusterPlanStrategy, AlterClusterRenamePlan, AlterClusterReplicaRenamePlan,
-    AlterClusterSwapPlan, AlterOptionParameter, AlterSetClusterPlan,
+    AlterClusterSwapPlan, AlterOptionParameter, AlterSetClusterPlan, ClusterSchedule,
     ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan, CreateClusterPlan,
     CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant, PlanClusterOption,
 };
@@ -41,6 +41,7 @@ use tracing::{Instrument, Span, debug};
 
 use super::return_if_err;
 use crate::catalog::{self, Op, ReplicaCreateDropReason};
+use crate::coord::cluster_scheduling::size_ladder;
 use crate::coord::{
     AlterCluster, AlterClusterFinalize, AlterClusterWaitForHydrated, ClusterStage, Coordinator,
     Message, PlanValidity, StageResult, Staged,
@@ -183,6 +184,7 @@ impl Coordinator {
                     disk,
                     optimizer_feature_overrides: Default::default(),
                     schedule: Default::default(),
+                    auto_suspend_after: None,
                 });
             }
         }
@@ -196,6 +198,7 @@ impl Coordinator {
                 disk,
                 optimizer_feature_overrides: _,
                 schedule,
+                auto_suspend_after,
             }) => {
                 match &options.size {
                     Set(s) => size.clone_from(s),
@@ -234,11 +237,25 @@ impl Coordinator {
                 }
                 match &options.schedule {
                     Set(new_schedule) => {
+                        self.ensure_valid_cluster_schedule(
+                            Some(session.role_metadata().current_role),
+                            new_schedule,
+                        )?;
                         *schedule = new_schedule.clone();
                     }
                     Reset => *schedule = Default::default(),
                     Unchanged => {}
                 }
+                match &options.auto_suspend_after {
+                    Set(asa) => *auto_suspend_after = asa.0,
+                    Reset => *auto_suspend_after = None,
+                    Unchanged => {}
+                }
+                if auto_suspend_after.is_some() && !matches!(schedule, ClusterSchedule::Manual) {
+                    coord_bail!(
+                        "AUTO SUSPEND AFTER cannot be combined with a SCHEDULE other than MANUAL"
+                    );
+                }
                 if !matches!(options.replicas, Unchanged) {
                     coord_bail!("Cannot change REPLICAS of managed clusters");
                 }
@@ -259,6 +276,9 @@ impl Coordinator {
                 if !matches!(options.replication_factor, Unchanged) {
                     coord_bail!("Cannot change REPLICATION FACTOR of unmanaged clusters");
                 }
+                if !matches!(options.auto_suspend_after, Unchanged) {
+                    coord_bail!("Cannot change AUTO SUSPEND AFTER of unmanaged clusters");
+                }
             }
         }
 
@@ -368,14 +388,40 @@ impl Coordinator {
     async fn finalize_alter_cluster_stage(
         &mut self,
         session: &Session,
-        AlterClusterPlan {
-            id: cluster_id,
-            name: cluster_name,
-            ..
-        }: AlterClusterPlan,
+        AlterClusterPlan { id: cluster_id, .. }: AlterClusterPlan,
         new_config: ClusterVariantManaged,
     ) -> Result<StageResult<Box<ClusterStage>>, AdapterError> {
+        self.finalize_cluster_reconfiguration(
+            Some(session),
+            cluster_id,
+            new_config,
+            ReplicaCreateDropReason::Manual,
+        )
+        .await?;
+        // Remove the cluster being altered from the ConnMeta
+        // pending_cluster_alters BTreeSet
+        self.active_conns
+            .get_mut(session.conn_id())
+            .expect("There must be an active connection")
+            .pending_cluster_alters
+            .remove(&cluster_id);
+
+        Ok(StageResult::Response(ExecuteResponse::AlteredObject(
+            ObjectType::Cluster,
+        )))
+    }
+
+    /// Finishes a zero-downtime reconfiguration of a managed cluster: drops the old replicas,
+    /// promotes the pending replicas, and applies `new_config` to the cluster.
+    pub(crate) async fn finalize_cluster_reconfiguration(
+        &mut self,
+        session: Option<&Session>,
+        cluster_id: ClusterId,
+        new_config: ClusterVariantManaged,
+        reason: ReplicaCreateDropReason,
+    ) -> Result<(), AdapterError> {
         let cluster = self.catalog.get_cluster(cluster_id);
+        let cluster_name = cluster.name.clone();
         let workload_class = cluster.config.workload_class.clone();
         let mut ops = vec![];
 
@@ -388,7 +434,7 @@ impl Coordinator {
                     Some(catalog::DropObjectInfo::ClusterReplica((
                         cluster_id.clone(),
                         r.replica_id,
-                        ReplicaCreateDropReason::Manual,
+                        reason.clone(),
                     )))
                 } else {
                     None
@@ -477,21 +523,12 @@ impl Coordinator {
                 workload_class: workload_class.clone(),
             },
         });
-        self.catalog_transact(Some(session), ops).await?;
-        // Remove the cluster being altered from the ConnMeta
-        // pending_cluster_alters BTreeSet
-        self.active_conns
-            .get_mut(session.conn_id())
-            .expect("There must be an active connection")
-            .pending_cluster_alters
-            .remove(&cluster_id);
+        self.catalog_transact(session, ops).await?;
 
         self.controller
             .update_cluster_workload_class(cluster_id, workload_class)?;
 
-        Ok(StageResult::Response(ExecuteResponse::AlteredObject(
-            ObjectType::Cluster,
-        )))
+        Ok(())
     }
 
     async fn check_if_pending_replicas_hydrated_stage(
@@ -633,6 +670,7 @@ impl Coordinator {
                     disk: plan.disk,
                     optimizer_feature_overrides: plan.optimizer_feature_overrides.clone(),
                     schedule: plan.schedule.clone(),
+                    auto_suspend_after: plan.auto_suspend_after,
                 })
             }
             CreateClusterVariant::Unmanaged(_) => ClusterVariant::Unmanaged,
@@ -672,7 +710,8 @@ impl Coordinator {
             size,
             disk,
             optimizer_feature_overrides: _,
-            schedule: _,
+            schedule,
+            auto_suspend_after: _,
         }: CreateClusterManagedPlan,
         cluster_id: ClusterId,
         mut ops: Vec<catalog::Op>,
@@ -688,6 +727,7 @@ impl Coordinator {
                 .get_role_allowed_cluster_sizes(&Some(role_id)),
             &size,
         )?;
+        self.ensure_valid_cluster_schedule(Some(role_id), &schedule)?;
 
         // Eagerly validate the `max_replicas_per_cluster` limit.
         // `catalog_transact` will do this validation too, but allocating
@@ -781,6 +821,28 @@ impl Coordinator {
         Ok(())
     }
 
+    /// Validates the MIN SIZE and MAX SIZE of a `SCHEDULE = ON UTILIZATION` cluster schedule.
+    fn ensure_valid_cluster_schedule(
+        &self,
+        role_id: Option<RoleId>,
+        schedule: &ClusterSchedule,
+    ) -> Result<(), AdapterError> {
+        let ClusterSchedule::Utilization { min_size, max_size } = schedule else {
+            return Ok(());
+        };
+        let allowed_sizes = self.catalog().get_role_allowed_cluster_sizes(&role_id);
+        self.catalog
+            .ensure_valid_replica_size(&allowed_sizes, min_size)?;
+        self.catalog
+            .ensure_valid_replica_size(&allowed_sizes, max_size)?;
+        let ladder = size_ladder(self.catalog().cluster_replica_sizes());
+        let position = |size: &String| ladder.iter().position(|s| s == size);
+        if position(min_size) > position(max_size) {
+            coord_bail!("MIN SIZE '{min_size}' must not be larger than MAX SIZE '{max_size}'");
+        }
+        Ok(())
+    }
+
     fn ensure_valid_azs<'a, I: IntoIterator<Item = &'a String>>(
         &self,
         azs: I,
@@ -1137,6 +1199,7 @@ impl Coordinator {
             disk,
             optimizer_feature_overrides: _,
             schedule: _,
+            auto_suspend_after: _,
         }) = &cluster.config.variant
         else {
             panic!("expected existing managed cluster config");
@@ -1149,6 +1212,7 @@ impl Coordinator {
             disk: new_disk,
             optimizer_feature_overrides: _,
             schedule: _,
+            auto_suspend_after: _,
         }) = &new_config.variant
         else {
             panic!("expected new managed cluster config");
@@ -1331,6 +1395,7 @@ impl Coordinator {
             disk: new_disk,
             optimizer_feature_overrides: _,
             schedule: _,
+            auto_suspend_after: _,
         }) = &mut new_config.variant
         else {
             panic!("expected new managed cluster config");
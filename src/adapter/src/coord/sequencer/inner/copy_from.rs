@@ -16,6 +16,7 @@ use mz_pgcopy::CopyFormatParams;
 use mz_repr::{CatalogItemId, Datum, RowArena};
 use mz_sql::plan::{self, CopyFromFilter, CopyFromSource, HirScalarExpr};
 use mz_sql::session::metadata::SessionMetadata;
+use mz_sql::session::vars::UNSAFE_ENABLE_COPY_FROM_LOCAL_FILE;
 use mz_storage_client::client::TableData;
 use mz_storage_types::oneshot_sources::{ContentShape, OneshotIngestionRequest};
 use smallvec::SmallVec;
@@ -83,6 +84,8 @@ impl Coordinator {
                 mz_storage_types::oneshot_sources::ContentFormat::Csv(csv.to_owned())
             }
             CopyFormatParams::Parquet => mz_storage_types::oneshot_sources::ContentFormat::Parquet,
+            CopyFormatParams::Json => mz_storage_types::oneshot_sources::ContentFormat::Json,
+            CopyFormatParams::Avro => mz_storage_types::oneshot_sources::ContentFormat::Avro,
             CopyFormatParams::Text(_) | CopyFormatParams::Binary => {
                 mz_ore::soft_panic_or_log!("unsupported formats should be rejected in planning");
                 ctx.retire(Err(AdapterError::Unsupported("COPY FROM URL format")));
@@ -93,12 +96,26 @@ impl Coordinator {
         let source = match source {
             CopyFromSource::Url(from_expr) => {
                 let url = return_if_err!(eval_uri(from_expr), ctx);
-                // TODO(cf2): Structured errors.
-                let result = Url::parse(&url)
-                    .map_err(|err| AdapterError::Unstructured(anyhow::anyhow!("{err}")));
-                let url = return_if_err!(result, ctx);
 
-                mz_storage_types::oneshot_sources::ContentSource::Http { url }
+                if let Some(path) = url.strip_prefix("file://") {
+                    // Reading from the filesystem of the cluster is only intended for testing.
+                    let result = UNSAFE_ENABLE_COPY_FROM_LOCAL_FILE
+                        .require(self.catalog().system_config())
+                        .map_err(AdapterError::from);
+                    return_if_err!(result, ctx);
+
+                    // Note: We intentionally don't parse the path as a `Url` since glob
+                    // characters like `?` have special meaning in URLs.
+                    let path = path.to_string();
+                    mz_storage_types::oneshot_sources::ContentSource::LocalFile { path }
+                } else {
+                    // TODO(cf2): Structured errors.
+                    let result = Url::parse(&url)
+                        .map_err(|err| AdapterError::Unstructured(anyhow::anyhow!("{err}")));
+                    let url = return_if_err!(result, ctx);
+
+                    mz_storage_types::oneshot_sources::ContentSource::Http { url }
+                }
             }
             CopyFromSource::AwsS3 {
                 uri,
@@ -0,0 +1,451 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! `EXPLAIN ANALYZE` support.
+//!
+//! An `EXPLAIN ANALYZE` measures a dataflow through the introspection sources
+//! of its cluster, and annotates the physical plan of the dataflow with the
+//! measurements of each LIR node. Indexes and materialized views are measured
+//! in place. A SELECT is first installed as a transient dataflow, which is
+//! dropped once it has hydrated and has been measured.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::anyhow;
+use futures::future;
+use itertools::Itertools;
+use mz_compute_types::plan::LirId;
+use mz_ore::instrument;
+use mz_repr::explain::{ExprHumanizerExt, Measurements, TransientItem};
+use mz_repr::optimize::{OptimizerFeatures, OverrideFrom};
+use mz_repr::{Datum, GlobalId, Row, RowIterator};
+use mz_sql::ast::ExplainAnalyzeProperty;
+use mz_sql::plan::{self, Explainee, ExplaineeStatement, Params, Plan};
+use mz_sql::session::metadata::SessionMetadata;
+use mz_sql_parser::ast::display::escaped_string_literal;
+use tokio::sync::oneshot;
+use tracing::{Instrument, Span};
+
+use crate::command::ExecuteResponse;
+use crate::coord::peek::{ExplainAnalyzeDataflow, PeekDataflowPlan, PeekPlan, PeekResponseUnary};
+use crate::coord::sequencer::inner::return_if_err;
+use crate::coord::{
+    Coordinator, ExecuteContext, ExplainAnalyzeContext, ExplainAnalyzeMeasureContext,
+    ExplainContext, PeekStage, PeekStageExplainAnalyzeDataflow, PeekStageExplainAnalyzeMeasure,
+    PeekStageExplainAnalyzeRender, PlanValidity, StageResult, TargetCluster, WatchSetResponse,
+};
+use crate::error::AdapterError;
+use crate::explain::explain_analyzed_dataflow;
+use crate::session::{Session, TransactionStatus};
+
+impl Coordinator {
+    #[instrument]
+    pub(crate) async fn sequence_explain_analyze(
+        &mut self,
+        ctx: ExecuteContext,
+        plan: plan::ExplainAnalyzePlan,
+        target_cluster: TargetCluster,
+    ) {
+        let plan::ExplainAnalyzePlan {
+            properties,
+            skew,
+            format,
+            config,
+            explainee,
+        } = plan;
+        let explain_ctx = ExplainAnalyzeContext {
+            properties,
+            skew,
+            format,
+            config,
+        };
+
+        // The measurements are read in a peek of their own, which must not be
+        // bound to the timestamp and the cluster of an ongoing transaction.
+        if !matches!(ctx.session().transaction(), TransactionStatus::Started(_)) {
+            ctx.retire(Err(AdapterError::OperationProhibitsTransaction(
+                "EXPLAIN ANALYZE".into(),
+            )));
+            return;
+        }
+
+        match explainee {
+            Explainee::Statement(ExplaineeStatement::Select {
+                broken: false,
+                plan,
+                desc: _,
+            }) => {
+                let stage = return_if_err!(
+                    self.peek_validate(
+                        ctx.session(),
+                        plan,
+                        target_cluster,
+                        None,
+                        ExplainContext::Analyze(explain_ctx),
+                        Some(ctx.session().vars().max_query_result_size()),
+                    ),
+                    ctx
+                );
+                self.sequence_staged(ctx, Span::current(), stage).await;
+            }
+            Explainee::Index(item_id) | Explainee::MaterializedView(item_id) => {
+                let entry = self.catalog().get_entry(&item_id);
+                let Some(cluster_id) = entry.cluster_id() else {
+                    ctx.retire(Err(AdapterError::Internal(format!(
+                        "{item_id} is not installed on a cluster"
+                    ))));
+                    return;
+                };
+                let Some(dataflow) = self
+                    .catalog()
+                    .try_get_physical_plan(&entry.latest_global_id())
+                else {
+                    ctx.retire(Err(AdapterError::Internal(format!(
+                        "physical plan for {item_id} is not available"
+                    ))));
+                    return;
+                };
+                let measure_ctx = ExplainAnalyzeMeasureContext {
+                    explain_ctx,
+                    cluster_id,
+                    dataflow: dataflow.clone(),
+                    dataflow_guard: None,
+                };
+                let validity = PlanValidity::new(
+                    self.catalog().transient_revision(),
+                    BTreeSet::from([item_id]),
+                    Some(cluster_id),
+                    None,
+                    ctx.session().role_metadata().clone(),
+                );
+                let stage = PeekStage::ExplainAnalyzeMeasure(PeekStageExplainAnalyzeMeasure {
+                    validity,
+                    measure_ctx,
+                });
+                self.sequence_staged(ctx, Span::current(), stage).await;
+            }
+            _ => {
+                ctx.retire(Err(AdapterError::Unsupported(
+                    "EXPLAIN ANALYZE queries for this explainee type",
+                )));
+            }
+        }
+    }
+
+    /// Installs the dataflow of an analyzed SELECT, and waits for it to
+    /// hydrate at the timestamp of the SELECT.
+    #[instrument]
+    pub(crate) async fn peek_explain_analyze_dataflow(
+        &mut self,
+        session: &Session,
+        PeekStageExplainAnalyzeDataflow {
+            validity,
+            determination,
+            cluster_id,
+            global_lir_plan,
+            explain_ctx,
+        }: PeekStageExplainAnalyzeDataflow,
+    ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
+        let (peek_plan, _, _) = global_lir_plan.unapply();
+        // The optimizer never produces a fast path plan for an analyzed
+        // SELECT, as there would be no dataflow to measure.
+        let PeekPlan::SlowPath(PeekDataflowPlan {
+            desc: dataflow,
+            id: index_id,
+            ..
+        }) = peek_plan
+        else {
+            return Err(AdapterError::Internal(
+                "EXPLAIN ANALYZE expects a dataflow for the SELECT".into(),
+            ));
+        };
+
+        self.ship_dataflow(dataflow.clone(), cluster_id, None).await;
+        let dataflow_guard = ExplainAnalyzeDataflow {
+            cluster_id,
+            index_id,
+            internal_cmd_tx: self.internal_cmd_tx.clone(),
+        };
+
+        let (tx, rx) = oneshot::channel();
+        self.install_compute_watch_set(
+            session.conn_id().clone(),
+            BTreeSet::from([index_id]),
+            determination.timestamp_context.timestamp_or_default(),
+            WatchSetResponse::ExplainAnalyzeReady(tx),
+        );
+
+        // Canceling the statement does not stop this task, so it watches for
+        // cancellation itself, lest it holds on to the dataflow.
+        let mut cancel_rx = self
+            .staged_cancellation
+            .get(session.conn_id())
+            .map(|(_tx, rx)| rx.clone());
+        let span = Span::current();
+        Ok(StageResult::Handle(mz_ore::task::spawn(
+            || "explain analyze hydration",
+            async move {
+                let canceled = async {
+                    match &mut cancel_rx {
+                        Some(rx) => {
+                            let _ = rx.wait_for(|v| *v).await;
+                        }
+                        None => future::pending().await,
+                    }
+                };
+                tokio::select! {
+                    res = rx => res?,
+                    () = canceled => return Err(AdapterError::Canceled),
+                }
+                let measure_ctx = ExplainAnalyzeMeasureContext {
+                    explain_ctx,
+                    cluster_id,
+                    dataflow,
+                    dataflow_guard: Some(dataflow_guard),
+                };
+                let stage = PeekStage::ExplainAnalyzeMeasure(PeekStageExplainAnalyzeMeasure {
+                    validity,
+                    measure_ctx,
+                });
+                Ok(Box::new(stage))
+            }
+            .instrument(span),
+        )))
+    }
+
+    /// Peeks the introspection sources of the cluster that runs the analyzed
+    /// dataflow for its measurements.
+    #[instrument]
+    pub(crate) fn peek_explain_analyze_measure(
+        &mut self,
+        session: &mut Session,
+        PeekStageExplainAnalyzeMeasure {
+            validity: _,
+            measure_ctx,
+        }: PeekStageExplainAnalyzeMeasure,
+    ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
+        let global_ids = measure_ctx
+            .dataflow
+            .objects_to_build
+            .iter()
+            .map(|build| build.id);
+        let sql = measurement_query(global_ids);
+
+        let catalog = self.catalog().for_session(session);
+        let parsed = mz_sql::parse::parse(&sql)?.into_element();
+        let (stmt, resolved_ids) = mz_sql::names::resolve(&catalog, parsed.ast)?;
+        let plan = mz_sql::plan::plan(None, &catalog, stmt, &Params::empty(), &resolved_ids)?;
+        let Plan::Select(plan) = plan else {
+            return Err(AdapterError::Internal(format!(
+                "unexpected plan for EXPLAIN ANALYZE measurements: {plan:?}"
+            )));
+        };
+
+        // The analyzed SELECT no longer needs its timestamp and read holds, as
+        // its dataflow has hydrated. The measurements are read at a timestamp
+        // of their own instead.
+        session.take_transaction_timestamp_context();
+        self.txn_read_holds.remove(session.conn_id());
+
+        // The measurements are read from the replicas of the cluster that
+        // runs the dataflow, regardless of the active cluster.
+        let cluster_id = measure_ctx.cluster_id;
+        let stage = self.peek_validate(
+            session,
+            plan,
+            TargetCluster::Transaction(cluster_id),
+            None,
+            ExplainContext::AnalyzeMeasure(Box::new(measure_ctx)),
+            Some(session.vars().max_query_result_size()),
+        )?;
+        Ok(StageResult::Immediate(Box::new(stage)))
+    }
+
+    /// Collects the measurements returned by the peek issued by
+    /// [`Coordinator::peek_explain_analyze_measure`].
+    pub(crate) fn peek_explain_analyze_collect(
+        &self,
+        validity: PlanValidity,
+        measure_ctx: ExplainAnalyzeMeasureContext,
+        resp: ExecuteResponse,
+    ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
+        let span = Span::current();
+        Ok(StageResult::Handle(mz_ore::task::spawn(
+            || "explain analyze measurements",
+            async move {
+                let mut rows = match resp {
+                    ExecuteResponse::SendingRows { future, .. } => match future.await {
+                        PeekResponseUnary::Rows(rows) => rows,
+                        PeekResponseUnary::Canceled => return Err(AdapterError::Canceled),
+                        PeekResponseUnary::Error(e) => {
+                            return Err(AdapterError::Unstructured(anyhow!(e)));
+                        }
+                    },
+                    ExecuteResponse::SendingRowsImmediate { rows } => rows,
+                    resp => {
+                        return Err(AdapterError::Internal(format!(
+                            "unexpected response for EXPLAIN ANALYZE measurements: {resp:?}"
+                        )));
+                    }
+                };
+                let measurements = collect_measurements(&measure_ctx.explain_ctx, &mut *rows);
+                let stage = PeekStage::ExplainAnalyzeRender(PeekStageExplainAnalyzeRender {
+                    validity,
+                    measure_ctx,
+                    measurements,
+                });
+                Ok(Box::new(stage))
+            }
+            .instrument(span),
+        )))
+    }
+
+    /// Renders the physical plan of the analyzed dataflow, annotated with its
+    /// measurements.
+    #[instrument]
+    pub(crate) fn peek_explain_analyze_render(
+        &self,
+        session: &Session,
+        PeekStageExplainAnalyzeRender {
+            validity: _,
+            measure_ctx,
+            measurements,
+        }: PeekStageExplainAnalyzeRender,
+    ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
+        let ExplainAnalyzeMeasureContext {
+            explain_ctx,
+            cluster_id,
+            dataflow,
+            // Dropped once the plan is rendered, which drops the dataflow of
+            // an analyzed SELECT.
+            dataflow_guard: _dataflow_guard,
+        } = measure_ctx;
+
+        let session_catalog = self.catalog().for_session(session);
+        // The dataflow of an analyzed SELECT builds a transient view.
+        let transient_items = dataflow
+            .objects_to_build
+            .iter()
+            .filter(|build| build.id.is_transient())
+            .map(|build| {
+                let item = TransientItem::new(Some(vec![GlobalId::Explain.to_string()]), None);
+                (build.id, item)
+            })
+            .collect();
+        let expr_humanizer = ExprHumanizerExt::new(transient_items, &session_catalog);
+
+        let target_cluster = self.catalog().get_cluster(cluster_id);
+        let features = OptimizerFeatures::from(self.catalog().system_config())
+            .override_from(&target_cluster.config.features());
+
+        let explanation = explain_analyzed_dataflow(
+            dataflow,
+            explain_ctx.format,
+            &explain_ctx.config,
+            &features,
+            &expr_humanizer,
+            Some(target_cluster.name.as_str()),
+            &measurements,
+        )?;
+        let row = Row::pack_slice(&[Datum::from(explanation.as_str())]);
+        Ok(StageResult::Response(Self::send_immediate_rows(row)))
+    }
+}
+
+/// Returns the introspection query that measures the dataflow that builds the
+/// objects with the given `global_ids`.
+///
+/// The query returns a row for each LIR node of the dataflow with its LIR ID,
+/// the size of and the number of records in its arrangements, the skew of its
+/// memory across workers, the time spent in its operators, and the skew of
+/// that time across workers. The skew is the ratio of the maximum to the
+/// average across workers.
+fn measurement_query(global_ids: impl Iterator<Item = GlobalId>) -> String {
+    let global_ids = global_ids
+        .map(|id| escaped_string_literal(&id.to_string()).to_string())
+        .join(", ");
+    format!(
+        "WITH
+lir AS (
+    SELECT lir_id, operator_id_start, operator_id_end
+    FROM mz_introspection.mz_lir_mapping
+    WHERE global_id IN ({global_ids})
+),
+memory_per_worker AS (
+    SELECT lir.lir_id, m.worker_id, sum(m.size) AS size, sum(m.records) AS records
+    FROM lir JOIN mz_introspection.mz_arrangement_sizes_per_worker AS m
+        ON lir.operator_id_start <= m.operator_id AND m.operator_id < lir.operator_id_end
+    GROUP BY lir.lir_id, m.worker_id
+),
+memory AS (
+    SELECT lir_id, sum(size) AS size, sum(records) AS records,
+        max(size) / nullif(avg(size), 0) AS skew
+    FROM memory_per_worker
+    GROUP BY lir_id
+),
+cpu_per_worker AS (
+    SELECT lir.lir_id, m.worker_id, sum(m.elapsed_ns) AS elapsed_ns
+    FROM lir JOIN mz_introspection.mz_scheduling_elapsed_per_worker AS m
+        ON lir.operator_id_start <= m.id AND m.id < lir.operator_id_end
+    GROUP BY lir.lir_id, m.worker_id
+),
+cpu AS (
+    SELECT lir_id, sum(elapsed_ns) AS elapsed_ns,
+        max(elapsed_ns) / nullif(avg(elapsed_ns), 0) AS skew
+    FROM cpu_per_worker
+    GROUP BY lir_id
+)
+SELECT
+    lir.lir_id,
+    memory.size::uint8,
+    memory.records::uint8,
+    memory.skew::float8,
+    cpu.elapsed_ns::uint8,
+    cpu.skew::float8
+FROM lir
+LEFT JOIN memory ON lir.lir_id = memory.lir_id
+LEFT JOIN cpu ON lir.lir_id = cpu.lir_id"
+    )
+}
+
+/// Collects the rows returned by the [`measurement_query`] into the
+/// measurements requested by `explain_ctx`, by LIR ID.
+fn collect_measurements(
+    explain_ctx: &ExplainAnalyzeContext,
+    rows: &mut dyn RowIterator,
+) -> BTreeMap<LirId, Measurements> {
+    let memory = explain_ctx
+        .properties
+        .contains(&ExplainAnalyzeProperty::Memory);
+    let cpu = explain_ctx
+        .properties
+        .contains(&ExplainAnalyzeProperty::Cpu);
+    let skew = explain_ctx.skew;
+
+    let uint8 = |datum: Datum| (!datum.is_null()).then(|| datum.unwrap_uint64());
+    let float8 = |datum: Datum| (!datum.is_null()).then(|| datum.unwrap_float64());
+
+    let mut measurements = BTreeMap::new();
+    while let Some(row) = rows.next() {
+        let mut datums = row.iter();
+        let mut next = || datums.next().expect("measurement query returns 6 columns");
+        let lir_id = LirId::from(next().unwrap_uint64());
+        let (memory_bytes, records, memory_skew) = (uint8(next()), uint8(next()), float8(next()));
+        let (elapsed_ns, cpu_skew) = (uint8(next()), float8(next()));
+        let m = Measurements {
+            memory_bytes: memory_bytes.filter(|_| memory),
+            records: records.filter(|_| memory),
+            memory_skew: memory_skew.filter(|_| memory && skew),
+            elapsed_ns: elapsed_ns.filter(|_| cpu),
+            cpu_skew: cpu_skew.filter(|_| cpu && skew),
+        };
+        measurements.insert(lir_id, m);
+    }
+    measurements
+}
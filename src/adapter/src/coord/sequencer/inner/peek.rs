@@ -29,6 +29,7 @@ use mz_catalog::memory::objects::CatalogItem;
 use mz_sql::plan::QueryWhen;
 use mz_sql::plan::{self, HirScalarExpr};
 use mz_sql::session::metadata::SessionMetadata;
+use mz_sql::session::vars::WorkloadClass;
 use mz_transform::EmptyStatisticsOracle;
 use tokio::sync::oneshot;
 use tracing::warn;
@@ -45,9 +46,10 @@ use crate::coord::timestamp_selection::{
 };
 use crate::coord::{
     Coordinator, CopyToContext, ExecuteContext, ExplainContext, ExplainPlanContext, Message,
-    PeekStage, PeekStageCopyTo, PeekStageExplainPlan, PeekStageExplainPushdown, PeekStageFinish,
-    PeekStageLinearizeTimestamp, PeekStageOptimize, PeekStageRealTimeRecency,
-    PeekStageTimestampReadHold, PlanValidity, StageResult, Staged, TargetCluster, WatchSetResponse,
+    PeekStage, PeekStageCopyTo, PeekStageExplainAnalyzeDataflow, PeekStageExplainPlan,
+    PeekStageExplainPushdown, PeekStageFinish, PeekStageLinearizeTimestamp, PeekStageOptimize,
+    PeekStageRealTimeRecency, PeekStageTimestampReadHold, PlanValidity, StageResult, Staged,
+    TargetCluster, WatchSetResponse,
 };
 use crate::error::AdapterError;
 use crate::explain::insights::PlanInsightsContext;
@@ -70,6 +72,9 @@ impl Staged for PeekStage {
             PeekStage::Finish(stage) => &mut stage.validity,
             PeekStage::ExplainPlan(stage) => &mut stage.validity,
             PeekStage::ExplainPushdown(stage) => &mut stage.validity,
+            PeekStage::ExplainAnalyzeDataflow(stage) => &mut stage.validity,
+            PeekStage::ExplainAnalyzeMeasure(stage) => &mut stage.validity,
+            PeekStage::ExplainAnalyzeRender(stage) => &mut stage.validity,
             PeekStage::CopyToPreflight(stage) => &mut stage.validity,
             PeekStage::CopyToDataflow(stage) => &mut stage.validity,
         }
@@ -96,6 +101,17 @@ impl Staged for PeekStage {
             PeekStage::ExplainPushdown(stage) => {
                 coord.peek_explain_pushdown(ctx.session(), stage).await
             }
+            PeekStage::ExplainAnalyzeDataflow(stage) => {
+                coord
+                    .peek_explain_analyze_dataflow(ctx.session(), stage)
+                    .await
+            }
+            PeekStage::ExplainAnalyzeMeasure(stage) => {
+                coord.peek_explain_analyze_measure(ctx.session_mut(), stage)
+            }
+            PeekStage::ExplainAnalyzeRender(stage) => {
+                coord.peek_explain_analyze_render(ctx.session(), stage)
+            }
             PeekStage::CopyToPreflight(stage) => coord.peek_copy_to_preflight(stage).await,
             PeekStage::CopyToDataflow(stage) => coord.peek_copy_to_dataflow(ctx, stage).await,
         }
@@ -651,6 +667,8 @@ impl Coordinator {
                                         global_lir_plan,
                                         optimization_finished_at,
                                         insights_ctx,
+                                        reservation: None,
+                                        explain_analyze: None,
                                     })
                                 }
                                 ExplainContext::None => PeekStage::Finish(PeekStageFinish {
@@ -667,7 +685,39 @@ impl Coordinator {
                                     global_lir_plan,
                                     optimization_finished_at,
                                     insights_ctx,
+                                    reservation: None,
+                                    explain_analyze: None,
                                 }),
+                                ExplainContext::AnalyzeMeasure(measure_ctx) => {
+                                    PeekStage::Finish(PeekStageFinish {
+                                        validity,
+                                        plan,
+                                        max_query_result_size,
+                                        id_bundle,
+                                        target_replica,
+                                        source_ids,
+                                        determination,
+                                        cluster_id: optimizer.cluster_id(),
+                                        finishing: optimizer.finishing().clone(),
+                                        plan_insights_optimizer_trace: None,
+                                        global_lir_plan,
+                                        optimization_finished_at,
+                                        insights_ctx,
+                                        reservation: None,
+                                        explain_analyze: Some(measure_ctx),
+                                    })
+                                }
+                                ExplainContext::Analyze(explain_ctx) => {
+                                    PeekStage::ExplainAnalyzeDataflow(
+                                        PeekStageExplainAnalyzeDataflow {
+                                            validity,
+                                            determination,
+                                            cluster_id: optimizer.cluster_id(),
+                                            global_lir_plan,
+                                            explain_ctx,
+                                        },
+                                    )
+                                }
                                 ExplainContext::Pushdown => {
                                     let (plan, _, _) = global_lir_plan.unapply();
                                     let imports = match plan {
@@ -803,12 +853,61 @@ impl Coordinator {
         }
     }
 
+    /// Admits the peek to its cluster before finishing it, or queues it until
+    /// its workload class has capacity on the cluster.
     #[instrument]
     async fn peek_finish(
+        &mut self,
+        ctx: &mut ExecuteContext,
+        mut stage: PeekStageFinish,
+    ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
+        let cluster_id = stage.cluster_id;
+        let workload_class = ctx.session().vars().workload_class();
+
+        // A peek that was queued holds a reservation once it is admitted. If
+        // the stage is dropped instead, e.g. because it is no longer valid,
+        // dropping the reservation releases the slot.
+        let reservation = stage.reservation.take();
+        if reservation.is_none() && !self.try_admit_peek(cluster_id, workload_class) {
+            let rx = self.peek_admission.enqueue(cluster_id, workload_class);
+            let timeout = self
+                .catalog()
+                .system_config()
+                .workload_class_queue_timeout();
+            let span = Span::current();
+            return Ok(StageResult::Handle(mz_ore::task::spawn(
+                || "peek admission",
+                async move {
+                    match tokio::time::timeout(timeout, rx).await {
+                        Ok(Ok(reservation)) => {
+                            stage.reservation = Some(reservation);
+                            Ok(Box::new(PeekStage::Finish(stage)))
+                        }
+                        // The sender is dropped if the cluster is dropped,
+                        // which the validity check of the next stage reports.
+                        Ok(Err(_)) => Ok(Box::new(PeekStage::Finish(stage))),
+                        Err(_) => Err(AdapterError::WorkloadClassQueueTimeout(workload_class)),
+                    }
+                }
+                .instrument(span),
+            )));
+        }
+
+        let result = self.peek_finish_admitted(ctx, stage, workload_class).await;
+        // Once issued, the peek counts against the limits as a pending peek.
+        // It may also not have needed a slot after all, e.g. because it
+        // evaluated to a constant or failed.
+        drop(reservation);
+        self.admit_queued_peeks(cluster_id);
+        result
+    }
+
+    #[instrument]
+    async fn peek_finish_admitted(
         &mut self,
         ctx: &mut ExecuteContext,
         PeekStageFinish {
-            validity: _,
+            validity,
             plan,
             max_query_result_size,
             id_bundle,
@@ -821,7 +920,10 @@ impl Coordinator {
             global_lir_plan,
             optimization_finished_at,
             insights_ctx,
+            reservation: _,
+            explain_analyze,
         }: PeekStageFinish,
+        workload_class: WorkloadClass,
     ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
         if let Some(id) = ctx.extra.contents() {
             self.record_statement_lifecycle_event(
@@ -931,6 +1033,7 @@ impl Coordinator {
                 target_replica,
                 max_result_size,
                 max_query_result_size,
+                workload_class,
             )
             .await?;
 
@@ -941,6 +1044,12 @@ impl Coordinator {
                 .add_notice(AdapterNotice::QueryTimestamp { explanation });
         }
 
+        // The rows of a peek that measures an analyzed dataflow are rendered
+        // into the plan, rather than returned.
+        if let Some(measure_ctx) = explain_analyze {
+            return self.peek_explain_analyze_collect(validity, *measure_ctx, resp);
+        }
+
         let resp = match plan.copy_to {
             None => resp,
             Some(format) => ExecuteResponse::CopyTo {
@@ -26,7 +26,7 @@ use mz_repr::explain::ExplainError;
 use mz_repr::{NotNullViolation, Timestamp};
 use mz_sql::plan::PlanError;
 use mz_sql::rbac;
-use mz_sql::session::vars::VarError;
+use mz_sql::session::vars::{VarError, WorkloadClass};
 use mz_storage_types::connections::ConnectionValidationError;
 use mz_storage_types::controller::StorageError;
 use smallvec::SmallVec;
@@ -144,6 +144,10 @@ pub enum AdapterError {
     StatementTimeout,
     /// The user canceled the query
     Canceled,
+    /// A query waited for admission to its cluster, or a DDL statement waited
+    /// for other DDL statements, for longer than the workload class queue
+    /// timeout.
+    WorkloadClassQueueTimeout(WorkloadClass),
     /// An idle session in a transaction has timed out.
     IdleInTransactionSessionTimeout,
     /// The transaction is in single-subscribe mode.
@@ -410,6 +414,12 @@ impl AdapterError {
                  statement_timeout = '120s'`."
                     .into(),
             ),
+            AdapterError::WorkloadClassQueueTimeout(_) => Some(
+                "Statements of this or higher priority workload classes are occupying the \
+                 cluster or the DDL queue. Retry the statement later, or raise the limits or \
+                 queue timeout of the workload class."
+                    .into(),
+            ),
             AdapterError::PlanError(e) => e.hint(),
             AdapterError::UnallowedOnCluster { cluster, .. } => {
                 (cluster != MZ_CATALOG_SERVER_CLUSTER.name).then(||
@@ -451,6 +461,7 @@ impl AdapterError {
                     VarError::ReadOnlyParameter(_) => SqlState::CANT_CHANGE_RUNTIME_PARAM,
                     VarError::UnknownParameter(_) => SqlState::UNDEFINED_OBJECT,
                     VarError::RequiresUnsafeMode { .. } => SqlState::CANT_CHANGE_RUNTIME_PARAM,
+                    VarError::RequiresSuperuser(_) => SqlState::INSUFFICIENT_PRIVILEGE,
                     VarError::RequiresFeatureFlag { .. } => SqlState::CANT_CHANGE_RUNTIME_PARAM,
                 },
                 _ => SqlState::INTERNAL_ERROR,
@@ -503,6 +514,7 @@ impl AdapterError {
             AdapterError::WrongSetOfLocks => SqlState::LOCK_NOT_AVAILABLE,
             AdapterError::StatementTimeout => SqlState::QUERY_CANCELED,
             AdapterError::Canceled => SqlState::QUERY_CANCELED,
+            AdapterError::WorkloadClassQueueTimeout(_) => SqlState::QUERY_CANCELED,
             AdapterError::IdleInTransactionSessionTimeout => {
                 SqlState::IDLE_IN_TRANSACTION_SESSION_TIMEOUT
             }
@@ -673,6 +685,14 @@ impl fmt::Display for AdapterError {
             AdapterError::Canceled => {
                 write!(f, "canceling statement due to user request")
             }
+            AdapterError::WorkloadClassQueueTimeout(workload_class) => {
+                write!(
+                    f,
+                    "canceling statement due to workload class queue timeout: statement in \
+                     workload class {} was not admitted in time",
+                    workload_class.as_str().quoted()
+                )
+            }
             AdapterError::IdleInTransactionSessionTimeout => {
                 write!(
                     f,
@@ -18,9 +18,14 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use mz_compute_types::dataflows::DataflowDescription;
+use mz_compute_types::plan::{LirId, Plan};
 use mz_expr::explain::ExplainContext;
 use mz_repr::GlobalId;
-use mz_repr::explain::{Explain, ExplainConfig, ExplainError, ExplainFormat, ExprHumanizer};
+use mz_repr::explain::json::{DisplayJson, json_string};
+use mz_repr::explain::text::text_string;
+use mz_repr::explain::{
+    Explain, ExplainConfig, ExplainError, ExplainFormat, ExprHumanizer, Measurements,
+};
 use mz_repr::optimize::OptimizerFeatures;
 use mz_transform::dataflow::DataflowMetainfo;
 use mz_transform::notice::OptimizerNotice;
@@ -85,6 +90,51 @@ where
     Ok(Explainable::new(&mut plan).explain(&format, &context)?)
 }
 
+/// Like [`explain_dataflow`], but annotates the physical plan of the dataflow
+/// with the `measurements` of its LIR nodes, as reported by `EXPLAIN ANALYZE`.
+///
+/// In the [`ExplainFormat::Json`] format, the measurements are reported next
+/// to the plans, keyed by LIR ID.
+pub(crate) fn explain_analyzed_dataflow(
+    mut plan: DataflowDescription<Plan>,
+    format: ExplainFormat,
+    config: &ExplainConfig,
+    features: &OptimizerFeatures,
+    humanizer: &dyn ExprHumanizer,
+    target_cluster: Option<&str>,
+    measurements: &BTreeMap<LirId, Measurements>,
+) -> Result<String, AdapterError> {
+    let context = ExplainContext {
+        config,
+        features,
+        humanizer,
+        cardinality_stats: Default::default(),
+        used_indexes: Default::default(),
+        finishing: Default::default(),
+        duration: Default::default(),
+        target_cluster,
+        optimizer_notices: Default::default(),
+    };
+
+    let explanation = plan.explain_analyzed(&context, measurements)?;
+    match format {
+        ExplainFormat::Text => Ok(text_string(&explanation)),
+        ExplainFormat::Json => {
+            let mut json = explanation.to_serde_value().map_err(ExplainError::from)?;
+            let measurements = measurements
+                .iter()
+                .map(|(lir_id, m)| Ok((lir_id.to_string(), serde_json::to_value(m)?)))
+                .collect::<Result<serde_json::Map<_, _>, serde_json::Error>>()
+                .map_err(ExplainError::from)?;
+            if let Some(object) = json.as_object_mut() {
+                object.insert("measurements".to_owned(), measurements.into());
+            }
+            Ok(json_string(&json))
+        }
+        format => Err(ExplainError::UnsupportedFormat(format).into()),
+    }
+}
+
 /// Convenience method to explain a single plan.
 ///
 /// In the long term, this method and [`explain_dataflow`] should be unified. In
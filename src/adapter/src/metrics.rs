@@ -28,6 +28,8 @@ pub struct Metrics {
     pub storage_usage_collection_time_seconds: HistogramVec,
     pub subscribe_outputs: IntCounterVec,
     pub canceled_peeks: IntCounterVec,
+    pub peek_result_cache_hits: IntCounterVec,
+    pub peek_result_cache_misses: IntCounterVec,
     pub linearize_message_seconds: HistogramVec,
     pub time_to_first_row_seconds: HistogramVec,
     pub statement_logging_records: IntCounterVec,
@@ -103,6 +105,16 @@ impl Metrics {
                 name: "mz_canceled_peeks_total",
                 help: "The total number of canceled peeks since process start.",
             )),
+            peek_result_cache_hits: registry.register(metric!(
+                name: "mz_peek_result_cache_hits_total",
+                help: "The total number of peeks answered from the peek result cache.",
+                var_labels: ["cluster_id"],
+            )),
+            peek_result_cache_misses: registry.register(metric!(
+                name: "mz_peek_result_cache_misses_total",
+                help: "The total number of peeks not found in the peek result cache.",
+                var_labels: ["cluster_id"],
+            )),
             linearize_message_seconds: registry.register(metric!(
                 name: "mz_linearize_message_seconds",
                 help: "The number of seconds it takes to linearize strict serializable messages",
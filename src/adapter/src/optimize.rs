@@ -219,8 +219,15 @@ impl OverrideFrom<OptimizerFeatureOverrides> for OptimizerConfig {
 /// [`OptimizerConfig`] overrides coming from an [`ExplainContext`].
 impl OverrideFrom<ExplainContext> for OptimizerConfig {
     fn override_from(mut self, ctx: &ExplainContext) -> Self {
-        let ExplainContext::Plan(ctx) = ctx else {
-            return self; // Return immediately for all other contexts.
+        let ctx = match ctx {
+            ExplainContext::Plan(ctx) => ctx,
+            // The analyzed dataflow must be installed, even when the SELECT
+            // could be answered by a fast path peek.
+            ExplainContext::Analyze(_) => {
+                self.no_fast_path = true;
+                return self;
+            }
+            _ => return self, // Return immediately for all other contexts.
         };
 
         // Override general parameters.
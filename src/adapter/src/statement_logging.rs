@@ -73,6 +73,9 @@ pub enum StatementExecutionStrategy {
     /// The statement was determined to be constant by
     /// environmentd, and not sent to a cluster.
     Constant,
+    /// The statement was answered from the peek result cache, without
+    /// sending it to a cluster.
+    ResultCache,
 }
 
 impl StatementExecutionStrategy {
@@ -82,6 +85,7 @@ impl StatementExecutionStrategy {
             Self::FastPath => "fast-path",
             Self::PersistFastPath => "persist-fast-path",
             Self::Constant => "constant",
+            Self::ResultCache => "result-cache",
         }
     }
 }
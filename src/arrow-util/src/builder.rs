@@ -172,6 +172,8 @@ fn scalar_to_arrow_datatype(scalar_type: &ScalarType) -> Result<(DataType, Strin
             "timestamptz",
         ),
         ScalarType::Bytes => (DataType::LargeBinary, "bytea"),
+        ScalarType::HllSketch => (DataType::LargeBinary, "hll_sketch"),
+        ScalarType::QuantileSketch => (DataType::LargeBinary, "quantile_sketch"),
         ScalarType::Char { length } => {
             if length.map_or(false, |l| l.into_u32() < i32::MAX.unsigned_abs()) {
                 (DataType::Utf8, "text")
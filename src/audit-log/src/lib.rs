@@ -158,10 +158,12 @@ pub enum EventDetails {
     CreateClusterReplicaV1(CreateClusterReplicaV1),
     CreateClusterReplicaV2(CreateClusterReplicaV2),
     CreateClusterReplicaV3(CreateClusterReplicaV3),
+    CreateClusterReplicaV4(CreateClusterReplicaV4),
     #[serde(rename = "DropComputeReplicaV1")] // historical name
     DropClusterReplicaV1(DropClusterReplicaV1),
     DropClusterReplicaV2(DropClusterReplicaV2),
     DropClusterReplicaV3(DropClusterReplicaV3),
+    DropClusterReplicaV4(DropClusterReplicaV4),
     CreateSourceSinkV1(CreateSourceSinkV1),
     CreateSourceSinkV2(CreateSourceSinkV2),
     CreateSourceSinkV3(CreateSourceSinkV3),
@@ -280,6 +282,17 @@ pub struct DropClusterReplicaV3 {
     pub scheduling_policies: Option<SchedulingDecisionsWithReasonsV2>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct DropClusterReplicaV4 {
+    pub cluster_id: String,
+    pub cluster_name: String,
+    pub replica_id: Option<String>,
+    pub replica_name: String,
+    pub reason: CreateOrDropClusterReplicaReasonV1,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduling_policies: Option<SchedulingDecisionsWithReasonsV3>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 pub struct CreateClusterReplicaV1 {
     pub cluster_id: String,
@@ -324,6 +337,21 @@ pub struct CreateClusterReplicaV3 {
     pub scheduling_policies: Option<SchedulingDecisionsWithReasonsV2>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct CreateClusterReplicaV4 {
+    pub cluster_id: String,
+    pub cluster_name: String,
+    pub replica_id: Option<String>,
+    pub replica_name: String,
+    pub logical_size: String,
+    pub disk: bool,
+    pub billed_as: Option<String>,
+    pub internal: bool,
+    pub reason: CreateOrDropClusterReplicaReasonV1,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduling_policies: Option<SchedulingDecisionsWithReasonsV3>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 #[serde(rename_all = "kebab-case")]
 pub enum CreateOrDropClusterReplicaReasonV1 {
@@ -350,6 +378,23 @@ pub struct SchedulingDecisionsWithReasonsV2 {
     pub on_refresh: RefreshDecisionWithReasonV2,
 }
 
+/// The reasons for the automated cluster scheduling to create or drop replicas of a cluster. The
+/// policy in charge of the cluster (as determined by the cluster's SCHEDULE and AUTO SUSPEND AFTER
+/// options) records its decision, along with its reasons. Policies that are not in charge of the
+/// cluster have no entry.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct SchedulingDecisionsWithReasonsV3 {
+    /// The reason for the refresh policy for wanting to turn a cluster On or Off.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_refresh: Option<RefreshDecisionWithReasonV2>,
+    /// The reason for the utilization policy for wanting to resize a cluster.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_utilization: Option<UtilizationDecisionWithReasonV1>,
+    /// The reason for the auto-suspend policy for wanting to suspend or resume a cluster.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_auto_suspend: Option<AutoSuspendDecisionWithReasonV1>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 pub struct RefreshDecisionWithReasonV1 {
     pub decision: SchedulingDecisionV1,
@@ -373,6 +418,39 @@ pub struct RefreshDecisionWithReasonV2 {
     pub hydration_time_estimate: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct AutoSuspendDecisionWithReasonV1 {
+    pub decision: SchedulingDecisionV1,
+    /// Objects on the cluster whose results have to be kept fresh, and therefore keep the cluster
+    /// On.
+    pub objects_needing_freshness: Vec<String>,
+    /// How long the cluster has been without peeks and subscribes.
+    pub idle_for: String,
+    /// Whether a query on the suspended cluster asked for the cluster to be resumed.
+    pub resumed_for_query: bool,
+    /// The AUTO SUSPEND AFTER setting of the cluster.
+    pub auto_suspend_after: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct UtilizationDecisionWithReasonV1 {
+    /// The replica size that the policy wants the cluster to have.
+    pub target_size: String,
+    /// The replica size of the cluster when the decision was made.
+    pub current_size: String,
+    /// The highest CPU utilization of the cluster's replicas, in percent of their CPU limit.
+    pub cpu_percent: Option<u32>,
+    /// The highest memory utilization of the cluster's replicas, in percent of their memory
+    /// limit.
+    pub memory_percent: Option<u32>,
+    /// Whether all replicas of the cluster were hydrated.
+    pub hydrated: bool,
+    /// The MIN SIZE setting of the cluster.
+    pub min_size: String,
+    /// The MAX SIZE setting of the cluster.
+    pub max_size: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 #[serde(rename_all = "kebab-case")]
 pub enum SchedulingDecisionV1 {
@@ -573,6 +651,9 @@ impl EventDetails {
             EventDetails::CreateClusterReplicaV3(v) => {
                 serde_json::to_value(v).expect("must serialize")
             }
+            EventDetails::CreateClusterReplicaV4(v) => {
+                serde_json::to_value(v).expect("must serialize")
+            }
             EventDetails::DropClusterReplicaV1(v) => {
                 serde_json::to_value(v).expect("must serialize")
             }
@@ -582,6 +663,9 @@ impl EventDetails {
             EventDetails::DropClusterReplicaV3(v) => {
                 serde_json::to_value(v).expect("must serialize")
             }
+            EventDetails::DropClusterReplicaV4(v) => {
+                serde_json::to_value(v).expect("must serialize")
+            }
             EventDetails::IdFullNameV1(v) => serde_json::to_value(v).expect("must serialize"),
             EventDetails::RenameClusterV1(v) => serde_json::to_value(v).expect("must serialize"),
             EventDetails::RenameClusterReplicaV1(v) => {
@@ -229,6 +229,24 @@ impl<R: AvroRead> Reader<R> {
         }
     }
 
+    /// Get the sync marker that terminates every block in the file.
+    pub fn sync_marker(&self) -> &[u8; 16] {
+        &self.header.marker
+    }
+
+    /// Get the codec used to compress blocks in the file.
+    pub fn codec(&self) -> Codec {
+        self.header.codec
+    }
+
+    /// Consumes the `Reader`, returning the underlying reader.
+    ///
+    /// If no values have been read yet, the underlying reader is positioned
+    /// just past the header, at the start of the first block.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
     #[inline]
     /// Read the next Avro value from the file, if one exists.
     pub fn read_next(&mut self) -> Result<Option<Value>, AvroError> {
@@ -0,0 +1,71 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use aws_sdk_kinesis::error::SdkError;
+use aws_sdk_kinesis::operation::describe_stream_summary::DescribeStreamSummaryError;
+use aws_sdk_kinesis::operation::list_shards::ListShardsError;
+use aws_sdk_kinesis::types::Shard;
+use aws_types::sdk_config::SdkConfig;
+
+pub use aws_sdk_kinesis::Client;
+
+/// Creates a new client from an [SDK config](aws_types::sdk_config::SdkConfig).
+pub fn new_client(sdk_config: &SdkConfig) -> Client {
+    Client::new(sdk_config)
+}
+
+/// Lists every shard of `stream`, including closed shards that are still
+/// within the stream's retention period.
+///
+/// Shards are returned in the order in which Kinesis created them.
+pub async fn list_shards(
+    client: &Client,
+    stream: &str,
+) -> Result<Vec<Shard>, SdkError<ListShardsError>> {
+    let mut shards = vec![];
+    let mut next_token: Option<String> = None;
+    loop {
+        // The stream name must not be specified alongside a pagination token.
+        let request = match next_token {
+            Some(token) => client.list_shards().next_token(token),
+            None => client.list_shards().stream_name(stream),
+        };
+        let response = request.send().await?;
+        shards.extend(response.shards.unwrap_or_default());
+        match response.next_token {
+            Some(token) => next_token = Some(token),
+            None => break,
+        }
+    }
+    Ok(shards)
+}
+
+/// Reports whether `stream` exists.
+///
+/// Any error other than the stream not being found is returned to the caller.
+pub async fn stream_exists(
+    client: &Client,
+    stream: &str,
+) -> Result<bool, SdkError<DescribeStreamSummaryError>> {
+    match client
+        .describe_stream_summary()
+        .stream_name(stream)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e)
+            if e.as_service_error()
+                .is_some_and(|e| e.is_resource_not_found_exception()) =>
+        {
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
@@ -15,15 +15,17 @@
 
 use mz_audit_log::{
     AlterDefaultPrivilegeV1, AlterRetainHistoryV1, AlterSetClusterV1, AlterSourceSinkV1,
-    CreateClusterReplicaV1, CreateClusterReplicaV2, CreateClusterReplicaV3, CreateIndexV1,
-    CreateMaterializedViewV1, CreateOrDropClusterReplicaReasonV1, CreateSourceSinkV1,
-    CreateSourceSinkV2, CreateSourceSinkV3, CreateSourceSinkV4, DropClusterReplicaV1,
-    DropClusterReplicaV2, DropClusterReplicaV3, EventDetails, EventType, EventV1, FromPreviousIdV1,
-    FullNameV1, GrantRoleV1, GrantRoleV2, IdFullNameV1, IdNameV1, RefreshDecisionWithReasonV1,
+    AutoSuspendDecisionWithReasonV1, CreateClusterReplicaV1, CreateClusterReplicaV2,
+    CreateClusterReplicaV3, CreateClusterReplicaV4, CreateIndexV1, CreateMaterializedViewV1,
+    CreateOrDropClusterReplicaReasonV1, CreateSourceSinkV1, CreateSourceSinkV2, CreateSourceSinkV3,
+    CreateSourceSinkV4, DropClusterReplicaV1, DropClusterReplicaV2, DropClusterReplicaV3,
+    DropClusterReplicaV4, EventDetails, EventType, EventV1, FromPreviousIdV1, FullNameV1,
+    GrantRoleV1, GrantRoleV2, IdFullNameV1, IdNameV1, RefreshDecisionWithReasonV1,
     RefreshDecisionWithReasonV2, RenameClusterReplicaV1, RenameClusterV1, RenameItemV1,
     RenameSchemaV1, RevokeRoleV1, RevokeRoleV2, RotateKeysV1, SchedulingDecisionV1,
-    SchedulingDecisionsWithReasonsV1, SchedulingDecisionsWithReasonsV2, SchemaV1, SchemaV2, SetV1,
-    ToNewIdV1, UpdateItemV1, UpdateOwnerV1, UpdatePrivilegeV1, VersionedEvent,
+    SchedulingDecisionsWithReasonsV1, SchedulingDecisionsWithReasonsV2,
+    SchedulingDecisionsWithReasonsV3, SchemaV1, SchemaV2, SetV1, ToNewIdV1, UpdateItemV1,
+    UpdateOwnerV1, UpdatePrivilegeV1, UtilizationDecisionWithReasonV1, VersionedEvent,
 };
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 
@@ -404,6 +406,39 @@ impl RustType<crate::objects::audit_log_event_v1::DropClusterReplicaV3> for Drop
     }
 }
 
+impl RustType<crate::objects::audit_log_event_v1::DropClusterReplicaV4> for DropClusterReplicaV4 {
+    fn into_proto(&self) -> crate::objects::audit_log_event_v1::DropClusterReplicaV4 {
+        crate::objects::audit_log_event_v1::DropClusterReplicaV4 {
+            cluster_id: self.cluster_id.to_string(),
+            cluster_name: self.cluster_name.to_string(),
+            replica_id: self
+                .replica_id
+                .as_ref()
+                .map(|id| crate::objects::StringWrapper {
+                    inner: id.to_string(),
+                }),
+            replica_name: self.replica_name.to_string(),
+            reason: Some(self.reason.into_proto()),
+            scheduling_policies: self.scheduling_policies.into_proto(),
+        }
+    }
+
+    fn from_proto(
+        proto: crate::objects::audit_log_event_v1::DropClusterReplicaV4,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(DropClusterReplicaV4 {
+            cluster_id: proto.cluster_id,
+            cluster_name: proto.cluster_name,
+            replica_id: proto.replica_id.map(|s| s.inner),
+            replica_name: proto.replica_name,
+            reason: proto
+                .reason
+                .into_rust_if_some("DropClusterReplicaV4::reason")?,
+            scheduling_policies: proto.scheduling_policies.into_rust()?,
+        })
+    }
+}
+
 impl RustType<crate::objects::audit_log_event_v1::CreateClusterReplicaV1>
     for CreateClusterReplicaV1
 {
@@ -527,6 +562,49 @@ impl RustType<crate::objects::audit_log_event_v1::CreateClusterReplicaV3>
     }
 }
 
+impl RustType<crate::objects::audit_log_event_v1::CreateClusterReplicaV4>
+    for CreateClusterReplicaV4
+{
+    fn into_proto(&self) -> crate::objects::audit_log_event_v1::CreateClusterReplicaV4 {
+        crate::objects::audit_log_event_v1::CreateClusterReplicaV4 {
+            cluster_id: self.cluster_id.to_string(),
+            cluster_name: self.cluster_name.to_string(),
+            replica_id: self
+                .replica_id
+                .as_ref()
+                .map(|id| crate::objects::StringWrapper {
+                    inner: id.to_string(),
+                }),
+            replica_name: self.replica_name.to_string(),
+            logical_size: self.logical_size.to_string(),
+            disk: self.disk,
+            billed_as: self.billed_as.clone(),
+            internal: self.internal,
+            reason: Some(self.reason.into_proto()),
+            scheduling_policies: self.scheduling_policies.into_proto(),
+        }
+    }
+
+    fn from_proto(
+        proto: crate::objects::audit_log_event_v1::CreateClusterReplicaV4,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(CreateClusterReplicaV4 {
+            cluster_id: proto.cluster_id,
+            cluster_name: proto.cluster_name,
+            replica_id: proto.replica_id.map(|id| id.inner),
+            replica_name: proto.replica_name,
+            logical_size: proto.logical_size,
+            disk: proto.disk,
+            billed_as: proto.billed_as,
+            internal: proto.internal,
+            reason: proto
+                .reason
+                .into_rust_if_some("CreateClusterReplicaV4::reason")?,
+            scheduling_policies: proto.scheduling_policies.into_rust()?,
+        })
+    }
+}
+
 impl RustType<crate::objects::audit_log_event_v1::CreateOrDropClusterReplicaReasonV1>
     for CreateOrDropClusterReplicaReasonV1
 {
@@ -596,6 +674,28 @@ impl RustType<crate::objects::audit_log_event_v1::SchedulingDecisionsWithReasons
     }
 }
 
+impl RustType<crate::objects::audit_log_event_v1::SchedulingDecisionsWithReasonsV3>
+    for SchedulingDecisionsWithReasonsV3
+{
+    fn into_proto(&self) -> crate::objects::audit_log_event_v1::SchedulingDecisionsWithReasonsV3 {
+        crate::objects::audit_log_event_v1::SchedulingDecisionsWithReasonsV3 {
+            on_refresh: self.on_refresh.into_proto(),
+            on_utilization: self.on_utilization.into_proto(),
+            on_auto_suspend: self.on_auto_suspend.into_proto(),
+        }
+    }
+
+    fn from_proto(
+        proto: crate::objects::audit_log_event_v1::SchedulingDecisionsWithReasonsV3,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(SchedulingDecisionsWithReasonsV3 {
+            on_refresh: proto.on_refresh.into_rust()?,
+            on_utilization: proto.on_utilization.into_rust()?,
+            on_auto_suspend: proto.on_auto_suspend.into_rust()?,
+        })
+    }
+}
+
 impl RustType<crate::objects::audit_log_event_v1::RefreshDecisionWithReasonV1>
     for RefreshDecisionWithReasonV1
 {
@@ -700,6 +800,91 @@ impl RustType<crate::objects::audit_log_event_v1::RefreshDecisionWithReasonV2>
     }
 }
 
+impl RustType<crate::objects::audit_log_event_v1::UtilizationDecisionWithReasonV1>
+    for UtilizationDecisionWithReasonV1
+{
+    fn into_proto(&self) -> crate::objects::audit_log_event_v1::UtilizationDecisionWithReasonV1 {
+        crate::objects::audit_log_event_v1::UtilizationDecisionWithReasonV1 {
+            target_size: self.target_size.clone(),
+            current_size: self.current_size.clone(),
+            cpu_percent: self.cpu_percent,
+            memory_percent: self.memory_percent,
+            hydrated: self.hydrated,
+            min_size: self.min_size.clone(),
+            max_size: self.max_size.clone(),
+        }
+    }
+
+    fn from_proto(
+        proto: crate::objects::audit_log_event_v1::UtilizationDecisionWithReasonV1,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(UtilizationDecisionWithReasonV1 {
+            target_size: proto.target_size,
+            current_size: proto.current_size,
+            cpu_percent: proto.cpu_percent,
+            memory_percent: proto.memory_percent,
+            hydrated: proto.hydrated,
+            min_size: proto.min_size,
+            max_size: proto.max_size,
+        })
+    }
+}
+
+impl RustType<crate::objects::audit_log_event_v1::AutoSuspendDecisionWithReasonV1>
+    for AutoSuspendDecisionWithReasonV1
+{
+    fn into_proto(&self) -> crate::objects::audit_log_event_v1::AutoSuspendDecisionWithReasonV1 {
+        let decision = match &self.decision {
+            SchedulingDecisionV1::On => {
+                crate::objects::audit_log_event_v1::auto_suspend_decision_with_reason_v1::Decision::On(
+                    Empty {},
+                )
+            }
+            SchedulingDecisionV1::Off => {
+                crate::objects::audit_log_event_v1::auto_suspend_decision_with_reason_v1::Decision::Off(
+                    Empty {},
+                )
+            }
+        };
+        crate::objects::audit_log_event_v1::AutoSuspendDecisionWithReasonV1 {
+            decision: Some(decision),
+            objects_needing_freshness: self.objects_needing_freshness.clone(),
+            idle_for: self.idle_for.clone(),
+            resumed_for_query: self.resumed_for_query,
+            auto_suspend_after: self.auto_suspend_after.clone(),
+        }
+    }
+
+    fn from_proto(
+        proto: crate::objects::audit_log_event_v1::AutoSuspendDecisionWithReasonV1,
+    ) -> Result<Self, TryFromProtoError> {
+        let decision = match proto.decision {
+            None => {
+                return Err(TryFromProtoError::missing_field(
+                    "AutoSuspendDecisionWithReasonV1::decision",
+                ));
+            }
+            Some(
+                crate::objects::audit_log_event_v1::auto_suspend_decision_with_reason_v1::Decision::On(
+                    Empty {},
+                ),
+            ) => SchedulingDecisionV1::On,
+            Some(
+                crate::objects::audit_log_event_v1::auto_suspend_decision_with_reason_v1::Decision::Off(
+                    Empty {},
+                ),
+            ) => SchedulingDecisionV1::Off,
+        };
+        Ok(AutoSuspendDecisionWithReasonV1 {
+            decision,
+            objects_needing_freshness: proto.objects_needing_freshness,
+            idle_for: proto.idle_for,
+            resumed_for_query: proto.resumed_for_query,
+            auto_suspend_after: proto.auto_suspend_after,
+        })
+    }
+}
+
 impl RustType<crate::objects::audit_log_event_v1::CreateSourceSinkV1> for CreateSourceSinkV1 {
     fn into_proto(&self) -> crate::objects::audit_log_event_v1::CreateSourceSinkV1 {
         crate::objects::audit_log_event_v1::CreateSourceSinkV1 {
@@ -1252,6 +1437,9 @@ impl RustType<crate::objects::audit_log_event_v1::Details> for EventDetails {
             EventDetails::CreateClusterReplicaV3(details) => {
                 CreateClusterReplicaV3(details.into_proto())
             }
+            EventDetails::CreateClusterReplicaV4(details) => {
+                CreateClusterReplicaV4(details.into_proto())
+            }
             EventDetails::DropClusterReplicaV1(details) => {
                 DropClusterReplicaV1(details.into_proto())
             }
@@ -1261,6 +1449,9 @@ impl RustType<crate::objects::audit_log_event_v1::Details> for EventDetails {
             EventDetails::DropClusterReplicaV3(details) => {
                 DropClusterReplicaV3(details.into_proto())
             }
+            EventDetails::DropClusterReplicaV4(details) => {
+                DropClusterReplicaV4(details.into_proto())
+            }
             EventDetails::CreateSourceSinkV1(details) => CreateSourceSinkV1(details.into_proto()),
             EventDetails::CreateSourceSinkV2(details) => CreateSourceSinkV2(details.into_proto()),
             EventDetails::CreateSourceSinkV3(details) => CreateSourceSinkV3(details.into_proto()),
@@ -1317,6 +1508,9 @@ impl RustType<crate::objects::audit_log_event_v1::Details> for EventDetails {
             CreateClusterReplicaV3(details) => {
                 Ok(EventDetails::CreateClusterReplicaV3(details.into_rust()?))
             }
+            CreateClusterReplicaV4(details) => {
+                Ok(EventDetails::CreateClusterReplicaV4(details.into_rust()?))
+            }
             DropClusterReplicaV1(details) => {
                 Ok(EventDetails::DropClusterReplicaV1(details.into_rust()?))
             }
@@ -1326,6 +1520,9 @@ impl RustType<crate::objects::audit_log_event_v1::Details> for EventDetails {
             DropClusterReplicaV3(details) => {
                 Ok(EventDetails::DropClusterReplicaV3(details.into_rust()?))
             }
+            DropClusterReplicaV4(details) => {
+                Ok(EventDetails::DropClusterReplicaV4(details.into_rust()?))
+            }
             CreateSourceSinkV1(details) => {
                 Ok(EventDetails::CreateSourceSinkV1(details.into_rust()?))
             }
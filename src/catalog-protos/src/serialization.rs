@@ -720,6 +720,16 @@ impl RustType<crate::objects::ClusterSchedule> for ClusterSchedule {
                     },
                 )),
             },
+            ClusterSchedule::Utilization { min_size, max_size } => {
+                crate::objects::ClusterSchedule {
+                    value: Some(crate::objects::cluster_schedule::Value::Utilization(
+                        crate::objects::ClusterScheduleUtilizationOptions {
+                            min_size: min_size.clone(),
+                            max_size: max_size.clone(),
+                        },
+                    )),
+                }
+            }
         }
     }
 
@@ -736,6 +746,12 @@ impl RustType<crate::objects::ClusterSchedule> for ClusterSchedule {
                         .into_rust_if_some("rehydration_time_estimate")?,
                 })
             }
+            Some(crate::objects::cluster_schedule::Value::Utilization(csuo)) => {
+                Ok(ClusterSchedule::Utilization {
+                    min_size: csuo.min_size,
+                    max_size: csuo.max_size,
+                })
+            }
         }
     }
 }
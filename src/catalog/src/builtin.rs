@@ -1750,6 +1750,54 @@ pub const TYPE_ACL_ITEM_ARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_HLL_SKETCH: BuiltinType<NameReference> = BuiltinType {
+    name: "hll_sketch",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_HLL_SKETCH_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::HllSketch,
+        array_id: None,
+        pg_metadata: None,
+    },
+};
+
+pub const TYPE_HLL_SKETCH_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_hll_sketch",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_HLL_SKETCH_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_HLL_SKETCH.name,
+        },
+        array_id: None,
+        pg_metadata: None,
+    },
+};
+
+pub const TYPE_QUANTILE_SKETCH: BuiltinType<NameReference> = BuiltinType {
+    name: "quantile_sketch",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_QUANTILE_SKETCH_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::QuantileSketch,
+        array_id: None,
+        pg_metadata: None,
+    },
+};
+
+pub const TYPE_QUANTILE_SKETCH_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_quantile_sketch",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: mz_pgrepr::oid::TYPE_QUANTILE_SKETCH_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_QUANTILE_SKETCH.name,
+        },
+        array_id: None,
+        pg_metadata: None,
+    },
+};
+
 pub const TYPE_INTERNAL: BuiltinType<NameReference> = BuiltinType {
     name: "internal",
     schema: PG_CATALOG_SCHEMA,
@@ -3447,17 +3495,30 @@ pub static MZ_CLUSTER_SCHEDULES: LazyLock<BuiltinTable> = LazyLock::new(|| Built
             "refresh_hydration_time_estimate",
             ScalarType::Interval.nullable(true),
         )
+        .with_column("utilization_min_size", ScalarType::String.nullable(true))
+        .with_column("utilization_max_size", ScalarType::String.nullable(true))
         .finish(),
     column_comments: BTreeMap::from_iter([
         (
             "cluster_id",
             "The ID of the cluster. Corresponds to `mz_clusters.id`.",
         ),
-        ("type", "`on-refresh`, or `manual`. Default: `manual`"),
+        (
+            "type",
+            "`on-refresh`, `on-utilization`, or `manual`. Default: `manual`",
+        ),
         (
             "refresh_hydration_time_estimate",
             "The interval given in the `HYDRATION TIME ESTIMATE` option.",
         ),
+        (
+            "utilization_min_size",
+            "The size given in the `MIN SIZE` option of an `ON UTILIZATION` schedule.",
+        ),
+        (
+            "utilization_max_size",
+            "The size given in the `MAX SIZE` option of an `ON UTILIZATION` schedule.",
+        ),
     ]),
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
@@ -11816,6 +11877,56 @@ GROUP BY policy.name, comments.comment;",
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_WORKLOAD_CLASSES: LazyLock<BuiltinView> = LazyLock::new(|| BuiltinView {
+    name: "mz_workload_classes",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_WORKLOAD_CLASSES_OID,
+    desc: RelationDesc::builder()
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("priority", ScalarType::UInt32.nullable(false))
+        .with_column("max_concurrent_peeks", ScalarType::UInt32.nullable(true))
+        .with_column("queue_timeout", ScalarType::Interval.nullable(false))
+        .with_column("is_current", ScalarType::Bool.nullable(false))
+        .with_key(vec![0])
+        .finish(),
+    column_comments: BTreeMap::from_iter([
+        ("name", "The name of the workload class."),
+        (
+            "priority",
+            "The priority of the workload class. Queued queries and DDL statements of classes with a higher priority are admitted first.",
+        ),
+        (
+            "max_concurrent_peeks",
+            "The maximum number of queries of the workload class that can execute concurrently on a cluster, or `NULL` if there is no limit.",
+        ),
+        (
+            "queue_timeout",
+            "The maximum duration a query waits for admission to its cluster, or a DDL statement waits for other DDL statements, before it is canceled.",
+        ),
+        (
+            "is_current",
+            "Whether the workload class is the workload class of the current session.",
+        ),
+    ]),
+    sql: "
+SELECT
+    name,
+    priority,
+    NULLIF(
+        pg_catalog.current_setting('max_concurrent_peeks_' || name)::pg_catalog.uint4,
+        0
+    ) AS max_concurrent_peeks,
+    pg_catalog.current_setting('workload_class_queue_timeout')::pg_catalog.interval
+        AS queue_timeout,
+    name = pg_catalog.current_setting('workload_class') AS is_current
+FROM (VALUES
+    ('interactive', 3::pg_catalog.uint4),
+    ('standard', 2::pg_catalog.uint4),
+    ('batch', 1::pg_catalog.uint4)
+) AS classes (name, priority)",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_CLUSTER_REPLICA_HISTORY: LazyLock<BuiltinView> = LazyLock::new(|| BuiltinView {
     name: "mz_cluster_replica_history",
     schema: MZ_INTERNAL_SCHEMA,
@@ -13586,6 +13697,10 @@ pub static BUILTINS_STATIC: LazyLock<Vec<Builtin<NameReference>>> = LazyLock::ne
         Builtin::Type(&TYPE_MZ_ACL_ITEM_ARRAY),
         Builtin::Type(&TYPE_ACL_ITEM),
         Builtin::Type(&TYPE_ACL_ITEM_ARRAY),
+        Builtin::Type(&TYPE_HLL_SKETCH),
+        Builtin::Type(&TYPE_HLL_SKETCH_ARRAY),
+        Builtin::Type(&TYPE_QUANTILE_SKETCH),
+        Builtin::Type(&TYPE_QUANTILE_SKETCH_ARRAY),
         Builtin::Type(&TYPE_INTERNAL),
     ];
     for (schema, funcs) in &[
@@ -13908,6 +14023,7 @@ pub static BUILTINS_STATIC: LazyLock<Vec<Builtin<NameReference>>> = LazyLock::ne
         Builtin::View(&MZ_HYDRATION_STATUSES),
         Builtin::View(&MZ_SHOW_CLUSTER_REPLICAS),
         Builtin::View(&MZ_SHOW_NETWORK_POLICIES),
+        Builtin::View(&MZ_WORKLOAD_CLASSES),
         Builtin::View(&MZ_CLUSTER_DEPLOYMENT_LINEAGE),
         Builtin::Index(&MZ_SHOW_DATABASES_IND),
         Builtin::Index(&MZ_SHOW_SCHEMAS_IND),
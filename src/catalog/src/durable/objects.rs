@@ -30,6 +30,7 @@ pub(crate) mod state_update;
 
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 use mz_audit_log::VersionedEvent;
 use mz_controller::clusters::ReplicaLogging;
@@ -351,6 +352,7 @@ pub struct ClusterVariantManaged {
     pub disk: bool,
     pub optimizer_feature_overrides: BTreeMap<String, String>,
     pub schedule: ClusterSchedule,
+    pub auto_suspend_after: Option<Duration>,
 }
 
 #[derive(Clone, Debug, Ord, PartialOrd, PartialEq, Eq)]
@@ -76,6 +76,7 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                 disk,
                 optimizer_feature_overrides,
                 schedule,
+                auto_suspend_after,
             }) => proto::cluster_config::Variant::Managed(proto::cluster_config::ManagedCluster {
                 size: size.to_string(),
                 availability_zones: availability_zones.clone(),
@@ -84,6 +85,7 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                 disk: *disk,
                 optimizer_feature_overrides: optimizer_feature_overrides.into_proto(),
                 schedule: Some(schedule.into_proto()),
+                auto_suspend_after: auto_suspend_after.into_proto(),
             }),
             ClusterVariant::Unmanaged => proto::cluster_config::Variant::Unmanaged(proto::Empty {}),
         }
@@ -103,6 +105,7 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                     disk: managed.disk,
                     optimizer_feature_overrides: managed.optimizer_feature_overrides.into_rust()?,
                     schedule: managed.schedule.unwrap_or_default().into_rust()?,
+                    auto_suspend_after: managed.auto_suspend_after.into_rust()?,
                 }))
             }
         }
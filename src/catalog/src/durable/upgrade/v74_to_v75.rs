@@ -0,0 +1,19 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::durable::upgrade::MigrationAction;
+use crate::durable::upgrade::{objects_v74 as v74, objects_v75 as v75};
+
+/// In v75, we add the `ON UTILIZATION` cluster schedule and the `AUTO SUSPEND AFTER` option of
+/// managed clusters, and the audit log entries for creating and dropping replicas due to them.
+pub fn upgrade(
+    _snapshot: Vec<v74::StateUpdateKind>,
+) -> Vec<MigrationAction<v74::StateUpdateKind, v75::StateUpdateKind>> {
+    Vec::new()
+}
@@ -15,6 +15,7 @@ use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::ops::{Deref, DerefMut};
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use mz_adapter_types::compaction::CompactionWindow;
@@ -407,6 +408,7 @@ impl Cluster {
                 disk,
                 optimizer_feature_overrides,
                 schedule,
+                auto_suspend_after,
             }) => {
                 let introspection = match logging {
                     ReplicaLogging {
@@ -430,6 +432,7 @@ impl Cluster {
                     disk: disk.clone(),
                     optimizer_feature_overrides: optimizer_feature_overrides.clone(),
                     schedule: schedule.clone(),
+                    auto_suspend_after: auto_suspend_after.clone(),
                 })
             }
             ClusterVariant::Unmanaged => {
@@ -1232,7 +1235,8 @@ impl Source {
                     // for users.
                     GenericSourceConnection::Postgres(_)
                     | GenericSourceConnection::MySql(_)
-                    | GenericSourceConnection::SqlServer(_) => 0,
+                    | GenericSourceConnection::SqlServer(_)
+                    | GenericSourceConnection::MongoDb(_) => 0,
                     GenericSourceConnection::LoadGenerator(lg) => {
                         // TODO: make this a method on the load generator.
                         if lg.load_generator.views().is_empty() {
@@ -1244,7 +1248,7 @@ impl Source {
                             0
                         }
                     }
-                    GenericSourceConnection::Kafka(_) => 1,
+                    GenericSourceConnection::Kafka(_) | GenericSourceConnection::Kinesis(_) => 1,
                 }
             }
             //  DataSourceDesc::IngestionExport represents a subsource, which
@@ -1315,20 +1319,40 @@ impl Sink {
     /// both the same (either avro or json), we return the value format name,
     /// otherwise we return a composite name.
     pub fn combined_format(&self) -> Cow<'_, str> {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
-        connection.format.get_format_name()
+        match &self.connection {
+            StorageSinkConnection::Kafka(connection) => connection.format.get_format_name(),
+            StorageSinkConnection::Iceberg(_) => Cow::Borrowed("parquet"),
+            StorageSinkConnection::S3(connection) => {
+                Cow::Borrowed(connection.format.get_format_name())
+            }
+            StorageSinkConnection::Postgres(_) => Cow::Borrowed("postgres"),
+            StorageSinkConnection::Http(_) => Cow::Borrowed("json"),
+        }
     }
 
     /// Output distinct key_format and value_format of the sink.
     pub fn formats(&self) -> (Option<&str>, &str) {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
-        let key_format = connection
-            .format
-            .key_format
-            .as_ref()
-            .map(|format| format.get_format_name());
-        let value_format = connection.format.value_format.get_format_name();
-        (key_format, value_format)
+        match &self.connection {
+            StorageSinkConnection::Kafka(connection) => {
+                let key_format = connection
+                    .format
+                    .key_format
+                    .as_ref()
+                    .map(|format| format.get_format_name());
+                let value_format = connection.format.value_format.get_format_name();
+                (key_format, value_format)
+            }
+            // Iceberg sinks write the key as part of the value.
+            StorageSinkConnection::Iceberg(_) => (None, "parquet"),
+            // S3 sinks write the key as part of the value.
+            StorageSinkConnection::S3(connection) => (None, connection.format.get_format_name()),
+            // Postgres sinks write the key as part of the row.
+            StorageSinkConnection::Postgres(_) => (None, "postgres"),
+            StorageSinkConnection::Http(connection) => {
+                let key_format = connection.key_desc_and_indices.as_ref().map(|_| "json");
+                (key_format, "json")
+            }
+        }
     }
 
     pub fn connection_id(&self) -> Option<CatalogItemId> {
@@ -2965,6 +2989,7 @@ pub struct ClusterVariantManaged {
     pub disk: bool,
     pub optimizer_feature_overrides: OptimizerFeatureOverrides,
     pub schedule: ClusterSchedule,
+    pub auto_suspend_after: Option<Duration>,
 }
 
 impl From<ClusterVariantManaged> for durable::ClusterVariantManaged {
@@ -2977,6 +3002,7 @@ impl From<ClusterVariantManaged> for durable::ClusterVariantManaged {
             disk: managed.disk,
             optimizer_feature_overrides: managed.optimizer_feature_overrides.into(),
             schedule: managed.schedule,
+            auto_suspend_after: managed.auto_suspend_after,
         }
     }
 }
@@ -2991,6 +3017,7 @@ impl From<durable::ClusterVariantManaged> for ClusterVariantManaged {
             disk: managed.disk,
             optimizer_feature_overrides: managed.optimizer_feature_overrides.into(),
             schedule: managed.schedule,
+            auto_suspend_after: managed.auto_suspend_after,
         }
     }
 }
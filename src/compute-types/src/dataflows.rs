@@ -926,7 +926,7 @@ impl RustType<ProtoIndexDesc> for IndexDesc {
 }
 
 /// Information about an imported index, and how it will be used by the dataflow.
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Arbitrary)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Arbitrary)]
 pub struct IndexImport {
     /// Description of index.
     pub desc: IndexDesc,
@@ -16,10 +16,12 @@ use std::collections::BTreeMap;
 use mz_expr::explain::{ExplainContext, ExplainMultiPlan, ExplainSource, enforce_linear_chains};
 use mz_expr::{MirRelationExpr, OptimizedMirRelationExpr};
 use mz_repr::GlobalId;
-use mz_repr::explain::{AnnotatedPlan, Explain, ExplainError, UnsupportedFormat};
+use mz_repr::explain::{
+    Analyses, AnnotatedPlan, Explain, ExplainError, Measurements, UnsupportedFormat,
+};
 
 use crate::dataflows::DataflowDescription;
-use crate::plan::Plan;
+use crate::plan::{LirId, Plan};
 
 impl<'a> Explain<'a> for DataflowDescription<Plan> {
     type Context = ExplainContext<'a>;
@@ -33,25 +35,36 @@ impl<'a> Explain<'a> for DataflowDescription<Plan> {
     type Dot = UnsupportedFormat;
 
     fn explain_text(&'a mut self, context: &'a Self::Context) -> Result<Self::Text, ExplainError> {
-        self.as_explain_multi_plan(context)
+        self.as_explain_multi_plan(context, &BTreeMap::new())
     }
 
     fn explain_verbose_text(
         &'a mut self,
         context: &'a Self::Context,
     ) -> Result<Self::VerboseText, ExplainError> {
-        self.as_explain_multi_plan(context)
+        self.as_explain_multi_plan(context, &BTreeMap::new())
     }
 
     fn explain_json(&'a mut self, context: &'a Self::Context) -> Result<Self::Text, ExplainError> {
-        self.as_explain_multi_plan(context)
+        self.as_explain_multi_plan(context, &BTreeMap::new())
     }
 }
 
 impl<'a> DataflowDescription<Plan> {
+    /// Explains the plans of this dataflow, annotating each LIR node with its
+    /// entry in `measurements`, as reported by `EXPLAIN ANALYZE`.
+    pub fn explain_analyzed(
+        &'a mut self,
+        context: &'a ExplainContext<'a>,
+        measurements: &BTreeMap<LirId, Measurements>,
+    ) -> Result<ExplainMultiPlan<'a, Plan>, ExplainError> {
+        self.as_explain_multi_plan(context, measurements)
+    }
+
     fn as_explain_multi_plan(
         &'a mut self,
         context: &'a ExplainContext<'a>,
+        measurements: &BTreeMap<LirId, Measurements>,
     ) -> Result<ExplainMultiPlan<'a, Plan>, ExplainError> {
         let export_ids = export_ids_for(self);
         let plans = self
@@ -69,7 +82,7 @@ impl<'a> DataflowDescription<Plan> {
                     .unwrap_or_else(|| public_id.to_string());
                 let plan = AnnotatedPlan {
                     plan: &build_desc.plan,
-                    annotations: BTreeMap::default(),
+                    annotations: measured_nodes(&build_desc.plan, measurements),
                 };
                 (id, plan)
             })
@@ -92,6 +105,29 @@ impl<'a> DataflowDescription<Plan> {
     }
 }
 
+/// Collects the `measurements` of the nodes of `plan` as [`Analyses`].
+fn measured_nodes<'a>(
+    plan: &'a Plan,
+    measurements: &BTreeMap<LirId, Measurements>,
+) -> BTreeMap<&'a Plan, Analyses> {
+    let mut annotations = BTreeMap::new();
+    if measurements.is_empty() {
+        return annotations;
+    }
+    let mut todo = vec![plan];
+    while let Some(plan) = todo.pop() {
+        if let Some(measurements) = measurements.get(&plan.lir_id) {
+            let analyses = Analyses {
+                measurements: Some(measurements.clone()),
+                ..Default::default()
+            };
+            annotations.insert(plan, analyses);
+        }
+        todo.extend(plan.children());
+    }
+    annotations
+}
+
 impl<'a> Explain<'a> for DataflowDescription<OptimizedMirRelationExpr> {
     type Context = ExplainContext<'a>;
 
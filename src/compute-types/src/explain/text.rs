@@ -22,6 +22,7 @@
 
 use std::fmt;
 use std::ops::Deref;
+use std::time::Duration;
 
 use itertools::{Itertools, izip};
 use mz_expr::explain::{HumanizedExplain, HumanizerMode, fmt_text_constant_rows};
@@ -29,7 +30,7 @@ use mz_expr::{Id, MirScalarExpr};
 use mz_ore::str::{IndentLike, StrExt, separated};
 use mz_repr::explain::text::DisplayText;
 use mz_repr::explain::{
-    CompactScalarSeq, CompactScalars, ExplainConfig, Indices, PlanRenderingContext,
+    CompactScalarSeq, CompactScalars, ExplainConfig, Indices, Measurements, PlanRenderingContext,
 };
 
 use crate::plan::join::delta_join::{DeltaPathPlan, DeltaStagePlan};
@@ -49,7 +50,7 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
         use PlanNode::*;
 
         let mode = HumanizedExplain::new(ctx.config.redacted);
-        let annotations = PlanAnnotations::new(ctx.config.clone(), self);
+        let annotations = PlanAnnotations::new(ctx, self);
 
         match &self.node {
             Constant { rows } => match rows {
@@ -856,29 +857,55 @@ impl<'a> fmt::Display for Permutation<'a> {
 struct PlanAnnotations {
     config: ExplainConfig,
     node_id: LirId,
+    measurements: Option<Measurements>,
 }
 
-// The current implementation deviates from the `AnnotatedPlan` used in `Mir~`-based plans. This is
-// fine, since at the moment the only attribute we are going to explain is the `node_id`, which at
-// the moment is kept inline with the `Plan` variants. If at some point in the future we want to
-// start deriving and printing attributes that are derived ad-hoc, however, we might want to adopt
-// `AnnotatedPlan` here as well.
+// The `node_id` is kept inline with the `Plan` variants, while the
+// `measurements` reported by `EXPLAIN ANALYZE` are looked up in the
+// `AnnotatedPlan` annotations of the rendering context.
 impl PlanAnnotations {
-    fn new(config: ExplainConfig, plan: &Plan) -> Self {
+    fn new(ctx: &PlanRenderingContext<'_, Plan>, plan: &Plan) -> Self {
         let node_id = plan.lir_id;
-        Self { config, node_id }
+        let measurements = ctx
+            .annotations
+            .get(plan)
+            .and_then(|analyses| analyses.measurements.clone());
+        Self {
+            config: ctx.config.clone(),
+            node_id,
+            measurements,
+        }
     }
 }
 
 impl fmt::Display for PlanAnnotations {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.config.node_ids {
-            f.debug_struct(" //")
-                .field("node_id", &self.node_id)
-                .finish()
-        } else {
+        if !self.config.node_ids && self.measurements.is_none() {
             // No physical plan annotations enabled.
-            Ok(())
+            return Ok(());
+        }
+
+        let mut builder = f.debug_struct(" //");
+        if self.config.node_ids {
+            builder.field("node_id", &self.node_id);
+        }
+        if let Some(measurements) = &self.measurements {
+            if let Some(memory_bytes) = measurements.memory_bytes {
+                builder.field("memory_bytes", &memory_bytes);
+            }
+            if let Some(records) = measurements.records {
+                builder.field("records", &records);
+            }
+            if let Some(memory_skew) = measurements.memory_skew {
+                builder.field("memory_skew", &format_args!("{memory_skew:.2}"));
+            }
+            if let Some(elapsed_ns) = measurements.elapsed_ns {
+                builder.field("elapsed", &Duration::from_nanos(elapsed_ns));
+            }
+            if let Some(cpu_skew) = measurements.cpu_skew {
+                builder.field("cpu_skew", &format_args!("{cpu_skew:.2}"));
+            }
         }
+        builder.finish()
     }
 }
@@ -64,7 +64,7 @@ include!(concat!(env!("OUT_DIR"), "/mz_compute_types.plan.rs"));
 /// row from the concatenation of key and value. Further explanation is available
 /// in the documentation for `KeyValRowMapping`.
 #[derive(
-    Arbitrary, Clone, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize,
+    Arbitrary, Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
 )]
 pub struct AvailableCollections {
     /// Whether the collection exists in unarranged form.
@@ -162,7 +162,9 @@ impl AvailableCollections {
 }
 
 /// An identifier for an LIR node.
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize, Columnar)]
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Columnar,
+)]
 pub struct LirId(u64);
 
 impl LirId {
@@ -177,6 +179,12 @@ impl From<LirId> for u64 {
     }
 }
 
+impl From<u64> for LirId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
 impl std::fmt::Display for LirId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -194,7 +202,7 @@ impl RustType<u64> for LirId {
 }
 
 /// A rendering plan with as much conditional logic as possible removed.
-#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct Plan<T = mz_repr::Timestamp> {
     /// A dataflow-local identifier.
     pub lir_id: LirId,
@@ -203,7 +211,7 @@ pub struct Plan<T = mz_repr::Timestamp> {
 }
 
 /// The actual AST node of the `Plan`.
-#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum PlanNode<T = mz_repr::Timestamp> {
     /// A collection containing a pre-determined collection.
     Constant {
@@ -701,7 +709,7 @@ impl Arbitrary for Plan {
 }
 
 /// How a `Get` stage will be rendered.
-#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum GetPlan {
     /// Simply pass input arrangements on to the next stage.
     PassArrangements,
@@ -147,7 +147,7 @@ impl TryFrom<&ReducePlan> for ReductionType {
 /// shape / general computation of the rendered dataflow graph
 /// in this plan, and then make actually rendering the graph
 /// be as simple (and compiler verifiable) as possible.
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum ReducePlan {
     /// Plan for not computing any aggregations, just determining the set of
     /// distinct keys.
@@ -263,7 +263,7 @@ impl RustType<ProtoReducePlan> for ReducePlan {
 /// apply only to the distinct set of values. We need
 /// to apply a distinct operator to those before we
 /// combine them with everything else.
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct AccumulablePlan {
     /// All of the aggregations we were asked to compute, stored
     /// in order.
@@ -320,7 +320,7 @@ impl RustType<ProtoAccumulablePlan> for AccumulablePlan {
 /// with monotonic plans, but otherwise, we need to render
 /// them with a reduction tree that splits the inputs into
 /// small, and then progressively larger, buckets
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum HierarchicalPlan {
     /// Plan hierarchical aggregations under monotonic inputs.
     Monotonic(MonotonicPlan),
@@ -331,8 +331,16 @@ pub enum HierarchicalPlan {
 impl HierarchicalPlan {
     /// Upgrades from a bucketed plan to a monotonic plan, if necessary,
     /// and sets consolidation requirements.
+    ///
+    /// Bucketed plans of aggregates with partial state are not upgraded, see
+    /// [`AggregateFunc::has_partial_state`].
     pub fn as_monotonic(&mut self, must_consolidate: bool) {
         match self {
+            HierarchicalPlan::Bucketed(bucketed)
+                if bucketed
+                    .aggr_funcs
+                    .iter()
+                    .any(|func| func.has_partial_state()) => {}
             HierarchicalPlan::Bucketed(bucketed) => {
                 // TODO: ideally we would not have the `clone()` but ownership
                 // seems fraught here as we are behind a `&mut self` reference.
@@ -377,7 +385,7 @@ impl RustType<ProtoHierarchicalPlan> for HierarchicalPlan {
 /// append only, so we can change our computation to
 /// only retain the "best" value in the diff field, instead
 /// of holding onto all values.
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct MonotonicPlan {
     /// All of the aggregations we were asked to compute.
     pub aggr_funcs: Vec<AggregateFunc>,
@@ -422,7 +430,7 @@ impl RustType<ProtoMonotonicPlan> for MonotonicPlan {
 /// fraction of the original input) and redo the reduction in another
 /// layer. Effectively, we'll construct a min / max heap out of a series
 /// of reduce operators (each one is a separate layer).
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct BucketedPlan {
     /// All of the aggregations we were asked to compute.
     pub aggr_funcs: Vec<AggregateFunc>,
@@ -478,7 +486,7 @@ impl RustType<ProtoBucketedPlan> for BucketedPlan {
 /// were only asked to compute a single aggregation, we can skip
 /// that step and return the arrangement provided by computing the aggregation
 /// directly.
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum BasicPlan {
     /// Plan for rendering a single basic aggregation.
     Single(SingleBasicPlan),
@@ -492,7 +500,7 @@ pub enum BasicPlan {
 
 /// Plan for rendering a single basic aggregation, with possibly fusing a `FlatMap UnnestList` with
 /// this aggregation.
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct SingleBasicPlan {
     /// The index in the set of inputs that we are aggregating over.
     pub index: usize,
@@ -573,7 +581,7 @@ impl RustType<ProtoBasicPlan> for BasicPlan {
 /// types.
 ///
 /// TODO: could we express this as a delta join
-#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct CollationPlan {
     /// Accumulable aggregation results to collate, if any.
     pub accumulable: Option<AccumulablePlan>,
@@ -768,7 +776,9 @@ impl ReducePlan {
                 // generating a list of "skips" an iterator over the Row needs
                 // to do to get the desired indexes.
                 let skips = convert_indexes_to_skips(indexes);
-                if monotonic {
+                // Partial results cannot be accumulated in a monoid, so aggregates with partial
+                // state are rendered with buckets even on monotonic inputs.
+                if monotonic && !aggr_funcs.iter().any(|func| func.has_partial_state()) {
                     let monotonic = MonotonicPlan {
                         aggr_funcs,
                         skips,
@@ -879,7 +889,7 @@ impl ReducePlan {
 }
 
 /// Plan for extracting keys and values in preparation for a reduction.
-#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct KeyValPlan {
     /// Extracts the columns used as the key.
     pub key_plan: mz_expr::SafeMfpPlan,
@@ -1038,6 +1048,19 @@ pub fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::MinTimestamp
         | AggregateFunc::MinTimestampTz
         | AggregateFunc::MinTime => ReductionType::Hierarchical,
+        // Merging HyperLogLog sketches takes the maximum of each register, which is associative
+        // and idempotent just like `max`, so partial merges over subsets of the values can be
+        // merged again. Quantile sketches are merged by adding their bucket counts, which is
+        // associative but not idempotent, so hierarchical stages merge each distinct sketch as
+        // many times as it occurs (see `AggregateFunc::eval_with_multiplicities`).
+        AggregateFunc::HllMerge | AggregateFunc::QuantileSketchMerge => ReductionType::Hierarchical,
+        // The percentile of a key cannot be derived from the percentiles of subsets of its
+        // values, so hierarchical stages of ordered-set aggregates instead produce the sorted
+        // values of their bucket with their counts, and merge the sorted runs of the previous
+        // stage instead of sorting the whole group (see `AggregateFunc::has_partial_state`).
+        AggregateFunc::PercentileCont { .. }
+        | AggregateFunc::PercentileDisc { .. }
+        | AggregateFunc::Mode { .. } => ReductionType::Hierarchical,
         AggregateFunc::JsonbAgg { .. }
         | AggregateFunc::JsonbObjectAgg { .. }
         | AggregateFunc::MapAgg { .. }
@@ -1047,9 +1070,13 @@ pub fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::RowNumber { .. }
         | AggregateFunc::Rank { .. }
         | AggregateFunc::DenseRank { .. }
+        | AggregateFunc::PercentRank { .. }
+        | AggregateFunc::CumeDist { .. }
+        | AggregateFunc::Ntile { .. }
         | AggregateFunc::LagLead { .. }
         | AggregateFunc::FirstValue { .. }
         | AggregateFunc::LastValue { .. }
+        | AggregateFunc::NthValue { .. }
         | AggregateFunc::WindowAggregate { .. }
         | AggregateFunc::FusedValueWindowFunc { .. }
         | AggregateFunc::FusedWindowAggregate { .. } => ReductionType::Basic,
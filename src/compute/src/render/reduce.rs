@@ -1022,13 +1022,17 @@ where
             // The first mod to apply to the hash.
             let first_mod = buckets.get(0).copied().unwrap_or(1);
 
-            // Gather the relevant keys with their hashes along with values ordered by aggregation_index.
+            // Gather the relevant keys with their hashes along with values ordered by aggregation_index,
+            // converted to the partial results that the stages combine.
+            let input_funcs = aggr_funcs.clone();
             let mut stage = input.map(move |(key, row)| {
+                let temp_storage = RowArena::new();
                 let mut row_builder = SharedRow::get();
                 let mut row_packer = row_builder.packer();
                 let mut row_iter = row.iter();
-                for skip in skips.iter() {
-                    row_packer.push(row_iter.nth(*skip).unwrap());
+                for (skip, func) in skips.iter().zip(input_funcs.iter()) {
+                    row_packer
+                        .push(func.partial_input(row_iter.nth(*skip).unwrap(), &temp_storage));
                 }
                 let values = row_builder.clone();
 
@@ -1129,12 +1133,13 @@ where
 
                             let mut source_iters = source
                                 .iter()
-                                .map(|(values, _cnt)| *values)
+                                .map(|(values, cnt)| (*values, hierarchical_multiplicity(*cnt)))
                                 .collect::<Vec<_>>();
                             for func in aggr_funcs2.iter() {
-                                let column_iter = (0..source_iters.len())
-                                    .map(|i| source_iters[i].next().unwrap());
-                                datums_local.push(func.eval(column_iter, &temp_storage));
+                                let column_iter = source_iters
+                                    .iter_mut()
+                                    .map(|(values, count)| (values.next().unwrap(), *count));
+                                datums_local.push(func.eval_partials(column_iter, &temp_storage));
                             }
                             if let Result::Err(e) =
                                 mfp.evaluate_inner(&mut datums_local, &temp_storage)
@@ -1163,12 +1168,13 @@ where
 
                         let mut source_iters = source
                             .iter()
-                            .map(|(values, _cnt)| *values)
+                            .map(|(values, cnt)| (*values, hierarchical_multiplicity(*cnt)))
                             .collect::<Vec<_>>();
                         for func in aggr_funcs.iter() {
-                            let column_iter =
-                                (0..source_iters.len()).map(|i| source_iters[i].next().unwrap());
-                            datums_local.push(func.eval(column_iter, &temp_storage));
+                            let column_iter = source_iters
+                                .iter_mut()
+                                .map(|(values, count)| (values.next().unwrap(), *count));
+                            datums_local.push(func.eval_partials(column_iter, &temp_storage));
                         }
 
                         if let Some(row) = evaluate_mfp_after(
@@ -1305,12 +1311,13 @@ where
 
                 let mut source_iters = source
                     .iter()
-                    .map(|(values, _cnt)| *values)
+                    .map(|(values, cnt)| (*values, hierarchical_multiplicity(*cnt)))
                     .collect::<Vec<_>>();
                 for func in aggrs.iter() {
-                    let column_iter =
-                        (0..source_iters.len()).map(|i| source_iters[i].next().unwrap());
-                    row_packer.push(func.eval(column_iter, &RowArena::new()));
+                    let column_iter = source_iters
+                        .iter_mut()
+                        .map(|(values, count)| (values.next().unwrap(), *count));
+                    row_packer.push(func.combine_partials(column_iter, &RowArena::new()));
                 }
                 // We only want to arrange the parts of the input that are not part of the output.
                 // More specifically, we want to arrange it so that `input.concat(&output.negate())`
@@ -1690,6 +1697,17 @@ fn evaluate_mfp_after<'a, 'b>(
     }
 }
 
+/// Returns the number of times a value with accumulation `count` contributes to a stage of a
+/// hierarchical reduction.
+///
+/// The first stage sees the input with its multiplicities, which are validated to be positive.
+/// Later stages see the partial result of each bucket once, but with a negative multiplicity,
+/// see [`Context::build_bucketed_negated_output`]. In both cases the magnitude is the number of
+/// times the value contributes.
+fn hierarchical_multiplicity(count: Diff) -> u64 {
+    count.into_inner().unsigned_abs()
+}
+
 fn accumulable_zero(aggr_func: &AggregateFunc) -> Accum {
     match aggr_func {
         AggregateFunc::Any | AggregateFunc::All => Accum::Bool {
@@ -2311,6 +2329,7 @@ mod monoids {
     use differential_dataflow::difference::{IsZero, Multiply, Semigroup};
     use mz_expr::AggregateFunc;
     use mz_ore::soft_panic_or_log;
+    use mz_repr::adt::sketch::{HyperLogLog, QuantileSketch};
     use mz_repr::{Datum, Diff, Row};
     use serde::{Deserialize, Serialize};
 
@@ -2319,13 +2338,17 @@ mod monoids {
     pub enum ReductionMonoid {
         Min(Row),
         Max(Row),
+        /// An encoded [`HyperLogLog`] sketch, combined by merging registers.
+        HllMerge(Row),
+        /// An encoded [`QuantileSketch`], combined by adding bucket counts.
+        QuantileSketchMerge(Row),
     }
 
     impl ReductionMonoid {
         pub fn finalize(&self) -> &Row {
             use ReductionMonoid::*;
             match self {
-                Min(row) | Max(row) => row,
+                Min(row) | Max(row) | HllMerge(row) | QuantileSketchMerge(row) => row,
             }
         }
     }
@@ -2336,6 +2359,8 @@ mod monoids {
             match self {
                 Min(row) => Min(row.clone()),
                 Max(row) => Max(row.clone()),
+                HllMerge(row) => HllMerge(row.clone()),
+                QuantileSketchMerge(row) => QuantileSketchMerge(row.clone()),
             }
         }
 
@@ -2343,11 +2368,11 @@ mod monoids {
             use ReductionMonoid::*;
 
             let mut row = std::mem::take(match self {
-                Min(row) | Max(row) => row,
+                Min(row) | Max(row) | HllMerge(row) | QuantileSketchMerge(row) => row,
             });
 
             let source_row = match source {
-                Min(row) | Max(row) => row,
+                Min(row) | Max(row) | HllMerge(row) | QuantileSketchMerge(row) => row,
             };
 
             row.clone_from(source_row);
@@ -2355,6 +2380,8 @@ mod monoids {
             match source {
                 Min(_) => *self = Min(row),
                 Max(_) => *self = Max(row),
+                HllMerge(_) => *self = HllMerge(row),
+                QuantileSketchMerge(_) => *self = QuantileSketchMerge(row),
             }
         }
     }
@@ -2363,12 +2390,25 @@ mod monoids {
         type Output = Self;
 
         fn multiply(self, factor: &Diff) -> Self {
-            // Multiplication in ReductionMonoid is idempotent, and
+            // Multiplication in ReductionMonoid is idempotent, except for quantile sketches, and
             // its users must ascertain its monotonicity beforehand
             // (typically with ensure_monotonic) since it has no zero
             // value for us to use here.
             assert!(factor.is_positive());
-            self
+            match self {
+                // A sketch that occurs `factor` times contributes its bucket counts that often.
+                ReductionMonoid::QuantileSketchMerge(mut row) if *factor != Diff::ONE => {
+                    if let Datum::Bytes(bytes) = row.unpack_first() {
+                        let sketch = QuantileSketch::decode(bytes)
+                            .expect("sketches are validated when they enter the reduction");
+                        let mut scaled = QuantileSketch::new();
+                        scaled.merge_scaled(&sketch, factor.into_inner().unsigned_abs());
+                        row.packer().push(Datum::Bytes(&scaled.encode()));
+                    }
+                    ReductionMonoid::QuantileSketchMerge(row)
+                }
+                monoid => monoid,
+            }
         }
     }
 
@@ -2405,6 +2445,39 @@ mod monoids {
                         lhs.clone_from(rhs);
                     }
                 }
+                (ReductionMonoid::HllMerge(lhs), ReductionMonoid::HllMerge(rhs)) => {
+                    // Datum::Null is the identity, not an empty sketch.
+                    match (lhs.unpack_first(), rhs.unpack_first()) {
+                        (_, Datum::Null) => {}
+                        (Datum::Null, _) => lhs.clone_from(rhs),
+                        (lhs_val, rhs_val) => {
+                            let mut sketch = HyperLogLog::decode(lhs_val.unwrap_bytes())
+                                .expect("sketches are validated when they enter the reduction");
+                            sketch
+                                .merge_encoded(rhs_val.unwrap_bytes())
+                                .expect("sketches are validated when they enter the reduction");
+                            lhs.packer().push(Datum::Bytes(&sketch.encode()));
+                        }
+                    }
+                }
+                (
+                    ReductionMonoid::QuantileSketchMerge(lhs),
+                    ReductionMonoid::QuantileSketchMerge(rhs),
+                ) => {
+                    // Datum::Null is the identity, not an empty sketch.
+                    match (lhs.unpack_first(), rhs.unpack_first()) {
+                        (_, Datum::Null) => {}
+                        (Datum::Null, _) => lhs.clone_from(rhs),
+                        (lhs_val, rhs_val) => {
+                            let mut sketch = QuantileSketch::decode(lhs_val.unwrap_bytes())
+                                .expect("sketches are validated when they enter the reduction");
+                            let rhs_sketch = QuantileSketch::decode(rhs_val.unwrap_bytes())
+                                .expect("sketches are validated when they enter the reduction");
+                            sketch.merge(&rhs_sketch);
+                            lhs.packer().push(Datum::Bytes(&sketch.encode()));
+                        }
+                    }
+                }
                 (lhs, rhs) => {
                     soft_panic_or_log!(
                         "Mismatched monoid variants in reduction! lhs: {lhs:?} rhs: {rhs:?}"
@@ -2445,6 +2518,8 @@ mod monoids {
             match item {
                 Min(row) => Min(unsafe { self.inner.copy(row) }),
                 Max(row) => Max(unsafe { self.inner.copy(row) }),
+                HllMerge(row) => HllMerge(unsafe { self.inner.copy(row) }),
+                QuantileSketchMerge(row) => QuantileSketchMerge(unsafe { self.inner.copy(row) }),
             }
         }
 
@@ -2512,6 +2587,8 @@ mod monoids {
             | AggregateFunc::MinTimestampTz
             | AggregateFunc::MinInterval
             | AggregateFunc::MinTime => Some(ReductionMonoid::Min(row)),
+            AggregateFunc::HllMerge => Some(ReductionMonoid::HllMerge(row)),
+            AggregateFunc::QuantileSketchMerge => Some(ReductionMonoid::QuantileSketchMerge(row)),
             AggregateFunc::SumInt16
             | AggregateFunc::SumInt32
             | AggregateFunc::SumInt64
@@ -2531,12 +2608,19 @@ mod monoids {
             | AggregateFunc::ArrayConcat { .. }
             | AggregateFunc::ListConcat { .. }
             | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
             | AggregateFunc::RowNumber { .. }
             | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. }
+            | AggregateFunc::Ntile { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
             | AggregateFunc::LastValue { .. }
+            | AggregateFunc::NthValue { .. }
             | AggregateFunc::WindowAggregate { .. }
             | AggregateFunc::FusedValueWindowFunc { .. }
             | AggregateFunc::FusedWindowAggregate { .. } => None,
@@ -2560,6 +2644,10 @@ mod window_agg_helpers {
     impl mz_expr::OneByOneAggr for OneByOneAggrImpls {
         fn new(agg: &AggregateFunc, reverse: bool) -> Self {
             match reduction_type(agg) {
+                // Aggregates with partial state have no monoid to accumulate into.
+                ReductionType::Hierarchical if agg.has_partial_state() => {
+                    OneByOneAggrImpls::Basic(mz_expr::NaiveOneByOneAggr::new(agg, reverse))
+                }
                 ReductionType::Basic => {
                     OneByOneAggrImpls::Basic(mz_expr::NaiveOneByOneAggr::new(agg, reverse))
                 }
@@ -1635,7 +1635,9 @@ pub mod plan {
     };
 
     /// A wrapper type which indicates it is safe to simply evaluate all expressions.
-    #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+    #[derive(
+        Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd,
+    )]
     pub struct SafeMfpPlan {
         pub(crate) mfp: MapFilterProject,
     }
@@ -1949,6 +1951,12 @@ pub mod plan {
             }
         }
 
+        /// Returns the non-temporal part of the plan, which is evaluated in
+        /// addition to the temporal bounds.
+        pub fn nontemporal(&self) -> &SafeMfpPlan {
+            &self.mfp
+        }
+
         /// Returns an iterator over mutable references to all non-temporal
         /// scalar expressions in the plan.
         ///
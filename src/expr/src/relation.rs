@@ -2813,20 +2813,64 @@ impl AggregateExpr {
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)))
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
 
+            // PercentileCont and PercentileDisc take nested (value, fraction) records and output the
+            // value, unless the fraction is null
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. } => {
+                let record = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+                let value = record
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+                record
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)))
+                    .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
+                    .if_then_else(
+                        MirScalarExpr::literal_null(self.typ(input_type).scalar_type),
+                        value,
+                    )
+            }
+
+            // Mode takes a single level of records and outputs the value
+            AggregateFunc::Mode { .. } => self
+                .expr
+                .clone()
+                .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
+
             // ListConcat and ArrayConcat take a single level of records and output a list containing exactly 1 element
             AggregateFunc::ListConcat { .. } | AggregateFunc::ArrayConcat { .. } => self
                 .expr
                 .clone()
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
 
-            // RowNumber, Rank, DenseRank take a list of records and output a list containing exactly 1 element
-            AggregateFunc::RowNumber { .. } => {
-                self.on_unique_ranking_window_funcs(input_type, "?row_number?")
-            }
-            AggregateFunc::Rank { .. } => self.on_unique_ranking_window_funcs(input_type, "?rank?"),
-            AggregateFunc::DenseRank { .. } => {
-                self.on_unique_ranking_window_funcs(input_type, "?dense_rank?")
-            }
+            // RowNumber, Rank, DenseRank, PercentRank, CumeDist take a list of records and output a
+            // list containing exactly 1 element
+            AggregateFunc::RowNumber { .. } => self.on_unique_ranking_window_funcs(
+                input_type,
+                "?row_number?",
+                MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+            ),
+            AggregateFunc::Rank { .. } => self.on_unique_ranking_window_funcs(
+                input_type,
+                "?rank?",
+                MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+            ),
+            AggregateFunc::DenseRank { .. } => self.on_unique_ranking_window_funcs(
+                input_type,
+                "?dense_rank?",
+                MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+            ),
+            AggregateFunc::PercentRank { .. } => self.on_unique_ranking_window_funcs(
+                input_type,
+                "?percent_rank?",
+                MirScalarExpr::literal_ok(Datum::from(0.0f64), ScalarType::Float64),
+            ),
+            AggregateFunc::CumeDist { .. } => self.on_unique_ranking_window_funcs(
+                input_type,
+                "?cume_dist?",
+                MirScalarExpr::literal_ok(Datum::from(1.0f64), ScalarType::Float64),
+            ),
 
             // The input type for LagLead is ((OriginalRow, (InputValue, Offset, Default)), OrderByExprs...)
             AggregateFunc::LagLead { lag_lead, .. } => {
@@ -2955,6 +2999,84 @@ impl AggregateExpr {
                 }
             }
 
+            // The input type for Ntile is ((OriginalRow, InputValue), OrderByExprs...)
+            AggregateFunc::Ntile { .. } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type_with_orig_row = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the input value
+                let arg = tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                let (result_expr, column_name) = Self::on_unique_ntile(arg);
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type_with_orig_row,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![column_name, ColumnName::from("?record?")],
+                        },
+                        exprs: vec![result_expr, original_row],
+                    }],
+                }
+            }
+
+            // The input type for NthValue is ((OriginalRow, (InputValue, N)), OrderByExprs...)
+            AggregateFunc::NthValue { window_frame, .. } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type_with_orig_row = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let nth_value_return_type =
+                    return_type_with_orig_row.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the encoded args
+                let encoded_args =
+                    tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                let (result_expr, column_name) =
+                    Self::on_unique_nth_value(window_frame, encoded_args, nth_value_return_type);
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type_with_orig_row,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![column_name, ColumnName::from("?record?")],
+                        },
+                        exprs: vec![result_expr, original_row],
+                    }],
+                }
+            }
+
             // The input type for window aggs is ((OriginalRow, InputValue), OrderByExprs...)
             // See an example MIR in `window_func_applied_to`.
             AggregateFunc::WindowAggregate {
@@ -3144,6 +3266,21 @@ impl AggregateExpr {
                                 return_type_for_func,
                             )
                         }
+                        AggregateFunc::Ntile { order_by } => {
+                            assert_eq!(order_by, outer_order_by);
+                            Self::on_unique_ntile(args_for_func)
+                        }
+                        AggregateFunc::NthValue {
+                            window_frame,
+                            order_by,
+                        } => {
+                            assert_eq!(order_by, outer_order_by);
+                            Self::on_unique_nth_value(
+                                window_frame,
+                                args_for_func,
+                                return_type_for_func,
+                            )
+                        }
                         _ => panic!("unknown function in FusedValueWindowFunc"),
                     };
                     func_result_exprs.push(result);
@@ -3214,15 +3351,18 @@ impl AggregateExpr {
             | AggregateFunc::SumNumeric
             | AggregateFunc::Any
             | AggregateFunc::All
-            | AggregateFunc::Dummy => self.expr.clone(),
+            | AggregateFunc::Dummy
+            | AggregateFunc::HllMerge
+            | AggregateFunc::QuantileSketchMerge => self.expr.clone(),
         }
     }
 
-    /// `on_unique` for ROW_NUMBER, RANK, DENSE_RANK
+    /// `on_unique` for ROW_NUMBER, RANK, DENSE_RANK, PERCENT_RANK, CUME_DIST
     fn on_unique_ranking_window_funcs(
         &self,
         input_type: &[ColumnType],
         col_name: &str,
+        result_expr: MirScalarExpr,
     ) -> MirScalarExpr {
         let list = self
             .expr
@@ -3251,10 +3391,7 @@ impl AggregateExpr {
                 func: VariadicFunc::RecordCreate {
                     field_names: vec![ColumnName::from(col_name), ColumnName::from("?record?")],
                 },
-                exprs: vec![
-                    MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
-                    record,
-                ],
+                exprs: vec![result_expr, record],
             }],
         }
     }
@@ -3310,6 +3447,42 @@ impl AggregateExpr {
         (result_expr, ColumnName::from("?first_value?"))
     }
 
+    /// `on_unique` for `ntile`
+    fn on_unique_ntile(arg: MirScalarExpr) -> (MirScalarExpr, ColumnName) {
+        // The single row always lands in the first bucket, unless the number of buckets is null.
+        let result_expr = arg
+            .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
+            .if_then_else(
+                MirScalarExpr::literal_null(ScalarType::Int32),
+                MirScalarExpr::literal_ok(Datum::Int32(1), ScalarType::Int32),
+            );
+        (result_expr, ColumnName::from("?ntile?"))
+    }
+
+    /// `on_unique` for `nth_value`
+    fn on_unique_nth_value(
+        window_frame: &WindowFrame,
+        encoded_args: MirScalarExpr,
+        return_type: ScalarType,
+    ) -> (MirScalarExpr, ColumnName) {
+        // If the window frame includes the current (single) row, return its value if asked for
+        // the first row of the frame, null otherwise
+        let result_expr = if window_frame.includes_current_row() {
+            let value = encoded_args
+                .clone()
+                .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+            let n = encoded_args.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+            n.call_binary(
+                MirScalarExpr::literal_ok(Datum::Int32(1), ScalarType::Int32),
+                crate::BinaryFunc::Eq,
+            )
+            .if_then_else(value, MirScalarExpr::literal_null(return_type))
+        } else {
+            MirScalarExpr::literal_null(return_type)
+        };
+        (result_expr, ColumnName::from("?nth_value?"))
+    }
+
     /// `on_unique` for window aggregations
     fn on_unique_window_agg(
         window_frame: &WindowFrame,
@@ -3638,7 +3811,7 @@ impl JoinInputCharacteristicsV1 {
 /// The generic parameters are for accommodating prepared statement parameters in
 /// `limit` and `offset`: the planner can hold these fields as HirScalarExpr long enough to call
 /// `bind_parameters` on them.
-#[derive(Arbitrary, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Arbitrary, Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct RowSetFinishing<L = NonNeg<i64>, O = usize> {
     /// Order rows by the given columns.
     pub order_by: Vec<ColumnOrder>,
@@ -9,7 +9,7 @@
 
 #![allow(missing_docs)]
 
-use std::cmp::{max, min};
+use std::cmp::{Ordering, max, min};
 use std::iter::Sum;
 use std::ops::Deref;
 use std::str::FromStr;
@@ -19,7 +19,7 @@ use chrono::{DateTime, NaiveDateTime, NaiveTime, Utc};
 use dec::OrderedDecimal;
 use itertools::{Either, Itertools};
 use mz_lowertest::MzReflect;
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
 
 use mz_ore::soft_assert_or_log;
 use mz_ore::str::separated;
@@ -27,8 +27,10 @@ use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::date::Date;
 use mz_repr::adt::interval::Interval;
+use mz_repr::adt::jsonpath::JsonPath;
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
 use mz_repr::adt::regex::Regex as ReprRegex;
+use mz_repr::adt::sketch::{HyperLogLog, QuantileSketch};
 use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampLike};
 use mz_repr::{
     ColumnName, ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType, SharedRow,
@@ -56,7 +58,7 @@ use crate::relation::{
     ColumnOrder, ProtoAggregateFunc, ProtoTableFunc, WindowFrame, WindowFrameBound,
     WindowFrameUnits, compare_columns, proto_table_func,
 };
-use crate::scalar::func::{add_timestamp_months, jsonb_stringify};
+use crate::scalar::func::{add_timestamp_months, jsonb_path_eval, jsonb_stringify};
 
 include!(concat!(env!("OUT_DIR"), "/mz_expr.relation.func.rs"));
 
@@ -280,6 +282,269 @@ where
     })
 }
 
+/// A run of equal inputs of an ordered-set aggregate (`percentile_cont`, `percentile_disc` or
+/// `mode`): the input datum, its decoded `order_by` columns, and how often it occurs.
+///
+/// Each input datum is a record whose first field is the aggregated payload, and whose remaining
+/// fields are the `order_by` columns.
+type OrderedSetRun<'a> = (Datum<'a>, Vec<Datum<'a>>, u64);
+
+fn ordered_set_run<'a>(
+    input: Datum<'a>,
+    count: u64,
+    order_by: &[ColumnOrder],
+) -> OrderedSetRun<'a> {
+    // We decode the order_by Datums upfront, for the same reason as in
+    // `order_aggregate_datums_with_rank_inner`.
+    let order_by_datums = input
+        .unwrap_list()
+        .iter()
+        .skip(1)
+        .take(order_by.len())
+        .collect();
+    (input, order_by_datums, count)
+}
+
+/// Compares runs by the `WITHIN GROUP` ordering, breaking ties by the whole input, so that only
+/// equal inputs compare equal.
+fn compare_ordered_set_runs(
+    order_by: &[ColumnOrder],
+    (left, left_order_by_datums, _): &OrderedSetRun,
+    (right, right_order_by_datums, _): &OrderedSetRun,
+) -> Ordering {
+    compare_columns(
+        order_by,
+        left_order_by_datums,
+        right_order_by_datums,
+        || left.cmp(right),
+    )
+}
+
+/// Merges adjacent runs of equal inputs.
+fn coalesce_ordered_set_runs<'a, I>(runs: I) -> Vec<OrderedSetRun<'a>>
+where
+    I: IntoIterator<Item = OrderedSetRun<'a>>,
+{
+    let mut coalesced: Vec<OrderedSetRun> = Vec::new();
+    for run in runs {
+        match coalesced.last_mut() {
+            Some(last) if last.0 == run.0 => last.2 = last.2.saturating_add(run.2),
+            _ => coalesced.push(run),
+        }
+    }
+    coalesced
+}
+
+/// Sorts the inputs of an ordered-set aggregate, each given with the number of times it occurs,
+/// into runs of equal inputs.
+fn ordered_set_runs<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Vec<OrderedSetRun<'a>>
+where
+    I: IntoIterator<Item = (Datum<'a>, u64)>,
+{
+    let mut runs: Vec<_> = datums
+        .into_iter()
+        .filter(|(d, count)| !d.is_null() && *count > 0)
+        .map(|(d, count)| ordered_set_run(d, count, order_by))
+        .collect();
+    runs.sort_by(|left, right| compare_ordered_set_runs(order_by, left, right));
+    coalesce_ordered_set_runs(runs)
+}
+
+/// Merges partial results of an ordered-set aggregate, each given with the number of times it
+/// occurs, into runs of equal inputs.
+///
+/// A partial result is a list of `[input, count]` pairs that is already sorted by the
+/// `WITHIN GROUP` ordering, so the partial results are merged rather than sorted.
+fn merge_ordered_set_partials<'a, I>(
+    partials: I,
+    order_by: &[ColumnOrder],
+) -> Vec<OrderedSetRun<'a>>
+where
+    I: IntoIterator<Item = (Datum<'a>, u64)>,
+{
+    let partials: Vec<Vec<_>> = partials
+        .into_iter()
+        .filter(|(partial, multiplicity)| !partial.is_null() && *multiplicity > 0)
+        .map(|(partial, multiplicity)| {
+            partial
+                .unwrap_list()
+                .iter()
+                .map(|entry| {
+                    let mut entry = entry.unwrap_list().iter();
+                    let input = entry.next().unwrap();
+                    let count = entry.next().unwrap().unwrap_uint64();
+                    ordered_set_run(input, count.saturating_mul(multiplicity), order_by)
+                })
+                .collect()
+        })
+        .collect();
+    coalesce_ordered_set_runs(
+        partials.into_iter().kmerge_by(|left, right| {
+            compare_ordered_set_runs(order_by, left, right) == Ordering::Less
+        }),
+    )
+}
+
+/// Packs runs of an ordered-set aggregate into a partial result, as read by
+/// `merge_ordered_set_partials`.
+fn pack_ordered_set_partial<'a>(runs: &[OrderedSetRun], temp_storage: &'a RowArena) -> Datum<'a> {
+    temp_storage.make_datum(|packer| {
+        packer.push_list_with(|packer| {
+            for (input, _, count) in runs {
+                packer.push_list([*input, Datum::UInt64(*count)]);
+            }
+        })
+    })
+}
+
+/// Returns the non-null values of a `percentile_cont` or `percentile_disc` aggregation with the
+/// number of times each occurs, the total number of non-null values, and the requested fraction.
+///
+/// The payload of each input is a `(value, fraction)` record. Returns `None` if there are no
+/// non-null values or if the fraction is null.
+fn percentile_values<'a>(runs: &[OrderedSetRun<'a>]) -> Option<(Vec<(Datum<'a>, u64)>, u64, f64)> {
+    let mut fraction = None;
+    let mut total = 0u64;
+    let mut values = Vec::new();
+    for (input, _, count) in runs {
+        let payload = input.unwrap_list().iter().next().unwrap();
+        if payload.is_null() {
+            continue;
+        }
+        let mut value_fraction = payload.unwrap_list().iter();
+        let value = value_fraction.next().unwrap();
+        if fraction.is_none() {
+            let f = value_fraction.next().unwrap();
+            if !f.is_null() {
+                fraction = Some(f.unwrap_float64());
+            }
+        }
+        if !value.is_null() {
+            total = total.saturating_add(*count);
+            values.push((value, *count));
+        }
+    }
+    if values.is_empty() {
+        return None;
+    }
+    fraction.map(|fraction| (values, total, fraction))
+}
+
+/// Returns the value at `index` in the sequence in which each value is repeated as often as it
+/// occurs.
+fn nth_percentile_value<'a>(values: &[(Datum<'a>, u64)], mut index: u64) -> Datum<'a> {
+    for (value, count) in values {
+        if index < *count {
+            return *value;
+        }
+        index -= count;
+    }
+    values.last().unwrap().0
+}
+
+fn percentile_cont<'a>(runs: &[OrderedSetRun<'a>]) -> Datum<'a> {
+    let Some((values, total, fraction)) = percentile_values(runs) else {
+        return Datum::Null;
+    };
+    // Matches PostgreSQL: interpolate linearly between the two values adjacent to the requested
+    // position.
+    let position = fraction * f64::cast_lossy(total - 1);
+    let lower = position.floor();
+    let upper = position.ceil();
+    let lower_value = nth_percentile_value(&values, u64::cast_lossy(lower));
+    let upper_value = nth_percentile_value(&values, u64::cast_lossy(upper));
+    if lower == upper {
+        return lower_value;
+    }
+    let proportion = position - lower;
+    match (lower_value, upper_value) {
+        (Datum::Float64(lo), Datum::Float64(hi)) => Datum::from(*lo + (*hi - *lo) * proportion),
+        (Datum::Interval(lo), Datum::Interval(hi)) => {
+            // TODO: this should produce an error on overflow, but aggregate
+            // functions cannot presently produce errors.
+            lo.checked_mul(1.0 - proportion)
+                .zip(hi.checked_mul(proportion))
+                .and_then(|(lo, hi)| lo.checked_add(&hi))
+                .map_or(Datum::Null, Datum::Interval)
+        }
+        _ => unreachable!("percentile_cont only accepts float8 and interval values"),
+    }
+}
+
+fn percentile_disc<'a>(runs: &[OrderedSetRun<'a>]) -> Datum<'a> {
+    let Some((values, total, fraction)) = percentile_values(runs) else {
+        return Datum::Null;
+    };
+    // Matches PostgreSQL: return the first value whose position in the ordering is greater than
+    // or equal to the requested fraction.
+    let row = u64::cast_lossy((fraction * f64::cast_lossy(total)).ceil());
+    nth_percentile_value(&values, row.saturating_sub(1).min(total - 1))
+}
+
+fn mode<'a>(runs: &[OrderedSetRun<'a>]) -> Datum<'a> {
+    // Equal values are adjacent in the ordering, so the most frequent one can be found in a single
+    // pass over the runs. Ties go to the value that appears first in the ordering.
+    let mut best = (Datum::Null, 0);
+    let mut current = (Datum::Null, 0);
+    for (input, _, count) in runs {
+        let value = input.unwrap_list().iter().next().unwrap();
+        if value.is_null() {
+            continue;
+        }
+        if current.1 > 0 && current.0 == value {
+            current.1 += count;
+        } else {
+            current = (value, *count);
+        }
+        if current.1 > best.1 {
+            best = current;
+        }
+    }
+    best.0
+}
+
+fn hll_merge<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut merged = None;
+    for sketch in datums.into_iter().filter(|d| !d.is_null()) {
+        let merged = merged.get_or_insert_with(HyperLogLog::new);
+        merged
+            .merge_encoded(sketch.unwrap_bytes())
+            .expect("sketches are validated when they enter the aggregation");
+    }
+    match merged {
+        Some(merged) => Datum::Bytes(temp_storage.push_bytes(merged.encode())),
+        None => Datum::Null,
+    }
+}
+
+fn quantile_sketch_merge<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    quantile_sketch_merge_scaled(datums.into_iter().map(|d| (d, 1)), temp_storage)
+}
+
+/// Merges quantile sketches, each of which is given with the number of times it occurs.
+fn quantile_sketch_merge_scaled<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = (Datum<'a>, u64)>,
+{
+    let mut merged = None;
+    for (sketch, factor) in datums.into_iter().filter(|(d, _)| !d.is_null()) {
+        let merged = merged.get_or_insert_with(QuantileSketch::new);
+        let sketch = QuantileSketch::decode(sketch.unwrap_bytes())
+            .expect("sketches are validated when they enter the aggregation");
+        merged.merge_scaled(&sketch, factor);
+    }
+    match merged {
+        Some(merged) => Datum::Bytes(temp_storage.push_bytes(merged.encode())),
+        None => Datum::Null,
+    }
+}
+
 /// Assuming datums is a List, sort them by the 2nd through Nth elements
 /// corresponding to order_by, then return the 1st element.
 ///
@@ -580,6 +845,141 @@ where
     })
 }
 
+/// The expected input is in the format of `[((OriginalRow, [EncodedArgs]), OrderByExprs...)]`
+/// The output is in the format of `[result_value, original_row]`.
+/// See an example at `lag_lead`, where the input-output formats are similar.
+fn percent_rank<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let datums = percent_rank_no_list(datums, &temp_storage, order_by);
+
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(datums);
+    })
+}
+
+/// Like `percent_rank`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn percent_rank_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (rows, order_by_rows) = unnest_ranked_datums(datums, order_by);
+    let peer_groups = peer_group_bounds(&order_by_rows);
+    let count = rows.len();
+
+    callers_temp_storage.reserve(count);
+    rows.into_iter()
+        .zip_eq(peer_groups)
+        .map(move |(d, (peer_group_start, _))| {
+            // The number of rows before the peer group is the rank minus one.
+            let percent_rank = if count > 1 {
+                f64::cast_lossy(peer_group_start) / f64::cast_lossy(count - 1)
+            } else {
+                0.0
+            };
+            callers_temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(Datum::from(percent_rank));
+                    packer.push(d);
+                });
+            })
+        })
+}
+
+/// The expected input is in the format of `[((OriginalRow, [EncodedArgs]), OrderByExprs...)]`
+/// The output is in the format of `[result_value, original_row]`.
+/// See an example at `lag_lead`, where the input-output formats are similar.
+fn cume_dist<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let datums = cume_dist_no_list(datums, &temp_storage, order_by);
+
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(datums);
+    })
+}
+
+/// Like `cume_dist`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn cume_dist_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (rows, order_by_rows) = unnest_ranked_datums(datums, order_by);
+    let peer_groups = peer_group_bounds(&order_by_rows);
+    let count = rows.len();
+
+    callers_temp_storage.reserve(count);
+    rows.into_iter()
+        .zip_eq(peer_groups)
+        .map(move |(d, (_, peer_group_end))| {
+            // The peer group end is the number of rows preceding or peer with the current row.
+            let cume_dist = f64::cast_lossy(peer_group_end) / f64::cast_lossy(count);
+            callers_temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(Datum::from(cume_dist));
+                    packer.push(d);
+                });
+            })
+        })
+}
+
+/// Sorts the input of a ranking window function, and returns the original rows together with
+/// their OrderByRows, which are needed to determine the peer groups.
+fn unnest_ranked_datums<'a, I>(datums: I, order_by: &[ColumnOrder]) -> (Vec<Datum<'a>>, Vec<Row>)
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    order_aggregate_datums_with_rank(datums, order_by)
+        .flat_map(|(d0, order_row)| {
+            d0.unwrap_list()
+                .iter()
+                .map(move |d1| (d1, order_row.clone()))
+        })
+        .unzip()
+}
+
+/// Given the OrderByRows of a sorted window partition, returns for each row the index of the
+/// first row of its peer group, and the index just after the last row of its peer group.
+fn peer_group_bounds(order_by_rows: &[Row]) -> Vec<(usize, usize)> {
+    let mut bounds = Vec::with_capacity(order_by_rows.len());
+    let mut peer_group_start = 0;
+    while peer_group_start < order_by_rows.len() {
+        let peer_group_len = order_by_rows[peer_group_start..]
+            .iter()
+            .take_while(|row| **row == order_by_rows[peer_group_start])
+            .count();
+        let peer_group_end = peer_group_start + peer_group_len;
+        bounds.extend(iter::repeat_n(
+            (peer_group_start, peer_group_end),
+            peer_group_len,
+        ));
+        peer_group_start = peer_group_end;
+    }
+    bounds
+}
+
 /// The expected input is in the format of `[((OriginalRow, EncodedArgs), OrderByExprs...)]`
 /// For example,
 ///
@@ -1120,6 +1520,230 @@ fn last_value_inner<'a>(
     results
 }
 
+/// The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
+fn ntile<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let iter = ntile_no_list(datums, &temp_storage, order_by);
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(iter);
+    })
+}
+
+/// Like `ntile`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn ntile_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the (OriginalRow, InputValue) record
+    let datums = order_aggregate_datums(datums, order_by);
+
+    // Decode the input (OriginalRow, InputValue) into separate datums
+    let (orig_rows, args): (Vec<_>, Vec<_>) = datums
+        .into_iter()
+        .map(|d| {
+            let mut iter = d.unwrap_list().iter();
+            let original_row = iter.next().unwrap();
+            let arg = iter.next().unwrap();
+
+            (original_row, arg)
+        })
+        .unzip();
+
+    let results = ntile_inner(args);
+
+    callers_temp_storage.reserve(results.len());
+    results
+        .into_iter()
+        .zip_eq(orig_rows)
+        .map(|(result_value, original_row)| {
+            callers_temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(result_value);
+                    packer.push(original_row);
+                });
+            })
+        })
+}
+
+/// Divides the window partition into as equal buckets as possible, where the number of buckets
+/// is the argument at the first row of the partition, as in Postgres. The first
+/// `length % buckets` buckets get one more row than the others.
+fn ntile_inner<'a>(args: Vec<Datum<'a>>) -> Vec<Datum<'a>> {
+    let length = args.len();
+    // A null or non-positive bucket count makes all results null. (Non-positive bucket counts are
+    // rejected by an error check added during planning.)
+    let buckets = match args.first() {
+        Some(Datum::Int32(buckets)) if *buckets > 0 => usize::cast_from(buckets.unsigned_abs()),
+        _ => return vec![Datum::Null; length],
+    };
+    let small_bucket_len = length / buckets;
+    let large_bucket_len = small_bucket_len + 1;
+    let large_buckets_total_len = (length % buckets) * large_bucket_len;
+    (0..length)
+        .map(|idx| {
+            let bucket = if idx < large_buckets_total_len {
+                idx / large_bucket_len
+            } else {
+                length % buckets + (idx - large_buckets_total_len) / small_bucket_len
+            };
+            Datum::Int32(i32::try_from(bucket + 1).expect("at most the number of buckets"))
+        })
+        .collect()
+}
+
+/// The expected input is in the format of [((OriginalRow, (InputValue, N)), OrderByExprs...)]
+fn nth_value<'a, I>(
+    datums: I,
+    callers_temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let temp_storage = RowArena::new();
+    let iter = nth_value_no_list(datums, &temp_storage, order_by, window_frame);
+    callers_temp_storage.make_datum(|packer| {
+        packer.push_list(iter);
+    })
+}
+
+/// Like `nth_value`, but doesn't perform the final wrapping in a list, returning an Iterator
+/// instead.
+fn nth_value_no_list<'a: 'b, 'b, I>(
+    datums: I,
+    callers_temp_storage: &'b RowArena,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> impl Iterator<Item = Datum<'b>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, (InputValue, N)), OrderByRow) record
+    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE mode
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
+
+    // Decode the input (OriginalRow, (InputValue, N)) into separate datums, while keeping the OrderByRow
+    let size_hint = datums.size_hint().0;
+    let mut args = Vec::with_capacity(size_hint);
+    let mut original_rows = Vec::with_capacity(size_hint);
+    let mut order_by_rows = Vec::with_capacity(size_hint);
+    for (d, order_by_row) in datums.into_iter() {
+        let mut iter = d.unwrap_list().iter();
+        let original_row = iter.next().unwrap();
+        let encoded_args = iter.next().unwrap();
+        order_by_rows.push(order_by_row);
+        original_rows.push(original_row);
+        args.push(unwrap_nth_value_encoded_args(encoded_args));
+    }
+
+    let results = nth_value_inner(args, &order_by_rows, window_frame);
+
+    callers_temp_storage.reserve(results.len());
+    results
+        .into_iter()
+        .zip_eq(original_rows)
+        .map(|(result_value, original_row)| {
+            callers_temp_storage.make_datum(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push(result_value);
+                    packer.push(original_row);
+                });
+            })
+        })
+}
+
+/// `nth_value` has 2 arguments, which are encoded into a record: the value and `n`.
+fn unwrap_nth_value_encoded_args(encoded_args: Datum) -> (Datum, Datum) {
+    let mut encoded_args_iter = encoded_args.unwrap_list().iter();
+    let (input_value, n) = (
+        encoded_args_iter.next().unwrap(),
+        encoded_args_iter.next().unwrap(),
+    );
+    (input_value, n)
+}
+
+fn nth_value_inner<'a>(
+    args: Vec<(Datum<'a>, Datum<'a>)>,
+    order_by_rows: &Vec<Row>,
+    window_frame: &WindowFrame,
+) -> Vec<Datum<'a>> {
+    let length = args.len();
+    let peer_groups = match &window_frame.units {
+        WindowFrameUnits::Range => peer_group_bounds(order_by_rows),
+        WindowFrameUnits::Rows => Vec::new(),
+        // GROUPS is not supported, and forbidden during planning
+        WindowFrameUnits::Groups => unreachable!(),
+    };
+    let mut results: Vec<Datum> = Vec::with_capacity(length);
+    for (idx, (_, n)) in args.iter().enumerate() {
+        // A null or non-positive `n` makes the result null. (Non-positive values are rejected by
+        // an error check added during planning.)
+        let n = match n {
+            Datum::Int32(n) if *n > 0 => usize::cast_from(n.unsigned_abs()),
+            _ => {
+                results.push(Datum::Null);
+                continue;
+            }
+        };
+        // The first index of the frame. This can point past the end of the partition.
+        let frame_start = match &window_frame.start_bound {
+            WindowFrameBound::UnboundedPreceding => 0,
+            WindowFrameBound::OffsetPreceding(offset) => {
+                idx.saturating_sub(usize::cast_from(*offset))
+            }
+            // Range is only supported for the default window frame (RANGE BETWEEN UNBOUNDED
+            // PRECEDING AND CURRENT ROW), so the frame can start at the current row only in ROWS
+            // mode.
+            WindowFrameBound::CurrentRow => idx,
+            WindowFrameBound::OffsetFollowing(offset) => {
+                idx.saturating_add(usize::cast_from(*offset))
+            }
+            // Forbidden during planning
+            WindowFrameBound::UnboundedFollowing => unreachable!(),
+        };
+        // The last index of the frame, or `None` if the frame ends before the first row.
+        let frame_end = match &window_frame.end_bound {
+            WindowFrameBound::UnboundedFollowing => Some(length - 1),
+            WindowFrameBound::OffsetFollowing(offset) => Some(min(
+                idx.saturating_add(usize::cast_from(*offset)),
+                length - 1,
+            )),
+            WindowFrameBound::CurrentRow => match &window_frame.units {
+                WindowFrameUnits::Rows => Some(idx),
+                // When in RANGE mode, the frame ends with the last row of the peer group
+                WindowFrameUnits::Range => Some(peer_groups[idx].1 - 1),
+                WindowFrameUnits::Groups => unreachable!(),
+            },
+            WindowFrameBound::OffsetPreceding(offset) => idx.checked_sub(usize::cast_from(*offset)),
+            // Forbidden during planning
+            WindowFrameBound::UnboundedPreceding => unreachable!(),
+        };
+        let nth_value = match frame_end {
+            Some(frame_end) => match frame_start.checked_add(n - 1) {
+                // Return null if the frame is empty or has fewer than `n` rows
+                Some(target_idx) if target_idx <= frame_end => args[target_idx].0,
+                _ => Datum::Null,
+            },
+            None => Datum::Null,
+        };
+        results.push(nth_value);
+    }
+    results
+}
+
 /// Executes `FusedValueWindowFunc` on a reduction group.
 /// The expected input is in the format of `[((OriginalRow, (Args1, Args2, ...)), OrderByExprs...)]`
 /// where `Args1`, `Args2`, are the arguments of each of the fused functions. For functions that
@@ -1152,9 +1776,13 @@ fn fused_value_window_func_no_list<'a: 'b, 'b, I>(
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
-    let has_last_value = funcs
-        .iter()
-        .any(|f| matches!(f, AggregateFunc::LastValue { .. }));
+    // The OrderByRows are needed for computing the peer groups in RANGE mode.
+    let needs_order_by_rows = funcs.iter().any(|f| {
+        matches!(
+            f,
+            AggregateFunc::LastValue { .. } | AggregateFunc::NthValue { .. }
+        )
+    });
 
     let input_datums_with_ranks = order_aggregate_datums_with_rank(input_datums, order_by);
 
@@ -1171,7 +1799,7 @@ where
             let encoded_args = argss_iter.next().unwrap();
             encoded_argsss[i].push(encoded_args);
         }
-        if has_last_value {
+        if needs_order_by_rows {
             order_by_rows.push(order_by_row);
         }
     }
@@ -1209,6 +1837,25 @@ where
                 // wrapped into a record.)
                 last_value_inner(encoded_argss, &order_by_rows, window_frame)
             }
+            AggregateFunc::Ntile {
+                order_by: inner_order_by,
+            } => {
+                assert_eq!(order_by, inner_order_by);
+                // (No unwrapping to do on the args here, because there is only 1 arg, so it's not
+                // wrapped into a record.)
+                ntile_inner(encoded_argss)
+            }
+            AggregateFunc::NthValue {
+                order_by: inner_order_by,
+                window_frame,
+            } => {
+                assert_eq!(order_by, inner_order_by);
+                let unwrapped_argss = encoded_argss
+                    .into_iter()
+                    .map(|encoded_args| unwrap_nth_value_encoded_args(encoded_args))
+                    .collect();
+                nth_value_inner(unwrapped_argss, &order_by_rows, window_frame)
+            }
             _ => panic!("unknown window function in FusedValueWindowFunc"),
         };
         for (results, result) in results_per_row.iter_mut().zip_eq(results) {
@@ -1887,6 +2534,32 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Computes a continuous percentile over `Datum::Float64`s or `Datum::Interval`s, interpolating
+    /// between adjacent values if needed. Accepts `Datum::List`s whose first element is a
+    /// `(value, fraction)` record; the other elements are the `WITHIN GROUP` columns used by
+    /// `order_by`.
+    PercentileCont {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `PercentileCont`, but returns the first input value whose position in the ordering
+    /// equals or exceeds the fraction, without interpolating.
+    PercentileDisc {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Returns the most frequent value. Accepts `Datum::List`s whose first element is the value;
+    /// the other elements are the `WITHIN GROUP` columns used by `order_by`, which also decide
+    /// between equally frequent values.
+    Mode {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Merges `hll_sketch` values into a single [`HyperLogLog`] sketch.
+    ///
+    /// Merging cannot report errors, so the input expression must validate the sketches, which
+    /// turns an invalid sketch into an error before it reaches the aggregation.
+    HllMerge,
+    /// Merges `quantile_sketch` values into a single [`QuantileSketch`]. See
+    /// [`AggregateFunc::HllMerge`] about validating the sketches.
+    QuantileSketchMerge,
     RowNumber {
         order_by: Vec<ColumnOrder>,
     },
@@ -1896,6 +2569,18 @@ pub enum AggregateFunc {
     DenseRank {
         order_by: Vec<ColumnOrder>,
     },
+    /// The relative rank of each row, `(rank - 1) / (partition rows - 1)`.
+    PercentRank {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// The fraction of partition rows that precede or are peers of each row.
+    CumeDist {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Divides the partition into the given number of buckets, numbering them from 1.
+    Ntile {
+        order_by: Vec<ColumnOrder>,
+    },
     LagLead {
         order_by: Vec<ColumnOrder>,
         lag_lead: LagLeadType,
@@ -1909,6 +2594,11 @@ pub enum AggregateFunc {
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
     },
+    /// The value at the `n`th row of the window frame, counting from 1.
+    NthValue {
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
     /// Several value window functions fused into one function, to amortize overheads.
     FusedValueWindowFunc {
         funcs: Vec<AggregateFunc>,
@@ -2018,12 +2708,32 @@ impl Arbitrary for AggregateFunc {
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::StringAgg { order_by })
                 .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::PercentileCont { order_by })
+                .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::PercentileDisc { order_by })
+                .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::Mode { order_by })
+                .boxed(),
+            Just(AggregateFunc::HllMerge).boxed(),
+            Just(AggregateFunc::QuantileSketchMerge).boxed(),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::RowNumber { order_by })
                 .boxed(),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::DenseRank { order_by })
                 .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::PercentRank { order_by })
+                .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::CumeDist { order_by })
+                .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::Ntile { order_by })
+                .boxed(),
             (
                 vec(proptest_any::<ColumnOrder>(), 1..4),
                 proptest_any::<LagLeadType>(),
@@ -2055,6 +2765,15 @@ impl Arbitrary for AggregateFunc {
                     window_frame,
                 })
                 .boxed(),
+            (
+                vec(proptest_any::<ColumnOrder>(), 1..4),
+                proptest_any::<WindowFrame>(),
+            )
+                .prop_map(|(order_by, window_frame)| AggregateFunc::NthValue {
+                    order_by,
+                    window_frame,
+                })
+                .boxed(),
             Just(AggregateFunc::Dummy).boxed(),
         ])
     }
@@ -2137,9 +2856,21 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::ArrayConcat { order_by } => Kind::ArrayConcat(order_by.into_proto()),
                 AggregateFunc::ListConcat { order_by } => Kind::ListConcat(order_by.into_proto()),
                 AggregateFunc::StringAgg { order_by } => Kind::StringAgg(order_by.into_proto()),
+                AggregateFunc::PercentileCont { order_by } => {
+                    Kind::PercentileCont(order_by.into_proto())
+                }
+                AggregateFunc::PercentileDisc { order_by } => {
+                    Kind::PercentileDisc(order_by.into_proto())
+                }
+                AggregateFunc::Mode { order_by } => Kind::Mode(order_by.into_proto()),
+                AggregateFunc::HllMerge => Kind::HllMerge(()),
+                AggregateFunc::QuantileSketchMerge => Kind::QuantileSketchMerge(()),
                 AggregateFunc::RowNumber { order_by } => Kind::RowNumber(order_by.into_proto()),
                 AggregateFunc::Rank { order_by } => Kind::Rank(order_by.into_proto()),
                 AggregateFunc::DenseRank { order_by } => Kind::DenseRank(order_by.into_proto()),
+                AggregateFunc::PercentRank { order_by } => Kind::PercentRank(order_by.into_proto()),
+                AggregateFunc::CumeDist { order_by } => Kind::CumeDist(order_by.into_proto()),
+                AggregateFunc::Ntile { order_by } => Kind::Ntile(order_by.into_proto()),
                 AggregateFunc::LagLead {
                     order_by,
                     lag_lead,
@@ -2168,6 +2899,13 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
                 }),
+                AggregateFunc::NthValue {
+                    order_by,
+                    window_frame,
+                } => Kind::NthValue(proto_aggregate_func::ProtoFramedWindowFunc {
+                    order_by: Some(order_by.into_proto()),
+                    window_frame: Some(window_frame.into_proto()),
+                }),
                 AggregateFunc::WindowAggregate {
                     wrapped_aggregate,
                     order_by,
@@ -2270,6 +3008,17 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::StringAgg(order_by) => AggregateFunc::StringAgg {
                 order_by: order_by.into_rust()?,
             },
+            Kind::PercentileCont(order_by) => AggregateFunc::PercentileCont {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::PercentileDisc(order_by) => AggregateFunc::PercentileDisc {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::Mode(order_by) => AggregateFunc::Mode {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::HllMerge(()) => AggregateFunc::HllMerge,
+            Kind::QuantileSketchMerge(()) => AggregateFunc::QuantileSketchMerge,
             Kind::RowNumber(order_by) => AggregateFunc::RowNumber {
                 order_by: order_by.into_rust()?,
             },
@@ -2279,6 +3028,15 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::DenseRank(order_by) => AggregateFunc::DenseRank {
                 order_by: order_by.into_rust()?,
             },
+            Kind::PercentRank(order_by) => AggregateFunc::PercentRank {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::CumeDist(order_by) => AggregateFunc::CumeDist {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::Ntile(order_by) => AggregateFunc::Ntile {
+                order_by: order_by.into_rust()?,
+            },
             Kind::LagLead(pll) => AggregateFunc::LagLead {
                 order_by: pll.order_by.into_rust_if_some("ProtoLagLead::order_by")?,
                 lag_lead: match pll.lag_lead {
@@ -2312,6 +3070,14 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     .window_frame
                     .into_rust_if_some("ProtoFramedWindowFunc::window_frame")?,
             },
+            Kind::NthValue(pfv) => AggregateFunc::NthValue {
+                order_by: pfv
+                    .order_by
+                    .into_rust_if_some("ProtoFramedWindowFunc::order_by")?,
+                window_frame: pfv
+                    .window_frame
+                    .into_rust_if_some("ProtoFramedWindowFunc::window_frame")?,
+            },
             Kind::WindowAggregate(paf) => AggregateFunc::WindowAggregate {
                 wrapped_aggregate: paf
                     .wrapped_aggregate
@@ -2414,9 +3180,19 @@ impl AggregateFunc {
             AggregateFunc::ArrayConcat { order_by } => array_concat(datums, temp_storage, order_by),
             AggregateFunc::ListConcat { order_by } => list_concat(datums, temp_storage, order_by),
             AggregateFunc::StringAgg { order_by } => string_agg(datums, temp_storage, order_by),
+            AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. } => {
+                self.eval_with_multiplicities(datums.into_iter().map(|d| (d, 1)), temp_storage)
+            }
+            AggregateFunc::HllMerge => hll_merge(datums, temp_storage),
+            AggregateFunc::QuantileSketchMerge => quantile_sketch_merge(datums, temp_storage),
             AggregateFunc::RowNumber { order_by } => row_number(datums, temp_storage, order_by),
             AggregateFunc::Rank { order_by } => rank(datums, temp_storage, order_by),
             AggregateFunc::DenseRank { order_by } => dense_rank(datums, temp_storage, order_by),
+            AggregateFunc::PercentRank { order_by } => percent_rank(datums, temp_storage, order_by),
+            AggregateFunc::CumeDist { order_by } => cume_dist(datums, temp_storage, order_by),
+            AggregateFunc::Ntile { order_by } => ntile(datums, temp_storage, order_by),
             AggregateFunc::LagLead {
                 order_by,
                 lag_lead: lag_lead_type,
@@ -2430,6 +3206,10 @@ impl AggregateFunc {
                 order_by,
                 window_frame,
             } => last_value(datums, temp_storage, order_by, window_frame),
+            AggregateFunc::NthValue {
+                order_by,
+                window_frame,
+            } => nth_value(datums, temp_storage, order_by, window_frame),
             AggregateFunc::WindowAggregate {
                 wrapped_aggregate,
                 order_by,
@@ -2459,6 +3239,103 @@ impl AggregateFunc {
         }
     }
 
+    /// Like `eval`, but it's given the distinct values of the group, each with the number of
+    /// times it occurs.
+    ///
+    /// Hierarchical reductions only see distinct values. Most hierarchical aggregates are
+    /// idempotent and ignore the multiplicities, but merging quantile sketches adds their bucket
+    /// counts, so a sketch has to be merged as many times as it occurs, and ordered-set aggregates
+    /// count how often each value occurs.
+    pub fn eval_with_multiplicities<'a, I>(
+        &self,
+        datums: I,
+        temp_storage: &'a RowArena,
+    ) -> Datum<'a>
+    where
+        I: IntoIterator<Item = (Datum<'a>, u64)>,
+    {
+        match self {
+            AggregateFunc::QuantileSketchMerge => {
+                quantile_sketch_merge_scaled(datums, temp_storage)
+            }
+            AggregateFunc::PercentileCont { order_by } => {
+                percentile_cont(&ordered_set_runs(datums, order_by))
+            }
+            AggregateFunc::PercentileDisc { order_by } => {
+                percentile_disc(&ordered_set_runs(datums, order_by))
+            }
+            AggregateFunc::Mode { order_by } => mode(&ordered_set_runs(datums, order_by)),
+            _ => self.eval(datums.into_iter().map(|(d, _)| d), temp_storage),
+        }
+    }
+
+    /// Returns whether the partial results that hierarchical reductions compute for this
+    /// aggregate differ from its results.
+    ///
+    /// Ordered-set aggregates cannot be computed from the results of subsets of the group, so
+    /// their partial results are the sorted inputs of the subset, with the number of times each
+    /// occurs. Such partial results cannot be accumulated in a monoid, so these aggregates are
+    /// never rendered as monotonic reductions.
+    pub fn has_partial_state(&self) -> bool {
+        matches!(
+            self,
+            AggregateFunc::PercentileCont { .. }
+                | AggregateFunc::PercentileDisc { .. }
+                | AggregateFunc::Mode { .. }
+        )
+    }
+
+    /// Converts an input of a hierarchical reduction to a partial result that stands for just
+    /// that input. See [`AggregateFunc::has_partial_state`].
+    pub fn partial_input<'a>(&self, datum: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+        match self {
+            AggregateFunc::PercentileCont { order_by }
+            | AggregateFunc::PercentileDisc { order_by }
+            | AggregateFunc::Mode { order_by } => {
+                let runs = ordered_set_runs([(datum, 1)], order_by);
+                pack_ordered_set_partial(&runs, temp_storage)
+            }
+            _ => datum,
+        }
+    }
+
+    /// Combines partial results of a hierarchical reduction, each given with the number of times
+    /// it occurs, into a partial result for their union.
+    pub fn combine_partials<'a, I>(&self, partials: I, temp_storage: &'a RowArena) -> Datum<'a>
+    where
+        I: IntoIterator<Item = (Datum<'a>, u64)>,
+    {
+        match self {
+            AggregateFunc::PercentileCont { order_by }
+            | AggregateFunc::PercentileDisc { order_by }
+            | AggregateFunc::Mode { order_by } => {
+                let runs = merge_ordered_set_partials(partials, order_by);
+                pack_ordered_set_partial(&runs, temp_storage)
+            }
+            _ => self.eval_with_multiplicities(partials, temp_storage),
+        }
+    }
+
+    /// Computes the result of a hierarchical reduction from partial results, each given with the
+    /// number of times it occurs.
+    pub fn eval_partials<'a, I>(&self, partials: I, temp_storage: &'a RowArena) -> Datum<'a>
+    where
+        I: IntoIterator<Item = (Datum<'a>, u64)>,
+    {
+        match self {
+            AggregateFunc::PercentileCont { order_by } => {
+                percentile_cont(&merge_ordered_set_partials(partials, order_by))
+            }
+            AggregateFunc::PercentileDisc { order_by } => {
+                percentile_disc(&merge_ordered_set_partials(partials, order_by))
+            }
+            AggregateFunc::Mode { order_by } => {
+                mode(&merge_ordered_set_partials(partials, order_by))
+            }
+            _ => self.eval_with_multiplicities(partials, temp_storage),
+        }
+    }
+
     /// Like `eval`, but it's given a [OneByOneAggr]. If `self` is a `WindowAggregate`, then
     /// the given [OneByOneAggr] will be used to evaluate the wrapped aggregate inside the
     /// `WindowAggregate`. If `self` is not a `WindowAggregate`, then it simply calls `eval`.
@@ -2519,6 +3396,15 @@ impl AggregateFunc {
             AggregateFunc::DenseRank { order_by } => {
                 dense_rank_no_list(datums, temp_storage, order_by).collect_vec()
             }
+            AggregateFunc::PercentRank { order_by } => {
+                percent_rank_no_list(datums, temp_storage, order_by).collect_vec()
+            }
+            AggregateFunc::CumeDist { order_by } => {
+                cume_dist_no_list(datums, temp_storage, order_by).collect_vec()
+            }
+            AggregateFunc::Ntile { order_by } => {
+                ntile_no_list(datums, temp_storage, order_by).collect_vec()
+            }
             AggregateFunc::LagLead {
                 order_by,
                 lag_lead: lag_lead_type,
@@ -2533,6 +3419,10 @@ impl AggregateFunc {
                 order_by,
                 window_frame,
             } => last_value_no_list(datums, temp_storage, order_by, window_frame).collect_vec(),
+            AggregateFunc::NthValue {
+                order_by,
+                window_frame,
+            } => nth_value_no_list(datums, temp_storage, order_by, window_frame).collect_vec(),
             AggregateFunc::FusedValueWindowFunc { funcs, order_by } => {
                 fused_value_window_func_no_list(datums, temp_storage, funcs, order_by).collect_vec()
             }
@@ -2589,9 +3479,13 @@ impl AggregateFunc {
             AggregateFunc::RowNumber { .. }
             | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. }
+            | AggregateFunc::Ntile { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
             | AggregateFunc::LastValue { .. }
+            | AggregateFunc::NthValue { .. }
             | AggregateFunc::WindowAggregate { .. }
             | AggregateFunc::FusedValueWindowFunc { .. }
             | AggregateFunc::FusedWindowAggregate { .. } => Datum::empty_list(),
@@ -2642,7 +3536,12 @@ impl AggregateFunc {
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
             | AggregateFunc::MapAgg { .. }
-            | AggregateFunc::StringAgg { .. } => Datum::Null,
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::HllMerge
+            | AggregateFunc::QuantileSketchMerge => Datum::Null,
         }
     }
 
@@ -2651,9 +3550,13 @@ impl AggregateFunc {
             AggregateFunc::RowNumber { .. }
             | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::PercentRank { .. }
+            | AggregateFunc::CumeDist { .. }
+            | AggregateFunc::Ntile { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
             | AggregateFunc::LastValue { .. }
+            | AggregateFunc::NthValue { .. }
             | AggregateFunc::WindowAggregate { .. }
             | AggregateFunc::FusedValueWindowFunc { .. }
             | AggregateFunc::FusedWindowAggregate { .. } => true,
@@ -2709,7 +3612,12 @@ impl AggregateFunc {
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
             | AggregateFunc::MapAgg { .. }
-            | AggregateFunc::StringAgg { .. } => false,
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::HllMerge
+            | AggregateFunc::QuantileSketchMerge => false,
         }
     }
 
@@ -2747,14 +3655,51 @@ impl AggregateFunc {
                 }
             }
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. } => {
+                // The input is wrapped in a Record whose first field is a (value, fraction) Record.
+                input_type.scalar_type.unwrap_record_element_type()[0]
+                    .unwrap_record_element_type()[0]
+                    .clone()
+            }
+            AggregateFunc::Mode { .. } => {
+                input_type.scalar_type.unwrap_record_element_type()[0].clone()
+            }
+            AggregateFunc::HllMerge => ScalarType::HllSketch,
+            AggregateFunc::QuantileSketchMerge => ScalarType::QuantileSketch,
             AggregateFunc::RowNumber { .. } => {
-                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?row_number?")
+                AggregateFunc::output_type_ranking_window_funcs(
+                    &input_type,
+                    "?row_number?",
+                    ScalarType::Int64,
+                )
             }
             AggregateFunc::Rank { .. } => {
-                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?rank?")
+                AggregateFunc::output_type_ranking_window_funcs(
+                    &input_type,
+                    "?rank?",
+                    ScalarType::Int64,
+                )
             }
             AggregateFunc::DenseRank { .. } => {
-                AggregateFunc::output_type_ranking_window_funcs(&input_type, "?dense_rank?")
+                AggregateFunc::output_type_ranking_window_funcs(
+                    &input_type,
+                    "?dense_rank?",
+                    ScalarType::Int64,
+                )
+            }
+            AggregateFunc::PercentRank { .. } => {
+                AggregateFunc::output_type_ranking_window_funcs(
+                    &input_type,
+                    "?percent_rank?",
+                    ScalarType::Float64,
+                )
+            }
+            AggregateFunc::CumeDist { .. } => {
+                AggregateFunc::output_type_ranking_window_funcs(
+                    &input_type,
+                    "?cume_dist?",
+                    ScalarType::Float64,
+                )
             }
             AggregateFunc::LagLead { lag_lead: lag_lead_type, .. } => {
                 // The input type for Lag is ((OriginalRow, EncodedArgs), OrderByExprs...)
@@ -2818,6 +3763,45 @@ impl AggregateFunc {
                     custom_id: None,
                 }
             }
+            AggregateFunc::Ntile { .. } => {
+                // The input type for Ntile is ((OriginalRow, Arg), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: [
+                            (ColumnName::from("?ntile?"), ScalarType::Int32.nullable(true)),
+                            (ColumnName::from("?orig_row?"), original_row_type),
+                        ].into(),
+                        custom_id: None,
+                    }),
+                    custom_id: None,
+                }
+            }
+            AggregateFunc::NthValue { .. } => {
+                // The input type for NthValue is ((OriginalRow, EncodedArgs), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+                let value_type = Self::nth_value_output_type_inner_from_encoded_args(
+                    fields[0].unwrap_record_element_type()[1],
+                );
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: [
+                            (ColumnName::from("?nth_value?"), value_type),
+                            (ColumnName::from("?orig_row?"), original_row_type),
+                        ].into(),
+                        custom_id: None,
+                    }),
+                    custom_id: None,
+                }
+            }
             AggregateFunc::WindowAggregate {
                 wrapped_aggregate, ..
             } => {
@@ -2877,8 +3861,8 @@ impl AggregateFunc {
             AggregateFunc::FusedValueWindowFunc { funcs, order_by: _ } => {
                 // The input type is ((OriginalRow, EncodedArgs), OrderByExprs...)
                 // where EncodedArgs is a record, where each element is the argument to one of the
-                // function calls that got fused. This is a record for lag/lead, and a simple type
-                // for first_value/last_value.
+                // function calls that got fused. This is a record for lag/lead/nth_value, and a
+                // simple type for first_value/last_value/ntile.
                 let fields = input_type.scalar_type.unwrap_record_element_type();
                 let original_row_type = fields[0].unwrap_record_element_type()[0]
                     .clone()
@@ -2909,6 +3893,18 @@ impl AggregateFunc {
                                                 arg_type.clone().nullable(true),
                                             )
                                         }
+                                        AggregateFunc::Ntile { .. } => {
+                                            (
+                                                ColumnName::from("?ntile?"),
+                                                ScalarType::Int32.nullable(true),
+                                            )
+                                        }
+                                        AggregateFunc::NthValue { .. } => {
+                                            (
+                                                ColumnName::from("?nth_value?"),
+                                                Self::nth_value_output_type_inner_from_encoded_args(arg_type),
+                                            )
+                                        }
                                         _ => panic!("FusedValueWindowFunc has an unknown function"),
                                     }
                                 }).collect(),
@@ -2977,21 +3973,28 @@ impl AggregateFunc {
                 },
                 _ => unreachable!(),
             },
+            // Ordered-set aggregates ignore null values and produce null if there are none left.
+            AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. } => true,
+            // Sketch merges produce null when there are no non-null sketches to merge.
+            AggregateFunc::HllMerge | AggregateFunc::QuantileSketchMerge => true,
             _ => input_type.nullable,
         };
         scalar_type.nullable(nullable)
     }
 
-    /// Compute output type for ROW_NUMBER, RANK, DENSE_RANK
-    fn output_type_ranking_window_funcs(input_type: &ColumnType, col_name: &str) -> ScalarType {
+    /// Compute output type for ROW_NUMBER, RANK, DENSE_RANK, PERCENT_RANK, CUME_DIST
+    fn output_type_ranking_window_funcs(
+        input_type: &ColumnType,
+        col_name: &str,
+        result_type: ScalarType,
+    ) -> ScalarType {
         match input_type.scalar_type {
             ScalarType::Record { ref fields, .. } => ScalarType::List {
                 element_type: Box::new(ScalarType::Record {
                     fields: [
-                        (
-                            ColumnName::from(col_name),
-                            ScalarType::Int64.nullable(false),
-                        ),
+                        (ColumnName::from(col_name), result_type.nullable(false)),
                         (ColumnName::from("?orig_row?"), {
                             let inner = match &fields[0].1.scalar_type {
                                 ScalarType::List { element_type, .. } => element_type.clone(),
@@ -3021,6 +4024,16 @@ impl AggregateFunc {
             .nullable(true)
     }
 
+    /// Given the `EncodedArgs` part of `((OriginalRow, EncodedArgs), OrderByExprs...)` of
+    /// `nth_value`, this computes the type of the first field of the output type.
+    fn nth_value_output_type_inner_from_encoded_args(encoded_args_type: &ScalarType) -> ColumnType {
+        // The output type is the same as the type of the value argument, but always nullable.
+        // (It's null when the frame has fewer than `n` rows.)
+        encoded_args_type.unwrap_record_element_type()[0]
+            .clone()
+            .nullable(true)
+    }
+
     fn lag_lead_result_column_name(lag_lead_type: &LagLeadType) -> ColumnName {
         ColumnName::from(match lag_lead_type {
             LagLeadType::Lag => "?lag?",
@@ -3127,6 +4140,16 @@ fn jsonb_array_elements<'a>(
     })
 }
 
+fn jsonb_path_query<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<impl Iterator<Item = (Row, Diff)> + 'a, EvalError> {
+    let items = jsonb_path_eval(datums, temp_storage, JsonPath::query)?.unwrap_or_default();
+    Ok(items
+        .into_iter()
+        .map(|item| (Row::pack_slice(&[item]), Diff::ONE)))
+}
+
 fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<(Row, Diff)> {
     let r = r.inner();
     let a = a.unwrap_str();
@@ -3381,9 +4404,17 @@ impl AggregateFunc {
             Self::ArrayConcat { .. } => "array_agg",
             Self::ListConcat { .. } => "list_agg",
             Self::StringAgg { .. } => "string_agg",
+            Self::PercentileCont { .. } => "percentile_cont",
+            Self::PercentileDisc { .. } => "percentile_disc",
+            Self::Mode { .. } => "mode",
+            Self::HllMerge => "hll_merge",
+            Self::QuantileSketchMerge => "quantile_sketch_merge",
             Self::RowNumber { .. } => "row_number",
             Self::Rank { .. } => "rank",
             Self::DenseRank { .. } => "dense_rank",
+            Self::PercentRank { .. } => "percent_rank",
+            Self::CumeDist { .. } => "cume_dist",
+            Self::Ntile { .. } => "ntile",
             Self::LagLead {
                 lag_lead: LagLeadType::Lag,
                 ..
@@ -3394,6 +4425,7 @@ impl AggregateFunc {
             } => "lead",
             Self::FirstValue { .. } => "first_value",
             Self::LastValue { .. } => "last_value",
+            Self::NthValue { .. } => "nth_value",
             Self::WindowAggregate { .. } => "window_agg",
             Self::FusedValueWindowFunc { .. } => "fused_value_window_func",
             Self::FusedWindowAggregate { .. } => "fused_window_agg",
@@ -3416,9 +4448,15 @@ where
             | ArrayConcat { order_by }
             | ListConcat { order_by }
             | StringAgg { order_by }
+            | PercentileCont { order_by }
+            | PercentileDisc { order_by }
+            | Mode { order_by }
             | RowNumber { order_by }
             | Rank { order_by }
-            | DenseRank { order_by } => {
+            | DenseRank { order_by }
+            | PercentRank { order_by }
+            | CumeDist { order_by }
+            | Ntile { order_by } => {
                 let order_by = order_by.iter().map(|col| self.child(col));
                 write!(f, "{}[order_by=[{}]]", name, separated(", ", order_by))
             }
@@ -3452,6 +4490,10 @@ where
             LastValue {
                 order_by,
                 window_frame,
+            }
+            | NthValue {
+                order_by,
+                window_frame,
             } => {
                 let order_by = order_by.iter().map(|col| self.child(col));
                 f.write_str(name)?;
@@ -3728,6 +4770,7 @@ pub enum TableFunc {
     JsonbArrayElements {
         stringify: bool,
     },
+    JsonbPathQuery,
     RegexpExtract(AnalyzedRegex),
     CsvExtract(usize),
     GenerateSeriesInt32,
@@ -3773,6 +4816,7 @@ impl RustType<ProtoTableFunc> for TableFunc {
                 TableFunc::JsonbEach { stringify } => Kind::JsonbEach(*stringify),
                 TableFunc::JsonbObjectKeys => Kind::JsonbObjectKeys(()),
                 TableFunc::JsonbArrayElements { stringify } => Kind::JsonbArrayElements(*stringify),
+                TableFunc::JsonbPathQuery => Kind::JsonbPathQuery(()),
                 TableFunc::RegexpExtract(x) => Kind::RegexpExtract(x.into_proto()),
                 TableFunc::CsvExtract(x) => Kind::CsvExtract(x.into_proto()),
                 TableFunc::GenerateSeriesInt32 => Kind::GenerateSeriesInt32(()),
@@ -3812,6 +4856,7 @@ impl RustType<ProtoTableFunc> for TableFunc {
             Kind::JsonbEach(stringify) => TableFunc::JsonbEach { stringify },
             Kind::JsonbObjectKeys(()) => TableFunc::JsonbObjectKeys,
             Kind::JsonbArrayElements(stringify) => TableFunc::JsonbArrayElements { stringify },
+            Kind::JsonbPathQuery(()) => TableFunc::JsonbPathQuery,
             Kind::RegexpExtract(x) => TableFunc::RegexpExtract(x.into_rust()?),
             Kind::CsvExtract(x) => TableFunc::CsvExtract(x.into_rust()?),
             Kind::GenerateSeriesInt32(()) => TableFunc::GenerateSeriesInt32,
@@ -3865,6 +4910,7 @@ impl TableFunc {
                 temp_storage,
                 *stringify,
             ))),
+            TableFunc::JsonbPathQuery => Ok(Box::new(jsonb_path_query(datums, temp_storage)?)),
             TableFunc::RegexpExtract(a) => Ok(Box::new(regexp_extract(datums[0], a).into_iter())),
             TableFunc::CsvExtract(n_cols) => Ok(Box::new(csv_extract(datums[0], *n_cols))),
             TableFunc::GenerateSeriesInt32 => {
@@ -3976,6 +5022,11 @@ impl TableFunc {
                 let keys = vec![];
                 (column_types, keys)
             }
+            TableFunc::JsonbPathQuery => {
+                let column_types = vec![ScalarType::Jsonb.nullable(false)];
+                let keys = vec![];
+                (column_types, keys)
+            }
             TableFunc::RegexpExtract(a) => {
                 let column_types = a
                     .capture_groups_iter()
@@ -4071,6 +5122,7 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => 2,
             TableFunc::JsonbObjectKeys => 1,
             TableFunc::JsonbArrayElements { .. } => 1,
+            TableFunc::JsonbPathQuery => 1,
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeriesInt32 => 1,
@@ -4095,6 +5147,7 @@ impl TableFunc {
             | TableFunc::JsonbEach { .. }
             | TableFunc::JsonbObjectKeys
             | TableFunc::JsonbArrayElements { .. }
+            | TableFunc::JsonbPathQuery
             | TableFunc::GenerateSeriesInt32
             | TableFunc::GenerateSeriesInt64
             | TableFunc::GenerateSeriesTimestamp
@@ -4122,6 +5175,7 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => true,
             TableFunc::JsonbObjectKeys => true,
             TableFunc::JsonbArrayElements { .. } => true,
+            TableFunc::JsonbPathQuery => true,
             TableFunc::RegexpExtract(_) => true,
             TableFunc::CsvExtract(_) => true,
             TableFunc::GenerateSeriesInt32 => true,
@@ -4148,6 +5202,7 @@ impl fmt::Display for TableFunc {
             TableFunc::JsonbEach { .. } => f.write_str("jsonb_each"),
             TableFunc::JsonbObjectKeys => f.write_str("jsonb_object_keys"),
             TableFunc::JsonbArrayElements { .. } => f.write_str("jsonb_array_elements"),
+            TableFunc::JsonbPathQuery => f.write_str("jsonb_path_query"),
             TableFunc::RegexpExtract(a) => write!(f, "regexp_extract({:?}, _)", a.0),
             TableFunc::CsvExtract(n_cols) => write!(f, "csv_extract({}, _)", n_cols),
             TableFunc::GenerateSeriesInt32 => f.write_str("generate_series"),
@@ -4168,9 +5223,13 @@ impl fmt::Display for TableFunc {
 
 #[cfg(test)]
 mod tests {
+    use std::ops::Range;
+
     use super::{AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, TableFunc};
+    use crate::ColumnOrder;
     use mz_ore::assert_ok;
     use mz_proto::protobuf_roundtrip;
+    use mz_repr::{Datum, RowArena};
     use proptest::prelude::*;
 
     proptest! {
@@ -4192,4 +5251,53 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    #[mz_ore::test]
+    fn ordered_set_partials() {
+        let order_by = vec![ColumnOrder {
+            column: 0,
+            desc: false,
+            nulls_last: true,
+        }];
+        let funcs = [
+            AggregateFunc::PercentileCont {
+                order_by: order_by.clone(),
+            },
+            AggregateFunc::PercentileDisc {
+                order_by: order_by.clone(),
+            },
+            AggregateFunc::Mode { order_by },
+        ];
+        let arena = RowArena::new();
+        for func in funcs {
+            let inputs: Vec<_> = [3.0, 1.0, 2.0, 3.0, 5.0, 3.0, 1.0]
+                .into_iter()
+                .map(|v: f64| {
+                    arena.make_datum(|packer| {
+                        packer.push_list_with(|packer| {
+                            // Percentiles aggregate `(value, fraction)` records.
+                            if matches!(func, AggregateFunc::Mode { .. }) {
+                                packer.push(Datum::from(v));
+                            } else {
+                                packer.push_list([Datum::from(v), Datum::from(0.4)]);
+                            }
+                            packer.push(Datum::from(v));
+                        })
+                    })
+                })
+                .collect();
+            let partial = |range: Range<usize>| {
+                let partials = inputs[range].iter();
+                let partials = partials.map(|d| (func.partial_input(*d, &arena), 1));
+                func.combine_partials(partials, &arena)
+            };
+            // The second partial result stands for two copies of its inputs.
+            let (left, right) = (partial(0..4), partial(4..inputs.len()));
+            let expected = func.eval(inputs.iter().chain(&inputs[4..]).copied(), &arena);
+            assert_eq!(
+                func.eval_partials([(left, 1), (right, 2)], &arena),
+                expected
+            );
+        }
+    }
 }
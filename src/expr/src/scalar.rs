@@ -27,8 +27,10 @@ use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::InvalidArrayError;
 use mz_repr::adt::date::DateError;
 use mz_repr::adt::datetime::DateTimeUnits;
+use mz_repr::adt::jsonpath::JsonPathError;
 use mz_repr::adt::range::InvalidRangeError;
 use mz_repr::adt::regex::Regex;
+use mz_repr::adt::sketch::InvalidSketchError;
 use mz_repr::adt::timestamp::TimestampError;
 use mz_repr::strconv::{ParseError, ParseHexError};
 use mz_repr::{ColumnType, Datum, Row, RowArena, ScalarType, arb_datum};
@@ -2999,6 +3001,18 @@ impl From<InvalidRangeError> for EvalError {
     }
 }
 
+impl From<InvalidSketchError> for EvalError {
+    fn from(e: InvalidSketchError) -> EvalError {
+        EvalError::InvalidParameterValue(e.to_string().into())
+    }
+}
+
+impl From<JsonPathError> for EvalError {
+    fn from(e: JsonPathError) -> EvalError {
+        EvalError::InvalidParameterValue(e.to_string().into())
+    }
+}
+
 impl RustType<ProtoEvalError> for EvalError {
     fn into_proto(&self) -> ProtoEvalError {
         use proto_eval_error::Kind::*;
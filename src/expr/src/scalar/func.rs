@@ -44,10 +44,12 @@ use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::date::Date;
 use mz_repr::adt::interval::{Interval, RoundBehavior};
 use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::adt::jsonpath::{JsonPath, JsonPathError};
 use mz_repr::adt::mz_acl_item::{AclItem, AclMode, MzAclItem};
 use mz_repr::adt::numeric::{self, DecimalLike, Numeric, NumericMaxScale};
 use mz_repr::adt::range::{self, Range, RangeBound, RangeOps};
 use mz_repr::adt::regex::{Regex, any_regex};
+use mz_repr::adt::sketch::QuantileSketch;
 use mz_repr::adt::system::Oid;
 use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampLike};
 use mz_repr::role_id::RoleId;
@@ -3311,6 +3313,22 @@ fn pretty_sql<'a>(
     Ok(Datum::String(pretty))
 }
 
+#[sqlfunc(
+    output_type = "Option<f64>",
+    sqlname = "approx_percentile_estimate",
+    propagates_nulls = true
+)]
+fn quantile_sketch_estimate<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let sketch = QuantileSketch::decode(a.unwrap_bytes())?;
+    let fraction = b.unwrap_float64();
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(EvalError::InvalidParameterValue(
+            format!("percentile value {fraction} is not between 0 and 1").into(),
+        ));
+    }
+    Ok(sketch.quantile(fraction).map_or(Datum::Null, Datum::from))
+}
+
 #[sqlfunc(output_type = "bool", propagates_nulls = true)]
 fn starts_with<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     let a = a.unwrap_str();
@@ -3458,6 +3476,7 @@ pub enum BinaryFunc {
     JsonbContainsJsonb,
     JsonbDeleteInt64,
     JsonbDeleteString,
+    JsonbDeletePath,
     MapContainsKey,
     MapGetValue,
     MapContainsAllKeys,
@@ -3513,6 +3532,7 @@ pub enum BinaryFunc {
     ParseIdent,
     PrettySql,
     RegexpReplace { regex: Regex, limit: usize },
+    QuantileSketchEstimate,
     StartsWith,
 }
 
@@ -3709,6 +3729,7 @@ impl BinaryFunc {
             BinaryFunc::JsonbContainsJsonb => Ok(jsonb_contains_jsonb(a, b)),
             BinaryFunc::JsonbDeleteInt64 => Ok(jsonb_delete_int64(a, b, temp_storage)),
             BinaryFunc::JsonbDeleteString => Ok(jsonb_delete_string(a, b, temp_storage)),
+            BinaryFunc::JsonbDeletePath => jsonb_delete_path(a, b, temp_storage),
             BinaryFunc::MapContainsKey => Ok(map_contains_key(a, b)),
             BinaryFunc::MapGetValue => Ok(map_get_value(a, b)),
             BinaryFunc::MapContainsAllKeys => Ok(map_contains_all_keys(a, b)),
@@ -3782,6 +3803,7 @@ impl BinaryFunc {
             BinaryFunc::RegexpReplace { regex, limit } => {
                 regexp_replace_static(a, b, regex, *limit, temp_storage)
             }
+            BinaryFunc::QuantileSketchEstimate => quantile_sketch_estimate(a, b),
             BinaryFunc::StartsWith => Ok(starts_with(a, b)),
         }
     }
@@ -3912,6 +3934,8 @@ impl BinaryFunc {
             | JsonbDeleteInt64
             | JsonbDeleteString => ScalarType::Jsonb.nullable(true),
 
+            JsonbDeletePath => ScalarType::Jsonb.nullable(in_nullable),
+
             JsonbContainsString | JsonbContainsJsonb | MapContainsKey | MapContainsAllKeys
             | MapContainsAnyKeys | MapContainsMap => ScalarType::Bool.nullable(in_nullable),
 
@@ -3977,6 +4001,7 @@ impl BinaryFunc {
             PrettySql => ScalarType::String.nullable(in_nullable),
             RegexpReplace { .. } => ScalarType::String.nullable(in_nullable),
 
+            QuantileSketchEstimate => ScalarType::Float64.nullable(true),
             StartsWith => ScalarType::Bool.nullable(in_nullable),
         }
     }
@@ -4183,7 +4208,8 @@ impl BinaryFunc {
             | ParseIdent
             | PrettySql
             | RegexpReplace { .. }
-            | StartsWith => false,
+            | StartsWith
+            | JsonbDeletePath => false,
 
             JsonbGetInt64
             | JsonbGetInt64Stringify
@@ -4198,7 +4224,8 @@ impl BinaryFunc {
             | ListLengthMax { .. }
             | ArrayLength
             | ArrayLower
-            | ArrayUpper => true,
+            | ArrayUpper
+            | QuantileSketchEstimate => true,
         }
     }
 
@@ -4314,6 +4341,7 @@ impl BinaryFunc {
             | JsonbContainsString
             | JsonbDeleteInt64
             | JsonbDeleteString
+            | JsonbDeletePath
             | MapContainsKey
             | MapGetValue
             | MapContainsAllKeys
@@ -4398,6 +4426,7 @@ impl BinaryFunc {
             | ParseIdent
             | PrettySql
             | RegexpReplace { .. }
+            | QuantileSketchEstimate
             | StartsWith => false,
         }
     }
@@ -4465,6 +4494,7 @@ impl BinaryFunc {
             | BinaryFunc::JsonbContainsJsonb
             | BinaryFunc::JsonbDeleteInt64
             | BinaryFunc::JsonbDeleteString => false,
+            BinaryFunc::JsonbDeletePath => true,
             BinaryFunc::MapContainsKey
             | BinaryFunc::MapGetValue
             | BinaryFunc::MapContainsAllKeys
@@ -4662,6 +4692,7 @@ impl BinaryFunc {
             | BinaryFunc::JsonbContainsJsonb
             | BinaryFunc::JsonbDeleteInt64
             | BinaryFunc::JsonbDeleteString
+            | BinaryFunc::JsonbDeletePath
             | BinaryFunc::MapContainsKey
             | BinaryFunc::MapGetValue
             | BinaryFunc::MapContainsAllKeys
@@ -4714,6 +4745,7 @@ impl BinaryFunc {
             BinaryFunc::ConstantTimeEqBytes | BinaryFunc::ConstantTimeEqString => (false, false),
             BinaryFunc::PrettySql => (false, false),
             BinaryFunc::RegexpReplace { .. } => (false, false),
+            BinaryFunc::QuantileSketchEstimate => (false, false),
             BinaryFunc::StartsWith => (false, false),
         }
     }
@@ -4869,6 +4901,7 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::JsonbContainsJsonb | BinaryFunc::MapContainsMap => f.write_str("@>"),
             BinaryFunc::JsonbDeleteInt64 => f.write_str("-"),
             BinaryFunc::JsonbDeleteString => f.write_str("-"),
+            BinaryFunc::JsonbDeletePath => f.write_str("#-"),
             BinaryFunc::MapGetValue => f.write_str("->"),
             BinaryFunc::MapContainsAllKeys => f.write_str("?&"),
             BinaryFunc::MapContainsAnyKeys => f.write_str("?|"),
@@ -4932,6 +4965,7 @@ impl fmt::Display for BinaryFunc {
                 regex.case_insensitive,
                 limit
             ),
+            BinaryFunc::QuantileSketchEstimate => f.write_str("approx_percentile_estimate"),
             BinaryFunc::StartsWith => f.write_str("starts_with"),
         }
     }
@@ -5092,6 +5126,7 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::JsonbContainsJsonb).boxed(),
             Just(BinaryFunc::JsonbDeleteInt64).boxed(),
             Just(BinaryFunc::JsonbDeleteString).boxed(),
+            Just(BinaryFunc::JsonbDeletePath).boxed(),
             Just(BinaryFunc::MapContainsKey).boxed(),
             Just(BinaryFunc::MapGetValue).boxed(),
             Just(BinaryFunc::MapContainsAllKeys).boxed(),
@@ -5289,6 +5324,7 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::JsonbContainsJsonb => JsonbContainsJsonb(()),
             BinaryFunc::JsonbDeleteInt64 => JsonbDeleteInt64(()),
             BinaryFunc::JsonbDeleteString => JsonbDeleteString(()),
+            BinaryFunc::JsonbDeletePath => JsonbDeletePath(()),
             BinaryFunc::MapContainsKey => MapContainsKey(()),
             BinaryFunc::MapGetValue => MapGetValue(()),
             BinaryFunc::MapContainsAllKeys => MapContainsAllKeys(()),
@@ -5355,6 +5391,7 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                     limit: limit.into_proto(),
                 })
             }
+            BinaryFunc::QuantileSketchEstimate => QuantileSketchEstimate(()),
             BinaryFunc::StartsWith => StartsWith(()),
         };
         ProtoBinaryFunc { kind: Some(kind) }
@@ -5504,6 +5541,7 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 JsonbContainsJsonb(()) => Ok(BinaryFunc::JsonbContainsJsonb),
                 JsonbDeleteInt64(()) => Ok(BinaryFunc::JsonbDeleteInt64),
                 JsonbDeleteString(()) => Ok(BinaryFunc::JsonbDeleteString),
+                JsonbDeletePath(()) => Ok(BinaryFunc::JsonbDeletePath),
                 MapContainsKey(()) => Ok(BinaryFunc::MapContainsKey),
                 MapGetValue(()) => Ok(BinaryFunc::MapGetValue),
                 MapContainsAllKeys(()) => Ok(BinaryFunc::MapContainsAllKeys),
@@ -5569,6 +5607,7 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                     regex: inner.regex.into_rust_if_some("ProtoRegexReplace::regex")?,
                     limit: inner.limit.into_rust()?,
                 }),
+                QuantileSketchEstimate(()) => Ok(BinaryFunc::QuantileSketchEstimate),
                 StartsWith(()) => Ok(BinaryFunc::StartsWith),
             }
         } else {
@@ -6068,7 +6107,14 @@ derive_unary!(
     KafkaMurmur2String,
     SeahashBytes,
     SeahashString,
-    Reverse
+    Reverse,
+    HllSketch,
+    HllEstimate,
+    CastBytesToHllSketch,
+    CastHllSketchToBytes,
+    QuantileSketchFloat64,
+    CastBytesToQuantileSketch,
+    CastQuantileSketchToBytes
 );
 
 impl UnaryFunc {
@@ -6895,6 +6941,13 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::SeahashBytes(_) => SeahashBytes(()),
             UnaryFunc::SeahashString(_) => SeahashString(()),
             UnaryFunc::Reverse(_) => Reverse(()),
+            UnaryFunc::HllSketch(_) => HllSketch(()),
+            UnaryFunc::HllEstimate(_) => HllEstimate(()),
+            UnaryFunc::CastBytesToHllSketch(_) => CastBytesToHllSketch(()),
+            UnaryFunc::CastHllSketchToBytes(_) => CastHllSketchToBytes(()),
+            UnaryFunc::QuantileSketchFloat64(_) => QuantileSketchFloat64(()),
+            UnaryFunc::CastBytesToQuantileSketch(_) => CastBytesToQuantileSketch(()),
+            UnaryFunc::CastQuantileSketchToBytes(_) => CastQuantileSketchToBytes(()),
         };
         ProtoUnaryFunc { kind: Some(kind) }
     }
@@ -7394,6 +7447,13 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 SeahashBytes(()) => Ok(impls::SeahashBytes.into()),
                 SeahashString(()) => Ok(impls::SeahashString.into()),
                 Reverse(()) => Ok(impls::Reverse.into()),
+                HllSketch(()) => Ok(impls::HllSketch.into()),
+                HllEstimate(()) => Ok(impls::HllEstimate.into()),
+                CastBytesToHllSketch(()) => Ok(impls::CastBytesToHllSketch.into()),
+                CastHllSketchToBytes(()) => Ok(impls::CastHllSketchToBytes.into()),
+                QuantileSketchFloat64(()) => Ok(impls::QuantileSketchFloat64.into()),
+                CastBytesToQuantileSketch(()) => Ok(impls::CastBytesToQuantileSketch.into()),
+                CastQuantileSketchToBytes(()) => Ok(impls::CastQuantileSketchToBytes.into()),
             }
         } else {
             Err(TryFromProtoError::missing_field("ProtoUnaryFunc::kind"))
@@ -7925,6 +7985,59 @@ fn jsonb_build_object<'a>(
     })
 }
 
+/// Evaluates the SQL/JSON path in `datums[1]` against the `jsonb` value in
+/// `datums[0]`, with the variables in `datums[2]`. If `datums[3]` is true,
+/// evaluation errors are suppressed and `None` is returned instead.
+pub(crate) fn jsonb_path_eval<'a, T>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+    f: impl FnOnce(&JsonPath, Datum<'a>, Datum<'a>, &'a RowArena) -> Result<T, JsonPathError>,
+) -> Result<Option<T>, EvalError> {
+    let path: JsonPath = datums[1].unwrap_str().parse()?;
+    match f(&path, datums[0], datums[2], temp_storage) {
+        Ok(result) => Ok(Some(result)),
+        Err(e) if datums[3].unwrap_bool() && e.is_silenceable() => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn jsonb_path_exists<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    Ok(Datum::from(jsonb_path_eval(
+        datums,
+        temp_storage,
+        JsonPath::exists,
+    )?))
+}
+
+fn jsonb_path_match<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let result = jsonb_path_eval(datums, temp_storage, JsonPath::matches)?;
+    Ok(Datum::from(result.flatten()))
+}
+
+fn jsonb_path_query_array<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let items = jsonb_path_eval(datums, temp_storage, JsonPath::query)?.unwrap_or_default();
+    Ok(temp_storage.make_datum(|packer| packer.push_list(items)))
+}
+
+fn jsonb_path_query_first<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let items = jsonb_path_eval(datums, temp_storage, JsonPath::query)?;
+    Ok(items
+        .and_then(|items| items.into_iter().next())
+        .unwrap_or(Datum::Null))
+}
+
 fn map_build<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     // Collect into a `BTreeMap` to provide the same semantics as it.
     let map: std::collections::BTreeMap<&str, _> = datums
@@ -9106,6 +9219,13 @@ pub enum VariadicFunc {
     TimezoneTime,
     RegexpSplitToArray,
     RegexpReplace,
+    JsonbPathExists,
+    JsonbPathMatch,
+    JsonbPathQueryArray,
+    JsonbPathQueryFirst,
+    JsonbSet,
+    JsonbInsert,
+    JsonbSetLax,
 }
 
 impl VariadicFunc {
@@ -9209,6 +9329,13 @@ impl VariadicFunc {
                 regexp_split_to_array(ds[0], ds[1], flags, temp_storage)
             }
             VariadicFunc::RegexpReplace => regexp_replace_dynamic(&ds, temp_storage),
+            VariadicFunc::JsonbPathExists => jsonb_path_exists(&ds, temp_storage),
+            VariadicFunc::JsonbPathMatch => jsonb_path_match(&ds, temp_storage),
+            VariadicFunc::JsonbPathQueryArray => jsonb_path_query_array(&ds, temp_storage),
+            VariadicFunc::JsonbPathQueryFirst => jsonb_path_query_first(&ds, temp_storage),
+            VariadicFunc::JsonbSet => jsonb_set(&ds, temp_storage),
+            VariadicFunc::JsonbInsert => jsonb_insert(&ds, temp_storage),
+            VariadicFunc::JsonbSetLax => jsonb_set_lax(&ds, temp_storage),
             VariadicFunc::StringToArray => {
                 let null_string = if ds.len() == 2 { Datum::Null } else { ds[2] };
 
@@ -9261,7 +9388,14 @@ impl VariadicFunc {
             | VariadicFunc::TimezoneTime
             | VariadicFunc::RegexpSplitToArray
             | VariadicFunc::StringToArray
-            | VariadicFunc::RegexpReplace => false,
+            | VariadicFunc::RegexpReplace
+            | VariadicFunc::JsonbPathExists
+            | VariadicFunc::JsonbPathMatch
+            | VariadicFunc::JsonbPathQueryArray
+            | VariadicFunc::JsonbPathQueryFirst
+            | VariadicFunc::JsonbSet
+            | VariadicFunc::JsonbInsert
+            | VariadicFunc::JsonbSetLax => false,
         }
     }
 
@@ -9367,6 +9501,10 @@ impl VariadicFunc {
             }
             RegexpReplace => ScalarType::String.nullable(in_nullable),
             StringToArray => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            JsonbPathExists | JsonbPathMatch => ScalarType::Bool.nullable(true),
+            JsonbPathQueryArray => ScalarType::Jsonb.nullable(in_nullable),
+            JsonbPathQueryFirst => ScalarType::Jsonb.nullable(true),
+            JsonbSet | JsonbInsert | JsonbSetLax => ScalarType::Jsonb.nullable(in_nullable),
         }
     }
 
@@ -9398,6 +9536,7 @@ impl VariadicFunc {
                 | VariadicFunc::ArrayPosition
                 | VariadicFunc::ArrayFill { .. }
                 | VariadicFunc::StringToArray
+                | VariadicFunc::JsonbSetLax
         )
     }
 
@@ -9442,7 +9581,11 @@ impl VariadicFunc {
             | ArrayFill { .. }
             | TimezoneTime
             | RegexpSplitToArray
-            | RegexpReplace => false,
+            | RegexpReplace
+            | JsonbPathQueryArray
+            | JsonbSet
+            | JsonbInsert
+            | JsonbSetLax => false,
             Coalesce
             | Greatest
             | Least
@@ -9450,7 +9593,10 @@ impl VariadicFunc {
             | ArrayIndex { .. }
             | StringToArray
             | ListIndex
-            | RegexpMatch => true,
+            | RegexpMatch
+            | JsonbPathExists
+            | JsonbPathMatch
+            | JsonbPathQueryFirst => true,
         }
     }
 
@@ -9557,7 +9703,14 @@ impl VariadicFunc {
             | VariadicFunc::TimezoneTime
             | VariadicFunc::RegexpSplitToArray
             | VariadicFunc::StringToArray
-            | VariadicFunc::RegexpReplace => false,
+            | VariadicFunc::RegexpReplace
+            | VariadicFunc::JsonbPathExists
+            | VariadicFunc::JsonbPathMatch
+            | VariadicFunc::JsonbPathQueryArray
+            | VariadicFunc::JsonbPathQueryFirst
+            | VariadicFunc::JsonbSet
+            | VariadicFunc::JsonbInsert
+            | VariadicFunc::JsonbSetLax => false,
         }
     }
 }
@@ -9616,6 +9769,13 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::RegexpSplitToArray => f.write_str("regexp_split_to_array"),
             VariadicFunc::RegexpReplace => f.write_str("regexp_replace"),
             VariadicFunc::StringToArray => f.write_str("string_to_array"),
+            VariadicFunc::JsonbPathExists => f.write_str("jsonb_path_exists"),
+            VariadicFunc::JsonbPathMatch => f.write_str("jsonb_path_match"),
+            VariadicFunc::JsonbPathQueryArray => f.write_str("jsonb_path_query_array"),
+            VariadicFunc::JsonbPathQueryFirst => f.write_str("jsonb_path_query_first"),
+            VariadicFunc::JsonbSet => f.write_str("jsonb_set"),
+            VariadicFunc::JsonbInsert => f.write_str("jsonb_insert"),
+            VariadicFunc::JsonbSetLax => f.write_str("jsonb_set_lax"),
         }
     }
 }
@@ -9686,6 +9846,13 @@ impl Arbitrary for VariadicFunc {
             ScalarType::arbitrary()
                 .prop_map(|elem_type| VariadicFunc::ArrayFill { elem_type })
                 .boxed(),
+            Just(VariadicFunc::JsonbPathExists).boxed(),
+            Just(VariadicFunc::JsonbPathMatch).boxed(),
+            Just(VariadicFunc::JsonbPathQueryArray).boxed(),
+            Just(VariadicFunc::JsonbPathQueryFirst).boxed(),
+            Just(VariadicFunc::JsonbSet).boxed(),
+            Just(VariadicFunc::JsonbInsert).boxed(),
+            Just(VariadicFunc::JsonbSetLax).boxed(),
         ])
     }
 }
@@ -9739,6 +9906,13 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
             VariadicFunc::RegexpSplitToArray => RegexpSplitToArray(()),
             VariadicFunc::RegexpReplace => RegexpReplace(()),
             VariadicFunc::StringToArray => StringToArray(()),
+            VariadicFunc::JsonbPathExists => JsonbPathExists(()),
+            VariadicFunc::JsonbPathMatch => JsonbPathMatch(()),
+            VariadicFunc::JsonbPathQueryArray => JsonbPathQueryArray(()),
+            VariadicFunc::JsonbPathQueryFirst => JsonbPathQueryFirst(()),
+            VariadicFunc::JsonbSet => JsonbSet(()),
+            VariadicFunc::JsonbInsert => JsonbInsert(()),
+            VariadicFunc::JsonbSetLax => JsonbSetLax(()),
         };
         ProtoVariadicFunc { kind: Some(kind) }
     }
@@ -9806,6 +9980,13 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
                 RegexpSplitToArray(()) => Ok(VariadicFunc::RegexpSplitToArray),
                 RegexpReplace(()) => Ok(VariadicFunc::RegexpReplace),
                 StringToArray(()) => Ok(VariadicFunc::StringToArray),
+                JsonbPathExists(()) => Ok(VariadicFunc::JsonbPathExists),
+                JsonbPathMatch(()) => Ok(VariadicFunc::JsonbPathMatch),
+                JsonbPathQueryArray(()) => Ok(VariadicFunc::JsonbPathQueryArray),
+                JsonbPathQueryFirst(()) => Ok(VariadicFunc::JsonbPathQueryFirst),
+                JsonbSet(()) => Ok(VariadicFunc::JsonbSet),
+                JsonbInsert(()) => Ok(VariadicFunc::JsonbInsert),
+                JsonbSetLax(()) => Ok(VariadicFunc::JsonbSetLax),
             }
         } else {
             Err(TryFromProtoError::missing_field(
@@ -1039,6 +1039,12 @@ mod test {
             &i32_ty,
         );
         check(func::ParseIdent, BF::ParseIdent, &i32_ty, &i32_ty);
+        check(
+            func::QuantileSketchEstimate,
+            BF::QuantileSketchEstimate,
+            &i32_ty,
+            &i32_ty,
+        );
         check(func::StartsWith, BF::StartsWith, &i32_ty, &i32_ty);
         check(func::PrettySql, BF::PrettySql, &i32_ty, &i32_ty);
     }
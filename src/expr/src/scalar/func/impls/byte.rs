@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use mz_ore::cast::CastFrom;
+use mz_repr::adt::sketch::{HyperLogLog, QuantileSketch};
 use mz_repr::strconv;
 
 use crate::EvalError;
@@ -88,3 +89,45 @@ sqlfunc!(
         i32::try_from(val).or_else(|_| Err(EvalError::Int32OutOfRange(val.to_string().into())))
     }
 );
+
+sqlfunc!(
+    #[sqlname = "hll_estimate"]
+    fn hll_estimate<'a>(a: &'a [u8]) -> Result<i64, EvalError> {
+        let estimate = HyperLogLog::decode(a)?.estimate();
+        Ok(i64::try_from(estimate).unwrap_or(i64::MAX))
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "bytea_to_hll_sketch"]
+    #[inverse = to_unary!(super::CastHllSketchToBytes)]
+    fn cast_bytes_to_hll_sketch<'a>(a: &'a [u8]) -> Result<HyperLogLog, EvalError> {
+        Ok(HyperLogLog::decode(a)?)
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "hll_sketch_to_bytea"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastBytesToHllSketch)]
+    fn cast_hll_sketch_to_bytes<'a>(a: &'a [u8]) -> &'a [u8] {
+        a
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "bytea_to_quantile_sketch"]
+    #[inverse = to_unary!(super::CastQuantileSketchToBytes)]
+    fn cast_bytes_to_quantile_sketch<'a>(a: &'a [u8]) -> Result<QuantileSketch, EvalError> {
+        Ok(QuantileSketch::decode(a)?)
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "quantile_sketch_to_bytea"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastBytesToQuantileSketch)]
+    fn cast_quantile_sketch_to_bytes<'a>(a: &'a [u8]) -> &'a [u8] {
+        a
+    }
+);
@@ -7,7 +7,9 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_repr::{Datum, DatumList};
+use mz_repr::adt::sketch::HyperLogLog;
+use mz_repr::{Datum, DatumList, ProtoDatum};
+use prost::Message;
 
 use crate::EvalError;
 
@@ -55,3 +57,17 @@ sqlfunc!(
         i32::try_from(sz).or_else(|_| Err(EvalError::Int32OutOfRange(sz.to_string().into())))
     }
 );
+
+sqlfunc!(
+    #[sqlname = "hll_sketch"]
+    fn hll_sketch<'a>(a: Datum<'a>) -> Option<HyperLogLog> {
+        if a.is_null() {
+            return None;
+        }
+        // Hash the datum's protobuf encoding, which, unlike its row encoding, is stable across
+        // versions, so that sketches built by different versions can be merged.
+        let mut hll = HyperLogLog::new();
+        hll.insert_hash(seahash::hash(&ProtoDatum::from(a).encode_to_vec()));
+        Some(hll)
+    }
+);
@@ -13,6 +13,7 @@ use chrono::{DateTime, Utc};
 use mz_lowertest::MzReflect;
 use mz_ore::cast::TryCastFrom;
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
+use mz_repr::adt::sketch::QuantileSketch;
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::{ColumnType, ScalarType, strconv};
 use serde::{Deserialize, Serialize};
@@ -485,3 +486,12 @@ sqlfunc!(
         }
     }
 );
+
+sqlfunc!(
+    #[sqlname = "quantile_sketch"]
+    fn quantile_sketch_float64<'a>(a: f64) -> Result<QuantileSketch, EvalError> {
+        let mut sketch = QuantileSketch::new();
+        sketch.insert(a)?;
+        Ok(sketch)
+    }
+);
@@ -10,9 +10,12 @@
 use std::fmt;
 
 use mz_lowertest::MzReflect;
+use mz_ore::cast::CastFrom;
 use mz_repr::adt::jsonb::{Jsonb, JsonbRef};
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
-use mz_repr::{ColumnType, Datum, Row, RowPacker, ScalarType, strconv};
+use mz_repr::{
+    ColumnType, Datum, DatumList, DatumMap, Row, RowArena, RowPacker, ScalarType, strconv,
+};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
@@ -245,3 +248,277 @@ sqlfunc!(
         buf
     }
 );
+
+/// How [`jsonb_modify_path`] modifies the value at the end of a path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum JsonbPathOp {
+    /// Replaces an existing value, as `jsonb_set` does with `create_if_missing`
+    /// set to false.
+    Replace,
+    /// Replaces an existing value or creates a missing one.
+    Create,
+    /// Inserts before an existing array element or creates a missing value.
+    InsertBefore,
+    /// Inserts after an existing array element or creates a missing value.
+    InsertAfter,
+    /// Removes an existing value.
+    Delete,
+}
+
+impl JsonbPathOp {
+    fn creates(self) -> bool {
+        matches!(
+            self,
+            JsonbPathOp::Create | JsonbPathOp::InsertBefore | JsonbPathOp::InsertAfter
+        )
+    }
+}
+
+/// Applies `op` to the value at `path` in `target`, following the semantics
+/// of PostgreSQL's `jsonb_set`, `jsonb_insert` and `#-`.
+///
+/// The modified value is packed directly into `temp_storage`. Containers that
+/// `path` does not descend into are copied as-is.
+fn jsonb_modify_path<'a>(
+    target: Datum<'a>,
+    path: Datum<'a>,
+    new_value: Datum<'a>,
+    op: JsonbPathOp,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let is_empty = match target {
+        Datum::Map(map) => map.iter().next().is_none(),
+        Datum::List(list) => list.iter().next().is_none(),
+        _ if op == JsonbPathOp::Delete => {
+            return Err(EvalError::InvalidParameterValue(
+                "cannot delete path in scalar".into(),
+            ));
+        }
+        _ => {
+            return Err(EvalError::InvalidParameterValue(
+                "cannot set path in scalar".into(),
+            ));
+        }
+    };
+    let path: Vec<_> = path.unwrap_array().elements().iter().collect();
+    if path.is_empty() || (is_empty && !op.creates()) {
+        return Ok(target);
+    }
+    temp_storage.try_make_datum(|packer| set_path(packer, target, &path, 0, new_value, op))
+}
+
+/// Packs `target` into `packer`, applying `op` if `path[level..]` leads to a
+/// value within it.
+fn set_path<'a>(
+    packer: &mut RowPacker,
+    target: Datum<'a>,
+    path: &[Datum<'a>],
+    level: usize,
+    new_value: Datum<'a>,
+    op: JsonbPathOp,
+) -> Result<(), EvalError> {
+    let key = match path[level] {
+        Datum::Null => {
+            return Err(EvalError::InvalidParameterValue(
+                format!("path element at position {} is null", level + 1).into(),
+            ));
+        }
+        key => key.unwrap_str(),
+    };
+    match target {
+        Datum::Map(map) => packer
+            .push_dict_with(|packer| set_path_object(packer, map, key, path, level, new_value, op)),
+        Datum::List(list) => packer
+            .push_list_with(|packer| set_path_array(packer, list, key, path, level, new_value, op)),
+        // Paths that run into scalars leave them unchanged.
+        scalar => {
+            packer.push(scalar);
+            Ok(())
+        }
+    }
+}
+
+fn set_path_object<'a>(
+    packer: &mut RowPacker,
+    map: DatumMap<'a>,
+    key: &str,
+    path: &[Datum<'a>],
+    level: usize,
+    new_value: Datum<'a>,
+    op: JsonbPathOp,
+) -> Result<(), EvalError> {
+    let last = level == path.len() - 1;
+    let mut done = false;
+    for (k, v) in map.iter() {
+        // Keys must be pushed in ascending order, so a missing key is created
+        // right before the first key that sorts after it.
+        if !done && last && op.creates() && key < k {
+            packer.push(Datum::String(key));
+            packer.push(new_value);
+            done = true;
+        }
+        if !done && key == k {
+            done = true;
+            if !last {
+                packer.push(Datum::String(k));
+                set_path(packer, v, path, level + 1, new_value, op)?;
+                continue;
+            }
+            match op {
+                JsonbPathOp::InsertBefore | JsonbPathOp::InsertAfter => {
+                    return Err(EvalError::InvalidParameterValue(
+                        "cannot replace existing key".into(),
+                    ));
+                }
+                JsonbPathOp::Replace | JsonbPathOp::Create => {
+                    packer.push(Datum::String(k));
+                    packer.push(new_value);
+                }
+                JsonbPathOp::Delete => {}
+            }
+            continue;
+        }
+        packer.push(Datum::String(k));
+        packer.push(v);
+    }
+    if !done && last && op.creates() {
+        packer.push(Datum::String(key));
+        packer.push(new_value);
+    }
+    Ok(())
+}
+
+fn set_path_array<'a>(
+    packer: &mut RowPacker,
+    list: DatumList<'a>,
+    key: &str,
+    path: &[Datum<'a>],
+    level: usize,
+    new_value: Datum<'a>,
+    op: JsonbPathOp,
+) -> Result<(), EvalError> {
+    let last = level == path.len() - 1;
+    let len = list.iter().count();
+    let index: i32 = key.parse().map_err(|_| {
+        EvalError::InvalidParameterValue(
+            format!(
+                "path element at position {} is not an integer: \"{}\"",
+                level + 1,
+                key
+            )
+            .into(),
+        )
+    })?;
+    // Negative indexes count backwards from the end. `None` refers to the
+    // position before the first element.
+    let index = if index >= 0 {
+        Some(usize::cast_from(index.unsigned_abs()))
+    } else {
+        len.checked_sub(usize::cast_from(index.unsigned_abs()))
+    };
+    let mut done = false;
+    if (index.is_none() || len == 0) && last && op.creates() {
+        packer.push(new_value);
+        done = true;
+    }
+    for (i, elem) in list.iter().enumerate() {
+        if index != Some(i) {
+            packer.push(elem);
+            continue;
+        }
+        done = true;
+        if !last {
+            set_path(packer, elem, path, level + 1, new_value, op)?;
+            continue;
+        }
+        match op {
+            JsonbPathOp::Replace | JsonbPathOp::Create => packer.push(new_value),
+            JsonbPathOp::InsertBefore => {
+                packer.push(new_value);
+                packer.push(elem);
+            }
+            JsonbPathOp::InsertAfter => {
+                packer.push(elem);
+                packer.push(new_value);
+            }
+            JsonbPathOp::Delete => {}
+        }
+    }
+    if !done && last && op.creates() {
+        packer.push(new_value);
+    }
+    Ok(())
+}
+
+/// Implements `jsonb_set(target, path, new_value, create_if_missing)`.
+pub fn jsonb_set<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let op = if datums[3].unwrap_bool() {
+        JsonbPathOp::Create
+    } else {
+        JsonbPathOp::Replace
+    };
+    jsonb_modify_path(datums[0], datums[1], datums[2], op, temp_storage)
+}
+
+/// Implements `jsonb_insert(target, path, new_value, insert_after)`.
+pub fn jsonb_insert<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let op = if datums[3].unwrap_bool() {
+        JsonbPathOp::InsertAfter
+    } else {
+        JsonbPathOp::InsertBefore
+    };
+    jsonb_modify_path(datums[0], datums[1], datums[2], op, temp_storage)
+}
+
+const NULL_VALUE_TREATMENT_ERR: &str = "null_value_treatment must be \"delete_key\", \
+     \"return_target\", \"use_json_null\", or \"raise_exception\"";
+
+/// Implements `jsonb_set_lax(target, path, new_value, create_if_missing,
+/// null_value_treatment)`, which unlike `jsonb_set` accepts a SQL `NULL`
+/// `new_value`.
+pub fn jsonb_set_lax<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let treatment = match datums[4] {
+        Datum::Null => {
+            return Err(EvalError::InvalidParameterValue(
+                NULL_VALUE_TREATMENT_ERR.into(),
+            ));
+        }
+        treatment => treatment.unwrap_str(),
+    };
+    if datums[0].is_null() || datums[1].is_null() || datums[3].is_null() {
+        return Ok(Datum::Null);
+    }
+    if !datums[2].is_null() {
+        return jsonb_set(datums, temp_storage);
+    }
+    match treatment {
+        "use_json_null" => {
+            let datums = [datums[0], datums[1], Datum::JsonNull, datums[3]];
+            jsonb_set(&datums, temp_storage)
+        }
+        "delete_key" => jsonb_delete_path(datums[0], datums[1], temp_storage),
+        "return_target" => Ok(datums[0]),
+        "raise_exception" => Err(EvalError::MustNotBeNull("JSON value".into())),
+        _ => Err(EvalError::InvalidParameterValue(
+            NULL_VALUE_TREATMENT_ERR.into(),
+        )),
+    }
+}
+
+/// Implements the `#-` operator, which removes the value at a path.
+pub fn jsonb_delete_path<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    jsonb_modify_path(a, b, Datum::Null, JsonbPathOp::Delete, temp_storage)
+}
@@ -349,7 +349,9 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                     debug_assert_eq!(buf.len(), 16);
                     buf
                 }),
-                ScalarType::Bytes => Value::Bytes(Vec::from(datum.unwrap_bytes())),
+                ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch => {
+                    Value::Bytes(Vec::from(datum.unwrap_bytes()))
+                }
                 ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
                     Value::String(datum.unwrap_str().to_owned())
                 }
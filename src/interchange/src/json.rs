@@ -37,12 +37,16 @@ impl JsonEncoder {
         };
         JsonEncoder { columns }
     }
+
+    /// Encodes a row as a JSON value, rather than as bytes.
+    pub fn encode_json(&self, row: &mz_repr::Row) -> serde_json::Value {
+        encode_datums_as_json(row.iter(), self.columns.as_ref())
+    }
 }
 
 impl Encode for JsonEncoder {
     fn encode_unchecked(&self, row: mz_repr::Row) -> Vec<u8> {
-        let value = encode_datums_as_json(row.iter(), self.columns.as_ref());
-        value.to_string().into_bytes()
+        self.encode_json(&row).to_string().into_bytes()
     }
 }
 
@@ -149,7 +153,9 @@ impl ToJson for TypedDatum<'_> {
             ScalarType::Interval => {
                 serde_json::Value::String(format!("{}", datum.unwrap_interval()))
             }
-            ScalarType::Bytes => json!(datum.unwrap_bytes()),
+            ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch => {
+                json!(datum.unwrap_bytes())
+            }
             ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
                 json!(datum.unwrap_str())
             }
@@ -301,7 +307,7 @@ fn build_row_schema_field_type(
             },
         }),
         ScalarType::Interval => type_namer.interval_type(),
-        ScalarType::Bytes => json!("bytes"),
+        ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch => json!("bytes"),
         ScalarType::String
         | ScalarType::Char { .. }
         | ScalarType::VarChar { .. }
@@ -8,14 +8,26 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::BTreeSet;
+use std::fmt::Write;
 
 use anyhow::{Context, anyhow, bail};
+use bytes::{Bytes, BytesMut};
+use itertools::Itertools;
 use mz_ore::str::StrExt;
-use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, RowPacker, ScalarType};
+use prost::Message;
 use prost_reflect::{
     Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
     ReflectMessage, Value,
 };
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    OneofDescriptorProto,
+};
+
+use crate::encode::{Encode, column_names_and_types};
+use crate::envelopes;
 
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
@@ -238,3 +250,541 @@ fn pack_value(
     }
     Ok(())
 }
+
+/// A Protobuf schema generated for the rows of a relation.
+#[derive(Debug)]
+pub struct GeneratedSchema {
+    /// The `.proto` source of the schema, as published to a schema registry.
+    pub source: String,
+    /// The encoded `FileDescriptorSet` of the schema.
+    pub descriptors: Vec<u8>,
+}
+
+/// Generates a Protobuf schema in which the message named `message_name`
+/// describes the rows of `desc`.
+///
+/// If `debezium` is set, the message instead has `before` and `after` fields
+/// holding the rows of `desc` in a nested `Row` message.
+///
+/// Each column is encoded into a field whose name is the column name with any
+/// characters that are not valid in a Protobuf identifier replaced by `_`.
+/// Nullable columns become `optional` fields, records become nested messages,
+/// and columns of types without a natural Protobuf counterpart become `string`
+/// fields holding the text representation of the value.
+pub fn generate_schema(
+    desc: RelationDesc,
+    debezium: bool,
+    message_name: &str,
+) -> Result<GeneratedSchema, anyhow::Error> {
+    let (package, name) = match message_name.rsplit_once('.') {
+        Some((package, name)) => (Some(package), name),
+        None => (None, message_name),
+    };
+    let scope = match package {
+        Some(package) => format!(".{package}.{name}"),
+        None => format!(".{name}"),
+    };
+    let columns = column_names_and_types(desc);
+
+    let mut source = String::from("syntax = \"proto3\";\n\n");
+    if let Some(package) = package {
+        writeln!(source, "package {package};\n").expect("writing to string cannot fail");
+    }
+    let message = if debezium {
+        let row_scope = format!("{scope}.Row");
+        writeln!(source, "message {name} {{").expect("writing to string cannot fail");
+        let row = generate_message("Row", &columns, &row_scope, 1, &mut source)?;
+        writeln!(source, "  Row before = 1;\n  Row after = 2;\n}}")
+            .expect("writing to string cannot fail");
+        let row_field = |name: &str, number| FieldDescriptorProto {
+            name: Some(name.into()),
+            number: Some(number),
+            label: Some(Label::Optional.into()),
+            r#type: Some(Type::Message.into()),
+            type_name: Some(row_scope.clone()),
+            ..Default::default()
+        };
+        DescriptorProto {
+            name: Some(name.into()),
+            field: vec![row_field("before", 1), row_field("after", 2)],
+            nested_type: vec![row],
+            ..Default::default()
+        }
+    } else {
+        generate_message(name, &columns, &scope, 0, &mut source)?
+    };
+
+    let file = FileDescriptorProto {
+        name: Some(format!("{message_name}.proto")),
+        package: package.map(|package| package.into()),
+        message_type: vec![message],
+        syntax: Some("proto3".into()),
+        ..Default::default()
+    };
+    let descriptors = FileDescriptorSet { file: vec![file] }.encode_to_vec();
+    // Ensure that the generated descriptors are valid.
+    DescriptorPool::decode(descriptors.as_slice()).context("generating protobuf schema")?;
+    Ok(GeneratedSchema {
+        source,
+        descriptors,
+    })
+}
+
+/// Generates the message `name` describing `columns`, appending its `.proto`
+/// source to `source` at the given nesting `depth`.
+///
+/// `scope` is the fully qualified name of the message, which nested messages
+/// are declared within.
+fn generate_message(
+    name: &str,
+    columns: &[(ColumnName, ColumnType)],
+    scope: &str,
+    depth: usize,
+    source: &mut String,
+) -> Result<DescriptorProto, anyhow::Error> {
+    let indent = "  ".repeat(depth);
+    let mut message = DescriptorProto {
+        name: Some(name.into()),
+        ..Default::default()
+    };
+    let mut fields = String::new();
+    writeln!(source, "{indent}message {name} {{").expect("writing to string cannot fail");
+    for (number, (field_name, (_, typ))) in
+        field_names(columns).into_iter().zip(columns).enumerate()
+    {
+        let number = i32::try_from(number + 1).context("too many columns")?;
+        let mut field = FieldDescriptorProto {
+            name: Some(field_name.clone()),
+            number: Some(number),
+            label: Some(Label::Optional.into()),
+            ..Default::default()
+        };
+        match &typ.scalar_type {
+            ScalarType::Record { fields: record, .. } => {
+                let nested_name = upper_camel_case(&field_name);
+                let nested_scope = format!("{scope}.{nested_name}");
+                let nested =
+                    generate_message(&nested_name, record, &nested_scope, depth + 1, source)?;
+                message.nested_type.push(nested);
+                field.r#type = Some(Type::Message.into());
+                field.type_name = Some(nested_scope);
+                writeln!(fields, "{indent}  {nested_name} {field_name} = {number};")
+                    .expect("writing to string cannot fail");
+            }
+            scalar_type => {
+                let (ty, ty_name) = proto_type(scalar_type);
+                field.r#type = Some(ty.into());
+                let label = if typ.nullable {
+                    // Nullable columns are represented by proto3 `optional`
+                    // fields, which are backed by a synthetic oneof.
+                    let oneof_index =
+                        i32::try_from(message.oneof_decl.len()).expect("fewer oneofs than fields");
+                    message.oneof_decl.push(OneofDescriptorProto {
+                        name: Some(format!("_{field_name}")),
+                        ..Default::default()
+                    });
+                    field.oneof_index = Some(oneof_index);
+                    field.proto3_optional = Some(true);
+                    "optional "
+                } else {
+                    ""
+                };
+                writeln!(
+                    fields,
+                    "{indent}  {label}{ty_name} {field_name} = {number};"
+                )
+                .expect("writing to string cannot fail");
+            }
+        }
+        message.field.push(field);
+    }
+    source.push_str(&fields);
+    writeln!(source, "{indent}}}").expect("writing to string cannot fail");
+    Ok(message)
+}
+
+/// Returns the names of the fields that `columns` are encoded into.
+fn field_names(columns: &[(ColumnName, ColumnType)]) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    columns
+        .iter()
+        .map(|(name, _)| {
+            let mut field: String = name
+                .as_str()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            if !field.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                field.insert(0, '_');
+            }
+            let stem_len = field.len();
+            let mut i = 1;
+            while seen.contains(&field) {
+                field.truncate(stem_len);
+                field.push_str(&format!("_{i}"));
+                i += 1;
+            }
+            seen.insert(field.clone());
+            field
+        })
+        .collect()
+}
+
+/// Converts a field name like `some_field` to a message name like `SomeField`.
+fn upper_camel_case(field_name: &str) -> String {
+    let mut name = String::with_capacity(field_name.len());
+    for part in field_name.split('_').filter(|part| !part.is_empty()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.push(first.to_ascii_uppercase());
+            name.extend(chars);
+        }
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, 'M');
+    }
+    name
+}
+
+/// Returns the Protobuf type that values of `scalar_type` are encoded as, and
+/// its name in `.proto` source.
+fn proto_type(scalar_type: &ScalarType) -> (Type, &'static str) {
+    match scalar_type {
+        ScalarType::Bool => (Type::Bool, "bool"),
+        ScalarType::Int16 | ScalarType::Int32 => (Type::Int32, "int32"),
+        ScalarType::Int64 => (Type::Int64, "int64"),
+        ScalarType::UInt16 | ScalarType::UInt32 => (Type::Uint32, "uint32"),
+        ScalarType::UInt64 => (Type::Uint64, "uint64"),
+        ScalarType::Float32 => (Type::Float, "float"),
+        ScalarType::Float64 => (Type::Double, "double"),
+        ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch => {
+            (Type::Bytes, "bytes")
+        }
+        _ => (Type::String, "string"),
+    }
+}
+
+/// Reports whether values of `scalar_type` can be encoded into a field of
+/// kind `kind`.
+fn is_compatible(scalar_type: &ScalarType, kind: &Kind) -> bool {
+    match (scalar_type, kind) {
+        (ScalarType::Bool, Kind::Bool) => true,
+        (
+            ScalarType::Int16 | ScalarType::Int32,
+            Kind::Int32
+            | Kind::Sint32
+            | Kind::Sfixed32
+            | Kind::Int64
+            | Kind::Sint64
+            | Kind::Sfixed64,
+        ) => true,
+        (ScalarType::Int64, Kind::Int64 | Kind::Sint64 | Kind::Sfixed64) => true,
+        (
+            ScalarType::UInt16 | ScalarType::UInt32,
+            Kind::Uint32 | Kind::Fixed32 | Kind::Uint64 | Kind::Fixed64,
+        ) => true,
+        (ScalarType::UInt64, Kind::Uint64 | Kind::Fixed64) => true,
+        (ScalarType::Float32, Kind::Float | Kind::Double) => true,
+        (ScalarType::Float64, Kind::Double) => true,
+        (ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch, Kind::Bytes) => {
+            true
+        }
+        (ScalarType::Record { .. }, _) => false,
+        // Everything else can be encoded as text.
+        (_, Kind::String) => true,
+        _ => false,
+    }
+}
+
+/// Encodes rows as a particular Protobuf message.
+#[derive(Debug)]
+pub struct Encoder {
+    message: MessageEncoder,
+    /// The ID of the schema in the schema registry, if the message is to be
+    /// framed in the Confluent wire format.
+    schema_id: Option<i32>,
+}
+
+impl Encoder {
+    /// Constructs an encoder of the rows of `desc` as the message named
+    /// `message_name` in the encoded `FileDescriptorSet` `descriptors`.
+    ///
+    /// Each column must have a correspondingly named field of a compatible
+    /// type, as described in [`generate_schema`]. Fields that do not
+    /// correspond to a column are left unset.
+    pub fn new(
+        desc: RelationDesc,
+        debezium: bool,
+        descriptors: &[u8],
+        message_name: &str,
+        schema_id: Option<i32>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut columns = column_names_and_types(desc);
+        if debezium {
+            columns = envelopes::dbz_envelope(columns);
+        }
+        let pool = DescriptorPool::decode(descriptors).context("decoding file descriptor set")?;
+        let message_descriptor = pool.get_message_by_name(message_name).ok_or_else(|| {
+            anyhow!(
+                "protobuf message {} not found in file descriptor set",
+                message_name.quoted(),
+            )
+        })?;
+        Ok(Encoder {
+            message: MessageEncoder::new(message_descriptor, &columns)?,
+            schema_id,
+        })
+    }
+}
+
+impl Encode for Encoder {
+    fn encode_unchecked(&self, row: Row) -> Vec<u8> {
+        let mut buf = vec![];
+        if let Some(schema_id) = self.schema_id {
+            // The Confluent wire format consists of a magic byte (0), a big
+            // endian 32-bit schema ID, and the indexes of the message within
+            // the schema. The encoded message is always the first message in
+            // the schema, which is abbreviated to a single 0.
+            //
+            // https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+            buf.push(0);
+            buf.extend(schema_id.to_be_bytes());
+            buf.push(0);
+        }
+        self.message
+            .encode(row.iter())
+            .encode(&mut buf)
+            .expect("vec has unlimited capacity");
+        buf
+    }
+
+    fn hash(&self, buf: &[u8]) -> u64 {
+        // Compute a stable hash by ignoring the header which might contain a
+        // non-deterministic schema id.
+        match self.schema_id {
+            Some(_) => {
+                let (_schema_id, payload) = crate::confluent::extract_protobuf_header(buf).unwrap();
+                seahash::hash(payload)
+            }
+            None => seahash::hash(buf),
+        }
+    }
+}
+
+/// Encodes datums as the fields of a message.
+#[derive(Debug)]
+struct MessageEncoder {
+    descriptor: MessageDescriptor,
+    /// The field that each column is encoded into, along with the encoder of
+    /// the nested message for record columns.
+    fields: Vec<(FieldDescriptor, ColumnType, Option<MessageEncoder>)>,
+}
+
+impl MessageEncoder {
+    fn new(
+        descriptor: MessageDescriptor,
+        columns: &[(ColumnName, ColumnType)],
+    ) -> Result<Self, anyhow::Error> {
+        let mut fields = Vec::with_capacity(columns.len());
+        for (field_name, (column_name, typ)) in field_names(columns).into_iter().zip(columns) {
+            let field = descriptor.get_field_by_name(&field_name).ok_or_else(|| {
+                anyhow!(
+                    "protobuf message {} has no field {} for column {}",
+                    descriptor.full_name().quoted(),
+                    field_name.quoted(),
+                    column_name.quoted(),
+                )
+            })?;
+            if field.is_list() || field.is_map() {
+                bail!(
+                    "protobuf field {} is repeated, which is not supported in sinks",
+                    field.full_name().quoted()
+                );
+            }
+            if typ.nullable && !field.supports_presence() {
+                bail!(
+                    "column {} is nullable, but protobuf field {} cannot represent null; \
+                    declare it as optional",
+                    column_name.quoted(),
+                    field.full_name().quoted()
+                );
+            }
+            let nested = match (&typ.scalar_type, field.kind()) {
+                (ScalarType::Record { fields, .. }, Kind::Message(nested)) => {
+                    Some(MessageEncoder::new(nested, fields)?)
+                }
+                (scalar_type, kind) if is_compatible(scalar_type, &kind) => None,
+                (scalar_type, kind) => bail!(
+                    "column {} of type {:?} cannot be encoded as protobuf field {} of type {:?}",
+                    column_name.quoted(),
+                    scalar_type,
+                    field.full_name().quoted(),
+                    kind
+                ),
+            };
+            fields.push((field, typ.clone(), nested));
+        }
+        Ok(MessageEncoder { descriptor, fields })
+    }
+
+    fn encode<'a>(&self, datums: impl IntoIterator<Item = Datum<'a>>) -> DynamicMessage {
+        let mut message = DynamicMessage::new(self.descriptor.clone());
+        for ((field, typ, nested), datum) in self.fields.iter().zip_eq(datums) {
+            if datum.is_null() {
+                continue;
+            }
+            let value = match nested {
+                Some(nested) => Value::Message(nested.encode(&datum.unwrap_list())),
+                None => encode_value(datum, &typ.scalar_type, &field.kind()),
+            };
+            message.set_field(field, value);
+        }
+        message
+    }
+}
+
+/// Encodes a non-null datum as a value of a field of kind `kind`, which must
+/// be compatible with `scalar_type`.
+fn encode_value(datum: Datum, scalar_type: &ScalarType, kind: &Kind) -> Value {
+    match (kind, datum) {
+        (Kind::Bool, datum) => Value::Bool(datum.unwrap_bool()),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Datum::Int16(i)) => Value::I32(i.into()),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Datum::Int32(i)) => Value::I32(i),
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Datum::Int16(i)) => Value::I64(i.into()),
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Datum::Int32(i)) => Value::I64(i.into()),
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Datum::Int64(i)) => Value::I64(i),
+        (Kind::Uint32 | Kind::Fixed32, Datum::UInt16(i)) => Value::U32(i.into()),
+        (Kind::Uint32 | Kind::Fixed32, Datum::UInt32(i)) => Value::U32(i),
+        (Kind::Uint64 | Kind::Fixed64, Datum::UInt16(i)) => Value::U64(i.into()),
+        (Kind::Uint64 | Kind::Fixed64, Datum::UInt32(i)) => Value::U64(i.into()),
+        (Kind::Uint64 | Kind::Fixed64, Datum::UInt64(i)) => Value::U64(i),
+        (Kind::Float, datum) => Value::F32(datum.unwrap_float32()),
+        (Kind::Double, Datum::Float32(f)) => Value::F64(f.into_inner().into()),
+        (Kind::Double, Datum::Float64(f)) => Value::F64(f.into_inner()),
+        (Kind::Bytes, datum) => Value::Bytes(Bytes::copy_from_slice(datum.unwrap_bytes())),
+        (Kind::String, Datum::String(s)) => Value::String(s.to_owned()),
+        (Kind::String, datum) => {
+            let mut buf = BytesMut::new();
+            if let Some(value) = mz_pgrepr::Value::from_datum(datum, scalar_type) {
+                value.encode_text(&mut buf);
+            }
+            Value::String(String::from_utf8(buf.to_vec()).expect("text encoding is valid UTF-8"))
+        }
+        (kind, datum) => unreachable!("datum {datum:?} cannot be encoded as {kind:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::adt::date::Date;
+
+    use super::*;
+
+    fn desc() -> RelationDesc {
+        RelationDesc::builder()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("the name", ScalarType::String.nullable(true))
+            .with_column("ratio", ScalarType::Float32.nullable(false))
+            .with_column("created", ScalarType::Date.nullable(false))
+            .finish()
+    }
+
+    #[mz_ore::test]
+    fn generated_schema() {
+        let schema = generate_schema(desc(), false, "materialize.Value").unwrap();
+        assert_eq!(
+            schema.source,
+            r#"syntax = "proto3";
+
+package materialize;
+
+message Value {
+  int64 id = 1;
+  optional string the_name = 2;
+  float ratio = 3;
+  string created = 4;
+}
+"#
+        );
+
+        let schema = generate_schema(desc(), true, "Value").unwrap();
+        assert_eq!(
+            schema.source,
+            r#"syntax = "proto3";
+
+message Value {
+  message Row {
+    int64 id = 1;
+    optional string the_name = 2;
+    float ratio = 3;
+    string created = 4;
+  }
+  Row before = 1;
+  Row after = 2;
+}
+"#
+        );
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decNumberFromInt32` on OS `linux`
+    fn roundtrip() {
+        let schema = generate_schema(desc(), false, "materialize.Value").unwrap();
+        let encoder = Encoder::new(
+            desc(),
+            false,
+            &schema.descriptors,
+            "materialize.Value",
+            Some(7),
+        )
+        .unwrap();
+        let date = Date::from_pg_epoch(1).unwrap();
+        let row = Row::pack_slice(&[
+            Datum::Int64(42),
+            Datum::String("x"),
+            Datum::Float32(1.5f32.into()),
+            Datum::Date(date),
+        ]);
+        let buf = encoder.encode_unchecked(row);
+        assert_eq!(buf[..6], [0, 0, 0, 0, 7, 0]);
+
+        let descriptors =
+            DecodedDescriptors::from_bytes(&schema.descriptors, "materialize.Value".into())
+                .unwrap();
+        let mut decoder = Decoder::new(descriptors, true).unwrap();
+        let decoded = decoder.decode(&buf).unwrap().unwrap();
+        assert_eq!(
+            decoded,
+            Row::pack_slice(&[
+                Datum::Int64(42),
+                Datum::String("x"),
+                Datum::Float32(1.5f32.into()),
+                Datum::String("2000-01-02"),
+            ])
+        );
+
+        // Null values leave optional fields unset.
+        let row = Row::pack_slice(&[
+            Datum::Int64(42),
+            Datum::Null,
+            Datum::Float32(1.5f32.into()),
+            Datum::Date(date),
+        ]);
+        let message = encoder.message.encode(row.iter());
+        let field = message.descriptor().get_field_by_name("the_name").unwrap();
+        assert!(!message.has_field(&field));
+    }
+
+    #[mz_ore::test]
+    fn incompatible_schema() {
+        let other = RelationDesc::builder()
+            .with_column("id", ScalarType::Bool.nullable(false))
+            .finish();
+        let schema = generate_schema(other, false, "Value").unwrap();
+        let err = Encoder::new(desc(), false, &schema.descriptors, "Value", None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"column "id" of type Int64 cannot be encoded as protobuf field "Value.id" of type Bool"#
+        );
+    }
+}
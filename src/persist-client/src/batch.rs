@@ -35,7 +35,7 @@ use mz_persist_types::parquet::{CompressionFormat, EncodingConfig};
 use mz_persist_types::part::{Part, PartBuilder};
 use mz_persist_types::schema::SchemaId;
 use mz_persist_types::stats::{
-    PartStats, TRUNCATE_LEN, TruncateBound, trim_to_budget, truncate_bytes,
+    BloomFilterColumns, PartStats, TRUNCATE_LEN, TruncateBound, trim_to_budget, truncate_bytes,
 };
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::RustType;
@@ -60,7 +60,9 @@ use crate::internal::state::{
     BatchPart, HollowBatch, HollowBatchPart, HollowRun, HollowRunRef, ProtoInlineBatchPart,
     RunMeta, RunOrder, RunPart,
 };
-use crate::stats::{STATS_BUDGET_BYTES, STATS_COLLECTION_ENABLED, untrimmable_columns};
+use crate::stats::{
+    STATS_BUDGET_BYTES, STATS_COLLECTION_ENABLED, bloom_filter_columns, untrimmable_columns,
+};
 use crate::{PersistConfig, ShardId};
 
 include!(concat!(env!("OUT_DIR"), "/mz_persist_client.batch.rs"));
@@ -347,6 +349,7 @@ pub struct BatchBuilderConfig {
     pub(crate) stats_collection_enabled: bool,
     pub(crate) stats_budget: usize,
     pub(crate) stats_untrimmable_columns: Arc<UntrimmableColumns>,
+    pub(crate) stats_bloom_filter_columns: Arc<BloomFilterColumns>,
     pub(crate) encoding_config: EncodingConfig,
     pub(crate) preferred_order: RunOrder,
     pub(crate) structured_key_lower_len: usize,
@@ -440,6 +443,7 @@ impl BatchBuilderConfig {
             stats_collection_enabled: STATS_COLLECTION_ENABLED.get(value),
             stats_budget: STATS_BUDGET_BYTES.get(value),
             stats_untrimmable_columns: Arc::new(untrimmable_columns(value)),
+            stats_bloom_filter_columns: Arc::new(bloom_filter_columns(value)),
             encoding_config: EncodingConfig {
                 use_dictionary: ENCODING_ENABLE_DICTIONARY.get(value),
                 compression: CompressionFormat::from_str(&ENCODING_COMPRESSION_FORMAT.get(value)),
@@ -1093,13 +1097,17 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                             write_schemas.val.as_ref(),
                         );
 
-                        let key_stats = write_schemas
+                        let key_decoder = write_schemas
                             .key
                             .decoder_any(ext.key.as_ref())
-                            .expect("decoding just-encoded data")
-                            .stats();
+                            .expect("decoding just-encoded data");
+                        let key_stats = key_decoder.stats();
+                        let key_blooms = key_decoder.bloom_filters(&cfg.stats_bloom_filter_columns);
 
-                        let part_stats = PartStats { key: key_stats };
+                        let part_stats = PartStats {
+                            key: key_stats,
+                            blooms: key_blooms,
+                        };
 
                         // Collect stats about the updates, if stats collection is enabled.
                         let trimmed_start = Instant::now();
@@ -1330,7 +1338,15 @@ impl<T: Timestamp> PartDeletes<T> {
     // inserted.
     pub fn add(&mut self, part: &RunPart<T>) -> bool {
         match part {
+            RunPart::Many(r) if r.key.owner().is_some() => {
+                // Forked from another shard, which is responsible for deleting it.
+                true
+            }
             RunPart::Many(r) => self.hollow_runs.insert(r.key.clone(), r.clone()).is_none(),
+            RunPart::Single(BatchPart::Hollow(x)) if x.key.owner().is_some() => {
+                // Forked from another shard, which is responsible for deleting it.
+                true
+            }
             RunPart::Single(BatchPart::Hollow(x)) => self.blob_keys.insert(x.key.clone()),
             RunPart::Single(BatchPart::Inline { .. }) => {
                 // Nothing to delete.
@@ -1339,6 +1355,15 @@ impl<T: Timestamp> PartDeletes<T> {
         }
     }
 
+    /// Removes the parts and runs with any of the given keys, so they're not
+    /// deleted. Returns the number removed.
+    pub fn retain_unleased(&mut self, leased: &BTreeSet<PartialBatchKey>) -> usize {
+        let before = self.len();
+        self.blob_keys.retain(|key| !leased.contains(key));
+        self.hollow_runs.retain(|key, _| !leased.contains(key));
+        before - self.len()
+    }
+
     pub fn contains(&self, part: &RunPart<T>) -> bool {
         match part {
             RunPart::Many(r) => self.hollow_runs.contains_key(&r.key),
@@ -31,11 +31,11 @@ use mz_persist::location::{
 use mz_persist_types::{Codec, Codec64};
 use timely::progress::Timestamp;
 use tokio::sync::{Mutex, OnceCell};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::async_runtime::IsolatedRuntime;
 use crate::error::{CodecConcreteType, CodecMismatch};
-use crate::internal::cache::BlobMemCache;
+use crate::internal::cache::{BlobDiskCache, BlobMemCache};
 use crate::internal::machine::retry_external;
 use crate::internal::metrics::{LockMetrics, Metrics, MetricsBlob, MetricsConsensus, ShardMetrics};
 use crate::internal::state::TypedState;
@@ -224,8 +224,29 @@ impl PersistClientCache {
                     Self::PROMETHEUS_SCRAPE_INTERVAL,
                 )
                 .await;
-                // This is intentionally "outside" (wrapping) MetricsBlob so
-                // that we don't include cached responses in blob metrics.
+                // The caches are intentionally "outside" (wrapping) MetricsBlob
+                // so that we don't include cached responses in blob metrics.
+                let blob: Arc<dyn Blob> = blob;
+                let blob = match &self.cfg.blob_cache_disk_dir {
+                    None => blob,
+                    Some(dir) => {
+                        let disk_cache = BlobDiskCache::new(
+                            &self.cfg,
+                            Arc::clone(&self.metrics),
+                            Arc::clone(&blob),
+                            dir.clone(),
+                        )
+                        .await;
+                        match disk_cache {
+                            Ok(disk_cache) => disk_cache,
+                            Err(err) => {
+                                // The cache is an optimization, so don't fail.
+                                warn!("failed to open disk blob cache at {dir:?}: {err}");
+                                blob
+                            }
+                        }
+                    }
+                };
                 let blob = BlobMemCache::new(&self.cfg, Arc::clone(&self.metrics), blob);
                 Arc::clone(&x.insert((RttLatencyTask(task.abort_on_drop()), blob)).1)
             }
@@ -11,6 +11,7 @@
 
 //! The tunable knobs for persist.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -132,6 +133,9 @@ pub struct PersistConfig {
     /// Number of worker threads to create for the [`crate::IsolatedRuntime`], defaults to the
     /// number of threads.
     pub isolated_runtime_worker_threads: usize,
+    /// Directory on local disk for caching blobs, if any. Only used if
+    /// `persist_blob_cache_disk_enabled` is set.
+    pub blob_cache_disk_dir: Option<PathBuf>,
 }
 
 // Impl Deref to ConfigSet for convenience of accessing the dynamic configs.
@@ -173,6 +177,7 @@ impl PersistConfig {
             writer_lease_duration: 60 * Duration::from_secs(60),
             critical_downgrade_interval: Duration::from_secs(30),
             isolated_runtime_worker_threads: num_cpus::get(),
+            blob_cache_disk_dir: None,
             // TODO: This doesn't work with the process orchestrator. Instead,
             // separate --log-prefix into --service-name and --enable-log-prefix
             // options, where the first is always provided and the second is
@@ -313,6 +318,8 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&crate::fetch::FETCH_SEMAPHORE_COST_ADJUSTMENT)
         .add(&crate::fetch::FETCH_SEMAPHORE_PERMIT_ADJUSTMENT)
         .add(&crate::fetch::OPTIMIZE_IGNORED_DATA_FETCH)
+        .add(&crate::internal::cache::BLOB_CACHE_DISK_ENABLED)
+        .add(&crate::internal::cache::BLOB_CACHE_DISK_LIMIT_BYTES)
         .add(&crate::internal::cache::BLOB_CACHE_MEM_LIMIT_BYTES)
         .add(&crate::internal::cache::BLOB_CACHE_SCALE_WITH_THREADS)
         .add(&crate::internal::cache::BLOB_CACHE_SCALE_FACTOR_BYTES)
@@ -345,6 +352,8 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&crate::rpc::PUBSUB_STATE_CACHE_SHARD_REF_CHANNEL_SIZE)
         .add(&crate::rpc::PUBSUB_RECONNECT_BACKOFF)
         .add(&crate::stats::STATS_AUDIT_PERCENT)
+        .add(&crate::stats::STATS_BLOOM_FILTER_BUDGET_BYTES)
+        .add(&crate::stats::STATS_BLOOM_FILTER_COLUMNS)
         .add(&crate::stats::STATS_BUDGET_BYTES)
         .add(&crate::stats::STATS_COLLECTION_ENABLED)
         .add(&crate::stats::STATS_FILTER_ENABLED)
@@ -11,6 +11,7 @@
 
 use std::any::Any;
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -42,6 +43,7 @@ use crate::internal::gc::{GarbageCollector, GcReq};
 use crate::internal::machine::Machine;
 use crate::internal::trace::FueledMergeRes;
 use crate::rpc::{NoopPubSubSender, PubSubSender};
+use crate::stats::STATS_COLLECTION_ENABLED;
 use crate::write::{WriteHandle, WriterId};
 use crate::{
     BUILD_INFO, Diagnostics, Metrics, PersistClient, PersistConfig, ShardId, StateVersions,
@@ -74,6 +76,14 @@ pub(crate) enum Command {
     ForceGc(ForceGcArgs),
     /// Manually finalize an unfinalized shard.
     Finalize(FinalizeArgs),
+    /// Create a new shard that references the batches of an existing one as
+    /// of some time.
+    ForkShard(ForkShardArgs),
+    /// Write the consolidated contents of a shard as of some time to Parquet
+    /// files, for offline backups.
+    ExportShard(ExportShardArgs),
+    /// Write the contents of an export to a shard that has never been written.
+    ImportShard(ImportShardArgs),
     /// Attempt to ensure that all the files referenced by consensus are available
     /// in Blob.
     RestoreBlob(RestoreBlobArgs),
@@ -112,6 +122,44 @@ pub(crate) struct FinalizeArgs {
     force_downgrade_upper: bool,
 }
 
+/// Creates a new shard that references the batches of an existing one.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ForkShardArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// The time as of which to fork the shard. Must be readable in the shard.
+    #[clap(long)]
+    as_of: u64,
+}
+
+/// Exports the contents of a shard to Parquet files.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ExportShardArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// The time as of which to export the shard. Must be readable in the shard.
+    #[clap(long)]
+    as_of: u64,
+
+    /// The directory to write the manifest and Parquet files to.
+    #[clap(long)]
+    dir: PathBuf,
+}
+
+/// Imports the contents of an export into a shard.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ImportShardArgs {
+    /// The shard to import into.
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// The directory with the manifest and Parquet files of the export.
+    #[clap(long)]
+    dir: PathBuf,
+}
+
 /// Attempt to restore all the blobs that are referenced by the current state of consensus.
 #[derive(Debug, clap::Parser)]
 pub(crate) struct RestoreBlobArgs {
@@ -223,24 +271,9 @@ pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
             .await?;
 
             if force_downgrade_upper {
-                let isolated_runtime = Arc::new(IsolatedRuntime::new(&metrics_registry, None));
-                let pubsub_sender: Arc<dyn PubSubSender> = Arc::new(NoopPubSubSender);
-                let shared_states = Arc::new(StateCache::new(
-                    &cfg,
-                    Arc::clone(&metrics),
-                    Arc::clone(&pubsub_sender),
-                ));
-
                 // We need a PersistClient to open a write handle so we can append an empty batch.
-                let persist_client = PersistClient::new(
-                    cfg,
-                    blob,
-                    consensus,
-                    metrics,
-                    isolated_runtime,
-                    shared_states,
-                    pubsub_sender,
-                )?;
+                let persist_client =
+                    make_persist_client(cfg, consensus, blob, metrics, &metrics_registry)?;
                 let diagnostics = Diagnostics {
                     shard_name: shard_id.to_string(),
                     handle_purpose: "persist-cli finalize shard".to_string(),
@@ -314,6 +347,240 @@ pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
             }
             info_log_non_zero_metrics(&metrics_registry.gather());
         }
+        Command::ForkShard(args) => {
+            let ForkShardArgs {
+                state:
+                    StateArgs {
+                        shard_id,
+                        consensus_uri,
+                        blob_uri,
+                    },
+                as_of,
+            } = args;
+            let shard_id = ShardId::from_str(&shard_id).expect("invalid shard id");
+            let commit = command.commit;
+            let expected_version = command
+                .expected_version
+                .as_ref()
+                .map(|v| Version::parse(v))
+                .transpose()?;
+
+            let configs = all_dyncfgs(ConfigSet::default());
+            // TODO: Fetch the latest values of these configs from Launch Darkly.
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), configs);
+            let metrics_registry = MetricsRegistry::new();
+            let metrics = Arc::new(Metrics::new(&cfg, &metrics_registry));
+            let consensus =
+                make_consensus(&cfg, &consensus_uri, commit, Arc::clone(&metrics)).await?;
+            let blob = make_blob(&cfg, &blob_uri, commit, Arc::clone(&metrics)).await?;
+
+            // Open a machine to set our fake codecs.
+            let machine = make_machine(
+                &cfg,
+                Arc::clone(&consensus),
+                Arc::clone(&blob),
+                Arc::clone(&metrics),
+                shard_id,
+                commit,
+                expected_version,
+            )
+            .await?;
+            let as_of = Antichain::from_elem(as_of);
+
+            if !commit {
+                match machine.applier.snapshot(&as_of) {
+                    Ok(batches) => info!(
+                        "would fork {} batches with {} parts as of {:?}",
+                        batches.len(),
+                        batches.iter().map(|b| b.part_count()).sum::<usize>(),
+                        as_of
+                    ),
+                    Err(err) => bail!("cannot fork as of {as_of:?}: {err:?}"),
+                }
+                return Ok(());
+            }
+
+            let persist_client = make_persist_client(
+                cfg,
+                consensus,
+                blob,
+                Arc::clone(&metrics),
+                &metrics_registry,
+            )?;
+            let diagnostics = Diagnostics {
+                shard_name: shard_id.to_string(),
+                handle_purpose: "persist-cli fork shard".to_string(),
+            };
+            let fork_id = persist_client
+                .fork_shard::<crate::cli::inspect::K, crate::cli::inspect::V, u64, i64>(
+                    shard_id,
+                    as_of.clone(),
+                    diagnostics,
+                )
+                .await?;
+            info!("forked {shard_id} as of {as_of:?} into {fork_id}");
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
+        Command::ExportShard(args) => {
+            let ExportShardArgs {
+                state:
+                    StateArgs {
+                        shard_id,
+                        consensus_uri,
+                        blob_uri,
+                    },
+                as_of,
+                dir,
+            } = args;
+            let shard_id = ShardId::from_str(&shard_id).expect("invalid shard id");
+            let commit = command.commit;
+            let expected_version = command
+                .expected_version
+                .as_ref()
+                .map(|v| Version::parse(v))
+                .transpose()?;
+
+            let configs = all_dyncfgs(ConfigSet::default());
+            // TODO: Fetch the latest values of these configs from Launch Darkly.
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), configs);
+            let metrics_registry = MetricsRegistry::new();
+            let metrics = Arc::new(Metrics::new(&cfg, &metrics_registry));
+            let consensus =
+                make_consensus(&cfg, &consensus_uri, commit, Arc::clone(&metrics)).await?;
+            let blob = make_blob(&cfg, &blob_uri, commit, Arc::clone(&metrics)).await?;
+
+            // Open a machine to set our fake codecs.
+            let _machine = make_machine(
+                &cfg,
+                Arc::clone(&consensus),
+                Arc::clone(&blob),
+                Arc::clone(&metrics),
+                shard_id,
+                commit,
+                expected_version,
+            )
+            .await?;
+
+            // The export holds a reader lease on the shard, so that none of the
+            // blobs it's reading are deleted out from under it.
+            if !commit {
+                info!("skipping export because --commit is not set");
+                return Ok(());
+            }
+
+            let persist_client = make_persist_client(
+                cfg,
+                consensus,
+                blob,
+                Arc::clone(&metrics),
+                &metrics_registry,
+            )?;
+            let diagnostics = Diagnostics {
+                shard_name: shard_id.to_string(),
+                handle_purpose: "persist-cli export shard".to_string(),
+            };
+            let manifest = crate::export::export_shard::<
+                crate::cli::inspect::K,
+                crate::cli::inspect::V,
+                u64,
+                i64,
+            >(
+                &persist_client,
+                shard_id,
+                Arc::new(TodoSchema::default()),
+                Arc::new(TodoSchema::default()),
+                as_of,
+                &dir,
+                diagnostics,
+            )
+            .await?;
+            info!(
+                "exported {} updates in {} files to {}",
+                manifest.files.iter().map(|f| f.updates).sum::<usize>(),
+                manifest.files.len(),
+                dir.display()
+            );
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
+        Command::ImportShard(args) => {
+            let ImportShardArgs {
+                state:
+                    StateArgs {
+                        shard_id,
+                        consensus_uri,
+                        blob_uri,
+                    },
+                dir,
+            } = args;
+            let shard_id = ShardId::from_str(&shard_id).expect("invalid shard id");
+            let commit = command.commit;
+
+            let manifest = crate::export::read_manifest(&dir).await?;
+            if !commit {
+                info!(
+                    "would import {} updates in {} files exported from {} into {}",
+                    manifest.files.iter().map(|f| f.updates).sum::<usize>(),
+                    manifest.files.len(),
+                    manifest.shard_id,
+                    shard_id
+                );
+                return Ok(());
+            }
+
+            // The shard doesn't exist yet, so take our fake codecs from the
+            // export instead of from its state.
+            *crate::cli::inspect::KVTD_CODECS.lock().expect("lockable") = (
+                manifest.key_codec,
+                manifest.val_codec,
+                manifest.ts_codec,
+                manifest.diff_codec,
+                None,
+            );
+
+            let configs = all_dyncfgs(ConfigSet::default());
+            // TODO: Fetch the latest values of these configs from Launch Darkly.
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), configs);
+            // Our fake schemas can't compute stats. The parts get them when
+            // they're next compacted.
+            cfg.set_config(&STATS_COLLECTION_ENABLED, false);
+            let metrics_registry = MetricsRegistry::new();
+            let metrics = Arc::new(Metrics::new(&cfg, &metrics_registry));
+            let consensus =
+                make_consensus(&cfg, &consensus_uri, commit, Arc::clone(&metrics)).await?;
+            let blob = make_blob(&cfg, &blob_uri, commit, Arc::clone(&metrics)).await?;
+            let persist_client = make_persist_client(
+                cfg,
+                consensus,
+                blob,
+                Arc::clone(&metrics),
+                &metrics_registry,
+            )?;
+            let diagnostics = Diagnostics {
+                shard_name: shard_id.to_string(),
+                handle_purpose: "persist-cli import shard".to_string(),
+            };
+            let manifest = crate::export::import_shard::<
+                crate::cli::inspect::K,
+                crate::cli::inspect::V,
+                u64,
+                i64,
+            >(
+                &persist_client,
+                shard_id,
+                Arc::new(TodoSchema::default()),
+                Arc::new(TodoSchema::default()),
+                &dir,
+                diagnostics,
+            )
+            .await?;
+            info!(
+                "imported {} updates exported from {} into {}",
+                manifest.files.iter().map(|f| f.updates).sum::<usize>(),
+                manifest.shard_id,
+                shard_id
+            );
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
         Command::RestoreBlob(args) => {
             let RestoreBlobArgs {
                 state:
@@ -532,6 +799,32 @@ where
     }
 }
 
+/// Makes a [PersistClient] for commands that need more than a [Machine].
+fn make_persist_client(
+    cfg: PersistConfig,
+    consensus: Arc<dyn Consensus>,
+    blob: Arc<dyn Blob>,
+    metrics: Arc<Metrics>,
+    metrics_registry: &MetricsRegistry,
+) -> Result<PersistClient, ExternalError> {
+    let isolated_runtime = Arc::new(IsolatedRuntime::new(metrics_registry, None));
+    let pubsub_sender: Arc<dyn PubSubSender> = Arc::new(NoopPubSubSender);
+    let shared_states = Arc::new(StateCache::new(
+        &cfg,
+        Arc::clone(&metrics),
+        Arc::clone(&pubsub_sender),
+    ));
+    PersistClient::new(
+        cfg,
+        blob,
+        consensus,
+        metrics,
+        isolated_runtime,
+        shared_states,
+        pubsub_sender,
+    )
+}
+
 async fn make_machine(
     cfg: &PersistConfig,
     consensus: Arc<dyn Consensus>,
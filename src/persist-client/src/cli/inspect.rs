@@ -473,6 +473,7 @@ pub async fn blob_counts(blob_uri: &SensitiveUrl) -> Result<impl serde::Serializ
                     blob_count.rollup_count += 1;
                     blob_count.rollup_bytes += usize::cast_from(metadata.size_in_bytes);
                 }
+                Ok((_, PartialBlobKey::ForkLease(_))) => {}
                 Err(err) => {
                     eprintln!("error parsing blob: {}", err);
                 }
@@ -585,7 +586,7 @@ pub async fn unreferenced_blobs(args: &StateArgs) -> Result<impl serde::Serializ
                 Ok((_, PartialBlobKey::Rollup(seqno, rollup))) => {
                     all_rollups.push(PartialRollupKey::new(seqno, &rollup));
                 }
-                Err(_) => {}
+                Ok((_, PartialBlobKey::ForkLease(_))) | Err(_) => {}
             },
         )
         .await?;
@@ -694,9 +695,9 @@ pub async fn blob_usage(args: &StateArgs) -> Result<(), anyhow::Error> {
 /// return static Codec names, and rebind the names if/when we get a CodecMismatch, so we can convince
 /// the type system and our safety checks that we really can read the data.
 
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct K;
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct V;
 #[derive(Default, Debug, PartialEq, Eq)]
 struct T;
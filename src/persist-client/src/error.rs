@@ -79,6 +79,8 @@ pub enum InvalidUsage<T> {
     CodecMismatch(Box<CodecMismatch>),
     /// An invalid usage of [crate::batch::Batch::rewrite_ts].
     InvalidRewrite(String),
+    /// An invalid usage of [crate::PersistClient::fork_shard].
+    InvalidFork(String),
 }
 
 impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
@@ -126,6 +128,7 @@ impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
             }
             InvalidUsage::CodecMismatch(err) => std::fmt::Display::fmt(err, f),
             InvalidUsage::InvalidRewrite(err) => write!(f, "invalid rewrite: {err}"),
+            InvalidUsage::InvalidFork(err) => write!(f, "invalid fork: {err}"),
         }
     }
 }
@@ -0,0 +1,639 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Export and import of the contents of a shard, for offline backups.
+//!
+//! An export is a directory with a [MANIFEST_FILE] and some number of Parquet
+//! files. Each Parquet file has a `k` and a `v` column, with the structured
+//! encoding of the shard's key and val schemas, and a `t` and a `d` column,
+//! with the [Codec64] encoding of the times and diffs. The updates are
+//! consolidated as of a single time, so an export doesn't depend on how the
+//! shard's batches happened to be laid out or on the version of persist that
+//! wrote them.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use arrow::array::{Array, ArrayRef, Int64Array};
+use arrow::record_batch::RecordBatch;
+use bytes::Bytes;
+use differential_dataflow::difference::Semigroup;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::trace::Description;
+use mz_persist_types::part::Part;
+use mz_persist_types::schema::SchemaId;
+use mz_persist_types::{Codec, Codec64, StepForward};
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+use timely::progress::{Antichain, Timestamp};
+use tracing::info;
+
+use crate::batch::{BatchBuilderConfig, BatchBuilderInternal, BatchParts};
+use crate::internal::encoding::Schemas;
+use crate::internal::gc::GarbageCollector;
+use crate::internal::state::{EncodedSchemas, RunOrder, Since, SnapshotErr, Upper};
+use crate::{Diagnostics, PersistClient, ShardId};
+
+/// The name of the manifest in an export directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// The version of the export format written by this version of persist.
+pub const EXPORT_FORMAT_VERSION: u64 = 1;
+
+/// Describes the contents of an export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// The version of the export format.
+    pub format_version: u64,
+    /// The version of persist that wrote the export.
+    pub build_version: String,
+    /// The shard that was exported.
+    pub shard_id: ShardId,
+    /// The [Codec64] encoding of the time the updates are consolidated as of.
+    pub as_of: i64,
+    /// The name of the key codec of the exported shard.
+    pub key_codec: String,
+    /// The name of the val codec of the exported shard.
+    pub val_codec: String,
+    /// The name of the timestamp codec of the exported shard.
+    pub ts_codec: String,
+    /// The name of the diff codec of the exported shard.
+    pub diff_codec: String,
+    /// The latest schemas registered for the exported shard, if any.
+    pub schemas: Option<ExportedSchemas>,
+    /// The Parquet files with the exported updates.
+    pub files: Vec<ExportedFile>,
+}
+
+/// The schemas of an exported shard, as they were registered in its state.
+///
+/// Each field is hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedSchemas {
+    /// The id of the schemas in the exported shard.
+    pub id: SchemaId,
+    /// The key schema, encoded with [Codec::encode_schema].
+    pub key: String,
+    /// The arrow `DataType` of the key schema, encoded as a `ProtoDataType`.
+    pub key_data_type: String,
+    /// The val schema, encoded with [Codec::encode_schema].
+    pub val: String,
+    /// The arrow `DataType` of the val schema, encoded as a `ProtoDataType`.
+    pub val_data_type: String,
+}
+
+impl ExportedSchemas {
+    fn new(id: SchemaId, schemas: &EncodedSchemas) -> Self {
+        ExportedSchemas {
+            id,
+            key: hex::encode(&schemas.key),
+            key_data_type: hex::encode(&schemas.key_data_type),
+            val: hex::encode(&schemas.val),
+            val_data_type: hex::encode(&schemas.val_data_type),
+        }
+    }
+
+    fn decode(&self) -> Result<EncodedSchemas, anyhow::Error> {
+        let decode = |x: &str| hex::decode(x).map(Bytes::from);
+        Ok(EncodedSchemas {
+            key: decode(&self.key)?,
+            key_data_type: decode(&self.key_data_type)?,
+            val: decode(&self.val)?,
+            val_data_type: decode(&self.val_data_type)?,
+        })
+    }
+}
+
+/// A Parquet file in an export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedFile {
+    /// The name of the file, relative to the export directory.
+    pub name: String,
+    /// The number of updates in the file.
+    pub updates: usize,
+}
+
+/// Writes the consolidated contents of `shard_id` as of `as_of` to the
+/// directory `dir`, which is created if it doesn't exist.
+///
+/// The `key_schema` and `val_schema` are only used to read parts that predate
+/// structured data, which are not supported. Returns an error if `as_of` is not
+/// readable in the shard.
+pub async fn export_shard<K, V, T, D>(
+    client: &PersistClient,
+    shard_id: ShardId,
+    key_schema: Arc<K::Schema>,
+    val_schema: Arc<V::Schema>,
+    as_of: T,
+    dir: &Path,
+    diagnostics: Diagnostics,
+) -> Result<ExportManifest, anyhow::Error>
+where
+    K: Debug + Codec + Ord,
+    V: Debug + Codec + Ord,
+    T: Timestamp + Lattice + Codec64 + Sync,
+    D: Semigroup + Ord + Codec64 + Send + Sync,
+{
+    let mut read = client
+        .open_leased_reader::<K, V, T, D>(shard_id, key_schema, val_schema, diagnostics, false)
+        .await?;
+    let as_of_frontier = Antichain::from_elem(as_of.clone());
+
+    let files = async {
+        // A snapshot would wait for the as_of to become readable, which isn't
+        // what anyone running an export wants.
+        let schemas = match read.machine.applier.snapshot(&as_of_frontier) {
+            Ok(_) => read.machine.applier.schemas(|_, schemas| {
+                schemas
+                    .last_key_value()
+                    .map(|(id, schemas)| ExportedSchemas::new(*id, schemas))
+            }),
+            Err(SnapshotErr::AsOfNotYetAvailable(_, Upper(upper))) => {
+                bail!("as_of {:?} is not less than upper {:?}", as_of, upper)
+            }
+            Err(SnapshotErr::AsOfHistoricalDistinctionsLost(Since(since))) => {
+                bail!("as_of {:?} is not beyond since {:?}", as_of, since)
+            }
+        };
+        let mut cursor = read
+            .snapshot_cursor(as_of_frontier.clone(), |_| true)
+            .await
+            .map_err(|Since(since)| anyhow!("as_of {:?} is not beyond since {:?}", as_of, since))?;
+
+        tokio::fs::create_dir_all(dir).await?;
+        let mut files = Vec::new();
+        while let Some(part) = cursor.next_part().await {
+            if part.len() == 0 {
+                continue;
+            }
+            let file = ExportedFile {
+                name: format!("part-{:05}.parquet", files.len()),
+                updates: part.len(),
+            };
+            tokio::fs::write(dir.join(&file.name), encode_part(part)?).await?;
+            info!("exported {} updates to {}", file.updates, file.name);
+            files.push(file);
+        }
+        Ok::<_, anyhow::Error>((schemas, files))
+    }
+    .await;
+    read.expire().await;
+    let (schemas, files) = files?;
+
+    let manifest = ExportManifest {
+        format_version: EXPORT_FORMAT_VERSION,
+        build_version: client.cfg.build_version.to_string(),
+        shard_id,
+        as_of: i64::from_le_bytes(T::encode(&as_of)),
+        key_codec: K::codec_name(),
+        val_codec: V::codec_name(),
+        ts_codec: T::codec_name(),
+        diff_codec: D::codec_name(),
+        schemas,
+        files,
+    };
+    let bytes = serde_json::to_vec_pretty(&manifest)?;
+    tokio::fs::write(dir.join(MANIFEST_FILE), bytes).await?;
+    Ok(manifest)
+}
+
+/// Reads the manifest of the export in `dir`.
+pub async fn read_manifest(dir: &Path) -> Result<ExportManifest, anyhow::Error> {
+    let bytes = tokio::fs::read(dir.join(MANIFEST_FILE)).await?;
+    let manifest: ExportManifest = serde_json::from_slice(&bytes)?;
+    if manifest.format_version != EXPORT_FORMAT_VERSION {
+        bail!(
+            "unsupported export format version {}, expected {}",
+            manifest.format_version,
+            EXPORT_FORMAT_VERSION
+        );
+    }
+    Ok(manifest)
+}
+
+/// Writes the contents of the export in `dir` to `shard_id`, which must not
+/// have been written to.
+///
+/// The shard ends up with the exported schemas, a since of the export's as_of,
+/// and an upper just past it. The `key_schema` and `val_schema` are only used
+/// to compute stats for the written parts.
+pub async fn import_shard<K, V, T, D>(
+    client: &PersistClient,
+    shard_id: ShardId,
+    key_schema: Arc<K::Schema>,
+    val_schema: Arc<V::Schema>,
+    dir: &Path,
+    diagnostics: Diagnostics,
+) -> Result<ExportManifest, anyhow::Error>
+where
+    K: Debug + Codec,
+    V: Debug + Codec,
+    T: Timestamp + Lattice + Codec64 + StepForward + Sync,
+    D: Semigroup + Codec64 + Send + Sync,
+{
+    let manifest = read_manifest(dir).await?;
+    let codecs = (
+        K::codec_name(),
+        V::codec_name(),
+        T::codec_name(),
+        D::codec_name(),
+    );
+    let exported = (
+        manifest.key_codec.clone(),
+        manifest.val_codec.clone(),
+        manifest.ts_codec.clone(),
+        manifest.diff_codec.clone(),
+    );
+    if codecs != exported {
+        bail!(
+            "requested codecs {:?} did not match exported {:?}",
+            codecs,
+            exported
+        );
+    }
+    let (schema_id, schemas) = match &manifest.schemas {
+        Some(schemas) => (
+            Some(schemas.id),
+            BTreeMap::from([(schemas.id, schemas.decode()?)]),
+        ),
+        None => (None, BTreeMap::new()),
+    };
+
+    let machine = client
+        .make_machine::<K, V, T, D>(shard_id, diagnostics)
+        .await?;
+    let lower = Antichain::from_elem(T::minimum());
+    let upper = machine.applier.clone_upper();
+    if upper != lower {
+        bail!("cannot import into {}: upper is {:?}", shard_id, upper);
+    }
+
+    let as_of = T::decode(manifest.as_of.to_le_bytes());
+    let desc = Description::new(
+        lower,
+        Antichain::from_elem(as_of.step_forward()),
+        Antichain::from_elem(T::minimum()),
+    );
+    let cfg = BatchBuilderConfig::new(&client.cfg, shard_id);
+    // The exported parts are consolidated, but they were written by another
+    // version of persist, so don't rely on their order.
+    let parts = BatchParts::new_ordered(
+        cfg.clone(),
+        RunOrder::Unordered,
+        Arc::clone(&client.metrics),
+        Arc::clone(&machine.applier.shard_metrics),
+        shard_id,
+        Arc::clone(&client.blob),
+        Arc::clone(&client.isolated_runtime),
+        &client.metrics.user,
+    );
+    let mut builder = BatchBuilderInternal::<K, V, T, D>::new(
+        cfg,
+        parts,
+        Arc::clone(&client.metrics),
+        Schemas {
+            id: schema_id,
+            key: key_schema,
+            val: val_schema,
+        },
+        Arc::clone(&client.blob),
+        shard_id,
+        client.cfg.build_version.clone(),
+    );
+    for file in &manifest.files {
+        let bytes = tokio::fs::read(dir.join(&file.name)).await?;
+        let part = decode_part(Bytes::from(bytes))?;
+        if part.len() != file.updates {
+            bail!(
+                "{} has {} updates but the manifest expected {}",
+                file.name,
+                part.len(),
+                file.updates
+            );
+        }
+        builder.flush_part(desc.clone(), part).await;
+        info!("imported {} updates from {}", file.updates, file.name);
+    }
+    let mut batch = builder.finish(desc).await?;
+
+    let gc = GarbageCollector::new(machine.clone(), Arc::clone(&client.isolated_runtime));
+    let (res, maintenance) = machine
+        .fork_from(
+            &schemas,
+            &[batch.batch.clone()],
+            &Antichain::from_elem(as_of),
+        )
+        .await;
+    match res {
+        Ok(()) => batch.mark_consumed(),
+        Err(Upper(upper)) => {
+            batch.delete().await;
+            bail!("cannot import into {}: upper is {:?}", shard_id, upper);
+        }
+    }
+    let () = maintenance.perform(&machine, &gc).await;
+
+    Ok(manifest)
+}
+
+fn encode_part(part: Part) -> Result<Vec<u8>, anyhow::Error> {
+    let batch = RecordBatch::try_from_iter([
+        ("k", part.key),
+        ("v", part.val),
+        ("t", Arc::new(part.time) as ArrayRef),
+        ("d", Arc::new(part.diff) as ArrayRef),
+    ])?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::default()))
+        .build();
+    let mut buf = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(buf)
+}
+
+fn decode_part(bytes: Bytes) -> Result<Part, anyhow::Error> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)?;
+    let schema = Arc::clone(builder.schema());
+    let batches = builder.build()?.collect::<Result<Vec<_>, _>>()?;
+    let batch = arrow::compute::concat_batches(&schema, &batches)?;
+    let column = |name: &str| {
+        batch
+            .column_by_name(name)
+            .map(Arc::clone)
+            .ok_or_else(|| anyhow!("missing column {}", name))
+    };
+    let int64_column = |name: &str| {
+        Array::as_any(column(name)?.as_ref())
+            .downcast_ref::<Int64Array>()
+            .cloned()
+            .ok_or_else(|| anyhow!("expected column {} to be an Int64Array", name))
+    };
+    Ok(Part {
+        key: column("k")?,
+        val: column("v")?,
+        time: int64_column("t")?,
+        diff: int64_column("d")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_dyncfg::ConfigUpdates;
+    use mz_persist_types::codec_impls::{StringSchema, VecU8Schema};
+
+    use crate::tests::{all_ok, new_test_client};
+
+    use super::*;
+
+    async fn import(
+        client: &PersistClient,
+        shard_id: ShardId,
+        dir: &Path,
+    ) -> Result<ExportManifest, anyhow::Error> {
+        import_shard::<String, String, u64, i64>(
+            client,
+            shard_id,
+            Arc::new(StringSchema),
+            Arc::new(StringSchema),
+            dir,
+            Diagnostics::for_tests(),
+        )
+        .await
+    }
+
+    async fn write_manifest(dir: &Path, manifest: &ExportManifest) {
+        let bytes = serde_json::to_vec_pretty(manifest).expect("valid manifest");
+        tokio::fs::write(dir.join(MANIFEST_FILE), bytes)
+            .await
+            .expect("writable manifest");
+    }
+
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn export_import_roundtrip(dyncfgs: ConfigUpdates) {
+        let data = [
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 2, 1),
+            (("1".to_owned(), "one".to_owned()), 3, -1),
+            (("4".to_owned(), "four".to_owned()), 4, 1),
+            (("5".to_owned(), "five".to_owned()), 5, 1),
+        ];
+        let client = new_test_client(&dyncfgs).await;
+        let source_id = ShardId::new();
+        let (mut write, _read) = client
+            .expect_open::<String, String, u64, i64>(source_id)
+            .await;
+        // Spread the updates over several batches, so the export has to
+        // consolidate across them.
+        write.expect_compare_and_append(&data[..1], 0, 2).await;
+        write.expect_compare_and_append(&data[1..3], 2, 3).await;
+        write.expect_compare_and_append(&data[3..5], 3, 5).await;
+        write.expect_compare_and_append(&data[5..], 5, 6).await;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        // An export must be as of a readable time.
+        let res = export_shard::<String, String, u64, i64>(
+            &client,
+            source_id,
+            Arc::new(StringSchema),
+            Arc::new(StringSchema),
+            6,
+            dir.path(),
+            Diagnostics::for_tests(),
+        )
+        .await;
+        assert!(res.is_err(), "unexpected result {:?}", res);
+
+        let exported = export_shard::<String, String, u64, i64>(
+            &client,
+            source_id,
+            Arc::new(StringSchema),
+            Arc::new(StringSchema),
+            4,
+            dir.path(),
+            Diagnostics::for_tests(),
+        )
+        .await
+        .expect("valid export");
+        assert_eq!(exported.shard_id, source_id);
+        assert_eq!(exported.as_of, 4);
+        assert_eq!(
+            (
+                exported.key_codec.as_str(),
+                exported.val_codec.as_str(),
+                exported.ts_codec.as_str(),
+                exported.diff_codec.as_str()
+            ),
+            ("String", "String", "u64", "i64")
+        );
+        assert!(exported.schemas.is_some());
+        assert_eq!(exported.files.iter().map(|f| f.updates).sum::<usize>(), 3);
+        assert_eq!(
+            read_manifest(dir.path()).await.expect("valid manifest"),
+            exported
+        );
+
+        let target_id = ShardId::new();
+        let imported = import(&client, target_id, dir.path())
+            .await
+            .expect("valid import");
+        assert_eq!(imported, exported);
+
+        let (mut target_write, mut target_read) = client
+            .expect_open::<String, String, u64, i64>(target_id)
+            .await;
+        assert_eq!(target_read.since(), &Antichain::from_elem(4));
+        assert_eq!(target_write.upper(), &Antichain::from_elem(5));
+        let expected = [
+            (("2".to_owned(), "two".to_owned()), 4, 1),
+            (("3".to_owned(), "three".to_owned()), 4, 1),
+            (("4".to_owned(), "four".to_owned()), 4, 1),
+        ];
+        assert_eq!(
+            target_read.expect_snapshot_and_fetch(4).await,
+            all_ok(&expected, 4)
+        );
+        let schema_id = client
+            .latest_schema::<String, String, u64, i64>(target_id, Diagnostics::for_tests())
+            .await
+            .expect("valid usage")
+            .map(|(id, _, _)| id);
+        assert_eq!(schema_id, exported.schemas.as_ref().map(|s| s.id));
+        target_write
+            .expect_compare_and_append(&data[5..], 5, 6)
+            .await;
+
+        // Exporting the imported shard results in the same updates.
+        let reexport_dir = tempfile::tempdir().expect("tempdir");
+        let reexported = export_shard::<String, String, u64, i64>(
+            &client,
+            target_id,
+            Arc::new(StringSchema),
+            Arc::new(StringSchema),
+            4,
+            reexport_dir.path(),
+            Diagnostics::for_tests(),
+        )
+        .await
+        .expect("valid export");
+        assert_eq!(reexported.shard_id, target_id);
+        assert_eq!(
+            (reexported.as_of, &reexported.schemas),
+            (exported.as_of, &exported.schemas)
+        );
+        assert_eq!(reexported.files.iter().map(|f| f.updates).sum::<usize>(), 3);
+
+        // Importing into a shard that has been written to is an error.
+        let res = import(&client, target_id, dir.path()).await;
+        assert!(res.is_err(), "unexpected result {:?}", res);
+    }
+
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn import_mismatched_export(dyncfgs: ConfigUpdates) {
+        let data = [
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+        ];
+        let client = new_test_client(&dyncfgs).await;
+        let source_id = ShardId::new();
+        let (mut write, _read) = client
+            .expect_open::<String, String, u64, i64>(source_id)
+            .await;
+        write.expect_compare_and_append(&data, 0, 3).await;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let exported = export_shard::<String, String, u64, i64>(
+            &client,
+            source_id,
+            Arc::new(StringSchema),
+            Arc::new(StringSchema),
+            2,
+            dir.path(),
+            Diagnostics::for_tests(),
+        )
+        .await
+        .expect("valid export");
+        let target_id = ShardId::new();
+
+        // Importing with codecs other than the exported ones is an error.
+        let res = import_shard::<String, Vec<u8>, u64, i64>(
+            &client,
+            target_id,
+            Arc::new(StringSchema),
+            Arc::new(VecU8Schema),
+            dir.path(),
+            Diagnostics::for_tests(),
+        )
+        .await;
+        let err = res.expect_err("mismatched codecs");
+        assert!(
+            err.to_string().contains("did not match exported"),
+            "unexpected error {}",
+            err
+        );
+
+        // So is a manifest of an unknown format version.
+        let mut manifest = exported.clone();
+        manifest.format_version = EXPORT_FORMAT_VERSION + 1;
+        write_manifest(dir.path(), &manifest).await;
+        let err = import(&client, target_id, dir.path())
+            .await
+            .expect_err("unknown format version");
+        assert!(
+            err.to_string()
+                .contains("unsupported export format version"),
+            "unexpected error {}",
+            err
+        );
+
+        // And a manifest that disagrees with the files in the export.
+        let mut manifest = exported.clone();
+        manifest.files[0].updates += 1;
+        write_manifest(dir.path(), &manifest).await;
+        let err = import(&client, target_id, dir.path())
+            .await
+            .expect_err("mismatched update count");
+        assert!(
+            err.to_string().contains("but the manifest expected"),
+            "unexpected error {}",
+            err
+        );
+
+        let mut manifest = exported.clone();
+        manifest.files[0].name = "part-missing.parquet".to_owned();
+        write_manifest(dir.path(), &manifest).await;
+        let res = import(&client, target_id, dir.path()).await;
+        assert!(res.is_err(), "unexpected result {:?}", res);
+
+        // None of the failed imports wrote to the shard, so it can still be
+        // imported into.
+        write_manifest(dir.path(), &exported).await;
+        let imported = import(&client, target_id, dir.path())
+            .await
+            .expect("valid import");
+        assert_eq!(imported, exported);
+        let (_target_write, mut target_read) = client
+            .expect_open::<String, String, u64, i64>(target_id)
+            .await;
+        assert_eq!(
+            target_read.expect_snapshot_and_fetch(2).await,
+            all_ok(&data, 2)
+        );
+    }
+}
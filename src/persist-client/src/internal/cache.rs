@@ -9,6 +9,8 @@
 
 //! In-process caches of [Blob].
 
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
@@ -17,6 +19,7 @@ use mz_dyncfg::{Config, ConfigSet};
 use mz_ore::bytes::SegmentedBytes;
 use mz_ore::cast::CastFrom;
 use mz_persist::location::{Blob, BlobMetadata, ExternalError};
+use tracing::{info, warn};
 
 use crate::cfg::PersistConfig;
 use crate::internal::metrics::Metrics;
@@ -170,6 +173,266 @@ impl Blob for BlobMemCache {
     }
 }
 
+pub(crate) const BLOB_CACHE_DISK_ENABLED: Config<bool> = Config::new(
+    "persist_blob_cache_disk_enabled",
+    false,
+    "Whether to cache blobs on local disk, if the process has a directory for it (Materialize).",
+);
+
+pub(crate) const BLOB_CACHE_DISK_LIMIT_BYTES: Config<usize> = Config::new(
+    "persist_blob_cache_disk_limit_bytes",
+    // 10GiB
+    10 * 1024 * 1024 * 1024,
+    "Capacity of the on-disk blob cache in bytes (Materialize).",
+);
+
+/// On-disk cache for [Blob], intended to sit between [BlobMemCache] and the
+/// real [Blob].
+///
+/// Each cached blob is a file in `dir`, prefixed by a checksum of its contents
+/// that is validated on every read, so a corrupted file is a cache miss
+/// instead of an error. The files outlive the process, so after a restart we
+/// don't have to re-fetch everything from the real [Blob].
+#[derive(Debug)]
+pub struct BlobDiskCache {
+    /// [`ConfigSet`] of dynamic configs.
+    cfg: Arc<ConfigSet>,
+    metrics: Arc<Metrics>,
+    dir: PathBuf,
+    /// The cached blobs, weighted by the size of their file.
+    cache: Mutex<lru::Lru<String, ()>>,
+    blob: Arc<dyn Blob>,
+}
+
+impl BlobDiskCache {
+    const CHECKSUM_LEN: usize = 4;
+    const TMP_EXTENSION: &'static str = "tmp";
+
+    /// Returns a new [BlobDiskCache] that stores blobs in `dir`, including any
+    /// blobs that were cached there by a previous process.
+    pub async fn new(
+        cfg: &PersistConfig,
+        metrics: Arc<Metrics>,
+        blob: Arc<dyn Blob>,
+        dir: PathBuf,
+    ) -> Result<Arc<dyn Blob>, ExternalError> {
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let eviction_metrics = Arc::clone(&metrics);
+        let eviction_dir = dir.clone();
+        let capacity_bytes = BLOB_CACHE_DISK_LIMIT_BYTES.get(&cfg.configs);
+        let mut cache = lru::Lru::new(capacity_bytes, move |key: String, (), _| {
+            eviction_metrics.blob_cache_disk.cache.evictions.inc();
+            // Removing a file is cheap enough to do inline.
+            let path = Self::blob_path(&eviction_dir, &key);
+            if let Err(err) = std::fs::remove_file(&path) {
+                warn!("failed to remove cached blob {}: {}", path.display(), err);
+            }
+        });
+
+        // Restore the blobs cached by a previous process, oldest first so that
+        // they're evicted first.
+        let mut existing = Vec::new();
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let key = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| hex::decode(name).ok())
+                .and_then(|key| String::from_utf8(key).ok());
+            let metadata = entry.metadata().await?;
+            match key {
+                Some(key) if metadata.is_file() => {
+                    let modified = metadata.modified().ok();
+                    existing.push((modified, key, usize::cast_from(metadata.len())));
+                }
+                // Partial writes from a previous process, or something that
+                // doesn't belong to us.
+                _ => {
+                    info!("removing unexpected file in blob cache {}", path.display());
+                    if metadata.is_dir() {
+                        tokio::fs::remove_dir_all(&path).await?;
+                    } else {
+                        tokio::fs::remove_file(&path).await?;
+                    }
+                }
+            }
+        }
+        existing.sort();
+        for (_, key, size) in existing {
+            cache.insert(key, (), size);
+        }
+        info!(
+            "restored {} blobs ({} bytes) into disk cache at {}",
+            cache.entry_count(),
+            cache.entry_weight(),
+            dir.display()
+        );
+
+        let blob = BlobDiskCache {
+            cfg: Arc::clone(&cfg.configs),
+            metrics,
+            dir,
+            cache: Mutex::new(cache),
+            blob,
+        };
+        blob.resize_and_update_size_metrics(&mut blob.cache.lock().expect("lock poisoned"));
+        Ok(Arc::new(blob))
+    }
+
+    /// Blob keys contain `/`, so we hex encode them to get a flat directory.
+    fn blob_path(dir: &Path, key: &str) -> PathBuf {
+        dir.join(hex::encode(key))
+    }
+
+    fn resize_and_update_size_metrics(&self, cache: &mut lru::Lru<String, ()>) {
+        cache.update_capacity(BLOB_CACHE_DISK_LIMIT_BYTES.get(&self.cfg));
+        let metrics = &self.metrics.blob_cache_disk.cache;
+        metrics.size_blobs.set(u64::cast_from(cache.entry_count()));
+        metrics.size_bytes.set(u64::cast_from(cache.entry_weight()));
+    }
+
+    fn remove(&self, key: &str) {
+        let mut cache = self.cache.lock().expect("lock poisoned");
+        cache.remove(key);
+        self.resize_and_update_size_metrics(&mut cache);
+    }
+
+    /// Reads and validates a cached blob, returning `None` if it's not a valid
+    /// cache entry.
+    async fn read(&self, key: &str) -> Option<Bytes> {
+        let path = Self::blob_path(&self.dir, key);
+        let buf = match tokio::fs::read(&path).await {
+            Ok(buf) => Bytes::from(buf),
+            Err(err) => {
+                if err.kind() != ErrorKind::NotFound {
+                    warn!("failed to read cached blob {}: {}", path.display(), err);
+                    self.metrics.blob_cache_disk.io_errors.inc();
+                }
+                self.remove(key);
+                return None;
+            }
+        };
+
+        let valid = buf.len() >= Self::CHECKSUM_LEN && {
+            let (checksum, data) = buf.split_at(Self::CHECKSUM_LEN);
+            checksum == crc32fast::hash(data).to_le_bytes()
+        };
+        if !valid {
+            warn!("checksum mismatch for cached blob {}", path.display());
+            self.metrics.blob_cache_disk.checksum_failures.inc();
+            self.remove(key);
+            let _ = tokio::fs::remove_file(&path).await;
+            return None;
+        }
+
+        Some(buf.slice(Self::CHECKSUM_LEN..))
+    }
+
+    /// Durably writes a blob to the cache, via a temp file that's atomically
+    /// renamed into place.
+    async fn write(&self, key: &str, value: &SegmentedBytes) -> Result<(), std::io::Error> {
+        let path = Self::blob_path(&self.dir, key);
+        let tmp_path = path.with_extension(Self::TMP_EXTENSION);
+
+        // Reserve space for the checksum, which we fill in at the end.
+        let mut buf = vec![0; Self::CHECKSUM_LEN];
+        buf.reserve(value.len());
+        let mut hasher = crc32fast::Hasher::new();
+        for segment in value.clone().into_segments() {
+            hasher.update(&segment);
+            buf.extend_from_slice(&segment);
+        }
+        buf[..Self::CHECKSUM_LEN].copy_from_slice(&hasher.finalize().to_le_bytes());
+
+        tokio::fs::write(&tmp_path, &buf).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Blob for BlobDiskCache {
+    async fn get(&self, key: &str) -> Result<Option<SegmentedBytes>, ExternalError> {
+        if !BLOB_CACHE_DISK_ENABLED.get(&self.cfg) {
+            return self.blob.get(key).await;
+        }
+
+        // Blobs are write-once modify-never, so like [BlobMemCache], we don't
+        // have to worry about invalidating cached values.
+        let cached = self.cache.lock().expect("lock poisoned").get(key).is_some();
+        if cached {
+            if let Some(value) = self.read(key).await {
+                let metrics = &self.metrics.blob_cache_disk.cache;
+                metrics.hits_blobs.inc();
+                metrics.hits_bytes.inc_by(u64::cast_from(value.len()));
+                return Ok(Some(SegmentedBytes::from(value)));
+            }
+        }
+
+        let res = self.blob.get(key).await?;
+        if let Some(value) = res.as_ref() {
+            // Like [BlobMemCache], skip blobs that would evict everything.
+            let weight = Self::CHECKSUM_LEN + value.len();
+            let capacity = self.cache.lock().expect("lock poisoned").capacity();
+            if weight <= capacity {
+                match self.write(key, value).await {
+                    Ok(()) => {
+                        let mut cache = self.cache.lock().expect("lock poisoned");
+                        cache.insert(key.to_owned(), (), weight);
+                        self.resize_and_update_size_metrics(&mut cache);
+                    }
+                    Err(err) => {
+                        // The cache is best effort, so don't fail the read.
+                        warn!("failed to write blob {} to disk cache: {}", key, err);
+                        self.metrics.blob_cache_disk.io_errors.inc();
+                    }
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    async fn list_keys_and_metadata(
+        &self,
+        key_prefix: &str,
+        f: &mut (dyn FnMut(BlobMetadata) + Send + Sync),
+    ) -> Result<(), ExternalError> {
+        self.blob.list_keys_and_metadata(key_prefix, f).await
+    }
+
+    async fn set(&self, key: &str, value: Bytes) -> Result<(), ExternalError> {
+        // Only blobs that are read get cached on disk, [BlobMemCache] already
+        // handles recently written blobs.
+        self.blob.set(key, value).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<Option<usize>, ExternalError> {
+        let res = self.blob.delete(key).await;
+        let cached = self
+            .cache
+            .lock()
+            .expect("lock poisoned")
+            .remove(key)
+            .is_some();
+        if cached {
+            let path = Self::blob_path(&self.dir, key);
+            if let Err(err) = tokio::fs::remove_file(&path).await {
+                if err.kind() != ErrorKind::NotFound {
+                    warn!("failed to remove cached blob {}: {}", path.display(), err);
+                }
+            }
+            self.resize_and_update_size_metrics(&mut self.cache.lock().expect("lock poisoned"));
+        }
+        res
+    }
+
+    async fn restore(&self, key: &str) -> Result<(), ExternalError> {
+        self.blob.restore(key).await
+    }
+}
+
 mod lru {
     use std::borrow::Borrow;
     use std::collections::BTreeMap;
@@ -411,11 +674,14 @@ mod lru {
 #[cfg(test)]
 mod tests {
     use mz_ore::assert_none;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_persist::mem::{MemBlob, MemBlobConfig};
     use proptest::arbitrary::any;
     use proptest::proptest;
     use proptest_derive::Arbitrary;
 
     use super::lru::*;
+    use super::*;
 
     #[derive(Debug, Arbitrary)]
     enum LruOp {
@@ -555,4 +821,68 @@ mod tests {
         assert_eq!(cache.entry_weight(), 2);
         assert_eq!(cache.keys(), &["j", "i"]);
     }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mkdir`
+    async fn blob_disk_cache() {
+        async fn get(blob: &Arc<dyn Blob>, key: &str) -> Option<Vec<u8>> {
+            let value = blob.get(key).await.expect("mem blob is infallible");
+            value.map(|x| x.into_contiguous())
+        }
+
+        let cfg = PersistConfig::new_for_tests();
+        cfg.set_config(&BLOB_CACHE_DISK_ENABLED, true);
+        let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+        let dir = tempfile::tempdir().unwrap();
+        let blob: Arc<dyn Blob> = Arc::new(MemBlob::open(MemBlobConfig::new(false)));
+        let open = || {
+            BlobDiskCache::new(
+                &cfg,
+                Arc::clone(&metrics),
+                Arc::clone(&blob),
+                dir.path().to_owned(),
+            )
+        };
+        let disk_metrics = &metrics.blob_cache_disk;
+        blob.set("a/b", Bytes::from("hello")).await.unwrap();
+
+        // The first get is a miss, the second a hit.
+        let cache = open().await.unwrap();
+        assert_eq!(get(&cache, "a/b").await, Some(b"hello".to_vec()));
+        assert_eq!(disk_metrics.cache.hits_blobs.get(), 0);
+        assert_eq!(get(&cache, "a/b").await, Some(b"hello".to_vec()));
+        assert_eq!(disk_metrics.cache.hits_blobs.get(), 1);
+        assert_none!(get(&cache, "missing").await);
+
+        // Cached blobs survive a restart.
+        let cache = open().await.unwrap();
+        assert_eq!(get(&cache, "a/b").await, Some(b"hello".to_vec()));
+        assert_eq!(disk_metrics.cache.hits_blobs.get(), 2);
+
+        // A corrupted file is a miss, and gets replaced.
+        let path = BlobDiskCache::blob_path(dir.path(), "a/b");
+        std::fs::write(&path, b"garbage").unwrap();
+        assert_eq!(get(&cache, "a/b").await, Some(b"hello".to_vec()));
+        assert_eq!(disk_metrics.checksum_failures.get(), 1);
+        assert_eq!(disk_metrics.cache.hits_blobs.get(), 2);
+        assert_eq!(get(&cache, "a/b").await, Some(b"hello".to_vec()));
+        assert_eq!(disk_metrics.cache.hits_blobs.get(), 3);
+
+        // Least recently used blobs, and their files, are evicted to stay
+        // within the capacity.
+        let weight = BlobDiskCache::CHECKSUM_LEN + "hello".len();
+        cfg.set_config(&BLOB_CACHE_DISK_LIMIT_BYTES, 2 * weight);
+        for key in ["c/1", "c/2", "c/3"] {
+            blob.set(key, Bytes::from("hello")).await.unwrap();
+            assert_eq!(get(&cache, key).await, Some(b"hello".to_vec()));
+        }
+        assert_eq!(disk_metrics.cache.evictions.get(), 2);
+        assert_eq!(disk_metrics.cache.size_blobs.get(), 2);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        // Deleting a blob removes it from the cache.
+        cache.delete("c/3").await.unwrap();
+        assert_eq!(disk_metrics.cache.size_blobs.get(), 1);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
 }
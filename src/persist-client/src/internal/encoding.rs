@@ -23,7 +23,7 @@ use mz_persist::indexed::encoding::{BatchColumnarFormat, BlobTraceBatchPart, Blo
 use mz_persist::location::{SeqNo, VersionedData};
 use mz_persist::metrics::ColumnarMetrics;
 use mz_persist_types::schema::SchemaId;
-use mz_persist_types::stats::{PartStats, ProtoStructStats};
+use mz_persist_types::stats::{PartStats, ProtoBloomFilters, ProtoStructStats};
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoMapEntry, ProtoType, RustType, TryFromProtoError};
 use proptest::prelude::Arbitrary;
@@ -1447,6 +1447,7 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatchPart> for HollowRunRef<T>
             key_lower: Bytes::copy_from_slice(&self.key_lower),
             diffs_sum: None,
             key_stats: None,
+            key_bloom_filters: None,
             ts_rewrite: None,
             format: None,
             schema_id: None,
@@ -1482,7 +1483,8 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatchPart> for BatchPart<T> {
                 encoded_size_bytes: x.encoded_size_bytes.into_proto(),
                 key_lower: Bytes::copy_from_slice(&x.key_lower),
                 structured_key_lower: x.structured_key_lower.as_ref().map(|lazy| lazy.buf.clone()),
-                key_stats: x.stats.into_proto(),
+                key_stats: x.stats.as_ref().map(|x| x.into_proto_fields().0),
+                key_bloom_filters: x.stats.as_ref().and_then(|x| x.into_proto_fields().1),
                 ts_rewrite: x.ts_rewrite.as_ref().map(|x| x.into_proto()),
                 diffs_sum: x.diffs_sum.as_ref().map(|x| i64::from_le_bytes(*x)),
                 format: x.format.map(|f| f.into_proto()),
@@ -1500,6 +1502,7 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatchPart> for BatchPart<T> {
                 key_lower: Bytes::new(),
                 structured_key_lower: None,
                 key_stats: None,
+                key_bloom_filters: None,
                 ts_rewrite: ts_rewrite.as_ref().map(|x| x.into_proto()),
                 diffs_sum: None,
                 format: None,
@@ -1523,7 +1526,10 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatchPart> for BatchPart<T> {
                     encoded_size_bytes: proto.encoded_size_bytes.into_rust()?,
                     key_lower: proto.key_lower.into(),
                     structured_key_lower: proto.structured_key_lower.into_rust()?,
-                    stats: proto.key_stats.into_rust()?,
+                    stats: proto
+                        .key_stats
+                        .map(|key| LazyPartStats::from_proto_fields(key, proto.key_bloom_filters))
+                        .transpose()?,
                     ts_rewrite,
                     diffs_sum: proto.diffs_sum.map(i64::to_le_bytes),
                     format: proto.format.map(|f| f.into_rust()).transpose()?,
@@ -1535,6 +1541,7 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatchPart> for BatchPart<T> {
                 assert_eq!(proto.encoded_size_bytes, 0);
                 assert_eq!(proto.key_lower.len(), 0);
                 assert_none!(proto.key_stats);
+                assert_none!(proto.key_bloom_filters);
                 assert_none!(proto.diffs_sum);
                 let updates = LazyInlineBatchPart(x.into_rust()?);
                 Ok(BatchPart::Inline {
@@ -1581,15 +1588,22 @@ impl RustType<proto_hollow_batch_part::Format> for BatchColumnarFormat {
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct LazyPartStats {
     key: LazyProto<ProtoStructStats>,
+    /// None if the part has no Bloom filters. These are stored separately from
+    /// the key stats, so that they're not subject to the stats budget.
+    blooms: Option<LazyProto<ProtoBloomFilters>>,
 }
 
 impl LazyPartStats {
+    /// Encodes the given stats, applying `map_proto` (e.g. trimming) to the
+    /// key stats.
     pub(crate) fn encode(x: &PartStats, map_proto: impl FnOnce(&mut ProtoStructStats)) -> Self {
-        let PartStats { key } = x;
+        let PartStats { key, blooms } = x;
         let mut proto_stats = ProtoStructStats::from_rust(key);
         map_proto(&mut proto_stats);
+        let blooms = (!blooms.is_empty()).then(|| LazyProto::from(&blooms.into_proto()));
         LazyPartStats {
             key: LazyProto::from(&proto_stats),
+            blooms,
         }
     }
     /// Decodes and returns PartStats from the encoded representation.
@@ -1598,21 +1612,28 @@ impl LazyPartStats {
     /// called.
     pub fn decode(&self) -> PartStats {
         let key = self.key.decode().expect("valid proto");
+        let blooms = match &self.blooms {
+            Some(blooms) => {
+                let blooms = blooms.decode().expect("valid proto");
+                blooms.into_rust().expect("valid bloom filters")
+            }
+            None => BTreeMap::new(),
+        };
         PartStats {
             key: key.into_rust().expect("valid stats"),
+            blooms,
         }
     }
-}
 
-impl RustType<Bytes> for LazyPartStats {
-    fn into_proto(&self) -> Bytes {
-        let LazyPartStats { key } = self;
-        key.into_proto()
+    fn into_proto_fields(&self) -> (Bytes, Option<Bytes>) {
+        let LazyPartStats { key, blooms } = self;
+        (key.into_proto(), blooms.as_ref().map(|x| x.into_proto()))
     }
 
-    fn from_proto(proto: Bytes) -> Result<Self, TryFromProtoError> {
+    fn from_proto_fields(key: Bytes, blooms: Option<Bytes>) -> Result<Self, TryFromProtoError> {
         Ok(LazyPartStats {
-            key: proto.into_rust()?,
+            key: key.into_rust()?,
+            blooms: blooms.into_rust()?,
         })
     }
 }
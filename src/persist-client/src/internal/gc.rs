@@ -8,13 +8,14 @@
 // by the Apache License, Version 2.0.
 
 use std::borrow::Borrow;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::mem;
 use std::sync::Arc;
 use std::time::Instant;
 
+use bytes::Bytes;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use futures_util::StreamExt;
@@ -32,16 +33,20 @@ use crate::cfg::GC_BLOB_DELETE_CONCURRENCY_LIMIT;
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::HashSet;
 use mz_ore::soft_assert_or_log;
-use mz_persist::location::{Blob, SeqNo};
+use mz_persist::location::{Blob, ExternalError, SeqNo};
 use mz_persist_types::{Codec, Codec64};
+use mz_proto::{ProtoType, RustType};
 
 use crate::ShardId;
 use crate::internal::machine::{Machine, retry_external};
 use crate::internal::maintenance::RoutineMaintenance;
 use crate::internal::metrics::{GcStepTimings, RetryMetrics};
-use crate::internal::paths::{BlobKey, PartialBlobKey, PartialRollupKey};
-use crate::internal::state::HollowBlobRef;
-use crate::internal::state_versions::{InspectDiff, StateVersionsIter};
+use crate::internal::paths::{
+    BlobKey, BlobKeyPrefix, PartialBatchKey, PartialBlobKey, PartialRollupKey,
+};
+use crate::internal::state::{BatchPart, HollowBatch, HollowBlobRef, ProtoForkLease, RunPart};
+use crate::internal::state_versions::{InspectDiff, StateVersions, StateVersionsIter};
+use crate::metrics::Metrics;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GcReq {
@@ -406,6 +411,9 @@ where
         let shard_id = states.state().shard_id;
         let mut batch_parts_to_delete = PartDeletes::default();
         let mut rollups_to_delete: BTreeSet<PartialRollupKey> = BTreeSet::new();
+        // Fetched lazily, the first time there are batch parts to delete.
+        let mut fork_leases: Option<(ForkLeases<T>, BTreeSet<PartialBatchKey>)> = None;
+        let mut forked_from = BTreeSet::new();
 
         for truncate_lt in gc_rollups.truncate_seqnos() {
             assert!(batch_parts_to_delete.is_empty());
@@ -432,6 +440,7 @@ where
                 timer,
                 &mut batch_parts_to_delete,
                 &mut rollups_to_delete,
+                &mut forked_from,
             );
 
             // After finding removable blobs, our state should be exactly `truncate_lt`,
@@ -481,6 +490,24 @@ where
                 }
             });
 
+            // Keep any of the parts that forks of this shard still reference.
+            if !batch_parts_to_delete.is_empty() {
+                if fork_leases.is_none() {
+                    let leases =
+                        ForkLeases::fetch(&machine.applier.state_versions, &shard_id).await;
+                    let leased = leases.leased_keys();
+                    fork_leases = Some((leases, leased));
+                }
+                let (_, leased) = fork_leases.as_ref().expect("fetched above");
+                let retained = batch_parts_to_delete.retain_unleased(leased);
+                machine
+                    .applier
+                    .metrics
+                    .gc
+                    .fork_leased_parts
+                    .inc_by(u64::cast_from(retained));
+            }
+
             gc_results.truncated_consensus_to.push(truncate_lt);
             gc_results.batch_parts_deleted_from_blob += batch_parts_to_delete.len();
             gc_results.rollups_deleted_from_blob += rollups_to_delete.len();
@@ -494,10 +521,26 @@ where
             )
             .await;
         }
+
+        let state_versions = &machine.applier.state_versions;
+        if let Some((leases, _)) = fork_leases {
+            leases.release(state_versions, &shard_id).await;
+        }
+        // Once a fork has been finalized, it no longer needs the blobs it
+        // leased from the shards it was forked from.
+        if machine.is_finalized() {
+            for owner in forked_from {
+                ForkLeases::<T>::fetch(state_versions, &owner)
+                    .await
+                    .release(state_versions, &owner)
+                    .await;
+            }
+        }
     }
 
     /// Iterates through `states`, accumulating all deleted blobs (both batch parts
-    /// and rollups) until reaching the seqno `truncate_lt`.
+    /// and rollups) until reaching the seqno `truncate_lt`. The shards that any
+    /// deleted parts were forked from are accumulated into `forked_from`.
     ///
     /// * The initial seqno of `states` MUST be less than `truncate_lt`.
     /// * The seqno of `states` after this fn will be exactly `truncate_lt`.
@@ -508,6 +551,7 @@ where
         timer: &mut F,
         batch_parts_to_delete: &mut PartDeletes<T>,
         rollups_to_delete: &mut BTreeSet<PartialRollupKey>,
+        forked_from: &mut BTreeSet<ShardId>,
     ) where
         F: FnMut(&Counter),
     {
@@ -522,6 +566,7 @@ where
                             // see repeat blob insertions within a single GC run, otherwise we
                             // have a logic error or our diffs are incorrect (!)
                             assert!(batch_parts_to_delete.add(part));
+                            forked_from.extend(part_key(part).and_then(|key| key.owner()));
                         }
                     }
                     HollowBlobRef::Rollup(rollup) => {
@@ -616,6 +661,194 @@ where
     }
 }
 
+/// The leases that forks of a shard hold on its batch parts.
+///
+/// A fork (see [crate::PersistClient::fork_shard]) references the batch parts
+/// of its source shard instead of copying them. Before the fork is created, a
+/// lease listing the batches it references is written alongside the source's
+/// blobs, and GC of the source keeps every part in it. Once the fork has been
+/// finalized, the next GC of the source releases the lease and deletes any of
+/// its parts that the source itself no longer references.
+#[derive(Debug)]
+pub(crate) struct ForkLeases<T> {
+    /// Leases held by forks that might still read the leased parts.
+    active: BTreeMap<ShardId, Vec<HollowBatch<T>>>,
+    /// Leases held by forks that have since been finalized.
+    released: BTreeMap<ShardId, Vec<HollowBatch<T>>>,
+}
+
+impl<T> ForkLeases<T>
+where
+    T: Timestamp + Lattice + Codec64 + Sync,
+{
+    /// Records that `fork` references `batches`, whose keys must be relative
+    /// to `shard_id`.
+    pub(crate) async fn write(
+        blob: &dyn Blob,
+        metrics: &Metrics,
+        shard_id: &ShardId,
+        fork: &ShardId,
+        batches: &[HollowBatch<T>],
+    ) {
+        let proto = ProtoForkLease {
+            batches: batches.iter().map(|b| b.into_proto()).collect(),
+        };
+        let bytes = Bytes::from(prost::Message::encode_to_vec(&proto));
+        let key = BlobKey::fork_lease(shard_id, fork);
+        retry_external(&metrics.retries.external.fork_lease_set, || {
+            blob.set(&key, bytes.clone())
+        })
+        .await
+    }
+
+    /// Fetches all the leases that forks hold on `shard_id`.
+    pub(crate) async fn fetch(state_versions: &StateVersions, shard_id: &ShardId) -> Self {
+        let blob: &dyn Blob = &*state_versions.blob;
+        let metrics = &state_versions.metrics;
+        let prefix = BlobKeyPrefix::ForkLeases(shard_id).to_string();
+        let prefix = prefix.as_str();
+        let forks = retry_external(
+            &metrics.retries.external.fork_lease_scan,
+            move || async move {
+                let mut forks = Vec::new();
+                blob.list_keys_and_metadata(prefix, &mut |metadata| {
+                    if let Ok((_, PartialBlobKey::ForkLease(fork))) =
+                        BlobKey::parse_ids(metadata.key)
+                    {
+                        forks.push(fork);
+                    } else {
+                        warn!("unexpected fork lease key: {}", metadata.key);
+                    }
+                })
+                .await?;
+                Ok::<_, ExternalError>(forks)
+            },
+        )
+        .await;
+
+        let mut leases = ForkLeases {
+            active: BTreeMap::new(),
+            released: BTreeMap::new(),
+        };
+        for fork in forks {
+            let key = BlobKey::fork_lease(shard_id, &fork);
+            let Some(mut bytes) =
+                retry_external(&metrics.retries.external.fork_lease_get, || blob.get(&key)).await
+            else {
+                // Released by a concurrent GC.
+                continue;
+            };
+            let proto: ProtoForkLease =
+                prost::Message::decode(&mut bytes).expect("illegal state: invalid proto bytes");
+            let batches = proto
+                .batches
+                .into_iter()
+                .map(|b| {
+                    b.into_rust()
+                        .expect("illegal state: invalid encoded fork lease proto")
+                })
+                .collect();
+            if Self::is_finalized(state_versions, &fork).await {
+                leases.released.insert(fork, batches);
+            } else {
+                leases.active.insert(fork, batches);
+            }
+        }
+        leases
+    }
+
+    async fn is_finalized(state_versions: &StateVersions, fork: &ShardId) -> bool {
+        let diffs = state_versions.fetch_recent_live_diffs::<T>(fork).await;
+        // The lease is written before the fork's initial state, so an
+        // uninitialized fork might still be in the middle of being created.
+        if diffs.0.is_empty() {
+            return false;
+        }
+        let state = state_versions.fetch_current_state::<T>(fork, diffs.0).await;
+        match state.check_ts_codec(fork) {
+            Ok(state) => state.collections.is_tombstone(),
+            // Conservatively keep the lease of a fork we can't read.
+            Err(_) => false,
+        }
+    }
+
+    /// The keys of all the parts and runs held by active leases.
+    pub(crate) fn leased_keys(&self) -> BTreeSet<PartialBatchKey> {
+        self.active
+            .values()
+            .flatten()
+            .flat_map(|batch| batch.parts.iter())
+            .filter_map(part_key)
+            .collect()
+    }
+
+    /// Deletes the released leases on `shard_id`, along with any of the parts
+    /// they held that are referenced by neither a live state of `shard_id` nor
+    /// an active lease.
+    pub(crate) async fn release(self, state_versions: &StateVersions, shard_id: &ShardId) {
+        if self.released.is_empty() {
+            return;
+        }
+        let shard_id = *shard_id;
+        let metrics = &state_versions.metrics;
+
+        let mut referenced = self.leased_keys();
+        let states = state_versions.fetch_all_live_states::<T>(shard_id).await;
+        let Some(Ok(mut states)) = states.map(|states| states.check_ts_codec()) else {
+            warn!("unable to fetch live states of {shard_id} to release fork leases");
+            return;
+        };
+        let mut add_referenced = |diff: InspectDiff<'_, T>| {
+            diff.referenced_blobs().for_each(|blob| {
+                if let HollowBlobRef::Batch(batch) = blob {
+                    referenced.extend(batch.parts.iter().filter_map(part_key));
+                }
+            })
+        };
+        while states.next(&mut add_referenced).is_some() {}
+
+        let mut part_deletes = PartDeletes::default();
+        for part in self
+            .released
+            .values()
+            .flatten()
+            .flat_map(|b| b.parts.iter())
+        {
+            if part_key(part).is_some_and(|key| !referenced.contains(&key)) {
+                part_deletes.add(part);
+            }
+        }
+        let concurrency_limit = GC_BLOB_DELETE_CONCURRENCY_LIMIT.get(&state_versions.cfg);
+        part_deletes
+            .delete(
+                state_versions.blob.borrow(),
+                shard_id,
+                concurrency_limit,
+                metrics,
+                &metrics.retries.external.batch_delete,
+            )
+            .await;
+
+        for fork in self.released.keys() {
+            let key = BlobKey::fork_lease(&shard_id, fork);
+            let _deleted_bytes =
+                retry_external(&metrics.retries.external.fork_lease_delete, || {
+                    state_versions.blob.delete(&key)
+                })
+                .await;
+        }
+    }
+}
+
+/// The key of a part or run that's stored in blob.
+fn part_key<T>(part: &RunPart<T>) -> Option<PartialBatchKey> {
+    match part {
+        RunPart::Single(BatchPart::Hollow(x)) => Some(x.key.clone()),
+        RunPart::Many(r) => Some(r.key.clone()),
+        RunPart::Single(BatchPart::Inline { .. }) => None,
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct GcResults {
     pub(crate) batch_parts_deleted_from_blob: usize,
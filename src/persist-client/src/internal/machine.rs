@@ -9,6 +9,7 @@
 
 //! Implementation of the persist state machine.
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::ops::ControlFlow::{self, Continue};
 use std::sync::Arc;
@@ -47,9 +48,9 @@ use crate::internal::maintenance::{RoutineMaintenance, WriterMaintenance};
 use crate::internal::metrics::{CmdMetrics, Metrics, MetricsRetryStream, RetryMetrics};
 use crate::internal::paths::PartialRollupKey;
 use crate::internal::state::{
-    CompareAndAppendBreak, CriticalReaderState, HandleDebugState, HollowBatch, HollowRollup,
-    IdempotencyToken, LeasedReaderState, NoOpStateTransition, Since, SnapshotErr, StateCollections,
-    Upper,
+    CompareAndAppendBreak, CriticalReaderState, EncodedSchemas, HandleDebugState, HollowBatch,
+    HollowRollup, IdempotencyToken, LeasedReaderState, NoOpStateTransition, Since, SnapshotErr,
+    StateCollections, Upper,
 };
 use crate::internal::state_versions::StateVersions;
 use crate::internal::trace::{ApplyMergeResult, FueledMergeRes};
@@ -785,6 +786,23 @@ where
         (state, maintenance)
     }
 
+    /// Initializes an empty shard with the given schemas and batches. See
+    /// [crate::PersistClient::fork_shard] and [crate::export::import_shard].
+    pub async fn fork_from(
+        &self,
+        schemas: &BTreeMap<SchemaId, EncodedSchemas>,
+        batches: &[HollowBatch<T>],
+        since: &Antichain<T>,
+    ) -> (Result<(), Upper<T>>, RoutineMaintenance) {
+        let metrics = Arc::clone(&self.applier.metrics);
+        let (_seqno, res, maintenance) = self
+            .apply_unbatched_idempotent_cmd(&metrics.cmds.fork, |_seqno, _cfg, state| {
+                state.fork_from(schemas, batches, since)
+            })
+            .await;
+        (res, maintenance)
+    }
+
     async fn tombstone_step(&self) -> Result<(bool, RoutineMaintenance), InvalidUsage<T>> {
         let metrics = Arc::clone(&self.applier.metrics);
         let mut retry = self
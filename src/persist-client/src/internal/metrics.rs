@@ -94,6 +94,8 @@ pub struct Metrics {
     pub consolidation: ConsolidationMetrics,
     /// Metrics for blob caching.
     pub blob_cache_mem: BlobMemCache,
+    /// Metrics for the on-disk blob cache.
+    pub blob_cache_disk: BlobDiskCache,
     /// Metrics for tokio tasks.
     pub tasks: TasksMetrics,
     /// Metrics for columnar data encoding and decoding.
@@ -165,7 +167,8 @@ impl Metrics {
             pubsub_client: PubSubClientMetrics::new(registry),
             pushdown: PushdownMetrics::new(registry),
             consolidation: ConsolidationMetrics::new(registry),
-            blob_cache_mem: BlobMemCache::new(registry),
+            blob_cache_mem: BlobMemCache::new(registry, "mem"),
+            blob_cache_disk: BlobDiskCache::new(registry),
             tasks: TasksMetrics::new(registry),
             columnar,
             schema: SchemaMetrics::new(registry),
@@ -427,6 +430,7 @@ impl MetricsVecs {
             merge_res: self.cmd_metrics("merge_res"),
             become_tombstone: self.cmd_metrics("become_tombstone"),
             compare_and_evolve_schema: self.cmd_metrics("compare_and_evolve_schema"),
+            fork: self.cmd_metrics("fork"),
             spine_exert: self.cmd_metrics("spine_exert"),
             fetch_upper_count: registry.register(metric!(
                 name: "mz_persist_cmd_fetch_upper_count",
@@ -466,6 +470,10 @@ impl MetricsVecs {
                 rollup_set: self.retry_metrics("rollup::set"),
                 hollow_run_get: self.retry_metrics("hollow_run::get"),
                 hollow_run_set: self.retry_metrics("hollow_run::set"),
+                fork_lease_delete: self.retry_metrics("fork_lease::delete"),
+                fork_lease_get: self.retry_metrics("fork_lease::get"),
+                fork_lease_scan: self.retry_metrics("fork_lease::scan"),
+                fork_lease_set: self.retry_metrics("fork_lease::set"),
                 storage_usage_shard_size: self.retry_metrics("storage_usage::shard_size"),
             },
             compare_and_append_idempotent: self.retry_metrics("compare_and_append_idempotent"),
@@ -636,6 +644,7 @@ pub struct CmdsMetrics {
     pub(crate) merge_res: CmdMetrics,
     pub(crate) become_tombstone: CmdMetrics,
     pub(crate) compare_and_evolve_schema: CmdMetrics,
+    pub(crate) fork: CmdMetrics,
     pub(crate) spine_exert: CmdMetrics,
     pub(crate) fetch_upper_count: IntCounter,
 }
@@ -676,6 +685,10 @@ pub struct RetryExternal {
     pub(crate) rollup_set: RetryMetrics,
     pub(crate) hollow_run_get: RetryMetrics,
     pub(crate) hollow_run_set: RetryMetrics,
+    pub(crate) fork_lease_delete: RetryMetrics,
+    pub(crate) fork_lease_get: RetryMetrics,
+    pub(crate) fork_lease_scan: RetryMetrics,
+    pub(crate) fork_lease_set: RetryMetrics,
     pub(crate) storage_usage_shard_size: RetryMetrics,
 }
 
@@ -975,6 +988,7 @@ pub struct GcMetrics {
     pub(crate) finished: IntCounter,
     pub(crate) merged: IntCounter,
     pub(crate) seconds: Counter,
+    pub(crate) fork_leased_parts: IntCounter,
     pub(crate) steps: GcStepTimings,
 }
 
@@ -1034,6 +1048,10 @@ impl GcMetrics {
                 name: "mz_persist_gc_seconds",
                 help: "time spent in garbage collections",
             )),
+            fork_leased_parts: registry.register(metric!(
+                name: "mz_persist_gc_fork_leased_parts",
+                help: "count of batch parts not deleted because a fork references them",
+            )),
             steps: GcStepTimings::new(step_timings),
         }
     }
@@ -2481,32 +2499,55 @@ pub struct BlobMemCache {
 }
 
 impl BlobMemCache {
-    fn new(registry: &MetricsRegistry) -> Self {
+    fn new(registry: &MetricsRegistry, cache: &str) -> Self {
         BlobMemCache {
             size_blobs: registry.register(metric!(
                 name: "mz_persist_blob_cache_size_blobs",
                 help: "count of blobs in the cache",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
             size_bytes: registry.register(metric!(
                 name: "mz_persist_blob_cache_size_bytes",
                 help: "total size of blobs in the cache",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
             hits_blobs: registry.register(metric!(
                 name: "mz_persist_blob_cache_hits_blobs",
                 help: "count of blobs served via cache instead of s3",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
             hits_bytes: registry.register(metric!(
                 name: "mz_persist_blob_cache_hits_bytes",
                 help: "total size of blobs served via cache instead of s3",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
             evictions: registry.register(metric!(
                 name: "mz_persist_blob_cache_evictions",
                 help: "count of capacity-based cache evictions",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BlobDiskCache {
+    pub(crate) cache: BlobMemCache,
+    pub(crate) checksum_failures: IntCounter,
+    pub(crate) io_errors: IntCounter,
+}
+
+impl BlobDiskCache {
+    fn new(registry: &MetricsRegistry) -> Self {
+        BlobDiskCache {
+            cache: BlobMemCache::new(registry, "disk"),
+            checksum_failures: registry.register(metric!(
+                name: "mz_persist_blob_cache_disk_checksum_failures",
+                help: "count of cached blobs discarded because of a checksum mismatch",
+            )),
+            io_errors: registry.register(metric!(
+                name: "mz_persist_blob_cache_disk_io_errors",
+                help: "count of errors reading or writing cached blobs",
             )),
         }
     }
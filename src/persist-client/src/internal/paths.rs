@@ -134,12 +134,57 @@ impl PartialBatchKey {
         PartialBatchKey(format!("{}/{}", version, part_id))
     }
 
+    /// Returns a key that refers to the same blob from a shard forked off of
+    /// `shard_id`.
+    ///
+    /// Forked shards reference the blobs of their source instead of copying
+    /// them, so these keys are qualified with the [ShardId] that owns the blob.
+    /// Keys that are already qualified are returned unchanged, which keeps
+    /// forks of forks pointing at the original blob.
+    pub fn forked_from(&self, shard_id: &ShardId) -> Self {
+        match self.owner() {
+            Some(_) => self.clone(),
+            None => PartialBatchKey(format!("{}/{}", shard_id, self)),
+        }
+    }
+
+    /// Returns a key that refers to the same blob from the shard `shard_id`,
+    /// undoing [Self::forked_from] if `shard_id` owns the blob.
+    pub fn relative_to(&self, shard_id: &ShardId) -> Self {
+        match self.split_owner() {
+            Some((owner, key)) if &owner == shard_id => PartialBatchKey(key.to_owned()),
+            _ => self.clone(),
+        }
+    }
+
+    /// The shard that owns the referenced blob, if it was written by a
+    /// different shard than the one whose state contains this key.
+    pub fn owner(&self) -> Option<ShardId> {
+        self.split_owner().map(|(owner, _key)| owner)
+    }
+
+    fn split_owner(&self) -> Option<(ShardId, &str)> {
+        let (shard, key) = self.0.split_once('/')?;
+        if !shard.starts_with('s') {
+            return None;
+        }
+        let shard = ShardId::from_str(shard).ok()?;
+        Some((shard, key))
+    }
+
     pub fn split(&self) -> Option<(WriterKey, PartId)> {
-        split_batch_key(&self.0).ok()
+        let key = match self.split_owner() {
+            Some((_owner, key)) => key,
+            None => &self.0,
+        };
+        split_batch_key(key).ok()
     }
 
     pub fn complete(&self, shard_id: &ShardId) -> BlobKey {
-        BlobKey(format!("{}/{}", shard_id, self))
+        match self.owner() {
+            Some(_) => BlobKey(self.0.clone()),
+            None => BlobKey(format!("{}/{}", shard_id, self)),
+        }
     }
 }
 
@@ -222,8 +267,14 @@ pub enum PartialBlobKey {
     Batch(WriterKey, PartId),
     /// A parsed [PartialRollupKey].
     Rollup(SeqNo, RollupId),
+    /// The lease held by the given fork on the blobs of this shard. See
+    /// [BlobKey::fork_lease].
+    ForkLease(ShardId),
 }
 
+/// Prefix of the (partial) blob keys of fork leases.
+const FORK_LEASE_PREFIX: &str = "fork/";
+
 /// Fully encoded path used in [mz_persist::location::Blob] storage. Composed of
 /// a [ShardId], [WriterId] and [PartId].
 ///
@@ -261,12 +312,20 @@ impl BlobKey {
         let blob_key = if blob.starts_with('w') | blob.starts_with('n') {
             let (writer, part) = split_batch_key(blob)?;
             PartialBlobKey::Batch(writer, part)
+        } else if let Some(fork) = blob.strip_prefix(FORK_LEASE_PREFIX) {
+            PartialBlobKey::ForkLease(ShardId::from_str(fork)?)
         } else {
             let (seqno, rollup) = blob.split_once('/').ok_or_else(err)?;
             PartialBlobKey::Rollup(SeqNo::from_str(seqno)?, RollupId::from_str(rollup)?)
         };
         Ok((shard_id, blob_key))
     }
+
+    /// The key of the lease that the shard `fork` holds on the blobs of the
+    /// shard `source` it was forked from.
+    pub fn fork_lease(source: &ShardId, fork: &ShardId) -> Self {
+        BlobKey(format!("{}/{}{}", source, FORK_LEASE_PREFIX, fork))
+    }
 }
 
 /// Represents the prefix of a blob path. Used for selecting subsets of blobs
@@ -276,6 +335,8 @@ pub enum BlobKeyPrefix<'a> {
     All,
     /// Scoped to the batch and state rollup blobs of an individual shard
     Shard(&'a ShardId),
+    /// Scoped to the leases held by forks on the blobs of an individual shard
+    ForkLeases(&'a ShardId),
     /// Scoped to the batch blobs of an individual writer
     #[cfg(test)]
     Writer(&'a ShardId, &'a WriterKey),
@@ -289,6 +350,7 @@ impl std::fmt::Display for BlobKeyPrefix<'_> {
         let s = match self {
             BlobKeyPrefix::All => "".into(),
             BlobKeyPrefix::Shard(shard) => format!("{}", shard),
+            BlobKeyPrefix::ForkLeases(shard) => format!("{}/{}", shard, FORK_LEASE_PREFIX),
             #[cfg(test)]
             BlobKeyPrefix::Writer(shard, writer) => format!("{}/{}", shard, writer),
             #[cfg(test)]
@@ -333,6 +395,21 @@ mod tests {
             partial_key.complete(&shard_id),
             BlobKey(format!("{}/{}/{}", shard_id, writer_id, part_id))
         );
+
+        // A key forked from another shard completes to the blob of that shard,
+        // no matter which shard it's completed with.
+        let (fork_id, fork_of_fork_id) = (ShardId::new(), ShardId::new());
+        let forked_key = partial_key.forked_from(&shard_id);
+        assert_eq!(partial_key.owner(), None);
+        assert_eq!(forked_key.owner(), Some(shard_id));
+        assert_eq!(forked_key.split(), partial_key.split());
+        assert_eq!(
+            forked_key.complete(&fork_id),
+            partial_key.complete(&shard_id)
+        );
+        assert_eq!(forked_key.forked_from(&fork_of_fork_id), forked_key);
+        assert_eq!(forked_key.relative_to(&fork_id), forked_key);
+        assert_eq!(forked_key.relative_to(&shard_id), partial_key);
     }
 
     #[mz_ore::test]
@@ -362,6 +439,16 @@ mod tests {
             ))
         );
 
+        let fork_id = ShardId::new();
+        assert_eq!(
+            BlobKey::parse_ids(&BlobKey::fork_lease(&shard_id, &fork_id)),
+            Ok((shard_id, PartialBlobKey::ForkLease(fork_id)))
+        );
+        assert!(
+            BlobKey::fork_lease(&shard_id, &fork_id)
+                .starts_with(&BlobKeyPrefix::ForkLeases(&shard_id).to_string())
+        );
+
         // fails on invalid blob key formats
         assert!(matches!(
             BlobKey::parse_ids(&format!("{}/{}", WriterId::new(), PartId::new())),
@@ -472,9 +472,16 @@ impl<T: Timestamp + Codec64> HollowRunRef<T> {
         .await?;
         let proto_runs: ProtoHollowRun =
             prost::Message::decode(&mut bytes).expect("illegal state: invalid proto bytes");
-        let runs = proto_runs
+        let mut runs: HollowRun<T> = proto_runs
             .into_rust()
             .expect("illegal state: invalid encoded runs proto");
+        // The keys in a run are relative to the shard that wrote it, which
+        // isn't us if this run was forked from another shard.
+        if let Some(owner) = self.key.owner() {
+            for part in runs.parts.iter_mut() {
+                *part = part.map_key(|key| key.forked_from(&owner));
+            }
+        }
         Some(runs)
     }
 }
@@ -507,6 +514,27 @@ impl<T: Ord> Ord for RunPart<T> {
 }
 
 impl<T> RunPart<T> {
+    /// Returns a copy of this part with its blob key replaced by `f`, e.g. to
+    /// refer to it from a fork. See [PartialBatchKey::forked_from].
+    pub(crate) fn map_key(&self, f: impl Fn(&PartialBatchKey) -> PartialBatchKey) -> Self
+    where
+        T: Clone,
+    {
+        match self {
+            RunPart::Single(BatchPart::Hollow(x)) => {
+                RunPart::Single(BatchPart::Hollow(HollowBatchPart {
+                    key: f(&x.key),
+                    ..x.clone()
+                }))
+            }
+            RunPart::Single(BatchPart::Inline { .. }) => self.clone(),
+            RunPart::Many(r) => RunPart::Many(HollowRunRef {
+                key: f(&r.key),
+                ..r.clone()
+            }),
+        }
+    }
+
     #[cfg(test)]
     pub fn expect_hollow_part(&self) -> &HollowBatchPart<T> {
         match self {
@@ -969,6 +997,21 @@ impl<T> HollowBatch<T> {
         run_metas.zip(run_parts)
     }
 
+    /// Returns a copy of this batch with the blob keys of its parts replaced by
+    /// `f`. See [RunPart::map_key].
+    pub(crate) fn map_keys(&self, f: impl Fn(&PartialBatchKey) -> PartialBatchKey) -> Self
+    where
+        T: Clone,
+    {
+        HollowBatch {
+            desc: self.desc.clone(),
+            len: self.len,
+            parts: self.parts.iter().map(|x| x.map_key(&f)).collect(),
+            run_splits: self.run_splits.clone(),
+            run_meta: self.run_meta.clone(),
+        }
+    }
+
     pub(crate) fn inline_bytes(&self) -> usize {
         self.parts.iter().map(|x| x.inline_bytes()).sum()
     }
@@ -1524,6 +1567,44 @@ where
         Continue(CaESchema::Ok(id))
     }
 
+    /// Initializes this shard with the given schemas and batches, and a since
+    /// of `since`. This is used to fork a shard, referencing the batches of the
+    /// source (with keys already qualified by [PartialBatchKey::forked_from])
+    /// instead of copying them, and to import an exported one.
+    ///
+    /// The shard must not have been written to or had any schemas registered,
+    /// except by a previous attempt at this same call, in which case this is a
+    /// no-op.
+    pub fn fork_from(
+        &mut self,
+        schemas: &BTreeMap<SchemaId, EncodedSchemas>,
+        batches: &[HollowBatch<T>],
+        since: &Antichain<T>,
+    ) -> ControlFlow<NoOpStateTransition<Result<(), Upper<T>>>, Result<(), Upper<T>>> {
+        let fork_upper = match batches.last() {
+            Some(batch) => batch.desc.upper().clone(),
+            None => Antichain::from_elem(T::minimum()),
+        };
+        if self.trace.upper() == &fork_upper && &self.schemas == schemas {
+            return Break(NoOpStateTransition(Ok(())));
+        }
+        if self.is_tombstone()
+            || self.trace.upper() != &Antichain::from_elem(T::minimum())
+            || !self.schemas.is_empty()
+        {
+            return Break(NoOpStateTransition(Err(Upper(self.trace.upper().clone()))));
+        }
+
+        self.schemas.clone_from(schemas);
+        for batch in batches {
+            // Any merges this kicks off are picked up the next time a writer
+            // appends to the fork.
+            let _merge_reqs = self.trace.push_batch(batch.clone());
+        }
+        self.trace.downgrade_since(since);
+        Continue(Ok(()))
+    }
+
     pub fn compare_and_append(
         &mut self,
         batch: &HollowBatch<T>,
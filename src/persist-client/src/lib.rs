@@ -15,6 +15,7 @@
 // https://github.com/rust-lang/rust/issues/87417 pans out.
 #![allow(ungated_async_fn_track_caller)]
 
+use std::collections::BTreeSet;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -27,7 +28,7 @@ use mz_ore::{instrument, soft_assert_or_log};
 use mz_persist::location::{Blob, Consensus, ExternalError};
 use mz_persist_types::schema::SchemaId;
 use mz_persist_types::{Codec, Codec64, Opaque};
-use timely::progress::Timestamp;
+use timely::progress::{Antichain, Timestamp};
 
 use crate::async_runtime::IsolatedRuntime;
 use crate::cache::{PersistClientCache, StateCache};
@@ -37,8 +38,9 @@ use crate::error::InvalidUsage;
 use crate::fetch::{BatchFetcher, BatchFetcherConfig};
 use crate::internal::compact::Compactor;
 use crate::internal::encoding::{Schemas, parse_id};
-use crate::internal::gc::GarbageCollector;
+use crate::internal::gc::{ForkLeases, GarbageCollector};
 use crate::internal::machine::{Machine, retry_external};
+use crate::internal::state::{BatchPart, RunPart, Since, SnapshotErr, Upper};
 use crate::internal::state_versions::StateVersions;
 use crate::metrics::Metrics;
 use crate::read::{LeasedReaderId, READER_LEASE_DURATION, ReadHandle};
@@ -59,6 +61,7 @@ pub mod cli {
 }
 pub mod critical;
 pub mod error;
+pub mod export;
 pub mod fetch;
 pub mod internals_bench;
 pub mod iter;
@@ -665,6 +668,107 @@ impl PersistClient {
         Ok(())
     }
 
+    /// Creates a new shard with the same contents as `source_id` as of
+    /// `as_of`, and returns its id.
+    ///
+    /// The new shard references the source's blobs instead of copying them,
+    /// so this is cheap regardless of the size of the source. Both shards can
+    /// be written, compacted, and finalized independently: the source keeps
+    /// any blobs that the fork still references until the fork is finalized.
+    ///
+    /// The fork's since is `as_of`. Its upper is beyond `as_of`, but might be
+    /// further ahead than strictly necessary, in which case the fork also
+    /// contains the source's updates up to that upper.
+    #[instrument(level = "debug", fields(shard = %source_id))]
+    pub async fn fork_shard<K, V, T, D>(
+        &self,
+        source_id: ShardId,
+        as_of: Antichain<T>,
+        diagnostics: Diagnostics,
+    ) -> Result<ShardId, InvalidUsage<T>>
+    where
+        K: Debug + Codec,
+        V: Debug + Codec,
+        T: Timestamp + Lattice + Codec64 + Sync,
+        D: Semigroup + Codec64 + Send + Sync,
+    {
+        let source = self
+            .make_machine::<K, V, T, D>(source_id, diagnostics.clone())
+            .await?;
+        let gc = GarbageCollector::new(source.clone(), Arc::clone(&self.isolated_runtime));
+
+        // Hold back GC of the source's state while we write the leases, so
+        // that none of the blobs in our snapshot are deleted out from under us.
+        let reader_id = LeasedReaderId::new();
+        let (_, maintenance) = source
+            .register_leased_reader(
+                &reader_id,
+                "fork",
+                READER_LEASE_DURATION.get(&self.cfg),
+                (self.cfg.now)(),
+                false,
+            )
+            .await;
+        maintenance.start_performing(&source, &gc);
+
+        let snapshot = source.applier.snapshot(&as_of).map_err(|err| {
+            InvalidUsage::InvalidFork(match err {
+                SnapshotErr::AsOfNotYetAvailable(_, Upper(upper)) => {
+                    format!("as_of {:?} is not less than upper {:?}", as_of, upper)
+                }
+                SnapshotErr::AsOfHistoricalDistinctionsLost(Since(since)) => {
+                    format!("as_of {:?} is not beyond since {:?}", as_of, since)
+                }
+            })
+        });
+        let batches = match snapshot {
+            Ok(batches) => batches,
+            Err(err) => {
+                let (_, maintenance) = source.expire_leased_reader(&reader_id).await;
+                maintenance.start_performing(&source, &gc);
+                return Err(err);
+            }
+        };
+        let schemas = source.applier.schemas(|_, schemas| schemas.clone());
+        let forked: Vec<_> = batches
+            .iter()
+            .map(|batch| batch.map_keys(|key| key.forked_from(&source_id)))
+            .collect();
+
+        // Each blob is leased from the shard that wrote it, which is not
+        // necessarily the source if the source is itself a fork.
+        let fork_id = ShardId::new();
+        let owners: BTreeSet<_> = forked
+            .iter()
+            .flat_map(|batch| batch.parts.iter())
+            .filter_map(|part| match part {
+                RunPart::Single(BatchPart::Hollow(x)) => x.key.owner(),
+                RunPart::Many(r) => r.key.owner(),
+                RunPart::Single(BatchPart::Inline { .. }) => None,
+            })
+            .collect();
+        for owner in owners {
+            let leased: Vec<_> = forked
+                .iter()
+                .map(|batch| batch.map_keys(|key| key.relative_to(&owner)))
+                .collect();
+            ForkLeases::write(&*self.blob, &self.metrics, &owner, &fork_id, &leased).await;
+        }
+
+        let fork = self
+            .make_machine::<K, V, T, D>(fork_id, diagnostics)
+            .await?;
+        let (res, maintenance) = fork.fork_from(&schemas, &forked, &as_of).await;
+        res.expect("fork is a new shard");
+        let fork_gc = GarbageCollector::new(fork.clone(), Arc::clone(&self.isolated_runtime));
+        let () = maintenance.perform(&fork, &fork_gc).await;
+
+        let (_, maintenance) = source.expire_leased_reader(&reader_id).await;
+        let () = maintenance.perform(&source, &gc).await;
+
+        Ok(fork_id)
+    }
+
     /// Returns the internal state of the shard for debugging and QA.
     ///
     /// We'll be thoughtful about making unnecessary changes, but the **output
@@ -753,7 +857,7 @@ mod tests {
     use crate::cache::PersistClientCache;
     use crate::cfg::BATCH_BUILDER_MAX_OUTSTANDING_PARTS;
     use crate::error::{CodecConcreteType, CodecMismatch, UpperMismatch};
-    use crate::internal::paths::BlobKey;
+    use crate::internal::paths::{BlobKey, BlobKeyPrefix};
     use crate::read::ListenEvent;
 
     use super::*;
@@ -1962,6 +2066,96 @@ mod tests {
         assert!(is_finalized, "shard must still be finalized");
     }
 
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn fork_shard(dyncfgs: ConfigUpdates) {
+        let data = [
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+        let client = new_test_client(&dyncfgs).await;
+        let source_id = ShardId::new();
+        let (mut write, mut read) = client
+            .expect_open::<String, String, u64, i64>(source_id)
+            .await;
+        write.expect_compare_and_append(&data[..2], 0, 3).await;
+
+        // A fork must be as of a readable time.
+        let res = client
+            .fork_shard::<String, String, u64, i64>(
+                source_id,
+                Antichain::from_elem(3),
+                Diagnostics::for_tests(),
+            )
+            .await;
+        assert!(
+            matches!(res, Err(InvalidUsage::InvalidFork(_))),
+            "unexpected result {:?}",
+            res
+        );
+
+        let fork_id = client
+            .fork_shard::<String, String, u64, i64>(
+                source_id,
+                Antichain::from_elem(2),
+                Diagnostics::for_tests(),
+            )
+            .await
+            .expect("valid fork");
+        let (mut fork_write, mut fork_read) = client
+            .expect_open::<String, String, u64, i64>(fork_id)
+            .await;
+        assert_eq!(fork_read.since(), &Antichain::from_elem(2));
+        assert_eq!(fork_write.upper(), &Antichain::from_elem(3));
+        assert_eq!(
+            fork_read.expect_snapshot_and_fetch(2).await,
+            all_ok(&data[..2], 2)
+        );
+
+        // The source holds a lease on behalf of the fork.
+        let mut leases = Vec::new();
+        client
+            .blob
+            .list_keys_and_metadata(
+                &BlobKeyPrefix::ForkLeases(&source_id).to_string(),
+                &mut |metadata| leases.push(metadata.key.to_owned()),
+            )
+            .await
+            .expect("list succeeds");
+        assert_eq!(
+            leases,
+            vec![BlobKey::fork_lease(&source_id, &fork_id).to_string()]
+        );
+
+        // The fork can be written independently of the source.
+        fork_write.expect_compare_and_append(&data[2..], 3, 4).await;
+        assert_eq!(
+            fork_read.expect_snapshot_and_fetch(3).await,
+            all_ok(&data, 3)
+        );
+
+        // Finalizing the source doesn't delete anything the fork references.
+        let () = read.downgrade_since(&Antichain::new()).await;
+        let () = write
+            .compare_and_append(
+                &[] as &[((String, String), u64, i64)],
+                Antichain::from_elem(3),
+                Antichain::new(),
+            )
+            .await
+            .expect("usage should be valid")
+            .expect("upper should match");
+        client
+            .finalize_shard::<String, String, u64, i64>(source_id, Diagnostics::for_tests())
+            .await
+            .expect("finalization must succeed");
+        assert_eq!(
+            fork_read.expect_snapshot_and_fetch(3).await,
+            all_ok(&data, 3)
+        );
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(4096))]
 
@@ -29,6 +29,7 @@ use mz_ore::now::EpochMillis;
 use mz_ore::task::{AbortOnDropHandle, JoinHandle, RuntimeExt};
 use mz_persist::location::{Blob, SeqNo};
 use mz_persist_types::columnar::{ColumnDecoder, Schema};
+use mz_persist_types::part::Part;
 use mz_persist_types::{Codec, Codec64};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -891,38 +892,41 @@ where
     pub async fn next(
         &mut self,
     ) -> Option<impl Iterator<Item = ((Result<K, String>, Result<V, String>), T, D)> + '_> {
+        let part = self.next_part().await?;
+        let key_decoder = self
+            .read_schemas
+            .key
+            .decoder_any(part.key.as_ref())
+            .expect("ok");
+        let val_decoder = self
+            .read_schemas
+            .val
+            .decoder_any(part.val.as_ref())
+            .expect("ok");
+        let iter = (0..part.len()).map(move |i| {
+            let mut k = K::default();
+            let mut v = V::default();
+            key_decoder.decode(i, &mut k);
+            val_decoder.decode(i, &mut v);
+            let t = T::decode(part.time.value(i).to_le_bytes());
+            let d = D::decode(part.diff.value(i).to_le_bytes());
+            ((Ok(k), Ok(v)), t, d)
+        });
+
+        Some(iter)
+    }
+
+    /// Grab the next batch of consolidated data, without decoding it.
+    pub(crate) async fn next_part(&mut self) -> Option<Part> {
         match &mut self.consolidator {
             CursorConsolidator::Structured {
                 consolidator,
                 max_len,
                 max_bytes,
-            } => {
-                let part = consolidator
-                    .next_chunk(*max_len, *max_bytes)
-                    .await
-                    .expect("fetching a leased part")?;
-                let key_decoder = self
-                    .read_schemas
-                    .key
-                    .decoder_any(part.key.as_ref())
-                    .expect("ok");
-                let val_decoder = self
-                    .read_schemas
-                    .val
-                    .decoder_any(part.val.as_ref())
-                    .expect("ok");
-                let iter = (0..part.len()).map(move |i| {
-                    let mut k = K::default();
-                    let mut v = V::default();
-                    key_decoder.decode(i, &mut k);
-                    val_decoder.decode(i, &mut v);
-                    let t = T::decode(part.time.value(i).to_le_bytes());
-                    let d = D::decode(part.diff.value(i).to_le_bytes());
-                    ((Ok(k), Ok(v)), t, d)
-                });
-
-                Some(iter)
-            }
+            } => consolidator
+                .next_chunk(*max_len, *max_bytes)
+                .await
+                .expect("fetching a leased part"),
         }
     }
 }
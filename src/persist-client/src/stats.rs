@@ -13,6 +13,7 @@ use std::borrow::Cow;
 use std::sync::Arc;
 
 use mz_dyncfg::{Config, ConfigSet};
+use mz_persist_types::stats::BloomFilterColumns;
 
 use crate::batch::UntrimmableColumns;
 use crate::metrics::Metrics;
@@ -102,6 +103,41 @@ pub(crate) const STATS_UNTRIMMABLE_COLUMNS_SUFFIX: Config<fn() -> String> = Conf
     Comma separated list.",
 );
 
+/// The key columns to maintain Bloom filters for.
+///
+/// These make equality lookups on high-cardinality columns, which the min/max
+/// stats rarely help with, skip most parts. They don't count against
+/// [STATS_BUDGET_BYTES] and are never trimmed.
+pub(crate) const STATS_BLOOM_FILTER_COLUMNS: Config<&'static str> = Config::new(
+    "persist_stats_bloom_filter_columns",
+    "",
+    "\
+    Which columns to maintain Bloom filters for in the stats of each batch \
+    part. Any column with a name exactly equal (case-insensitive) to one of \
+    these will have one. Comma separated list.",
+);
+
+/// The budget (in bytes) of each Bloom filter in a batch part.
+pub(crate) const STATS_BLOOM_FILTER_BUDGET_BYTES: Config<usize> = Config::new(
+    "persist_stats_bloom_filter_budget_bytes",
+    4 * 1024,
+    "\
+    The budget (in bytes) of the Bloom filter for each column in each batch \
+    part, see persist_stats_bloom_filter_columns.",
+);
+
+pub(crate) fn bloom_filter_columns(cfg: &ConfigSet) -> BloomFilterColumns {
+    BloomFilterColumns {
+        names: STATS_BLOOM_FILTER_COLUMNS
+            .get(cfg)
+            .split(',')
+            .filter(|x| !x.is_empty())
+            .map(|x| x.to_owned())
+            .collect(),
+        max_bytes: STATS_BLOOM_FILTER_BUDGET_BYTES.get(cfg),
+    }
+}
+
 pub(crate) fn untrimmable_columns(cfg: &ConfigSet) -> UntrimmableColumns {
     fn split(x: String) -> Vec<Cow<'static, str>> {
         x.split(',')
@@ -367,7 +367,9 @@ impl StorageUsageClient {
                                         *shard_usage.by_writer.entry(writer_id).or_default() +=
                                             metadata.size_in_bytes;
                                     }
-                                    PartialBlobKey::Rollup(_, _) => {
+                                    // Fork leases are tiny and, like rollups, are
+                                    // metadata about the shard rather than data.
+                                    PartialBlobKey::Rollup(_, _) | PartialBlobKey::ForkLease(_) => {
                                         usage.rollup_size += metadata.size_in_bytes;
                                         usage.rollup_count += 1;
                                         shard_usage.rollup_bytes += metadata.size_in_bytes;
@@ -28,11 +28,12 @@
 use anyhow::anyhow;
 use arrow::array::{Array, ArrayRef, BinaryArray, BinaryBuilder};
 use arrow::datatypes::DataType;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::Codec;
-use crate::stats::{DynStats, StructStats};
+use crate::stats::{BloomFilter, BloomFilterColumns, DynStats, StructStats};
 
 /// A __stable__ encoding for a type that gets durably persisted in an
 /// [`arrow::array::FixedSizeBinaryArray`].
@@ -82,6 +83,17 @@ pub trait ColumnDecoder<T> {
     /// named the empty string. Fix this restriction if we end up with non-test
     /// code that isn't naturally a struct.
     fn stats(&self) -> StructStats;
+
+    /// Returns [BloomFilter]s over the values of the requested columns, keyed
+    /// by column name. Like [Self::stats], these are preserved in the part
+    /// metadata and made available to readers.
+    ///
+    /// Columns that the client can't or doesn't want to maintain filters for
+    /// are omitted, which is the default.
+    fn bloom_filters(&self, cols: &BloomFilterColumns) -> BTreeMap<String, BloomFilter> {
+        let _ = cols;
+        BTreeMap::new()
+    }
 }
 
 /// An encoder for values of a fixed schema
@@ -11,6 +11,7 @@
 
 //! Aggregate statistics about data stored in persist.
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use anyhow::Context;
@@ -29,14 +30,17 @@ use prost::Message;
 
 use crate::columnar::{ColumnDecoder, Schema};
 use crate::part::Part;
+use crate::stats::bloom::any_bloom_filters;
 use crate::stats::bytes::any_bytes_stats;
 use crate::stats::primitive::any_primitive_stats;
 
+pub mod bloom;
 pub mod bytes;
 pub mod json;
 pub mod primitive;
 pub mod structured;
 
+pub use bloom::{BloomFilter, BloomFilterColumns};
 pub use bytes::{AtomicBytesStats, BytesStats, FixedSizeBytesStats, FixedSizeBytesStatsKind};
 pub use json::{JsonMapElementStats, JsonStats};
 pub use primitive::{
@@ -281,6 +285,7 @@ impl From<BytesStats> for ColumnStatKinds {
 #[derive(Debug)]
 pub struct PartStatsMetrics {
     pub mismatched_count: IntCounter,
+    pub bloom_filtered_count: IntCounter,
 }
 
 impl PartStatsMetrics {
@@ -290,6 +295,10 @@ impl PartStatsMetrics {
                 name: "mz_persist_pushdown_parts_mismatched_stats_count",
                 help: "number of parts read with unexpectedly the incorrect type of stats",
             )),
+            bloom_filtered_count: registry.register(metric!(
+                name: "mz_persist_pushdown_parts_bloom_filtered_count",
+                help: "number of parts filtered by their bloom filters but not their other stats",
+            )),
         }
     }
 }
@@ -362,11 +371,14 @@ pub trait TrimStats: Message {
 pub struct PartStats {
     /// Aggregate statistics about key data contained in a [Part].
     pub key: StructStats,
+    /// Bloom filters over the values of selected key columns, by column name.
+    #[proptest(strategy = "any_bloom_filters()")]
+    pub blooms: BTreeMap<String, BloomFilter>,
 }
 
 impl serde::Serialize for PartStats {
     fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        let PartStats { key } = self;
+        let PartStats { key, blooms: _ } = self;
         key.serialize(s)
     }
 }
@@ -379,7 +391,10 @@ impl PartStats {
     {
         let decoder = K::decoder_any(desc, &part.key).context("decoder_any")?;
         let stats = decoder.stats();
-        Ok(PartStats { key: stats })
+        Ok(PartStats {
+            key: stats,
+            blooms: BTreeMap::new(),
+        })
     }
 }
 
@@ -0,0 +1,225 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Bloom filters over the values of a column.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use mz_ore::cast::CastFrom;
+use mz_proto::{ProtoType, RustType, TryFromProtoError};
+use proptest::prelude::*;
+use proptest::strategy::Strategy;
+
+use crate::stats::{ProtoBloomFilter, ProtoBloomFilters};
+
+/// Which columns to maintain [BloomFilter]s for, and how large to make them.
+#[derive(Debug, Clone, Default)]
+pub struct BloomFilterColumns {
+    /// Names of the columns, compared case-insensitively.
+    pub names: Vec<String>,
+    /// The maximum size of the filter for a single column in a single part.
+    pub max_bytes: usize,
+}
+
+impl BloomFilterColumns {
+    /// Whether to maintain a [BloomFilter] for the column with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.max_bytes > 0 && self.names.iter().any(|x| x.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A probabilistic set of byte strings.
+///
+/// [Self::may_contain] never returns false for a value that was inserted, but
+/// may return true for a value that wasn't. The filter is sized when it's
+/// created, so the rate of these false positives goes up if more values are
+/// inserted than it was sized for.
+///
+/// The hash function is part of the encoded format: filters written by one
+/// version of persist are read by another.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    num_hashes: u32,
+    bits: Vec<u8>,
+}
+
+impl Debug for BloomFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BloomFilter")
+            .field("num_hashes", &self.num_hashes)
+            .field("num_bytes", &self.bits.len())
+            .finish()
+    }
+}
+
+impl BloomFilter {
+    /// The number of bits per value we size filters for, which gives a false
+    /// positive rate of about 1%.
+    const BITS_PER_VALUE: usize = 10;
+    /// An upper bound on the number of hash functions, to bound the cost of
+    /// inserts and lookups in sparse filters.
+    const MAX_HASHES: usize = 16;
+
+    /// Returns an empty filter sized for `num_values` values, but no larger than
+    /// `max_bytes`.
+    pub fn new(num_values: usize, max_bytes: usize) -> Self {
+        let num_bytes = num_values
+            .saturating_mul(Self::BITS_PER_VALUE)
+            .div_ceil(8)
+            .clamp(1, max_bytes.max(1));
+        // The optimal number of hashes is `ln(2) * bits / values`.
+        let num_hashes = (num_bytes * 8 * 693 / 1000)
+            .checked_div(num_values)
+            .unwrap_or(1)
+            .clamp(1, Self::MAX_HASHES);
+        BloomFilter {
+            num_hashes: u32::try_from(num_hashes).expect("clamped to MAX_HASHES"),
+            bits: vec![0; num_bytes],
+        }
+    }
+
+    /// The size of the filter in bytes.
+    pub fn num_bytes(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Adds a value to the filter.
+    pub fn insert(&mut self, value: &[u8]) {
+        for idx in self.bit_indexes(value) {
+            self.bits[idx / 8] |= 1 << (idx % 8);
+        }
+    }
+
+    /// Returns false if `value` is definitely not in the filter.
+    pub fn may_contain(&self, value: &[u8]) -> bool {
+        self.bit_indexes(value)
+            .all(|idx| self.bits[idx / 8] & (1 << (idx % 8)) != 0)
+    }
+
+    /// The bits for a value, derived from one 64-bit hash with the technique
+    /// from "Less Hashing, Same Performance" (Kirsch and Mitzenmacher).
+    fn bit_indexes(&self, value: &[u8]) -> impl Iterator<Item = usize> + use<> {
+        let hash = seahash::hash(value);
+        let (h1, h2) = (hash & 0xffff_ffff, hash >> 32);
+        let num_bits = u64::cast_from(self.bits.len()) * 8;
+        (0..u64::from(self.num_hashes))
+            .map(move |i| usize::cast_from(h1.wrapping_add(i.wrapping_mul(h2)) % num_bits))
+    }
+}
+
+impl RustType<ProtoBloomFilter> for BloomFilter {
+    fn into_proto(&self) -> ProtoBloomFilter {
+        ProtoBloomFilter {
+            num_hashes: self.num_hashes,
+            bits: self.bits.clone().into(),
+        }
+    }
+
+    fn from_proto(proto: ProtoBloomFilter) -> Result<Self, TryFromProtoError> {
+        if proto.num_hashes == 0 || proto.bits.is_empty() {
+            return Err(TryFromProtoError::InvalidFieldError(format!(
+                "empty bloom filter: {} hashes, {} bytes",
+                proto.num_hashes,
+                proto.bits.len()
+            )));
+        }
+        Ok(BloomFilter {
+            num_hashes: proto.num_hashes,
+            bits: proto.bits.to_vec(),
+        })
+    }
+}
+
+impl RustType<ProtoBloomFilters> for BTreeMap<String, BloomFilter> {
+    fn into_proto(&self) -> ProtoBloomFilters {
+        ProtoBloomFilters {
+            cols: self
+                .iter()
+                .map(|(k, v)| (k.into_proto(), RustType::into_proto(v)))
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoBloomFilters) -> Result<Self, TryFromProtoError> {
+        let mut cols = BTreeMap::new();
+        for (k, v) in proto.cols {
+            cols.insert(k.into_rust()?, v.into_rust()?);
+        }
+        Ok(cols)
+    }
+}
+
+/// Returns a [`Strategy`] for generating arbitrary [`BloomFilter`]s by column.
+pub(crate) fn any_bloom_filters() -> impl Strategy<Value = BTreeMap<String, BloomFilter>> {
+    let filter = (
+        0..64usize,
+        1..128usize,
+        proptest::collection::vec(any::<Vec<u8>>(), 0..8),
+    )
+        .prop_map(|(num_values, max_bytes, values)| {
+            let mut filter = BloomFilter::new(num_values, max_bytes);
+            for value in values {
+                filter.insert(&value);
+            }
+            filter
+        });
+    proptest::collection::btree_map(any::<String>(), filter, 0..3)
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::assert_ok;
+    use mz_proto::protobuf_roundtrip;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn bloom_filter() {
+        let values: Vec<String> = (0..1000).map(|x| x.to_string()).collect();
+        let mut filter = BloomFilter::new(values.len(), 1024);
+        assert_eq!(filter.num_bytes(), 1024);
+        for value in values.iter() {
+            filter.insert(value.as_bytes());
+        }
+        // No false negatives.
+        for value in values.iter() {
+            assert!(filter.may_contain(value.as_bytes()));
+        }
+        // Few false positives.
+        let false_positives = (1000..11000)
+            .filter(|x| filter.may_contain(x.to_string().as_bytes()))
+            .count();
+        assert!(false_positives < 500, "{} false positives", false_positives);
+
+        // A filter smaller than it should be still has no false negatives.
+        let mut filter = BloomFilter::new(values.len(), 16);
+        assert_eq!(filter.num_bytes(), 16);
+        for value in values.iter() {
+            filter.insert(value.as_bytes());
+        }
+        for value in values.iter() {
+            assert!(filter.may_contain(value.as_bytes()));
+        }
+
+        // An empty filter contains nothing.
+        let filter = BloomFilter::new(0, 1024);
+        assert!(!filter.may_contain(b"a"));
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn bloom_filters_protobuf_roundtrip() {
+        proptest!(|(expect in any_bloom_filters())| {
+            let actual = protobuf_roundtrip::<_, ProtoBloomFilters>(&expect);
+            assert_ok!(actual);
+            assert_eq!(actual.unwrap(), expect);
+        });
+    }
+}
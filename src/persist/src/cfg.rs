@@ -22,7 +22,8 @@ use mz_postgres_client::PostgresClientKnobs;
 use mz_postgres_client::metrics::PostgresClientMetrics;
 
 use crate::azure::{AzureBlob, AzureBlobConfig};
-use crate::file::{FileBlob, FileBlobConfig};
+use crate::encrypted::{BlobKeyring, EncryptedBlob};
+use crate::file::{FileBlob, FileBlobConfig, FileConsensus, FileConsensusConfig};
 use crate::location::{Blob, Consensus, Determinate, ExternalError};
 use crate::mem::{MemBlob, MemBlobConfig, MemConsensus};
 use crate::metrics::S3BlobMetrics;
@@ -51,6 +52,8 @@ pub enum BlobConfig {
     Mem(bool),
     /// Config for [AzureBlob].
     Azure(AzureBlobConfig),
+    /// Config for an [EncryptedBlob] wrapping another [Blob].
+    Encrypted(Box<BlobConfig>, BlobKeyring),
 }
 
 /// Configuration knobs for [Blob].
@@ -77,6 +80,10 @@ impl BlobConfig {
             BlobConfig::Mem(tombstone) => {
                 Ok(Arc::new(MemBlob::open(MemBlobConfig::new(tombstone))))
             }
+            BlobConfig::Encrypted(config, keyring) => {
+                let blob = Box::pin(config.open()).await?;
+                Ok(Arc::new(EncryptedBlob::new(blob, keyring)))
+            }
         }
     }
 
@@ -88,6 +95,10 @@ impl BlobConfig {
         cfg: Arc<ConfigSet>,
     ) -> Result<Self, ExternalError> {
         let mut query_params = url.query_pairs().collect::<BTreeMap<_, _>>();
+        // Any scheme can be wrapped with encryption, so handle it first.
+        let encryption_key_file = query_params
+            .remove("encryption_key_file")
+            .map(|x| x.into_owned());
 
         let config = match url.scheme() {
             "file" => {
@@ -205,6 +216,16 @@ impl BlobConfig {
             )));
         }
 
+        let config = match encryption_key_file {
+            None => config,
+            Some(path) => {
+                let keys = tokio::fs::read_to_string(&path)
+                    .await
+                    .map_err(|err| anyhow!("reading blob encryption keys from {path}: {err}"))?;
+                BlobConfig::Encrypted(Box::new(config), BlobKeyring::parse(&keys)?)
+            }
+        };
+
         Ok(config)
     }
 }
@@ -212,6 +233,8 @@ impl BlobConfig {
 /// Config for an implementation of [Consensus].
 #[derive(Debug, Clone)]
 pub enum ConsensusConfig {
+    /// Config for [FileConsensus].
+    File(FileConsensusConfig),
     /// Config for [PostgresConsensus].
     Postgres(PostgresConsensusConfig),
     /// Config for [MemConsensus], only available in testing.
@@ -222,6 +245,7 @@ impl ConsensusConfig {
     /// Opens the associated implementation of [Consensus].
     pub async fn open(self) -> Result<Arc<dyn Consensus>, ExternalError> {
         match self {
+            ConsensusConfig::File(config) => Ok(Arc::new(FileConsensus::open(config).await?)),
             ConsensusConfig::Postgres(config) => {
                 Ok(Arc::new(PostgresConsensus::open(config).await?))
             }
@@ -237,6 +261,7 @@ impl ConsensusConfig {
         dyncfg: Arc<ConfigSet>,
     ) -> Result<Self, ExternalError> {
         let config = match url.scheme() {
+            "file" => Ok(ConsensusConfig::File(FileConsensusConfig::from(url.path()))),
             "postgres" | "postgresql" => Ok(ConsensusConfig::Postgres(
                 PostgresConsensusConfig::new(url, knobs, metrics, dyncfg)?,
             )),
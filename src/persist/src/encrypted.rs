@@ -0,0 +1,412 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Client-side envelope encryption for [Blob] implementations.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use base64::Engine;
+use bytes::Bytes;
+use mz_ore::bytes::SegmentedBytes;
+use mz_ore::cast::CastFrom;
+use openssl::symm::{Cipher, decrypt_aead, encrypt_aead};
+
+use crate::location::{Blob, BlobMetadata, ExternalError};
+
+/// Magic bytes, including a format version, at the start of every encrypted
+/// blob.
+const MAGIC: &[u8; 4] = b"MZE\x01";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const FINGERPRINT_LEN: usize = 8;
+/// Length of the magic, key-encryption key fingerprint, and wrapped data key
+/// that prefix every encrypted blob.
+const HEADER_LEN: usize = MAGIC.len() + FINGERPRINT_LEN + NONCE_LEN + TAG_LEN + KEY_LEN;
+
+/// The number of bytes an encrypted blob is larger than its plaintext.
+pub const ENCRYPTION_OVERHEAD: usize = HEADER_LEN + NONCE_LEN + TAG_LEN;
+
+/// A key-encryption key, used to wrap the data key of each blob.
+#[derive(Clone)]
+struct KeyEncryptionKey {
+    /// Identifies the key in the header of encrypted blobs, without revealing it.
+    fingerprint: [u8; FINGERPRINT_LEN],
+    key: [u8; KEY_LEN],
+}
+
+impl KeyEncryptionKey {
+    fn new(key: [u8; KEY_LEN]) -> Self {
+        let mut input = b"mz-persist-kek".to_vec();
+        input.extend_from_slice(&key);
+        let hash = openssl::sha::sha256(&input);
+        let fingerprint = hash[..FINGERPRINT_LEN]
+            .try_into()
+            .expect("sha256 is longer than a fingerprint");
+        KeyEncryptionKey { fingerprint, key }
+    }
+}
+
+/// The key-encryption keys available to an [EncryptedBlob].
+///
+/// The first key is used to wrap the data keys of newly written blobs, all of
+/// them are used to unwrap the data keys of existing blobs. To rotate keys,
+/// add a new key to the front of the keyring, [EncryptedBlob::rewrap] every
+/// blob, and then remove the old key.
+#[derive(Clone)]
+pub struct BlobKeyring {
+    keys: Vec<KeyEncryptionKey>,
+}
+
+impl Debug for BlobKeyring {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Never print the keys themselves.
+        let fingerprints: Vec<_> = self
+            .keys
+            .iter()
+            .map(|kek| fingerprint_to_string(&kek.fingerprint))
+            .collect();
+        f.debug_struct("BlobKeyring")
+            .field("fingerprints", &fingerprints)
+            .finish()
+    }
+}
+
+impl BlobKeyring {
+    /// Returns a keyring with the given 256-bit keys, the first of which is
+    /// the active key.
+    pub fn new(keys: Vec<[u8; KEY_LEN]>) -> Result<Self, ExternalError> {
+        if keys.is_empty() {
+            return Err(ExternalError::from(anyhow!(
+                "blob keyring requires at least one key"
+            )));
+        }
+        let keys = keys.into_iter().map(KeyEncryptionKey::new).collect();
+        Ok(BlobKeyring { keys })
+    }
+
+    /// Parses a keyring that contains one base64 encoded 256-bit key per line.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self, ExternalError> {
+        let keys = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .enumerate()
+            .map(|(idx, line)| {
+                let key = base64::engine::general_purpose::STANDARD
+                    .decode(line)
+                    .map_err(|err| anyhow!("invalid blob encryption key {idx}: {err}"))?;
+                let key = <[u8; KEY_LEN]>::try_from(key).map_err(|key| {
+                    anyhow!(
+                        "invalid blob encryption key {idx}: expected {KEY_LEN} bytes got {}",
+                        key.len()
+                    )
+                })?;
+                Ok::<_, anyhow::Error>(key)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(keys)
+    }
+
+    fn active(&self) -> &KeyEncryptionKey {
+        self.keys.first().expect("validated non-empty")
+    }
+
+    fn find(&self, fingerprint: &[u8]) -> Option<&KeyEncryptionKey> {
+        self.keys.iter().find(|kek| kek.fingerprint == fingerprint)
+    }
+}
+
+fn fingerprint_to_string(fingerprint: &[u8]) -> String {
+    fingerprint.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Encrypts `plaintext` with `key`, appending the nonce, tag, and then the
+/// ciphertext to `out`.
+fn seal(key: &[u8], aad: &[u8], plaintext: &[u8], out: &mut Vec<u8>) -> Result<(), ExternalError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    openssl::rand::rand_bytes(&mut nonce).map_err(anyhow::Error::new)?;
+    let mut tag = [0u8; TAG_LEN];
+    let ciphertext = encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce),
+        aad,
+        plaintext,
+        &mut tag,
+    )
+    .map_err(anyhow::Error::new)?;
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&tag);
+    out.extend_from_slice(&ciphertext);
+    Ok(())
+}
+
+/// The inverse of [seal].
+fn open(key: &[u8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    if sealed.len() < NONCE_LEN + TAG_LEN {
+        return Err(anyhow!("truncated ciphertext"));
+    }
+    let (nonce, rest) = sealed.split_at(NONCE_LEN);
+    let (tag, ciphertext) = rest.split_at(TAG_LEN);
+    let plaintext = decrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(nonce),
+        aad,
+        ciphertext,
+        tag,
+    )?;
+    Ok(plaintext)
+}
+
+/// A [Blob] that encrypts the contents of every blob before delegating to
+/// another [Blob].
+///
+/// Every blob is encrypted with AES-256-GCM using a fresh data key, which is
+/// itself encrypted ("wrapped") by the active key-encryption key of a
+/// [BlobKeyring] and stored in a fixed size header. The data is authenticated
+/// along with the key of the blob, so the contents of two blobs can't be
+/// swapped undetected.
+///
+/// Sizes reported by [Blob::list_keys_and_metadata] and [Blob::delete] are
+/// the plaintext sizes, so this is transparent to callers.
+#[derive(Debug)]
+pub struct EncryptedBlob {
+    blob: Arc<dyn Blob>,
+    keyring: BlobKeyring,
+}
+
+impl EncryptedBlob {
+    /// Returns a new [EncryptedBlob].
+    pub fn new(blob: Arc<dyn Blob>, keyring: BlobKeyring) -> Self {
+        EncryptedBlob { blob, keyring }
+    }
+
+    /// Returns the header for a blob with the data key `dek`, wrapped by the
+    /// active key-encryption key.
+    fn header(&self, dek: &[u8]) -> Result<Vec<u8>, ExternalError> {
+        let kek = self.keyring.active();
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&kek.fingerprint);
+        let aad = header.clone();
+        seal(&kek.key, &aad, dek, &mut header)?;
+        debug_assert_eq!(header.len(), HEADER_LEN);
+        Ok(header)
+    }
+
+    /// Unwraps the data key from the header of an encrypted blob. Also returns
+    /// the fingerprint of the key-encryption key that wrapped it.
+    fn unwrap_key<'a>(
+        &self,
+        key: &str,
+        value: &'a [u8],
+    ) -> Result<(&'a [u8], Vec<u8>), ExternalError> {
+        if value.len() < ENCRYPTION_OVERHEAD || !value.starts_with(MAGIC) {
+            return Err(ExternalError::from(anyhow!("blob {key} is not encrypted")));
+        }
+        let (aad, wrapped) = value[..HEADER_LEN].split_at(MAGIC.len() + FINGERPRINT_LEN);
+        let fingerprint = &aad[MAGIC.len()..];
+        let kek = self.keyring.find(fingerprint).ok_or_else(|| {
+            anyhow!(
+                "blob {key} was encrypted with unknown key {}",
+                fingerprint_to_string(fingerprint)
+            )
+        })?;
+        let dek = open(&kek.key, aad, wrapped)
+            .map_err(|err| anyhow!("unwrapping data key of blob {key}: {err}"))?;
+        Ok((fingerprint, dek))
+    }
+
+    fn encrypt(&self, key: &str, value: &[u8]) -> Result<Bytes, ExternalError> {
+        let mut dek = [0u8; KEY_LEN];
+        openssl::rand::rand_bytes(&mut dek).map_err(anyhow::Error::new)?;
+
+        let mut out = Vec::with_capacity(value.len() + ENCRYPTION_OVERHEAD);
+        out.extend_from_slice(&self.header(&dek)?);
+        seal(&dek, key.as_bytes(), value, &mut out)?;
+        Ok(Bytes::from(out))
+    }
+
+    fn decrypt(&self, key: &str, value: &[u8]) -> Result<Vec<u8>, ExternalError> {
+        let (_fingerprint, dek) = self.unwrap_key(key, value)?;
+        let plaintext = open(&dek, key.as_bytes(), &value[HEADER_LEN..])
+            .map_err(|err| anyhow!("decrypting blob {key}: {err}"))?;
+        Ok(plaintext)
+    }
+
+    /// Re-wraps the data key of the blob at `key` with the active
+    /// key-encryption key, without re-encrypting the data.
+    ///
+    /// Returns whether the blob was rewritten, which is not the case if it
+    /// doesn't exist or already uses the active key. Note that a blob deleted
+    /// concurrently with a rewrap can be resurrected, which leaks it.
+    pub async fn rewrap(&self, key: &str) -> Result<bool, ExternalError> {
+        let Some(value) = self.blob.get(key).await? else {
+            return Ok(false);
+        };
+        let value = value.into_contiguous();
+        let (fingerprint, dek) = self.unwrap_key(key, &value)?;
+        if fingerprint == self.keyring.active().fingerprint {
+            return Ok(false);
+        }
+
+        let mut out = self.header(&dek)?;
+        out.extend_from_slice(&value[HEADER_LEN..]);
+        self.blob.set(key, Bytes::from(out)).await?;
+        Ok(true)
+    }
+
+    /// Calls [Self::rewrap] for every blob with the given prefix, returning
+    /// the number of blobs that were rewritten.
+    pub async fn rewrap_all(&self, key_prefix: &str) -> Result<usize, ExternalError> {
+        let mut keys = Vec::new();
+        self.blob
+            .list_keys_and_metadata(key_prefix, &mut |metadata| {
+                keys.push(metadata.key.to_string())
+            })
+            .await?;
+
+        let mut count = 0;
+        for key in keys {
+            if self.rewrap(&key).await? {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl Blob for EncryptedBlob {
+    async fn get(&self, key: &str) -> Result<Option<SegmentedBytes>, ExternalError> {
+        let Some(value) = self.blob.get(key).await? else {
+            return Ok(None);
+        };
+        let plaintext = self.decrypt(key, &value.into_contiguous())?;
+        Ok(Some(SegmentedBytes::from(plaintext)))
+    }
+
+    async fn list_keys_and_metadata(
+        &self,
+        key_prefix: &str,
+        f: &mut (dyn FnMut(BlobMetadata) + Send + Sync),
+    ) -> Result<(), ExternalError> {
+        let overhead = u64::cast_from(ENCRYPTION_OVERHEAD);
+        self.blob
+            .list_keys_and_metadata(key_prefix, &mut |metadata| {
+                f(BlobMetadata {
+                    key: metadata.key,
+                    size_in_bytes: metadata.size_in_bytes.saturating_sub(overhead),
+                })
+            })
+            .await
+    }
+
+    async fn set(&self, key: &str, value: Bytes) -> Result<(), ExternalError> {
+        let value = self.encrypt(key, &value)?;
+        self.blob.set(key, value).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<Option<usize>, ExternalError> {
+        let size = self.blob.delete(key).await?;
+        Ok(size.map(|size| size.saturating_sub(ENCRYPTION_OVERHEAD)))
+    }
+
+    async fn restore(&self, key: &str) -> Result<(), ExternalError> {
+        self.blob.restore(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::location::tests::blob_impl_test;
+    use crate::mem::{MemBlob, MemBlobConfig};
+
+    use super::*;
+
+    fn keyring(keys: &[u8]) -> BlobKeyring {
+        BlobKeyring::new(keys.iter().map(|k| [*k; KEY_LEN]).collect()).unwrap()
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl`
+    async fn encrypted_blob() -> Result<(), ExternalError> {
+        let registry = Arc::new(tokio::sync::Mutex::new(crate::mem::MemMultiRegistry::new(
+            true,
+        )));
+        blob_impl_test(move |path| {
+            let path = path.to_owned();
+            let registry = Arc::clone(&registry);
+            async move {
+                let blob = registry.lock().await.blob(&path);
+                Ok(EncryptedBlob::new(Arc::new(blob), keyring(&[1])))
+            }
+        })
+        .await
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl`
+    async fn encrypted_blob_rewrap() -> Result<(), ExternalError> {
+        let inner: Arc<dyn Blob> = Arc::new(MemBlob::open(MemBlobConfig::new(false)));
+        let value = Bytes::from("hello world");
+
+        let old = EncryptedBlob::new(Arc::clone(&inner), keyring(&[1]));
+        old.set("k", value.clone()).await?;
+
+        // The plaintext doesn't appear in the underlying blob.
+        let raw = inner.get("k").await?.unwrap().into_contiguous();
+        assert_eq!(raw.len(), value.len() + ENCRYPTION_OVERHEAD);
+        assert!(!raw.windows(value.len()).any(|w| w == &value[..]));
+
+        // Data can't be read without the right key, or under a different key.
+        let other = EncryptedBlob::new(Arc::clone(&inner), keyring(&[2]));
+        assert!(other.get("k").await.is_err());
+        inner.set("k2", Bytes::from(raw)).await?;
+        assert!(old.get("k2").await.is_err());
+        inner.delete("k2").await?;
+
+        // Rotate to a new key, which can still read data wrapped by the old key.
+        let new = EncryptedBlob::new(Arc::clone(&inner), keyring(&[2, 1]));
+        assert_eq!(
+            new.get("k").await?.map(|s| s.into_contiguous()),
+            Some(value.to_vec())
+        );
+        assert_eq!(new.rewrap_all("k").await?, 1);
+        assert_eq!(new.rewrap_all("k").await?, 0);
+
+        // After rewrapping, the old key is no longer necessary.
+        assert!(old.get("k").await.is_err());
+        assert_eq!(
+            other.get("k").await?.map(|s| s.into_contiguous()),
+            Some(value.to_vec())
+        );
+
+        Ok(())
+    }
+
+    #[mz_ore::test]
+    fn keyring_parse() {
+        let key = base64::engine::general_purpose::STANDARD.encode([7u8; KEY_LEN]);
+        let keyring = BlobKeyring::parse(&format!("# active\n{key}\n\n{key}\n")).unwrap();
+        assert_eq!(keyring.keys.len(), 2);
+        assert!(!format!("{keyring:?}").contains(&key));
+
+        assert!(BlobKeyring::parse("# empty\n").is_err());
+        assert!(BlobKeyring::parse("not base64!").is_err());
+        assert!(BlobKeyring::parse("c2hvcnQ=").is_err());
+    }
+}
@@ -7,22 +7,28 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-//! File backed implementations for testing and benchmarking.
+//! File backed implementations for testing, benchmarking and single-node
+//! deployments.
 
-use std::io::ErrorKind;
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::anyhow;
+use async_stream::try_stream;
 use async_trait::async_trait;
 use bytes::Bytes;
 use fail::fail_point;
 use mz_ore::bytes::SegmentedBytes;
 use mz_ore::cast::CastFrom;
+use nix::fcntl::{Flock, FlockArg};
 use tokio::fs::{self, File};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::error::Error;
-use crate::location::{Blob, BlobMetadata, Determinate, ExternalError};
+use crate::location::{
+    Blob, BlobMetadata, CaSResult, Consensus, Determinate, ExternalError, ResultStream, SeqNo,
+    VersionedData,
+};
 
 /// Configuration for opening a [FileBlob].
 #[derive(Debug, Clone)]
@@ -269,9 +275,355 @@ impl Blob for FileBlob {
     }
 }
 
+/// Configuration for opening a [FileConsensus].
+#[derive(Debug, Clone)]
+pub struct FileConsensusConfig {
+    base_dir: PathBuf,
+}
+
+impl<P: AsRef<Path>> From<P> for FileConsensusConfig {
+    fn from(base_dir: P) -> Self {
+        FileConsensusConfig {
+            base_dir: base_dir.as_ref().to_path_buf(),
+        }
+    }
+}
+
+/// Implementation of [Consensus] backed by files.
+///
+/// Each key is stored in its own file as an append-only log of versions, where
+/// every version is encoded as a header with its sequence number and the length
+/// of its data, then the data itself, and then the header again. The trailing
+/// copy of the header lets [Consensus::head] and [Consensus::compare_and_set]
+/// read just the latest version from the end of the log, only falling back to
+/// reading the whole log if the end is a partial write left behind by a crash.
+///
+/// Operations hold an advisory lock on a lock file next to the log of their
+/// key, shared for reads and exclusive for writes, so operations on different
+/// keys don't contend with each other. This makes it safe for multiple
+/// processes on the same machine to share a directory, but not for network
+/// filesystems, where advisory locks are unreliable.
+#[derive(Debug)]
+pub struct FileConsensus {
+    base_dir: PathBuf,
+}
+
+impl FileConsensus {
+    const LOCK_EXTENSION: &'static str = "lock";
+    const LOG_EXTENSION: &'static str = "log";
+    /// Length of the sequence number and data length that surround each
+    /// version.
+    const HEADER_LEN: usize = 16;
+
+    /// Opens the given location for non-exclusive read-write access.
+    pub async fn open(config: FileConsensusConfig) -> Result<Self, ExternalError> {
+        let FileConsensusConfig { base_dir } = config;
+        fs::create_dir_all(&base_dir).await.map_err(Error::from)?;
+        Ok(FileConsensus { base_dir })
+    }
+
+    /// Keys are escaped so they're valid file names, see
+    /// [FileBlob::replace_forward_slashes] for a similar problem.
+    fn log_path(&self, key: &str) -> PathBuf {
+        let name = format!("{}.{}", urlencoding::encode(key), Self::LOG_EXTENSION);
+        self.base_dir.join(name)
+    }
+
+    fn lock_path(&self, key: &str) -> PathBuf {
+        let name = format!("{}.{}", urlencoding::encode(key), Self::LOCK_EXTENSION);
+        self.base_dir.join(name)
+    }
+
+    /// Runs `f` on a blocking thread while holding the lock for `key`,
+    /// exclusively if `exclusive` is set.
+    async fn with_lock<R, F>(&self, key: &str, exclusive: bool, f: F) -> Result<R, ExternalError>
+    where
+        R: Send + 'static,
+        F: FnOnce() -> Result<R, ExternalError> + Send + 'static,
+    {
+        let lock_path = self.lock_path(key);
+        mz_ore::task::spawn_blocking(
+            || "persist::file_consensus",
+            move || {
+                let file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .truncate(false)
+                    .write(true)
+                    .open(&lock_path)?;
+                let arg = if exclusive {
+                    FlockArg::LockExclusive
+                } else {
+                    FlockArg::LockShared
+                };
+                // The lock is released when dropped.
+                let _lock = Flock::lock(file, arg).map_err(|(_, errno)| {
+                    ExternalError::from(anyhow!("locking {}: {errno}", lock_path.display()))
+                })?;
+                f()
+            },
+        )
+        .await?
+    }
+
+    /// Reads all of the versions in the log at `path`, in ascending order of
+    /// sequence number.
+    ///
+    /// Also returns the length of the log up to the end of the last complete
+    /// version, anything after is a partial write left behind by a crash.
+    fn read_log(path: &Path) -> Result<(Vec<VersionedData>, u64), ExternalError> {
+        let buf = match std::fs::read(path) {
+            Ok(buf) => Bytes::from(buf),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut versions = Vec::new();
+        let mut offset = 0;
+        while let Some(header) = buf.get(offset..offset + Self::HEADER_LEN) {
+            let (seqno, len) = Self::decode_header(header);
+            let start = offset + Self::HEADER_LEN;
+            let Some(end) = start.checked_add(usize::cast_from(len)) else {
+                break;
+            };
+            // A version is only complete once its trailing header is written.
+            let trailer = end
+                .checked_add(Self::HEADER_LEN)
+                .and_then(|trailer_end| buf.get(end..trailer_end));
+            if trailer != Some(header) {
+                break;
+            }
+            versions.push(VersionedData {
+                seqno: SeqNo(seqno),
+                data: buf.slice(start..end),
+            });
+            offset = end + Self::HEADER_LEN;
+        }
+
+        Ok((versions, u64::cast_from(offset)))
+    }
+
+    /// Reads the latest version in the log at `path` from the end of the log,
+    /// along with the length of the log.
+    ///
+    /// Returns `None` if the end of the log is not a complete version, in which
+    /// case it has to be read with [Self::read_log].
+    fn read_tail(path: &Path) -> Result<Option<(Option<VersionedData>, u64)>, ExternalError> {
+        let mut file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Some((None, 0))),
+            Err(err) => return Err(err.into()),
+        };
+        let len = file.metadata()?.len();
+        if len == 0 {
+            return Ok(Some((None, 0)));
+        }
+
+        let header_len = u64::cast_from(Self::HEADER_LEN);
+        let Some(trailer_start) = len.checked_sub(header_len) else {
+            return Ok(None);
+        };
+        let mut trailer = [0; Self::HEADER_LEN];
+        file.seek(SeekFrom::Start(trailer_start))?;
+        file.read_exact(&mut trailer)?;
+        let (seqno, data_len) = Self::decode_header(&trailer);
+        let Some(start) = trailer_start
+            .checked_sub(data_len)
+            .and_then(|data_start| data_start.checked_sub(header_len))
+        else {
+            return Ok(None);
+        };
+
+        let mut buf = vec![0; usize::cast_from(trailer_start - start)];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut buf)?;
+        if buf[..Self::HEADER_LEN] != trailer {
+            return Ok(None);
+        }
+        let data = Bytes::from(buf).slice(Self::HEADER_LEN..);
+        Ok(Some((
+            Some(VersionedData {
+                seqno: SeqNo(seqno),
+                data,
+            }),
+            len,
+        )))
+    }
+
+    /// Reads the latest version in the log at `path`, along with the length of
+    /// the log up to the end of the last complete version.
+    fn read_head(path: &Path) -> Result<(Option<VersionedData>, u64), ExternalError> {
+        if let Some(tail) = Self::read_tail(path)? {
+            return Ok(tail);
+        }
+        let (mut versions, valid_len) = Self::read_log(path)?;
+        Ok((versions.pop(), valid_len))
+    }
+
+    fn decode_header(header: &[u8]) -> (u64, u64) {
+        let (seqno, len) = header.split_at(8);
+        (
+            u64::from_be_bytes(seqno.try_into().expect("8 bytes")),
+            u64::from_be_bytes(len.try_into().expect("8 bytes")),
+        )
+    }
+
+    fn encode_version(buf: &mut Vec<u8>, version: &VersionedData) {
+        let start = buf.len();
+        buf.extend_from_slice(&version.seqno.0.to_be_bytes());
+        buf.extend_from_slice(&u64::cast_from(version.data.len()).to_be_bytes());
+        buf.extend_from_slice(&version.data);
+        buf.extend_from_within(start..start + Self::HEADER_LEN);
+    }
+}
+
+#[async_trait]
+impl Consensus for FileConsensus {
+    fn list_keys(&self) -> ResultStream<String> {
+        Box::pin(try_stream! {
+            let mut entries = fs::read_dir(&self.base_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.extension().and_then(|os| os.to_str()) != Some(Self::LOG_EXTENSION) {
+                    continue;
+                }
+                let Some(name) = path.file_stem().and_then(|os| os.to_str()) else {
+                    continue;
+                };
+                let key = urlencoding::decode(name).map_err(|err| {
+                    ExternalError::from(anyhow!("malformed consensus file {name}: {err}"))
+                })?;
+                yield key.into_owned();
+            }
+        })
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<VersionedData>, ExternalError> {
+        let path = self.log_path(key);
+        self.with_lock(key, false, move || {
+            let (head, _) = Self::read_head(&path)?;
+            Ok(head)
+        })
+        .await
+    }
+
+    async fn compare_and_set(
+        &self,
+        key: &str,
+        expected: Option<SeqNo>,
+        new: VersionedData,
+    ) -> Result<CaSResult, ExternalError> {
+        if let Some(expected) = expected {
+            if new.seqno <= expected {
+                return Err(ExternalError::from(anyhow!(
+                    "new seqno must be strictly greater than expected. Got new: {:?} expected: {:?}",
+                    new.seqno,
+                    expected
+                )));
+            }
+        }
+
+        if new.seqno.0 > i64::MAX.try_into().expect("i64::MAX known to fit in u64") {
+            return Err(ExternalError::from(anyhow!(
+                "sequence numbers must fit within [0, i64::MAX], received: {:?}",
+                new.seqno
+            )));
+        }
+
+        let path = self.log_path(key);
+        let base_dir = self.base_dir.clone();
+        self.with_lock(key, true, move || {
+            let (head, valid_len) = Self::read_head(&path)?;
+            if head.as_ref().map(|data| data.seqno) != expected {
+                return Ok(CaSResult::ExpectationMismatch);
+            }
+
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&path)?;
+            // Drop any partially written version left behind by a crash.
+            file.set_len(valid_len)?;
+            file.seek(SeekFrom::End(0))?;
+
+            let mut buf = Vec::with_capacity(2 * Self::HEADER_LEN + new.data.len());
+            Self::encode_version(&mut buf, &new);
+            file.write_all(&buf)?;
+
+            fail_point!("fileconsensus_cas_sync", |_| {
+                Err(ExternalError::from(anyhow!(
+                    "FileConsensus::compare_and_set fail point reached for file {:?}",
+                    path
+                )))
+            });
+
+            file.sync_data()?;
+            if head.is_none() {
+                // fsync the directory to guarantee it can see a newly created file.
+                std::fs::File::open(&base_dir)?.sync_all()?;
+            }
+
+            Ok(CaSResult::Committed)
+        })
+        .await
+    }
+
+    async fn scan(
+        &self,
+        key: &str,
+        from: SeqNo,
+        limit: usize,
+    ) -> Result<Vec<VersionedData>, ExternalError> {
+        let path = self.log_path(key);
+        self.with_lock(key, false, move || {
+            let (versions, _) = Self::read_log(&path)?;
+            let from_idx = versions.partition_point(|x| x.seqno < from);
+            Ok(versions.into_iter().skip(from_idx).take(limit).collect())
+        })
+        .await
+    }
+
+    async fn truncate(&self, key: &str, seqno: SeqNo) -> Result<usize, ExternalError> {
+        let path = self.log_path(key);
+        let base_dir = self.base_dir.clone();
+        self.with_lock(key, true, move || {
+            let (versions, _) = Self::read_log(&path)?;
+            if versions.last().map_or(true, |data| data.seqno < seqno) {
+                return Err(ExternalError::from(anyhow!(
+                    "upper bound too high for truncate: {:?}",
+                    seqno
+                )));
+            }
+
+            let deleted = versions.partition_point(|x| x.seqno < seqno);
+            if deleted == 0 {
+                return Ok(0);
+            }
+
+            // To atomically truncate, write the remaining versions to a temp
+            // file and rename it into place.
+            let mut buf = Vec::new();
+            for version in &versions[deleted..] {
+                Self::encode_version(&mut buf, version);
+            }
+            let tmp_path = path.with_extension("tmp");
+            let mut file = std::fs::File::create(&tmp_path)?;
+            file.write_all(&buf)?;
+            file.sync_all()?;
+            std::fs::rename(&tmp_path, &path)?;
+            std::fs::File::open(&base_dir)?.sync_all()?;
+
+            Ok(deleted)
+        })
+        .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::location::tests::blob_impl_test;
+    use futures_util::TryStreamExt;
+
+    use crate::location::tests::{blob_impl_test, consensus_impl_test};
 
     use super::*;
 
@@ -296,4 +648,94 @@ mod tests {
 
         Ok(())
     }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `flock`
+    async fn file_consensus() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let base_dir = temp_dir.path().to_owned();
+        consensus_impl_test(|| FileConsensus::open(base_dir.clone().into())).await?;
+
+        // A partial write at the end of the log is ignored, and then
+        // overwritten by the next successful compare_and_set.
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let consensus = FileConsensus::open(temp_dir.path().into()).await?;
+        let v1 = VersionedData {
+            seqno: SeqNo(1),
+            data: Bytes::from("abc"),
+        };
+        let v2 = VersionedData {
+            seqno: SeqNo(2),
+            data: Bytes::from("def"),
+        };
+        assert_eq!(
+            consensus.compare_and_set("k", None, v1.clone()).await,
+            Ok(CaSResult::Committed)
+        );
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(consensus.log_path("k"))?;
+        file.write_all(&[0, 0, 0, 0, 0, 0, 0, 2, 0, 0])?;
+        drop(file);
+
+        assert_eq!(consensus.head("k").await, Ok(Some(v1.clone())));
+        assert_eq!(
+            consensus
+                .compare_and_set("k", Some(SeqNo(1)), v2.clone())
+                .await,
+            Ok(CaSResult::Committed)
+        );
+
+        // The data is durable across reopening the directory.
+        let consensus = FileConsensus::open(temp_dir.path().into()).await?;
+        assert_eq!(
+            consensus.scan("k", SeqNo(0), 10).await,
+            Ok(vec![v1, v2.clone()])
+        );
+        assert_eq!(consensus.truncate("k", SeqNo(2)).await, Ok(1));
+        assert_eq!(
+            consensus.scan("k", SeqNo(0), 10).await,
+            Ok(vec![v2.clone()])
+        );
+
+        // A version without its trailing header is a partial write too, even
+        // though the data it announces is all there.
+        let v3 = VersionedData {
+            seqno: SeqNo(3),
+            data: Bytes::from("ghi"),
+        };
+        let mut buf = Vec::new();
+        FileConsensus::encode_version(&mut buf, &v3);
+        buf.truncate(buf.len() - FileConsensus::HEADER_LEN);
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(consensus.log_path("k"))?;
+        file.write_all(&buf)?;
+        drop(file);
+        assert_eq!(consensus.head("k").await, Ok(Some(v2.clone())));
+        assert_eq!(consensus.scan("k", SeqNo(0), 10).await, Ok(vec![v2]));
+
+        // Keys are locked independently, so holding the lock of one key
+        // doesn't block writes to another.
+        let lock_file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(consensus.lock_path("k"))?;
+        let lock = Flock::lock(lock_file, FlockArg::LockExclusive)
+            .map_err(|(_, errno)| ExternalError::from(anyhow!("locking: {errno}")))?;
+        assert_eq!(
+            consensus.compare_and_set("other", None, v1.clone()).await,
+            Ok(CaSResult::Committed)
+        );
+        assert_eq!(consensus.head("other").await, Ok(Some(v1)));
+        drop(lock);
+
+        // Lock files are not mistaken for keys.
+        let mut keys: Vec<_> = consensus.list_keys().try_collect().await?;
+        keys.sort();
+        assert_eq!(keys, vec!["k".to_owned(), "other".to_owned()]);
+
+        Ok(())
+    }
 }
@@ -439,6 +439,10 @@ pub enum CopyFormatParams<'a> {
     Csv(CopyCsvFormatParams<'a>),
     Binary,
     Parquet,
+    /// Newline-delimited JSON objects, one record per line.
+    Json,
+    /// Avro object container files.
+    Avro,
 }
 
 impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
@@ -450,6 +454,8 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
                 Self::Csv(f) => Kind::Csv(f.into_proto()),
                 Self::Binary => Kind::Binary(()),
                 Self::Parquet => Kind::Parquet(ProtoCopyParquetFormatParams::default()),
+                Self::Json => Kind::Json(ProtoCopyJsonFormatParams::default()),
+                Self::Avro => Kind::Avro(ProtoCopyAvroFormatParams::default()),
             }),
         }
     }
@@ -461,6 +467,8 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
             Some(Kind::Csv(f)) => Ok(Self::Csv(f.into_rust()?)),
             Some(Kind::Binary(())) => Ok(Self::Binary),
             Some(Kind::Parquet(ProtoCopyParquetFormatParams {})) => Ok(Self::Parquet),
+            Some(Kind::Json(ProtoCopyJsonFormatParams {})) => Ok(Self::Json),
+            Some(Kind::Avro(ProtoCopyAvroFormatParams {})) => Ok(Self::Avro),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoCopyFormatParams::kind",
             )),
@@ -488,6 +496,8 @@ impl CopyFormatParams<'static> {
             &CopyFormatParams::Csv(_) => "csv",
             &CopyFormatParams::Binary => "bin",
             &CopyFormatParams::Parquet => "parquet",
+            &CopyFormatParams::Json => "json",
+            &CopyFormatParams::Avro => "avro",
         }
     }
 
@@ -497,6 +507,8 @@ impl CopyFormatParams<'static> {
             CopyFormatParams::Csv(params) => params.header,
             CopyFormatParams::Binary => false,
             CopyFormatParams::Parquet => false,
+            CopyFormatParams::Json => false,
+            CopyFormatParams::Avro => false,
         }
     }
 }
@@ -518,6 +530,8 @@ pub fn decode_copy_format<'a>(
             // TODO(cf2): Support Parquet over STDIN.
             Err(io::Error::new(io::ErrorKind::Unsupported, "parquet format"))
         }
+        CopyFormatParams::Json => Err(io::Error::new(io::ErrorKind::Unsupported, "json format")),
+        CopyFormatParams::Avro => Err(io::Error::new(io::ErrorKind::Unsupported, "avro format")),
     }
 }
 
@@ -536,6 +550,8 @@ pub fn encode_copy_format<'a>(
             // TODO(cf2): Support Parquet over STDIN.
             Err(io::Error::new(io::ErrorKind::Unsupported, "parquet format"))
         }
+        CopyFormatParams::Json => Err(io::Error::new(io::ErrorKind::Unsupported, "json format")),
+        CopyFormatParams::Avro => Err(io::Error::new(io::ErrorKind::Unsupported, "avro format")),
     }
 }
 
@@ -565,6 +581,8 @@ pub fn encode_copy_format_header<'a>(
             // TODO(cf2): Support Parquet over STDIN.
             Err(io::Error::new(io::ErrorKind::Unsupported, "parquet format"))
         }
+        CopyFormatParams::Json => Err(io::Error::new(io::ErrorKind::Unsupported, "json format")),
+        CopyFormatParams::Avro => Err(io::Error::new(io::ErrorKind::Unsupported, "avro format")),
     }
 }
 
@@ -777,3 +777,38 @@ pub const VIEW_MZ_WALLCLOCK_GLOBAL_LAG_OID: u32 = 17054;
 pub const SOURCE_MZ_WALLCLOCK_GLOBAL_LAG_HISTOGRAM_RAW_OID: u32 = 17055;
 pub const VIEW_MZ_WALLCLOCK_GLOBAL_LAG_HISTOGRAM_OID: u32 = 17056;
 pub const TABLE_MZ_SQL_SERVER_SOURCE_TABLES_OID: u32 = 17057;
+pub const VIEW_MZ_WORKLOAD_CLASSES_OID: u32 = 17058;
+pub const FUNC_APPROX_COUNT_DISTINCT_OID: u32 = 17059;
+pub const FUNC_APPROX_PERCENTILE_OID: u32 = 17060;
+pub const FUNC_APPROX_PERCENTILE_ACCUMULATE_OID: u32 = 17061;
+pub const FUNC_APPROX_PERCENTILE_COMBINE_OID: u32 = 17062;
+pub const FUNC_APPROX_PERCENTILE_ESTIMATE_OID: u32 = 17063;
+pub const FUNC_HLL_ACCUMULATE_OID: u32 = 17064;
+pub const FUNC_HLL_COMBINE_OID: u32 = 17065;
+pub const FUNC_HLL_ESTIMATE_OID: u32 = 17066;
+pub const FUNC_JSONB_PATH_EXISTS_OID: u32 = 17067;
+pub const FUNC_JSONB_PATH_EXISTS_VARS_OID: u32 = 17068;
+pub const FUNC_JSONB_PATH_EXISTS_VARS_SILENT_OID: u32 = 17069;
+pub const FUNC_JSONB_PATH_MATCH_OID: u32 = 17070;
+pub const FUNC_JSONB_PATH_MATCH_VARS_OID: u32 = 17071;
+pub const FUNC_JSONB_PATH_MATCH_VARS_SILENT_OID: u32 = 17072;
+pub const FUNC_JSONB_PATH_QUERY_OID: u32 = 17073;
+pub const FUNC_JSONB_PATH_QUERY_VARS_OID: u32 = 17074;
+pub const FUNC_JSONB_PATH_QUERY_VARS_SILENT_OID: u32 = 17075;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_OID: u32 = 17076;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_VARS_OID: u32 = 17077;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_VARS_SILENT_OID: u32 = 17078;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_OID: u32 = 17079;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_VARS_OID: u32 = 17080;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_VARS_SILENT_OID: u32 = 17081;
+pub const OP_JSONB_PATH_EXISTS_OID: u32 = 17082;
+pub const OP_JSONB_PATH_MATCH_OID: u32 = 17083;
+pub const FUNC_JSONB_SET_OID: u32 = 17084;
+pub const FUNC_JSONB_INSERT_OID: u32 = 17085;
+pub const FUNC_JSONB_SET_LAX_OID: u32 = 17086;
+pub const FUNC_JSONB_SET_LAX_CREATE_OID: u32 = 17087;
+pub const FUNC_JSONB_SET_LAX_CREATE_TREATMENT_OID: u32 = 17088;
+pub const TYPE_HLL_SKETCH_OID: u32 = 17089;
+pub const TYPE_HLL_SKETCH_ARRAY_OID: u32 = 17090;
+pub const TYPE_QUANTILE_SKETCH_OID: u32 = 17091;
+pub const TYPE_QUANTILE_SKETCH_ARRAY_OID: u32 = 17092;
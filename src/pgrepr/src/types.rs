@@ -163,6 +163,10 @@ pub enum Type {
     /// A list of privileges granted to a user that uses [`mz_repr::adt::system::Oid`]s for role
     /// references. This type is used primarily for compatibility with PostgreSQL.
     AclItem,
+    /// A HyperLogLog sketch. This does not exist in PostgreSQL.
+    HllSketch,
+    /// A quantile sketch. This does not exist in PostgreSQL.
+    QuantileSketch,
 }
 
 /// An unpacked [`typmod`](Type::typmod) for a [`Type`].
@@ -528,6 +532,46 @@ pub static MZ_ACL_ITEM_ARRAY: LazyLock<postgres_types::Type> = LazyLock::new(||
     )
 });
 
+/// An anonymous [`Type::HllSketch`], akin to [`postgres_types::Type::BYTEA`].
+pub static HLL_SKETCH: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "hll_sketch".to_owned(),
+        oid::TYPE_HLL_SKETCH_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
+/// An anonymous [`Type::Array`], akin to [`postgres_types::Type::BYTEA_ARRAY`].
+pub static HLL_SKETCH_ARRAY: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "_hll_sketch".to_owned(),
+        oid::TYPE_HLL_SKETCH_ARRAY_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
+/// An anonymous [`Type::QuantileSketch`], akin to [`postgres_types::Type::BYTEA`].
+pub static QUANTILE_SKETCH: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "quantile_sketch".to_owned(),
+        oid::TYPE_QUANTILE_SKETCH_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
+/// An anonymous [`Type::Array`], akin to [`postgres_types::Type::BYTEA_ARRAY`].
+pub static QUANTILE_SKETCH_ARRAY: LazyLock<postgres_types::Type> = LazyLock::new(|| {
+    postgres_types::Type::new(
+        "_quantile_sketch".to_owned(),
+        oid::TYPE_QUANTILE_SKETCH_ARRAY_OID,
+        postgres_types::Kind::Pseudo,
+        MZ_CATALOG_SCHEMA.to_owned(),
+    )
+});
+
 impl Type {
     /// Returns the type corresponding to the provided OID, if the OID is known.
     pub fn from_oid(oid: u32) -> Result<Type, TypeFromOidError> {
@@ -746,6 +790,8 @@ impl Type {
                     _ => unreachable!(),
                 },
                 Type::MzAclItem => &MZ_ACL_ITEM_ARRAY,
+                Type::HllSketch => &HLL_SKETCH_ARRAY,
+                Type::QuantileSketch => &QUANTILE_SKETCH_ARRAY,
             },
             Type::Bool => &postgres_types::Type::BOOL,
             Type::Bytea => &postgres_types::Type::BYTEA,
@@ -791,6 +837,8 @@ impl Type {
                 t => unreachable!("{t:?} is not a range element type"),
             },
             Type::MzAclItem => &MZ_ACL_ITEM,
+            Type::HllSketch => &HLL_SKETCH,
+            Type::QuantileSketch => &QUANTILE_SKETCH,
         }
     }
 
@@ -846,6 +894,8 @@ impl Type {
                 oid::TYPE_UINT8_ARRAY_OID => "uint8[]",
                 oid::TYPE_MZ_TIMESTAMP_ARRAY_OID => "mz_timestamp[]",
                 oid::TYPE_MZ_ACL_ITEM_ARRAY_OID => "mz_aclitem[]",
+                oid::TYPE_HLL_SKETCH_ARRAY_OID => "hll_sketch[]",
+                oid::TYPE_QUANTILE_SKETCH_ARRAY_OID => "quantile_sketch[]",
                 _ => other.name(),
             },
         }
@@ -922,7 +972,9 @@ impl Type {
             | Type::MzTimestamp
             | Type::VarChar { max_length: None }
             | Type::Range { .. }
-            | Type::MzAclItem => None,
+            | Type::MzAclItem
+            | Type::HllSketch
+            | Type::QuantileSketch => None,
         }
     }
 
@@ -972,6 +1024,8 @@ impl Type {
             Type::Range { .. } => -1,
             Type::MzAclItem => MzAclItem::binary_size().try_into().expect("must fit"),
             Type::AclItem => AclItem::binary_size().try_into().expect("must fit"),
+            Type::HllSketch => -1,
+            Type::QuantileSketch => -1,
         }
     }
 
@@ -1104,6 +1158,8 @@ impl TryFrom<&Type> for ScalarType {
                 element_type: Box::new(TryFrom::try_from(&**element_type)?),
             }),
             Type::MzAclItem => Ok(ScalarType::MzAclItem),
+            Type::HllSketch => Ok(ScalarType::HllSketch),
+            Type::QuantileSketch => Ok(ScalarType::QuantileSketch),
         }
     }
 }
@@ -1272,6 +1328,8 @@ impl From<&ScalarType> for Type {
                 element_type: Box::new(From::from(&**element_type)),
             },
             ScalarType::MzAclItem => Type::MzAclItem,
+            ScalarType::HllSketch => Type::HllSketch,
+            ScalarType::QuantileSketch => Type::QuantileSketch,
         }
     }
 }
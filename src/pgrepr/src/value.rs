@@ -22,6 +22,7 @@ use mz_repr::adt::jsonb::JsonbRef;
 use mz_repr::adt::mz_acl_item::{AclItem, MzAclItem};
 use mz_repr::adt::pg_legacy_name::NAME_MAX_BYTES;
 use mz_repr::adt::range::{Range, RangeInner};
+use mz_repr::adt::sketch::{HyperLogLog, QuantileSketch};
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::strconv::{self, Nestable};
 use mz_repr::{Datum, RelationType, RowArena, RowPacker, RowRef, ScalarType};
@@ -153,6 +154,9 @@ impl Value {
             }
             (Datum::Interval(iv), ScalarType::Interval) => Some(Value::Interval(Interval(iv))),
             (Datum::Bytes(b), ScalarType::Bytes) => Some(Value::Bytea(b.to_vec())),
+            (Datum::Bytes(b), ScalarType::HllSketch | ScalarType::QuantileSketch) => {
+                Some(Value::Bytea(b.to_vec()))
+            }
             (Datum::String(s), ScalarType::String) => Some(Value::Text(s.to_owned())),
             (Datum::String(s), ScalarType::VarChar { .. }) => Some(Value::VarChar(s.to_owned())),
             (Datum::String(s), ScalarType::Char { length }) => {
@@ -574,6 +578,8 @@ impl Value {
             ScalarType::TimestampTz { .. } => true,
             ScalarType::Interval => true,
             ScalarType::Bytes => true,
+            ScalarType::HllSketch => true,
+            ScalarType::QuantileSketch => true,
             ScalarType::String => true,
             ScalarType::VarChar { .. } => true,
             ScalarType::Char { .. } => true,
@@ -675,6 +681,9 @@ impl Value {
             })?),
             Type::MzAclItem => Value::MzAclItem(strconv::parse_mz_acl_item(s)?),
             Type::AclItem => Value::AclItem(strconv::parse_acl_item(s)?),
+            Type::HllSketch | Type::QuantileSketch => {
+                Value::Bytea(check_sketch(ty, strconv::parse_bytes(s)?)?)
+            }
         })
     }
 
@@ -789,6 +798,9 @@ impl Value {
             }
             Type::MzAclItem => packer.push(Datum::MzAclItem(strconv::parse_mz_acl_item(s)?)),
             Type::AclItem => packer.push(Datum::AclItem(strconv::parse_acl_item(s)?)),
+            Type::HllSketch | Type::QuantileSketch => {
+                packer.push(Datum::Bytes(&check_sketch(ty, strconv::parse_bytes(s)?)?))
+            }
         })
     }
 
@@ -859,8 +871,26 @@ impl Value {
                 Ok(Value::MzAclItem(mz_acl_item))
             }
             Type::AclItem => Err("aclitem has no binary encoding".into()),
+            Type::HllSketch | Type::QuantileSketch => Ok(Value::Bytea(check_sketch(
+                ty,
+                Vec::<u8>::from_sql(&PgType::BYTEA, raw)?,
+            )?)),
+        }
+    }
+}
+
+/// Validates that `bytes` is an encoded sketch of type `ty`.
+fn check_sketch(ty: &Type, bytes: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Sync + Send>> {
+    match ty {
+        Type::HllSketch => {
+            HyperLogLog::decode(&bytes)?;
+        }
+        Type::QuantileSketch => {
+            QuantileSketch::decode(&bytes)?;
         }
+        _ => unreachable!("{ty:?} is not a sketch type"),
     }
+    Ok(bytes)
 }
 
 fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result<(), io::Error> {
@@ -2060,8 +2060,8 @@ where
                 CopyFormatParams::Csv(CopyCsvFormatParams::default()),
                 Format::Text,
             ),
-            CopyFormat::Parquet => {
-                let text = "Parquet format is not supported".to_string();
+            CopyFormat::Parquet | CopyFormat::Json | CopyFormat::Avro => {
+                let text = format!("{format:?} format is not supported");
                 return self
                     .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text.clone()))
                     .await
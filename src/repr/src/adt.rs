@@ -23,11 +23,13 @@ pub mod date;
 pub mod datetime;
 pub mod interval;
 pub mod jsonb;
+pub mod jsonpath;
 pub mod mz_acl_item;
 pub mod numeric;
 pub mod pg_legacy_name;
 pub mod range;
 pub mod regex;
+pub mod sketch;
 pub mod system;
 pub mod timestamp;
 pub mod varchar;
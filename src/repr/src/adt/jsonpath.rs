@@ -0,0 +1,1784 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The SQL/JSON path language.
+//!
+//! A [`JsonPath`] is parsed from its textual representation, e.g.
+//! `strict $.events[*] ? (@.kind == "click").ts.datetime()`, and evaluated
+//! directly against the [`Datum`]s that make up a `jsonb` value. There is no
+//! separate `jsonpath` datum; paths are passed around as strings.
+//!
+//! The semantics follow PostgreSQL's implementation:
+//!
+//!   * In `lax` mode, which is the default, arrays are automatically unwrapped
+//!     and non-arrays automatically wrapped where the path requires it, and
+//!     structural errors like missing keys produce empty results.
+//!   * In `strict` mode, structural errors are raised.
+//!   * Predicates have three-valued logic. Errors while evaluating the operands
+//!     of a predicate make the predicate unknown rather than failing the path.
+//!
+//! Items produced by `.datetime()` are `date`, `time`, `timestamp` or
+//! `timestamptz` datums. They can be compared with each other, and are
+//! converted to JSON strings when they are returned from a query.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use dec::Rounding;
+use regex::{Regex, RegexBuilder};
+use thiserror::Error;
+
+use crate::adt::date::Date;
+use crate::adt::numeric::{self, Numeric};
+use crate::adt::timestamp::CheckedTimestamp;
+use crate::{Datum, RowArena, strconv};
+
+/// An error parsing or evaluating a [`JsonPath`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum JsonPathError {
+    /// The path is not valid.
+    #[error("{0}")]
+    Syntax(String),
+    /// The variables passed to the path are not a JSON object.
+    #[error("\"vars\" argument is not an object")]
+    InvalidVars,
+    /// The path refers to a variable that was not passed to it.
+    #[error("could not find jsonpath variable \"{0}\"")]
+    UnknownVariable(String),
+    /// Evaluating the path failed.
+    #[error("{0}")]
+    Eval(String),
+}
+
+impl JsonPathError {
+    /// Reports whether the error is suppressed when the path is evaluated in
+    /// silent mode.
+    pub fn is_silenceable(&self) -> bool {
+        matches!(self, JsonPathError::Eval(_))
+    }
+}
+
+fn eval_error(msg: impl Into<String>) -> JsonPathError {
+    JsonPathError::Eval(msg.into())
+}
+
+/// A parsed SQL/JSON path.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    strict: bool,
+    expr: Expr,
+}
+
+impl JsonPath {
+    /// Returns the items that the path selects from `target`.
+    ///
+    /// `vars` must be a JSON object whose fields are the values of the
+    /// variables referenced in the path.
+    pub fn query<'a>(
+        &self,
+        target: Datum<'a>,
+        vars: Datum<'a>,
+        temp_storage: &'a RowArena,
+    ) -> Result<Vec<Datum<'a>>, JsonPathError> {
+        let evaluator = Evaluator::new(self.strict, target, vars, temp_storage)?;
+        let mut items = vec![];
+        evaluator.eval(&self.expr, target, None, &mut items)?;
+        Ok(items
+            .into_iter()
+            .map(|item| evaluator.to_json(item))
+            .collect())
+    }
+
+    /// Reports whether the path selects any items from `target`.
+    pub fn exists<'a>(
+        &self,
+        target: Datum<'a>,
+        vars: Datum<'a>,
+        temp_storage: &'a RowArena,
+    ) -> Result<bool, JsonPathError> {
+        Ok(!self.query(target, vars, temp_storage)?.is_empty())
+    }
+
+    /// Returns the result of a path that is a predicate, where `None` means
+    /// that the predicate is unknown.
+    pub fn matches<'a>(
+        &self,
+        target: Datum<'a>,
+        vars: Datum<'a>,
+        temp_storage: &'a RowArena,
+    ) -> Result<Option<bool>, JsonPathError> {
+        match self.query(target, vars, temp_storage)?.as_slice() {
+            [Datum::True] => Ok(Some(true)),
+            [Datum::False] => Ok(Some(false)),
+            [Datum::JsonNull] => Ok(None),
+            _ => Err(eval_error("single boolean result is expected")),
+        }
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = JsonPathError;
+
+    fn from_str(s: &str) -> Result<JsonPath, JsonPathError> {
+        let mut parser = Parser {
+            tokens: lex(s)?,
+            pos: 0,
+            filter_depth: 0,
+            subscript_depth: 0,
+        };
+        let strict = if parser.eat_ident("strict") {
+            true
+        } else {
+            parser.eat_ident("lax");
+            false
+        };
+        let expr = parser.parse_expr()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(parser.error_at(parser.pos));
+        }
+        Ok(JsonPath { strict, expr })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    /// `$`
+    Root,
+    /// `@`
+    Current,
+    /// `last`
+    Last,
+    /// `$name`
+    Variable(String),
+    Null,
+    Bool(bool),
+    Numeric(Numeric),
+    String(String),
+    Accessor(Box<Expr>, Accessor),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    IsUnknown(Box<Expr>),
+    Exists(Box<Expr>),
+    StartsWith(Box<Expr>, Box<Expr>),
+    LikeRegex(Box<Expr>, Regex),
+}
+
+impl Expr {
+    fn is_predicate(&self) -> bool {
+        matches!(
+            self,
+            Expr::Compare(..)
+                | Expr::And(..)
+                | Expr::Or(..)
+                | Expr::Not(_)
+                | Expr::IsUnknown(_)
+                | Expr::Exists(_)
+                | Expr::StartsWith(..)
+                | Expr::LikeRegex(..)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Accessor {
+    /// `.key` or `."key"`
+    Member(String),
+    /// `.*`
+    MemberWildcard,
+    /// `[*]`
+    ElementWildcard,
+    /// `[i, j to k]`
+    Elements(Vec<(Expr, Option<Expr>)>),
+    /// `.**`, optionally restricted to the levels `{first to last}`. A missing
+    /// `last` level is unbounded.
+    Descendant { first: u32, last: Option<u32> },
+    /// `? (predicate)`
+    Filter(Box<Expr>),
+    /// `.method()`
+    Method(Method),
+}
+
+#[derive(Debug, Clone)]
+enum Method {
+    Type,
+    Size,
+    Double,
+    Ceiling,
+    Floor,
+    Abs,
+    Datetime(Option<String>),
+}
+
+impl Method {
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Type => "type",
+            Method::Size => "size",
+            Method::Double => "double",
+            Method::Ceiling => "ceiling",
+            Method::Floor => "floor",
+            Method::Abs => "abs",
+            Method::Datetime(_) => "datetime",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UnaryOp {
+    Plus,
+    Minus,
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnaryOp::Plus => f.write_str("+"),
+            UnaryOp::Minus => f.write_str("-"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryOp::Add => f.write_str("+"),
+            BinaryOp::Sub => f.write_str("-"),
+            BinaryOp::Mul => f.write_str("*"),
+            BinaryOp::Div => f.write_str("/"),
+            BinaryOp::Mod => f.write_str("%"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+impl CompareOp {
+    fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => ordering == Ordering::Equal,
+            CompareOp::NotEq => ordering != Ordering::Equal,
+            CompareOp::Lt => ordering == Ordering::Less,
+            CompareOp::Lte => ordering != Ordering::Greater,
+            CompareOp::Gt => ordering == Ordering::Greater,
+            CompareOp::Gte => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// An unquoted key or keyword.
+    Ident(String),
+    String(String),
+    Number(String),
+    Variable(String),
+    Root,
+    Current,
+    Punct(&'static str),
+}
+
+/// Punctuation, ordered so that longer tokens are matched first.
+const PUNCTUATION: &[&str] = &[
+    "**", "==", "!=", "<>", "<=", ">=", "&&", "||", ".", "[", "]", "(", ")", "{", "}", ",", "?",
+    "*", "/", "%", "+", "-", "<", ">", "!",
+];
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn ident_len(s: &str) -> usize {
+    match s.chars().next() {
+        Some(c) if is_ident_start(c) => s.find(|c| !is_ident_char(c)).unwrap_or(s.len()),
+        _ => 0,
+    }
+}
+
+fn digits_len(s: &str) -> usize {
+    s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len())
+}
+
+fn number_len(s: &str) -> usize {
+    let mut len = digits_len(s);
+    let rest = &s[len..];
+    if rest.starts_with('.') && digits_len(&rest[1..]) > 0 {
+        len += 1 + digits_len(&rest[1..]);
+    }
+    let rest = &s[len..];
+    if rest.starts_with(['e', 'E']) {
+        let sign = usize::from(rest[1..].starts_with(['+', '-']));
+        let exponent = digits_len(&rest[1 + sign..]);
+        if exponent > 0 {
+            len += 1 + sign + exponent;
+        }
+    }
+    len
+}
+
+/// Lexes the double-quoted string at the start of `s`, returning its value and
+/// its length in `s`.
+fn lex_string(s: &str) -> Result<(String, usize), JsonPathError> {
+    let mut value = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, i + 1)),
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, 'b')) => '\u{8}',
+                    Some((_, 'f')) => '\u{c}',
+                    Some((_, 'n')) => '\n',
+                    Some((_, 'r')) => '\r',
+                    Some((_, 't')) => '\t',
+                    Some((_, 'v')) => '\u{b}',
+                    Some((_, c @ ('u' | 'x'))) => {
+                        let digits = if c == 'x' { 2 } else { 4 };
+                        let hex: String = (&mut chars).take(digits).map(|(_, c)| c).collect();
+                        u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .filter(|_| hex.len() == digits)
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| {
+                                JsonPathError::Syntax(format!(
+                                    "invalid escape sequence \"\\{c}{hex}\" in jsonpath input"
+                                ))
+                            })?
+                    }
+                    Some((_, c)) => c,
+                    None => break,
+                };
+                value.push(escaped);
+            }
+            c => value.push(c),
+        }
+    }
+    Err(JsonPathError::Syntax(
+        "unterminated quoted string in jsonpath input".into(),
+    ))
+}
+
+/// Splits `input` into tokens, each paired with its source text.
+fn lex(input: &str) -> Result<Vec<(Token, &str)>, JsonPathError> {
+    let mut tokens = vec![];
+    let mut pos = 0;
+    while let Some(c) = input[pos..].chars().next() {
+        let rest = &input[pos..];
+        if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        }
+        let (token, len) = if c == '"' {
+            let (s, len) = lex_string(rest)?;
+            (Token::String(s), len)
+        } else if c == '$' {
+            let name = &rest[1..];
+            if name.starts_with('"') {
+                let (s, len) = lex_string(name)?;
+                (Token::Variable(s), 1 + len)
+            } else {
+                match ident_len(name) {
+                    0 => (Token::Root, 1),
+                    len => (Token::Variable(name[..len].into()), 1 + len),
+                }
+            }
+        } else if c == '@' {
+            (Token::Current, 1)
+        } else if c.is_ascii_digit() {
+            let len = number_len(rest);
+            if let Some(junk) = rest[len..].chars().next().filter(|c| is_ident_char(*c)) {
+                return Err(JsonPathError::Syntax(format!(
+                    "trailing junk after numeric literal at or near \"{}\" of jsonpath input",
+                    &rest[..len + junk.len_utf8()]
+                )));
+            }
+            (Token::Number(rest[..len].into()), len)
+        } else if is_ident_start(c) {
+            let len = ident_len(rest);
+            (Token::Ident(rest[..len].into()), len)
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) {
+            (Token::Punct(*punct), punct.len())
+        } else {
+            return Err(JsonPathError::Syntax(format!(
+                "syntax error at or near \"{c}\" of jsonpath input"
+            )));
+        };
+        tokens.push((token, &rest[..len]));
+        pos += len;
+    }
+    Ok(tokens)
+}
+
+struct Parser<'s> {
+    tokens: Vec<(Token, &'s str)>,
+    pos: usize,
+    /// The number of filters that enclose the current position, which
+    /// determines whether `@` is allowed.
+    filter_depth: usize,
+    /// The number of array subscripts that enclose the current position, which
+    /// determines whether `last` is allowed.
+    subscript_depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn error_at(&self, index: usize) -> JsonPathError {
+        match self.tokens.get(index) {
+            Some((_, text)) => JsonPathError::Syntax(format!(
+                "syntax error at or near \"{text}\" of jsonpath input"
+            )),
+            None => JsonPathError::Syntax("syntax error at end of jsonpath input".into()),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), JsonPathError> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(self.error_at(self.pos))
+        }
+    }
+
+    fn eat_ident(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(i)) if i == keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_ident(&mut self, keyword: &str) -> Result<(), JsonPathError> {
+        if self.eat_ident(keyword) {
+            Ok(())
+        } else {
+            Err(self.error_at(self.pos))
+        }
+    }
+
+    fn expect_string(&mut self) -> Result<String, JsonPathError> {
+        let index = self.pos;
+        match self.next() {
+            Some(Token::String(s)) => Ok(s),
+            _ => Err(self.error_at(index)),
+        }
+    }
+
+    /// Checks that `expr`, which was parsed before the token at `index`, is a
+    /// predicate.
+    fn predicate(&self, expr: Expr, index: usize) -> Result<Box<Expr>, JsonPathError> {
+        if expr.is_predicate() {
+            Ok(Box::new(expr))
+        } else {
+            Err(self.error_at(index))
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, JsonPathError> {
+        let mut left = self.parse_and()?;
+        loop {
+            let index = self.pos;
+            if !self.eat_punct("||") {
+                return Ok(left);
+            }
+            let right = self.parse_and()?;
+            left = Expr::Or(self.predicate(left, index)?, self.predicate(right, index)?);
+        }
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, JsonPathError> {
+        let mut left = self.parse_not()?;
+        loop {
+            let index = self.pos;
+            if !self.eat_punct("&&") {
+                return Ok(left);
+            }
+            let right = self.parse_not()?;
+            left = Expr::And(self.predicate(left, index)?, self.predicate(right, index)?);
+        }
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, JsonPathError> {
+        let index = self.pos;
+        if self.eat_punct("!") {
+            let operand = self.parse_not()?;
+            Ok(Expr::Not(self.predicate(operand, index)?))
+        } else {
+            self.parse_comparison()
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, JsonPathError> {
+        let left = self.parse_additive()?;
+        let index = self.pos;
+        let op = match self.peek() {
+            Some(Token::Punct("==")) => Some(CompareOp::Eq),
+            Some(Token::Punct("!=" | "<>")) => Some(CompareOp::NotEq),
+            Some(Token::Punct("<")) => Some(CompareOp::Lt),
+            Some(Token::Punct("<=")) => Some(CompareOp::Lte),
+            Some(Token::Punct(">")) => Some(CompareOp::Gt),
+            Some(Token::Punct(">=")) => Some(CompareOp::Gte),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.pos += 1;
+            let right = self.parse_additive()?;
+            Ok(Expr::Compare(op, Box::new(left), Box::new(right)))
+        } else if self.eat_ident("starts") {
+            self.expect_ident("with")?;
+            let right = self.parse_additive()?;
+            Ok(Expr::StartsWith(Box::new(left), Box::new(right)))
+        } else if self.eat_ident("like_regex") {
+            let pattern = self.expect_string()?;
+            let flags = if self.eat_ident("flag") {
+                self.expect_string()?
+            } else {
+                String::new()
+            };
+            Ok(Expr::LikeRegex(
+                Box::new(left),
+                build_regex(&pattern, &flags)?,
+            ))
+        } else if self.eat_ident("is") {
+            self.expect_ident("unknown")?;
+            Ok(Expr::IsUnknown(self.predicate(left, index)?))
+        } else {
+            Ok(left)
+        }
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, JsonPathError> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = if self.eat_punct("+") {
+                BinaryOp::Add
+            } else if self.eat_punct("-") {
+                BinaryOp::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.parse_multiplicative()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, JsonPathError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = if self.eat_punct("*") {
+                BinaryOp::Mul
+            } else if self.eat_punct("/") {
+                BinaryOp::Div
+            } else if self.eat_punct("%") {
+                BinaryOp::Mod
+            } else {
+                return Ok(left);
+            };
+            let right = self.parse_unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, JsonPathError> {
+        let op = if self.eat_punct("+") {
+            UnaryOp::Plus
+        } else if self.eat_punct("-") {
+            UnaryOp::Minus
+        } else {
+            return self.parse_accessors();
+        };
+        Ok(Expr::Unary(op, Box::new(self.parse_unary()?)))
+    }
+
+    fn parse_accessors(&mut self) -> Result<Expr, JsonPathError> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let accessor = if self.eat_punct(".") {
+                self.parse_dot_accessor()?
+            } else if self.eat_punct("[") {
+                self.parse_subscripts()?
+            } else if self.eat_punct("?") {
+                self.expect_punct("(")?;
+                let index = self.pos;
+                self.filter_depth += 1;
+                let predicate = self.parse_expr()?;
+                self.filter_depth -= 1;
+                let predicate = self.predicate(predicate, index)?;
+                self.expect_punct(")")?;
+                Accessor::Filter(predicate)
+            } else {
+                return Ok(expr);
+            };
+            expr = Expr::Accessor(Box::new(expr), accessor);
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, JsonPathError> {
+        let index = self.pos;
+        match self.next() {
+            Some(Token::Root) => Ok(Expr::Root),
+            Some(Token::Current) if self.filter_depth == 0 => Err(JsonPathError::Syntax(
+                "@ is not allowed in root expressions".into(),
+            )),
+            Some(Token::Current) => Ok(Expr::Current),
+            Some(Token::Variable(name)) => Ok(Expr::Variable(name)),
+            Some(Token::String(s)) => Ok(Expr::String(s)),
+            Some(Token::Number(n)) => match strconv::parse_numeric(&n) {
+                Ok(n) => Ok(Expr::Numeric(n.0)),
+                Err(_) => Err(self.error_at(index)),
+            },
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Expr::Bool(true)),
+                "false" => Ok(Expr::Bool(false)),
+                "null" => Ok(Expr::Null),
+                "last" if self.subscript_depth == 0 => Err(JsonPathError::Syntax(
+                    "LAST is allowed only in array subscripts".into(),
+                )),
+                "last" => Ok(Expr::Last),
+                "exists" => {
+                    self.expect_punct("(")?;
+                    let expr = self.parse_expr()?;
+                    self.expect_punct(")")?;
+                    Ok(Expr::Exists(Box::new(expr)))
+                }
+                _ => Err(self.error_at(index)),
+            },
+            Some(Token::Punct("(")) => {
+                let expr = self.parse_expr()?;
+                self.expect_punct(")")?;
+                Ok(expr)
+            }
+            _ => Err(self.error_at(index)),
+        }
+    }
+
+    fn parse_dot_accessor(&mut self) -> Result<Accessor, JsonPathError> {
+        let index = self.pos;
+        match self.next() {
+            Some(Token::Punct("*")) => Ok(Accessor::MemberWildcard),
+            Some(Token::Punct("**")) => {
+                let (first, last) = if self.eat_punct("{") {
+                    let first = match self.parse_level()? {
+                        Some(first) => first,
+                        None => return Err(self.error_at(self.pos - 1)),
+                    };
+                    let last = if self.eat_ident("to") {
+                        self.parse_level()?
+                    } else {
+                        Some(first)
+                    };
+                    self.expect_punct("}")?;
+                    (first, last)
+                } else {
+                    (0, None)
+                };
+                Ok(Accessor::Descendant { first, last })
+            }
+            Some(Token::String(key)) => Ok(Accessor::Member(key)),
+            Some(Token::Ident(name)) if self.eat_punct("(") => {
+                let method = match name.as_str() {
+                    "type" => Method::Type,
+                    "size" => Method::Size,
+                    "double" => Method::Double,
+                    "ceiling" => Method::Ceiling,
+                    "floor" => Method::Floor,
+                    "abs" => Method::Abs,
+                    "datetime" if matches!(self.peek(), Some(Token::String(_))) => {
+                        Method::Datetime(Some(self.expect_string()?))
+                    }
+                    "datetime" => Method::Datetime(None),
+                    _ => {
+                        return Err(JsonPathError::Syntax(format!(
+                            "unsupported jsonpath item method .{name}()"
+                        )));
+                    }
+                };
+                self.expect_punct(")")?;
+                Ok(Accessor::Method(method))
+            }
+            Some(Token::Ident(key)) => Ok(Accessor::Member(key)),
+            _ => Err(self.error_at(index)),
+        }
+    }
+
+    /// Parses a level of a `.**{first to last}` accessor, where `None` is the
+    /// unbounded `last` level.
+    fn parse_level(&mut self) -> Result<Option<u32>, JsonPathError> {
+        let index = self.pos;
+        match self.next() {
+            Some(Token::Ident(ident)) if ident == "last" => Ok(None),
+            Some(Token::Number(n)) => n.parse().map(Some).map_err(|_| self.error_at(index)),
+            _ => Err(self.error_at(index)),
+        }
+    }
+
+    fn parse_subscripts(&mut self) -> Result<Accessor, JsonPathError> {
+        if self.eat_punct("*") {
+            self.expect_punct("]")?;
+            return Ok(Accessor::ElementWildcard);
+        }
+        self.subscript_depth += 1;
+        let mut subscripts = vec![];
+        loop {
+            let from = self.parse_additive()?;
+            let to = if self.eat_ident("to") {
+                Some(self.parse_additive()?)
+            } else {
+                None
+            };
+            subscripts.push((from, to));
+            if !self.eat_punct(",") {
+                break;
+            }
+        }
+        self.subscript_depth -= 1;
+        self.expect_punct("]")?;
+        Ok(Accessor::Elements(subscripts))
+    }
+}
+
+fn build_regex(pattern: &str, flags: &str) -> Result<Regex, JsonPathError> {
+    let mut builder_flags = (false, false, false, false);
+    let mut quote = false;
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder_flags.0 = true,
+            's' => builder_flags.1 = true,
+            'm' => builder_flags.2 = true,
+            'x' => builder_flags.3 = true,
+            'q' => quote = true,
+            _ => {
+                return Err(JsonPathError::Syntax(format!(
+                    "unrecognized flag character \"{flag}\" in LIKE_REGEX predicate"
+                )));
+            }
+        }
+    }
+    let pattern = if quote {
+        regex::escape(pattern)
+    } else {
+        pattern.to_owned()
+    };
+    let (case_insensitive, dot_matches_new_line, multi_line, ignore_whitespace) = builder_flags;
+    RegexBuilder::new(&pattern)
+        .case_insensitive(case_insensitive)
+        .dot_matches_new_line(dot_matches_new_line)
+        .multi_line(multi_line)
+        .ignore_whitespace(ignore_whitespace && !quote)
+        .build()
+        .map_err(|e| JsonPathError::Syntax(format!("invalid regular expression: {e}")))
+}
+
+/// The result of a predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ternary {
+    True,
+    False,
+    Unknown,
+}
+
+impl From<bool> for Ternary {
+    fn from(b: bool) -> Ternary {
+        if b { Ternary::True } else { Ternary::False }
+    }
+}
+
+struct Evaluator<'a> {
+    strict: bool,
+    root: Datum<'a>,
+    vars: Datum<'a>,
+    temp_storage: &'a RowArena,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(
+        strict: bool,
+        root: Datum<'a>,
+        vars: Datum<'a>,
+        temp_storage: &'a RowArena,
+    ) -> Result<Self, JsonPathError> {
+        if !matches!(vars, Datum::Map(_)) {
+            return Err(JsonPathError::InvalidVars);
+        }
+        Ok(Evaluator {
+            strict,
+            root,
+            vars,
+            temp_storage,
+        })
+    }
+
+    /// Raises a structural error in strict mode, and ignores it in lax mode.
+    fn structural_error(&self, msg: impl Into<String>) -> Result<(), JsonPathError> {
+        if self.strict {
+            Err(eval_error(msg))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Evaluates `expr`, where `current` is the item that `@` refers to and
+    /// `last` the index of the last element of the innermost subscripted
+    /// array.
+    fn eval(
+        &self,
+        expr: &Expr,
+        current: Datum<'a>,
+        last: Option<i64>,
+        out: &mut Vec<Datum<'a>>,
+    ) -> Result<(), JsonPathError> {
+        match expr {
+            Expr::Root => out.push(self.root),
+            Expr::Current => out.push(current),
+            Expr::Last => match last {
+                Some(last) => out.push(Datum::from(Numeric::from(last))),
+                None => {
+                    return Err(eval_error(
+                        "evaluating jsonpath LAST outside of array subscript",
+                    ));
+                }
+            },
+            Expr::Variable(name) => {
+                let value = self
+                    .vars
+                    .unwrap_map()
+                    .iter()
+                    .find(|(key, _)| *key == name.as_str())
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| JsonPathError::UnknownVariable(name.clone()))?;
+                out.push(value);
+            }
+            Expr::Null => out.push(Datum::JsonNull),
+            Expr::Bool(b) => out.push(Datum::from(*b)),
+            Expr::Numeric(n) => out.push(Datum::from(*n)),
+            Expr::String(s) => out.push(Datum::String(self.temp_storage.push_string(s.clone()))),
+            Expr::Accessor(base, accessor) => {
+                let mut items = vec![];
+                self.eval(base, current, last, &mut items)?;
+                for item in items {
+                    self.apply(accessor, item, current, last, true, out)?;
+                }
+            }
+            Expr::Unary(op, operand) => {
+                for item in self.eval_unwrapped(operand, current, last)? {
+                    let Datum::Numeric(n) = item else {
+                        return Err(eval_error(format!(
+                            "operand of unary jsonpath operator {op} is not a numeric value"
+                        )));
+                    };
+                    let mut n = n.0;
+                    if let UnaryOp::Minus = op {
+                        numeric::cx_datum().neg(&mut n);
+                        numeric::munge_numeric(&mut n).expect("negation cannot increase precision");
+                    }
+                    out.push(Datum::from(n));
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let left = self.eval_unwrapped(left, current, last)?;
+                let right = self.eval_unwrapped(right, current, last)?;
+                let (a, b) = match (left.as_slice(), right.as_slice()) {
+                    ([Datum::Numeric(a)], [Datum::Numeric(b)]) => (a.0, b.0),
+                    ([Datum::Numeric(_)], _) => {
+                        return Err(eval_error(format!(
+                            "right operand of jsonpath operator {op} is not a single numeric value"
+                        )));
+                    }
+                    _ => {
+                        return Err(eval_error(format!(
+                            "left operand of jsonpath operator {op} is not a single numeric value"
+                        )));
+                    }
+                };
+                out.push(Datum::from(arithmetic(*op, a, b)?));
+            }
+            _ => out.push(match self.predicate(expr, current, last)? {
+                Ternary::True => Datum::True,
+                Ternary::False => Datum::False,
+                Ternary::Unknown => Datum::JsonNull,
+            }),
+        }
+        Ok(())
+    }
+
+    /// Evaluates `expr` and, in lax mode, unwraps any arrays in the result.
+    fn eval_unwrapped(
+        &self,
+        expr: &Expr,
+        current: Datum<'a>,
+        last: Option<i64>,
+    ) -> Result<Vec<Datum<'a>>, JsonPathError> {
+        let mut items = vec![];
+        self.eval(expr, current, last, &mut items)?;
+        if self.strict {
+            return Ok(items);
+        }
+        let mut unwrapped = vec![];
+        for item in items {
+            match item {
+                Datum::List(list) => unwrapped.extend(list.iter()),
+                item => unwrapped.push(item),
+            }
+        }
+        Ok(unwrapped)
+    }
+
+    /// Applies `accessor` to `item`. In lax mode, arrays are unwrapped first if
+    /// `unwrap` is set.
+    fn apply(
+        &self,
+        accessor: &Accessor,
+        item: Datum<'a>,
+        current: Datum<'a>,
+        last: Option<i64>,
+        unwrap: bool,
+        out: &mut Vec<Datum<'a>>,
+    ) -> Result<(), JsonPathError> {
+        let unwrap = unwrap && !self.strict;
+        match (accessor, item) {
+            (Accessor::Member(key), Datum::Map(map)) => {
+                match map.iter().find(|(k, _)| *k == key.as_str()) {
+                    Some((_, value)) => out.push(value),
+                    None => self
+                        .structural_error(format!("JSON object does not contain key \"{key}\""))?,
+                }
+                Ok(())
+            }
+            (Accessor::MemberWildcard, Datum::Map(map)) => {
+                out.extend(map.iter().map(|(_, value)| value));
+                Ok(())
+            }
+            (Accessor::Member(_) | Accessor::MemberWildcard, Datum::List(list)) if unwrap => {
+                for element in list.iter() {
+                    self.apply(accessor, element, current, last, false, out)?;
+                }
+                Ok(())
+            }
+            (Accessor::Member(_), _) => {
+                self.structural_error("jsonpath member accessor can only be applied to an object")
+            }
+            (Accessor::MemberWildcard, _) => self.structural_error(
+                "jsonpath wildcard member accessor can only be applied to an object",
+            ),
+            (Accessor::ElementWildcard, Datum::List(list)) => {
+                out.extend(list.iter());
+                Ok(())
+            }
+            (Accessor::ElementWildcard, item) => {
+                if !self.strict {
+                    out.push(item);
+                }
+                self.structural_error(
+                    "jsonpath wildcard array accessor can only be applied to an array",
+                )
+            }
+            (Accessor::Elements(subscripts), item) => {
+                let elements: Vec<_> = match item {
+                    Datum::List(list) => list.iter().collect(),
+                    item if !self.strict => vec![item],
+                    _ => {
+                        return self.structural_error(
+                            "jsonpath array accessor can only be applied to an array",
+                        );
+                    }
+                };
+                let len = i64::try_from(elements.len()).expect("array length fits in i64");
+                for (from, to) in subscripts {
+                    let mut from = self.subscript(from, current, len - 1)?;
+                    let mut to = match to {
+                        Some(to) => self.subscript(to, current, len - 1)?,
+                        None => from,
+                    };
+                    if from < 0 || from > to || to >= len {
+                        self.structural_error("jsonpath array subscript is out of bounds")?;
+                        from = from.max(0);
+                        to = to.min(len - 1);
+                    }
+                    for i in from..=to {
+                        let i = usize::try_from(i).expect("subscript is in bounds");
+                        out.push(elements[i]);
+                    }
+                }
+                Ok(())
+            }
+            (Accessor::Descendant { first, last }, item) => {
+                descendants(item, 0, *first, *last, out);
+                Ok(())
+            }
+            (Accessor::Filter(_) | Accessor::Method(_), Datum::List(list))
+                if unwrap && !matches!(accessor, Accessor::Method(Method::Type | Method::Size)) =>
+            {
+                for element in list.iter() {
+                    self.apply(accessor, element, current, last, false, out)?;
+                }
+                Ok(())
+            }
+            (Accessor::Filter(predicate), item) => {
+                if self.predicate(predicate, item, last)? == Ternary::True {
+                    out.push(item);
+                }
+                Ok(())
+            }
+            (Accessor::Method(method), item) => {
+                out.push(self.method(method, item)?);
+                Ok(())
+            }
+        }
+    }
+
+    /// Evaluates an array subscript to an index.
+    fn subscript(&self, expr: &Expr, current: Datum<'a>, last: i64) -> Result<i64, JsonPathError> {
+        let mut items = vec![];
+        self.eval(expr, current, Some(last), &mut items)?;
+        let [Datum::Numeric(n)] = items.as_slice() else {
+            return Err(eval_error(
+                "jsonpath array subscript is not a single numeric value",
+            ));
+        };
+        let mut n = n.0;
+        let mut cx = numeric::cx_datum();
+        cx.set_rounding(Rounding::Down);
+        cx.round(&mut n);
+        cx.clear_status();
+        cx.try_into_i32(n)
+            .map(i64::from)
+            .map_err(|_| eval_error("jsonpath array subscript is out of integer range"))
+    }
+
+    fn method(&self, method: &Method, item: Datum<'a>) -> Result<Datum<'a>, JsonPathError> {
+        let numeric_error = || {
+            eval_error(format!(
+                "jsonpath item method .{}() can only be applied to a numeric value",
+                method.name()
+            ))
+        };
+        match method {
+            Method::Type => Ok(Datum::String(item_type(item))),
+            Method::Size => match item {
+                Datum::List(list) => {
+                    let len = i64::try_from(list.iter().count()).expect("array length fits in i64");
+                    Ok(Datum::from(Numeric::from(len)))
+                }
+                _ => {
+                    self.structural_error(
+                        "jsonpath item method .size() can only be applied to an array",
+                    )?;
+                    Ok(Datum::from(Numeric::from(1)))
+                }
+            },
+            Method::Double => match item {
+                Datum::Numeric(_) => Ok(item),
+                Datum::String(s) => match strconv::parse_numeric(s) {
+                    Ok(n) if !n.0.is_nan() && !n.0.is_infinite() => Ok(Datum::Numeric(n)),
+                    _ => Err(eval_error(format!(
+                        "argument \"{s}\" of jsonpath item method .double() is invalid for type double precision"
+                    ))),
+                },
+                _ => Err(eval_error(
+                    "jsonpath item method .double() can only be applied to a string or numeric value",
+                )),
+            },
+            Method::Ceiling | Method::Floor => {
+                let Datum::Numeric(n) = item else {
+                    return Err(numeric_error());
+                };
+                let mut n = n.0;
+                // Rounding is a no-op if there are no fractional digits.
+                if n.exponent() < 0 {
+                    let mut cx = numeric::cx_datum();
+                    cx.set_rounding(match method {
+                        Method::Ceiling => Rounding::Ceiling,
+                        _ => Rounding::Floor,
+                    });
+                    cx.round(&mut n);
+                    numeric::munge_numeric(&mut n).expect("rounding cannot increase precision");
+                }
+                Ok(Datum::from(n))
+            }
+            Method::Abs => {
+                let Datum::Numeric(n) = item else {
+                    return Err(numeric_error());
+                };
+                let mut n = n.0;
+                numeric::cx_datum().abs(&mut n);
+                Ok(Datum::from(n))
+            }
+            Method::Datetime(template) => {
+                let Datum::String(s) = item else {
+                    return Err(eval_error(
+                        "jsonpath item method .datetime() can only be applied to a string",
+                    ));
+                };
+                parse_datetime(s, template.as_deref())
+            }
+        }
+    }
+
+    /// Evaluates a predicate with `current` as the item that `@` refers to.
+    fn predicate(
+        &self,
+        expr: &Expr,
+        current: Datum<'a>,
+        last: Option<i64>,
+    ) -> Result<Ternary, JsonPathError> {
+        match expr {
+            Expr::And(left, right) => {
+                let left = self.predicate(left, current, last)?;
+                if left == Ternary::False {
+                    return Ok(Ternary::False);
+                }
+                let right = self.predicate(right, current, last)?;
+                Ok(if right == Ternary::True { left } else { right })
+            }
+            Expr::Or(left, right) => {
+                let left = self.predicate(left, current, last)?;
+                if left == Ternary::True {
+                    return Ok(Ternary::True);
+                }
+                let right = self.predicate(right, current, last)?;
+                Ok(if right == Ternary::False { left } else { right })
+            }
+            Expr::Not(operand) => Ok(match self.predicate(operand, current, last)? {
+                Ternary::True => Ternary::False,
+                Ternary::False => Ternary::True,
+                Ternary::Unknown => Ternary::Unknown,
+            }),
+            Expr::IsUnknown(operand) => Ok(Ternary::from(
+                self.predicate(operand, current, last)? == Ternary::Unknown,
+            )),
+            Expr::Exists(operand) => {
+                let mut items = vec![];
+                match self.eval(operand, current, last, &mut items) {
+                    Ok(()) => Ok(Ternary::from(!items.is_empty())),
+                    Err(e) if e.is_silenceable() => Ok(Ternary::Unknown),
+                    Err(e) => Err(e),
+                }
+            }
+            Expr::Compare(op, left, right) => {
+                self.any_pair(left, Some((right, true)), current, last, |a, b| {
+                    compare_items(*op, a, b.expect("comparisons are binary"))
+                })
+            }
+            Expr::StartsWith(left, right) => {
+                self.any_pair(left, Some((right, false)), current, last, |a, b| {
+                    match (a, b) {
+                        (Datum::String(a), Some(Datum::String(b))) => {
+                            Ternary::from(a.starts_with(b))
+                        }
+                        _ => Ternary::Unknown,
+                    }
+                })
+            }
+            Expr::LikeRegex(left, regex) => {
+                self.any_pair(left, None, current, last, |a, _| match a {
+                    Datum::String(a) => Ternary::from(regex.is_match(a)),
+                    _ => Ternary::Unknown,
+                })
+            }
+            _ => unreachable!("the parser only accepts predicates here"),
+        }
+    }
+
+    /// Evaluates a predicate over all pairs of items of its operands. The
+    /// predicate is true if it holds for any pair. It is unknown if evaluating
+    /// the operands fails, or if it is unknown for any pair and either the
+    /// path is in strict mode or no pair makes it true.
+    ///
+    /// `right` is the right operand, if any, and whether to unwrap arrays in
+    /// its result in lax mode.
+    fn any_pair(
+        &self,
+        left: &Expr,
+        right: Option<(&Expr, bool)>,
+        current: Datum<'a>,
+        last: Option<i64>,
+        f: impl Fn(Datum<'a>, Option<Datum<'a>>) -> Ternary,
+    ) -> Result<Ternary, JsonPathError> {
+        let operands = (|| {
+            let left = self.eval_unwrapped(left, current, last)?;
+            let right: Vec<Option<Datum<'a>>> = match right {
+                Some((right, true)) => self
+                    .eval_unwrapped(right, current, last)?
+                    .into_iter()
+                    .map(Some)
+                    .collect(),
+                Some((right, false)) => {
+                    let mut items = vec![];
+                    self.eval(right, current, last, &mut items)?;
+                    items.into_iter().map(Some).collect()
+                }
+                None => vec![None],
+            };
+            Ok::<_, JsonPathError>((left, right))
+        })();
+        let (left, right) = match operands {
+            Ok(operands) => operands,
+            Err(e) if e.is_silenceable() => return Ok(Ternary::Unknown),
+            Err(e) => return Err(e),
+        };
+        let mut found = false;
+        let mut unknown = false;
+        for a in &left {
+            for b in &right {
+                match f(*a, *b) {
+                    Ternary::True if !self.strict => return Ok(Ternary::True),
+                    Ternary::True => found = true,
+                    Ternary::Unknown if self.strict => return Ok(Ternary::Unknown),
+                    Ternary::Unknown => unknown = true,
+                    Ternary::False => {}
+                }
+            }
+        }
+        Ok(if found {
+            Ternary::True
+        } else if unknown {
+            Ternary::Unknown
+        } else {
+            Ternary::False
+        })
+    }
+
+    /// Converts an item into a JSON datum.
+    fn to_json(&self, item: Datum<'a>) -> Datum<'a> {
+        match format_datetime(item) {
+            Some(s) => Datum::String(self.temp_storage.push_string(s)),
+            None => item,
+        }
+    }
+}
+
+fn arithmetic(op: BinaryOp, mut a: Numeric, b: Numeric) -> Result<Numeric, JsonPathError> {
+    let mut cx = numeric::cx_datum();
+    match op {
+        BinaryOp::Add => cx.add(&mut a, &b),
+        BinaryOp::Sub => cx.sub(&mut a, &b),
+        BinaryOp::Mul => cx.mul(&mut a, &b),
+        BinaryOp::Div | BinaryOp::Mod if b.is_zero() => {
+            return Err(eval_error("division by zero"));
+        }
+        BinaryOp::Div => cx.div(&mut a, &b),
+        BinaryOp::Mod => cx.rem(&mut a, &b),
+    }
+    if cx.status().overflow() || numeric::munge_numeric(&mut a).is_err() {
+        return Err(eval_error("value out of range for type numeric"));
+    }
+    Ok(a)
+}
+
+/// Pushes `item` and its descendants, in pre-order, if they are between the
+/// `first` and `last` levels. `item` is at level `level`.
+fn descendants<'a>(
+    item: Datum<'a>,
+    level: u32,
+    first: u32,
+    last: Option<u32>,
+    out: &mut Vec<Datum<'a>>,
+) {
+    if level >= first && last.map_or(true, |last| level <= last) {
+        out.push(item);
+    }
+    if last.map_or(false, |last| level >= last) {
+        return;
+    }
+    match item {
+        Datum::List(list) => {
+            for element in list.iter() {
+                descendants(element, level + 1, first, last, out);
+            }
+        }
+        Datum::Map(map) => {
+            for (_, value) in map.iter() {
+                descendants(value, level + 1, first, last, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn item_type(item: Datum) -> &'static str {
+    match item {
+        Datum::Map(_) => "object",
+        Datum::List(_) => "array",
+        Datum::String(_) => "string",
+        Datum::Numeric(_) => "number",
+        Datum::True | Datum::False => "boolean",
+        Datum::JsonNull => "null",
+        Datum::Date(_) => "date",
+        Datum::Time(_) => "time without time zone",
+        Datum::Timestamp(_) => "timestamp without time zone",
+        Datum::TimestampTz(_) => "timestamp with time zone",
+        _ => unreachable!("not a jsonpath item: {item:?}"),
+    }
+}
+
+fn compare_items(op: CompareOp, a: Datum, b: Datum) -> Ternary {
+    let ordering = match (a, b) {
+        (Datum::JsonNull, Datum::JsonNull) => Ordering::Equal,
+        // Null is only equal to itself, but is otherwise not comparable.
+        (Datum::JsonNull, _) | (_, Datum::JsonNull) => {
+            return Ternary::from(op == CompareOp::NotEq);
+        }
+        (Datum::True | Datum::False, Datum::True | Datum::False) => {
+            (a == Datum::True).cmp(&(b == Datum::True))
+        }
+        (Datum::Numeric(a), Datum::Numeric(b)) => a.cmp(&b),
+        (Datum::String(a), Datum::String(b)) => a.cmp(b),
+        _ => match compare_datetimes(a, b) {
+            Some(ordering) => ordering,
+            None => return Ternary::Unknown,
+        },
+    };
+    Ternary::from(op.holds(ordering))
+}
+
+/// Compares two datetime items. Dates are comparable with timestamps, but
+/// values with and without time zones are not comparable.
+fn compare_datetimes(a: Datum, b: Datum) -> Option<Ordering> {
+    fn naive(d: Datum) -> Option<NaiveDateTime> {
+        match d {
+            Datum::Date(d) => Some(NaiveDate::from(d).and_time(NaiveTime::MIN)),
+            Datum::Timestamp(ts) => Some(ts.to_naive()),
+            _ => None,
+        }
+    }
+    match (a, b) {
+        (Datum::Time(a), Datum::Time(b)) => Some(a.cmp(&b)),
+        (Datum::TimestampTz(a), Datum::TimestampTz(b)) => Some(a.cmp(&b)),
+        _ => Some(naive(a)?.cmp(&naive(b)?)),
+    }
+}
+
+/// Formats the fractional seconds of `t` without trailing zeros.
+fn format_fraction(t: &impl Timelike) -> String {
+    let micros = t.nanosecond() / 1_000;
+    if micros == 0 {
+        String::new()
+    } else {
+        format!(".{micros:06}").trim_end_matches('0').to_owned()
+    }
+}
+
+/// Formats datetime items as ISO 8601 strings.
+fn format_datetime(item: Datum) -> Option<String> {
+    match item {
+        Datum::Date(d) => Some(NaiveDate::from(d).format("%Y-%m-%d").to_string()),
+        Datum::Time(t) => Some(format!("{}{}", t.format("%H:%M:%S"), format_fraction(&t))),
+        Datum::Timestamp(ts) => {
+            let ts = ts.to_naive();
+            Some(format!(
+                "{}{}",
+                ts.format("%Y-%m-%dT%H:%M:%S"),
+                format_fraction(&ts)
+            ))
+        }
+        Datum::TimestampTz(ts) => {
+            let ts = ts.to_naive();
+            Some(format!(
+                "{}{}+00:00",
+                ts.format("%Y-%m-%dT%H:%M:%S"),
+                format_fraction(&ts)
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The kinds of datetime values that `.datetime()` produces.
+enum DatetimeKind {
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+}
+
+/// Translates a datetime template, like `YYYY-MM-DD HH24:MI:SS`, into a
+/// [`chrono`] format string, and determines the kind of value it describes.
+/// Only the most common template patterns are supported.
+fn translate_template(template: &str) -> Result<(String, DatetimeKind), JsonPathError> {
+    const PATTERNS: &[(&str, &str)] = &[
+        ("YYYY", "%Y"),
+        ("HH24", "%H"),
+        ("HH12", "%I"),
+        ("TZH:TZM", "%#z"),
+        ("TZH", "%#z"),
+        ("MM", "%m"),
+        ("MI", "%M"),
+        ("MS", "%3f"),
+        ("DD", "%d"),
+        ("HH", "%I"),
+        ("SS", "%S"),
+        ("US", "%6f"),
+        ("AM", "%p"),
+        ("PM", "%p"),
+    ];
+    let (mut has_date, mut has_time, mut has_tz) = (false, false, false);
+    let mut format = String::new();
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if let Some((pattern, specifier)) = PATTERNS.iter().find(|(p, _)| rest.starts_with(p)) {
+            match *pattern {
+                "YYYY" | "MM" | "DD" => has_date = true,
+                "TZH:TZM" | "TZH" => has_tz = true,
+                _ => has_time = true,
+            }
+            format.push_str(specifier);
+            rest = &rest[pattern.len()..];
+        } else if c == '"' {
+            let end = rest[1..].find('"').ok_or_else(|| {
+                eval_error(format!(
+                    "unterminated quoted text in datetime format \"{template}\""
+                ))
+            })?;
+            format.push_str(&rest[1..end + 1].replace('%', "%%"));
+            rest = &rest[end + 2..];
+        } else {
+            if c == '%' {
+                format.push('%');
+            }
+            format.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    let kind = match (has_date, has_time, has_tz) {
+        (true, true, true) => DatetimeKind::TimestampTz,
+        (true, true, false) => DatetimeKind::Timestamp,
+        (true, false, false) => DatetimeKind::Date,
+        (false, true, false) => DatetimeKind::Time,
+        _ => {
+            return Err(eval_error(format!(
+                "datetime format \"{template}\" is not supported"
+            )));
+        }
+    };
+    Ok((format, kind))
+}
+
+/// Parses `s` as a value of `kind` using the [`chrono`] format `format`.
+fn parse_datetime_kind<'a>(s: &str, format: &str, kind: &DatetimeKind) -> Option<Datum<'a>> {
+    match kind {
+        DatetimeKind::Date => {
+            let d = NaiveDate::parse_from_str(s, format).ok()?;
+            Date::try_from(d).ok().map(Datum::Date)
+        }
+        DatetimeKind::Time => NaiveTime::parse_from_str(s, format).ok().map(Datum::Time),
+        DatetimeKind::Timestamp => {
+            let ts = NaiveDateTime::parse_from_str(s, format).ok()?;
+            CheckedTimestamp::from_timestamplike(ts)
+                .ok()
+                .map(Datum::from)
+        }
+        DatetimeKind::TimestampTz => {
+            let ts = DateTime::parse_from_str(s, format).ok()?;
+            CheckedTimestamp::from_timestamplike(ts.with_timezone(&Utc))
+                .ok()
+                .map(Datum::from)
+        }
+    }
+}
+
+/// Implements the `.datetime()` item method. Without a template, `s` must be in
+/// one of the ISO 8601 formats that PostgreSQL recognizes.
+fn parse_datetime<'a>(s: &str, template: Option<&str>) -> Result<Datum<'a>, JsonPathError> {
+    if let Some(template) = template {
+        let (format, kind) = translate_template(template)?;
+        return parse_datetime_kind(s, &format, &kind).ok_or_else(|| {
+            eval_error(format!(
+                "unable to parse \"{s}\" using datetime format \"{template}\""
+            ))
+        });
+    }
+    const FORMATS: &[(&str, DatetimeKind)] = &[
+        ("%Y-%m-%d %H:%M:%S%.f%#z", DatetimeKind::TimestampTz),
+        ("%Y-%m-%dT%H:%M:%S%.f%#z", DatetimeKind::TimestampTz),
+        ("%Y-%m-%d %H:%M:%S%.f", DatetimeKind::Timestamp),
+        ("%Y-%m-%dT%H:%M:%S%.f", DatetimeKind::Timestamp),
+        ("%Y-%m-%d", DatetimeKind::Date),
+        ("%H:%M:%S%.f", DatetimeKind::Time),
+    ];
+    FORMATS
+        .iter()
+        .find_map(|(format, kind)| parse_datetime_kind(s, format, kind))
+        .ok_or_else(|| eval_error(format!("datetime format is not recognized: \"{s}\"")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adt::jsonb::{Jsonb, JsonbRef};
+
+    use super::*;
+
+    fn query(target: &str, path: &str, vars: &str) -> Result<Vec<String>, JsonPathError> {
+        let target: Jsonb = target.parse().unwrap();
+        let vars: Jsonb = vars.parse().unwrap();
+        let path: JsonPath = path.parse()?;
+        let arena = RowArena::new();
+        let items = path.query(
+            target.as_ref().into_datum(),
+            vars.as_ref().into_datum(),
+            &arena,
+        )?;
+        Ok(items
+            .into_iter()
+            .map(|item| JsonbRef::from_datum(item).to_string())
+            .collect())
+    }
+
+    fn ok(target: &str, path: &str) -> Vec<String> {
+        query(target, path, "{}").unwrap()
+    }
+
+    fn err(target: &str, path: &str) -> String {
+        query(target, path, "{}").unwrap_err().to_string()
+    }
+
+    #[mz_ore::test]
+    fn test_parse_errors() {
+        for (path, expected) in [
+            ("", "syntax error at end of jsonpath input"),
+            ("$.a[", "syntax error at end of jsonpath input"),
+            ("$ $", "syntax error at or near \"$\" of jsonpath input"),
+            ("@.a", "@ is not allowed in root expressions"),
+            ("$.a[last]", ""),
+            ("last", "LAST is allowed only in array subscripts"),
+            (
+                "$.a && $.b",
+                "syntax error at or near \"&&\" of jsonpath input",
+            ),
+            (
+                "$.a.keyvalue()",
+                "unsupported jsonpath item method .keyvalue()",
+            ),
+            (
+                "$ ? (@ like_regex \"a\" flag \"z\")",
+                "unrecognized flag character \"z\" in LIKE_REGEX predicate",
+            ),
+            ("\"abc", "unterminated quoted string in jsonpath input"),
+            (
+                "$.a[1a]",
+                "trailing junk after numeric literal at or near \"1a\" of jsonpath input",
+            ),
+        ] {
+            let result = path
+                .parse::<JsonPath>()
+                .map(|_| ())
+                .map_err(|e| e.to_string());
+            if expected.is_empty() {
+                assert_eq!(result, Ok(()), "{path}");
+            } else {
+                assert_eq!(result, Err(expected.to_string()), "{path}");
+            }
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_accessors() {
+        let doc = r#"{"a": [1, {"b": 2}, [3, 4]], "c": {"d": {"e": 5}}}"#;
+        assert_eq!(ok(doc, "$.a[0]"), ["1"]);
+        assert_eq!(ok(doc, "$.a[last]"), ["[3,4]"]);
+        assert_eq!(ok(doc, "$.a[0, 2 to last]"), ["1", "[3,4]"]);
+        assert_eq!(ok(doc, "$.a[*].b"), ["2"]);
+        assert_eq!(ok(doc, "$.\"c\".*"), [r#"{"e":5}"#]);
+        assert_eq!(ok(doc, "$.c.**.e"), ["5"]);
+        assert_eq!(ok(doc, "$.c.**{2}"), ["5"]);
+        assert_eq!(ok(doc, "$.a.size()"), ["3"]);
+        assert_eq!(
+            ok(doc, "$.a[*].type()"),
+            [r#""number""#, r#""object""#, r#""array""#]
+        );
+        // Lax mode unwraps arrays and ignores structural errors.
+        assert_eq!(ok(doc, "$.a.b"), ["2"]);
+        assert_eq!(ok(doc, "$.a[5]"), Vec::<String>::new());
+        assert_eq!(ok(doc, "$.c[0].d"), [r#"{"e":5}"#]);
+        assert_eq!(ok(doc, "lax $.x"), Vec::<String>::new());
+        // Strict mode doesn't.
+        assert_eq!(
+            err(doc, "strict $.x"),
+            "JSON object does not contain key \"x\""
+        );
+        assert_eq!(
+            err(doc, "strict $.a.b"),
+            "jsonpath member accessor can only be applied to an object"
+        );
+        assert_eq!(
+            err(doc, "strict $.a[5]"),
+            "jsonpath array subscript is out of bounds"
+        );
+        assert_eq!(
+            err(doc, "strict $.c[*]"),
+            "jsonpath wildcard array accessor can only be applied to an array"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_arithmetic() {
+        assert_eq!(ok(r#"{"a": 7, "b": [2]}"#, "$.a % $.b"), ["1"]);
+        assert_eq!(ok(r#"{"a": 7}"#, "-$.a * 2 + 1"), ["-13"]);
+        assert_eq!(ok(r#"[1.5, -2.5]"#, "$[*].floor()"), ["1", "-3"]);
+        assert_eq!(ok(r#"[1.5, -2.5]"#, "$.ceiling()"), ["2", "-2"]);
+        assert_eq!(ok(r#"["1.5"]"#, "$[0].double().abs()"), ["1.5"]);
+        assert_eq!(err("1", "$ / 0"), "division by zero");
+        assert_eq!(
+            err("[1, 2]", "strict $ + 1"),
+            "left operand of jsonpath operator + is not a single numeric value"
+        );
+        assert_eq!(
+            err(r#""a""#, "$.abs()"),
+            "jsonpath item method .abs() can only be applied to a numeric value"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_predicates() {
+        let doc = r#"[{"a": 1, "s": "apple"}, {"a": 2, "s": "Banana"}, {"a": null}, {"s": 3}]"#;
+        assert_eq!(ok(doc, "$[*] ? (@.a > 1).s"), [r#""Banana""#]);
+        assert_eq!(ok(doc, "$ ? (@.a == null).a"), ["null"]);
+        assert_eq!(ok(doc, "$ ? (@.a != 1).a"), ["2", "null"]);
+        assert_eq!(ok(doc, "$ ? (@.s starts with \"ap\").a"), ["1"]);
+        assert_eq!(ok(doc, "$ ? (@.s like_regex \"^b\" flag \"i\").a"), ["2"]);
+        assert_eq!(ok(doc, "$ ? (!exists(@.a)).s"), ["3"]);
+        assert_eq!(
+            ok(doc, "$ ? ((@.s > 1) is unknown).s"),
+            [r#""apple""#, r#""Banana""#]
+        );
+        assert_eq!(ok(doc, "$[0].a == 1 && $[1].a < 2"), ["false"]);
+        assert_eq!(ok(doc, "$[0].s == 1"), ["null"]);
+        assert_eq!(
+            ok(r#"{"xs": [1, 5, 9]}"#, "$.xs ? (@ >= 5 || @ < 0)"),
+            ["5", "9"]
+        );
+        // In strict mode, errors in any pair make a predicate unknown.
+        assert_eq!(ok("[1, \"a\"]", "lax $[*] > 0"), ["true"]);
+        assert_eq!(ok("[1, \"a\"]", "strict $[*] > 0"), ["null"]);
+    }
+
+    #[mz_ore::test]
+    fn test_variables() {
+        let doc = r#"{"a": [1, 2, 3]}"#;
+        assert_eq!(
+            query(
+                doc,
+                "$.a[*] ? (@ > $min && @ < $\"max\")",
+                r#"{"min": 1, "max": 3}"#
+            )
+            .unwrap(),
+            ["2"]
+        );
+        assert_eq!(
+            query(doc, "$.a ? (@ > $min)", "{}").unwrap_err(),
+            JsonPathError::UnknownVariable("min".into())
+        );
+        assert_eq!(
+            query(doc, "$", "[]").unwrap_err(),
+            JsonPathError::InvalidVars
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_datetime() {
+        let doc = r#"["2024-02-29", "2024-02-29 13:14:15.5", "2024-02-29T13:14:15+02", "08:30:00", "bogus"]"#;
+        assert_eq!(ok(doc, "$[0].datetime()"), [r#""2024-02-29""#]);
+        assert_eq!(ok(doc, "$[1].datetime()"), [r#""2024-02-29T13:14:15.5""#]);
+        assert_eq!(
+            ok(doc, "$[2].datetime()"),
+            [r#""2024-02-29T11:14:15+00:00""#]
+        );
+        assert_eq!(
+            ok(doc, "$[3].datetime().type()"),
+            [r#""time without time zone""#]
+        );
+        assert_eq!(
+            ok(
+                r#""29/02/2024 10:00""#,
+                r#"$.datetime("DD/MM/YYYY HH24:MI")"#
+            ),
+            [r#""2024-02-29T10:00:00""#]
+        );
+        assert_eq!(
+            ok(
+                doc,
+                "$[0 to 1] ? (@.datetime() < \"2024-02-29 12:00\".datetime(\"YYYY-MM-DD HH24:MI\"))"
+            ),
+            [r#""2024-02-29""#]
+        );
+        assert_eq!(
+            err(doc, "$[4].datetime()"),
+            "datetime format is not recognized: \"bogus\""
+        );
+        assert_eq!(
+            err("1", "$.datetime()"),
+            "jsonpath item method .datetime() can only be applied to a string"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_matches() {
+        let arena = RowArena::new();
+        let target: Jsonb = r#"{"a": 1}"#.parse().unwrap();
+        let vars: Jsonb = "{}".parse().unwrap();
+        let eval = |path: &str| {
+            path.parse::<JsonPath>().unwrap().matches(
+                target.as_ref().into_datum(),
+                vars.as_ref().into_datum(),
+                &arena,
+            )
+        };
+        assert_eq!(eval("$.a == 1"), Ok(Some(true)));
+        assert_eq!(eval("$.a == \"x\""), Ok(None));
+        assert_eq!(
+            eval("$.a"),
+            Err(eval_error("single boolean result is expected"))
+        );
+    }
+}
@@ -0,0 +1,578 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Mergeable sketches for approximate aggregation.
+//!
+//! Sketches are values of the `hll_sketch` and `quantile_sketch` types
+//! ([`ScalarType::HllSketch`] and [`ScalarType::QuantileSketch`]), so that
+//! partial sketches can be written to tables and materialized views and
+//! combined later. Both types are stored as [`Datum::Bytes`] holding the
+//! encoding defined in this module. Every encoded sketch starts with a tag
+//! identifying the kind of sketch and the version of its encoding.
+//!
+//! [`ScalarType::HllSketch`]: crate::ScalarType::HllSketch
+//! [`ScalarType::QuantileSketch`]: crate::ScalarType::QuantileSketch
+//! [`Datum::Bytes`]: crate::Datum::Bytes
+//!
+//! Encodings are canonical: two sketches that summarize the same state encode
+//! to the same bytes. Differential dataflow relies on this to consolidate
+//! updates to sketches.
+
+use std::collections::BTreeMap;
+
+use mz_ore::cast::{CastFrom, CastLossy};
+use thiserror::Error;
+
+/// The version of the encoding of all sketches.
+const SKETCH_VERSION: u8 = 1;
+
+/// The tag of an encoded [`HyperLogLog`].
+const HLL_TAG: u8 = 1;
+
+/// The tag of an encoded [`QuantileSketch`].
+const QUANTILE_TAG: u8 = 2;
+
+/// An error decoding or updating a sketch.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid {kind} sketch: {detail}")]
+pub struct InvalidSketchError {
+    kind: &'static str,
+    detail: &'static str,
+}
+
+impl InvalidSketchError {
+    fn hll(detail: &'static str) -> Self {
+        InvalidSketchError {
+            kind: "hll",
+            detail,
+        }
+    }
+
+    fn quantile(detail: &'static str) -> Self {
+        InvalidSketchError {
+            kind: "quantile",
+            detail,
+        }
+    }
+}
+
+/// The number of bits of a hash that select a [`HyperLogLog`] register.
+pub const HLL_PRECISION: u32 = 12;
+
+/// The number of registers in a [`HyperLogLog`].
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// The largest rank that can be stored in a register: one more than the number
+/// of hash bits that are left after selecting the register.
+const HLL_MAX_RANK: u8 = 64 - 12 + 1;
+
+/// Encoding layout that lists the nonzero registers as `(index, rank)` pairs.
+const HLL_SPARSE: u8 = 0;
+
+/// Encoding layout that stores every register.
+const HLL_DENSE: u8 = 1;
+
+/// The size of an encoded [`HyperLogLog`] header.
+const HLL_HEADER_LEN: usize = 4;
+
+/// The size of one `(index, rank)` pair in the sparse layout.
+const HLL_SPARSE_ENTRY_LEN: usize = 3;
+
+/// A HyperLogLog sketch for estimating the number of distinct values.
+///
+/// Values are inserted by their 64-bit hash. Merging two sketches takes the
+/// maximum of each register, which is associative, commutative and idempotent,
+/// so sketches can be combined in any order and any number of times.
+///
+/// With [`HLL_PRECISION`] bits of register index, the relative standard error of
+/// the estimate is about 1.6%.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        HyperLogLog {
+            registers: vec![0; HLL_REGISTERS],
+        }
+    }
+}
+
+impl HyperLogLog {
+    /// Returns an empty sketch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the value with the given hash to the sketch.
+    pub fn insert_hash(&mut self, hash: u64) {
+        let index = usize::cast_from(hash >> (64 - HLL_PRECISION));
+        // Set the lowest bit above the discarded index bits, so that the rank
+        // is bounded even if all of the remaining bits are zero.
+        let rest = (hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1));
+        let rank = u8::try_from(rest.leading_zeros() + 1).expect("rank is at most 64");
+        self.update(index, rank);
+    }
+
+    /// Merges `other` into this sketch.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (lhs, rhs) in self.registers.iter_mut().zip(&other.registers) {
+            *lhs = std::cmp::max(*lhs, *rhs);
+        }
+    }
+
+    /// Merges an encoded sketch into this sketch, without decoding it into a
+    /// separate [`HyperLogLog`] first.
+    pub fn merge_encoded(&mut self, bytes: &[u8]) -> Result<(), InvalidSketchError> {
+        match Self::decode_header(bytes)? {
+            (HLL_SPARSE, entries) => {
+                for (index, rank) in Self::sparse_entries(entries)? {
+                    self.update(index, rank);
+                }
+            }
+            (_, registers) => {
+                if registers.iter().any(|rank| *rank > HLL_MAX_RANK) {
+                    return Err(InvalidSketchError::hll("register out of range"));
+                }
+                for (lhs, rhs) in self.registers.iter_mut().zip(registers) {
+                    *lhs = std::cmp::max(*lhs, *rhs);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the estimated number of distinct values in the sketch.
+    pub fn estimate(&self) -> u64 {
+        let m = f64::cast_lossy(HLL_REGISTERS);
+        let mut sum = 0.0;
+        let mut zeros = 0;
+        for rank in &self.registers {
+            sum += 2f64.powi(-i32::from(*rank));
+            if *rank == 0 {
+                zeros += 1;
+            }
+        }
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let raw = alpha * m * m / sum;
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate than the raw estimate for small
+            // cardinalities.
+            m * (m / f64::cast_lossy(zeros)).ln()
+        } else {
+            raw
+        };
+        u64::cast_lossy(estimate.round())
+    }
+
+    /// Encodes the sketch.
+    ///
+    /// Sketches with few nonzero registers are encoded sparsely, which keeps
+    /// the sketch of a single value to a few bytes.
+    pub fn encode(&self) -> Vec<u8> {
+        let nonzero = self.registers.iter().filter(|rank| **rank != 0).count();
+        let sparse = nonzero * HLL_SPARSE_ENTRY_LEN < HLL_REGISTERS;
+        let layout = if sparse { HLL_SPARSE } else { HLL_DENSE };
+        let precision = u8::try_from(HLL_PRECISION).expect("precision fits in u8");
+        let mut buf = vec![HLL_TAG, SKETCH_VERSION, precision, layout];
+        if sparse {
+            buf.reserve(nonzero * HLL_SPARSE_ENTRY_LEN);
+            for (index, rank) in self.registers.iter().enumerate() {
+                if *rank != 0 {
+                    let index = u16::try_from(index).expect("register index fits in u16");
+                    buf.extend(index.to_be_bytes());
+                    buf.push(*rank);
+                }
+            }
+        } else {
+            buf.extend(&self.registers);
+        }
+        buf
+    }
+
+    /// Decodes a sketch produced by [`HyperLogLog::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, InvalidSketchError> {
+        let mut hll = HyperLogLog::new();
+        hll.merge_encoded(bytes)?;
+        Ok(hll)
+    }
+
+    /// Checks the header of an encoded sketch, and returns its layout along
+    /// with the rest of the encoding.
+    fn decode_header(bytes: &[u8]) -> Result<(u8, &[u8]), InvalidSketchError> {
+        let Some((header, rest)) = bytes.split_at_checked(HLL_HEADER_LEN) else {
+            return Err(InvalidSketchError::hll("truncated header"));
+        };
+        if header[0] != HLL_TAG {
+            return Err(InvalidSketchError::hll("not an hll sketch"));
+        }
+        if header[1] != SKETCH_VERSION {
+            return Err(InvalidSketchError::hll("unsupported version"));
+        }
+        if u32::from(header[2]) != HLL_PRECISION {
+            return Err(InvalidSketchError::hll("unsupported precision"));
+        }
+        match header[3] {
+            HLL_SPARSE if rest.len() % HLL_SPARSE_ENTRY_LEN == 0 => Ok((HLL_SPARSE, rest)),
+            HLL_DENSE if rest.len() == HLL_REGISTERS => Ok((HLL_DENSE, rest)),
+            HLL_SPARSE | HLL_DENSE => Err(InvalidSketchError::hll("wrong length")),
+            _ => Err(InvalidSketchError::hll("unknown layout")),
+        }
+    }
+
+    /// Validates and returns the `(index, rank)` pairs of a sparse encoding.
+    fn sparse_entries(
+        entries: &[u8],
+    ) -> Result<impl Iterator<Item = (usize, u8)> + '_, InvalidSketchError> {
+        let mut prev = None;
+        for entry in entries.chunks_exact(HLL_SPARSE_ENTRY_LEN) {
+            let index = u16::from_be_bytes([entry[0], entry[1]]);
+            if usize::from(index) >= HLL_REGISTERS || entry[2] == 0 || entry[2] > HLL_MAX_RANK {
+                return Err(InvalidSketchError::hll("register out of range"));
+            }
+            if prev.is_some_and(|prev| prev >= index) {
+                return Err(InvalidSketchError::hll("registers out of order"));
+            }
+            prev = Some(index);
+        }
+        Ok(entries.chunks_exact(HLL_SPARSE_ENTRY_LEN).map(|entry| {
+            (
+                usize::from(u16::from_be_bytes([entry[0], entry[1]])),
+                entry[2],
+            )
+        }))
+    }
+
+    fn update(&mut self, index: usize, rank: u8) {
+        let register = &mut self.registers[index];
+        *register = std::cmp::max(*register, rank);
+    }
+}
+
+/// The relative accuracy of the quantiles estimated by a [`QuantileSketch`].
+pub const QUANTILE_SKETCH_RELATIVE_ACCURACY: f64 = 0.01;
+
+/// Values whose magnitude is below this are counted as zero by a
+/// [`QuantileSketch`].
+const QUANTILE_SKETCH_MIN_MAGNITUDE: f64 = 1e-9;
+
+/// The size of an encoded [`QuantileSketch`] bucket.
+const QUANTILE_BUCKET_LEN: usize = 12;
+
+/// A sketch for estimating quantiles with a bounded relative error, in the
+/// style of DDSketch.
+///
+/// Values are counted in logarithmically sized buckets, so that any value in a
+/// bucket is within [`QUANTILE_SKETCH_RELATIVE_ACCURACY`] of the value the
+/// bucket reports. Merging two sketches adds their bucket counts, which is
+/// associative and commutative but, unlike [`HyperLogLog`], not idempotent.
+///
+/// The number of buckets grows with the logarithm of the range of the inserted
+/// values, not with the number of values.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuantileSketch {
+    /// Counts of negative values, by the bucket of their magnitude.
+    negative: BTreeMap<i32, u64>,
+    /// Count of values that are too close to zero to be bucketed.
+    zero: u64,
+    /// Counts of positive values, by bucket.
+    positive: BTreeMap<i32, u64>,
+}
+
+impl QuantileSketch {
+    /// Returns an empty sketch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value to the sketch. Returns an error if the value is NaN or
+    /// infinite.
+    pub fn insert(&mut self, value: f64) -> Result<(), InvalidSketchError> {
+        if !value.is_finite() {
+            return Err(InvalidSketchError::quantile("value must be finite"));
+        }
+        if value.abs() < QUANTILE_SKETCH_MIN_MAGNITUDE {
+            self.zero = self.zero.saturating_add(1);
+        } else {
+            let buckets = if value > 0.0 {
+                &mut self.positive
+            } else {
+                &mut self.negative
+            };
+            let count = buckets.entry(Self::bucket(value.abs())).or_default();
+            *count = count.saturating_add(1);
+        }
+        Ok(())
+    }
+
+    /// Merges `other` into this sketch.
+    pub fn merge(&mut self, other: &QuantileSketch) {
+        self.merge_scaled(other, 1);
+    }
+
+    /// Merges `other` into this sketch `factor` times, which multiplies the
+    /// counts of `other` by `factor`.
+    pub fn merge_scaled(&mut self, other: &QuantileSketch, factor: u64) {
+        if factor == 0 {
+            return;
+        }
+        self.zero = self.zero.saturating_add(other.zero.saturating_mul(factor));
+        for (lhs, rhs) in [
+            (&mut self.negative, &other.negative),
+            (&mut self.positive, &other.positive),
+        ] {
+            for (bucket, count) in rhs {
+                let lhs = lhs.entry(*bucket).or_default();
+                *lhs = lhs.saturating_add(count.saturating_mul(factor));
+            }
+        }
+    }
+
+    /// Returns the number of values in the sketch.
+    pub fn count(&self) -> u64 {
+        self.negative
+            .values()
+            .chain(self.positive.values())
+            .fold(self.zero, |sum, count| sum.saturating_add(*count))
+    }
+
+    /// Returns the estimated value at `fraction`, which must be between 0 and
+    /// 1, or `None` if the sketch is empty.
+    pub fn quantile(&self, fraction: f64) -> Option<f64> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let rank = fraction * f64::cast_lossy(count - 1);
+        let negative = self
+            .negative
+            .iter()
+            .rev()
+            .map(|(bucket, count)| (-Self::value(*bucket), *count));
+        let zero = std::iter::once((0.0, self.zero));
+        let positive = self
+            .positive
+            .iter()
+            .map(|(bucket, count)| (Self::value(*bucket), *count));
+        let mut seen = 0u64;
+        let mut last = None;
+        for (value, count) in negative.chain(zero).chain(positive) {
+            if count == 0 {
+                continue;
+            }
+            seen = seen.saturating_add(count);
+            last = Some(value);
+            if f64::cast_lossy(seen) > rank {
+                break;
+            }
+        }
+        last
+    }
+
+    /// Encodes the sketch.
+    pub fn encode(&self) -> Vec<u8> {
+        let buckets = self.negative.len() + self.positive.len();
+        let mut buf = Vec::with_capacity(2 + 8 + 2 * 4 + buckets * QUANTILE_BUCKET_LEN);
+        buf.extend([QUANTILE_TAG, SKETCH_VERSION]);
+        buf.extend(self.zero.to_le_bytes());
+        for buckets in [&self.negative, &self.positive] {
+            let len = u32::try_from(buckets.len()).expect("number of buckets fits in u32");
+            buf.extend(len.to_le_bytes());
+            for (bucket, count) in buckets {
+                buf.extend(bucket.to_le_bytes());
+                buf.extend(count.to_le_bytes());
+            }
+        }
+        buf
+    }
+
+    /// Decodes a sketch produced by [`QuantileSketch::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, InvalidSketchError> {
+        let mut rest = bytes;
+        let header = Self::take(&mut rest, 2)?;
+        if header[0] != QUANTILE_TAG {
+            return Err(InvalidSketchError::quantile("not a quantile sketch"));
+        }
+        if header[1] != SKETCH_VERSION {
+            return Err(InvalidSketchError::quantile("unsupported version"));
+        }
+        let zero = u64::from_le_bytes(Self::take(&mut rest, 8)?.try_into().expect("8 bytes"));
+        let negative = Self::decode_buckets(&mut rest)?;
+        let positive = Self::decode_buckets(&mut rest)?;
+        if !rest.is_empty() {
+            return Err(InvalidSketchError::quantile("trailing bytes"));
+        }
+        Ok(QuantileSketch {
+            negative,
+            zero,
+            positive,
+        })
+    }
+
+    fn decode_buckets(rest: &mut &[u8]) -> Result<BTreeMap<i32, u64>, InvalidSketchError> {
+        let len = u32::from_le_bytes(Self::take(rest, 4)?.try_into().expect("4 bytes"));
+        let mut buckets = BTreeMap::new();
+        let mut prev = None;
+        for _ in 0..len {
+            let entry = Self::take(rest, QUANTILE_BUCKET_LEN)?;
+            let bucket = i32::from_le_bytes(entry[..4].try_into().expect("4 bytes"));
+            let count = u64::from_le_bytes(entry[4..].try_into().expect("8 bytes"));
+            if count == 0 {
+                return Err(InvalidSketchError::quantile("empty bucket"));
+            }
+            if prev.is_some_and(|prev| prev >= bucket) {
+                return Err(InvalidSketchError::quantile("buckets out of order"));
+            }
+            prev = Some(bucket);
+            buckets.insert(bucket, count);
+        }
+        Ok(buckets)
+    }
+
+    /// Splits the first `n` bytes off of `rest`.
+    fn take<'a>(rest: &mut &'a [u8], n: usize) -> Result<&'a [u8], InvalidSketchError> {
+        let (taken, remaining) = rest
+            .split_at_checked(n)
+            .ok_or_else(|| InvalidSketchError::quantile("truncated"))?;
+        *rest = remaining;
+        Ok(taken)
+    }
+
+    /// The ratio between the bounds of consecutive buckets.
+    fn gamma() -> f64 {
+        (1.0 + QUANTILE_SKETCH_RELATIVE_ACCURACY) / (1.0 - QUANTILE_SKETCH_RELATIVE_ACCURACY)
+    }
+
+    /// Returns the bucket of a positive, finite magnitude.
+    fn bucket(magnitude: f64) -> i32 {
+        let bucket = i64::cast_lossy((magnitude.ln() / Self::gamma().ln()).ceil());
+        // Finite doubles are within ±1100 of zero on a natural log scale, so
+        // the bucket always fits.
+        i32::try_from(bucket).expect("bucket fits in i32")
+    }
+
+    /// Returns the value reported for a bucket, which is within the relative
+    /// accuracy of every magnitude in the bucket.
+    fn value(bucket: i32) -> f64 {
+        let gamma = Self::gamma();
+        2.0 * gamma.powi(bucket) / (gamma + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_hll_estimate() {
+        let mut hll = HyperLogLog::new();
+        assert_eq!(hll.estimate(), 0);
+        for i in 0..100_000u64 {
+            hll.insert_hash(seeded_hash(i));
+        }
+        let estimate = f64::cast_lossy(hll.estimate());
+        assert!(
+            (estimate - 100_000.0).abs() < 100_000.0 * 0.05,
+            "{estimate}"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_hll_roundtrip_and_merge() {
+        let mut small = HyperLogLog::new();
+        small.insert_hash(seeded_hash(1));
+        let encoded = small.encode();
+        assert_eq!(encoded.len(), HLL_HEADER_LEN + HLL_SPARSE_ENTRY_LEN);
+        assert_eq!(HyperLogLog::decode(&encoded).unwrap(), small);
+
+        let mut large = HyperLogLog::new();
+        for i in 0..10_000 {
+            large.insert_hash(seeded_hash(i));
+        }
+        let encoded = large.encode();
+        assert_eq!(encoded.len(), HLL_HEADER_LEN + HLL_REGISTERS);
+        assert_eq!(HyperLogLog::decode(&encoded).unwrap(), large);
+
+        // Merging is idempotent.
+        let mut merged = large.clone();
+        merged.merge_encoded(&small.encode()).unwrap();
+        merged.merge(&large);
+        assert_eq!(merged, large);
+
+        assert!(HyperLogLog::decode(b"").is_err());
+        assert!(HyperLogLog::decode(&[HLL_TAG, SKETCH_VERSION, 12, HLL_SPARSE, 0]).is_err());
+        assert!(HyperLogLog::decode(&QuantileSketch::new().encode()).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_quantile_sketch() {
+        let mut sketch = QuantileSketch::new();
+        assert_eq!(sketch.quantile(0.5), None);
+        for i in 1..=1000 {
+            sketch.insert(f64::from(i)).unwrap();
+            sketch.insert(-f64::from(i)).unwrap();
+        }
+        sketch.insert(0.0).unwrap();
+        assert_eq!(sketch.count(), 2001);
+        for (fraction, expected) in [(0.0, -1000.0), (0.25, -500.0), (0.75, 500.0), (1.0, 1000.0)] {
+            let actual = sketch.quantile(fraction).unwrap();
+            let error = ((actual - expected) / expected).abs();
+            assert!(
+                error <= QUANTILE_SKETCH_RELATIVE_ACCURACY,
+                "{actual} {expected}"
+            );
+        }
+        assert_eq!(sketch.quantile(0.5), Some(0.0));
+
+        assert!(sketch.insert(f64::NAN).is_err());
+        assert!(sketch.insert(f64::INFINITY).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_quantile_sketch_roundtrip_and_merge() {
+        let mut a = QuantileSketch::new();
+        let mut b = QuantileSketch::new();
+        let mut all = QuantileSketch::new();
+        for i in 0..100 {
+            let value = f64::from(i) * 1.5 - 20.0;
+            let half = if i % 2 == 0 { &mut a } else { &mut b };
+            half.insert(value).unwrap();
+            all.insert(value).unwrap();
+        }
+        let mut merged = QuantileSketch::decode(&a.encode()).unwrap();
+        merged.merge(&QuantileSketch::decode(&b.encode()).unwrap());
+        assert_eq!(merged, all);
+        assert_eq!(merged.encode(), all.encode());
+
+        // Merging a sketch `n` times is the same as scaling it by `n`.
+        let mut twice = a.clone();
+        twice.merge(&a);
+        let mut scaled = QuantileSketch::new();
+        scaled.merge_scaled(&a, 2);
+        assert_eq!(scaled, twice);
+        assert_eq!(scaled.count(), 2 * a.count());
+        scaled.merge_scaled(&b, 0);
+        assert_eq!(scaled, twice);
+
+        let encoded = all.encode();
+        assert!(QuantileSketch::decode(&encoded[..encoded.len() - 1]).is_err());
+        assert!(QuantileSketch::decode(&[encoded.as_slice(), &[0]].concat()).is_err());
+        assert!(QuantileSketch::decode(&HyperLogLog::new().encode()).is_err());
+    }
+
+    /// A stand-in for a real hash function, which spreads consecutive integers
+    /// over the whole `u64` range.
+    fn seeded_hash(i: u64) -> u64 {
+        let mut x = i.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^ (x >> 31)
+    }
+}
@@ -668,6 +668,29 @@ pub struct Analyses {
     pub cardinality: Option<String>,
     pub column_names: Option<Vec<String>>,
     pub equivalences: Option<String>,
+    pub measurements: Option<Measurements>,
+}
+
+/// Runtime measurements of a plan node, as reported by `EXPLAIN ANALYZE`.
+///
+/// Only the requested measurements are present.
+#[derive(Clone, Default, Debug, Serialize)]
+pub struct Measurements {
+    /// The size of the arrangements maintained by the node, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+    /// The number of records in the arrangements maintained by the node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub records: Option<u64>,
+    /// The ratio of the maximum to the average arrangement size per worker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_skew: Option<f64>,
+    /// The time the operators of the node were scheduled for, in nanoseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ns: Option<u64>,
+    /// The ratio of the maximum to the average scheduled time per worker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_skew: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -11,6 +11,7 @@
 //!
 //! See row.proto for details.
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::ops::AddAssign;
 use std::sync::Arc;
@@ -36,8 +37,8 @@ use mz_persist_types::Codec;
 use mz_persist_types::arrow::ArrayOrd;
 use mz_persist_types::columnar::{ColumnDecoder, ColumnEncoder, FixedSizeCodec, Schema};
 use mz_persist_types::stats::{
-    ColumnNullStats, ColumnStatKinds, ColumnarStats, ColumnarStatsBuilder, FixedSizeBytesStatsKind,
-    OptionStats, PrimitiveStats, StructStats,
+    BloomFilter, BloomFilterColumns, ColumnNullStats, ColumnStatKinds, ColumnarStats,
+    ColumnarStatsBuilder, FixedSizeBytesStatsKind, OptionStats, PrimitiveStats, StructStats,
 };
 use mz_proto::chrono::ProtoNaiveTime;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
@@ -101,6 +102,8 @@ pub fn preserves_order(scalar_type: &ScalarType) -> bool {
         | ScalarType::TimestampTz { .. }
         | ScalarType::Interval
         | ScalarType::Bytes
+        | ScalarType::HllSketch
+        | ScalarType::QuantileSketch
         | ScalarType::String
         | ScalarType::Uuid
         | ScalarType::MzTimestamp
@@ -1218,6 +1221,43 @@ impl DatumColumnDecoder {
         }
     }
 
+    /// Whether we maintain Bloom filters for this column. This is the same as
+    /// [`crate::stats::bloom_filter_supported`], except that we can't tell
+    /// `char` apart from other strings here.
+    fn supports_bloom_filter(&self) -> bool {
+        match self {
+            DatumColumnDecoder::Bool(_)
+            | DatumColumnDecoder::U16(_)
+            | DatumColumnDecoder::U32(_)
+            | DatumColumnDecoder::U64(_)
+            | DatumColumnDecoder::I16(_)
+            | DatumColumnDecoder::I32(_)
+            | DatumColumnDecoder::I64(_)
+            | DatumColumnDecoder::String(_)
+            | DatumColumnDecoder::Bytes(_)
+            | DatumColumnDecoder::Date(_)
+            | DatumColumnDecoder::Timestamp(_)
+            | DatumColumnDecoder::TimestampTz(_)
+            | DatumColumnDecoder::MzTimestamp(_)
+            | DatumColumnDecoder::Uuid(_) => true,
+            DatumColumnDecoder::U8(_)
+            | DatumColumnDecoder::F32(_)
+            | DatumColumnDecoder::F64(_)
+            | DatumColumnDecoder::Numeric(_)
+            | DatumColumnDecoder::Time(_)
+            | DatumColumnDecoder::Interval(_)
+            | DatumColumnDecoder::Json(_)
+            | DatumColumnDecoder::Array { .. }
+            | DatumColumnDecoder::List { .. }
+            | DatumColumnDecoder::Map { .. }
+            | DatumColumnDecoder::RecordEmpty(_)
+            | DatumColumnDecoder::Record { .. }
+            | DatumColumnDecoder::Range(_)
+            | DatumColumnDecoder::MzAclItem(_)
+            | DatumColumnDecoder::AclItem(_) => false,
+        }
+    }
+
     fn goodbytes(&self) -> usize {
         match self {
             DatumColumnDecoder::Bool(a) => ArrayOrd::Bool(a.clone()).goodbytes(),
@@ -1404,6 +1444,28 @@ impl ColumnDecoder<Row> for RowColumnarDecoder {
                 .collect(),
         }
     }
+
+    fn bloom_filters(&self, cols: &BloomFilterColumns) -> BTreeMap<String, BloomFilter> {
+        let mut filters = BTreeMap::new();
+        let mut row = Row::default();
+        for (name, null_count, decoder) in &self.decoders {
+            if !cols.contains(name) || !decoder.supports_bloom_filter() {
+                continue;
+            }
+            let num_values = self.len - null_count.unwrap_or(0);
+            let mut filter = BloomFilter::new(num_values, cols.max_bytes);
+            for idx in 0..self.len {
+                // This packs the same bytes as `bloom_filter_key` would, without
+                // the allocation.
+                decoder.get(idx, &mut row.packer());
+                if !row.unpack_first().is_null() {
+                    filter.insert(row.data());
+                }
+            }
+            filters.insert(name.to_string(), filter);
+        }
+        filters
+    }
 }
 
 /// A [`ColumnEncoder`] for a [`Row`].
@@ -1609,7 +1671,10 @@ fn array_to_decoder(
             let array = downcast_array::<StringArray>(array)?;
             DatumColumnDecoder::String(array.clone())
         }
-        (DataType::Binary, ScalarType::Bytes) => {
+        (
+            DataType::Binary,
+            ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch,
+        ) => {
             let array = downcast_array::<BinaryArray>(array)?;
             DatumColumnDecoder::Bytes(array.clone())
         }
@@ -1779,7 +1844,9 @@ fn scalar_type_to_encoder(col_ty: &ScalarType) -> Result<DatumColumnEncoder, any
         | ScalarType::PgLegacyName
         | ScalarType::Char { .. }
         | ScalarType::VarChar { .. } => DatumColumnEncoder::String(StringBuilder::new()),
-        ScalarType::Bytes => DatumColumnEncoder::Bytes(BinaryBuilder::new()),
+        ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch => {
+            DatumColumnEncoder::Bytes(BinaryBuilder::new())
+        }
         ScalarType::Date => DatumColumnEncoder::Date(Int32Builder::new()),
         ScalarType::Time => DatumColumnEncoder::Time(FixedSizeBinaryBuilder::new(TIME_FIXED_BYTES)),
         ScalarType::Timestamp { .. } => {
@@ -38,6 +38,7 @@ use crate::adt::mz_acl_item::{AclItem, AclMode, MzAclItem};
 use crate::adt::numeric::{Numeric, NumericMaxScale};
 use crate::adt::pg_legacy_name::PgLegacyName;
 use crate::adt::range::{Range, RangeLowerBound, RangeUpperBound};
+use crate::adt::sketch::{HyperLogLog, QuantileSketch};
 use crate::adt::system::{Oid, PgLegacyChar, RegClass, RegProc, RegType};
 use crate::adt::timestamp::{
     CheckedTimestamp, HIGH_DATE, LOW_DATE, TimestampError, TimestampPrecision,
@@ -1008,6 +1009,8 @@ impl<'a> Datum<'a> {
                     (Datum::Interval(_), ScalarType::Interval) => true,
                     (Datum::Interval(_), _) => false,
                     (Datum::Bytes(_), ScalarType::Bytes) => true,
+                    (Datum::Bytes(_), ScalarType::HllSketch) => true,
+                    (Datum::Bytes(_), ScalarType::QuantileSketch) => true,
                     (Datum::Bytes(_), _) => false,
                     (Datum::String(_), ScalarType::String)
                     | (Datum::String(_), ScalarType::VarChar { .. })
@@ -1543,6 +1546,16 @@ pub enum ScalarType {
     MzAclItem,
     /// The type of [`Datum::AclItem`]
     AclItem,
+    /// A HyperLogLog sketch for approximate distinct counting.
+    ///
+    /// Stored as a [`Datum::Bytes`] holding the encoding of a
+    /// [`HyperLogLog`](crate::adt::sketch::HyperLogLog).
+    HllSketch,
+    /// A relative-error sketch for approximate quantiles.
+    ///
+    /// Stored as a [`Datum::Bytes`] holding the encoding of a
+    /// [`QuantileSketch`](crate::adt::sketch::QuantileSketch).
+    QuantileSketch,
 }
 
 impl RustType<ProtoRecordField> for (ColumnName, ColumnType) {
@@ -1635,6 +1648,8 @@ impl RustType<ProtoScalarType> for ScalarType {
                 })),
                 ScalarType::MzAclItem => MzAclItem(()),
                 ScalarType::AclItem => AclItem(()),
+                ScalarType::HllSketch => HllSketch(()),
+                ScalarType::QuantileSketch => QuantileSketch(()),
             }),
         }
     }
@@ -1721,6 +1736,8 @@ impl RustType<ProtoScalarType> for ScalarType {
             }),
             MzAclItem(()) => Ok(ScalarType::MzAclItem),
             AclItem(()) => Ok(ScalarType::AclItem),
+            HllSketch(()) => Ok(ScalarType::HllSketch),
+            QuantileSketch(()) => Ok(ScalarType::QuantileSketch),
         }
     }
 }
@@ -2509,6 +2526,60 @@ impl<'a, E> DatumType<'a, E> for MzAclItem {
     }
 }
 
+impl AsColumnType for HyperLogLog {
+    fn as_column_type() -> ColumnType {
+        ScalarType::HllSketch.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for HyperLogLog {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn fallible() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::Bytes(b)) => HyperLogLog::decode(b).map_err(|_| res),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::Bytes(temp_storage.push_bytes(self.encode())))
+    }
+}
+
+impl AsColumnType for QuantileSketch {
+    fn as_column_type() -> ColumnType {
+        ScalarType::QuantileSketch.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for QuantileSketch {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn fallible() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::Bytes(b)) => QuantileSketch::decode(b).map_err(|_| res),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::Bytes(temp_storage.push_bytes(self.encode())))
+    }
+}
+
 impl AsColumnType for AclItem {
     fn as_column_type() -> ColumnType {
         ScalarType::AclItem.nullable(false)
@@ -3417,6 +3488,32 @@ impl ScalarType {
         });
         // aclitem has no binary encoding so we can't test it here.
         static ACLITEM: LazyLock<Row> = LazyLock::new(|| Row::pack_slice(&[]));
+        static HLL_SKETCH: LazyLock<Row> = LazyLock::new(|| {
+            let empty = HyperLogLog::new().encode();
+            let mut sparse = HyperLogLog::new();
+            sparse.insert_hash(0);
+            sparse.insert_hash(u64::MAX);
+            let sparse = sparse.encode();
+            let mut dense = HyperLogLog::new();
+            for i in 0..10_000u64 {
+                dense.insert_hash(i.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            }
+            let dense = dense.encode();
+            Row::pack_slice(&[
+                Datum::Bytes(&empty),
+                Datum::Bytes(&sparse),
+                Datum::Bytes(&dense),
+            ])
+        });
+        static QUANTILE_SKETCH: LazyLock<Row> = LazyLock::new(|| {
+            let empty = QuantileSketch::new().encode();
+            let mut sketch = QuantileSketch::new();
+            for value in [0.0, -1.0, 1.0, f64::MIN, f64::MAX] {
+                sketch.insert(value).expect("finite value");
+            }
+            let sketch = sketch.encode();
+            Row::pack_slice(&[Datum::Bytes(&empty), Datum::Bytes(&sketch)])
+        });
 
         let iter: Box<dyn Iterator<Item = Datum<'static>>> = match self {
             ScalarType::Bool => Box::new((*BOOL).iter()),
@@ -3466,6 +3563,8 @@ impl ScalarType {
             ScalarType::Range { .. } => Box::new((*RANGE).iter()),
             ScalarType::MzAclItem { .. } => Box::new((*MZACLITEM).iter()),
             ScalarType::AclItem { .. } => Box::new((*ACLITEM).iter()),
+            ScalarType::HllSketch => Box::new((*HLL_SKETCH).iter()),
+            ScalarType::QuantileSketch => Box::new((*QUANTILE_SKETCH).iter()),
         };
 
         iter
@@ -3524,6 +3623,8 @@ impl ScalarType {
             ScalarType::Int2Vector,
             ScalarType::MzTimestamp,
             ScalarType::MzAclItem,
+            ScalarType::HllSketch,
+            ScalarType::QuantileSketch,
             // TODO: Fill in some variants of these.
             /*
             ScalarType::AclItem,
@@ -3584,7 +3685,9 @@ impl ScalarType {
             | ScalarType::Int2Vector
             | ScalarType::MzTimestamp
             | ScalarType::Range { .. }
-            | ScalarType::MzAclItem { .. }) => Ok(t),
+            | ScalarType::MzAclItem { .. }
+            | ScalarType::HllSketch
+            | ScalarType::QuantileSketch) => Ok(t),
 
             ScalarType::Array(elem) => Ok(elem.array_of_self_elem_type()?),
 
@@ -3647,6 +3750,8 @@ impl Arbitrary for ScalarType {
             Just(ScalarType::RegType).boxed(),
             Just(ScalarType::RegClass).boxed(),
             Just(ScalarType::Int2Vector).boxed(),
+            Just(ScalarType::HllSketch).boxed(),
+            Just(ScalarType::QuantileSketch).boxed(),
         ])
         // None of the leaf ScalarTypes types are really "simpler" than others
         // so don't waste time trying to shrink.
@@ -3941,6 +4046,24 @@ pub fn arb_datum_for_scalar(scalar_type: ScalarType) -> impl Strategy<Value = Pr
             .boxed(),
         ScalarType::AclItem => any::<AclItem>().prop_map(PropDatum::AclItem).boxed(),
         ScalarType::MzAclItem => any::<MzAclItem>().prop_map(PropDatum::MzAclItem).boxed(),
+        ScalarType::HllSketch => prop::collection::vec(any::<u64>(), 0..100)
+            .prop_map(|hashes| {
+                let mut sketch = HyperLogLog::new();
+                for hash in hashes {
+                    sketch.insert_hash(hash);
+                }
+                PropDatum::Bytes(sketch.encode())
+            })
+            .boxed(),
+        ScalarType::QuantileSketch => prop::collection::vec(-1e9..1e9f64, 0..100)
+            .prop_map(|values| {
+                let mut sketch = QuantileSketch::new();
+                for value in values {
+                    sketch.insert(value).expect("finite value");
+                }
+                PropDatum::Bytes(sketch.encode())
+            })
+            .boxed(),
         ScalarType::Range { element_type } => {
             let data_strat = (
                 arb_datum_for_scalar(*element_type.clone()),
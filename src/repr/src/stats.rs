@@ -25,7 +25,7 @@ use mz_persist_types::stats::bytes::{BytesStats, FixedSizeBytesStats, FixedSizeB
 use mz_persist_types::stats::json::{JsonMapElementStats, JsonStats};
 use mz_persist_types::stats::primitive::PrimitiveStats;
 use mz_persist_types::stats::{
-    AtomicBytesStats, ColumnNullStats, ColumnStatKinds, ColumnStats, ColumnarStats,
+    AtomicBytesStats, BloomFilter, ColumnNullStats, ColumnStatKinds, ColumnStats, ColumnarStats,
     PrimitiveStatsVariants,
 };
 use ordered_float::OrderedFloat;
@@ -41,7 +41,7 @@ use crate::adt::jsonb::{KeyClass, KeyClassifier, NumberParser};
 use crate::adt::numeric::{Numeric, PackedNumeric};
 use crate::adt::timestamp::{CheckedTimestamp, PackedNaiveDateTime};
 use crate::row::ProtoDatum;
-use crate::{Datum, RowArena, ScalarType};
+use crate::{Datum, Row, RowArena, ScalarType};
 
 fn soft_expect_or_log<A, B: Debug>(result: Result<A, B>) -> Option<A> {
     match result {
@@ -96,6 +96,60 @@ pub fn fixed_stats_from_column(
     .into()
 }
 
+/// Whether persist [`BloomFilter`]s over columns of this type can answer
+/// equality lookups.
+///
+/// This requires that equal values always have the same [`Row`] encoding,
+/// which rules out e.g. floats (`-0 = 0`), numerics (`1.0 = 1.00`), and
+/// blank-padded `char`s.
+pub fn bloom_filter_supported(typ: &ScalarType) -> bool {
+    match typ {
+        ScalarType::Bool
+        | ScalarType::Int16
+        | ScalarType::Int32
+        | ScalarType::Int64
+        | ScalarType::UInt16
+        | ScalarType::UInt32
+        | ScalarType::UInt64
+        | ScalarType::Oid
+        | ScalarType::RegClass
+        | ScalarType::RegProc
+        | ScalarType::RegType
+        | ScalarType::String
+        | ScalarType::VarChar { .. }
+        | ScalarType::PgLegacyName
+        | ScalarType::Bytes
+        | ScalarType::Date
+        | ScalarType::Timestamp { .. }
+        | ScalarType::TimestampTz { .. }
+        | ScalarType::MzTimestamp
+        | ScalarType::Uuid => true,
+        ScalarType::PgLegacyChar
+        | ScalarType::Float32
+        | ScalarType::Float64
+        | ScalarType::Numeric { .. }
+        | ScalarType::Time
+        | ScalarType::Interval
+        | ScalarType::Char { .. }
+        | ScalarType::Jsonb
+        | ScalarType::Array(_)
+        | ScalarType::List { .. }
+        | ScalarType::Map { .. }
+        | ScalarType::Record { .. }
+        | ScalarType::Int2Vector
+        | ScalarType::Range { .. }
+        | ScalarType::MzAclItem
+        | ScalarType::AclItem
+        | ScalarType::HllSketch
+        | ScalarType::QuantileSketch => false,
+    }
+}
+
+/// Returns the value that persist [`BloomFilter`]s are keyed by for `datum`.
+pub fn bloom_filter_key(datum: Datum) -> Row {
+    Row::pack_slice(&[datum])
+}
+
 /// Returns a `(lower, upper)` bound from the provided [`ColumnStatKinds`], if applicable.
 pub fn col_values<'a>(
     typ: &ScalarType,
@@ -172,9 +226,10 @@ pub fn col_values<'a>(
             | ScalarType::VarChar { .. },
             ColumnStatKinds::Primitive(String(stats)),
         ) => map_stats(stats, Datum::String),
-        (ScalarType::Bytes, ColumnStatKinds::Bytes(BytesStats::Primitive(stats))) => {
-            Some((Datum::Bytes(&stats.lower), Datum::Bytes(&stats.upper)))
-        }
+        (
+            ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch,
+            ColumnStatKinds::Bytes(BytesStats::Primitive(stats)),
+        ) => Some((Datum::Bytes(&stats.lower), Datum::Bytes(&stats.upper))),
         (ScalarType::Date, ColumnStatKinds::Primitive(I32(stats))) => {
             let lower = soft_expect_or_log(Date::from_pg_epoch(stats.lower))?;
             let upper = soft_expect_or_log(Date::from_pg_epoch(stats.upper))?;
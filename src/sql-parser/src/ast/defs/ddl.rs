@@ -890,6 +890,7 @@ pub enum CreateConnectionType {
     SqlServer,
     MySql,
     Yugabyte,
+    MongoDb,
 }
 
 impl AstDisplay for CreateConnectionType {
@@ -922,6 +923,9 @@ impl AstDisplay for CreateConnectionType {
             Self::Yugabyte => {
                 f.write_str("YUGABYTE");
             }
+            Self::MongoDb => {
+                f.write_str("MONGODB");
+            }
         }
     }
 }
@@ -1012,6 +1016,41 @@ pub struct KafkaSourceConfigOption<T: AstInfo> {
 impl_display_for_with_option!(KafkaSourceConfigOption);
 impl_display_t!(KafkaSourceConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KinesisSourceConfigOptionName {
+    Stream,
+}
+
+impl AstDisplay for KinesisSourceConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            KinesisSourceConfigOptionName::Stream => "STREAM",
+        })
+    }
+}
+impl_display!(KinesisSourceConfigOptionName);
+
+impl WithOptionName for KinesisSourceConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            KinesisSourceConfigOptionName::Stream => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KinesisSourceConfigOption<T: AstInfo> {
+    pub name: KinesisSourceConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(KinesisSourceConfigOption);
+impl_display_t!(KinesisSourceConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum KafkaSinkConfigOptionName {
     CompressionType,
@@ -1076,6 +1115,167 @@ pub struct KafkaSinkConfigOption<T: AstInfo> {
 impl_display_for_with_option!(KafkaSinkConfigOption);
 impl_display_t!(KafkaSinkConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IcebergSinkConfigOptionName {
+    Namespace,
+    Table,
+    Warehouse,
+}
+
+impl AstDisplay for IcebergSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            IcebergSinkConfigOptionName::Namespace => "NAMESPACE",
+            IcebergSinkConfigOptionName::Table => "TABLE",
+            IcebergSinkConfigOptionName::Warehouse => "WAREHOUSE",
+        })
+    }
+}
+impl_display!(IcebergSinkConfigOptionName);
+
+impl WithOptionName for IcebergSinkConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            IcebergSinkConfigOptionName::Namespace
+            | IcebergSinkConfigOptionName::Table
+            | IcebergSinkConfigOptionName::Warehouse => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IcebergSinkConfigOption<T: AstInfo> {
+    pub name: IcebergSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(IcebergSinkConfigOption);
+impl_display_t!(IcebergSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum S3SinkConfigOptionName {
+    Format,
+    MaxFileSize,
+    Uri,
+}
+
+impl AstDisplay for S3SinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            S3SinkConfigOptionName::Format => "FORMAT",
+            S3SinkConfigOptionName::MaxFileSize => "MAX FILE SIZE",
+            S3SinkConfigOptionName::Uri => "URI",
+        })
+    }
+}
+impl_display!(S3SinkConfigOptionName);
+
+impl WithOptionName for S3SinkConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            S3SinkConfigOptionName::Format
+            | S3SinkConfigOptionName::MaxFileSize
+            | S3SinkConfigOptionName::Uri => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct S3SinkConfigOption<T: AstInfo> {
+    pub name: S3SinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(S3SinkConfigOption);
+impl_display_t!(S3SinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PostgresSinkConfigOptionName {
+    ProgressTable,
+    Schema,
+    Table,
+}
+
+impl AstDisplay for PostgresSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            PostgresSinkConfigOptionName::ProgressTable => "PROGRESS TABLE",
+            PostgresSinkConfigOptionName::Schema => "SCHEMA",
+            PostgresSinkConfigOptionName::Table => "TABLE",
+        })
+    }
+}
+impl_display!(PostgresSinkConfigOptionName);
+
+impl WithOptionName for PostgresSinkConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            PostgresSinkConfigOptionName::ProgressTable
+            | PostgresSinkConfigOptionName::Schema
+            | PostgresSinkConfigOptionName::Table => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PostgresSinkConfigOption<T: AstInfo> {
+    pub name: PostgresSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(PostgresSinkConfigOption);
+impl_display_t!(PostgresSinkConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HttpSinkConfigOptionName {
+    Authorization,
+    Url,
+}
+
+impl AstDisplay for HttpSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            HttpSinkConfigOptionName::Authorization => "AUTHORIZATION",
+            HttpSinkConfigOptionName::Url => "URL",
+        })
+    }
+}
+impl_display!(HttpSinkConfigOptionName);
+
+impl WithOptionName for HttpSinkConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            HttpSinkConfigOptionName::Authorization => true,
+            HttpSinkConfigOptionName::Url => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpSinkConfigOption<T: AstInfo> {
+    pub name: HttpSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(HttpSinkConfigOption);
+impl_display_t!(HttpSinkConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PgConfigOptionName {
     /// Hex encoded string of binary serialization of
@@ -1241,12 +1441,54 @@ pub struct SqlServerConfigOption<T: AstInfo> {
 impl_display_for_with_option!(SqlServerConfigOption);
 impl_display_t!(SqlServerConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MongoDbConfigOptionName {
+    /// Hex encoded string of binary serialization of
+    /// `mz_storage_types::sources::mongodb::MongoDbSourceDetails`.
+    Details,
+}
+
+impl AstDisplay for MongoDbConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MongoDbConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(MongoDbConfigOptionName);
+
+impl WithOptionName for MongoDbConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            MongoDbConfigOptionName::Details => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FROM MONGODB CONNECTION ...` statement.
+pub struct MongoDbConfigOption<T: AstInfo> {
+    pub name: MongoDbConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(MongoDbConfigOption);
+impl_display_t!(MongoDbConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceConnection<T: AstInfo> {
     Kafka {
         connection: T::ItemName,
         options: Vec<KafkaSourceConfigOption<T>>,
     },
+    Kinesis {
+        connection: T::ItemName,
+        options: Vec<KinesisSourceConfigOption<T>>,
+    },
     Postgres {
         connection: T::ItemName,
         options: Vec<PgConfigOption<T>>,
@@ -1263,6 +1505,10 @@ pub enum CreateSourceConnection<T: AstInfo> {
         connection: T::ItemName,
         options: Vec<MySqlConfigOption<T>>,
     },
+    MongoDb {
+        connection: T::ItemName,
+        options: Vec<MongoDbConfigOption<T>>,
+    },
     LoadGenerator {
         generator: LoadGenerator,
         options: Vec<LoadGeneratorOption<T>>,
@@ -1284,6 +1530,18 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
+            CreateSourceConnection::Kinesis {
+                connection,
+                options,
+            } => {
+                f.write_str("KINESIS CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
             CreateSourceConnection::Postgres {
                 connection,
                 options,
@@ -1332,6 +1590,18 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
+            CreateSourceConnection::MongoDb {
+                connection,
+                options,
+            } => {
+                f.write_str("MONGODB CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
             CreateSourceConnection::LoadGenerator { generator, options } => {
                 f.write_str("LOAD GENERATOR ");
                 f.write_node(generator);
@@ -1467,6 +1737,27 @@ pub enum CreateSinkConnection<T: AstInfo> {
         key: Option<KafkaSinkKey>,
         headers: Option<Ident>,
     },
+    Iceberg {
+        /// The AWS connection to use to access the warehouse. Warehouses on
+        /// the local filesystem do not require a connection.
+        connection: Option<T::ItemName>,
+        options: Vec<IcebergSinkConfigOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
+    S3 {
+        connection: T::ItemName,
+        options: Vec<S3SinkConfigOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
+    Postgres {
+        connection: T::ItemName,
+        options: Vec<PostgresSinkConfigOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
+    Http {
+        options: Vec<HttpSinkConfigOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
@@ -1493,6 +1784,68 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                     f.write_node(headers);
                 }
             }
+            CreateSinkConnection::Iceberg {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("ICEBERG");
+                if let Some(connection) = connection {
+                    f.write_str(" CONNECTION ");
+                    f.write_node(connection);
+                }
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::S3 {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("S3 CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Postgres {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("POSTGRES CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Http { options, key } => {
+                f.write_str("HTTP");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
         }
     }
 }
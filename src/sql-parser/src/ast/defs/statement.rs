@@ -95,6 +95,7 @@ pub enum Statement<T: AstInfo> {
     Subscribe(SubscribeStatement<T>),
     ExplainPlan(ExplainPlanStatement<T>),
     ExplainPushdown(ExplainPushdownStatement<T>),
+    ExplainAnalyze(ExplainAnalyzeStatement<T>),
     ExplainTimestamp(ExplainTimestampStatement<T>),
     ExplainSinkSchema(ExplainSinkSchemaStatement<T>),
     Declare(DeclareStatement<T>),
@@ -171,6 +172,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Subscribe(stmt) => f.write_node(stmt),
             Statement::ExplainPlan(stmt) => f.write_node(stmt),
             Statement::ExplainPushdown(stmt) => f.write_node(stmt),
+            Statement::ExplainAnalyze(stmt) => f.write_node(stmt),
             Statement::ExplainTimestamp(stmt) => f.write_node(stmt),
             Statement::ExplainSinkSchema(stmt) => f.write_node(stmt),
             Statement::Declare(stmt) => f.write_node(stmt),
@@ -250,6 +252,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::Subscribe => "subscribe",
         StatementKind::ExplainPlan => "explain_plan",
         StatementKind::ExplainPushdown => "explain_pushdown",
+        StatementKind::ExplainAnalyze => "explain_analyze",
         StatementKind::ExplainTimestamp => "explain_timestamp",
         StatementKind::ExplainSinkSchema => "explain_sink_schema",
         StatementKind::Declare => "declare",
@@ -2168,6 +2171,8 @@ impl_display_t!(CreateTypeStatement);
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ClusterOptionName {
+    /// The `AUTO SUSPEND AFTER [[=] <interval>]` option.
+    AutoSuspendAfter,
     /// The `AVAILABILITY ZONES [[=] '[' <values> ']' ]` option.
     AvailabilityZones,
     /// The `DISK` option.
@@ -2193,6 +2198,7 @@ pub enum ClusterOptionName {
 impl AstDisplay for ClusterOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
+            ClusterOptionName::AutoSuspendAfter => f.write_str("AUTO SUSPEND AFTER"),
             ClusterOptionName::AvailabilityZones => f.write_str("AVAILABILITY ZONES"),
             ClusterOptionName::Disk => f.write_str("DISK"),
             ClusterOptionName::IntrospectionDebugging => f.write_str("INTROSPECTION DEBUGGING"),
@@ -2215,7 +2221,8 @@ impl WithOptionName for ClusterOptionName {
     /// on the conservative side and return `true`.
     fn redact_value(&self) -> bool {
         match self {
-            ClusterOptionName::AvailabilityZones
+            ClusterOptionName::AutoSuspendAfter
+            | ClusterOptionName::AvailabilityZones
             | ClusterOptionName::Disk
             | ClusterOptionName::IntrospectionDebugging
             | ClusterOptionName::IntrospectionInterval
@@ -4033,6 +4040,86 @@ impl<T: AstInfo> AstDisplay for ExplainPushdownStatement<T> {
 }
 impl_display_t!(ExplainPushdownStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplainAnalyzeStatement<T: AstInfo> {
+    /// The runtime properties to report. Empty means all of them.
+    pub properties: Vec<ExplainAnalyzeProperty>,
+    /// Whether to report how unevenly the properties are spread over workers.
+    pub skew: bool,
+    pub format: Option<ExplainAnalyzeFormat>,
+    pub explainee: Explainee<T>,
+}
+
+impl<T: AstInfo> ExplainAnalyzeStatement<T> {
+    pub fn properties(&self) -> Vec<ExplainAnalyzeProperty> {
+        if self.properties.is_empty() {
+            vec![ExplainAnalyzeProperty::Memory, ExplainAnalyzeProperty::Cpu]
+        } else {
+            self.properties.clone()
+        }
+    }
+
+    pub fn format(&self) -> ExplainAnalyzeFormat {
+        self.format.unwrap_or(ExplainAnalyzeFormat::Text)
+    }
+}
+
+impl<T: AstInfo> AstDisplay for ExplainAnalyzeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("EXPLAIN ANALYZE");
+        if !self.properties.is_empty() {
+            f.write_str(" ");
+            f.write_node(&display::comma_separated(&self.properties));
+        }
+        if self.skew {
+            f.write_str(" WITH SKEW");
+        }
+        if let Some(format) = &self.format {
+            f.write_str(" AS ");
+            f.write_node(format);
+        }
+        f.write_str(" FOR ");
+        f.write_node(&self.explainee);
+    }
+}
+impl_display_t!(ExplainAnalyzeStatement);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExplainAnalyzeProperty {
+    /// The memory held by arrangements, and the number of records in them.
+    Memory,
+    /// The time spent in the dataflow operators.
+    Cpu,
+}
+
+impl AstDisplay for ExplainAnalyzeProperty {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            Self::Memory => f.write_str("MEMORY"),
+            Self::Cpu => f.write_str("CPU"),
+        }
+    }
+}
+impl_display!(ExplainAnalyzeProperty);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExplainAnalyzeFormat {
+    /// The physical plan, with the measurements annotated on its operators.
+    Text,
+    /// The physical plan as JSON, with the measurements keyed by LIR ID.
+    Json,
+}
+
+impl AstDisplay for ExplainAnalyzeFormat {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            Self::Text => f.write_str("TEXT"),
+            Self::Json => f.write_str("JSON"),
+        }
+    }
+}
+impl_display!(ExplainAnalyzeFormat);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainTimestampStatement<T: AstInfo> {
     pub format: Option<ExplainFormat>,
@@ -4354,6 +4441,10 @@ pub enum ClusterScheduleOptionValue {
     Refresh {
         hydration_time_estimate: Option<IntervalValue>,
     },
+    Utilization {
+        min_size: String,
+        max_size: String,
+    },
 }
 
 impl Default for ClusterScheduleOptionValue {
@@ -4379,6 +4470,13 @@ impl AstDisplay for ClusterScheduleOptionValue {
                     f.write_str(")");
                 }
             }
+            ClusterScheduleOptionValue::Utilization { min_size, max_size } => {
+                f.write_str("ON UTILIZATION (MIN SIZE = '");
+                f.write_node(&display::escape_single_quote_string(min_size));
+                f.write_str("', MAX SIZE = '");
+                f.write_node(&display::escape_single_quote_string(max_size));
+                f.write_str("')");
+            }
         }
     }
 }
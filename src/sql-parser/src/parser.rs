@@ -751,6 +751,29 @@ impl<'a> Parser<'a> {
             ));
         }
 
+        let within_group_pos = self.peek_pos();
+        let within_group = if self.parse_keywords(&[WITHIN, GROUP]) {
+            if distinct {
+                return Err(self.error(
+                    within_group_pos,
+                    "cannot use DISTINCT with WITHIN GROUP".to_string(),
+                ));
+            }
+            if matches!(&args, FunctionArgs::Args { order_by, .. } if !order_by.is_empty()) {
+                return Err(self.error(
+                    within_group_pos,
+                    "cannot use multiple ORDER BY clauses with WITHIN GROUP".to_string(),
+                ));
+            }
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[ORDER, BY])?;
+            let within_group = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+            within_group
+        } else {
+            vec![]
+        };
+
         let filter = if self.parse_keyword(FILTER) {
             self.expect_token(&Token::LParen)?;
             self.expect_keyword(WHERE)?;
@@ -806,6 +829,7 @@ impl<'a> Parser<'a> {
             filter,
             over,
             distinct,
+            within_group,
         })
     }
 
@@ -970,6 +994,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1033,6 +1058,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1051,6 +1077,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1246,6 +1273,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: vec![],
                     }))
                 }
                 COLLATE => Ok(Expr::Collate {
@@ -1381,6 +1409,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -2362,9 +2391,9 @@ impl<'a> Parser<'a> {
             TO => true,
             _ => unreachable!(),
         };
-        let connection_type = match self
-            .expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH, SQL, MYSQL, YUGABYTE])?
-        {
+        let connection_type = match self.expect_one_of_keywords(&[
+            AWS, KAFKA, CONFLUENT, POSTGRES, SSH, SQL, MYSQL, YUGABYTE, MONGODB,
+        ])? {
             AWS => {
                 if self.parse_keyword(PRIVATELINK) {
                     CreateConnectionType::AwsPrivatelink
@@ -2388,6 +2417,7 @@ impl<'a> Parser<'a> {
             }
             MYSQL => CreateConnectionType::MySql,
             YUGABYTE => CreateConnectionType::Yugabyte,
+            MONGODB => CreateConnectionType::MongoDb,
             _ => unreachable!(),
         };
         if expect_paren {
@@ -2538,6 +2568,19 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_kinesis_source_config_option(
+        &mut self,
+    ) -> Result<KinesisSourceConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[STREAM])? {
+            STREAM => KinesisSourceConfigOptionName::Stream,
+            _ => unreachable!(),
+        };
+        Ok(KinesisSourceConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_kafka_sink_config_option(
         &mut self,
     ) -> Result<KafkaSinkConfigOption<Raw>, ParserError> {
@@ -2607,6 +2650,67 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_iceberg_sink_config_option(
+        &mut self,
+    ) -> Result<IcebergSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[NAMESPACE, TABLE, WAREHOUSE])? {
+            NAMESPACE => IcebergSinkConfigOptionName::Namespace,
+            TABLE => IcebergSinkConfigOptionName::Table,
+            WAREHOUSE => IcebergSinkConfigOptionName::Warehouse,
+            _ => unreachable!(),
+        };
+        Ok(IcebergSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_s3_sink_config_option(&mut self) -> Result<S3SinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[FORMAT, MAX, URI])? {
+            FORMAT => S3SinkConfigOptionName::Format,
+            MAX => {
+                self.expect_keywords(&[FILE, SIZE])?;
+                S3SinkConfigOptionName::MaxFileSize
+            }
+            URI => S3SinkConfigOptionName::Uri,
+            _ => unreachable!(),
+        };
+        Ok(S3SinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_postgres_sink_config_option(
+        &mut self,
+    ) -> Result<PostgresSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PROGRESS, SCHEMA, TABLE])? {
+            PROGRESS => {
+                self.expect_keyword(TABLE)?;
+                PostgresSinkConfigOptionName::ProgressTable
+            }
+            SCHEMA => PostgresSinkConfigOptionName::Schema,
+            TABLE => PostgresSinkConfigOptionName::Table,
+            _ => unreachable!(),
+        };
+        Ok(PostgresSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_http_sink_config_option(&mut self) -> Result<HttpSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[AUTHORIZATION, URL])? {
+            AUTHORIZATION => HttpSinkConfigOptionName::Authorization,
+            URL => HttpSinkConfigOptionName::Url,
+            _ => unreachable!(),
+        };
+        Ok(HttpSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_connection_option_name(&mut self) -> Result<ConnectionOptionName, ParserError> {
         Ok(
             match self.expect_one_of_keywords(&[
@@ -3260,7 +3364,9 @@ impl<'a> Parser<'a> {
     fn parse_create_source_connection(
         &mut self,
     ) -> Result<CreateSourceConnection<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[KAFKA, POSTGRES, SQL, MYSQL, LOAD, YUGABYTE])? {
+        match self.expect_one_of_keywords(&[
+            KAFKA, KINESIS, POSTGRES, SQL, MYSQL, MONGODB, LOAD, YUGABYTE,
+        ])? {
             POSTGRES => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -3331,6 +3437,24 @@ impl<'a> Parser<'a> {
                     options,
                 })
             }
+            MONGODB => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_mongodb_connection_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::MongoDb {
+                    connection,
+                    options,
+                })
+            }
             KAFKA => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -3349,6 +3473,24 @@ impl<'a> Parser<'a> {
                     options,
                 })
             }
+            KINESIS => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_kinesis_source_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::Kinesis {
+                    connection,
+                    options,
+                })
+            }
             LOAD => {
                 self.expect_keyword(GENERATOR)?;
                 let generator = match self.expect_one_of_keywords(&[
@@ -3466,6 +3608,16 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_mongodb_connection_option(&mut self) -> Result<MongoDbConfigOption<Raw>, ParserError> {
+        match self.expect_one_of_keywords(&[DETAILS])? {
+            DETAILS => Ok(MongoDbConfigOption {
+                name: MongoDbConfigOptionName::Details,
+                value: self.parse_optional_option_value()?,
+            }),
+            _ => unreachable!(),
+        }
+    }
+
     fn parse_sql_server_connection_option(
         &mut self,
     ) -> Result<SqlServerConfigOption<Raw>, ParserError> {
@@ -3585,6 +3737,19 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        if self.parse_keyword(ICEBERG) {
+            return self.parse_create_iceberg_sink_connection();
+        }
+        if self.parse_keyword(S3) {
+            return self.parse_create_s3_sink_connection();
+        }
+        if self.parse_keyword(POSTGRES) {
+            return self.parse_create_postgres_sink_connection();
+        }
+        if self.parse_keyword(HTTP) {
+            return self.parse_create_http_sink_connection();
+        }
+
         self.expect_keyword(KAFKA)?;
         self.expect_keyword(CONNECTION)?;
 
@@ -3598,27 +3763,7 @@ impl<'a> Parser<'a> {
             vec![]
         };
 
-        // one token of lookahead:
-        // * `KEY (` means we're parsing a list of columns for the key
-        // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
-        let key =
-            if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
-                let _ = self.expect_keyword(KEY);
-                let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
-
-                let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
-                    let _ = self.expect_keywords(&[NOT, ENFORCED])?;
-                    true
-                } else {
-                    false
-                };
-                Some(KafkaSinkKey {
-                    key_columns,
-                    not_enforced,
-                })
-            } else {
-                None
-            };
+        let key = self.parse_sink_key()?;
 
         let headers = if self.parse_keyword(HEADERS) {
             Some(self.parse_identifier()?)
@@ -3634,6 +3779,118 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_create_iceberg_sink_connection(
+        &mut self,
+    ) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let connection = if self.parse_keyword(CONNECTION) {
+            Some(self.parse_raw_name()?)
+        } else {
+            None
+        };
+
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_iceberg_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
+        let key = self.parse_sink_key()?;
+
+        Ok(CreateSinkConnection::Iceberg {
+            connection,
+            options,
+            key,
+        })
+    }
+
+    fn parse_create_s3_sink_connection(
+        &mut self,
+    ) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        self.expect_keyword(CONNECTION)?;
+        let connection = self.parse_raw_name()?;
+
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_s3_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
+        let key = self.parse_sink_key()?;
+
+        Ok(CreateSinkConnection::S3 {
+            connection,
+            options,
+            key,
+        })
+    }
+
+    fn parse_create_postgres_sink_connection(
+        &mut self,
+    ) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        self.expect_keyword(CONNECTION)?;
+        let connection = self.parse_raw_name()?;
+
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_postgres_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
+        let key = self.parse_sink_key()?;
+
+        Ok(CreateSinkConnection::Postgres {
+            connection,
+            options,
+            key,
+        })
+    }
+
+    fn parse_create_http_sink_connection(
+        &mut self,
+    ) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        let options = if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_http_sink_config_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
+        let key = self.parse_sink_key()?;
+
+        Ok(CreateSinkConnection::Http { options, key })
+    }
+
+    /// Parses the optional `KEY (<columns>) [NOT ENFORCED]` clause of a sink.
+    fn parse_sink_key(&mut self) -> Result<Option<KafkaSinkKey>, ParserError> {
+        // one token of lookahead:
+        // * `KEY (` means we're parsing a list of columns for the key
+        // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
+        if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
+            let _ = self.expect_keyword(KEY);
+            let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
+
+            let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
+                let _ = self.expect_keywords(&[NOT, ENFORCED])?;
+                true
+            } else {
+                false
+            };
+            Ok(Some(KafkaSinkKey {
+                key_columns,
+                not_enforced,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn parse_create_view(&mut self) -> Result<Statement<Raw>, ParserError> {
         let mut if_exists = if self.parse_keyword(OR) {
             self.expect_keyword(REPLACE)?;
@@ -4334,6 +4591,7 @@ impl<'a> Parser<'a> {
 
     fn parse_cluster_option_name(&mut self) -> Result<ClusterOptionName, ParserError> {
         let option = self.expect_one_of_keywords(&[
+            AUTO,
             AVAILABILITY,
             DISK,
             INTROSPECTION,
@@ -4345,6 +4603,10 @@ impl<'a> Parser<'a> {
             WORKLOAD,
         ])?;
         let name = match option {
+            AUTO => {
+                self.expect_keywords(&[SUSPEND, AFTER])?;
+                ClusterOptionName::AutoSuspendAfter
+            }
             AVAILABILITY => {
                 self.expect_keyword(ZONES)?;
                 ClusterOptionName::AvailabilityZones
@@ -4456,22 +4718,37 @@ impl<'a> Parser<'a> {
         let kw = self.expect_one_of_keywords(&[MANUAL, ON])?;
         let value = match kw {
             MANUAL => ClusterScheduleOptionValue::Manual,
-            ON => {
-                self.expect_keyword(REFRESH)?;
-                // Parse optional `(HYDRATION TIME ESTIMATE ...)`
-                let hydration_time_estimate = if self.consume_token(&Token::LParen) {
-                    self.expect_keywords(&[HYDRATION, TIME, ESTIMATE])?;
+            ON => match self.expect_one_of_keywords(&[REFRESH, UTILIZATION])? {
+                REFRESH => {
+                    // Parse optional `(HYDRATION TIME ESTIMATE ...)`
+                    let hydration_time_estimate = if self.consume_token(&Token::LParen) {
+                        self.expect_keywords(&[HYDRATION, TIME, ESTIMATE])?;
+                        let _ = self.consume_token(&Token::Eq);
+                        let interval = self.parse_interval_value()?;
+                        self.expect_token(&Token::RParen)?;
+                        Some(interval)
+                    } else {
+                        None
+                    };
+                    ClusterScheduleOptionValue::Refresh {
+                        hydration_time_estimate,
+                    }
+                }
+                UTILIZATION => {
+                    // Parse mandatory `(MIN SIZE ..., MAX SIZE ...)`
+                    self.expect_token(&Token::LParen)?;
+                    self.expect_keywords(&[MIN, SIZE])?;
+                    let _ = self.consume_token(&Token::Eq);
+                    let min_size = self.parse_literal_string()?;
+                    self.expect_token(&Token::Comma)?;
+                    self.expect_keywords(&[MAX, SIZE])?;
                     let _ = self.consume_token(&Token::Eq);
-                    let interval = self.parse_interval_value()?;
+                    let max_size = self.parse_literal_string()?;
                     self.expect_token(&Token::RParen)?;
-                    Some(interval)
-                } else {
-                    None
-                };
-                ClusterScheduleOptionValue::Refresh {
-                    hydration_time_estimate,
+                    ClusterScheduleOptionValue::Utilization { min_size, max_size }
                 }
-            }
+                _ => unreachable!(),
+            },
             _ => unreachable!(),
         };
         Ok(ClusterOption {
@@ -8134,6 +8411,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: vec![],
                     },
                     alias,
                     with_ordinality,
@@ -8206,6 +8484,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: vec![],
                     },
                     alias,
                     with_ordinality,
@@ -8635,6 +8914,9 @@ impl<'a> Parser<'a> {
         } else if self.parse_keywords(&[FILTER, PUSHDOWN]) {
             self.parse_explain_pushdown()
                 .map_parser_err(StatementKind::ExplainPushdown)
+        } else if self.parse_keyword(ANALYZE) {
+            self.parse_explain_analyze()
+                .map_parser_err(StatementKind::ExplainAnalyze)
         } else if self.peek_keyword(KEY) || self.peek_keyword(VALUE) {
             self.parse_explain_schema()
                 .map_parser_err(StatementKind::ExplainSinkSchema)
@@ -8829,6 +9111,45 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parse an `EXPLAIN ANALYZE` statement, assuming that the `EXPLAIN
+    /// ANALYZE` tokens have already been consumed.
+    fn parse_explain_analyze(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let properties = if self.peek_one_of_keywords(&[MEMORY, CPU]) {
+            self.parse_comma_separated(|parser| {
+                match parser.expect_one_of_keywords(&[MEMORY, CPU])? {
+                    MEMORY => Ok(ExplainAnalyzeProperty::Memory),
+                    CPU => Ok(ExplainAnalyzeProperty::Cpu),
+                    _ => unreachable!(),
+                }
+            })?
+        } else {
+            vec![]
+        };
+
+        let skew = self.parse_keywords(&[WITH, SKEW]);
+
+        let format = if self.parse_keyword(AS) {
+            match self.expect_one_of_keywords(&[TEXT, JSON])? {
+                TEXT => Some(ExplainAnalyzeFormat::Text),
+                JSON => Some(ExplainAnalyzeFormat::Json),
+                _ => unreachable!(),
+            }
+        } else {
+            None
+        };
+
+        self.expect_keyword(FOR)?;
+
+        let explainee = self.parse_explainee()?;
+
+        Ok(Statement::ExplainAnalyze(ExplainAnalyzeStatement {
+            properties,
+            skew,
+            format,
+            explainee,
+        }))
+    }
+
     /// Parse an `EXPLAIN TIMESTAMP` statement, assuming that the `EXPLAIN
     /// TIMESTAMP` tokens have already been consumed.
     fn parse_explain_timestamp(&mut self) -> Result<Statement<Raw>, ParserError> {
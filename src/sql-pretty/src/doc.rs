@@ -761,7 +761,11 @@ impl Pretty {
                     // Nullary, don't allow newline between parens, so just delegate.
                     return self.doc_display_pass(v);
                 }
-                if v.filter.is_some() || v.over.is_some() || !order_by.is_empty() {
+                if v.filter.is_some()
+                    || v.over.is_some()
+                    || !order_by.is_empty()
+                    || !v.within_group.is_empty()
+                {
                     return self.doc_display(v, "function filter or over or order by");
                 }
                 let special = match v.name.to_ast_string_stable().as_str() {
@@ -997,6 +997,8 @@ pub enum CatalogType<T: TypeReference> {
     VarChar,
     Int2Vector,
     MzAclItem,
+    HllSketch,
+    QuantileSketch,
 }
 
 impl CatalogType<IdReference> {
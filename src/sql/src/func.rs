@@ -78,7 +78,9 @@ impl TypeCategory {
             | ScalarType::Bytes
             | ScalarType::Jsonb
             | ScalarType::Uuid
-            | ScalarType::MzAclItem => Self::UserDefined,
+            | ScalarType::MzAclItem
+            | ScalarType::HllSketch
+            | ScalarType::QuantileSketch => Self::UserDefined,
             ScalarType::Date
             | ScalarType::Time
             | ScalarType::Timestamp { .. }
@@ -152,7 +154,9 @@ impl TypeCategory {
             | CatalogType::Bytes
             | CatalogType::Jsonb
             | CatalogType::Uuid
-            | CatalogType::MzAclItem => Self::UserDefined,
+            | CatalogType::MzAclItem
+            | CatalogType::HllSketch
+            | CatalogType::QuantileSketch => Self::UserDefined,
             CatalogType::Date
             | CatalogType::Time
             | CatalogType::Timestamp
@@ -890,6 +894,8 @@ impl From<ScalarBaseType> for ParamType {
             Int2Vector => ScalarType::Int2Vector,
             MzTimestamp => ScalarType::MzTimestamp,
             MzAclItem => ScalarType::MzAclItem,
+            HllSketch => ScalarType::HllSketch,
+            QuantileSketch => ScalarType::QuantileSketch,
         };
         ParamType::Plain(s)
     }
@@ -2167,9 +2173,42 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
                     }).flatten().collect()))
             }) => Jsonb, 3273;
         },
+        "jsonb_insert" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => jsonb_modify_func(VariadicFunc::JsonbInsert, vec![(Datum::False, ScalarType::Bool)]) => Jsonb, oid::FUNC_JSONB_INSERT_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => VariadicFunc::JsonbInsert => Jsonb, 3579;
+        },
+        "jsonb_path_exists" => Scalar {
+            params!(Jsonb, String) => jsonb_path_func(VariadicFunc::JsonbPathExists, false) => Bool, oid::FUNC_JSONB_PATH_EXISTS_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathExists, false) => Bool, oid::FUNC_JSONB_PATH_EXISTS_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => VariadicFunc::JsonbPathExists => Bool, oid::FUNC_JSONB_PATH_EXISTS_VARS_SILENT_OID;
+        },
+        "jsonb_path_match" => Scalar {
+            params!(Jsonb, String) => jsonb_path_func(VariadicFunc::JsonbPathMatch, false) => Bool, oid::FUNC_JSONB_PATH_MATCH_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathMatch, false) => Bool, oid::FUNC_JSONB_PATH_MATCH_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => VariadicFunc::JsonbPathMatch => Bool, oid::FUNC_JSONB_PATH_MATCH_VARS_SILENT_OID;
+        },
+        "jsonb_path_query_array" => Scalar {
+            params!(Jsonb, String) => jsonb_path_func(VariadicFunc::JsonbPathQueryArray, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathQueryArray, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => VariadicFunc::JsonbPathQueryArray => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_VARS_SILENT_OID;
+        },
+        "jsonb_path_query_first" => Scalar {
+            params!(Jsonb, String) => jsonb_path_func(VariadicFunc::JsonbPathQueryFirst, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathQueryFirst, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => VariadicFunc::JsonbPathQueryFirst => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_VARS_SILENT_OID;
+        },
         "jsonb_pretty" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbPretty(func::JsonbPretty) => String, 3306;
         },
+        "jsonb_set" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => jsonb_modify_func(VariadicFunc::JsonbSet, vec![(Datum::True, ScalarType::Bool)]) => Jsonb, oid::FUNC_JSONB_SET_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => VariadicFunc::JsonbSet => Jsonb, 3304;
+        },
+        "jsonb_set_lax" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => jsonb_modify_func(VariadicFunc::JsonbSetLax, vec![(Datum::True, ScalarType::Bool), (Datum::String("use_json_null"), ScalarType::String)]) => Jsonb, oid::FUNC_JSONB_SET_LAX_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => jsonb_modify_func(VariadicFunc::JsonbSetLax, vec![(Datum::True, ScalarType::Bool), (Datum::String("use_json_null"), ScalarType::String)]) => Jsonb, oid::FUNC_JSONB_SET_LAX_CREATE_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool, String) => VariadicFunc::JsonbSetLax => Jsonb, oid::FUNC_JSONB_SET_LAX_CREATE_TREATMENT_OID;
+        },
         "jsonb_strip_nulls" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbStripNulls(func::JsonbStripNulls) => Jsonb, 3262;
         },
@@ -3114,6 +3153,24 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
                 Ok((e, AggregateFunc::JsonbObjectAgg { order_by }))
             }) => Jsonb, 3270;
         },
+        "mode" => Aggregate {
+            params!(AnyElement) => Operation::unary_ordered(|_ecx, value, order_by| {
+                Ok((value, AggregateFunc::Mode { order_by }))
+            }) => AnyElement, 3978;
+        },
+        "percentile_cont" => Aggregate {
+            params!(Float64, Float64) => Operation::binary_ordered(|_ecx, fraction, value, order_by| {
+                Ok((percentile_input(fraction, value), AggregateFunc::PercentileCont { order_by }))
+            }) => Float64, 3974;
+            params!(Float64, Interval) => Operation::binary_ordered(|_ecx, fraction, value, order_by| {
+                Ok((percentile_input(fraction, value), AggregateFunc::PercentileCont { order_by }))
+            }) => Interval, 3976;
+        },
+        "percentile_disc" => Aggregate {
+            params!(Float64, AnyElement) => Operation::binary_ordered(|_ecx, fraction, value, order_by| {
+                Ok((percentile_input(fraction, value), AggregateFunc::PercentileDisc { order_by }))
+            }) => AnyElement, 3972;
+        },
         "string_agg" => Aggregate {
             params!(String, String) => Operation::binary_ordered(|_ecx, value, sep, order_by| {
                 let e = HirScalarExpr::call_variadic(
@@ -3159,6 +3216,21 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "dense_rank" => ScalarWindow {
             params!() => ScalarWindowFunc::DenseRank => Int64, 3102;
         },
+        "percent_rank" => ScalarWindow {
+            params!() => ScalarWindowFunc::PercentRank => Float64, 3103;
+        },
+        "cume_dist" => ScalarWindow {
+            params!() => ScalarWindowFunc::CumeDist => Float64, 3104;
+        },
+        "ntile" => ValueWindow {
+            params!(Int32) => Operation::unary(|_ecx, buckets| {
+                let buckets = positive_window_func_arg(
+                    buckets,
+                    "argument of ntile must be greater than zero",
+                );
+                Ok((buckets, ValueWindowFunc::Ntile))
+            }) => Int32, 3105;
+        },
         "lag" => ValueWindow {
             // All args are encoded into a single record to be handled later
             params!(AnyElement) => Operation::unary(|ecx, e| {
@@ -3229,6 +3301,22 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
         "last_value" => ValueWindow {
             params!(AnyElement) => ValueWindowFunc::LastValue => AnyElement, 3113;
         },
+        "nth_value" => ValueWindow {
+            // Both args are encoded into a single record to be handled later
+            params!(AnyElement, Int32) => Operation::binary(|_ecx, e, n| {
+                let n = positive_window_func_arg(
+                    n,
+                    "argument of nth_value must be greater than zero",
+                );
+                let e = HirScalarExpr::call_variadic(
+                    VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("expr"), ColumnName::from("n")],
+                    },
+                    vec![e, n],
+                );
+                Ok((e, ValueWindowFunc::NthValue))
+            }) => AnyElement, 3114;
+        },
 
         // Table functions.
         "generate_series" => Table {
@@ -3355,6 +3443,11 @@ pub static PG_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
                 })
             }) => ReturnType::set_of(String.into()), 3931;
         },
+        "jsonb_path_query" => Table {
+            params!(Jsonb, String) => jsonb_path_query_func() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path_query_func() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => jsonb_path_query_func() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_VARS_SILENT_OID;
+        },
         // Note that these implementations' input to `generate_series` is
         // contrived to match Flink's expected values. There are other,
         // equally valid windows we could generate.
@@ -3490,6 +3583,29 @@ pub static MZ_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
     use ParamType::*;
     use ScalarBaseType::*;
     builtins! {
+        "approx_count_distinct" => Aggregate {
+            params!(Any) => Operation::nullary(|_ecx| catalog_name_only!("approx_count_distinct")) => Int64, oid::FUNC_APPROX_COUNT_DISTINCT_OID;
+        },
+        "approx_percentile" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("approx_percentile")) => Float64, oid::FUNC_APPROX_PERCENTILE_OID;
+        },
+        "approx_percentile_accumulate" => Aggregate {
+            params!(Float64) => Operation::unary(|_ecx, e| {
+                let sketch = e.call_unary(UnaryFunc::QuantileSketchFloat64(func::QuantileSketchFloat64));
+                Ok((sketch, AggregateFunc::QuantileSketchMerge))
+            }) => QuantileSketch, oid::FUNC_APPROX_PERCENTILE_ACCUMULATE_OID;
+        },
+        "approx_percentile_combine" => Aggregate {
+            params!(QuantileSketch) => Operation::unary(|_ecx, e| {
+                // Validate stored sketches as they enter the aggregation, which cannot report
+                // errors itself.
+                let sketch = e.call_unary(UnaryFunc::CastBytesToQuantileSketch(func::CastBytesToQuantileSketch));
+                Ok((sketch, AggregateFunc::QuantileSketchMerge))
+            }) => QuantileSketch, oid::FUNC_APPROX_PERCENTILE_COMBINE_OID;
+        },
+        "approx_percentile_estimate" => Scalar {
+            params!(QuantileSketch, Float64) => BinaryFunc::QuantileSketchEstimate => Float64, oid::FUNC_APPROX_PERCENTILE_ESTIMATE_OID;
+        },
         "constant_time_eq" => Scalar {
             params!(Bytes, Bytes) => BinaryFunc::ConstantTimeEqBytes => Bool, oid::FUNC_CONSTANT_TIME_EQ_BYTES_OID;
             params!(String, String) => BinaryFunc::ConstantTimeEqString => Bool, oid::FUNC_CONSTANT_TIME_EQ_STRING_OID;
@@ -3662,6 +3778,20 @@ pub static MZ_CATALOG_BUILTINS: LazyLock<BTreeMap<&'static str, Func>> = LazyLoc
             params!(String, String) => sql_impl_func("has_type_privilege(current_user, $1, $2)") => Bool, 3142;
             params!(Oid, String) => sql_impl_func("has_type_privilege(current_user, $1, $2)") => Bool, 3143;
         },
+        "hll_accumulate" => Aggregate {
+            params!(Any) => Operation::unary(|_ecx, e| {
+                Ok((e.call_unary(UnaryFunc::HllSketch(func::HllSketch)), AggregateFunc::HllMerge))
+            }) => HllSketch, oid::FUNC_HLL_ACCUMULATE_OID;
+        },
+        "hll_combine" => Aggregate {
+            params!(HllSketch) => Operation::unary(|_ecx, e| {
+                // See `approx_percentile_combine` about validating sketches.
+                Ok((e.call_unary(UnaryFunc::CastBytesToHllSketch(func::CastBytesToHllSketch)), AggregateFunc::HllMerge))
+            }) => HllSketch, oid::FUNC_HLL_COMBINE_OID;
+        },
+        "hll_estimate" => Scalar {
+            params!(HllSketch) => UnaryFunc::HllEstimate(func::HllEstimate) => Int64, oid::FUNC_HLL_ESTIMATE_OID;
+        },
         "kafka_murmur2" => Scalar {
             params!(String) => UnaryFunc::KafkaMurmur2String(func::KafkaMurmur2String) => Int32, oid::FUNC_KAFKA_MURMUR2_STRING_OID;
             params!(Bytes) => UnaryFunc::KafkaMurmur2Bytes(func::KafkaMurmur2Bytes) => Int32, oid::FUNC_KAFKA_MURMUR2_BYTES_OID;
@@ -4743,6 +4873,9 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
         "#>" => Scalar {
             params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String))) => JsonbGetPath => Jsonb, 3213;
         },
+        "#-" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String))) => JsonbDeletePath => Jsonb, 3287;
+        },
         "#>>" => Scalar {
             params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String))) => JsonbGetPathStringify => String, 3206;
         },
@@ -4817,6 +4950,12 @@ pub static OP_IMPLS: LazyLock<BTreeMap<&'static str, Func>> = LazyLock::new(|| {
         "?|" => Scalar {
             params!(MapAny, ScalarType::Array(Box::new(ScalarType::String))) => MapContainsAnyKeys => Bool, oid::OP_CONTAINS_ANY_KEYS_MAP_OID;
         },
+        "@?" => Scalar {
+            params!(Jsonb, String) => jsonb_path_func(VariadicFunc::JsonbPathExists, true) => Bool, oid::OP_JSONB_PATH_EXISTS_OID;
+        },
+        "@@" => Scalar {
+            params!(Jsonb, String) => jsonb_path_func(VariadicFunc::JsonbPathMatch, true) => Bool, oid::OP_JSONB_PATH_MATCH_OID;
+        },
         "&&" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeOverlaps => Bool, 3888;
         },
@@ -5067,3 +5206,111 @@ fn current_settings(
     );
     Ok(expr)
 }
+
+/// Plans a call to the `jsonb` modification function `func`, whose first three
+/// arguments are required. Omitted trailing arguments are filled in from
+/// `defaults`, which holds the defaults of all optional arguments.
+fn jsonb_modify_func(
+    func: VariadicFunc,
+    defaults: Vec<(Datum<'static>, ScalarType)>,
+) -> Operation<HirScalarExpr> {
+    Operation::variadic(move |_ecx, mut exprs| {
+        let omitted = &defaults[exprs.len() - 3..];
+        exprs.extend(
+            omitted
+                .iter()
+                .map(|(datum, typ)| HirScalarExpr::literal(*datum, typ.clone())),
+        );
+        Ok(HirScalarExpr::call_variadic(func.clone(), exprs))
+    })
+}
+
+/// Appends the defaults of the optional `vars` and `silent` arguments of a
+/// SQL/JSON path function to `exprs`.
+fn jsonb_path_default_args(exprs: &mut Vec<HirScalarExpr>, silent: bool) {
+    if exprs.len() < 3 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::empty_map(),
+            ScalarType::Jsonb,
+        ));
+    }
+    if exprs.len() < 4 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::from(silent),
+            ScalarType::Bool,
+        ));
+    }
+}
+
+/// Plans a call to the SQL/JSON path function `func`, which can omit the
+/// `vars` and `silent` arguments. Omitted `silent` arguments are set to
+/// `silent`.
+fn jsonb_path_func(func: VariadicFunc, silent: bool) -> Operation<HirScalarExpr> {
+    Operation::variadic(move |_ecx, mut exprs| {
+        jsonb_path_default_args(&mut exprs, silent);
+        Ok(HirScalarExpr::call_variadic(func.clone(), exprs))
+    })
+}
+
+/// Plans a call to the `jsonb_path_query` table function, which can omit the
+/// `vars` and `silent` arguments.
+fn jsonb_path_query_func() -> Operation<TableFuncPlan> {
+    Operation::variadic(|_ecx, mut exprs| {
+        jsonb_path_default_args(&mut exprs, false);
+        Ok(TableFuncPlan {
+            expr: HirRelationExpr::CallTable {
+                func: TableFunc::JsonbPathQuery,
+                exprs,
+            },
+            column_names: vec!["jsonb_path_query".into()],
+        })
+    })
+}
+
+/// Wraps the integer argument of `ntile` or `nth_value`, erroring with `err_msg`
+/// if it is not positive.
+fn positive_window_func_arg(n: HirScalarExpr, err_msg: &'static str) -> HirScalarExpr {
+    let zero = HirScalarExpr::literal(Datum::Int32(0), ScalarType::Int32);
+    HirScalarExpr::if_then_else(
+        n.clone().call_binary(zero, BinaryFunc::Lte),
+        HirScalarExpr::call_variadic(
+            VariadicFunc::ErrorIfNull,
+            vec![
+                HirScalarExpr::literal_null(ScalarType::Int32),
+                HirScalarExpr::literal(Datum::String(err_msg), ScalarType::String),
+            ],
+        ),
+        n,
+    )
+}
+
+/// Builds the `(value, fraction)` record aggregated by `percentile_cont` and
+/// `percentile_disc`, erroring if the fraction is not between 0 and 1.
+fn percentile_input(fraction: HirScalarExpr, value: HirScalarExpr) -> HirScalarExpr {
+    let zero = HirScalarExpr::literal(Datum::from(0.0f64), ScalarType::Float64);
+    let one = HirScalarExpr::literal(Datum::from(1.0f64), ScalarType::Float64);
+    let out_of_range = fraction
+        .clone()
+        .call_binary(zero, BinaryFunc::Lt)
+        .or(fraction.clone().call_binary(one, BinaryFunc::Gt));
+    let fraction = HirScalarExpr::if_then_else(
+        out_of_range,
+        HirScalarExpr::call_variadic(
+            VariadicFunc::ErrorIfNull,
+            vec![
+                HirScalarExpr::literal_null(ScalarType::Float64),
+                HirScalarExpr::literal(
+                    Datum::String("percentile value is not between 0 and 1"),
+                    ScalarType::String,
+                ),
+            ],
+        ),
+        fraction,
+    );
+    HirScalarExpr::call_variadic(
+        VariadicFunc::RecordCreate {
+            field_names: vec![ColumnName::from("value"), ColumnName::from("fraction")],
+        },
+        vec![value, fraction],
+    )
+}
@@ -2106,6 +2106,11 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                 filter: node.filter.map(|expr| Box::new(self.fold_expr(*expr))),
                 over: node.over.map(|over| self.fold_window_spec(over)),
                 distinct: node.distinct,
+                within_group: node
+                    .within_group
+                    .into_iter()
+                    .map(|obe| self.fold_order_by_expr(obe))
+                    .collect(),
             }
         })
     }
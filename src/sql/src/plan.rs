@@ -61,8 +61,8 @@ use mz_ssh_util::keys::SshKeyPair;
 use mz_storage_types::connections::aws::AwsConnection;
 use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::{
-    AwsPrivatelinkConnection, CsrConnection, KafkaConnection, MySqlConnection, PostgresConnection,
-    SqlServerConnectionDetails, SshConnection,
+    AwsPrivatelinkConnection, CsrConnection, KafkaConnection, MongoDbConnection, MySqlConnection,
+    PostgresConnection, SqlServerConnectionDetails, SshConnection,
 };
 use mz_storage_types::instances::StorageInstanceId;
 use mz_storage_types::sinks::{S3SinkFormat, SinkEnvelope, StorageSinkConnection};
@@ -73,8 +73,8 @@ use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
 use crate::ast::{
-    ExplainStage, Expr, FetchDirection, NoticeSeverity, Raw, Statement, StatementKind,
-    TransactionAccessMode,
+    ExplainAnalyzeProperty, ExplainStage, Expr, FetchDirection, NoticeSeverity, Raw, Statement,
+    StatementKind, TransactionAccessMode,
 };
 use crate::catalog::{
     CatalogType, DefaultPrivilegeAclItem, DefaultPrivilegeObject, IdReference, ObjectType,
@@ -171,6 +171,7 @@ pub enum Plan {
     CopyTo(CopyToPlan),
     ExplainPlan(ExplainPlanPlan),
     ExplainPushdown(ExplainPushdownPlan),
+    ExplainAnalyze(ExplainAnalyzePlan),
     ExplainTimestamp(ExplainTimestampPlan),
     ExplainSinkSchema(ExplainSinkSchemaPlan),
     Insert(InsertPlan),
@@ -291,6 +292,7 @@ impl Plan {
             StatementKind::Execute => &[PlanKind::Execute],
             StatementKind::ExplainPlan => &[PlanKind::ExplainPlan],
             StatementKind::ExplainPushdown => &[PlanKind::ExplainPushdown],
+            StatementKind::ExplainAnalyze => &[PlanKind::ExplainAnalyze],
             StatementKind::ExplainTimestamp => &[PlanKind::ExplainTimestamp],
             StatementKind::ExplainSinkSchema => &[PlanKind::ExplainSinkSchema],
             StatementKind::Fetch => &[PlanKind::Fetch],
@@ -384,6 +386,7 @@ impl Plan {
             Plan::CopyTo(_) => "copy to",
             Plan::ExplainPlan(_) => "explain plan",
             Plan::ExplainPushdown(_) => "EXPLAIN FILTER PUSHDOWN",
+            Plan::ExplainAnalyze(_) => "EXPLAIN ANALYZE",
             Plan::ExplainTimestamp(_) => "explain timestamp",
             Plan::ExplainSinkSchema(_) => "explain schema",
             Plan::Insert(_) => "insert",
@@ -493,6 +496,7 @@ impl Plan {
             Plan::CopyTo(_) => true,
             Plan::ExplainPlan(_) => true,
             Plan::ExplainPushdown(_) => true,
+            Plan::ExplainAnalyze(_) => true,
             Plan::ExplainTimestamp(_) => true,
             Plan::ExplainSinkSchema(_) => true,
             Plan::ValidateConnection(_) => true,
@@ -579,6 +583,7 @@ pub struct CreateClusterManagedPlan {
     pub disk: bool,
     pub optimizer_feature_overrides: OptimizerFeatureOverrides,
     pub schedule: ClusterSchedule,
+    pub auto_suspend_after: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -630,6 +635,9 @@ pub enum ClusterSchedule {
     /// `hydration_time_estimate` determines how much time before a refresh to turn the
     /// cluster On, so that it can rehydrate already before the refresh time.
     Refresh { hydration_time_estimate: Duration },
+    /// The replica size of the cluster is adjusted based on the sustained CPU and memory
+    /// utilization of its replicas, staying within `min_size` and `max_size`.
+    Utilization { min_size: String, max_size: String },
 }
 
 impl Default for ClusterSchedule {
@@ -1105,6 +1113,17 @@ pub struct ExplainPushdownPlan {
     pub explainee: Explainee,
 }
 
+#[derive(Clone, Debug)]
+pub struct ExplainAnalyzePlan {
+    /// The runtime properties to report.
+    pub properties: Vec<ExplainAnalyzeProperty>,
+    /// Whether to report how unevenly the properties are spread over workers.
+    pub skew: bool,
+    pub format: ExplainFormat,
+    pub config: ExplainConfig,
+    pub explainee: Explainee,
+}
+
 #[derive(Clone, Debug)]
 pub struct ExplainTimestampPlan {
     pub format: ExplainFormat,
@@ -1614,6 +1633,7 @@ pub enum ConnectionDetails {
     AwsPrivatelink(AwsPrivatelinkConnection),
     MySql(MySqlConnection<ReferencedConnection>),
     SqlServer(SqlServerConnectionDetails<ReferencedConnection>),
+    MongoDb(MongoDbConnection),
 }
 
 impl ConnectionDetails {
@@ -1639,6 +1659,9 @@ impl ConnectionDetails {
             ConnectionDetails::SqlServer(c) => {
                 mz_storage_types::connections::Connection::SqlServer(c.clone())
             }
+            ConnectionDetails::MongoDb(c) => {
+                mz_storage_types::connections::Connection::MongoDb(c.clone())
+            }
         }
     }
 }
@@ -1913,6 +1936,8 @@ pub enum CopyFormat {
     Csv,
     Binary,
     Parquet,
+    Json,
+    Avro,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -1945,6 +1970,7 @@ pub struct PlanClusterOption {
     pub size: AlterOptionParameter,
     pub disk: AlterOptionParameter<bool>,
     pub schedule: AlterOptionParameter<ClusterSchedule>,
+    pub auto_suspend_after: AlterOptionParameter<OptionalDuration>,
     pub workload_class: AlterOptionParameter<Option<String>>,
 }
 
@@ -1960,6 +1986,7 @@ impl Default for PlanClusterOption {
             size: AlterOptionParameter::Unchanged,
             disk: AlterOptionParameter::Unchanged,
             schedule: AlterOptionParameter::Unchanged,
+            auto_suspend_after: AlterOptionParameter::Unchanged,
             workload_class: AlterOptionParameter::Unchanged,
         }
     }
@@ -43,7 +43,8 @@ use crate::plan::scope::ScopeItem;
 use crate::plan::typeconv::CastContext;
 use crate::pure::error::{
     CsrPurificationError, KafkaSinkPurificationError, KafkaSourcePurificationError,
-    LoadGeneratorSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
+    KinesisSourcePurificationError, LoadGeneratorSourcePurificationError,
+    MongoDbSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
     SqlServerSourcePurificationError,
 };
 use crate::session::vars::VarError;
@@ -170,6 +171,9 @@ pub enum PlanError {
     SqlServerConnectionErr {
         cause: Arc<SqlServerError>,
     },
+    MongoDbConnectionErr {
+        cause: Arc<mongodb::error::Error>,
+    },
     SubsourceNameConflict {
         name: UnresolvedItemName,
         upstream_references: Vec<UnresolvedItemName>,
@@ -254,6 +258,8 @@ pub enum PlanError {
     CsrPurification(CsrPurificationError),
     MySqlSourcePurification(MySqlSourcePurificationError),
     SqlServerSourcePurificationError(SqlServerSourcePurificationError),
+    MongoDbSourcePurification(MongoDbSourcePurificationError),
+    KinesisSourcePurification(KinesisSourcePurificationError),
     UseTablesForSources(String),
     MissingName(CatalogItemType),
     InvalidRefreshAt,
@@ -339,6 +345,8 @@ impl PlanError {
             Self::PgSourcePurification(e) => e.detail(),
             Self::MySqlSourcePurification(e) => e.detail(),
             Self::SqlServerSourcePurificationError(e) => e.detail(),
+            Self::MongoDbSourcePurification(e) => e.detail(),
+            Self::KinesisSourcePurification(e) => e.detail(),
             Self::KafkaSourcePurification(e) => e.detail(),
             Self::LoadGeneratorSourcePurification(e) => e.detail(),
             Self::CsrPurification(e) => e.detail(),
@@ -449,6 +457,8 @@ impl PlanError {
             Self::PgSourcePurification(e) => e.hint(),
             Self::MySqlSourcePurification(e) => e.hint(),
             Self::SqlServerSourcePurificationError(e) => e.hint(),
+            Self::MongoDbSourcePurification(e) => e.hint(),
+            Self::KinesisSourcePurification(e) => e.hint(),
             Self::KafkaSourcePurification(e) => e.hint(),
             Self::LoadGeneratorSourcePurification(e) => e.hint(),
             Self::CsrPurification(e) => e.hint(),
@@ -641,6 +651,9 @@ impl fmt::Display for PlanError {
             Self::SqlServerConnectionErr { cause } => {
                 write!(f, "failed to connect to SQL Server database: {}", cause)
             }
+            Self::MongoDbConnectionErr { cause } => {
+                write!(f, "failed to connect to MongoDB deployment: {}", cause)
+            }
             Self::SubsourceNameConflict {
                 name , upstream_references: _,
             } => {
@@ -755,6 +768,8 @@ impl fmt::Display for PlanError {
             Self::CsrPurification(e) => write!(f, "CONFLUENT SCHEMA REGISTRY validation: {}", e),
             Self::MySqlSourcePurification(e) => write!(f, "MYSQL source validation: {}", e),
             Self::SqlServerSourcePurificationError(e) => write!(f, "SQL SERVER source validation: {}", e),
+            Self::MongoDbSourcePurification(e) => write!(f, "MONGODB source validation: {}", e),
+            Self::KinesisSourcePurification(e) => write!(f, "KINESIS source validation: {}", e),
             Self::UseTablesForSources(command) => write!(f, "{command} not supported; use CREATE TABLE .. FROM SOURCE instead"),
             Self::MangedReplicaName(name) => {
                 write!(f, "{name} is reserved for replicas of managed clusters")
@@ -916,6 +931,12 @@ impl From<SqlServerError> for PlanError {
     }
 }
 
+impl From<mongodb::error::Error> for PlanError {
+    fn from(e: mongodb::error::Error) -> PlanError {
+        PlanError::MongoDbConnectionErr { cause: Arc::new(e) }
+    }
+}
+
 impl From<VarError> for PlanError {
     fn from(e: VarError) -> Self {
         PlanError::VarError(e)
@@ -964,6 +985,18 @@ impl From<SqlServerSourcePurificationError> for PlanError {
     }
 }
 
+impl From<MongoDbSourcePurificationError> for PlanError {
+    fn from(e: MongoDbSourcePurificationError) -> Self {
+        PlanError::MongoDbSourcePurification(e)
+    }
+}
+
+impl From<KinesisSourcePurificationError> for PlanError {
+    fn from(e: KinesisSourcePurificationError) -> Self {
+        PlanError::KinesisSourcePurification(e)
+    }
+}
+
 impl From<IdentError> for PlanError {
     fn from(e: IdentError) -> Self {
         PlanError::InvalidIdent(e)
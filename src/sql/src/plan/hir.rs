@@ -483,6 +483,8 @@ impl ScalarWindowExpr {
             ScalarWindowFunc::RowNumber => {}
             ScalarWindowFunc::Rank => {}
             ScalarWindowFunc::DenseRank => {}
+            ScalarWindowFunc::PercentRank => {}
+            ScalarWindowFunc::CumeDist => {}
         }
         Ok(())
     }
@@ -496,6 +498,8 @@ impl ScalarWindowExpr {
             ScalarWindowFunc::RowNumber => {}
             ScalarWindowFunc::Rank => {}
             ScalarWindowFunc::DenseRank => {}
+            ScalarWindowFunc::PercentRank => {}
+            ScalarWindowFunc::CumeDist => {}
         }
         Ok(())
     }
@@ -520,6 +524,12 @@ impl ScalarWindowExpr {
             ScalarWindowFunc::DenseRank => mz_expr::AggregateFunc::DenseRank {
                 order_by: self.order_by,
             },
+            ScalarWindowFunc::PercentRank => mz_expr::AggregateFunc::PercentRank {
+                order_by: self.order_by,
+            },
+            ScalarWindowFunc::CumeDist => mz_expr::AggregateFunc::CumeDist {
+                order_by: self.order_by,
+            },
         }
     }
 }
@@ -530,6 +540,8 @@ pub enum ScalarWindowFunc {
     RowNumber,
     Rank,
     DenseRank,
+    PercentRank,
+    CumeDist,
 }
 
 impl Display for ScalarWindowFunc {
@@ -538,6 +550,8 @@ impl Display for ScalarWindowFunc {
             ScalarWindowFunc::RowNumber => write!(f, "row_number"),
             ScalarWindowFunc::Rank => write!(f, "rank"),
             ScalarWindowFunc::DenseRank => write!(f, "dense_rank"),
+            ScalarWindowFunc::PercentRank => write!(f, "percent_rank"),
+            ScalarWindowFunc::CumeDist => write!(f, "cume_dist"),
         }
     }
 }
@@ -548,6 +562,8 @@ impl ScalarWindowFunc {
             ScalarWindowFunc::RowNumber => ScalarType::Int64.nullable(false),
             ScalarWindowFunc::Rank => ScalarType::Int64.nullable(false),
             ScalarWindowFunc::DenseRank => ScalarType::Int64.nullable(false),
+            ScalarWindowFunc::PercentRank => ScalarType::Float64.nullable(false),
+            ScalarWindowFunc::CumeDist => ScalarType::Float64.nullable(false),
         }
     }
 }
@@ -574,6 +590,8 @@ impl Display for ValueWindowFunc {
             ValueWindowFunc::Lead => write!(f, "lead"),
             ValueWindowFunc::FirstValue => write!(f, "first_value"),
             ValueWindowFunc::LastValue => write!(f, "last_value"),
+            ValueWindowFunc::Ntile => write!(f, "ntile"),
+            ValueWindowFunc::NthValue => write!(f, "nth_value"),
             ValueWindowFunc::Fused(funcs) => write!(f, "fused[{}]", separated(", ", funcs)),
         }
     }
@@ -654,6 +672,8 @@ pub enum ValueWindowFunc {
     Lead,
     FirstValue,
     LastValue,
+    Ntile,
+    NthValue,
     Fused(Vec<ValueWindowFunc>),
 }
 
@@ -669,6 +689,14 @@ impl ValueWindowFunc {
             ValueWindowFunc::FirstValue | ValueWindowFunc::LastValue => {
                 input_type.scalar_type.nullable(true)
             }
+            // The input is the number of buckets
+            ValueWindowFunc::Ntile => ScalarType::Int32.nullable(true),
+            ValueWindowFunc::NthValue => {
+                // The input is a (value, n) record, so extract the type of the first arg
+                input_type.scalar_type.unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(true)
+            }
             ValueWindowFunc::Fused(funcs) => {
                 let input_types = input_type.scalar_type.unwrap_record_element_column_type();
                 ScalarType::Record {
@@ -710,6 +738,12 @@ impl ValueWindowFunc {
                 order_by,
                 window_frame,
             },
+            // The window frame doesn't affect ntile
+            ValueWindowFunc::Ntile => mz_expr::AggregateFunc::Ntile { order_by },
+            ValueWindowFunc::NthValue => mz_expr::AggregateFunc::NthValue {
+                order_by,
+                window_frame,
+            },
             ValueWindowFunc::Fused(funcs) => mz_expr::AggregateFunc::FusedValueWindowFunc {
                 funcs: funcs
                     .into_iter()
@@ -1195,6 +1229,28 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Computes a continuous percentile. Accepts `Datum::List`s whose first
+    /// element is a `(value, fraction)` record. The other elements are the
+    /// `WITHIN GROUP` columns used by `order_by`.
+    PercentileCont {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Computes a discrete percentile. Accepts `Datum::List`s whose first
+    /// element is a `(value, fraction)` record. The other elements are the
+    /// `WITHIN GROUP` columns used by `order_by`.
+    PercentileDisc {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Computes the most frequent value. Accepts `Datum::List`s whose first
+    /// element is the value. The other elements are the `WITHIN GROUP` columns
+    /// used by `order_by`.
+    Mode {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Merges `hll_sketch` values.
+    HllMerge,
+    /// Merges `quantile_sketch` values.
+    QuantileSketchMerge,
     /// A bundle of fused window aggregations: its input is a record, whose each
     /// component will be the input to one of the `AggregateFunc`s.
     ///
@@ -1278,6 +1334,15 @@ impl AggregateFunc {
                 mz_expr::AggregateFunc::ListConcat { order_by }
             }
             AggregateFunc::StringAgg { order_by } => mz_expr::AggregateFunc::StringAgg { order_by },
+            AggregateFunc::PercentileCont { order_by } => {
+                mz_expr::AggregateFunc::PercentileCont { order_by }
+            }
+            AggregateFunc::PercentileDisc { order_by } => {
+                mz_expr::AggregateFunc::PercentileDisc { order_by }
+            }
+            AggregateFunc::Mode { order_by } => mz_expr::AggregateFunc::Mode { order_by },
+            AggregateFunc::HllMerge => mz_expr::AggregateFunc::HllMerge,
+            AggregateFunc::QuantileSketchMerge => mz_expr::AggregateFunc::QuantileSketchMerge,
             // `AggregateFunc::FusedWindowAgg` should be specially handled in
             // `AggregateWindowExpr::into_expr`.
             AggregateFunc::FusedWindowAgg { funcs: _ } => {
@@ -1347,7 +1412,12 @@ impl AggregateFunc {
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
             | AggregateFunc::MapAgg { .. }
-            | AggregateFunc::StringAgg { .. } => Datum::Null,
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::HllMerge
+            | AggregateFunc::QuantileSketchMerge => Datum::Null,
             AggregateFunc::FusedWindowAgg { funcs: _ } => {
                 // `identity_datum` is used only in HIR planning, and `FusedWindowAgg` can't occur
                 // in HIR planning, because it is introduced only during HIR transformation.
@@ -1376,6 +1446,16 @@ impl AggregateFunc {
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. } => {
+                // The input is wrapped in a Record whose first field is a (value, fraction) Record.
+                let payload = input_type.scalar_type.unwrap_record_element_type()[0];
+                payload.unwrap_record_element_type()[0].clone()
+            }
+            AggregateFunc::Mode { .. } => {
+                input_type.scalar_type.unwrap_record_element_type()[0].clone()
+            }
+            AggregateFunc::HllMerge => ScalarType::HllSketch,
+            AggregateFunc::QuantileSketchMerge => ScalarType::QuantileSketch,
             AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Numeric {
                 max_scale: Some(NumericMaxScale::ZERO),
@@ -1460,6 +1540,19 @@ impl AggregateFunc {
                 | ArrayConcat { .. }
                 | ListConcat { .. }
                 | StringAgg { .. }
+                | PercentileCont { .. }
+                | PercentileDisc { .. }
+                | Mode { .. }
+        )
+    }
+
+    /// Reports whether this is an ordered-set aggregate, which must be called
+    /// with a `WITHIN GROUP` clause.
+    pub fn is_ordered_set(&self) -> bool {
+        use AggregateFunc::*;
+        matches!(
+            self,
+            PercentileCont { .. } | PercentileDisc { .. } | Mode { .. }
         )
     }
 }
@@ -3250,6 +3250,7 @@ fn plan_table_function_internal(
         filter,
         over,
         distinct,
+        within_group,
     }: &Function<Aug>,
     with_ordinality: bool,
     table_name: Option<FullItemName>,
@@ -3257,6 +3258,10 @@ fn plan_table_function_internal(
     assert_none!(filter, "cannot parse table function with FILTER");
     assert_none!(over, "cannot parse table function with OVER");
     assert!(!*distinct, "cannot parse table function with DISTINCT");
+    assert!(
+        within_group.is_empty(),
+        "cannot parse table function with WITHIN GROUP"
+    );
 
     let ecx = &ExprContext {
         qcx,
@@ -5051,8 +5056,9 @@ fn plan_aggregate_common(
         name,
         args,
         filter,
-        over: _,
+        over,
         distinct,
+        within_group,
     }: &Function<Aug>,
 ) -> Result<AggregateExpr, PlanError> {
     // Normal aggregate functions, like `sum`, expect as input a single expression
@@ -5082,8 +5088,20 @@ fn plan_aggregate_common(
     // rules to all aggregates, not just `count`, since we may one day support
     // user-defined aggregates, including user-defined aggregates that take no
     // parameters.
+    //
+    // Ordered-set aggregates, like `percentile_cont`, instead take the
+    // expressions of their `WITHIN GROUP` clause as arguments following the
+    // direct arguments, and sort by those expressions.
     let (args, order_by) = match &args {
         FunctionArgs::Star => (vec![], vec![]),
+        FunctionArgs::Args { args, order_by: _ } if !within_group.is_empty() => {
+            let mut args = plan_exprs(ecx, args)?;
+            args.extend(plan_exprs(
+                ecx,
+                &within_group.iter().map(|obe| &obe.expr).collect::<Vec<_>>(),
+            )?);
+            (args, within_group.clone())
+        }
         FunctionArgs::Args { args, order_by } => {
             if args.is_empty() {
                 sql_bail!(
@@ -5102,6 +5120,30 @@ fn plan_aggregate_common(
     let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &order_by)?;
 
     let (mut expr, func) = func::select_impl(ecx, FuncSpec::Func(name), impls, args, col_orders)?;
+    match (func.is_ordered_set(), within_group.is_empty()) {
+        (true, true) => sql_bail!(
+            "WITHIN GROUP is required for ordered-set aggregate {}",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        ),
+        (false, false) => sql_bail!(
+            "{} is not an ordered-set aggregate, so it cannot have WITHIN GROUP",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        ),
+        (true, false) if over.is_some() => bail_unsupported!(format!(
+            "OVER for ordered-set aggregate {}",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        )),
+        _ => {}
+    }
     if let Some(filter) = &filter {
         // If a filter is present, as in
         //
@@ -5273,6 +5315,7 @@ fn plan_function<'a>(
         filter,
         over,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<HirScalarExpr, PlanError> {
     let impls = match resolve_func(ecx, name, args)? {
@@ -5458,6 +5501,15 @@ fn plan_function<'a>(
                 .expect("already resolved")
         );
     }
+    if !within_group.is_empty() {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("already resolved")
+        );
+    }
 
     let scalar_args = match &args {
         FunctionArgs::Star => {
@@ -5666,6 +5718,7 @@ fn plan_window_function_non_aggr<'a>(
         filter,
         over,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<
     (
@@ -5688,6 +5741,13 @@ fn plan_window_function_non_aggr<'a>(
         );
     }
 
+    if !within_group.is_empty() {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            name
+        );
+    }
+
     if filter.is_some() {
         bail_unsupported!("FILTER in non-aggregate window functions");
     }
@@ -6104,6 +6164,8 @@ pub fn scalar_type_from_catalog(
                 CatalogType::Uuid => Ok(ScalarType::Uuid),
                 CatalogType::Int2Vector => Ok(ScalarType::Int2Vector),
                 CatalogType::MzAclItem => Ok(ScalarType::MzAclItem),
+                CatalogType::HllSketch => Ok(ScalarType::HllSketch),
+                CatalogType::QuantileSketch => Ok(ScalarType::QuantileSketch),
                 CatalogType::Numeric => unreachable!("handled above"),
                 CatalogType::Char => unreachable!("handled above"),
                 CatalogType::VarChar => unreachable!("handled above"),
@@ -6184,6 +6246,7 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                     filter,
                     over: _,
                     distinct: _,
+                    within_group,
                 } = func;
                 if let Some(filter) = filter {
                     self.visit_expr_mut(filter);
@@ -6194,6 +6257,9 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                     .push("aggregate function calls");
 
                 self.visit_function_args_mut(args);
+                for obe in within_group {
+                    self.visit_order_by_expr_mut(obe);
+                }
 
                 self.within_aggregate = old_within_aggregate;
                 self.table_disallowed_context.pop();
@@ -6251,17 +6317,20 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                 filter: None,
                 over: None,
                 distinct: false,
+                within_group,
             } = &func
             {
-                // Identical table functions can be de-duplicated.
-                let unique_id = self.id_gen.allocate_id();
-                let id = self
-                    .tables
-                    .entry(func)
-                    .or_insert_with(|| format!("table_func_{unique_id}"));
-                // We know this is okay because id is is 11 characters + <=20 characters, which is
-                // less than our max length.
-                *expr = Expr::Identifier(vec![Ident::new_unchecked(id.clone())]);
+                if within_group.is_empty() {
+                    // Identical table functions can be de-duplicated.
+                    let unique_id = self.id_gen.allocate_id();
+                    let id = self
+                        .tables
+                        .entry(func)
+                        .or_insert_with(|| format!("table_func_{unique_id}"));
+                    // We know this is okay because id is is 11 characters + <=20 characters, which is
+                    // less than our max length.
+                    *expr = Expr::Identifier(vec![Ident::new_unchecked(id.clone())]);
+                }
             }
         }
         if let Some(context) = disallowed_context {
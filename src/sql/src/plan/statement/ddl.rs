@@ -20,20 +20,24 @@ use std::time::Duration;
 use itertools::{Either, Itertools};
 use mz_adapter_types::compaction::{CompactionWindow, DEFAULT_LOGICAL_COMPACTION_WINDOW_DURATION};
 use mz_adapter_types::dyncfgs::ENABLE_MULTI_REPLICA_SOURCES;
+use mz_arrow_util::builder::ArrowBuilder;
 use mz_auth::password::Password;
 use mz_controller_types::{ClusterId, DEFAULT_REPLICA_LOGGING_INTERVAL, ReplicaId};
 use mz_expr::{CollectionPlan, UnmaterializableFunc};
 use mz_interchange::avro::{AvroSchemaGenerator, DocTarget};
+use mz_interchange::protobuf;
 use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_ore::collections::{CollectionExt, HashSet};
 use mz_ore::num::NonNeg;
 use mz_ore::soft_panic_or_log;
 use mz_ore::str::StrExt;
 use mz_ore::vec::VecExt;
+use mz_pgcopy::CopyFormatParams;
 use mz_postgres_util::tunnel::PostgresFlavor;
 use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
+use mz_repr::bytes::ByteSize;
 use mz_repr::network_policy_id::NetworkPolicyId;
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::refresh_schedule::{RefreshEvery, RefreshSchedule};
@@ -69,26 +73,34 @@ use mz_sql_parser::ast::{
     CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CsrConfigOption,
     CsrConfigOptionName, CsrConnection, CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf,
     CsvColumns, DeferredItemName, DocOnIdentifier, DocOnSchema, DropObjectsStatement,
-    DropOwnedStatement, Expr, Format, FormatSpecifier, Ident, IfExistsBehavior, IndexOption,
-    IndexOptionName, KafkaSinkConfigOption, KeyConstraint, LoadGeneratorOption,
-    LoadGeneratorOptionName, MaterializedViewOption, MaterializedViewOptionName, MySqlConfigOption,
-    MySqlConfigOptionName, NetworkPolicyOption, NetworkPolicyOptionName,
-    NetworkPolicyRuleDefinition, NetworkPolicyRuleOption, NetworkPolicyRuleOptionName,
-    PgConfigOption, PgConfigOptionName, ProtobufSchema, QualifiedReplica, RefreshAtOptionValue,
-    RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
-    ReplicaOptionName, RoleAttribute, SetRoleVar, SourceErrorPolicy, SourceIncludeMetadata,
-    SqlServerConfigOption, SqlServerConfigOptionName, Statement, TableConstraint,
-    TableFromSourceColumns, TableFromSourceOption, TableFromSourceOptionName, TableOption,
-    TableOptionName, UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName,
-    UnresolvedSchemaName, Value, ViewDefinition, WithOptionValue,
+    DropOwnedStatement, Expr, Format, FormatSpecifier, HttpSinkConfigOption,
+    HttpSinkConfigOptionName, IcebergSinkConfigOption, IcebergSinkConfigOptionName, Ident,
+    IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint,
+    KinesisSourceConfigOption, KinesisSourceConfigOptionName, LoadGeneratorOption,
+    LoadGeneratorOptionName, MaterializedViewOption, MaterializedViewOptionName,
+    MongoDbConfigOption, MongoDbConfigOptionName, MySqlConfigOption, MySqlConfigOptionName,
+    NetworkPolicyOption, NetworkPolicyOptionName, NetworkPolicyRuleDefinition,
+    NetworkPolicyRuleOption, NetworkPolicyRuleOptionName, PgConfigOption, PgConfigOptionName,
+    PostgresSinkConfigOption, PostgresSinkConfigOptionName, ProtobufSchema, QualifiedReplica,
+    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition,
+    ReplicaOption, ReplicaOptionName, RoleAttribute, S3SinkConfigOption, S3SinkConfigOptionName,
+    SetRoleVar, SourceErrorPolicy, SourceIncludeMetadata, SqlServerConfigOption,
+    SqlServerConfigOptionName, Statement, TableConstraint, TableFromSourceColumns,
+    TableFromSourceOption, TableFromSourceOptionName, TableOption, TableOptionName,
+    UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName, UnresolvedSchemaName, Value,
+    ViewDefinition, WithOptionValue,
 };
 use mz_sql_parser::ident;
 use mz_sql_parser::parser::StatementParseResult;
+use mz_storage_types::connections::aws::AwsConnectionReference;
 use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnection};
+use mz_storage_types::connections::string_or_secret::StringOrSecret;
 use mz_storage_types::connections::{Connection, KafkaTopicOptions};
+use mz_storage_types::sinks::s3::S3SinkLayout;
 use mz_storage_types::sinks::{
-    KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, KafkaSinkFormatType, SinkEnvelope,
-    StorageSinkConnection,
+    HttpSinkConnection, IcebergSinkConnection, KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat,
+    KafkaSinkFormatType, MAX_S3_SINK_FILE_SIZE, MIN_S3_SINK_FILE_SIZE, PostgresSinkConnection,
+    S3SinkConnection, S3SinkFormat, SinkEnvelope, StorageSinkConnection,
 };
 use mz_storage_types::sources::encoding::{
     AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, ProtobufEncoding, RegexEncoding,
@@ -100,10 +112,15 @@ use mz_storage_types::sources::envelope::{
 use mz_storage_types::sources::kafka::{
     KafkaMetadataKind, KafkaSourceConnection, KafkaSourceExportDetails, kafka_metadata_columns_desc,
 };
+use mz_storage_types::sources::kinesis::{KinesisSourceConnection, KinesisSourceExportDetails};
 use mz_storage_types::sources::load_generator::{
     KeyValueLoadGenerator, LOAD_GENERATOR_KEY_VALUE_OFFSET_DEFAULT, LoadGenerator,
     LoadGeneratorSourceConnection, LoadGeneratorSourceExportDetails,
 };
+use mz_storage_types::sources::mongodb::{
+    MongoDbSourceConnection, MongoDbSourceDetails, MongoDbSourceExportDetails,
+    ProtoMongoDbSourceDetails, collection_envelope,
+};
 use mz_storage_types::sources::mysql::{
     MySqlSourceConnection, MySqlSourceDetails, ProtoMySqlSourceDetails,
 };
@@ -162,8 +179,11 @@ use crate::plan::{
     plan_utils, query, transform_ast,
 };
 use crate::session::vars::{
-    self, ENABLE_CLUSTER_SCHEDULE_REFRESH, ENABLE_COLLECTION_PARTITION_BY,
-    ENABLE_CREATE_TABLE_FROM_SOURCE, ENABLE_KAFKA_SINK_HEADERS, ENABLE_REFRESH_EVERY_MVS,
+    self, ENABLE_CLUSTER_AUTO_SUSPEND, ENABLE_CLUSTER_SCHEDULE_REFRESH,
+    ENABLE_CLUSTER_SCHEDULE_UTILIZATION, ENABLE_COLLECTION_PARTITION_BY,
+    ENABLE_CREATE_TABLE_FROM_SOURCE, ENABLE_HTTP_SINK, ENABLE_ICEBERG_SINK,
+    ENABLE_KAFKA_SINK_HEADERS, ENABLE_POSTGRES_SINK, ENABLE_REFRESH_EVERY_MVS, ENABLE_S3_SINK,
+    UNSAFE_ENABLE_ICEBERG_SINK_LOCAL_WAREHOUSE,
 };
 use crate::{names, parse};
 
@@ -539,6 +559,10 @@ generate_extracted_config!(
     (ExcludeColumns, Vec::<UnresolvedItemName>, Default(vec![]))
 );
 
+generate_extracted_config!(MongoDbConfigOption, (Details, String));
+
+generate_extracted_config!(KinesisSourceConfigOption, (Stream, String));
+
 pub fn plan_create_webhook_source(
     scx: &StatementContext,
     mut stmt: CreateWebhookSourceStatement<Aug>,
@@ -908,6 +932,27 @@ pub fn plan_create_source(
 
             GenericSourceConnection::Kafka(connection)
         }
+        CreateSourceConnection::Kinesis {
+            connection,
+            options,
+        } => {
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            if !matches!(connection_item.connection()?, Connection::Aws(_)) {
+                sql_bail!(
+                    "{} is not an AWS connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                )
+            }
+
+            let KinesisSourceConfigOptionExtracted { stream, seen: _ } =
+                options.clone().try_into()?;
+
+            GenericSourceConnection::Kinesis(KinesisSourceConnection {
+                connection: connection_item.id(),
+                connection_id: connection_item.id(),
+                stream: stream.expect("validated exists during purification"),
+            })
+        }
         CreateSourceConnection::Postgres {
             connection,
             options,
@@ -1027,6 +1072,38 @@ pub fn plan_create_source(
 
             connection
         }
+        CreateSourceConnection::MongoDb {
+            connection,
+            options,
+        } => {
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            match connection_item.connection()? {
+                Connection::MongoDb(connection) => connection,
+                _ => sql_bail!(
+                    "{} is not a MongoDB connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                ),
+            };
+            let MongoDbConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: MongoDB source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoMongoDbSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                MongoDbSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            let connection =
+                GenericSourceConnection::<ReferencedConnection>::from(MongoDbSourceConnection {
+                    connection: connection_item.id(),
+                    connection_id: connection_item.id(),
+                    details,
+                });
+
+            connection
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let load_generator =
                 load_generator_ast_to_generator(scx, generator, options, include_metadata)?;
@@ -1634,23 +1711,37 @@ pub fn plan_create_subsource(
                     .map(|c| c.into_string())
                     .collect(),
             }),
+            SourceExportStatementDetails::MongoDb {
+                database,
+                collection,
+            } => SourceExportDetails::MongoDb(MongoDbSourceExportDetails {
+                database,
+                collection,
+            }),
             SourceExportStatementDetails::LoadGenerator { output } => {
                 SourceExportDetails::LoadGenerator(LoadGeneratorSourceExportDetails { output })
             }
             SourceExportStatementDetails::Kafka {} => {
                 bail_unsupported!("subsources cannot reference Kafka sources")
             }
+            SourceExportStatementDetails::Kinesis {} => {
+                bail_unsupported!("subsources cannot reference Kinesis sources")
+            }
+        };
+        // Subsources don't currently support non-default envelopes / encoding
+        let envelope = match details {
+            SourceExportDetails::MongoDb(_) => collection_envelope(),
+            _ => SourceEnvelope::None(NoneEnvelope {
+                key_envelope: KeyEnvelope::None,
+                key_arity: 0,
+            }),
         };
         DataSourceDesc::IngestionExport {
             ingestion_id,
             external_reference,
             details,
-            // Subsources don't currently support non-default envelopes / encoding
             data_config: SourceExportDataConfig {
-                envelope: SourceEnvelope::None(NoneEnvelope {
-                    key_envelope: KeyEnvelope::None,
-                    key_arity: 0,
-                }),
+                envelope,
                 encoding: None,
             },
         }
@@ -1710,6 +1801,7 @@ pub fn plan_create_table_from_source(
         with_options,
     } = &stmt;
 
+    let user_specified_envelope = envelope.is_some();
     let envelope = envelope.clone().unwrap_or(ast::SourceEnvelope::None);
 
     let TableFromSourceOptionExtracted {
@@ -1750,6 +1842,11 @@ pub fn plan_create_table_from_source(
     {
         bail_unsupported!("INCLUDE metadata with non-Kafka source table");
     }
+    if matches!(details, SourceExportStatementDetails::MongoDb { .. })
+        && (user_specified_envelope || format.is_some())
+    {
+        sql_bail!("MongoDB source tables do not support ENVELOPE or FORMAT");
+    }
 
     let details = match details {
         SourceExportStatementDetails::Postgres { table } => {
@@ -1786,6 +1883,13 @@ pub fn plan_create_table_from_source(
                 .map(|c| c.into_string())
                 .collect(),
         }),
+        SourceExportStatementDetails::MongoDb {
+            database,
+            collection,
+        } => SourceExportDetails::MongoDb(MongoDbSourceExportDetails {
+            database,
+            collection,
+        }),
         SourceExportStatementDetails::LoadGenerator { output } => {
             SourceExportDetails::LoadGenerator(LoadGeneratorSourceExportDetails { output })
         }
@@ -1853,6 +1957,9 @@ pub fn plan_create_table_from_source(
 
             SourceExportDetails::Kafka(KafkaSourceExportDetails { metadata_columns })
         }
+        SourceExportStatementDetails::Kinesis {} => {
+            SourceExportDetails::Kinesis(KinesisSourceExportDetails {})
+        }
     };
 
     let source_connection = &source_item.source_desc()?.expect("is source").connection;
@@ -1895,6 +2002,11 @@ pub fn plan_create_table_from_source(
     if let TableFromSourceColumns::Named(col_names) = columns {
         plan_utils::maybe_rename_columns(format!("source table {}", name), &mut desc, col_names)?;
     }
+    // MongoDB collections are always ingested with upsert semantics keyed by `_id`.
+    let envelope = match details {
+        SourceExportDetails::MongoDb(_) => collection_envelope(),
+        _ => envelope,
+    };
 
     let names: Vec<_> = desc.iter_names().cloned().collect();
     if let Some(dup) = names.iter().duplicates().next() {
@@ -3332,7 +3444,11 @@ fn plan_sink(
     }
     let desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
     let key_indices = match &connection {
-        CreateSinkConnection::Kafka { key, .. } => {
+        CreateSinkConnection::Kafka { key, .. }
+        | CreateSinkConnection::Iceberg { key, .. }
+        | CreateSinkConnection::S3 { key, .. }
+        | CreateSinkConnection::Postgres { key, .. }
+        | CreateSinkConnection::Http { key, .. } => {
             if let Some(key) = key.clone() {
                 let key_columns = key
                     .key_columns
@@ -3467,6 +3583,55 @@ fn plan_sink(
             envelope,
             from.id(),
         )?,
+        CreateSinkConnection::Iceberg {
+            connection,
+            options,
+            ..
+        } => iceberg_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+            envelope,
+        )?,
+        CreateSinkConnection::S3 {
+            connection,
+            options,
+            ..
+        } => s3_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+        )?,
+        CreateSinkConnection::Postgres {
+            connection,
+            options,
+            ..
+        } => postgres_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+            envelope,
+        )?,
+        CreateSinkConnection::Http { options, .. } => http_sink_builder(
+            scx,
+            options,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+        )?,
     };
 
     let CreateSinkOptionExtracted {
@@ -3818,6 +3983,74 @@ fn kafka_sink_builder(
                 csr_connection,
             })
         }
+        Format::Protobuf(ProtobufSchema::Csr {
+            csr_connection:
+                CsrConnectionProtobuf {
+                    connection:
+                        CsrConnection {
+                            connection,
+                            options,
+                        },
+                    seed,
+                },
+        }) => {
+            if seed.is_some() {
+                sql_bail!("SEED option does not make sense with sinks");
+            }
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let csr_connection = match item.connection()? {
+                Connection::Csr(_) => item.id(),
+                _ => {
+                    sql_bail!(
+                        "{} is not a schema registry connection",
+                        scx.catalog
+                            .resolve_full_name(item.name())
+                            .to_string()
+                            .quoted()
+                    )
+                }
+            };
+            let options: CsrConfigOptionExtracted = options.try_into()?;
+            if options.avro_key_fullname.is_some()
+                || options.avro_value_fullname.is_some()
+                || options.null_defaults
+                || !options.key_doc_options.is_empty()
+                || !options.value_doc_options.is_empty()
+            {
+                sql_bail!("Avro options cannot be used with FORMAT PROTOBUF");
+            }
+
+            let message_name = if is_key { "Key" } else { "Value" };
+            let debezium = !is_key && matches!(envelope, SinkEnvelope::Debezium);
+            let schema = protobuf::generate_schema(desc.clone(), debezium, message_name)?;
+            Ok(KafkaSinkFormatType::Protobuf {
+                message_name: message_name.into(),
+                descriptors: schema.descriptors,
+                schema: Some(schema.source),
+                compatibility_level: if is_key {
+                    options.key_compatibility_level
+                } else {
+                    options.value_compatibility_level
+                },
+                csr_connection: Some(csr_connection),
+            })
+        }
+        Format::Protobuf(ProtobufSchema::InlineSchema {
+            message_name,
+            schema: ast::Schema { schema },
+        }) => {
+            let descriptors = strconv::parse_bytes(&schema)?;
+            // Ensure that the relation can be encoded as the message.
+            let debezium = !is_key && matches!(envelope, SinkEnvelope::Debezium);
+            protobuf::Encoder::new(desc.clone(), debezium, &descriptors, &message_name, None)?;
+            Ok(KafkaSinkFormatType::Protobuf {
+                message_name,
+                descriptors,
+                schema: None,
+                compatibility_level: None,
+                csr_connection: None,
+            })
+        }
         format => bail_unsupported!(format!("sink format {:?}", format)),
     };
 
@@ -3915,6 +4148,296 @@ fn kafka_sink_builder(
     }))
 }
 
+generate_extracted_config!(
+    IcebergSinkConfigOption,
+    (Namespace, String),
+    (Table, String),
+    (Warehouse, String)
+);
+
+fn iceberg_sink_builder(
+    scx: &StatementContext,
+    connection: Option<ResolvedItemName>,
+    options: Vec<IcebergSinkConfigOption<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&ENABLE_ICEBERG_SINK)?;
+
+    if format.is_some() {
+        sql_bail!(
+            "FORMAT cannot be specified for Iceberg sinks; data is always written as Parquet"
+        );
+    }
+    match envelope {
+        SinkEnvelope::Upsert => (),
+        SinkEnvelope::Debezium => bail_unsupported!("ENVELOPE DEBEZIUM for Iceberg sinks"),
+    }
+
+    let IcebergSinkConfigOptionExtracted {
+        namespace,
+        table,
+        warehouse,
+        seen: _,
+    } = options.try_into()?;
+
+    let warehouse = warehouse.ok_or_else(|| sql_err!("ICEBERG must specify WAREHOUSE"))?;
+    let namespace = namespace.ok_or_else(|| sql_err!("ICEBERG must specify NAMESPACE"))?;
+    let table = table.ok_or_else(|| sql_err!("ICEBERG must specify TABLE"))?;
+    for (name, value) in [("NAMESPACE", &namespace), ("TABLE", &table)] {
+        if value.is_empty() || value.contains('/') {
+            sql_bail!("{} must be non-empty and cannot contain '/'", name);
+        }
+    }
+
+    let aws_connection = match connection {
+        Some(connection) => {
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            match item.connection()? {
+                Connection::Aws(_) => Some(AwsConnectionReference {
+                    connection_id: item.id(),
+                    connection: item.id(),
+                }),
+                _ => sql_bail!(
+                    "{} is not an AWS connection",
+                    scx.catalog.resolve_full_name(item.name())
+                ),
+            }
+        }
+        None => None,
+    };
+
+    if warehouse.starts_with("s3://") {
+        if aws_connection.is_none() {
+            sql_bail!("Iceberg sinks writing to an S3 warehouse must specify an AWS CONNECTION");
+        }
+        let uri = warehouse
+            .parse::<http::Uri>()
+            .map_err(|e| sql_err!("invalid WAREHOUSE {}: {}", warehouse.quoted(), e))?;
+        if uri.host().map_or(true, |host| host.is_empty()) {
+            sql_bail!("WAREHOUSE {} must specify a bucket", warehouse.quoted());
+        }
+    } else if warehouse.starts_with("file:///") {
+        scx.require_feature_flag(&UNSAFE_ENABLE_ICEBERG_SINK_LOCAL_WAREHOUSE)?;
+        if aws_connection.is_some() {
+            sql_bail!("Iceberg sinks writing to a local warehouse cannot specify a CONNECTION");
+        }
+    } else {
+        sql_bail!(
+            "WAREHOUSE {} must be an s3:// or file:/// URI",
+            warehouse.quoted()
+        );
+    }
+
+    mz_storage_types::sinks::iceberg::validate_desc(&value_desc).map_err(|e| sql_err!("{}", e))?;
+
+    Ok(StorageSinkConnection::Iceberg(IcebergSinkConnection {
+        aws_connection,
+        warehouse,
+        namespace,
+        table,
+        key_desc_and_indices,
+        relation_key_indices,
+        value_desc,
+    }))
+}
+
+generate_extracted_config!(
+    S3SinkConfigOption,
+    (Format, String),
+    (MaxFileSize, ByteSize, Default(ByteSize::mb(256))),
+    (Uri, String)
+);
+
+fn s3_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    options: Vec<S3SinkConfigOption<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&ENABLE_S3_SINK)?;
+
+    if format.is_some() {
+        sql_bail!("S3 sinks specify their format with the FORMAT option of the S3 connection");
+    }
+
+    let S3SinkConfigOptionExtracted {
+        format,
+        max_file_size,
+        uri,
+        seen: _,
+    } = options.try_into()?;
+
+    let item = scx.get_item_by_resolved_name(&connection)?;
+    let aws_connection = match item.connection()? {
+        Connection::Aws(_) => AwsConnectionReference {
+            connection_id: item.id(),
+            connection: item.id(),
+        },
+        _ => sql_bail!(
+            "{} is not an AWS connection",
+            scx.catalog.resolve_full_name(item.name())
+        ),
+    };
+
+    let uri = uri.ok_or_else(|| sql_err!("S3 must specify URI"))?;
+    S3SinkLayout::new(&uri).map_err(|e| sql_err!("invalid URI {}: {}", uri.quoted(), e))?;
+
+    if max_file_size.as_bytes() < MIN_S3_SINK_FILE_SIZE.as_bytes() {
+        sql_bail!(
+            "MAX FILE SIZE cannot be less than {}",
+            MIN_S3_SINK_FILE_SIZE
+        );
+    }
+    if max_file_size.as_bytes() > MAX_S3_SINK_FILE_SIZE.as_bytes() {
+        sql_bail!(
+            "MAX FILE SIZE cannot be greater than {}",
+            MAX_S3_SINK_FILE_SIZE
+        );
+    }
+
+    let file_desc =
+        mz_storage_types::sinks::s3::file_desc(&value_desc).map_err(|e| sql_err!("{}", e))?;
+    let format = match format.as_deref().map(str::to_lowercase).as_deref() {
+        Some("parquet") => {
+            ArrowBuilder::validate_desc(&file_desc).map_err(|e| sql_err!("{}", e))?;
+            S3SinkFormat::Parquet
+        }
+        Some("csv") => S3SinkFormat::PgCopy(CopyFormatParams::Csv(Default::default())),
+        Some(format) => sql_bail!("unknown FORMAT: {}", format),
+        None => sql_bail!("S3 must specify FORMAT"),
+    };
+
+    Ok(StorageSinkConnection::S3(S3SinkConnection {
+        aws_connection,
+        uri,
+        format,
+        max_file_size: max_file_size.as_bytes(),
+        key_desc_and_indices,
+        relation_key_indices,
+        value_desc,
+    }))
+}
+
+generate_extracted_config!(
+    PostgresSinkConfigOption,
+    (ProgressTable, String),
+    (Schema, String),
+    (Table, String)
+);
+
+fn postgres_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    options: Vec<PostgresSinkConfigOption<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&ENABLE_POSTGRES_SINK)?;
+
+    if format.is_some() {
+        sql_bail!("FORMAT cannot be specified for Postgres sinks; rows are written as table rows");
+    }
+    match envelope {
+        SinkEnvelope::Upsert => (),
+        SinkEnvelope::Debezium => bail_unsupported!("ENVELOPE DEBEZIUM for Postgres sinks"),
+    }
+
+    let PostgresSinkConfigOptionExtracted {
+        progress_table,
+        schema,
+        table,
+        seen: _,
+    } = options.try_into()?;
+
+    let item = scx.get_item_by_resolved_name(&connection)?;
+    match item.connection()? {
+        Connection::Postgres(_) => (),
+        _ => sql_bail!(
+            "{} is not a postgres connection",
+            scx.catalog.resolve_full_name(item.name())
+        ),
+    }
+
+    let table = table.ok_or_else(|| sql_err!("POSTGRES must specify TABLE"))?;
+    let schema = schema.unwrap_or_else(|| "public".into());
+    let progress_table = progress_table.unwrap_or_else(|| "mz_sink_progress".into());
+    for (name, value) in [
+        ("TABLE", &table),
+        ("SCHEMA", &schema),
+        ("PROGRESS TABLE", &progress_table),
+    ] {
+        if value.is_empty() {
+            sql_bail!("{} must be non-empty", name);
+        }
+    }
+    if progress_table == table {
+        sql_bail!("PROGRESS TABLE must differ from TABLE");
+    }
+
+    Ok(StorageSinkConnection::Postgres(PostgresSinkConnection {
+        connection_id: item.id(),
+        connection: item.id(),
+        schema,
+        table,
+        progress_table,
+        key_desc_and_indices,
+        relation_key_indices,
+        value_desc,
+    }))
+}
+
+generate_extracted_config!(
+    HttpSinkConfigOption,
+    (Authorization, StringOrSecret),
+    (Url, String)
+);
+
+fn http_sink_builder(
+    scx: &StatementContext,
+    options: Vec<HttpSinkConfigOption<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&ENABLE_HTTP_SINK)?;
+
+    if format.is_some() {
+        sql_bail!("FORMAT cannot be specified for HTTP sinks; changes are always sent as JSON");
+    }
+
+    let HttpSinkConfigOptionExtracted {
+        authorization,
+        url,
+        seen: _,
+    } = options.try_into()?;
+
+    let url = url.ok_or_else(|| sql_err!("HTTP must specify URL"))?;
+    let parsed =
+        reqwest::Url::parse(&url).map_err(|e| sql_err!("invalid URL {}: {}", url.quoted(), e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        sql_bail!("URL {} must be an http:// or https:// URL", url.quoted());
+    }
+
+    Ok(StorageSinkConnection::Http(HttpSinkConnection {
+        url,
+        authorization,
+        key_desc_and_indices,
+        relation_key_indices,
+        value_desc,
+    }))
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -4473,6 +4996,7 @@ pub fn describe_create_cluster(
 // to ALTER CLUSTER would always reset the value of that option to the default.
 generate_extracted_config!(
     ClusterOption,
+    (AutoSuspendAfter, OptionalDuration),
     (AvailabilityZones, Vec<String>),
     (Disk, bool),
     (IntrospectionDebugging, bool),
@@ -4569,6 +5093,7 @@ pub fn plan_create_cluster_inner(
     }: CreateClusterStatement<Aug>,
 ) -> Result<CreateClusterPlan, PlanError> {
     let ClusterOptionExtracted {
+        auto_suspend_after,
         availability_zones,
         introspection_debugging,
         introspection_interval,
@@ -4631,23 +5156,26 @@ pub fn plan_create_cluster_inner(
             introspection_debugging.unwrap_or(false),
         )?;
 
-        let replication_factor = if matches!(schedule, ClusterScheduleOptionValue::Manual) {
-            replication_factor.unwrap_or_else(|| {
-                scx.catalog
-                    .system_vars()
-                    .default_cluster_replication_factor()
-            })
-        } else {
-            scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_REFRESH)?;
-            if replication_factor.is_some() {
-                sql_bail!(
-                    "REPLICATION FACTOR cannot be given together with any SCHEDULE other than MANUAL"
-                );
+        require_cluster_schedule_feature_flag(scx, &schedule)?;
+        let replication_factor = match schedule {
+            ClusterScheduleOptionValue::Manual | ClusterScheduleOptionValue::Utilization { .. } => {
+                replication_factor.unwrap_or_else(|| {
+                    scx.catalog
+                        .system_vars()
+                        .default_cluster_replication_factor()
+                })
+            }
+            ClusterScheduleOptionValue::Refresh { .. } => {
+                if replication_factor.is_some() {
+                    sql_bail!(
+                        "REPLICATION FACTOR cannot be given together with SCHEDULE = ON REFRESH"
+                    );
+                }
+                // If we have a refresh schedule, then let's not have any replicas initially,
+                // to avoid quickly going back and forth if the schedule doesn't want a replica
+                // initially.
+                0
             }
-            // If we have a non-trivial schedule, then let's not have any replicas initially,
-            // to avoid quickly going back and forth if the schedule doesn't want a replica
-            // initially.
-            0
         };
         let availability_zones = availability_zones.unwrap_or_default();
 
@@ -4677,6 +5205,16 @@ pub fn plan_create_cluster_inner(
             ..Default::default()
         };
 
+        let auto_suspend_after = auto_suspend_after.and_then(|v| v.0);
+        if auto_suspend_after.is_some() {
+            scx.require_feature_flag(&ENABLE_CLUSTER_AUTO_SUSPEND)?;
+            if !matches!(schedule, ClusterScheduleOptionValue::Manual) {
+                sql_bail!(
+                    "AUTO SUSPEND AFTER cannot be given together with a SCHEDULE other than MANUAL"
+                );
+            }
+        }
+
         let schedule = plan_cluster_schedule(schedule)?;
 
         Ok(CreateClusterPlan {
@@ -4689,6 +5227,7 @@ pub fn plan_create_cluster_inner(
                 disk,
                 optimizer_feature_overrides,
                 schedule,
+                auto_suspend_after,
             }),
             workload_class,
         })
@@ -4722,6 +5261,9 @@ pub fn plan_create_cluster_inner(
                 "cluster schedules other than MANUAL are not supported for unmanaged clusters"
             );
         }
+        if auto_suspend_after.is_some() {
+            sql_bail!("AUTO SUSPEND AFTER not supported for unmanaged clusters");
+        }
 
         let mut replicas = vec![];
         for ReplicaDefinition { name, options } in replica_defs {
@@ -4756,6 +5298,7 @@ pub fn unplan_create_cluster(
             disk,
             optimizer_feature_overrides,
             schedule,
+            auto_suspend_after,
         }) => {
             let schedule = unplan_cluster_schedule(schedule);
             let OptimizerFeatureOverrides {
@@ -4797,7 +5340,8 @@ pub fn unplan_create_cluster(
             // Replication factor cannot be explicitly specified with a refresh schedule, it's
             // always 1 or less.
             let replication_factor = match &schedule {
-                ClusterScheduleOptionValue::Manual => Some(replication_factor),
+                ClusterScheduleOptionValue::Manual
+                | ClusterScheduleOptionValue::Utilization { .. } => Some(replication_factor),
                 ClusterScheduleOptionValue::Refresh { .. } => {
                     assert!(
                         replication_factor <= 1,
@@ -4810,6 +5354,7 @@ pub fn unplan_create_cluster(
             let options_extracted = ClusterOptionExtracted {
                 // Seen is ignored when unplanning.
                 seen: Default::default(),
+                auto_suspend_after: auto_suspend_after.map(|d| OptionalDuration(Some(d))),
                 availability_zones,
                 disk: Some(disk),
                 introspection_debugging: Some(introspection_debugging),
@@ -5069,9 +5614,30 @@ fn plan_cluster_schedule(
                 hydration_time_estimate: duration,
             }
         }
+        // The sizes are validated when sequencing, because only the coordinator knows the
+        // available replica sizes.
+        ClusterScheduleOptionValue::Utilization { min_size, max_size } => {
+            ClusterSchedule::Utilization { min_size, max_size }
+        }
     })
 }
 
+/// Requires the feature flag that guards `schedule`, if any.
+fn require_cluster_schedule_feature_flag(
+    scx: &StatementContext,
+    schedule: &ClusterScheduleOptionValue,
+) -> Result<(), PlanError> {
+    match schedule {
+        ClusterScheduleOptionValue::Manual => Ok(()),
+        ClusterScheduleOptionValue::Refresh { .. } => {
+            scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_REFRESH)
+        }
+        ClusterScheduleOptionValue::Utilization { .. } => {
+            scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_UTILIZATION)
+        }
+    }
+}
+
 /// Convert a [`ClusterSchedule`] into a [`ClusterScheduleOptionValue`].
 ///
 /// The reverse of [`plan_cluster_schedule`].
@@ -5088,6 +5654,9 @@ fn unplan_cluster_schedule(schedule: ClusterSchedule) -> ClusterScheduleOptionVa
                 hydration_time_estimate: Some(interval_value),
             }
         }
+        ClusterSchedule::Utilization { min_size, max_size } => {
+            ClusterScheduleOptionValue::Utilization { min_size, max_size }
+        }
     }
 }
 
@@ -5988,6 +6557,7 @@ pub fn plan_alter_cluster(
             with_options,
         } => {
             let ClusterOptionExtracted {
+                auto_suspend_after,
                 availability_zones,
                 introspection_debugging,
                 introspection_interval,
@@ -6025,26 +6595,23 @@ pub fn plan_alter_cluster(
                     if replica_defs.is_some() {
                         sql_bail!("REPLICAS not supported for managed clusters");
                     }
-                    if schedule.is_some()
-                        && !matches!(schedule, Some(ClusterScheduleOptionValue::Manual))
-                    {
-                        scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_REFRESH)?;
+                    if let Some(schedule) = &schedule {
+                        require_cluster_schedule_feature_flag(scx, schedule)?;
+                    }
+                    if matches!(auto_suspend_after, Some(OptionalDuration(Some(_)))) {
+                        scx.require_feature_flag(&ENABLE_CLUSTER_AUTO_SUSPEND)?;
                     }
 
                     if let Some(replication_factor) = replication_factor {
-                        if schedule.is_some()
-                            && !matches!(schedule, Some(ClusterScheduleOptionValue::Manual))
-                        {
+                        if matches!(schedule, Some(ClusterScheduleOptionValue::Refresh { .. })) {
                             sql_bail!(
-                                "REPLICATION FACTOR cannot be given together with any SCHEDULE other than MANUAL"
+                                "REPLICATION FACTOR cannot be given together with SCHEDULE = ON REFRESH"
                             );
                         }
-                        if let Some(current_schedule) = cluster.schedule() {
-                            if !matches!(current_schedule, ClusterSchedule::Manual) {
-                                sql_bail!(
-                                    "REPLICATION FACTOR cannot be set if the cluster SCHEDULE is anything other than MANUAL"
-                                );
-                            }
+                        if let Some(ClusterSchedule::Refresh { .. }) = cluster.schedule() {
+                            sql_bail!(
+                                "REPLICATION FACTOR cannot be set if the cluster SCHEDULE is ON REFRESH"
+                            );
                         }
 
                         let internal_replica_count =
@@ -6108,6 +6675,9 @@ pub fn plan_alter_cluster(
                             "cluster schedules other than MANUAL are not supported for unmanaged clusters"
                         );
                     }
+                    if auto_suspend_after.is_some() {
+                        sql_bail!("AUTO SUSPEND AFTER not supported for unmanaged clusters");
+                    }
                     if let Some(current_schedule) = cluster.schedule() {
                         if !matches!(current_schedule, ClusterSchedule::Manual)
                             && schedule.is_none()
@@ -6192,6 +6762,9 @@ pub fn plan_alter_cluster(
             if let Some(schedule) = schedule {
                 options.schedule = AlterOptionParameter::Set(plan_cluster_schedule(schedule)?);
             }
+            if let Some(auto_suspend_after) = auto_suspend_after {
+                options.auto_suspend_after = AlterOptionParameter::Set(auto_suspend_after);
+            }
             if let Some(workload_class) = workload_class {
                 options.workload_class = AlterOptionParameter::Set(workload_class.0);
             }
@@ -6208,6 +6781,7 @@ pub fn plan_alter_cluster(
 
             for option in reset_options {
                 match option {
+                    AutoSuspendAfter => options.auto_suspend_after = Reset,
                     AvailabilityZones => options.availability_zones = Reset,
                     Disk => options.disk = Reset,
                     IntrospectionInterval => options.introspection_interval = Reset,
@@ -6849,6 +7423,7 @@ pub fn plan_alter_connection(
         Connection::Ssh(_) => CreateConnectionType::Ssh,
         Connection::MySql(_) => CreateConnectionType::MySql,
         Connection::SqlServer(_) => CreateConnectionType::SqlServer,
+        Connection::MongoDb(_) => CreateConnectionType::MongoDb,
     };
 
     // Collect all options irrespective of action taken on them.
@@ -34,9 +34,9 @@ use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::string_or_secret::StringOrSecret;
 use mz_storage_types::connections::{
     AwsPrivatelink, AwsPrivatelinkConnection, CsrConnection, CsrConnectionHttpAuth,
-    KafkaConnection, KafkaSaslConfig, KafkaTlsConfig, KafkaTopicOptions, MySqlConnection,
-    MySqlSslMode, PostgresConnection, SqlServerConnectionDetails, SshConnection, SshTunnel,
-    TlsIdentity, Tunnel,
+    KafkaConnection, KafkaSaslConfig, KafkaTlsConfig, KafkaTopicOptions, MongoDbConnection,
+    MySqlConnection, MySqlSslMode, PostgresConnection, SqlServerConnectionDetails, SshConnection,
+    SshTunnel, TlsIdentity, Tunnel,
 };
 
 use crate::names::Aug;
@@ -179,6 +179,7 @@ pub(super) fn validate_options_per_connection_type(
             SslMode,
             User,
         ],
+        CreateConnectionType::MongoDb => &[Host, Password, Port, SslMode, User],
     };
 
     for o in permitted_options {
@@ -575,6 +576,35 @@ impl ConnectionOptionExtracted {
                     encryption,
                 })
             }
+            CreateConnectionType::MongoDb => {
+                scx.require_feature_flag(&vars::ENABLE_MONGODB_SOURCE)?;
+
+                let tls = match self
+                    .ssl_mode
+                    .map(|m| m.to_lowercase())
+                    .as_ref()
+                    .map(|m| m.as_str())
+                {
+                    None | Some("disable") => false,
+                    Some("require") => true,
+                    Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+                };
+
+                if self.password.is_some() && self.user.is_none() {
+                    sql_bail!("invalid CONNECTION: PASSWORD requires USER");
+                }
+
+                ConnectionDetails::MongoDb(MongoDbConnection {
+                    host: self
+                        .host
+                        .ok_or_else(|| sql_err!("HOST option is required"))?,
+                    // 27017 is the default port for mongod and mongos.
+                    port: self.port.unwrap_or(27017_u16),
+                    user: self.user,
+                    password: self.password.map(|password| password.into()),
+                    tls,
+                })
+            }
         };
 
         Ok(connection)
@@ -22,6 +22,7 @@ use mz_expr::visit::Visit;
 use mz_expr::{MirRelationExpr, RowSetFinishing};
 use mz_ore::num::NonNeg;
 use mz_ore::soft_panic_or_log;
+use mz_ore::str::StrExt;
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::bytes::ByteSize;
@@ -29,9 +30,10 @@ use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::{CatalogItemId, Datum, RelationDesc, ScalarType};
 use mz_sql_parser::ast::{
-    CteBlock, ExplainPlanOption, ExplainPlanOptionName, ExplainPushdownStatement,
-    ExplainSinkSchemaFor, ExplainSinkSchemaStatement, ExplainTimestampStatement, Expr,
-    IfExistsBehavior, OrderByExpr, SetExpr, SubscribeOutput, UnresolvedItemName,
+    CteBlock, ExplainAnalyzeFormat, ExplainAnalyzeStatement, ExplainPlanOption,
+    ExplainPlanOptionName, ExplainPushdownStatement, ExplainSinkSchemaFor,
+    ExplainSinkSchemaStatement, ExplainTimestampStatement, Expr, IfExistsBehavior, OrderByExpr,
+    SetExpr, SubscribeOutput, UnresolvedItemName,
 };
 use mz_sql_parser::ident;
 use mz_storage_types::sinks::{
@@ -53,8 +55,9 @@ use crate::plan::query::{ExprContext, QueryLifetime, offset_into_value, plan_exp
 use crate::plan::scope::Scope;
 use crate::plan::statement::{StatementContext, StatementDesc, ddl};
 use crate::plan::{
-    self, CopyFromFilter, CopyToPlan, CreateSinkPlan, ExplainPushdownPlan, ExplainSinkSchemaPlan,
-    ExplainTimestampPlan, HirRelationExpr, HirScalarExpr, side_effecting_func, transform_ast,
+    self, CopyFromFilter, CopyToPlan, CreateSinkPlan, ExplainAnalyzePlan, ExplainPushdownPlan,
+    ExplainSinkSchemaPlan, ExplainTimestampPlan, HirRelationExpr, HirScalarExpr,
+    side_effecting_func, transform_ast,
 };
 use crate::plan::{
     CopyFormat, CopyFromPlan, ExplainPlanPlan, InsertPlan, MutationKind, Params, Plan, PlanError,
@@ -344,6 +347,23 @@ pub fn describe_explain_pushdown(
         .with_column("Selected Bytes", ScalarType::UInt64.nullable(false))
         .with_column("Total Parts", ScalarType::UInt64.nullable(false))
         .with_column("Selected Parts", ScalarType::UInt64.nullable(false))
+        .with_column("Bloom Filtered Parts", ScalarType::UInt64.nullable(false))
+        .finish();
+
+    Ok(
+        StatementDesc::new(Some(relation_desc)).with_params(match statement.explainee {
+            Explainee::Select(select, _) => describe_select(scx, *select)?.param_types,
+            _ => vec![],
+        }),
+    )
+}
+
+pub fn describe_explain_analyze(
+    scx: &StatementContext,
+    statement: ExplainAnalyzeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    let relation_desc = RelationDesc::builder()
+        .with_column("Analyzed Plan", ScalarType::String.nullable(false))
         .finish();
 
     Ok(
@@ -702,6 +722,67 @@ pub fn plan_explain_pushdown(
     Ok(Plan::ExplainPushdown(ExplainPushdownPlan { explainee }))
 }
 
+pub fn plan_explain_analyze(
+    scx: &StatementContext,
+    statement: ExplainAnalyzeStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_EXPLAIN_ANALYZE)?;
+
+    let properties = statement.properties();
+    let format = match statement.format() {
+        ExplainAnalyzeFormat::Text => ExplainFormat::Text,
+        ExplainAnalyzeFormat::Json => ExplainFormat::Json,
+    };
+    // The measurements annotate the physical plan, which is explained with
+    // the same defaults as `EXPLAIN PHYSICAL PLAN`.
+    let config = {
+        let mut with_options = ExplainPlanOptionExtracted::try_from(vec![])?;
+        with_options.filter_pushdown = Some(false);
+        ExplainConfig::try_from(with_options)?
+    };
+
+    let explainee = match statement.explainee {
+        Explainee::MaterializedView(_) | Explainee::Index(_) | Explainee::Select(..) => {
+            plan_explainee(scx, statement.explainee, params)?
+        }
+        _ => sql_bail!(
+            "EXPLAIN ANALYZE is only supported for SELECT statements, and for existing indexes \
+             and materialized views"
+        ),
+    };
+
+    // Introspection data is only available on the replicas that run the
+    // dataflow, and introspection queries run on the active cluster.
+    if let plan::Explainee::MaterializedView(item_id) | plan::Explainee::Index(item_id) = &explainee
+    {
+        let item = scx.get_item(item_id);
+        let cluster_id = item
+            .cluster_id()
+            .expect("indexes and materialized views have a cluster");
+        let active_cluster = scx.resolve_cluster(None)?;
+        if active_cluster.id() != cluster_id {
+            let cluster_name = scx.get_cluster(&cluster_id).name();
+            sql_bail!(
+                "{} is installed on cluster {}, but the active cluster is {}; \
+                 run EXPLAIN ANALYZE after SET cluster = {}",
+                scx.catalog.resolve_full_name(item.name()),
+                cluster_name.quoted(),
+                active_cluster.name().quoted(),
+                cluster_name.quoted(),
+            );
+        }
+    }
+
+    Ok(Plan::ExplainAnalyze(ExplainAnalyzePlan {
+        properties,
+        skew: statement.skew,
+        format,
+        config,
+        explainee,
+    }))
+}
+
 pub fn plan_explain_timestamp(
     scx: &StatementContext,
     explain: ExplainTimestampStatement<Aug>,
@@ -1108,6 +1189,8 @@ fn plan_copy_to_expr(
         }
         CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
         CopyFormat::Text => bail_unsupported!("FORMAT TEXT"),
+        CopyFormat::Json => bail_unsupported!("FORMAT JSON"),
+        CopyFormat::Avro => bail_unsupported!("FORMAT AVRO"),
     };
 
     // Converting the to expr to a HirScalarExpr
@@ -1239,6 +1322,20 @@ fn plan_copy_from(
         }
         CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
         CopyFormat::Parquet => CopyFormatParams::Parquet,
+        CopyFormat::Json | CopyFormat::Avro => {
+            if matches!(source, CopyFromSource::Stdin) {
+                bail_unsupported!("COPY FROM STDIN with FORMAT JSON or AVRO")
+            }
+            only_available_with_csv(options.quote, "quote")?;
+            only_available_with_csv(options.escape, "escape")?;
+            only_available_with_csv(options.header, "HEADER")?;
+            only_available_with_csv(options.delimiter, "delimiter")?;
+            only_available_with_csv(options.null, "NULL")?;
+            match format {
+                CopyFormat::Json => CopyFormatParams::Json,
+                _ => CopyFormatParams::Avro,
+            }
+        }
     };
 
     let filter = match (options.files, options.pattern) {
@@ -1311,6 +1408,8 @@ pub fn plan_copy(
             "csv" => Ok(CopyFormat::Csv),
             "binary" => Ok(CopyFormat::Binary),
             "parquet" => Ok(CopyFormat::Parquet),
+            "json" => Ok(CopyFormat::Json),
+            "avro" => Ok(CopyFormat::Avro),
             _ => sql_bail!("unknown FORMAT: {}", format),
         })
         .transpose()?;
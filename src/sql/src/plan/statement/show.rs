@@ -1025,7 +1025,9 @@ fn humanize_sql_for_show_create(
     sql: &str,
     redacted: bool,
 ) -> Result<String, PlanError> {
-    use mz_sql_parser::ast::{CreateSourceConnection, MySqlConfigOptionName, PgConfigOptionName};
+    use mz_sql_parser::ast::{
+        CreateSourceConnection, MongoDbConfigOptionName, MySqlConfigOptionName, PgConfigOptionName,
+    };
 
     let parsed = parse::parse(sql)?.into_element().ast;
     let (mut resolved, _) = names::resolve(catalog, parsed)?;
@@ -1186,6 +1188,12 @@ fn humanize_sql_for_show_create(
                         }
                     });
                 }
+                CreateSourceConnection::MongoDb { options, .. } => {
+                    // Drop details, which does not roundtrip.
+                    options.retain(|o| match o.name {
+                        MongoDbConfigOptionName::Details => false,
+                    });
+                }
                 CreateSourceConnection::LoadGenerator { .. } if !curr_references.is_empty() => {
                     // Load generator sources with any references only support
                     // `FOR ALL TABLES`. However, this would change if database-issues#7911
@@ -1194,6 +1202,7 @@ fn humanize_sql_for_show_create(
                     stmt.external_references = Some(ExternalReferences::All);
                 }
                 CreateSourceConnection::Kafka { .. }
+                | CreateSourceConnection::Kinesis { .. }
                 | CreateSourceConnection::LoadGenerator { .. } => {}
             }
 
@@ -133,6 +133,7 @@ impl<'a> FuncRewriter<'a> {
             filter,
             over,
             distinct,
+            within_group: vec![],
         })
     }
 
@@ -311,6 +312,65 @@ impl<'a> FuncRewriter<'a> {
             )
     }
 
+    fn plan_approx_count_distinct(
+        &mut self,
+        expr: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+        over: Option<WindowSpec<Aug>>,
+    ) -> Expr<Aug> {
+        // The code below converts `approx_count_distinct(x)` into:
+        //
+        //     coalesce(hll_estimate(hll_accumulate(x)), 0)
+        //
+        // `hll_accumulate` returns NULL rather than an empty sketch when there
+        // are no non-null inputs, but a count is never NULL.
+        let sketch = self.plan_agg(
+            self.scx
+                .dangerous_resolve_name(vec![MZ_CATALOG_SCHEMA, "hll_accumulate"]),
+            expr,
+            vec![],
+            filter,
+            distinct,
+            over,
+        );
+        let estimate = sketch.call_unary(
+            self.scx
+                .dangerous_resolve_name(vec![MZ_CATALOG_SCHEMA, "hll_estimate"]),
+        );
+        Expr::HomogenizingFunction {
+            function: HomogenizingFunction::Coalesce,
+            exprs: vec![estimate, Expr::number("0")],
+        }
+    }
+
+    fn plan_approx_percentile(
+        &mut self,
+        expr: Expr<Aug>,
+        fraction: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+        over: Option<WindowSpec<Aug>>,
+    ) -> Expr<Aug> {
+        // The code below converts `approx_percentile(x, q)` into:
+        //
+        //     approx_percentile_estimate(approx_percentile_accumulate(x), q)
+        let sketch = self.plan_agg(
+            self.scx
+                .dangerous_resolve_name(vec![MZ_CATALOG_SCHEMA, "approx_percentile_accumulate"]),
+            expr,
+            vec![],
+            filter,
+            distinct,
+            over,
+        );
+        Expr::call(
+            self.scx
+                .dangerous_resolve_name(vec![MZ_CATALOG_SCHEMA, "approx_percentile_estimate"]),
+            vec![sketch, fraction],
+        )
+    }
+
     fn plan_bool_and(
         &mut self,
         expr: Expr<Aug>,
@@ -379,8 +439,14 @@ impl<'a> FuncRewriter<'a> {
             filter,
             distinct,
             over,
+            within_group,
         } = func
         {
+            // None of the rewritten functions are ordered-set aggregates, so leave calls with a
+            // `WITHIN GROUP` clause to be rejected during planning.
+            if !within_group.is_empty() {
+                return None;
+            }
             let pg_catalog_id = self
                 .scx
                 .catalog
@@ -423,6 +489,9 @@ impl<'a> FuncRewriter<'a> {
                     "stddev_pop" => self.plan_stddev(arg, filter, distinct, false, over),
                     "bool_and" => self.plan_bool_and(arg, filter, distinct, over),
                     "bool_or" => self.plan_bool_or(arg, filter, distinct, over),
+                    "approx_count_distinct" => {
+                        self.plan_approx_count_distinct(arg, filter, distinct, over)
+                    }
                     _ => return None,
                 }
             } else if args.len() == 2 {
@@ -434,6 +503,9 @@ impl<'a> FuncRewriter<'a> {
                             .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "power"]),
                         vec![lhs, rhs],
                     ),
+                    "approx_percentile" => {
+                        self.plan_approx_percentile(lhs, rhs, filter, distinct, over)
+                    }
                     _ => return None,
                 }
             } else {
@@ -690,6 +762,7 @@ impl<'a> Desugarer<'a> {
                                 filter: None,
                                 over: None,
                                 distinct: false,
+                                within_group: vec![],
                             },
                             alias: Some(TableAlias {
                                 name: ident!("_"),
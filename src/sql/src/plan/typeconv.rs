@@ -546,6 +546,14 @@ static VALID_CASTS: LazyLock<BTreeMap<(ScalarBaseType, ScalarBaseType), CastImpl
 
             // BYTES
             (Bytes, String) => Assignment: CastBytesToString(func::CastBytesToString),
+            (Bytes, HllSketch) => Explicit: CastBytesToHllSketch(func::CastBytesToHllSketch),
+            (Bytes, QuantileSketch) => Explicit: CastBytesToQuantileSketch(func::CastBytesToQuantileSketch),
+
+            // SKETCHES
+            (HllSketch, Bytes) => Explicit: CastHllSketchToBytes(func::CastHllSketchToBytes),
+            (HllSketch, String) => Assignment: CastBytesToString(func::CastBytesToString),
+            (QuantileSketch, Bytes) => Explicit: CastQuantileSketchToBytes(func::CastQuantileSketchToBytes),
+            (QuantileSketch, String) => Assignment: CastBytesToString(func::CastBytesToString),
 
             // STRING
             (String, Bool) => Explicit: CastStringToBool(func::CastStringToBool),
@@ -592,6 +600,14 @@ static VALID_CASTS: LazyLock<BTreeMap<(ScalarBaseType, ScalarBaseType), CastImpl
             }),
             (String, Interval) => Explicit: CastStringToInterval(func::CastStringToInterval),
             (String, Bytes) => Explicit: CastStringToBytes(func::CastStringToBytes),
+            (String, HllSketch) => Explicit: [
+                CastStringToBytes(func::CastStringToBytes),
+                CastBytesToHllSketch(func::CastBytesToHllSketch),
+            ],
+            (String, QuantileSketch) => Explicit: [
+                CastStringToBytes(func::CastStringToBytes),
+                CastBytesToQuantileSketch(func::CastBytesToQuantileSketch),
+            ],
             (String, Jsonb) => Explicit: CastStringToJsonb(func::CastStringToJsonb),
             (String, Uuid) => Explicit: CastStringToUuid(func::CastStringToUuid),
             (String, Array) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
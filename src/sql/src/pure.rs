@@ -44,11 +44,12 @@ use mz_sql_parser::ast::{
     CsrSeedAvro, CsrSeedProtobuf, CsrSeedProtobufSchema, DeferredItemName, DocOnIdentifier,
     DocOnSchema, Expr, Function, FunctionArgs, Ident, KafkaSourceConfigOption,
     KafkaSourceConfigOptionName, LoadGenerator, LoadGeneratorOption, LoadGeneratorOptionName,
-    MaterializedViewOption, MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName,
-    PgConfigOption, PgConfigOptionName, RawItemName, ReaderSchemaSelectionStrategy,
-    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, SourceEnvelope,
-    SqlServerConfigOption, SqlServerConfigOptionName, Statement, TableFromSourceColumns,
-    TableFromSourceOption, TableFromSourceOptionName, UnresolvedItemName,
+    MaterializedViewOption, MaterializedViewOptionName, MongoDbConfigOption,
+    MongoDbConfigOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
+    PgConfigOptionName, RawItemName, ReaderSchemaSelectionStrategy, RefreshAtOptionValue,
+    RefreshEveryOptionValue, RefreshOptionValue, SourceEnvelope, SqlServerConfigOption,
+    SqlServerConfigOptionName, Statement, TableFromSourceColumns, TableFromSourceOption,
+    TableFromSourceOptionName, UnresolvedItemName,
 };
 use mz_sql_server_util::desc::SqlServerTableDesc;
 use mz_storage_types::configuration::StorageConfiguration;
@@ -56,6 +57,7 @@ use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::connections::{Connection, PostgresConnection};
 use mz_storage_types::errors::ContextCreationError;
 use mz_storage_types::sources::load_generator::LoadGeneratorOutput;
+use mz_storage_types::sources::mongodb::MongoDbSourceDetails;
 use mz_storage_types::sources::mysql::MySqlSourceDetails;
 use mz_storage_types::sources::postgres::PostgresSourcePublicationDetails;
 use mz_storage_types::sources::{
@@ -83,8 +85,13 @@ use crate::names::{
 use crate::plan::error::PlanError;
 use crate::plan::statement::ddl::load_generator_ast_to_generator;
 use crate::plan::{SourceReferences, StatementContext};
-use crate::pure::error::SqlServerSourcePurificationError;
-use crate::session::vars::ENABLE_SQL_SERVER_SOURCE;
+use crate::pure::error::{
+    KinesisSourcePurificationError, MongoDbSourcePurificationError,
+    SqlServerSourcePurificationError,
+};
+use crate::session::vars::{
+    ENABLE_KINESIS_SOURCE, ENABLE_MONGODB_SOURCE, ENABLE_SQL_SERVER_SOURCE,
+};
 use crate::{kafka_util, normalize};
 
 use self::error::{
@@ -95,6 +102,7 @@ use self::error::{
 pub(crate) mod error;
 mod references;
 
+pub mod mongodb;
 pub mod mysql;
 pub mod postgres;
 pub mod sql_server;
@@ -262,7 +270,12 @@ pub enum PurifiedExportDetails {
         excl_columns: Option<Vec<Ident>>,
         capture_instance: Arc<str>,
     },
+    MongoDb {
+        database: String,
+        collection: String,
+    },
     Kafka {},
+    Kinesis {},
     LoadGenerator {
         table: Option<RelationDesc>,
         output: LoadGeneratorOutput,
@@ -526,6 +539,18 @@ async fn purify_create_sink(
                 Err(KafkaSinkPurificationError::ZeroBrokers)?;
             }
         }
+        // Iceberg sinks validate access to their warehouse when they first
+        // start running, as the table may not exist yet.
+        CreateSinkConnection::Iceberg { .. } => {}
+        // S3 sinks check that their path is not in use by another sink when
+        // they first start running.
+        CreateSinkConnection::S3 { .. } => {}
+        // Postgres sinks check that the target table exists and matches the
+        // sinked relation when they first start running.
+        CreateSinkConnection::Postgres { .. } => {}
+        // HTTP sinks retry failed deliveries, so an endpoint that is not yet
+        // reachable is not an error.
+        CreateSinkConnection::Http { .. } => {}
     }
 
     let mut csr_connection_ids = BTreeSet::new();
@@ -638,6 +663,9 @@ async fn purify_create_source(
         CreateSourceConnection::Kafka { .. } => {
             &mz_storage_types::sources::kafka::KAFKA_PROGRESS_DESC
         }
+        CreateSourceConnection::Kinesis { .. } => {
+            &mz_storage_types::sources::kinesis::KINESIS_PROGRESS_DESC
+        }
         CreateSourceConnection::Postgres { .. } | CreateSourceConnection::Yugabyte { .. } => {
             &mz_storage_types::sources::postgres::PG_PROGRESS_DESC
         }
@@ -647,6 +675,9 @@ async fn purify_create_source(
         CreateSourceConnection::MySql { .. } => {
             &mz_storage_types::sources::mysql::MYSQL_PROGRESS_DESC
         }
+        CreateSourceConnection::MongoDb { .. } => {
+            &mz_storage_types::sources::mongodb::MONGODB_PROGRESS_DESC
+        }
         CreateSourceConnection::LoadGenerator { .. } => {
             &mz_storage_types::sources::load_generator::LOAD_GEN_PROGRESS_DESC
         }
@@ -786,6 +817,59 @@ async fn purify_create_source(
 
             format_options = SourceFormatOptions::Kafka { topic };
         }
+        CreateSourceConnection::Kinesis {
+            connection,
+            options,
+        } => {
+            scx.require_feature_flag(&ENABLE_KINESIS_SOURCE)?;
+
+            if let Some(external_references) = external_references {
+                Err(KinesisSourcePurificationError::ReferencedSubsources(
+                    external_references.clone(),
+                ))?;
+            }
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::Aws(connection) => connection.clone(),
+                _ => Err(KinesisSourcePurificationError::NotAwsConnection(
+                    scx.catalog.resolve_full_name(connection_item.name()),
+                ))?,
+            };
+
+            let crate::plan::statement::ddl::KinesisSourceConfigOptionExtracted { stream, seen: _ } =
+                options.clone().try_into()?;
+            let stream = stream.ok_or(KinesisSourcePurificationError::ConnectionMissingStream)?;
+
+            let sdk_config = connection
+                .load_sdk_config(
+                    &storage_configuration.connection_context,
+                    connection_item.id(),
+                    InTask::No,
+                )
+                .await
+                .map_err(|e| {
+                    KinesisSourcePurificationError::DescribeStream(
+                        e.display_with_causes().to_string(),
+                    )
+                })?;
+            let client = mz_aws_util::kinesis::new_client(&sdk_config);
+            let exists = mz_aws_util::kinesis::stream_exists(&client, &stream)
+                .await
+                .map_err(|e| {
+                    KinesisSourcePurificationError::DescribeStream(
+                        e.display_with_causes().to_string(),
+                    )
+                })?;
+            if !exists {
+                Err(KinesisSourcePurificationError::StreamDoesNotExist(
+                    stream.clone(),
+                ))?;
+            }
+
+            let reference_client = SourceReferenceClient::Kinesis { stream: &stream };
+            retrieved_source_references = reference_client.get_source_references().await?;
+        }
         source_connection @ CreateSourceConnection::Postgres { .. }
         | source_connection @ CreateSourceConnection::Yugabyte { .. } => {
             let (source_flavor, connection, options) = match source_connection {
@@ -1182,6 +1266,62 @@ async fn purify_create_source(
                     Some(WithOptionValue::Sequence(normalized_exclude_columns));
             }
         }
+        CreateSourceConnection::MongoDb {
+            connection,
+            options,
+        } => {
+            scx.require_feature_flag(&ENABLE_MONGODB_SOURCE)?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::MongoDb(connection) => connection.clone(),
+                _ => Err(MongoDbSourcePurificationError::NotMongoDbConnection(
+                    scx.catalog.resolve_full_name(connection_item.name()),
+                ))?,
+            };
+            let crate::plan::statement::ddl::MongoDbConfigOptionExtracted { details, seen: _ } =
+                options.clone().try_into()?;
+
+            if details.is_some() {
+                Err(MongoDbSourcePurificationError::UserSpecifiedDetails)?;
+            }
+
+            let client = connection
+                .connect(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+
+            // Change streams are only available on replica sets. Record the
+            // replica set so the source can detect being pointed elsewhere.
+            let replica_set = mz_storage_types::sources::mongodb::replica_set_name(&client)
+                .await?
+                .ok_or(MongoDbSourcePurificationError::NotReplicaSet)?;
+
+            let reference_client = SourceReferenceClient::MongoDb { client: &client };
+            retrieved_source_references = reference_client.get_source_references().await?;
+
+            let source_exports = mongodb::purify_source_exports(
+                &retrieved_source_references,
+                external_references,
+                source_name,
+                &reference_policy,
+            )?;
+            requested_subsource_map.extend(source_exports);
+
+            let details = MongoDbSourceDetails { replica_set };
+            options.retain(|MongoDbConfigOption { name, .. }| {
+                name != &MongoDbConfigOptionName::Details
+            });
+            options.push(MongoDbConfigOption {
+                name: MongoDbConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            });
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let load_generator =
                 load_generator_ast_to_generator(&scx, generator, options, include_metadata)?;
@@ -1739,6 +1879,18 @@ async fn purify_alter_source_refresh_references(
             .await?;
             source_references
         }
+        GenericSourceConnection::MongoDb(mongodb_source_connection) => {
+            let client = mongodb_source_connection
+                .connection
+                .connect(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+            let reference_client = SourceReferenceClient::MongoDb { client: &client };
+            reference_client.get_source_references().await?
+        }
         GenericSourceConnection::LoadGenerator(load_gen_connection) => {
             let reference_client = SourceReferenceClient::LoadGenerator {
                 generator: &load_gen_connection.load_generator,
@@ -1751,6 +1903,12 @@ async fn purify_alter_source_refresh_references(
             };
             reference_client.get_source_references().await?
         }
+        GenericSourceConnection::Kinesis(kinesis_conn) => {
+            let reference_client = SourceReferenceClient::Kinesis {
+                stream: &kinesis_conn.stream,
+            };
+            reference_client.get_source_references().await?
+        }
     };
     Ok(PurifiedStatement::PurifiedAlterSourceRefreshReferences {
         source_name: resolved_source_name,
@@ -1974,6 +2132,36 @@ async fn purify_create_table_from_source(
                 discussion_no: None,
             });
         }
+        GenericSourceConnection::MongoDb(mongodb_source_connection) => {
+            if !text_columns.is_empty() || !exclude_columns.is_empty() {
+                sql_bail!(
+                    "{} is a {} source, which does not support TEXT COLUMNS or EXCLUDE COLUMNS.",
+                    scx.catalog.minimal_qualification(qualified_source_name),
+                    connection_name
+                )
+            }
+
+            let client = mongodb_source_connection
+                .connection
+                .connect(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+            let reference_client = SourceReferenceClient::MongoDb { client: &client };
+            retrieved_source_references = reference_client.get_source_references().await?;
+
+            let source_exports = mongodb::purify_source_exports(
+                &retrieved_source_references,
+                &requested_references,
+                &unresolved_source_name,
+                &SourceReferencePolicy::Required,
+            )?;
+            // There should be exactly one source_export returned for this statement
+            let (_, purified_export) = source_exports.into_iter().next().unwrap();
+            purified_export
+        }
         GenericSourceConnection::LoadGenerator(load_gen_connection) => {
             let reference_client = SourceReferenceClient::LoadGenerator {
                 generator: &load_gen_connection.load_generator,
@@ -2018,6 +2206,21 @@ async fn purify_create_table_from_source(
                 details: PurifiedExportDetails::Kafka {},
             }
         }
+        GenericSourceConnection::Kinesis(kinesis_conn) => {
+            let reference_client = SourceReferenceClient::Kinesis {
+                stream: &kinesis_conn.stream,
+            };
+            retrieved_source_references = reference_client.get_source_references().await?;
+            let requested_exports = retrieved_source_references
+                .requested_source_exports(requested_references.as_ref(), &unresolved_source_name)?;
+            // There should be exactly one source_export returned
+            let export = requested_exports.into_iter().next().unwrap();
+
+            PurifiedSourceExport {
+                external_reference: export.external_reference,
+                details: PurifiedExportDetails::Kinesis {},
+            }
+        }
     };
 
     purify_source_format(
@@ -2142,6 +2345,30 @@ async fn purify_create_table_from_source(
                 discussion_no: None,
             });
         }
+        PurifiedExportDetails::MongoDb { .. } => {
+            let mongodb::MongoDbExportStatementValues {
+                columns: gen_columns,
+                constraints: gen_constraints,
+                details: gen_details,
+                external_reference: _,
+            } = mongodb::generate_source_export_statement_values(&scx, purified_export)?;
+            match columns {
+                TableFromSourceColumns::Defined(_) => unreachable!(),
+                TableFromSourceColumns::NotSpecified => {
+                    *columns = TableFromSourceColumns::Defined(gen_columns);
+                    *constraints = gen_constraints;
+                }
+                TableFromSourceColumns::Named(_) => {
+                    sql_bail!("columns cannot be named for MongoDB sources")
+                }
+            }
+            with_options.push(TableFromSourceOption {
+                name: TableFromSourceOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    gen_details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
         PurifiedExportDetails::LoadGenerator { .. } => {
             let (desc, output) = match purified_export.details {
                 PurifiedExportDetails::LoadGenerator { table, output } => (table, output),
@@ -2184,6 +2411,17 @@ async fn purify_create_table_from_source(
                 )))),
             })
         }
+        PurifiedExportDetails::Kinesis {} => {
+            // Like Kafka, the RelationDesc of a Kinesis table is determined by its format during
+            // planning.
+            let details = SourceExportStatementDetails::Kinesis {};
+            with_options.push(TableFromSourceOption {
+                name: TableFromSourceOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
     };
 
     // TODO: We might as well use the retrieved available references to update the source
@@ -2299,6 +2537,13 @@ pub fn generate_subsource_statements(
                 subsources,
             )?
         }
+        PurifiedExportDetails::MongoDb { .. } => {
+            crate::pure::mongodb::generate_create_subsource_statements(
+                scx,
+                source_name,
+                subsources,
+            )?
+        }
         PurifiedExportDetails::LoadGenerator { .. } => {
             let mut subsource_stmts = Vec::with_capacity(subsources.len());
             for (subsource_name, purified_export) in subsources {
@@ -2352,6 +2597,13 @@ pub fn generate_subsource_statements(
             );
             vec![]
         }
+        PurifiedExportDetails::Kinesis { .. } => {
+            assert!(
+                subsources.is_empty(),
+                "Kinesis sources do not produce data-bearing subsources"
+            );
+            vec![]
+        }
     };
     Ok(statements)
 }
@@ -2598,6 +2850,7 @@ pub fn purify_create_materialized_view_options(
                 filter: None,
                 over: None,
                 distinct: false,
+                within_group: vec![],
             }),
         )
     };
@@ -160,6 +160,34 @@ impl KafkaSourcePurificationError {
     }
 }
 
+/// Logical errors detectable during purification for a KINESIS SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum KinesisSourcePurificationError {
+    #[error("{} is only valid for multi-output sources", .0.to_ast_string_simple())]
+    ReferencedSubsources(ExternalReferences),
+    #[error("KINESIS CONNECTION without STREAM")]
+    ConnectionMissingStream,
+    #[error("{0} is not an AWS CONNECTION")]
+    NotAwsConnection(FullItemName),
+    #[error("stream {0} does not exist")]
+    StreamDoesNotExist(String),
+    #[error("failed to describe Kinesis stream")]
+    DescribeStream(String),
+}
+
+impl KinesisSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::DescribeStream(e) => Some(e.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        None
+    }
+}
+
 /// Logical errors detectable during purification for a LOAD GENERATOR SOURCE.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum LoadGeneratorSourcePurificationError {
@@ -433,3 +461,43 @@ impl SqlServerSourcePurificationError {
         }
     }
 }
+
+/// Logical errors detectable during purification for a MongoDB SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum MongoDbSourcePurificationError {
+    #[error("{0} is not a MONGODB CONNECTION")]
+    NotMongoDbConnection(FullItemName),
+    #[error("CREATE SOURCE specifies DETAILS option")]
+    UserSpecifiedDetails,
+    #[error("upstream server is not a member of a replica set")]
+    NotReplicaSet,
+    #[error("missing TABLES specification")]
+    RequiresExternalReferences,
+    #[error("No collections found for provided reference")]
+    NoCollections,
+}
+
+impl MongoDbSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::NotReplicaSet => Some(
+                "MongoDB only provides change streams on replica sets and sharded clusters".into(),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::RequiresExternalReferences => {
+                Some("provide a FOR TABLES (..), FOR SCHEMAS (..), or FOR ALL TABLES clause".into())
+            }
+            Self::NoCollections => Some(
+                "No collections were found to replicate. This could be because \
+                the user does not have privileges on the intended collections."
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! MongoDB utilities for SQL purification.
+
+use std::collections::BTreeMap;
+
+use mz_proto::RustType;
+use mz_sql_parser::ast::display::AstDisplay;
+use mz_sql_parser::ast::{
+    ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
+    ExternalReferences, TableConstraint, UnresolvedItemName, Value, WithOptionValue,
+};
+use mz_storage_types::sources::SourceExportStatementDetails;
+use mz_storage_types::sources::mongodb::MONGODB_COLLECTION_DESC;
+use prost::Message;
+
+use crate::names::{Aug, ResolvedItemName};
+use crate::plan::{PlanError, StatementContext};
+use crate::pure::MongoDbSourcePurificationError;
+
+use super::references::RetrievedSourceReferences;
+use super::{PurifiedExportDetails, PurifiedSourceExport, SourceReferencePolicy};
+
+pub fn generate_create_subsource_statements(
+    scx: &StatementContext,
+    source_name: ResolvedItemName,
+    requested_subsources: BTreeMap<UnresolvedItemName, PurifiedSourceExport>,
+) -> Result<Vec<CreateSubsourceStatement<Aug>>, PlanError> {
+    let mut subsources = Vec::with_capacity(requested_subsources.len());
+
+    for (subsource_name, purified_export) in requested_subsources {
+        let MongoDbExportStatementValues {
+            columns,
+            constraints,
+            details,
+            external_reference,
+        } = generate_source_export_statement_values(scx, purified_export)?;
+
+        let with_options = vec![
+            CreateSubsourceOption {
+                name: CreateSubsourceOptionName::ExternalReference,
+                value: Some(WithOptionValue::UnresolvedItemName(external_reference)),
+            },
+            CreateSubsourceOption {
+                name: CreateSubsourceOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            },
+        ];
+
+        // Create the subsource statement
+        let subsource = CreateSubsourceStatement {
+            name: subsource_name,
+            columns,
+            of_source: Some(source_name.clone()),
+            constraints,
+            if_not_exists: false,
+            with_options,
+        };
+        subsources.push(subsource);
+    }
+
+    Ok(subsources)
+}
+
+pub(super) struct MongoDbExportStatementValues {
+    pub(super) columns: Vec<ColumnDef<Aug>>,
+    pub(super) constraints: Vec<TableConstraint<Aug>>,
+    pub(super) details: SourceExportStatementDetails,
+    pub(super) external_reference: UnresolvedItemName,
+}
+
+pub(super) fn generate_source_export_statement_values(
+    scx: &StatementContext,
+    purified_export: PurifiedSourceExport,
+) -> Result<MongoDbExportStatementValues, PlanError> {
+    let PurifiedExportDetails::MongoDb {
+        database,
+        collection,
+    } = purified_export.details
+    else {
+        unreachable!("purified export details must be mongodb")
+    };
+
+    // Every collection has the same shape: the `_id` of each document, which
+    // is its primary key, and the document itself.
+    let (columns, constraints) = scx.relation_desc_into_table_defs(&MONGODB_COLLECTION_DESC)?;
+
+    let details = SourceExportStatementDetails::MongoDb {
+        database,
+        collection,
+    };
+
+    Ok(MongoDbExportStatementValues {
+        columns,
+        constraints,
+        details,
+        external_reference: purified_export.external_reference,
+    })
+}
+
+pub(super) fn purify_source_exports(
+    retrieved_references: &RetrievedSourceReferences,
+    requested_references: &Option<ExternalReferences>,
+    unresolved_source_name: &UnresolvedItemName,
+    reference_policy: &SourceReferencePolicy,
+) -> Result<BTreeMap<UnresolvedItemName, PurifiedSourceExport>, PlanError> {
+    let requested_exports = match requested_references.as_ref() {
+        Some(requested) if matches!(reference_policy, SourceReferencePolicy::NotAllowed) => {
+            Err(PlanError::UseTablesForSources(requested.to_string()))?
+        }
+        Some(requested) => retrieved_references
+            .requested_source_exports(Some(requested), unresolved_source_name)?,
+        None => {
+            if matches!(reference_policy, SourceReferencePolicy::Required) {
+                Err(MongoDbSourcePurificationError::RequiresExternalReferences)?
+            }
+            return Ok(BTreeMap::new());
+        }
+    };
+
+    if requested_exports.is_empty() {
+        sql_bail!(
+            "MongoDB source must ingest at least one collection, but {} matched none",
+            requested_references
+                .as_ref()
+                .unwrap()
+                .to_ast_string_simple()
+        );
+    }
+
+    super::validate_source_export_names(&requested_exports)?;
+
+    let source_exports = requested_exports
+        .into_iter()
+        .map(|export| {
+            let (database, collection) = export.meta.mongodb_collection().expect("is mongodb");
+            (
+                export.name,
+                PurifiedSourceExport {
+                    external_reference: export.external_reference,
+                    details: PurifiedExportDetails::MongoDb {
+                        database: database.to_string(),
+                        collection: collection.to_string(),
+                    },
+                },
+            )
+        })
+        .collect();
+
+    Ok(source_exports)
+}
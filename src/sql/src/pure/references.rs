@@ -15,6 +15,7 @@ use mz_ore::now::SYSTEM_TIME;
 use mz_repr::RelationDesc;
 use mz_sql_parser::ast::{ExternalReferences, Ident, IdentError, UnresolvedItemName};
 use mz_storage_types::sources::load_generator::{LoadGenerator, LoadGeneratorOutput};
+use mz_storage_types::sources::mongodb::MONGODB_COLLECTION_DESC;
 use mz_storage_types::sources::{ExternalReferenceResolutionError, SourceReferenceResolver};
 
 use crate::names::{FullItemName, RawDatabaseSpecifier};
@@ -40,9 +41,15 @@ pub(super) enum SourceReferenceClient<'a> {
         client: &'a mut mz_sql_server_util::Client,
         database: Arc<str>,
     },
+    MongoDb {
+        client: &'a mongodb::Client,
+    },
     Kafka {
         topic: &'a str,
     },
+    Kinesis {
+        stream: &'a str,
+    },
     LoadGenerator {
         generator: &'a LoadGenerator,
     },
@@ -61,7 +68,12 @@ pub(super) enum ReferenceMetadata {
         database: Arc<str>,
         capture_instance: Arc<str>,
     },
+    MongoDb {
+        database: String,
+        collection: String,
+    },
     Kafka(String),
+    Kinesis(String),
     LoadGenerator {
         name: String,
         desc: Option<RelationDesc>,
@@ -76,7 +88,8 @@ impl ReferenceMetadata {
             ReferenceMetadata::Postgres { table, .. } => Some(&table.namespace),
             ReferenceMetadata::MySql(table) => Some(&table.schema_name),
             ReferenceMetadata::SqlServer { table, .. } => Some(table.schema_name.as_ref()),
-            ReferenceMetadata::Kafka(_) => None,
+            ReferenceMetadata::MongoDb { database, .. } => Some(database),
+            ReferenceMetadata::Kafka(_) | ReferenceMetadata::Kinesis(_) => None,
             ReferenceMetadata::LoadGenerator { namespace, .. } => Some(namespace),
         }
     }
@@ -86,7 +99,9 @@ impl ReferenceMetadata {
             ReferenceMetadata::Postgres { table, .. } => &table.name,
             ReferenceMetadata::MySql(table) => &table.name,
             ReferenceMetadata::SqlServer { table, .. } => table.name.as_ref(),
+            ReferenceMetadata::MongoDb { collection, .. } => collection,
             ReferenceMetadata::Kafka(topic) => topic,
+            ReferenceMetadata::Kinesis(stream) => stream,
             ReferenceMetadata::LoadGenerator { name, .. } => name,
         }
     }
@@ -121,6 +136,16 @@ impl ReferenceMetadata {
         }
     }
 
+    pub(super) fn mongodb_collection(&self) -> Option<(&str, &str)> {
+        match self {
+            ReferenceMetadata::MongoDb {
+                database,
+                collection,
+            } => Some((database, collection)),
+            _ => None,
+        }
+    }
+
     pub(super) fn load_generator_desc(&self) -> Option<&Option<RelationDesc>> {
         match self {
             ReferenceMetadata::LoadGenerator { desc, .. } => Some(desc),
@@ -160,9 +185,19 @@ impl ReferenceMetadata {
                 Ident::new(table.schema_name.as_ref())?,
                 Ident::new(table.name.as_ref())?,
             ])),
+            ReferenceMetadata::MongoDb {
+                database,
+                collection,
+            } => Ok(UnresolvedItemName::qualified(&[
+                Ident::new(database)?,
+                Ident::new(collection)?,
+            ])),
             ReferenceMetadata::Kafka(topic) => {
                 Ok(UnresolvedItemName::qualified(&[Ident::new(topic)?]))
             }
+            ReferenceMetadata::Kinesis(stream) => {
+                Ok(UnresolvedItemName::qualified(&[Ident::new(stream)?]))
+            }
             ReferenceMetadata::LoadGenerator {
                 name, namespace, ..
             } => {
@@ -195,6 +230,9 @@ pub(super) struct RetrievedSourceReferences {
 /// method only includes the database name for Postgres sources.
 pub(crate) static DATABASE_FAKE_NAME: &str = "database";
 
+/// MongoDB databases that hold server metadata rather than user data.
+static MONGODB_SYSTEM_DATABASES: &[&str] = &["admin", "config", "local"];
+
 impl<'a> SourceReferenceClient<'a> {
     /// Get all available source references from the upstream system
     /// and return a `RetrievedSourceReferences` object that can be used
@@ -272,9 +310,37 @@ impl<'a> SourceReferenceClient<'a> {
                     })
                     .collect()
             }
+            SourceReferenceClient::MongoDb { client } => {
+                let mut references = vec![];
+                for database in client.list_database_names().await? {
+                    if MONGODB_SYSTEM_DATABASES.contains(&database.as_str()) {
+                        continue;
+                    }
+                    // Views and time series collections do not support change
+                    // streams, so only offer regular collections.
+                    let collections = client
+                        .database(&database)
+                        .list_collection_names()
+                        .filter(mongodb::bson::doc! { "type": "collection" })
+                        .await?;
+                    for collection in collections {
+                        if collection.starts_with("system.") {
+                            continue;
+                        }
+                        references.push(ReferenceMetadata::MongoDb {
+                            database: database.clone(),
+                            collection,
+                        });
+                    }
+                }
+                references
+            }
             SourceReferenceClient::Kafka { topic } => {
                 vec![ReferenceMetadata::Kafka(topic.to_string())]
             }
+            SourceReferenceClient::Kinesis { stream } => {
+                vec![ReferenceMetadata::Kinesis(stream.to_string())]
+            }
             SourceReferenceClient::LoadGenerator { generator } => {
                 let mut references = generator
                     .views()
@@ -360,11 +426,27 @@ impl RetrievedSourceReferences {
                             .map(|c| c.name.to_string())
                             .collect(),
                     },
+                    ReferenceMetadata::MongoDb {
+                        database,
+                        collection,
+                    } => SourceReference {
+                        name: collection,
+                        namespace: Some(database),
+                        columns: MONGODB_COLLECTION_DESC
+                            .iter_names()
+                            .map(|n| n.to_string())
+                            .collect(),
+                    },
                     ReferenceMetadata::Kafka(topic) => SourceReference {
                         name: topic,
                         namespace: None,
                         columns: vec![],
                     },
+                    ReferenceMetadata::Kinesis(stream) => SourceReference {
+                        name: stream,
+                        namespace: None,
+                        columns: vec![],
+                    },
                     ReferenceMetadata::LoadGenerator {
                         name,
                         desc,
@@ -930,6 +930,44 @@ fn generate_rbac_requirements(
             },
             ..Default::default()
         },
+        Plan::ExplainAnalyze(plan::ExplainAnalyzePlan { explainee, .. }) => {
+            // Analyzing a statement runs it, and analyzing an existing object
+            // reads the introspection data of its cluster.
+            let mut privileges = match explainee {
+                Explainee::Statement(stmt) => {
+                    let items = stmt
+                        .depends_on()
+                        .into_iter()
+                        .map(|gid| catalog.resolve_item_id(&gid));
+                    generate_read_privileges(catalog, items, role_id)
+                }
+                Explainee::View(id)
+                | Explainee::MaterializedView(id)
+                | Explainee::Index(id)
+                | Explainee::ReplanView(id)
+                | Explainee::ReplanMaterializedView(id)
+                | Explainee::ReplanIndex(id) => {
+                    let item = catalog.get_item(id);
+                    let schema_id: ObjectId = item.name().qualifiers.clone().into();
+                    vec![(SystemObjectId::Object(schema_id), AclMode::USAGE, role_id)]
+                }
+            };
+            if let Some(cluster_id) = target_cluster_id {
+                privileges.push((
+                    SystemObjectId::Object(cluster_id.into()),
+                    AclMode::USAGE,
+                    role_id,
+                ));
+            }
+            RbacRequirements {
+                privileges,
+                item_usage: match explainee {
+                    Explainee::Statement(_) => &DEFAULT_ITEM_USAGE,
+                    _ => &EMPTY_ITEM_USAGE,
+                },
+                ..Default::default()
+            }
+        }
         Plan::ExplainSinkSchema(plan::ExplainSinkSchemaPlan { sink_from, .. }) => {
             RbacRequirements {
                 privileges: {
@@ -417,6 +417,7 @@ impl SessionVars {
             &EMIT_INTROSPECTION_QUERY_NOTICE,
             &UNSAFE_NEW_TRANSACTION_WALL_TIME,
             &WELCOME_MESSAGE,
+            &WORKLOAD_CLASS,
         ]
         .into_iter()
         .chain(SESSION_SYSTEM_VARS.iter().map(|(_name, var)| *var))
@@ -566,6 +567,7 @@ impl SessionVars {
 
         let name = UncasedStr::new(name);
         self.check_read_only(name)?;
+        self.check_superuser_only(name)?;
 
         self.vars
             .get_mut(name)
@@ -628,6 +630,18 @@ impl SessionVars {
             .ok_or_else(|| VarError::UnknownParameter(name.to_string()))
     }
 
+    /// Returns an error if the variable corresponding to `name` can only be set
+    /// by superusers and the session's user is not one.
+    ///
+    /// Other users get their value from the defaults of their role.
+    fn check_superuser_only(&self, name: &UncasedStr) -> Result<(), VarError> {
+        if name == WORKLOAD_CLASS.name && !self.user.is_superuser() {
+            Err(VarError::RequiresSuperuser(WORKLOAD_CLASS.name.as_str()))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Returns an error if the variable corresponding to `name` is read only.
     fn check_read_only(&self, name: &UncasedStr) -> Result<(), VarError> {
         if name == MZ_VERSION_NAME {
@@ -786,6 +800,11 @@ impl SessionVars {
         self.expect_value(&TRANSACTION_ISOLATION)
     }
 
+    /// Returns the value of the `workload_class` configuration parameter.
+    pub fn workload_class(&self) -> WorkloadClass {
+        *self.expect_value(&WORKLOAD_CLASS)
+    }
+
     /// Returns the value of `real_time_recency` configuration parameter.
     pub fn real_time_recency(&self) -> bool {
         *self.expect_value(&REAL_TIME_RECENCY)
@@ -1095,6 +1114,11 @@ impl SystemVars {
             &MAX_RULES_PER_NETWORK_POLICY,
             &MAX_RESULT_SIZE,
             &MAX_COPY_FROM_SIZE,
+            &MAX_CONCURRENT_PEEKS_PER_CLUSTER,
+            &MAX_CONCURRENT_PEEKS_INTERACTIVE,
+            &MAX_CONCURRENT_PEEKS_STANDARD,
+            &MAX_CONCURRENT_PEEKS_BATCH,
+            &WORKLOAD_CLASS_QUEUE_TIMEOUT,
             &ALLOWED_CLUSTER_REPLICA_SIZES,
             &DISK_CLUSTER_REPLICAS_DEFAULT,
             &upsert_rocksdb::UPSERT_ROCKSDB_AUTO_SPILL_TO_DISK,
@@ -1188,6 +1212,11 @@ impl SystemVars {
             &cluster_scheduling::CLUSTER_CHECK_SCHEDULING_POLICIES_INTERVAL,
             &cluster_scheduling::CLUSTER_SECURITY_CONTEXT_ENABLED,
             &cluster_scheduling::CLUSTER_REFRESH_MV_COMPACTION_ESTIMATE,
+            &cluster_scheduling::CLUSTER_UTILIZATION_SCALE_UP_THRESHOLD,
+            &cluster_scheduling::CLUSTER_UTILIZATION_SCALE_DOWN_THRESHOLD,
+            &cluster_scheduling::CLUSTER_UTILIZATION_SUSTAINED_INTERVAL,
+            &cluster_scheduling::CLUSTER_UTILIZATION_COOLDOWN,
+            &cluster_scheduling::CLUSTER_UTILIZATION_RESIZE_TIMEOUT,
             &grpc_client::HTTP2_KEEP_ALIVE_TIMEOUT,
             &STATEMENT_LOGGING_MAX_SAMPLE_RATE,
             &STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE,
@@ -1642,6 +1671,27 @@ impl SystemVars {
         *self.expect_value(&MAX_COPY_FROM_SIZE)
     }
 
+    /// Returns the value of the `max_concurrent_peeks_per_cluster` configuration parameter.
+    pub fn max_concurrent_peeks_per_cluster(&self) -> u32 {
+        *self.expect_value(&MAX_CONCURRENT_PEEKS_PER_CLUSTER)
+    }
+
+    /// Returns the value of the `max_concurrent_peeks_*` configuration parameter of
+    /// `workload_class`.
+    pub fn max_concurrent_peeks(&self, workload_class: WorkloadClass) -> u32 {
+        let var = match workload_class {
+            WorkloadClass::Interactive => &MAX_CONCURRENT_PEEKS_INTERACTIVE,
+            WorkloadClass::Standard => &MAX_CONCURRENT_PEEKS_STANDARD,
+            WorkloadClass::Batch => &MAX_CONCURRENT_PEEKS_BATCH,
+        };
+        *self.expect_value(var)
+    }
+
+    /// Returns the value of the `workload_class_queue_timeout` configuration parameter.
+    pub fn workload_class_queue_timeout(&self) -> Duration {
+        *self.expect_value(&WORKLOAD_CLASS_QUEUE_TIMEOUT)
+    }
+
     /// Returns the value of the `allowed_cluster_replica_sizes` configuration parameter.
     pub fn allowed_cluster_replica_sizes(&self) -> Vec<String> {
         self.expect_value::<Vec<Ident>>(&ALLOWED_CLUSTER_REPLICA_SIZES)
@@ -2115,6 +2165,26 @@ impl SystemVars {
         *self.expect_value(&cluster_scheduling::CLUSTER_REFRESH_MV_COMPACTION_ESTIMATE)
     }
 
+    pub fn cluster_utilization_scale_up_threshold(&self) -> u32 {
+        *self.expect_value(&cluster_scheduling::CLUSTER_UTILIZATION_SCALE_UP_THRESHOLD)
+    }
+
+    pub fn cluster_utilization_scale_down_threshold(&self) -> u32 {
+        *self.expect_value(&cluster_scheduling::CLUSTER_UTILIZATION_SCALE_DOWN_THRESHOLD)
+    }
+
+    pub fn cluster_utilization_sustained_interval(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_UTILIZATION_SUSTAINED_INTERVAL)
+    }
+
+    pub fn cluster_utilization_cooldown(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_UTILIZATION_COOLDOWN)
+    }
+
+    pub fn cluster_utilization_resize_timeout(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_UTILIZATION_RESIZE_TIMEOUT)
+    }
+
     /// Returns the `privatelink_status_update_quota_per_minute` configuration parameter.
     pub fn privatelink_status_update_quota_per_minute(&self) -> u32 {
         *self.expect_value(&PRIVATELINK_STATUS_UPDATE_QUOTA_PER_MINUTE)
@@ -45,7 +45,7 @@ use crate::session::vars::errors::VarError;
 use crate::session::vars::polyfill::{LazyValueFn, lazy_value, value};
 use crate::session::vars::value::{
     ClientEncoding, ClientSeverity, DEFAULT_DATE_STYLE, Failpoints, IntervalStyle, IsolationLevel,
-    TimeZone, Value,
+    TimeZone, Value, WorkloadClass,
 };
 use crate::session::vars::{FeatureFlag, Var, VarInput, VarParseError};
 use crate::{DEFAULT_SCHEMA, WEBHOOK_CONCURRENCY_LIMIT};
@@ -428,6 +428,15 @@ pub static TRANSACTION_ISOLATION: VarDefinition = VarDefinition::new(
     true,
 );
 
+pub static WORKLOAD_CLASS: VarDefinition = VarDefinition::new(
+    "workload_class",
+    value!(WorkloadClass; WorkloadClass::Standard),
+    "Sets the workload class that determines the priority and concurrency limits of the \
+    session's queries, one of interactive, standard, or batch. Only superusers can set it, \
+    including as a role default (Materialize).",
+    true,
+);
+
 pub static MAX_KAFKA_CONNECTIONS: VarDefinition = VarDefinition::new(
     "max_kafka_connections",
     value!(u32; 1000),
@@ -599,6 +608,47 @@ pub static MAX_COPY_FROM_SIZE: VarDefinition = VarDefinition::new(
     true,
 );
 
+pub static MAX_CONCURRENT_PEEKS_PER_CLUSTER: VarDefinition = VarDefinition::new(
+    "max_concurrent_peeks_per_cluster",
+    value!(u32; 0),
+    "The maximum number of queries that can execute concurrently on a cluster, across all \
+    workload classes. Further queries are queued. Zero means no limit (Materialize).",
+    true,
+);
+
+pub static MAX_CONCURRENT_PEEKS_INTERACTIVE: VarDefinition = VarDefinition::new(
+    "max_concurrent_peeks_interactive",
+    value!(u32; 0),
+    "The maximum number of queries of the interactive workload class that can execute \
+    concurrently on a cluster. Zero means no limit (Materialize).",
+    true,
+);
+
+pub static MAX_CONCURRENT_PEEKS_STANDARD: VarDefinition = VarDefinition::new(
+    "max_concurrent_peeks_standard",
+    value!(u32; 0),
+    "The maximum number of queries of the standard workload class that can execute \
+    concurrently on a cluster. Zero means no limit (Materialize).",
+    true,
+);
+
+pub static MAX_CONCURRENT_PEEKS_BATCH: VarDefinition = VarDefinition::new(
+    "max_concurrent_peeks_batch",
+    value!(u32; 0),
+    "The maximum number of queries of the batch workload class that can execute \
+    concurrently on a cluster. Zero means no limit (Materialize).",
+    true,
+);
+
+pub static WORKLOAD_CLASS_QUEUE_TIMEOUT: VarDefinition = VarDefinition::new(
+    "workload_class_queue_timeout",
+    value!(Duration; Duration::from_secs(60)),
+    "The maximum duration a query waits for admission to a cluster, or a DDL statement waits \
+    for other DDL statements, before it is canceled. If this value is specified without units, \
+    it is taken as milliseconds (Materialize).",
+    true,
+);
+
 pub static MAX_IDENTIFIER_LENGTH: VarDefinition = VarDefinition::new(
     "max_identifier_length",
     value!(usize; mz_sql_lexer::lexer::MAX_IDENTIFIER_LENGTH),
@@ -1668,6 +1718,55 @@ pub mod cluster_scheduling {
         false,
     );
 
+    const DEFAULT_CLUSTER_UTILIZATION_SCALE_UP_THRESHOLD: u32 = 80;
+
+    pub static CLUSTER_UTILIZATION_SCALE_UP_THRESHOLD: VarDefinition = VarDefinition::new(
+        "cluster_utilization_scale_up_threshold",
+        value!(u32; DEFAULT_CLUSTER_UTILIZATION_SCALE_UP_THRESHOLD),
+        "The CPU or memory utilization, in percent, above which a `SCHEDULE = ON UTILIZATION` \
+            cluster is resized to the next larger size.",
+        false,
+    );
+
+    const DEFAULT_CLUSTER_UTILIZATION_SCALE_DOWN_THRESHOLD: u32 = 30;
+
+    pub static CLUSTER_UTILIZATION_SCALE_DOWN_THRESHOLD: VarDefinition = VarDefinition::new(
+        "cluster_utilization_scale_down_threshold",
+        value!(u32; DEFAULT_CLUSTER_UTILIZATION_SCALE_DOWN_THRESHOLD),
+        "The CPU and memory utilization, in percent, below which a `SCHEDULE = ON UTILIZATION` \
+            cluster is resized to the next smaller size.",
+        false,
+    );
+
+    const DEFAULT_CLUSTER_UTILIZATION_SUSTAINED_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+    pub static CLUSTER_UTILIZATION_SUSTAINED_INTERVAL: VarDefinition = VarDefinition::new(
+        "cluster_utilization_sustained_interval",
+        value!(Duration; DEFAULT_CLUSTER_UTILIZATION_SUSTAINED_INTERVAL),
+        "How long the utilization of a `SCHEDULE = ON UTILIZATION` cluster has to stay above \
+            or below a threshold before the cluster is resized.",
+        false,
+    );
+
+    const DEFAULT_CLUSTER_UTILIZATION_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+
+    pub static CLUSTER_UTILIZATION_COOLDOWN: VarDefinition = VarDefinition::new(
+        "cluster_utilization_cooldown",
+        value!(Duration; DEFAULT_CLUSTER_UTILIZATION_COOLDOWN),
+        "The minimum time between two resizes of a `SCHEDULE = ON UTILIZATION` cluster.",
+        false,
+    );
+
+    const DEFAULT_CLUSTER_UTILIZATION_RESIZE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+    pub static CLUSTER_UTILIZATION_RESIZE_TIMEOUT: VarDefinition = VarDefinition::new(
+        "cluster_utilization_resize_timeout",
+        value!(Duration; DEFAULT_CLUSTER_UTILIZATION_RESIZE_TIMEOUT),
+        "How long to wait for the new replicas of a `SCHEDULE = ON UTILIZATION` cluster to \
+            hydrate during a resize, before abandoning the resize.",
+        false,
+    );
+
     const DEFAULT_CLUSTER_REFRESH_MV_COMPACTION_ESTIMATE: Duration = Duration::from_secs(60);
 
     pub static CLUSTER_REFRESH_MV_COMPACTION_ESTIMATE: VarDefinition = VarDefinition::new(
@@ -1831,6 +1930,12 @@ feature_flags!(
         default: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_explain_analyze,
+        desc: "EXPLAIN ANALYZE",
+        default: false,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_index_options,
         desc: "INDEX OPTIONS",
@@ -2061,6 +2166,18 @@ feature_flags!(
         default: false,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_cluster_schedule_utilization,
+        desc: "`SCHEDULE = ON UTILIZATION` cluster option",
+        default: false,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_cluster_auto_suspend,
+        desc: "`AUTO SUSPEND AFTER` cluster option",
+        default: false,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_reduce_mfp_fusion,
         desc: "fusion of MFPs in reductions",
@@ -2109,6 +2226,36 @@ feature_flags!(
         default: false,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_iceberg_sink,
+        desc: "CREATE SINK ... INTO ICEBERG",
+        default: false,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: unsafe_enable_iceberg_sink_local_warehouse,
+        desc: "Iceberg sinks writing to a warehouse on the local filesystem",
+        default: false,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_s3_sink,
+        desc: "CREATE SINK ... INTO S3",
+        default: false,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_postgres_sink,
+        desc: "CREATE SINK ... INTO POSTGRES",
+        default: false,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_http_sink,
+        desc: "CREATE SINK ... INTO HTTP",
+        default: false,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_unlimited_retain_history,
         desc: "Disable limits on RETAIN HISTORY (below 1s default, and 0 disables compaction).",
@@ -2187,6 +2334,12 @@ feature_flags!(
         default: false,
         enable_for_item_parsing: false,
     },
+    {
+        name: unsafe_enable_copy_from_local_file,
+        desc: "COPY FROM 'file://...' reading from the local filesystem of the cluster",
+        default: false,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_join_prioritize_arranged,
         desc: "Whether join planning should prioritize already-arranged keys over keys with more fields.",
@@ -2199,6 +2352,18 @@ feature_flags!(
         default: false,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_mongodb_source,
+        desc: "Creating a MONGODB source",
+        default: false,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_kinesis_source,
+        desc: "Creating a KINESIS source",
+        default: false,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_projection_pushdown_after_relation_cse,
         desc: "Run ProjectionPushdown one more time after the last RelationCSE.",
@@ -87,6 +87,9 @@ pub enum VarError {
     /// The specified session parameter is read only unless in unsafe mode.
     #[error("parameter {} can only be set in unsafe mode", .0.quoted())]
     RequiresUnsafeMode(&'static str),
+    /// The specified session parameter can only be set by superusers.
+    #[error("permission denied to set parameter {}", .0.quoted())]
+    RequiresSuperuser(&'static str),
     #[error(
         "{} is not {}",
         .feature_flag.feature_desc,
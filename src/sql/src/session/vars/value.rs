@@ -994,6 +994,75 @@ impl From<TransactionIsolationLevel> for IsolationLevel {
     }
 }
 
+/// The workload class of a session, which determines the priority and
+/// concurrency limits its queries are admitted with.
+///
+/// Variants are ordered by increasing priority.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum WorkloadClass {
+    /// Heavy, latency-insensitive queries, e.g. ad hoc analysis.
+    Batch,
+    /// The default class.
+    Standard,
+    /// Latency-sensitive queries, e.g. those issued by applications.
+    Interactive,
+}
+
+impl WorkloadClass {
+    /// All workload classes, in order of decreasing priority.
+    pub const ALL: [WorkloadClass; 3] = [Self::Interactive, Self::Standard, Self::Batch];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Batch => "batch",
+            Self::Standard => "standard",
+            Self::Interactive => "interactive",
+        }
+    }
+
+    fn valid_values() -> Vec<&'static str> {
+        Self::ALL.iter().map(|class| class.as_str()).collect()
+    }
+}
+
+impl fmt::Display for WorkloadClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Value for WorkloadClass {
+    fn type_name() -> Cow<'static, str>
+    where
+        Self: Sized,
+    {
+        "string".into()
+    }
+
+    fn parse(input: VarInput<'_>) -> Result<Self, VarParseError>
+    where
+        Self: Sized,
+    {
+        let s = extract_single_value(input)?;
+        let s = UncasedStr::new(s);
+        Self::ALL
+            .into_iter()
+            .find(|class| s == class.as_str())
+            .ok_or_else(|| VarParseError::ConstrainedParameter {
+                invalid_values: input.to_vec(),
+                valid_values: Some(WorkloadClass::valid_values()),
+            })
+    }
+
+    fn box_clone(&self) -> Box<dyn Value> {
+        Box::new(self.clone())
+    }
+
+    fn format(&self) -> String {
+        self.as_str().into()
+    }
+}
+
 impl Value for CloneableEnvFilter {
     fn type_name() -> Cow<'static, str>
     where
@@ -2496,7 +2496,8 @@ where
                     GenericSourceConnection::Kafka(_)
                     | GenericSourceConnection::Postgres(_)
                     | GenericSourceConnection::MySql(_)
-                    | GenericSourceConnection::SqlServer(_) => {
+                    | GenericSourceConnection::SqlServer(_)
+                    | GenericSourceConnection::MongoDb(_) => {
                         (connection.clone(), *remap_collection_id)
                     }
 
@@ -2505,6 +2506,9 @@ where
                     // poses an annoying user experience, so instead just skip
                     // over them.
                     GenericSourceConnection::LoadGenerator(_) => continue,
+                    // Kinesis does not expose a cheap way to learn the latest
+                    // sequence number of every shard, so RTR is not supported.
+                    GenericSourceConnection::Kinesis(_) => continue,
                 },
                 // Skip over all other objects
                 _ => {
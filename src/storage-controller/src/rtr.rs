@@ -44,6 +44,8 @@ use crate::Timestamp;
 ///   generator sources do not yet (or might never) support real-time
 ///   recency. You can avoid this panic by choosing to not call this
 ///   function on load generator sources.
+/// - If `self` is a [`GenericSourceConnection::Kinesis`], which likewise does
+///   not support real-time recency.
 pub(super) async fn real_time_recency_ts<
     T: Timestamp + Lattice + TotalOrder + Codec64 + From<EpochMillis> + Sync,
 >(
@@ -110,12 +112,30 @@ pub(super) async fn real_time_recency_ts<
             )
             .await
         }
+        GenericSourceConnection::MongoDb(mongodb) => {
+            let external_frontier = mongodb
+                .fetch_write_frontier(&config)
+                .await
+                .map_err(StorageError::Generic)?;
+
+            decode_remap_data_until_geq_external_frontier(
+                id,
+                external_frontier,
+                as_of,
+                remap_subscribe,
+            )
+            .await
+        }
         // Load generator sources have no "external system" to reach out to,
-        // so it's unclear what RTR would mean for them.
-        s @ GenericSourceConnection::LoadGenerator(_) => unreachable!(
-            "do not try to determine RTR timestamp on {} source",
-            s.name()
-        ),
+        // so it's unclear what RTR would mean for them. Kinesis sources skip
+        // RTR because the stream's shards can't be cheaply asked for their
+        // latest sequence numbers.
+        s @ (GenericSourceConnection::LoadGenerator(_) | GenericSourceConnection::Kinesis(_)) => {
+            unreachable!(
+                "do not try to determine RTR timestamp on {} source",
+                s.name()
+            )
+        }
     }
 }
 
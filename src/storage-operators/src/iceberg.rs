@@ -0,0 +1,487 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Committing snapshots to Apache Iceberg tables.
+//!
+//! Tables use the Hadoop catalog layout: the table lives entirely under its
+//! location, each commit writes a new `metadata/v{N}.metadata.json` file, and
+//! `metadata/version-hint.text` points at the latest version. A commit is
+//! atomic because creating the next metadata file is an exclusive write; a
+//! writer that loses the race to create it must reload the table.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, anyhow, bail};
+use bytes::Bytes;
+use mz_repr::GlobalId;
+use mz_storage_types::sinks::iceberg::{
+    FRONTIER_SUMMARY_KEY, Schema, Snapshot, TableMetadata, VERSION_HINT_FILE, metadata_file_path,
+};
+use tracing::{debug, info};
+
+use crate::iceberg::io::{FileIo, PutIfAbsent};
+use crate::iceberg::manifest::{
+    DataFile, EntryStatus, ManifestContent, ManifestEntry, ManifestFile,
+};
+
+pub mod io;
+pub mod manifest;
+pub mod parquet;
+
+/// The number of snapshots and metadata log entries retained in the table
+/// metadata. Files referenced only by expired snapshots are not deleted.
+const MAX_RETAINED_SNAPSHOTS: usize = 100;
+
+/// An Iceberg table and the manifests of its current snapshot.
+#[derive(Debug)]
+pub struct Table {
+    io: FileIo,
+    location: String,
+    metadata: TableMetadata,
+    /// The version of the metadata file that `metadata` was read from.
+    version: u64,
+    /// The manifests of the current snapshot.
+    manifests: Vec<ManifestFile>,
+}
+
+/// The changes made by a snapshot.
+#[derive(Debug, Default)]
+pub struct SnapshotUpdate {
+    /// Data files added by the snapshot.
+    pub data_files: Vec<DataFile>,
+    /// Equality delete files added by the snapshot.
+    pub delete_files: Vec<DataFile>,
+    /// Additional entries to record in the snapshot summary.
+    pub summary: BTreeMap<String, String>,
+}
+
+impl Table {
+    /// Loads the table at `location`, returning `None` if no table exists
+    /// there.
+    pub async fn load(io: FileIo, location: String) -> Result<Option<Self>, anyhow::Error> {
+        let location = location.trim_end_matches('/').to_string();
+        let hint_uri = format!("{location}/metadata/{VERSION_HINT_FILE}");
+        let hint = match io.get(&hint_uri).await? {
+            Some(bytes) => std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .ok_or_else(|| anyhow!("invalid version hint in {hint_uri}"))?,
+            None => 0,
+        };
+
+        // The version hint is only updated after a commit succeeds, so it may
+        // lag behind the latest metadata file.
+        let mut version = hint;
+        let mut latest = None;
+        loop {
+            let candidate = version + 1;
+            let uri = format!("{location}/{}", metadata_file_path(candidate));
+            match io.get(&uri).await? {
+                Some(bytes) => {
+                    latest = Some(bytes);
+                    version = candidate;
+                }
+                None => break,
+            }
+        }
+        let bytes = match latest {
+            Some(bytes) => bytes,
+            None if version == 0 => return Ok(None),
+            None => {
+                let uri = format!("{location}/{}", metadata_file_path(version));
+                io.get(&uri)
+                    .await?
+                    .ok_or_else(|| anyhow!("version hint points at missing metadata {uri}"))?
+            }
+        };
+        let metadata: TableMetadata =
+            serde_json::from_slice(&bytes).context("parsing table metadata")?;
+
+        let manifests = match metadata.current_snapshot() {
+            Some(snapshot) => {
+                let bytes = io
+                    .get(&snapshot.manifest_list)
+                    .await?
+                    .ok_or_else(|| anyhow!("missing manifest list {}", snapshot.manifest_list))?;
+                manifest::read_manifest_list(&bytes)?
+            }
+            None => vec![],
+        };
+
+        debug!(%location, version, "loaded iceberg table");
+        Ok(Some(Table {
+            io,
+            location,
+            metadata,
+            version,
+            manifests,
+        }))
+    }
+
+    /// Creates a new, empty table at `location` owned by `sink_id`.
+    pub async fn create(
+        io: FileIo,
+        location: String,
+        sink_id: GlobalId,
+        schema: Schema,
+        now_ms: i64,
+    ) -> Result<Self, anyhow::Error> {
+        let location = location.trim_end_matches('/').to_string();
+        let metadata = TableMetadata::new(
+            sink_id,
+            location.clone(),
+            uuid::Uuid::new_v4().to_string(),
+            schema,
+            now_ms,
+        );
+        let mut table = Table {
+            io,
+            location,
+            metadata,
+            version: 0,
+            manifests: vec![],
+        };
+        table.write_metadata(table.metadata.clone()).await?;
+        info!(location = %table.location, %sink_id, "created iceberg table");
+        Ok(table)
+    }
+
+    /// Returns the table's metadata.
+    pub fn metadata(&self) -> &TableMetadata {
+        &self.metadata
+    }
+
+    /// Returns the frontier recorded by the table's current snapshot, if the
+    /// snapshot was committed by a sink. `Some(None)` is the empty frontier.
+    pub fn frontier(&self) -> Option<Option<u64>> {
+        self.metadata
+            .current_snapshot()
+            .and_then(Snapshot::frontier)
+    }
+
+    /// Returns a new, unique URI for a data or delete file.
+    pub fn new_data_file_uri(&self) -> String {
+        format!("{}/data/{}.parquet", self.location, uuid::Uuid::new_v4())
+    }
+
+    /// Writes a file that will be referenced by a future snapshot.
+    pub async fn write_file(&self, uri: &str, bytes: Vec<u8>) -> Result<(), anyhow::Error> {
+        self.io.put(uri, Bytes::from(bytes)).await
+    }
+
+    /// Commits a new snapshot to the table.
+    ///
+    /// Manifests of the same kind are merged into one once there are more
+    /// than `merge_min_count` of them. Returns an error if another writer
+    /// committed to the table since it was loaded; the table must then be
+    /// reloaded.
+    pub async fn commit(
+        &mut self,
+        update: SnapshotUpdate,
+        now_ms: i64,
+        merge_min_count: usize,
+    ) -> Result<(), anyhow::Error> {
+        let snapshot_id = new_snapshot_id();
+        let parent_snapshot_id = self.metadata.current_snapshot_id;
+        let sequence_number = self.metadata.last_sequence_number + 1;
+        let schema = self
+            .metadata
+            .current_schema()
+            .ok_or_else(|| anyhow!("table has no current schema"))?;
+        let schema_json = serde_json::to_string(schema)?;
+        let schema_id = schema.schema_id;
+
+        let mut summary = update.summary;
+        let added_records: i64 = update.data_files.iter().map(|f| f.record_count).sum();
+        let added_deletes: i64 = update.delete_files.iter().map(|f| f.record_count).sum();
+        summary.insert(
+            "operation".into(),
+            if update.delete_files.is_empty() {
+                "append"
+            } else {
+                "overwrite"
+            }
+            .into(),
+        );
+        summary.insert(
+            "added-data-files".into(),
+            update.data_files.len().to_string(),
+        );
+        summary.insert("added-records".into(), added_records.to_string());
+        summary.insert(
+            "added-delete-files".into(),
+            update.delete_files.len().to_string(),
+        );
+        summary.insert("added-equality-deletes".into(), added_deletes.to_string());
+
+        let mut manifests = self.manifests.clone();
+        for (content, files) in [
+            (ManifestContent::Data, update.data_files),
+            (ManifestContent::Deletes, update.delete_files),
+        ] {
+            if files.is_empty() {
+                continue;
+            }
+            let entries: Vec<_> = files
+                .into_iter()
+                .map(|data_file| ManifestEntry {
+                    status: EntryStatus::Added,
+                    snapshot_id,
+                    sequence_number,
+                    file_sequence_number: sequence_number,
+                    data_file,
+                })
+                .collect();
+            let manifest = self
+                .write_manifest(
+                    &schema_json,
+                    schema_id,
+                    content,
+                    snapshot_id,
+                    sequence_number,
+                    entries,
+                )
+                .await?;
+            manifests.push(manifest);
+        }
+
+        for content in [ManifestContent::Data, ManifestContent::Deletes] {
+            let count = manifests.iter().filter(|m| m.content == content).count();
+            if count > merge_min_count {
+                manifests = self
+                    .merge_manifests(
+                        manifests,
+                        content,
+                        &schema_json,
+                        schema_id,
+                        snapshot_id,
+                        sequence_number,
+                    )
+                    .await?;
+            }
+        }
+
+        let manifest_list = manifest::write_manifest_list(
+            snapshot_id,
+            parent_snapshot_id,
+            sequence_number,
+            &manifests,
+        )?;
+        let manifest_list_uri = format!(
+            "{}/metadata/snap-{snapshot_id}-{}.avro",
+            self.location,
+            uuid::Uuid::new_v4()
+        );
+        self.io
+            .put(&manifest_list_uri, Bytes::from(manifest_list))
+            .await?;
+
+        let mut metadata = self.metadata.clone();
+        metadata.push_metadata_log(
+            format!("{}/{}", self.location, metadata_file_path(self.version)),
+            self.metadata.last_updated_ms,
+            MAX_RETAINED_SNAPSHOTS,
+        );
+        metadata.push_snapshot(
+            Snapshot {
+                snapshot_id,
+                parent_snapshot_id,
+                sequence_number,
+                timestamp_ms: now_ms,
+                manifest_list: manifest_list_uri,
+                summary,
+                schema_id: Some(schema_id),
+            },
+            MAX_RETAINED_SNAPSHOTS,
+        );
+        self.write_metadata(metadata).await?;
+        self.manifests = manifests;
+        debug!(
+            location = %self.location,
+            version = self.version,
+            snapshot_id,
+            "committed iceberg snapshot"
+        );
+        Ok(())
+    }
+
+    /// Writes `metadata` as the next version of the table's metadata and
+    /// makes it current.
+    async fn write_metadata(&mut self, metadata: TableMetadata) -> Result<(), anyhow::Error> {
+        let version = self.version + 1;
+        let uri = format!("{}/{}", self.location, metadata_file_path(version));
+        let bytes = serde_json::to_vec_pretty(&metadata)?;
+        match self.io.put_if_absent(&uri, Bytes::from(bytes)).await? {
+            PutIfAbsent::Written => {}
+            PutIfAbsent::AlreadyExists => {
+                bail!(
+                    "iceberg table {} was concurrently modified: {uri} already exists",
+                    self.location
+                )
+            }
+        }
+        self.version = version;
+        self.metadata = metadata;
+
+        // The hint is only an optimization for readers; a stale hint is
+        // corrected the next time the table is loaded.
+        let hint_uri = format!("{}/metadata/{VERSION_HINT_FILE}", self.location);
+        self.io
+            .put(&hint_uri, Bytes::from(version.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    async fn write_manifest(
+        &self,
+        schema_json: &str,
+        schema_id: i32,
+        content: ManifestContent,
+        snapshot_id: i64,
+        sequence_number: i64,
+        entries: Vec<ManifestEntry>,
+    ) -> Result<ManifestFile, anyhow::Error> {
+        let mut manifest = ManifestFile {
+            manifest_path: format!(
+                "{}/metadata/{}-m0.avro",
+                self.location,
+                uuid::Uuid::new_v4()
+            ),
+            manifest_length: 0,
+            content,
+            sequence_number,
+            min_sequence_number: entries
+                .iter()
+                .map(|e| e.sequence_number)
+                .min()
+                .unwrap_or(sequence_number),
+            added_snapshot_id: snapshot_id,
+            added_files_count: 0,
+            existing_files_count: 0,
+            deleted_files_count: 0,
+            added_rows_count: 0,
+            existing_rows_count: 0,
+            deleted_rows_count: 0,
+        };
+        for entry in &entries {
+            let rows = entry.data_file.record_count;
+            match entry.status {
+                EntryStatus::Added => {
+                    manifest.added_files_count += 1;
+                    manifest.added_rows_count += rows;
+                }
+                EntryStatus::Existing => {
+                    manifest.existing_files_count += 1;
+                    manifest.existing_rows_count += rows;
+                }
+                EntryStatus::Deleted => {
+                    manifest.deleted_files_count += 1;
+                    manifest.deleted_rows_count += rows;
+                }
+            }
+        }
+        let bytes = manifest::write_manifest(schema_json, schema_id, content, &entries)?;
+        manifest.manifest_length = parquet::file_size(&bytes);
+        self.io
+            .put(&manifest.manifest_path, Bytes::from(bytes))
+            .await?;
+        Ok(manifest)
+    }
+
+    /// Rewrites all manifests of `content` into a single manifest.
+    async fn merge_manifests(
+        &self,
+        manifests: Vec<ManifestFile>,
+        content: ManifestContent,
+        schema_json: &str,
+        schema_id: i32,
+        snapshot_id: i64,
+        sequence_number: i64,
+    ) -> Result<Vec<ManifestFile>, anyhow::Error> {
+        let (to_merge, mut keep): (Vec<_>, Vec<_>) =
+            manifests.into_iter().partition(|m| m.content == content);
+        let mut entries = vec![];
+        for manifest in &to_merge {
+            let bytes = self
+                .io
+                .get(&manifest.manifest_path)
+                .await?
+                .ok_or_else(|| anyhow!("missing manifest {}", manifest.manifest_path))?;
+            for mut entry in manifest::read_manifest(&bytes)? {
+                match entry.status {
+                    EntryStatus::Deleted => continue,
+                    // Entries added by the snapshot being committed keep
+                    // their status.
+                    EntryStatus::Added if entry.snapshot_id == snapshot_id => {}
+                    EntryStatus::Added | EntryStatus::Existing => {
+                        entry.status = EntryStatus::Existing;
+                    }
+                }
+                entries.push(entry);
+            }
+        }
+        debug!(
+            location = %self.location,
+            manifests = to_merge.len(),
+            entries = entries.len(),
+            "merging iceberg manifests"
+        );
+        let merged = self
+            .write_manifest(
+                schema_json,
+                schema_id,
+                content,
+                snapshot_id,
+                sequence_number,
+                entries,
+            )
+            .await?;
+        keep.push(merged);
+        Ok(keep)
+    }
+}
+
+/// Loads the table at `location`, creating it if it does not exist, and
+/// checks that it can be written to by `sink_id`.
+pub async fn load_or_create_table(
+    io: FileIo,
+    location: String,
+    sink_id: GlobalId,
+    schema: Schema,
+    now_ms: i64,
+) -> Result<Table, anyhow::Error> {
+    let table = match Table::load(io.clone(), location.clone()).await? {
+        Some(table) => table,
+        None => return Table::create(io, location, sink_id, schema, now_ms).await,
+    };
+    let sink_id = sink_id.to_string();
+    match table.metadata().owning_sink() {
+        Some(owner) if owner == sink_id => {}
+        Some(owner) => bail!("iceberg table {location} is owned by sink {owner}"),
+        None => bail!("iceberg table {location} was not created by a sink"),
+    }
+    match table.metadata().current_schema() {
+        Some(current) if current.same_columns(&schema) => {}
+        _ => bail!("iceberg table {location} has a schema that does not match the sink"),
+    }
+    Ok(table)
+}
+
+/// Returns the snapshot summary entries that record `frontier`.
+pub fn frontier_summary(frontier: Option<u64>) -> BTreeMap<String, String> {
+    let value = frontier.map(|t| t.to_string()).unwrap_or_default();
+    BTreeMap::from([(FRONTIER_SUMMARY_KEY.to_string(), value)])
+}
+
+/// Generates a random, positive snapshot ID.
+fn new_snapshot_id() -> i64 {
+    let bytes = uuid::Uuid::new_v4().into_bytes();
+    let id = i64::from_le_bytes(bytes[..8].try_into().expect("8 bytes"));
+    id & i64::MAX
+}
@@ -0,0 +1,230 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Access to the files of an Iceberg table.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, anyhow, bail};
+use aws_sdk_s3::Client;
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use mz_ore::future::InTask;
+use mz_storage_types::connections::ConnectionContext;
+use mz_storage_types::sinks::IcebergSinkConnection;
+
+/// The outcome of an exclusive write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PutIfAbsent {
+    /// The file was written.
+    Written,
+    /// A file already existed at the path and was left untouched.
+    AlreadyExists,
+}
+
+/// A location that stores the files of Iceberg tables, addressed by URI.
+///
+/// Object stores address files as `s3://bucket/key` and local filesystems as
+/// `file:///path`. All paths handed to and returned from a `FileIo` are full
+/// URIs so that they can be recorded verbatim in table metadata.
+#[derive(Debug, Clone)]
+pub enum FileIo {
+    S3 { client: Client },
+    Local,
+}
+
+impl FileIo {
+    /// Returns a `FileIo` for the warehouse of the given sink.
+    ///
+    /// Warehouses accessed through an AWS connection live in S3; all others
+    /// live on the local filesystem.
+    pub async fn for_sink(
+        connection: &IcebergSinkConnection,
+        connection_context: &ConnectionContext,
+    ) -> Result<Self, anyhow::Error> {
+        match &connection.aws_connection {
+            Some(aws) => {
+                let sdk_config = aws
+                    .connection
+                    .load_sdk_config(connection_context, aws.connection_id, InTask::Yes)
+                    .await?;
+                Ok(FileIo::S3 {
+                    client: mz_aws_util::s3::new_client(&sdk_config),
+                })
+            }
+            None => Ok(FileIo::Local),
+        }
+    }
+
+    /// Reads the file at `uri`, returning `None` if it does not exist.
+    pub async fn get(&self, uri: &str) -> Result<Option<Bytes>, anyhow::Error> {
+        match self {
+            FileIo::S3 { client } => {
+                let (bucket, key) = parse_s3_uri(uri)?;
+                let object = match client.get_object().bucket(bucket).key(key).send().await {
+                    Ok(object) => object,
+                    Err(SdkError::ServiceError(err)) if err.err().is_no_such_key() => {
+                        return Ok(None);
+                    }
+                    Err(err) => return Err(anyhow!(err).context(format!("reading {uri}"))),
+                };
+                let body = object
+                    .body
+                    .collect()
+                    .await
+                    .with_context(|| format!("reading {uri}"))?;
+                Ok(Some(body.into_bytes()))
+            }
+            FileIo::Local => {
+                let path = parse_file_uri(uri)?;
+                match tokio::fs::read(&path).await {
+                    Ok(bytes) => Ok(Some(bytes.into())),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(anyhow!(e).context(format!("reading {uri}"))),
+                }
+            }
+        }
+    }
+
+    /// Writes `bytes` to `uri`, replacing any existing file.
+    pub async fn put(&self, uri: &str, bytes: Bytes) -> Result<(), anyhow::Error> {
+        match self {
+            FileIo::S3 { client } => {
+                let (bucket, key) = parse_s3_uri(uri)?;
+                client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .body(ByteStream::from(bytes))
+                    .send()
+                    .await
+                    .with_context(|| format!("writing {uri}"))?;
+                Ok(())
+            }
+            FileIo::Local => {
+                let path = parse_file_uri(uri)?;
+                create_parent_dir(&path).await?;
+                // Write to a temporary file and rename it into place so that
+                // readers never observe a partially written file.
+                let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+                tokio::fs::write(&tmp, &bytes)
+                    .await
+                    .with_context(|| format!("writing {uri}"))?;
+                tokio::fs::rename(&tmp, &path)
+                    .await
+                    .with_context(|| format!("writing {uri}"))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes `bytes` to `uri` only if no file exists at `uri`.
+    ///
+    /// This is the primitive that makes table commits atomic: of any number of
+    /// concurrent writers attempting to create the same metadata file, exactly
+    /// one succeeds.
+    pub async fn put_if_absent(
+        &self,
+        uri: &str,
+        bytes: Bytes,
+    ) -> Result<PutIfAbsent, anyhow::Error> {
+        match self {
+            FileIo::S3 { client } => {
+                let (bucket, key) = parse_s3_uri(uri)?;
+                let res = client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .if_none_match("*")
+                    .body(ByteStream::from(bytes))
+                    .send()
+                    .await;
+                match res {
+                    Ok(_) => Ok(PutIfAbsent::Written),
+                    // S3 reports a failed precondition with a 412 status, and
+                    // a concurrent conditional write with a 409.
+                    Err(SdkError::ServiceError(err))
+                        if matches!(err.raw().status().as_u16(), 409 | 412) =>
+                    {
+                        Ok(PutIfAbsent::AlreadyExists)
+                    }
+                    Err(err) => Err(anyhow!(err).context(format!("writing {uri}"))),
+                }
+            }
+            FileIo::Local => {
+                let path = parse_file_uri(uri)?;
+                create_parent_dir(&path).await?;
+                // Stage the contents next to the destination and then hard
+                // link them into place, which fails if the destination exists.
+                let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+                tokio::fs::write(&tmp, &bytes)
+                    .await
+                    .with_context(|| format!("writing {uri}"))?;
+                let res = tokio::fs::hard_link(&tmp, &path).await;
+                let _ = tokio::fs::remove_file(&tmp).await;
+                match res {
+                    Ok(()) => Ok(PutIfAbsent::Written),
+                    Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                        Ok(PutIfAbsent::AlreadyExists)
+                    }
+                    Err(e) => Err(anyhow!(e).context(format!("writing {uri}"))),
+                }
+            }
+        }
+    }
+}
+
+/// Splits an `s3://bucket/key` URI into its bucket and key.
+pub fn parse_s3_uri(uri: &str) -> Result<(&str, &str), anyhow::Error> {
+    let Some(rest) = uri.strip_prefix("s3://") else {
+        bail!("expected an s3:// URI, got {uri}");
+    };
+    match rest.split_once('/') {
+        Some((bucket, key)) if !bucket.is_empty() && !key.is_empty() => Ok((bucket, key)),
+        _ => bail!("invalid S3 URI {uri}"),
+    }
+}
+
+/// Converts a `file:///path` URI into a filesystem path.
+pub fn parse_file_uri(uri: &str) -> Result<PathBuf, anyhow::Error> {
+    match uri.strip_prefix("file://") {
+        Some(path) if path.starts_with('/') => Ok(PathBuf::from(path)),
+        _ => bail!("expected an absolute file:/// URI, got {uri}"),
+    }
+}
+
+async fn create_parent_dir(path: &std::path::Path) -> Result<(), anyhow::Error> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("creating directory {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn uris() {
+        assert_eq!(
+            parse_s3_uri("s3://bucket/a/b.json").unwrap(),
+            ("bucket", "a/b.json")
+        );
+        assert!(parse_s3_uri("s3://bucket").is_err());
+        assert!(parse_s3_uri("file:///tmp").is_err());
+        assert_eq!(
+            parse_file_uri("file:///tmp/a").unwrap(),
+            PathBuf::from("/tmp/a")
+        );
+        assert!(parse_file_uri("file://tmp/a").is_err());
+    }
+}
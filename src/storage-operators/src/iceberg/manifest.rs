@@ -0,0 +1,570 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Reading and writing Iceberg manifests and manifest lists.
+//!
+//! Both are Avro object container files whose schemas annotate every field
+//! with an Iceberg field ID and whose headers carry Iceberg-specific metadata.
+//! The container format is written by hand because the Avro writer does not
+//! support custom header metadata.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, anyhow, bail};
+use mz_avro::Schema;
+use mz_avro::types::Value;
+
+/// The status of an entry in a manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryStatus {
+    Existing,
+    Added,
+    Deleted,
+}
+
+impl EntryStatus {
+    fn id(self) -> i32 {
+        match self {
+            EntryStatus::Existing => 0,
+            EntryStatus::Added => 1,
+            EntryStatus::Deleted => 2,
+        }
+    }
+}
+
+/// The kind of file described by a manifest entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFileContent {
+    Data,
+    PositionDeletes,
+    EqualityDeletes,
+}
+
+impl DataFileContent {
+    fn id(self) -> i32 {
+        match self {
+            DataFileContent::Data => 0,
+            DataFileContent::PositionDeletes => 1,
+            DataFileContent::EqualityDeletes => 2,
+        }
+    }
+}
+
+/// The kind of files tracked by a manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ManifestContent {
+    Data,
+    Deletes,
+}
+
+impl ManifestContent {
+    fn id(self) -> i32 {
+        match self {
+            ManifestContent::Data => 0,
+            ManifestContent::Deletes => 1,
+        }
+    }
+}
+
+/// A data or delete file of the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataFile {
+    pub content: DataFileContent,
+    pub file_path: String,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+    /// The field IDs used to match rows for equality delete files.
+    pub equality_ids: Vec<i32>,
+}
+
+/// An entry of a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub status: EntryStatus,
+    pub snapshot_id: i64,
+    pub sequence_number: i64,
+    pub file_sequence_number: i64,
+    pub data_file: DataFile,
+}
+
+/// An entry of a manifest list, describing a single manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestFile {
+    pub manifest_path: String,
+    pub manifest_length: i64,
+    pub content: ManifestContent,
+    pub sequence_number: i64,
+    pub min_sequence_number: i64,
+    pub added_snapshot_id: i64,
+    pub added_files_count: i32,
+    pub existing_files_count: i32,
+    pub deleted_files_count: i32,
+    pub added_rows_count: i64,
+    pub existing_rows_count: i64,
+    pub deleted_rows_count: i64,
+}
+
+impl ManifestFile {
+    /// Returns the number of live files tracked by the manifest.
+    pub fn live_files_count(&self) -> i32 {
+        self.added_files_count + self.existing_files_count
+    }
+}
+
+const MANIFEST_ENTRY_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "manifest_entry",
+  "fields": [
+    {"name": "status", "type": "int", "field-id": 0},
+    {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+    {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+    {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+    {"name": "data_file", "field-id": 2, "type": {
+      "type": "record",
+      "name": "r2",
+      "fields": [
+        {"name": "content", "type": "int", "field-id": 134},
+        {"name": "file_path", "type": "string", "field-id": 100},
+        {"name": "file_format", "type": "string", "field-id": 101},
+        {"name": "partition", "field-id": 102, "type": {"type": "record", "name": "r102", "fields": []}},
+        {"name": "record_count", "type": "long", "field-id": 103},
+        {"name": "file_size_in_bytes", "type": "long", "field-id": 104},
+        {"name": "equality_ids", "default": null, "field-id": 135, "type": ["null", {"type": "array", "items": "int", "element-id": 136}]}
+      ]
+    }}
+  ]
+}"#;
+
+const MANIFEST_FILE_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "manifest_file",
+  "fields": [
+    {"name": "manifest_path", "type": "string", "field-id": 500},
+    {"name": "manifest_length", "type": "long", "field-id": 501},
+    {"name": "partition_spec_id", "type": "int", "field-id": 502},
+    {"name": "content", "type": "int", "field-id": 517},
+    {"name": "sequence_number", "type": "long", "field-id": 515},
+    {"name": "min_sequence_number", "type": "long", "field-id": 516},
+    {"name": "added_snapshot_id", "type": "long", "field-id": 503},
+    {"name": "added_files_count", "type": "int", "field-id": 504},
+    {"name": "existing_files_count", "type": "int", "field-id": 505},
+    {"name": "deleted_files_count", "type": "int", "field-id": 506},
+    {"name": "added_rows_count", "type": "long", "field-id": 512},
+    {"name": "existing_rows_count", "type": "long", "field-id": 513},
+    {"name": "deleted_rows_count", "type": "long", "field-id": 514}
+  ]
+}"#;
+
+/// Encodes the entries of a manifest of `content` files as an Avro object
+/// container file. `table_schema` is the JSON of the table's current schema.
+pub fn write_manifest(
+    table_schema: &str,
+    schema_id: i32,
+    content: ManifestContent,
+    entries: &[ManifestEntry],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let schema: Schema = MANIFEST_ENTRY_SCHEMA.parse()?;
+    let metadata = [
+        ("schema", table_schema.to_string()),
+        ("schema-id", schema_id.to_string()),
+        ("partition-spec", "[]".to_string()),
+        ("partition-spec-id", "0".to_string()),
+        ("format-version", "2".to_string()),
+        (
+            "content",
+            match content {
+                ManifestContent::Data => "data",
+                ManifestContent::Deletes => "deletes",
+            }
+            .to_string(),
+        ),
+    ];
+    let values = entries.iter().map(|entry| {
+        let file = &entry.data_file;
+        let equality_ids = if file.equality_ids.is_empty() {
+            optional(None)
+        } else {
+            optional(Some(Value::Array(
+                file.equality_ids.iter().map(|id| Value::Int(*id)).collect(),
+            )))
+        };
+        Value::Record(vec![
+            ("status".into(), Value::Int(entry.status.id())),
+            (
+                "snapshot_id".into(),
+                optional(Some(Value::Long(entry.snapshot_id))),
+            ),
+            (
+                "sequence_number".into(),
+                optional(Some(Value::Long(entry.sequence_number))),
+            ),
+            (
+                "file_sequence_number".into(),
+                optional(Some(Value::Long(entry.file_sequence_number))),
+            ),
+            (
+                "data_file".into(),
+                Value::Record(vec![
+                    ("content".into(), Value::Int(file.content.id())),
+                    ("file_path".into(), Value::String(file.file_path.clone())),
+                    ("file_format".into(), Value::String("PARQUET".into())),
+                    ("partition".into(), Value::Record(vec![])),
+                    ("record_count".into(), Value::Long(file.record_count)),
+                    (
+                        "file_size_in_bytes".into(),
+                        Value::Long(file.file_size_in_bytes),
+                    ),
+                    ("equality_ids".into(), equality_ids),
+                ]),
+            ),
+        ])
+    });
+    Ok(write_container(
+        MANIFEST_ENTRY_SCHEMA,
+        &schema,
+        &metadata,
+        values,
+    ))
+}
+
+/// Decodes the entries of a manifest written by [`write_manifest`].
+pub fn read_manifest(bytes: &[u8]) -> Result<Vec<ManifestEntry>, anyhow::Error> {
+    let reader = mz_avro::Reader::new(bytes)?;
+    let mut entries = vec![];
+    for value in reader {
+        let mut record = Record::new(value?)?;
+        let mut data_file = Record::new(record.take("data_file")?)?;
+        let equality_ids = match data_file.take_optional("equality_ids")? {
+            Some(Value::Array(ids)) => ids.into_iter().map(as_int).collect::<Result<_, _>>()?,
+            Some(other) => bail!("unexpected equality_ids {other:?}"),
+            None => vec![],
+        };
+        let content = match as_int(data_file.take("content")?)? {
+            0 => DataFileContent::Data,
+            1 => DataFileContent::PositionDeletes,
+            2 => DataFileContent::EqualityDeletes,
+            other => bail!("unknown data file content {other}"),
+        };
+        let status = match as_int(record.take("status")?)? {
+            0 => EntryStatus::Existing,
+            1 => EntryStatus::Added,
+            2 => EntryStatus::Deleted,
+            other => bail!("unknown manifest entry status {other}"),
+        };
+        let snapshot_id = record
+            .take_optional("snapshot_id")?
+            .map(as_long)
+            .transpose()?;
+        let sequence_number = record
+            .take_optional("sequence_number")?
+            .map(as_long)
+            .transpose()?;
+        let file_sequence_number = record
+            .take_optional("file_sequence_number")?
+            .map(as_long)
+            .transpose()?;
+        entries.push(ManifestEntry {
+            status,
+            // Entries written by the sink always carry explicit snapshot IDs
+            // and sequence numbers rather than inheriting them.
+            snapshot_id: snapshot_id.context("manifest entry without snapshot_id")?,
+            sequence_number: sequence_number.context("manifest entry without sequence_number")?,
+            file_sequence_number: file_sequence_number
+                .or(sequence_number)
+                .context("manifest entry without file_sequence_number")?,
+            data_file: DataFile {
+                content,
+                file_path: as_string(data_file.take("file_path")?)?,
+                record_count: as_long(data_file.take("record_count")?)?,
+                file_size_in_bytes: as_long(data_file.take("file_size_in_bytes")?)?,
+                equality_ids,
+            },
+        });
+    }
+    Ok(entries)
+}
+
+/// Encodes a manifest list as an Avro object container file.
+pub fn write_manifest_list(
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+    manifests: &[ManifestFile],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let schema: Schema = MANIFEST_FILE_SCHEMA.parse()?;
+    let mut metadata = vec![
+        ("snapshot-id", snapshot_id.to_string()),
+        ("sequence-number", sequence_number.to_string()),
+        ("format-version", "2".to_string()),
+    ];
+    if let Some(parent) = parent_snapshot_id {
+        metadata.push(("parent-snapshot-id", parent.to_string()));
+    }
+    let values = manifests.iter().map(|m| {
+        Value::Record(vec![
+            (
+                "manifest_path".into(),
+                Value::String(m.manifest_path.clone()),
+            ),
+            ("manifest_length".into(), Value::Long(m.manifest_length)),
+            ("partition_spec_id".into(), Value::Int(0)),
+            ("content".into(), Value::Int(m.content.id())),
+            ("sequence_number".into(), Value::Long(m.sequence_number)),
+            (
+                "min_sequence_number".into(),
+                Value::Long(m.min_sequence_number),
+            ),
+            ("added_snapshot_id".into(), Value::Long(m.added_snapshot_id)),
+            ("added_files_count".into(), Value::Int(m.added_files_count)),
+            (
+                "existing_files_count".into(),
+                Value::Int(m.existing_files_count),
+            ),
+            (
+                "deleted_files_count".into(),
+                Value::Int(m.deleted_files_count),
+            ),
+            ("added_rows_count".into(), Value::Long(m.added_rows_count)),
+            (
+                "existing_rows_count".into(),
+                Value::Long(m.existing_rows_count),
+            ),
+            (
+                "deleted_rows_count".into(),
+                Value::Long(m.deleted_rows_count),
+            ),
+        ])
+    });
+    Ok(write_container(
+        MANIFEST_FILE_SCHEMA,
+        &schema,
+        &metadata,
+        values,
+    ))
+}
+
+/// Decodes a manifest list written by [`write_manifest_list`].
+pub fn read_manifest_list(bytes: &[u8]) -> Result<Vec<ManifestFile>, anyhow::Error> {
+    let reader = mz_avro::Reader::new(bytes)?;
+    let mut manifests = vec![];
+    for value in reader {
+        let mut r = Record::new(value?)?;
+        let content = match as_int(r.take("content")?)? {
+            0 => ManifestContent::Data,
+            1 => ManifestContent::Deletes,
+            other => bail!("unknown manifest content {other}"),
+        };
+        manifests.push(ManifestFile {
+            manifest_path: as_string(r.take("manifest_path")?)?,
+            manifest_length: as_long(r.take("manifest_length")?)?,
+            content,
+            sequence_number: as_long(r.take("sequence_number")?)?,
+            min_sequence_number: as_long(r.take("min_sequence_number")?)?,
+            added_snapshot_id: as_long(r.take("added_snapshot_id")?)?,
+            added_files_count: as_int(r.take("added_files_count")?)?,
+            existing_files_count: as_int(r.take("existing_files_count")?)?,
+            deleted_files_count: as_int(r.take("deleted_files_count")?)?,
+            added_rows_count: as_long(r.take("added_rows_count")?)?,
+            existing_rows_count: as_long(r.take("existing_rows_count")?)?,
+            deleted_rows_count: as_long(r.take("deleted_rows_count")?)?,
+        });
+    }
+    Ok(manifests)
+}
+
+/// Writes `values` as an uncompressed Avro object container file in a single
+/// block.
+fn write_container(
+    schema_json: &str,
+    schema: &Schema,
+    metadata: &[(&str, String)],
+    values: impl Iterator<Item = Value>,
+) -> Vec<u8> {
+    let mut buf = b"Obj\x01".to_vec();
+
+    // The header metadata is an Avro `map<bytes>`, written as a single block.
+    let mut header: BTreeMap<&str, &[u8]> = BTreeMap::new();
+    header.insert("avro.schema", schema_json.as_bytes());
+    header.insert("avro.codec", b"null");
+    for (key, value) in metadata {
+        header.insert(key, value.as_bytes());
+    }
+    encode_long(i64::try_from(header.len()).expect("small map"), &mut buf);
+    for (key, value) in header {
+        encode_bytes(key.as_bytes(), &mut buf);
+        encode_bytes(value, &mut buf);
+    }
+    encode_long(0, &mut buf);
+
+    let sync_marker = *uuid::Uuid::new_v4().as_bytes();
+    buf.extend_from_slice(&sync_marker);
+
+    let mut block = vec![];
+    let mut count = 0i64;
+    for value in values {
+        mz_avro::encode::encode(&value, schema, &mut block);
+        count += 1;
+    }
+    if count > 0 {
+        encode_long(count, &mut buf);
+        encode_long(
+            i64::try_from(block.len()).expect("block fits in i64"),
+            &mut buf,
+        );
+        buf.extend_from_slice(&block);
+        buf.extend_from_slice(&sync_marker);
+    }
+    buf
+}
+
+/// Writes `n` as a zig-zag encoded variable-length integer.
+fn encode_long(n: i64, buf: &mut Vec<u8>) {
+    let mut z = u64::from_ne_bytes(((n << 1) ^ (n >> 63)).to_ne_bytes());
+    loop {
+        let byte = u8::try_from(z & 0x7f).expect("masked to 7 bits");
+        z >>= 7;
+        if z == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn encode_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    encode_long(i64::try_from(bytes.len()).expect("bytes fit in i64"), buf);
+    buf.extend_from_slice(bytes);
+}
+
+/// Returns the value for the `["null", T]` union with the given value.
+fn optional(value: Option<Value>) -> Value {
+    let (index, inner) = match value {
+        None => (0, Value::Null),
+        Some(value) => (1, value),
+    };
+    Value::Union {
+        index,
+        inner: Box::new(inner),
+        n_variants: 2,
+        null_variant: Some(0),
+    }
+}
+
+/// A decoded Avro record whose fields can be taken by name.
+struct Record(BTreeMap<String, Value>);
+
+impl Record {
+    fn new(value: Value) -> Result<Self, anyhow::Error> {
+        match value {
+            Value::Record(fields) => Ok(Record(fields.into_iter().collect())),
+            other => bail!("expected record, got {other:?}"),
+        }
+    }
+
+    fn take(&mut self, name: &str) -> Result<Value, anyhow::Error> {
+        self.0
+            .remove(name)
+            .ok_or_else(|| anyhow!("record missing field {name}"))
+    }
+
+    /// Takes an optional field, unwrapping its union.
+    fn take_optional(&mut self, name: &str) -> Result<Option<Value>, anyhow::Error> {
+        match self.0.remove(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::Union { inner, .. }) => match *inner {
+                Value::Null => Ok(None),
+                inner => Ok(Some(inner)),
+            },
+            Some(other) => Ok(Some(other)),
+        }
+    }
+}
+
+fn as_int(value: Value) -> Result<i32, anyhow::Error> {
+    match value {
+        Value::Int(i) => Ok(i),
+        other => bail!("expected int, got {other:?}"),
+    }
+}
+
+fn as_long(value: Value) -> Result<i64, anyhow::Error> {
+    match value {
+        Value::Long(i) => Ok(i),
+        Value::Int(i) => Ok(i.into()),
+        other => bail!("expected long, got {other:?}"),
+    }
+}
+
+fn as_string(value: Value) -> Result<String, anyhow::Error> {
+    match value {
+        Value::String(s) => Ok(s),
+        other => bail!("expected string, got {other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // slow
+    fn manifest_roundtrip() {
+        let entries = vec![
+            ManifestEntry {
+                status: EntryStatus::Added,
+                snapshot_id: 7,
+                sequence_number: 3,
+                file_sequence_number: 3,
+                data_file: DataFile {
+                    content: DataFileContent::Data,
+                    file_path: "s3://b/t/data/a.parquet".into(),
+                    record_count: 10,
+                    file_size_in_bytes: 1024,
+                    equality_ids: vec![],
+                },
+            },
+            ManifestEntry {
+                status: EntryStatus::Existing,
+                snapshot_id: 6,
+                sequence_number: 2,
+                file_sequence_number: 2,
+                data_file: DataFile {
+                    content: DataFileContent::EqualityDeletes,
+                    file_path: "s3://b/t/data/b.parquet".into(),
+                    record_count: 1,
+                    file_size_in_bytes: 512,
+                    equality_ids: vec![1, 2],
+                },
+            },
+        ];
+        let bytes = write_manifest("{}", 0, ManifestContent::Data, &entries).unwrap();
+        assert_eq!(read_manifest(&bytes).unwrap(), entries);
+
+        let manifests = vec![ManifestFile {
+            manifest_path: "s3://b/t/metadata/m.avro".into(),
+            manifest_length: bytes.len().try_into().unwrap(),
+            content: ManifestContent::Deletes,
+            sequence_number: 3,
+            min_sequence_number: 2,
+            added_snapshot_id: 7,
+            added_files_count: 1,
+            existing_files_count: 1,
+            deleted_files_count: 0,
+            added_rows_count: 10,
+            existing_rows_count: 1,
+            deleted_rows_count: 0,
+        }];
+        let bytes = write_manifest_list(7, Some(6), 3, &manifests).unwrap();
+        assert_eq!(read_manifest_list(&bytes).unwrap(), manifests);
+
+        // Empty files are still valid containers.
+        let bytes = write_manifest_list(1, None, 1, &[]).unwrap();
+        assert!(read_manifest_list(&bytes).unwrap().is_empty());
+    }
+}
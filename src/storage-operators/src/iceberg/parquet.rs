@@ -0,0 +1,138 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Writing the Parquet data and delete files of an Iceberg table.
+
+// We need to allow the std::collections::HashMap type since it is directly used as a type
+// parameter to the arrow Field::with_metadata method.
+#![allow(clippy::disallowed_types)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::datatypes::{Field, Schema};
+use arrow::record_batch::RecordBatch;
+use mz_arrow_util::builder::ArrowBuilder;
+use mz_ore::cast::CastFrom;
+use mz_repr::{RelationDesc, Row};
+use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+use parquet::arrow::arrow_writer::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+
+const DEFAULT_ARRAY_BUILDER_ITEM_CAPACITY: usize = 1024;
+const DEFAULT_ARRAY_BUILDER_DATA_CAPACITY: usize = 1024;
+
+/// The approximate number of bytes buffered in Arrow before a row group is
+/// flushed to the Parquet writer.
+const ROW_GROUP_SIZE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Buffers rows into an in-memory Parquet file whose columns carry Iceberg
+/// field IDs.
+pub struct ParquetFileWriter {
+    desc: RelationDesc,
+    schema: Arc<Schema>,
+    builder: ArrowBuilder,
+    writer: ArrowWriter<Vec<u8>>,
+    record_count: i64,
+    /// An estimate of the number of bytes in the file so far.
+    size_estimate: usize,
+}
+
+impl ParquetFileWriter {
+    /// Creates a writer for rows of `desc`, whose columns have the given
+    /// Iceberg field IDs.
+    pub fn new(desc: RelationDesc, field_ids: &[i32]) -> Result<Self, anyhow::Error> {
+        assert_eq!(desc.arity(), field_ids.len(), "one field ID per column");
+        let builder = ArrowBuilder::new(
+            &desc,
+            DEFAULT_ARRAY_BUILDER_ITEM_CAPACITY,
+            DEFAULT_ARRAY_BUILDER_DATA_CAPACITY,
+        )?;
+        let fields: Vec<Field> = builder
+            .schema()
+            .fields()
+            .iter()
+            .zip(field_ids)
+            .map(|(field, id)| {
+                let mut metadata = field.metadata().clone();
+                metadata.insert(PARQUET_FIELD_ID_META_KEY.to_string(), id.to_string());
+                Field::clone(field).with_metadata(metadata)
+            })
+            .collect();
+        let schema = Arc::new(Schema::new_with_metadata(fields, HashMap::new()));
+        let props = WriterProperties::builder()
+            // Row groups are flushed based on their size in bytes.
+            .set_max_row_group_size(usize::MAX)
+            .set_compression(Compression::SNAPPY)
+            .set_statistics_enabled(EnabledStatistics::Chunk)
+            .build();
+        let writer = ArrowWriter::try_new(Vec::new(), Arc::clone(&schema), Some(props))?;
+        Ok(ParquetFileWriter {
+            desc,
+            schema,
+            builder,
+            writer,
+            record_count: 0,
+            size_estimate: 0,
+        })
+    }
+
+    /// Appends a row to the file.
+    pub fn add_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        self.builder.add_row(row)?;
+        self.record_count += 1;
+        if self.builder.row_size_bytes() >= ROW_GROUP_SIZE_BYTES {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of rows in the file.
+    pub fn record_count(&self) -> i64 {
+        self.record_count
+    }
+
+    /// Returns an estimate of the size of the file in bytes.
+    pub fn size_estimate(&self) -> usize {
+        self.size_estimate + self.builder.row_size_bytes()
+    }
+
+    /// Finishes the file, returning its contents.
+    pub fn finish(mut self) -> Result<Vec<u8>, anyhow::Error> {
+        self.flush_row_group()?;
+        Ok(self.writer.into_inner()?)
+    }
+
+    fn flush_row_group(&mut self) -> Result<(), anyhow::Error> {
+        if self.builder.row_size_bytes() == 0 {
+            return Ok(());
+        }
+        let builder = std::mem::replace(
+            &mut self.builder,
+            ArrowBuilder::new(
+                &self.desc,
+                DEFAULT_ARRAY_BUILDER_ITEM_CAPACITY,
+                DEFAULT_ARRAY_BUILDER_DATA_CAPACITY,
+            )?,
+        );
+        self.size_estimate += builder.row_size_bytes();
+        let batch = builder.to_record_batch()?;
+        // Swap in the schema that carries the field IDs.
+        let batch = RecordBatch::try_new(Arc::clone(&self.schema), batch.columns().to_vec())?;
+        self.writer.write(&batch)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Returns the size of `bytes` as an `i64`, as recorded in manifests.
+pub fn file_size(bytes: &[u8]) -> i64 {
+    i64::try_from(u64::cast_from(bytes.len())).expect("file size fits in i64")
+}
@@ -98,15 +98,21 @@ use timely::dataflow::{Scope, Stream as TimelyStream};
 use timely::progress::Antichain;
 use tracing::info;
 
+use crate::oneshot_source::avro::{AvroFormat, AvroRecords, AvroWorkRequest};
 use crate::oneshot_source::aws_source::{AwsS3Source, S3Checksum, S3Object};
 use crate::oneshot_source::csv::{CsvDecoder, CsvRecord, CsvWorkRequest};
+use crate::oneshot_source::file_source::{LocalFileChecksum, LocalFileObject, LocalFileSource};
 use crate::oneshot_source::http_source::{HttpChecksum, HttpObject, HttpOneshotSource};
+use crate::oneshot_source::json::{JsonFormat, JsonRecords, JsonWorkRequest};
 use crate::oneshot_source::parquet::{ParquetFormat, ParquetRowGroup, ParquetWorkRequest};
 
+pub mod avro;
 pub mod csv;
+pub mod json;
 pub mod parquet;
 
 pub mod aws_source;
+pub mod file_source;
 pub mod http_source;
 
 mod util;
@@ -161,6 +167,10 @@ where
             let source = AwsS3Source::new(connection, connection_id, connection_context, uri);
             SourceKind::AwsS3(source)
         }
+        ContentSource::LocalFile { path } => {
+            let source = LocalFileSource::new(path);
+            SourceKind::LocalFile(source)
+        }
     };
     tracing::info!(?source, "created oneshot source");
 
@@ -173,6 +183,14 @@ where
             let format = ParquetFormat::new(shape.source_desc);
             FormatKind::Parquet(format)
         }
+        ContentFormat::Json => {
+            let format = JsonFormat::new(&shape.source_desc);
+            FormatKind::Json(format)
+        }
+        ContentFormat::Avro => {
+            let format = AvroFormat::new(&shape.source_desc);
+            FormatKind::Avro(format)
+        }
     };
 
     // Discover what objects are available to copy.
@@ -676,7 +694,7 @@ pub trait OneshotObject {
 }
 
 /// Encoding of a [`OneshotObject`].
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Encoding {
     Bzip2,
     Gzip,
@@ -684,6 +702,39 @@ pub enum Encoding {
     Zstd,
 }
 
+impl Encoding {
+    /// Returns the [`Encoding`] implied by the extension of a file name, if any.
+    pub fn from_file_name(name: &str) -> Option<Encoding> {
+        let (_name, extension) = name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "bz2" => Some(Encoding::Bzip2),
+            "gz" | "gzip" => Some(Encoding::Gzip),
+            "xz" => Some(Encoding::Xz),
+            "zst" | "zstd" => Some(Encoding::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Parses the value of a [`Content-Encoding`] header, returning encodings in the order they
+    /// were applied.
+    ///
+    /// [`Content-Encoding`]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Encoding
+    pub fn from_content_encoding(value: &str) -> Result<Vec<Encoding>, StorageErrorXKind> {
+        value
+            .split(',')
+            .map(|encoding| encoding.trim().to_ascii_lowercase())
+            .filter(|encoding| !encoding.is_empty() && encoding != "identity")
+            .map(|encoding| match encoding.as_str() {
+                "bzip2" => Ok(Encoding::Bzip2),
+                "gzip" | "x-gzip" => Ok(Encoding::Gzip),
+                "xz" => Ok(Encoding::Xz),
+                "zstd" => Ok(Encoding::Zstd),
+                other => Err(StorageErrorXKind::UnsupportedEncoding(other.into())),
+            })
+            .collect()
+    }
+}
+
 /// Defines a remote system that we can fetch data from for a "one time" ingestion.
 pub trait OneshotSource: Clone + Send + Unpin {
     /// An individual unit within the source, e.g. a file.
@@ -722,6 +773,7 @@ pub trait OneshotSource: Clone + Send + Unpin {
 pub(crate) enum SourceKind {
     Http(HttpOneshotSource),
     AwsS3(AwsS3Source),
+    LocalFile(LocalFileSource),
 }
 
 impl OneshotSource for SourceKind {
@@ -750,6 +802,19 @@ impl OneshotSource for SourceKind {
                     .collect();
                 Ok(objects)
             }
+            SourceKind::LocalFile(file) => {
+                let objects = file.list().await.context("local_file")?;
+                let objects = objects
+                    .into_iter()
+                    .map(|(object, checksum)| {
+                        (
+                            ObjectKind::LocalFile(object),
+                            ChecksumKind::LocalFile(checksum),
+                        )
+                    })
+                    .collect();
+                Ok(objects)
+            }
         }
     }
 
@@ -769,7 +834,15 @@ impl OneshotSource for SourceKind {
                 .get(object, checksum, range)
                 .map(|result| result.context("aws_s3"))
                 .boxed(),
-            (SourceKind::AwsS3(_) | SourceKind::Http(_), _, _) => {
+            (
+                SourceKind::LocalFile(file),
+                ObjectKind::LocalFile(object),
+                ChecksumKind::LocalFile(checksum),
+            ) => file
+                .get(object, checksum, range)
+                .map(|result| result.context("local_file"))
+                .boxed(),
+            (SourceKind::AwsS3(_) | SourceKind::Http(_) | SourceKind::LocalFile(_), _, _) => {
                 unreachable!("programming error! wrong source, object, and checksum kind");
             }
         }
@@ -781,6 +854,7 @@ impl OneshotSource for SourceKind {
 pub(crate) enum ObjectKind {
     Http(HttpObject),
     AwsS3(S3Object),
+    LocalFile(LocalFileObject),
 }
 
 impl OneshotObject for ObjectKind {
@@ -788,6 +862,7 @@ impl OneshotObject for ObjectKind {
         match self {
             ObjectKind::Http(object) => object.name(),
             ObjectKind::AwsS3(object) => object.name(),
+            ObjectKind::LocalFile(object) => object.name(),
         }
     }
 
@@ -795,6 +870,7 @@ impl OneshotObject for ObjectKind {
         match self {
             ObjectKind::Http(object) => object.path(),
             ObjectKind::AwsS3(object) => object.path(),
+            ObjectKind::LocalFile(object) => object.path(),
         }
     }
 
@@ -802,6 +878,7 @@ impl OneshotObject for ObjectKind {
         match self {
             ObjectKind::Http(object) => object.size(),
             ObjectKind::AwsS3(object) => object.size(),
+            ObjectKind::LocalFile(object) => object.size(),
         }
     }
 
@@ -809,6 +886,7 @@ impl OneshotObject for ObjectKind {
         match self {
             ObjectKind::Http(object) => object.encodings(),
             ObjectKind::AwsS3(object) => object.encodings(),
+            ObjectKind::LocalFile(object) => object.encodings(),
         }
     }
 }
@@ -818,6 +896,7 @@ impl OneshotObject for ObjectKind {
 pub(crate) enum ChecksumKind {
     Http(HttpChecksum),
     AwsS3(S3Checksum),
+    LocalFile(LocalFileChecksum),
 }
 
 /// Defines a format that we fetch for a "one time" ingestion.
@@ -866,6 +945,8 @@ pub trait OneshotFormat: Clone {
 pub(crate) enum FormatKind {
     Csv(CsvDecoder),
     Parquet(ParquetFormat),
+    Json(JsonFormat),
+    Avro(AvroFormat),
 }
 
 impl OneshotFormat for FormatKind {
@@ -902,6 +983,26 @@ impl OneshotFormat for FormatKind {
                     .collect();
                 Ok(work)
             }
+            FormatKind::Json(json) => {
+                let work = json
+                    .split_work(source, object, checksum)
+                    .await
+                    .context("json")?
+                    .into_iter()
+                    .map(RequestKind::Json)
+                    .collect();
+                Ok(work)
+            }
+            FormatKind::Avro(avro) => {
+                let work = avro
+                    .split_work(source, object, checksum)
+                    .await
+                    .context("avro")?
+                    .into_iter()
+                    .map(RequestKind::Avro)
+                    .collect();
+                Ok(work)
+            }
         }
     }
 
@@ -921,8 +1022,23 @@ impl OneshotFormat for FormatKind {
                 .map_ok(RecordChunkKind::Parquet)
                 .map(|result| result.context("parquet"))
                 .boxed(),
-            (FormatKind::Parquet(_), RequestKind::Csv(_))
-            | (FormatKind::Csv(_), RequestKind::Parquet(_)) => {
+            (FormatKind::Json(json), RequestKind::Json(request)) => json
+                .fetch_work(source, request)
+                .map_ok(RecordChunkKind::Json)
+                .map(|result| result.context("json"))
+                .boxed(),
+            (FormatKind::Avro(avro), RequestKind::Avro(request)) => avro
+                .fetch_work(source, request)
+                .map_ok(RecordChunkKind::Avro)
+                .map(|result| result.context("avro"))
+                .boxed(),
+            (
+                FormatKind::Csv(_)
+                | FormatKind::Parquet(_)
+                | FormatKind::Json(_)
+                | FormatKind::Avro(_),
+                _,
+            ) => {
                 unreachable!("programming error, {self:?}")
             }
         }
@@ -940,8 +1056,19 @@ impl OneshotFormat for FormatKind {
             (FormatKind::Parquet(parquet), RecordChunkKind::Parquet(chunk)) => {
                 parquet.decode_chunk(chunk, rows).context("parquet")
             }
-            (FormatKind::Parquet(_), RecordChunkKind::Csv(_))
-            | (FormatKind::Csv(_), RecordChunkKind::Parquet(_)) => {
+            (FormatKind::Json(json), RecordChunkKind::Json(chunk)) => {
+                json.decode_chunk(chunk, rows).context("json")
+            }
+            (FormatKind::Avro(avro), RecordChunkKind::Avro(chunk)) => {
+                avro.decode_chunk(chunk, rows).context("avro")
+            }
+            (
+                FormatKind::Csv(_)
+                | FormatKind::Parquet(_)
+                | FormatKind::Json(_)
+                | FormatKind::Avro(_),
+                _,
+            ) => {
                 unreachable!("programming error, {self:?}")
             }
         }
@@ -952,12 +1079,16 @@ impl OneshotFormat for FormatKind {
 pub(crate) enum RequestKind<O, C> {
     Csv(CsvWorkRequest<O, C>),
     Parquet(ParquetWorkRequest<O, C>),
+    Json(JsonWorkRequest<O, C>),
+    Avro(AvroWorkRequest<O, C>),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum RecordChunkKind {
     Csv(CsvRecord),
     Parquet(ParquetRowGroup),
+    Json(JsonRecords),
+    Avro(AvroRecords),
 }
 
 pub(crate) enum ObjectFilter {
@@ -1018,6 +1149,10 @@ pub enum StorageErrorXKind {
     CsvDecoding(Arc<str>),
     #[error("parquet error: {0}")]
     ParquetError(Arc<str>),
+    #[error("json decoding error: {0}")]
+    JsonDecoding(Arc<str>),
+    #[error("avro decoding error: {0}")]
+    AvroDecoding(Arc<str>),
     #[error("reqwest error: {0}")]
     Reqwest(Arc<str>),
     #[error("aws s3 request error: {0}")]
@@ -1032,6 +1167,8 @@ pub enum StorageErrorXKind {
     ProgrammingError(Arc<str>),
     #[error("failed to get the size of an object")]
     MissingSize,
+    #[error("unsupported encoding: {0}")]
+    UnsupportedEncoding(Arc<str>),
     #[error("object is missing the required '{0}' field")]
     MissingField(Arc<str>),
     #[error("failed while evaluating the provided mfp: '{0}'")]
@@ -0,0 +1,611 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Avro Object Container File [`OneshotFormat`].
+//!
+//! An Object Container File (OCF) is a header, containing the writer schema
+//! and a random 16 byte sync marker, followed by blocks of records. Every
+//! block is terminated by the sync marker, which allows us to split a single
+//! file into byte ranges and find the first block within each range by
+//! scanning for the marker.
+//!
+//! See <https://avro.apache.org/docs/1.11.1/specification/#object-container-files>.
+
+use std::collections::BTreeMap;
+use std::io::{self, Cursor};
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use mz_avro::types::{DecimalValue, Value};
+use mz_avro::{Codec, Schema};
+use mz_repr::adt::date::Date;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::adt::numeric;
+use mz_repr::{ColumnName, Datum, RelationDesc, Row, RowArena, RowPacker};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::io::StreamReader;
+
+use crate::oneshot_source::{
+    OneshotFormat, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext,
+    StorageErrorXKind,
+};
+
+/// Objects larger than this are split into multiple byte ranges that get
+/// fetched and decoded in parallel.
+const SPLIT_SIZE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Number of bytes we initially fetch when reading the header of an object.
+const INITIAL_HEADER_FETCH_BYTES: usize = 64 * 1024;
+
+/// Length of the sync marker that terminates the header and every block.
+const SYNC_MARKER_LEN: usize = 16;
+
+#[derive(Debug, Clone)]
+pub struct AvroFormat {
+    /// Names and types of the columns in the table we're copying into.
+    columns: Arc<[(ColumnName, mz_pgrepr::Type)]>,
+}
+
+impl AvroFormat {
+    pub fn new(desc: &RelationDesc) -> Self {
+        let columns = desc
+            .iter()
+            .map(|(name, typ)| (name.clone(), mz_pgrepr::Type::from(&typ.scalar_type)))
+            .collect();
+        AvroFormat { columns }
+    }
+}
+
+/// Instructions on how to parse a range of an Avro OCF.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AvroWorkRequest<O, C> {
+    object: O,
+    checksum: C,
+    /// Blocks whose first byte falls within this range belong to this request.
+    range: Range<usize>,
+}
+
+/// Records decoded from a single Avro block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AvroRecords {
+    rows: Vec<Row>,
+}
+
+/// The parsed header of an Avro OCF.
+#[derive(Debug, Clone)]
+struct AvroHeader {
+    schema: Schema,
+    marker: [u8; SYNC_MARKER_LEN],
+    codec: Codec,
+    /// Length of the header in bytes, i.e. the offset of the first block.
+    len: usize,
+}
+
+impl AvroHeader {
+    fn parse(buf: &[u8]) -> Result<Self, mz_avro::error::Error> {
+        let reader = mz_avro::Reader::new(Cursor::new(buf))?;
+        let schema = reader.writer_schema().clone();
+        let marker = *reader.sync_marker();
+        let codec = reader.codec();
+        let len = usize::try_from(reader.into_inner().position()).expect("known to fit");
+
+        Ok(AvroHeader {
+            schema,
+            marker,
+            codec,
+            len,
+        })
+    }
+
+    /// Fetches and parses the header of `object`.
+    async fn fetch<S: OneshotSource>(
+        source: &S,
+        object: &S::Object,
+        checksum: &S::Checksum,
+    ) -> Result<Self, StorageErrorX> {
+        let size = object.size();
+        let mut prefix_len = std::cmp::min(INITIAL_HEADER_FETCH_BYTES, size);
+
+        // The header contains the schema which is unbounded in size, so keep
+        // fetching larger prefixes of the object until we can parse it.
+        loop {
+            let inclusive_end = prefix_len.saturating_sub(1);
+            let bytes: Vec<Bytes> = source
+                .get(object.clone(), checksum.clone(), Some(0..=inclusive_end))
+                .try_collect()
+                .await
+                .context("fetch header")?;
+            let prefix = bytes.concat();
+
+            match AvroHeader::parse(&prefix) {
+                Ok(header) => return Ok(header),
+                Err(_) if prefix_len < size => {
+                    prefix_len = std::cmp::min(prefix_len.saturating_mul(2), size);
+                }
+                Err(err) => {
+                    let err = StorageErrorXKind::AvroDecoding(err.to_string().into());
+                    return Err(err.with_context("parse header"));
+                }
+            }
+        }
+    }
+}
+
+impl OneshotFormat for AvroFormat {
+    type WorkRequest<S>
+        = AvroWorkRequest<S::Object, S::Checksum>
+    where
+        S: OneshotSource;
+    type RecordChunk = AvroRecords;
+
+    async fn split_work<S: OneshotSource + Send>(
+        &self,
+        source: S,
+        object: S::Object,
+        checksum: S::Checksum,
+    ) -> Result<Vec<Self::WorkRequest<S>>, StorageErrorX> {
+        // Avro does its own compression internally, and requires random access.
+        if !object.encodings().is_empty() {
+            let encodings = format!("{:?}", object.encodings());
+            return Err(StorageErrorXKind::UnsupportedEncoding(encodings.into()))
+                .context(object.name());
+        }
+
+        let size = object.size();
+        if size == 0 {
+            return Ok(Vec::new());
+        }
+        let header = AvroHeader::fetch(&source, &object, &checksum).await?;
+
+        let work: Vec<_> = (header.len..size)
+            .step_by(SPLIT_SIZE_BYTES)
+            .map(|start| AvroWorkRequest {
+                object: object.clone(),
+                checksum: checksum.clone(),
+                range: start..std::cmp::min(start.saturating_add(SPLIT_SIZE_BYTES), size),
+            })
+            .collect();
+
+        tracing::info!(
+            object = object.name(),
+            size,
+            requests = work.len(),
+            "splitting Avro object"
+        );
+
+        Ok(work)
+    }
+
+    fn fetch_work<'a, S: OneshotSource + Sync + 'static>(
+        &'a self,
+        source: &'a S,
+        request: Self::WorkRequest<S>,
+    ) -> BoxStream<'a, Result<Self::RecordChunk, StorageErrorX>> {
+        let AvroWorkRequest {
+            object,
+            checksum,
+            range,
+        } = request;
+
+        let initial_work = async move {
+            let header = AvroHeader::fetch(source, &object, &checksum).await?;
+
+            // Every block is preceded by a sync marker, the first one by the
+            // marker at the end of the header, so start fetching early enough
+            // to observe the marker for a block that begins at `range.start`.
+            let fetch_start = range
+                .start
+                .checked_sub(SYNC_MARKER_LEN)
+                .filter(|start| *start >= header.len.saturating_sub(SYNC_MARKER_LEN))
+                .ok_or_else(|| StorageErrorXKind::programming_error("range overlaps header"))?;
+            let fetch_end = object.size().saturating_sub(1);
+
+            let raw_byte_stream = source
+                .get(object, checksum, Some(fetch_start..=fetch_end))
+                .map_err(|e| io::Error::new(io::ErrorKind::Interrupted, format!("{e:?}")));
+            let reader = StreamReader::new(raw_byte_stream);
+
+            Ok::<_, StorageErrorX>(self.decode_blocks(header, reader, range))
+        };
+
+        futures::stream::once(initial_work).try_flatten().boxed()
+    }
+
+    fn decode_chunk(
+        &self,
+        chunk: Self::RecordChunk,
+        rows: &mut Vec<Row>,
+    ) -> Result<usize, StorageErrorX> {
+        // Decoding Avro requires the writer schema from the object's header,
+        // so records get decoded into Rows as we fetch each block.
+        let AvroRecords { rows: decoded } = chunk;
+        let count = decoded.len();
+        rows.extend(decoded);
+        Ok(count)
+    }
+}
+
+impl AvroFormat {
+    /// Returns a stream of the records in every block that starts within
+    /// `range`. The `reader` must be positioned [`SYNC_MARKER_LEN`] bytes
+    /// before `range.start`.
+    fn decode_blocks<'a, R>(
+        &'a self,
+        header: AvroHeader,
+        mut reader: R,
+        range: Range<usize>,
+    ) -> impl Stream<Item = Result<AvroRecords, StorageErrorX>> + Send + 'a
+    where
+        R: AsyncRead + Unpin + Send + 'a,
+    {
+        async_stream::try_stream! {
+            let io_err = |context: &'static str| {
+                move |err: io::Error| StorageErrorXKind::generic(err).with_context(context)
+            };
+
+            // Scan forward to the first sync marker that ends within our range,
+            // the block that follows it is the first one we own.
+            let mut window = [0u8; SYNC_MARKER_LEN];
+            reader
+                .read_exact(&mut window)
+                .await
+                .map_err(io_err("read sync marker"))?;
+            let mut position = range.start;
+            loop {
+                if position >= range.end {
+                    return;
+                }
+                if window == header.marker {
+                    break;
+                }
+                let byte = match reader.read_u8().await {
+                    Ok(byte) => byte,
+                    Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return,
+                    Err(err) => Err(io_err("scan for sync marker")(err))?,
+                };
+                window.rotate_left(1);
+                window[SYNC_MARKER_LEN - 1] = byte;
+                position += 1;
+            }
+
+            while position < range.end {
+                let block_offset = position;
+                let block_err = |err: &dyn std::fmt::Display| {
+                    let msg = format!("block at byte offset {block_offset}: {err}");
+                    StorageErrorX::from(StorageErrorXKind::AvroDecoding(msg.into()))
+                };
+
+                let Some(count) = read_long(&mut reader, &mut position)
+                    .await
+                    .map_err(io_err("read block count"))?
+                else {
+                    break;
+                };
+                let len = read_long(&mut reader, &mut position)
+                    .await
+                    .map_err(io_err("read block length"))?
+                    .ok_or_else(|| block_err(&"unexpected end of file"))?;
+                let count = usize::try_from(count).map_err(|err| block_err(&err))?;
+                let len = usize::try_from(len).map_err(|err| block_err(&err))?;
+
+                let mut block = vec![0u8; len];
+                reader
+                    .read_exact(&mut block)
+                    .await
+                    .map_err(io_err("read block"))?;
+                let mut marker = [0u8; SYNC_MARKER_LEN];
+                reader
+                    .read_exact(&mut marker)
+                    .await
+                    .map_err(io_err("read sync marker"))?;
+                position += len + SYNC_MARKER_LEN;
+                let marker_check = if marker == header.marker {
+                    Ok(())
+                } else {
+                    Err(block_err(&"sync marker does not match header"))
+                };
+                marker_check?;
+
+                header
+                    .codec
+                    .decompress(&mut block)
+                    .map_err(|err| block_err(&err))?;
+
+                let mut rows = Vec::new();
+                let mut remaining = &block[..];
+                for idx in 0..count {
+                    let row = mz_avro::from_avro_datum(&header.schema, &mut remaining)
+                        .map_err(|err| err.to_string())
+                        .and_then(|value| self.decode_record(value))
+                        .map_err(|err| block_err(&format!("record {idx}: {err}")))?;
+                    rows.push(row);
+                }
+
+                yield AvroRecords { rows };
+            }
+        }
+    }
+
+    /// Decodes a single Avro record into a [`Row`], mapping fields to columns by name.
+    ///
+    /// Fields that are missing or `null` become SQL `NULL`, fields that don't
+    /// match any column are ignored.
+    fn decode_record(&self, value: Value) -> Result<Row, String> {
+        let Value::Record(fields) = unwrap_union(value) else {
+            return Err("expected an Avro record".to_string());
+        };
+        let mut fields: BTreeMap<_, _> = fields.into_iter().collect();
+
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        let arena = RowArena::new();
+
+        for (name, typ) in self.columns.iter() {
+            match fields.remove(name.as_str()) {
+                None => packer.push(Datum::Null),
+                Some(value) => pack_value(&mut packer, &arena, typ, value)
+                    .map_err(|err| format!("column \"{name}\": {err}"))?,
+            }
+        }
+
+        Ok(row)
+    }
+}
+
+/// Packs an Avro value into a column of type `typ`.
+///
+/// Values that don't map directly onto the column type are converted to their
+/// text representation and parsed, the same way `COPY ... (FORMAT CSV)` does.
+fn pack_value(
+    packer: &mut RowPacker,
+    arena: &RowArena,
+    typ: &mz_pgrepr::Type,
+    value: Value,
+) -> Result<(), String> {
+    let text = match (typ, unwrap_union(value)) {
+        (_, Value::Null) => {
+            packer.push(Datum::Null);
+            return Ok(());
+        }
+        (mz_pgrepr::Type::Jsonb, value) => {
+            let json = avro_to_json(value)?;
+            return JsonbPacker::new(packer)
+                .pack_serde_json(json)
+                .map_err(|err| err.to_string());
+        }
+        (mz_pgrepr::Type::Bytea, Value::Bytes(bytes) | Value::Fixed(_, bytes)) => {
+            packer.push(Datum::Bytes(&bytes));
+            return Ok(());
+        }
+        (mz_pgrepr::Type::Date, Value::Date(days)) => {
+            let date = Date::from_unix_epoch(days).map_err(|err| err.to_string())?;
+            packer.push(Datum::Date(date));
+            return Ok(());
+        }
+        (_, Value::Boolean(b)) => b.to_string(),
+        (_, Value::Int(i)) => i.to_string(),
+        (_, Value::Long(i)) => i.to_string(),
+        (_, Value::Float(f)) => f.to_string(),
+        (_, Value::Double(f)) => f.to_string(),
+        (_, Value::Date(days)) => Date::from_unix_epoch(days)
+            .map_err(|err| err.to_string())?
+            .to_string(),
+        (_, Value::Timestamp(ts)) => ts.to_string(),
+        (_, Value::Decimal(decimal)) => decimal_to_string(decimal)?,
+        (_, Value::String(s) | Value::Enum(_, s)) => s,
+        (_, Value::Bytes(bytes) | Value::Fixed(_, bytes)) => {
+            String::from_utf8(bytes).map_err(|err| err.to_string())?
+        }
+        (_, Value::Uuid(uuid)) => uuid.to_string(),
+        (_, Value::Json(json)) => json.to_string(),
+        (_, value @ (Value::Array(_) | Value::Map(_) | Value::Record(_))) => {
+            avro_to_json(value)?.to_string()
+        }
+        (_, Value::Union { .. }) => unreachable!("unions are unwrapped"),
+    };
+
+    let value = mz_pgrepr::Value::decode_text(typ, text.as_bytes()).map_err(|e| e.to_string())?;
+    packer.push(value.into_datum(arena, typ));
+    Ok(())
+}
+
+/// Converts an Avro value into JSON, for packing into `jsonb` columns.
+fn avro_to_json(value: Value) -> Result<serde_json::Value, String> {
+    let json = match unwrap_union(value) {
+        Value::Null => serde_json::Value::Null,
+        Value::Boolean(b) => serde_json::Value::Bool(b),
+        Value::Int(i) => serde_json::Value::from(i),
+        Value::Long(i) => serde_json::Value::from(i),
+        Value::Float(f) => serde_json::Value::from(f),
+        Value::Double(f) => serde_json::Value::from(f),
+        Value::Date(days) => {
+            let date = Date::from_unix_epoch(days).map_err(|err| err.to_string())?;
+            serde_json::Value::String(date.to_string())
+        }
+        Value::Timestamp(ts) => serde_json::Value::String(ts.to_string()),
+        Value::Decimal(decimal) => serde_json::Value::String(decimal_to_string(decimal)?),
+        Value::Bytes(bytes) | Value::Fixed(_, bytes) => {
+            let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+            serde_json::Value::String(format!("\\x{hex}"))
+        }
+        Value::String(s) | Value::Enum(_, s) => serde_json::Value::String(s),
+        Value::Uuid(uuid) => serde_json::Value::String(uuid.to_string()),
+        Value::Json(json) => json,
+        Value::Array(values) => serde_json::Value::Array(
+            values
+                .into_iter()
+                .map(avro_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(entries) => serde_json::Value::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| avro_to_json(v).map(|v| (k, v)))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Record(fields) => serde_json::Value::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| avro_to_json(v).map(|v| (k, v)))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Union { .. } => unreachable!("unions are unwrapped"),
+    };
+    Ok(json)
+}
+
+fn decimal_to_string(decimal: DecimalValue) -> Result<String, String> {
+    let DecimalValue {
+        mut unscaled,
+        scale,
+        ..
+    } = decimal;
+    let scale = u8::try_from(scale).map_err(|_| format!("decimal scale {scale} is too large"))?;
+    let value = numeric::twos_complement_be_to_numeric(&mut unscaled, scale)
+        .map_err(|err| err.to_string())?;
+    Ok(value.to_string())
+}
+
+fn unwrap_union(value: Value) -> Value {
+    match value {
+        Value::Union { inner, .. } => *inner,
+        value => value,
+    }
+}
+
+/// Reads a zig-zag encoded `long`, advancing `position` by the number of bytes
+/// read. Returns `None` if the reader is at EOF.
+async fn read_long<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    position: &mut usize,
+) -> Result<Option<i64>, io::Error> {
+    let mut value: u64 = 0;
+    for i in 0..10 {
+        let byte = match reader.read_u8().await {
+            Ok(byte) => byte,
+            Err(err) if i == 0 && err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        *position += 1;
+        value |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            let magnitude = i64::try_from(value >> 1).expect("known to fit");
+            let sign = i64::try_from(value & 1).expect("known to fit");
+            return Ok(Some(magnitude ^ -sign));
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "invalid zig-zag long",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_avro::Writer;
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "test",
+        "fields": [
+            {"name": "a", "type": "long"},
+            {"name": "b", "type": ["null", "string"]},
+            {"name": "c", "type": {"type": "array", "items": "int"}}
+        ]
+    }"#;
+
+    fn format() -> AvroFormat {
+        let desc = RelationDesc::builder()
+            .with_column("c", ScalarType::Jsonb.nullable(true))
+            .with_column("a", ScalarType::Int32.nullable(true))
+            .with_column("b", ScalarType::String.nullable(true))
+            .finish();
+        AvroFormat::new(&desc)
+    }
+
+    fn record(a: i64, b: Option<&str>) -> Value {
+        let b = match b {
+            None => Value::Null,
+            Some(b) => Value::String(b.to_string()),
+        };
+        Value::Record(vec![
+            ("a".to_string(), Value::Long(a)),
+            (
+                "b".to_string(),
+                Value::Union {
+                    index: 1,
+                    inner: Box::new(b),
+                    n_variants: 2,
+                    null_variant: Some(0),
+                },
+            ),
+            (
+                "c".to_string(),
+                Value::Array(vec![Value::Int(1), Value::Int(2)]),
+            ),
+        ])
+    }
+
+    #[mz_ore::test]
+    fn test_decode_record() {
+        let format = format();
+
+        let row = format.decode_record(record(7, Some("hello"))).unwrap();
+        let datums: Vec<_> = row.iter().skip(1).collect();
+        assert_eq!(datums, vec![Datum::Int32(7), Datum::String("hello")]);
+
+        let row = format.decode_record(record(7, None)).unwrap();
+        assert_eq!(row.iter().nth(2), Some(Datum::Null));
+
+        let err = format.decode_record(record(i64::MAX, None)).unwrap_err();
+        assert!(err.contains("column \"a\""), "{err}");
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    async fn test_split_blocks() {
+        let schema: Schema = SCHEMA.parse().unwrap();
+        let mut writer = Writer::new(schema, Vec::new());
+        for i in 0..100 {
+            writer.append_value_ref(&record(i, Some("x"))).unwrap();
+            // Flush after every few records so we get many blocks.
+            if i % 7 == 0 {
+                writer.flush().unwrap();
+            }
+        }
+        writer.flush().unwrap();
+        let ocf = writer.into_inner();
+
+        let header = AvroHeader::parse(&ocf).unwrap();
+        let format = format();
+
+        // No matter how we split up the file, every record gets read exactly once.
+        for split_size in [1, 13, 100, ocf.len()] {
+            let mut count = 0;
+            for start in (header.len..ocf.len()).step_by(split_size) {
+                let range = start..std::cmp::min(start + split_size, ocf.len());
+                let reader = &ocf[range.start - SYNC_MARKER_LEN..];
+                let chunks: Vec<_> = format
+                    .decode_blocks(header.clone(), reader, range)
+                    .try_collect()
+                    .await
+                    .unwrap();
+                count += chunks.iter().map(|c| c.rows.len()).sum::<usize>();
+            }
+            assert_eq!(count, 100, "split size {split_size}");
+        }
+    }
+}
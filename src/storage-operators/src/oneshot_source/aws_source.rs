@@ -22,9 +22,9 @@ use mz_storage_types::connections::ConnectionContext;
 use mz_storage_types::connections::aws::AwsConnection;
 use serde::{Deserialize, Serialize};
 
-use crate::oneshot_source::util::IntoRangeHeaderValue;
+use crate::oneshot_source::util::{GLOB_MATCH_OPTIONS, IntoRangeHeaderValue, glob_prefix};
 use crate::oneshot_source::{
-    OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext, StorageErrorXKind,
+    Encoding, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext, StorageErrorXKind,
 };
 
 #[derive(Clone, Derivative)]
@@ -41,6 +41,8 @@ pub struct AwsS3Source {
     bucket: String,
     /// Optional prefix that can be specified via an S3 URI.
     prefix: Option<String>,
+    /// Optional glob pattern, specified via an S3 URI, that keys must match.
+    pattern: Option<String>,
     /// S3 client that is lazily initialized.
     #[derivative(Debug = "ignore")]
     client: std::sync::OnceLock<mz_aws_util::s3::Client>,
@@ -59,20 +61,25 @@ impl AwsS3Source {
             .host()
             .expect("validated host in sequencing")
             .to_string();
-        let prefix = if uri.path().is_empty() || uri.path() == "/" {
-            None
+        // Note: `?` is a valid glob character, so we include the query.
+        let key = uri.path_and_query().map(|p| p.as_str()).unwrap_or_default();
+        let key = key.strip_prefix('/').unwrap_or(key);
+
+        let (prefix, pattern) = if let Some(literal) = glob_prefix(key) {
+            // List everything before the first glob character, and then filter
+            // the keys with the pattern.
+            let prefix = (!literal.is_empty()).then(|| literal.to_string());
+            (prefix, Some(key.to_string()))
+        } else if key.is_empty() {
+            (None, None)
         } else {
             // The S3 client expects a trailing `/` but no leading `/`.
-            let mut prefix = uri.path().to_string();
-
-            if let Some(suffix) = prefix.strip_prefix('/') {
-                prefix = suffix.to_string();
-            }
+            let mut prefix = key.to_string();
             if !prefix.ends_with('/') {
                 prefix = format!("{prefix}/");
             }
 
-            Some(prefix)
+            (Some(prefix), None)
         };
 
         AwsS3Source {
@@ -81,6 +88,7 @@ impl AwsS3Source {
             connection_id,
             bucket,
             prefix,
+            pattern,
             client: std::sync::OnceLock::new(),
         }
     }
@@ -113,6 +121,8 @@ pub struct S3Object {
     name: String,
     /// Size of the object in bytes.
     size: usize,
+    /// Encodings implied by the extension of the object.
+    encodings: Vec<Encoding>,
 }
 
 impl OneshotObject for S3Object {
@@ -128,8 +138,8 @@ impl OneshotObject for S3Object {
         self.size
     }
 
-    fn encodings(&self) -> &[super::Encoding] {
-        &[]
+    fn encodings(&self) -> &[Encoding] {
+        &self.encodings
     }
 }
 
@@ -146,29 +156,44 @@ impl OneshotSource for AwsS3Source {
         &'a self,
     ) -> Result<Vec<(Self::Object, Self::Checksum)>, super::StorageErrorX> {
         let client = self.client().await.map_err(StorageErrorXKind::generic)?;
-        let mut objects_request = client.list_objects_v2().bucket(&self.bucket);
-
-        // Users can optionally specify a prefix via the S3 uri they originally specify.
-        if let Some(prefix) = &self.prefix {
-            objects_request = objects_request.prefix(prefix);
-        }
-
-        let objects = objects_request
-            .send()
-            .await
+        let pattern = self
+            .pattern
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
             .map_err(StorageErrorXKind::generic)
-            .context("list_objects_v2")?;
+            .context("glob pattern")?;
+
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut objects_request = client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .set_continuation_token(continuation_token.take());
+
+            // Users can optionally specify a prefix via the S3 uri they originally specify.
+            if let Some(prefix) = &self.prefix {
+                objects_request = objects_request.prefix(prefix);
+            }
 
-        // TODO(cf1): Pagination.
+            let response = objects_request
+                .send()
+                .await
+                .map_err(StorageErrorXKind::generic)
+                .context("list_objects_v2")?;
 
-        let objects: Vec<_> = objects
-            .contents()
-            .iter()
-            .map(|o| {
+            for o in response.contents() {
                 let key = o
                     .key()
                     .ok_or_else(|| StorageErrorXKind::MissingField("key".into()))?
                     .to_owned();
+                if let Some(pattern) = &pattern {
+                    if !pattern.matches_with(&key, GLOB_MATCH_OPTIONS) {
+                        continue;
+                    }
+                }
+
                 let name = Path::new(&key)
                     .file_name()
                     .and_then(|os_name| os_name.to_str())
@@ -178,16 +203,26 @@ impl OneshotSource for AwsS3Source {
                     .size()
                     .ok_or_else(|| StorageErrorXKind::MissingField("size".into()))?;
                 let size: usize = size.try_into().map_err(StorageErrorXKind::generic)?;
+                let encodings = Encoding::from_file_name(&name).into_iter().collect();
 
-                let object = S3Object { key, name, size };
+                let object = S3Object {
+                    key,
+                    name,
+                    size,
+                    encodings,
+                };
                 let checksum = S3Checksum {
                     e_tag: o.e_tag().map(|x| x.to_owned()),
                 };
+                objects.push((object, checksum));
+            }
 
-                Ok::<_, StorageErrorXKind>((object, checksum))
-            })
-            .collect::<Result<_, _>>()
-            .context("list")?;
+            match response.next_continuation_token() {
+                Some(token) => continuation_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+        tracing::info!(num_objects = objects.len(), "found objects");
 
         Ok(objects)
     }
@@ -11,7 +11,6 @@
 
 use std::fmt::Debug;
 use std::io;
-use std::pin::Pin;
 use std::sync::Arc;
 
 use futures::TryStreamExt;
@@ -19,9 +18,10 @@ use futures::stream::{BoxStream, StreamExt};
 use mz_pgcopy::CopyCsvFormatParams;
 use mz_repr::{Datum, RelationDesc, Row, RowArena};
 use serde::{Deserialize, Serialize};
-use smallvec::{SmallVec, smallvec};
+use smallvec::SmallVec;
 use tokio_util::io::StreamReader;
 
+use crate::oneshot_source::util;
 use crate::oneshot_source::{
     Encoding, OneshotFormat, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXKind,
 };
@@ -82,21 +82,8 @@ impl OneshotFormat for CsvDecoder {
         //
         // See <https://badrish.net/papers/dp-sigmod19.pdf> for general parallelization strategies.
 
-        // TODO(cf1): Check the encodings from the object to determine
-        // what decompression to apply. Also support the user manually
-        // specifying certain encodings.
-
-        let encodings = if object.name().ends_with(".gz") {
-            smallvec![Encoding::Gzip]
-        } else if object.name().ends_with(".bz2") {
-            smallvec![Encoding::Bzip2]
-        } else if object.name().ends_with(".xz") {
-            smallvec![Encoding::Xz]
-        } else if object.name().ends_with(".zst") {
-            smallvec![Encoding::Zstd]
-        } else {
-            smallvec![]
-        };
+        // TODO(cf1): Support the user manually specifying certain encodings.
+        let encodings = SmallVec::from_slice(object.encodings());
 
         let request = CsvWorkRequest {
             object,
@@ -123,37 +110,8 @@ impl OneshotFormat for CsvDecoder {
             .map_err(|e| io::Error::new(io::ErrorKind::Interrupted, format!("{e:?}")));
         let stream_reader = StreamReader::new(raw_byte_stream);
 
-        // TODO(cf3): Support multiple encodings.
-        assert!(encodings.len() <= 1, "TODO support multiple encodings");
-
         // Decompress the byte stream, if necessary.
-        let reader: Pin<Box<dyn tokio::io::AsyncRead + Send>> = if let Some(encoding) =
-            encodings.into_iter().next()
-        {
-            tracing::info!(?encoding, "decompressing byte stream");
-            match encoding {
-                Encoding::Bzip2 => {
-                    let decoder = async_compression::tokio::bufread::BzDecoder::new(stream_reader);
-                    Box::pin(decoder)
-                }
-                Encoding::Gzip => {
-                    let decoder =
-                        async_compression::tokio::bufread::GzipDecoder::new(stream_reader);
-                    Box::pin(decoder)
-                }
-                Encoding::Xz => {
-                    let decoder = async_compression::tokio::bufread::XzDecoder::new(stream_reader);
-                    Box::pin(decoder)
-                }
-                Encoding::Zstd => {
-                    let decoder =
-                        async_compression::tokio::bufread::ZstdDecoder::new(stream_reader);
-                    Box::pin(decoder)
-                }
-            }
-        } else {
-            Box::pin(stream_reader)
-        };
+        let reader = util::decompress(stream_reader, &encodings);
 
         let (double_quote, escape) = if self.params.quote == self.params.escape {
             (true, None)
@@ -0,0 +1,245 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Local filesystem [`OneshotSource`], only intended for testing.
+
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use futures::stream::{BoxStream, StreamExt};
+use mz_ore::cast::CastFrom;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+use crate::oneshot_source::util::{GLOB_MATCH_OPTIONS, glob_prefix};
+use crate::oneshot_source::{
+    Encoding, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext, StorageErrorXKind,
+};
+
+/// Oneshot source that reads files from the local filesystem.
+#[derive(Clone, Debug)]
+pub struct LocalFileSource {
+    /// Path to a single file, or a glob pattern that matches multiple files.
+    path: String,
+}
+
+impl LocalFileSource {
+    pub fn new(path: String) -> Self {
+        LocalFileSource { path }
+    }
+
+    /// Returns all of the paths that match our glob pattern.
+    async fn expand_glob(&self) -> Result<Vec<PathBuf>, StorageErrorX> {
+        let pattern = self.path.clone();
+        mz_ore::task::spawn_blocking(
+            || "oneshot-local-file-glob",
+            move || {
+                glob::glob_with(&pattern, GLOB_MATCH_OPTIONS)
+                    .map_err(StorageErrorXKind::generic)?
+                    .map(|entry| entry.map_err(StorageErrorXKind::generic))
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )
+        .await
+        .map_err(StorageErrorXKind::generic)?
+        .context("glob")
+    }
+}
+
+/// Object returned from a [`LocalFileSource`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalFileObject {
+    /// Path of the file.
+    path: String,
+    /// Name of the file, the last component of the path.
+    name: String,
+    /// Size of the file in bytes.
+    size: usize,
+    /// Encodings implied by the extension of the file.
+    encodings: Vec<Encoding>,
+}
+
+impl OneshotObject for LocalFileObject {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn encodings(&self) -> &[Encoding] {
+        &self.encodings
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalFileChecksum {
+    /// Last modification time of the file, if supported by the platform.
+    modified: Option<SystemTime>,
+}
+
+impl OneshotSource for LocalFileSource {
+    type Object = LocalFileObject;
+    type Checksum = LocalFileChecksum;
+
+    async fn list<'a>(&'a self) -> Result<Vec<(Self::Object, Self::Checksum)>, StorageErrorX> {
+        let (paths, is_glob) = match glob_prefix(&self.path) {
+            None => (vec![PathBuf::from(&self.path)], false),
+            Some(_) => (self.expand_glob().await?, true),
+        };
+
+        let mut objects = Vec::with_capacity(paths.len());
+        for path in paths {
+            let metadata = tokio::fs::metadata(&path)
+                .await
+                .map_err(StorageErrorXKind::generic)
+                .context(format!("metadata {}", path.display()))?;
+
+            // Glob patterns can match directories, which we skip.
+            if !metadata.is_file() {
+                if is_glob {
+                    continue;
+                }
+                let msg = format!("{} is not a file", path.display());
+                return Err(StorageErrorXKind::Generic(msg).into());
+            }
+
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| {
+                    StorageErrorXKind::Generic(format!("malformed path: {}", path.display()))
+                })?
+                .to_string();
+            let encodings = Encoding::from_file_name(&name).into_iter().collect();
+            let size = usize::try_from(metadata.len()).map_err(StorageErrorXKind::generic)?;
+            let path = path
+                .to_str()
+                .ok_or_else(|| {
+                    StorageErrorXKind::Generic(format!("malformed path: {}", path.display()))
+                })?
+                .to_string();
+
+            let object = LocalFileObject {
+                path,
+                name,
+                size,
+                encodings,
+            };
+            let checksum = LocalFileChecksum {
+                modified: metadata.modified().ok(),
+            };
+            objects.push((object, checksum));
+        }
+        tracing::info!(?objects, "found objects");
+
+        Ok(objects)
+    }
+
+    fn get<'s>(
+        &'s self,
+        object: Self::Object,
+        checksum: Self::Checksum,
+        range: Option<std::ops::RangeInclusive<usize>>,
+    ) -> BoxStream<'s, Result<Bytes, StorageErrorX>> {
+        let initial_read = async move {
+            tracing::info!(name = %object.name(), ?range, "reading file");
+
+            let mut file = tokio::fs::File::open(&object.path)
+                .await
+                .map_err(StorageErrorXKind::generic)
+                .context("open")?;
+            let metadata = file
+                .metadata()
+                .await
+                .map_err(StorageErrorXKind::generic)
+                .context("metadata")?;
+            if metadata.modified().ok() != checksum.modified {
+                let msg = format!("{} was modified while being copied", object.path);
+                return Err(StorageErrorXKind::Generic(msg).into());
+            }
+
+            let (start, len) = match range {
+                Some(range) => (*range.start(), range.end() - range.start() + 1),
+                None => (0, object.size),
+            };
+            file.seek(SeekFrom::Start(u64::cast_from(start)))
+                .await
+                .map_err(StorageErrorXKind::generic)
+                .context("seek")?;
+            let stream = ReaderStream::new(file.take(u64::cast_from(len)))
+                .map_err(|err| StorageErrorX::from(StorageErrorXKind::generic(err)));
+
+            Ok::<_, StorageErrorX>(stream)
+        };
+
+        futures::stream::once(initial_read).try_flatten().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    async fn test_list_and_get() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in [
+            "2026-01/part-0.csv.gz",
+            "2026-01/part-1.csv",
+            "2025-12/part-0.csv",
+        ] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let mut file = std::fs::File::create(&path).unwrap();
+            file.write_all(b"hello,world\n").unwrap();
+        }
+
+        let root = dir.path().to_str().unwrap();
+        let source = LocalFileSource::new(format!("{root}/2026-*/part-*.csv*"));
+        let mut objects = source.list().await.unwrap();
+        objects.sort_by(|a, b| a.0.path.cmp(&b.0.path));
+
+        let names: Vec<_> = objects.iter().map(|(o, _)| o.name()).collect();
+        assert_eq!(names, ["part-0.csv.gz", "part-1.csv"]);
+        assert_eq!(objects[0].0.encodings(), [Encoding::Gzip]);
+        assert!(objects[1].0.encodings().is_empty());
+
+        let (object, checksum) = objects.pop().unwrap();
+        let bytes: Vec<Bytes> = source
+            .get(object.clone(), checksum.clone(), Some(6..=10))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(bytes.concat(), b"world");
+
+        let bytes: Vec<Bytes> = source
+            .get(object, checksum, None)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(bytes.concat(), b"hello,world\n");
+
+        // A path without any glob characters must refer to a file.
+        let source = LocalFileSource::new(format!("{root}/2026-01"));
+        assert!(source.list().await.is_err());
+    }
+}
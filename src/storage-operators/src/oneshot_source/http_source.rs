@@ -9,6 +9,8 @@
 
 //! Generic HTTP oneshot source that will fetch a file from the public internet.
 
+use std::collections::BTreeSet;
+
 use bytes::Bytes;
 use derivative::Derivative;
 use futures::TryStreamExt;
@@ -17,7 +19,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::oneshot_source::util::IntoRangeHeaderValue;
+use crate::oneshot_source::util::{GLOB_MATCH_OPTIONS, IntoRangeHeaderValue};
 use crate::oneshot_source::{
     Encoding, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXContext, StorageErrorXKind,
 };
@@ -78,18 +80,14 @@ pub enum HttpChecksum {
     LastModified(String),
 }
 
-impl OneshotSource for HttpOneshotSource {
-    type Object = HttpObject;
-    type Checksum = HttpChecksum;
-
-    async fn list<'a>(&'a self) -> Result<Vec<(Self::Object, Self::Checksum)>, StorageErrorX> {
-        // TODO(cf3): Support listing files from a directory index.
-
+impl HttpOneshotSource {
+    /// Fetches metadata about the object at `url`.
+    async fn head(&self, url: Url) -> Result<(HttpObject, HttpChecksum), StorageErrorX> {
         // To get metadata about a file we'll first try issuing a `HEAD` request, which
         // canonically is the right thing do.
         let response = self
             .client
-            .head(self.origin.clone())
+            .head(url.clone())
             .send()
             .await
             .context("HEAD request")?;
@@ -103,7 +101,7 @@ impl OneshotSource for HttpOneshotSource {
 
                 let response = self
                     .client
-                    .get(self.origin.clone())
+                    .get(url.clone())
                     .send()
                     .await
                     .context("GET request")?;
@@ -145,21 +143,102 @@ impl OneshotSource for HttpOneshotSource {
 
         // TODO(cf1): We should probably check the content-type as well. At least for advisory purposes.
 
-        let filename = self
-            .origin
+        let filename = url
             .path_segments()
             .and_then(|segments| segments.rev().next())
             .map(|s| s.to_string())
             .unwrap_or_default();
+
+        // Prefer an explicit Content-Encoding, falling back to the file extension.
+        let content_encoding = match get_header(&reqwest::header::CONTENT_ENCODING) {
+            Some(value) => {
+                Encoding::from_content_encoding(value).context("content-encoding header")?
+            }
+            None => Encoding::from_file_name(&filename).into_iter().collect(),
+        };
+
         let object = HttpObject {
-            url: self.origin.clone(),
+            url,
             filename,
             size,
-            content_encoding: vec![],
+            content_encoding,
         };
-        tracing::info!(?object, "found objects");
 
-        Ok(vec![(object, checksum)])
+        Ok((object, checksum))
+    }
+
+    /// Lists the URLs in the directory index that contains our origin, whose
+    /// file name matches `pattern`.
+    async fn list_directory(&self, pattern: &str) -> Result<Vec<Url>, StorageErrorX> {
+        let pattern = glob::Pattern::new(pattern)
+            .map_err(StorageErrorXKind::generic)
+            .context("glob pattern")?;
+        let directory = self
+            .origin
+            .join("./")
+            .map_err(StorageErrorXKind::generic)
+            .context("directory url")?;
+
+        let index = self
+            .client
+            .get(directory.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .context("GET directory index")?
+            .text()
+            .await
+            .context("directory index body")?;
+
+        let mut urls = BTreeSet::new();
+        for href in parse_hrefs(&index) {
+            let Ok(url) = directory.join(href) else {
+                continue;
+            };
+            // Only consider files that are direct children of the directory.
+            let Some(name) = url
+                .as_str()
+                .strip_prefix(directory.as_str())
+                .filter(|name| !name.is_empty() && !name.contains(['/', '?', '#']))
+            else {
+                continue;
+            };
+            let name = percent_encoding::percent_decode_str(name).decode_utf8_lossy();
+            if pattern.matches_with(&name, GLOB_MATCH_OPTIONS) {
+                urls.insert(url);
+            }
+        }
+
+        Ok(urls.into_iter().collect())
+    }
+}
+
+impl OneshotSource for HttpOneshotSource {
+    type Object = HttpObject;
+    type Checksum = HttpChecksum;
+
+    async fn list<'a>(&'a self) -> Result<Vec<(Self::Object, Self::Checksum)>, StorageErrorX> {
+        // A glob pattern in the last segment of the URL matches files listed in
+        // the directory index of the containing directory.
+        let pattern = self
+            .origin
+            .path_segments()
+            .and_then(|segments| segments.rev().next())
+            .map(|segment| percent_encoding::percent_decode_str(segment).decode_utf8_lossy())
+            .filter(|segment| segment.contains(['*', '[']));
+
+        let urls = match pattern {
+            Some(pattern) => self.list_directory(&pattern).await?,
+            None => vec![self.origin.clone()],
+        };
+
+        let mut objects = Vec::with_capacity(urls.len());
+        for url in urls {
+            objects.push(self.head(url).await?);
+        }
+        tracing::info!(?objects, "found objects");
+
+        Ok(objects)
     }
 
     fn get<'s>(
@@ -193,3 +272,30 @@ impl OneshotSource for HttpOneshotSource {
             .boxed()
     }
 }
+
+/// Returns the values of all `href` attributes in an HTML document, e.g. the
+/// directory index generated by a web server.
+fn parse_hrefs(html: &str) -> impl Iterator<Item = &str> {
+    html.split("href=").skip(1).filter_map(|rest| {
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let rest = &rest[1..];
+        rest.find(quote).map(|end| &rest[..end])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_hrefs() {
+        let html = r#"<html><body>
+<a href="../">../</a>
+<a href="part-0.csv.gz">part-0.csv.gz</a> 12-Jan-2026 10:00 1024
+<a href='part-1.csv'>part-1.csv</a>
+<a href=unquoted>unquoted</a>
+</body></html>"#;
+        let hrefs: Vec<_> = parse_hrefs(html).collect();
+        assert_eq!(hrefs, ["../", "part-0.csv.gz", "part-1.csv"]);
+    }
+}
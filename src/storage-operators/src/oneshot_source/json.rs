@@ -0,0 +1,323 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Newline-delimited JSON [`OneshotFormat`].
+
+use std::io;
+use std::ops::Range;
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use futures::stream::{BoxStream, StreamExt};
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::{ColumnName, Datum, RelationDesc, Row, RowArena};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use tokio::io::AsyncBufReadExt;
+use tokio_util::io::StreamReader;
+
+use crate::oneshot_source::util;
+use crate::oneshot_source::{
+    Encoding, OneshotFormat, OneshotObject, OneshotSource, StorageErrorX, StorageErrorXKind,
+};
+
+/// Objects larger than this are split into multiple byte ranges that get
+/// fetched and decoded in parallel.
+const SPLIT_SIZE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Maximum number of records we'll put in a single [`JsonRecords`] chunk.
+const MAX_RECORDS_PER_CHUNK: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct JsonFormat {
+    /// Names and types of the columns in the table we're copying into.
+    columns: Arc<[(ColumnName, mz_pgrepr::Type)]>,
+}
+
+impl JsonFormat {
+    pub fn new(desc: &RelationDesc) -> Self {
+        let columns = desc
+            .iter()
+            .map(|(name, typ)| (name.clone(), mz_pgrepr::Type::from(&typ.scalar_type)))
+            .collect();
+        JsonFormat { columns }
+    }
+}
+
+/// Instructions on how to parse a range of a newline-delimited JSON object.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonWorkRequest<O, C> {
+    object: O,
+    checksum: C,
+    /// Records whose first byte falls within this range belong to this request.
+    range: Range<usize>,
+    /// Encodings that we need to decompress before reading records.
+    encodings: SmallVec<[Encoding; 1]>,
+}
+
+/// A chunk of undecoded JSON records.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JsonRecords {
+    bytes: Vec<u8>,
+    /// Byte offset of each record within the source object, and the range of
+    /// `bytes` that contains the record.
+    records: Vec<(usize, Range<usize>)>,
+}
+
+impl JsonRecords {
+    fn push(&mut self, offset: usize, line: &[u8]) {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return;
+        }
+
+        let start = self.bytes.len();
+        self.bytes.extend_from_slice(line);
+        self.records.push((offset, start..self.bytes.len()));
+    }
+}
+
+impl OneshotFormat for JsonFormat {
+    type WorkRequest<S>
+        = JsonWorkRequest<S::Object, S::Checksum>
+    where
+        S: OneshotSource;
+    type RecordChunk = JsonRecords;
+
+    async fn split_work<S: OneshotSource + Send>(
+        &self,
+        _source: S,
+        object: S::Object,
+        checksum: S::Checksum,
+    ) -> Result<Vec<Self::WorkRequest<S>>, StorageErrorX> {
+        let size = object.size();
+        let encodings = SmallVec::from_slice(object.encodings());
+
+        // We can't seek into the middle of a compressed object, so it gets
+        // decoded by a single request.
+        let work: Vec<_> = if !object.encodings().is_empty() {
+            vec![JsonWorkRequest {
+                object: object.clone(),
+                checksum,
+                range: 0..usize::MAX,
+                encodings,
+            }]
+        } else {
+            (0..size)
+                .step_by(SPLIT_SIZE_BYTES)
+                .map(|start| JsonWorkRequest {
+                    object: object.clone(),
+                    checksum: checksum.clone(),
+                    range: start..std::cmp::min(start.saturating_add(SPLIT_SIZE_BYTES), size),
+                    encodings: SmallVec::new(),
+                })
+                .collect()
+        };
+
+        tracing::info!(
+            object = object.name(),
+            size,
+            requests = work.len(),
+            "splitting JSON object"
+        );
+
+        Ok(work)
+    }
+
+    fn fetch_work<'a, S: OneshotSource + Sync + 'static>(
+        &'a self,
+        source: &'a S,
+        request: Self::WorkRequest<S>,
+    ) -> BoxStream<'a, Result<Self::RecordChunk, StorageErrorX>> {
+        let JsonWorkRequest {
+            object,
+            checksum,
+            range,
+            encodings,
+        } = request;
+
+        // A record belongs to the request whose range contains its first byte,
+        // so we start fetching one byte early to tell if a record begins
+        // exactly at `range.start`. The last record in our range likely ends
+        // past `range.end`, so we fetch until the end of the object and stop
+        // reading once we're done.
+        let fetch_start = range.start.saturating_sub(1);
+        let fetch_end = object.size().saturating_sub(1);
+
+        // Compressed objects are always fetched in their entirety.
+        let fetch_range = encodings.is_empty().then_some(fetch_start..=fetch_end);
+
+        // Wrap our `Stream<Bytes>` into a type that implements `tokio::io::AsyncBufRead`.
+        let raw_byte_stream = source
+            .get(object, checksum, fetch_range)
+            .map_err(|e| io::Error::new(io::ErrorKind::Interrupted, format!("{e:?}")));
+        let mut reader = util::decompress(StreamReader::new(raw_byte_stream), &encodings);
+
+        async_stream::try_stream! {
+            let mut position = fetch_start;
+            let mut line = Vec::new();
+
+            // Skip the remainder of the record that started in the previous range.
+            if range.start > 0 {
+                position += reader
+                    .read_until(b'\n', &mut line)
+                    .await
+                    .map_err(|err| StorageErrorXKind::generic(err).with_context("skip record"))?;
+            }
+
+            let mut chunk = JsonRecords::default();
+            while position < range.end {
+                line.clear();
+                let len = reader
+                    .read_until(b'\n', &mut line)
+                    .await
+                    .map_err(|err| StorageErrorXKind::generic(err).with_context("read record"))?;
+                if len == 0 {
+                    break;
+                }
+                chunk.push(position, &line);
+                position += len;
+
+                if chunk.records.len() >= MAX_RECORDS_PER_CHUNK {
+                    yield std::mem::take(&mut chunk);
+                }
+            }
+
+            if !chunk.records.is_empty() {
+                yield chunk;
+            }
+        }
+        .boxed()
+    }
+
+    fn decode_chunk(
+        &self,
+        chunk: Self::RecordChunk,
+        rows: &mut Vec<Row>,
+    ) -> Result<usize, StorageErrorX> {
+        let JsonRecords { bytes, records } = chunk;
+
+        let mut count = 0;
+        for (offset, range) in records {
+            let record = bytes
+                .get(range)
+                .ok_or_else(|| StorageErrorXKind::programming_error("invalid byte range"))?;
+            let row = self.decode_record(record).map_err(|err| {
+                StorageErrorXKind::JsonDecoding(
+                    format!("record at byte offset {offset}: {err}").into(),
+                )
+            })?;
+            rows.push(row);
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+impl JsonFormat {
+    /// Decodes a single JSON object into a [`Row`], mapping fields to columns by name.
+    ///
+    /// Fields that are missing or `null` become SQL `NULL`, fields that don't
+    /// match any column are ignored.
+    fn decode_record(&self, record: &[u8]) -> Result<Row, String> {
+        let value: serde_json::Value =
+            serde_json::from_slice(record).map_err(|err| err.to_string())?;
+        let serde_json::Value::Object(mut fields) = value else {
+            return Err("expected a JSON object".to_string());
+        };
+
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        let arena = RowArena::new();
+
+        for (name, typ) in self.columns.iter() {
+            let column_err = |err: &dyn std::fmt::Display| format!("column \"{name}\": {err}");
+
+            match fields.remove(name.as_str()) {
+                None | Some(serde_json::Value::Null) => packer.push(Datum::Null),
+                Some(value) if matches!(typ, mz_pgrepr::Type::Jsonb) => {
+                    JsonbPacker::new(&mut packer)
+                        .pack_serde_json(value)
+                        .map_err(|err| column_err(&err))?;
+                }
+                Some(value) => {
+                    let text = match value {
+                        serde_json::Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    let value = mz_pgrepr::Value::decode_text(typ, text.as_bytes())
+                        .map_err(|err| column_err(&err))?;
+                    packer.push(value.into_datum(&arena, typ));
+                }
+            }
+        }
+
+        Ok(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    fn format() -> JsonFormat {
+        let desc = RelationDesc::builder()
+            .with_column("a", ScalarType::Int64.nullable(true))
+            .with_column("b", ScalarType::String.nullable(true))
+            .with_column("c", ScalarType::Jsonb.nullable(true))
+            .finish();
+        JsonFormat::new(&desc)
+    }
+
+    #[mz_ore::test]
+    fn test_decode_records() {
+        let format = format();
+        let mut chunk = JsonRecords::default();
+        chunk.push(
+            0,
+            br#"{"b": "hello", "a": 42, "c": {"x": [1, 2]}, "d": true}"#,
+        );
+        chunk.push(10, b"   \r\n");
+        chunk.push(20, br#"{"a": "7", "b": 100}"#);
+
+        let mut rows = Vec::new();
+        let count = format.decode_chunk(chunk, &mut rows).unwrap();
+        assert_eq!(count, 2);
+
+        let datums: Vec<_> = rows[0].iter().take(2).collect();
+        assert_eq!(datums, vec![Datum::Int64(42), Datum::String("hello")]);
+        let datums: Vec<_> = rows[1].iter().collect();
+        assert_eq!(
+            datums,
+            vec![Datum::Int64(7), Datum::String("100"), Datum::Null]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_decode_errors() {
+        let format = format();
+        let mut chunk = JsonRecords::default();
+        chunk.push(0, br#"{"a": 1}"#);
+        chunk.push(9, br#"{"a": "not a number"}"#);
+
+        let err = format.decode_chunk(chunk, &mut Vec::new()).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("record at byte offset 9"), "{err}");
+        assert!(err.contains("column \"a\""), "{err}");
+
+        let mut chunk = JsonRecords::default();
+        chunk.push(0, b"[1, 2, 3]");
+        let err = format.decode_chunk(chunk, &mut Vec::new()).unwrap_err();
+        assert!(err.to_string().contains("expected a JSON object"), "{err}");
+    }
+}
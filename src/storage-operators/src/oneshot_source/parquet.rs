@@ -70,6 +70,13 @@ impl OneshotFormat for ParquetFormat {
         object: S::Object,
         checksum: S::Checksum,
     ) -> Result<Vec<Self::WorkRequest<S>>, StorageErrorX> {
+        // Parquet does its own compression internally, and requires random access.
+        if !object.encodings().is_empty() {
+            let encodings = format!("{:?}", object.encodings());
+            return Err(StorageErrorXKind::UnsupportedEncoding(encodings.into()))
+                .context(object.name());
+        }
+
         let mut adapter = ParquetReaderAdapter::new(source, object.clone(), checksum.clone());
         let parquet_metadata = adapter.get_metadata().await?;
 
@@ -9,6 +9,13 @@
 
 //! Utility functions for Oneshot sources.
 
+use std::pin::Pin;
+
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, XzDecoder, ZstdDecoder};
+use tokio::io::{AsyncBufRead, AsyncRead, BufReader};
+
+use crate::oneshot_source::Encoding;
+
 /// Utility trait for converting various Rust Range types into a header value.
 /// according to the MDN Web Docs.
 ///
@@ -30,3 +37,88 @@ impl IntoRangeHeaderValue for std::ops::RangeInclusive<usize> {
         format!("bytes={}-{}", self.start(), self.end())
     }
 }
+
+/// Characters that have a special meaning in a glob pattern.
+const GLOB_CHARS: &[char] = &['*', '?', '['];
+
+/// Options for matching glob patterns against paths. Like a shell, `*` and `?`
+/// never match a `/`, only `**` does.
+pub const GLOB_MATCH_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// If `path` contains a glob pattern, returns the literal prefix of `path`
+/// that precedes the first glob character.
+pub fn glob_prefix(path: &str) -> Option<&str> {
+    path.find(GLOB_CHARS).map(|idx| &path[..idx])
+}
+
+/// Wraps `reader` such that it transparently decompresses the provided
+/// `encodings`, which are listed in the order they were applied.
+pub fn decompress<'a, R>(
+    reader: R,
+    encodings: &[Encoding],
+) -> Pin<Box<dyn AsyncBufRead + Send + 'a>>
+where
+    R: AsyncBufRead + Send + 'a,
+{
+    let mut reader: Pin<Box<dyn AsyncBufRead + Send + 'a>> = Box::pin(reader);
+    for encoding in encodings.iter().rev() {
+        tracing::info!(?encoding, "decompressing byte stream");
+        let decoder: Pin<Box<dyn AsyncRead + Send + 'a>> = match encoding {
+            Encoding::Bzip2 => Box::pin(BzDecoder::new(reader)),
+            Encoding::Gzip => Box::pin(GzipDecoder::new(reader)),
+            Encoding::Xz => Box::pin(XzDecoder::new(reader)),
+            Encoding::Zstd => Box::pin(ZstdDecoder::new(reader)),
+        };
+        reader = Box::pin(BufReader::new(decoder));
+    }
+    reader
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_glob_prefix() {
+        assert_eq!(glob_prefix("csv/part-1.csv"), None);
+        assert_eq!(glob_prefix("2026-*/part-*.csv.gz"), Some("2026-"));
+        assert_eq!(glob_prefix("data/part-?.csv"), Some("data/part-"));
+        assert_eq!(glob_prefix("[ab]/x.csv"), Some(""));
+    }
+
+    #[mz_ore::test]
+    fn test_glob_match_options() {
+        let pattern = glob::Pattern::new("2026-*/part-*.csv.gz").unwrap();
+        assert!(pattern.matches_with("2026-01/part-0.csv.gz", GLOB_MATCH_OPTIONS));
+        assert!(!pattern.matches_with("2026-01/nested/part-0.csv.gz", GLOB_MATCH_OPTIONS));
+        assert!(!pattern.matches_with("2026-01/part-0.csv", GLOB_MATCH_OPTIONS));
+
+        let pattern = glob::Pattern::new("2026-*/**/part-*.csv.gz").unwrap();
+        assert!(pattern.matches_with("2026-01/nested/part-0.csv.gz", GLOB_MATCH_OPTIONS));
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    async fn test_decompress() {
+        use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut zstd = ZstdEncoder::new(Vec::new());
+        zstd.write_all(b"hello,world\n").await.unwrap();
+        zstd.shutdown().await.unwrap();
+        let mut gzip = GzipEncoder::new(Vec::new());
+        gzip.write_all(&zstd.into_inner()).await.unwrap();
+        gzip.shutdown().await.unwrap();
+        let compressed = gzip.into_inner();
+
+        // Encodings are listed in the order they were applied.
+        let mut reader = decompress(&compressed[..], &[Encoding::Zstd, Encoding::Gzip]);
+        let mut decompressed = String::new();
+        reader.read_to_string(&mut decompressed).await.unwrap();
+        assert_eq!(decompressed, "hello,world\n");
+    }
+}
@@ -419,7 +419,9 @@ fn filter_result(
             key: Arc::new(key),
             val: Arc::new(val),
         }
-    } else if may_error || may_keep {
+    } else if may_error {
+        FilterResult::Keep
+    } else if may_keep && stats.may_match_bloom_filters(plan.nontemporal()) {
         FilterResult::Keep
     } else {
         FilterResult::Discard
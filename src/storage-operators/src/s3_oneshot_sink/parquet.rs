@@ -256,7 +256,7 @@ impl ParquetUploader {
 
 /// Helper to tie the lifecycle of the `ArrowBuilder`, `ArrowWriter`, and `S3MultiPartUploader`
 /// together for a single parquet file.
-struct ParquetFile {
+pub(crate) struct ParquetFile {
     /// The active arrow builder.
     builder: ArrowBuilder,
     writer: ArrowWriter<Vec<u8>>,
@@ -269,7 +269,7 @@ struct ParquetFile {
 }
 
 impl ParquetFile {
-    async fn new(
+    pub(crate) async fn new(
         bucket: String,
         key: String,
         desc: Arc<RelationDesc>,
@@ -323,7 +323,7 @@ impl ParquetFile {
         })
     }
 
-    fn add_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+    pub(crate) fn add_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
         self.builder.add_row(row)?;
 
         if u64::cast_from(self.builder.row_size_bytes()) > self.arrow_builder_buffer_bytes {
@@ -334,7 +334,7 @@ impl ParquetFile {
     }
 
     /// Flush the current arrow builder, the parquet writer, and the uploader.
-    async fn finish(mut self) -> Result<CompletedUpload, anyhow::Error> {
+    pub(crate) async fn finish(mut self) -> Result<CompletedUpload, anyhow::Error> {
         self.flush_builder()?;
         let buffer = self.writer.into_inner()?;
         self.uploader.buffer_chunk(buffer.as_slice())?;
@@ -384,7 +384,7 @@ impl ParquetFile {
     }
 
     /// Returns an approximate size estimate of the file being written.
-    fn size_estimate(&self) -> u64 {
+    pub(crate) fn size_estimate(&self) -> u64 {
         // ArrowWriter.in_progress_size() is just an estimate since it doesn't seem
         // to account for data page compression and metadata that will be written for the next
         // row-group.
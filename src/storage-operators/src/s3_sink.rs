@@ -0,0 +1,395 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Writes the files of a continuous S3 sink.
+//!
+//! See [`mz_storage_types::sinks::s3`] for the layout of the files.
+
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use aws_sdk_s3::Client;
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_types::sdk_config::SdkConfig;
+use mz_aws_util::s3_uploader::{
+    AWS_S3_MAX_PART_COUNT, CompletedUpload, S3MultiPartUploader, S3MultiPartUploaderConfig,
+};
+use mz_ore::cast::CastFrom;
+use mz_ore::future::OreFutureExt;
+use mz_pgcopy::{encode_copy_format, encode_copy_format_header};
+use mz_repr::{GlobalId, RelationDesc, Row};
+use mz_storage_types::sinks::S3SinkFormat;
+use mz_storage_types::sinks::s3::{Manifest, ManifestFile, Progress, S3SinkLayout};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, info};
+
+use crate::s3_oneshot_sink::CopyToParameters;
+use crate::s3_oneshot_sink::parquet::ParquetFile;
+
+/// Returns the file extension of data files of the given format.
+pub fn file_extension(format: &S3SinkFormat) -> &str {
+    match format {
+        S3SinkFormat::PgCopy(params) => params.file_extension(),
+        S3SinkFormat::Parquet => "parquet",
+    }
+}
+
+/// Reads and writes the files of a sink under its S3 URI.
+#[derive(Clone)]
+pub struct S3SinkClient {
+    sdk_config: Arc<SdkConfig>,
+    client: Client,
+    layout: S3SinkLayout,
+}
+
+impl S3SinkClient {
+    pub fn new(sdk_config: SdkConfig, layout: S3SinkLayout) -> Self {
+        let client = mz_aws_util::s3::new_client(&sdk_config);
+        S3SinkClient {
+            sdk_config: Arc::new(sdk_config),
+            client,
+            layout,
+        }
+    }
+
+    /// Returns the progress of `sink_id`, claiming the path of the sink if
+    /// this is the first time the sink runs.
+    ///
+    /// A path may only be claimed if it is empty. Once claimed, any other sink
+    /// attempting to write to the same path fails.
+    pub async fn claim(&self, sink_id: GlobalId) -> Result<Progress, anyhow::Error> {
+        if let Some(progress) = self.read_progress().await? {
+            progress.check_owner(sink_id)?;
+            return Ok(progress);
+        }
+
+        let layout = &self.layout;
+        let prefix = if layout.prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", layout.prefix)
+        };
+        if let Some(files) =
+            mz_aws_util::s3::list_bucket_path(&self.client, &layout.bucket, &prefix).await?
+        {
+            if !files.is_empty() {
+                anyhow::bail!(
+                    "S3 bucket path is not empty, contains {} objects",
+                    files.len()
+                );
+            }
+        }
+
+        info!(%sink_id, "claiming s3://{}/{}", layout.bucket, layout.prefix);
+        let progress = Progress {
+            sink_id: sink_id.to_string(),
+            frontier: Some(0),
+        };
+        self.write_progress(&progress).await?;
+        Ok(progress)
+    }
+
+    /// Reads the progress recorded by the sink, if any.
+    pub async fn read_progress(&self) -> Result<Option<Progress>, anyhow::Error> {
+        get_json(
+            &self.client,
+            &self.layout.bucket,
+            &self.layout.progress_key(),
+        )
+        .await
+    }
+
+    /// Records that the sink has written all manifests up to
+    /// `progress.frontier`.
+    pub async fn write_progress(&self, progress: &Progress) -> Result<(), anyhow::Error> {
+        let key = self.layout.progress_key();
+        put_json(&self.client, &self.layout.bucket, &key, progress).await
+    }
+
+    /// Writes the manifest of a timestamp, closing it.
+    pub async fn write_manifest(&self, manifest: &Manifest) -> Result<(), anyhow::Error> {
+        let key = self.layout.manifest_key(manifest.timestamp);
+        put_json(&self.client, &self.layout.bucket, &key, manifest).await
+    }
+
+    /// Returns a writer for the data files of `timestamp`.
+    pub fn timestamp_writer(
+        &self,
+        desc: Arc<RelationDesc>,
+        format: S3SinkFormat,
+        max_file_size: u64,
+        params: CopyToParameters,
+        timestamp: u64,
+    ) -> Result<TimestampWriter, anyhow::Error> {
+        TimestampWriter::new(
+            Arc::clone(&self.sdk_config),
+            self.layout.clone(),
+            desc,
+            format,
+            max_file_size,
+            params,
+            timestamp,
+        )
+    }
+}
+
+async fn get_json<T: DeserializeOwned>(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<T>, anyhow::Error> {
+    let object = match client.get_object().bucket(bucket).key(key).send().await {
+        Ok(object) => object,
+        Err(SdkError::ServiceError(err)) if err.err().is_no_such_key() => return Ok(None),
+        Err(err) => return Err(anyhow!(err).context(format!("reading s3://{bucket}/{key}"))),
+    };
+    let body = object
+        .body
+        .collect()
+        .await
+        .with_context(|| format!("reading s3://{bucket}/{key}"))?;
+    let value = serde_json::from_slice(&body.into_bytes())
+        .with_context(|| format!("decoding s3://{bucket}/{key}"))?;
+    Ok(Some(value))
+}
+
+async fn put_json<T: Serialize>(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    value: &T,
+) -> Result<(), anyhow::Error> {
+    let body = serde_json::to_vec_pretty(value)?;
+    client
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .content_type("application/json")
+        .body(ByteStream::from(body))
+        .send()
+        .await
+        .with_context(|| format!("writing s3://{bucket}/{key}"))?;
+    Ok(())
+}
+
+/// Writes the data files of a single timestamp.
+///
+/// Files are named after the timestamp and their index within it, so writing
+/// the same timestamp again overwrites the files of the previous attempt.
+pub struct TimestampWriter {
+    sdk_config: Arc<SdkConfig>,
+    layout: S3SinkLayout,
+    /// The description of the rows in the data files.
+    desc: Arc<RelationDesc>,
+    format: S3SinkFormat,
+    max_file_size: u64,
+    params: CopyToParameters,
+    timestamp: u64,
+    /// The file currently being written, if any.
+    active_file: Option<ActiveFile>,
+    /// The files that have been completely written.
+    files: Vec<ManifestFile>,
+}
+
+struct ActiveFile {
+    key: String,
+    records: u64,
+    kind: ActiveFileKind,
+}
+
+enum ActiveFileKind {
+    PgCopy(S3MultiPartUploader),
+    Parquet(ParquetFile),
+}
+
+impl TimestampWriter {
+    pub fn new(
+        sdk_config: Arc<SdkConfig>,
+        layout: S3SinkLayout,
+        desc: Arc<RelationDesc>,
+        format: S3SinkFormat,
+        max_file_size: u64,
+        params: CopyToParameters,
+        timestamp: u64,
+    ) -> Result<Self, anyhow::Error> {
+        if params.parquet_row_group_ratio > 100 {
+            anyhow::bail!("parquet_row_group_ratio must be <= 100");
+        }
+        if params.arrow_builder_buffer_ratio < 100 {
+            anyhow::bail!("arrow_builder_buffer_ratio must be >= 100");
+        }
+        Ok(TimestampWriter {
+            sdk_config,
+            layout,
+            desc,
+            format,
+            max_file_size,
+            params,
+            timestamp,
+            active_file: None,
+            files: vec![],
+        })
+    }
+
+    /// Appends a row to the current data file, starting a new file if the
+    /// current one has reached the max file size.
+    pub async fn append_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        match &self.format {
+            S3SinkFormat::PgCopy(params) => {
+                let mut buf: Vec<u8> = vec![];
+                encode_copy_format(params, row, self.desc.typ(), &mut buf)
+                    .map_err(|_| anyhow!("error encoding row"))?;
+                let needs_new_file = match &self.active_file {
+                    Some(ActiveFile {
+                        kind: ActiveFileKind::PgCopy(uploader),
+                        records,
+                        ..
+                    }) => {
+                        *records > 0
+                            && uploader.added_bytes() + u64::cast_from(buf.len())
+                                > self.max_file_size
+                    }
+                    _ => true,
+                };
+                if needs_new_file {
+                    self.start_new_file().await?;
+                }
+                let file = self.active_file.as_mut().expect("known exists");
+                let ActiveFileKind::PgCopy(uploader) = &mut file.kind else {
+                    unreachable!("PgCopy format writes PgCopy files");
+                };
+                uploader.buffer_chunk(&buf)?;
+                file.records += 1;
+            }
+            S3SinkFormat::Parquet => {
+                if self.active_file.is_none() {
+                    self.start_new_file().await?;
+                }
+                let file = self.active_file.as_mut().expect("known exists");
+                let ActiveFileKind::Parquet(parquet) = &mut file.kind else {
+                    unreachable!("Parquet format writes Parquet files");
+                };
+                parquet.add_row(row)?;
+                file.records += 1;
+                if parquet.size_estimate() >= self.max_file_size {
+                    debug!("file size limit exceeded, starting new file");
+                    self.finish_file().await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes all files, returning the manifest entries of the timestamp.
+    pub async fn finish(mut self) -> Result<Vec<ManifestFile>, anyhow::Error> {
+        self.finish_file().await?;
+        Ok(self.files)
+    }
+
+    async fn finish_file(&mut self) -> Result<(), anyhow::Error> {
+        let Some(file) = self.active_file.take() else {
+            return Ok(());
+        };
+        let completed: CompletedUpload = match file.kind {
+            ActiveFileKind::PgCopy(uploader) => {
+                uploader
+                    .finish()
+                    .run_in_task(|| "S3SinkPgCopyFile::finish")
+                    .await?
+            }
+            ActiveFileKind::Parquet(parquet) => {
+                parquet
+                    .finish()
+                    .run_in_task(|| "S3SinkParquetFile::finish")
+                    .await?
+            }
+        };
+        info!(
+            "finished upload: bucket {}, key {}, bytes_uploaded {}, parts_uploaded {}",
+            completed.bucket, completed.key, completed.total_bytes_uploaded, completed.part_count
+        );
+        self.files.push(ManifestFile {
+            key: file.key,
+            records: file.records,
+            bytes: completed.total_bytes_uploaded,
+        });
+        Ok(())
+    }
+
+    async fn start_new_file(&mut self) -> Result<(), anyhow::Error> {
+        self.finish_file().await?;
+
+        let key = self.layout.data_key(
+            self.timestamp,
+            self.files.len(),
+            file_extension(&self.format),
+        );
+        let bucket = self.layout.bucket.clone();
+        info!("starting upload: bucket {}, key {}", bucket, key);
+        let part_size_limit = u64::cast_from(self.params.s3_multipart_part_size_bytes);
+        let kind = match &self.format {
+            S3SinkFormat::PgCopy(params) => {
+                let sdk_config = Arc::clone(&self.sdk_config);
+                let object_key = key.clone();
+                let mut uploader = async move {
+                    S3MultiPartUploader::try_new(
+                        &sdk_config,
+                        bucket,
+                        object_key,
+                        S3MultiPartUploaderConfig {
+                            part_size_limit,
+                            // We are already enforcing the max size ourselves,
+                            // without ever splitting a row across files.
+                            file_size_limit: part_size_limit
+                                .checked_mul(AWS_S3_MAX_PART_COUNT.try_into().expect("known safe"))
+                                .expect("known safe"),
+                        },
+                    )
+                    .await
+                }
+                .run_in_task(|| "S3SinkPgCopyFile::new")
+                .await?;
+                if params.requires_header() {
+                    let mut buf: Vec<u8> = vec![];
+                    encode_copy_format_header(params, &self.desc, &mut buf)
+                        .map_err(|_| anyhow!("error encoding header"))?;
+                    uploader.buffer_chunk(&buf)?;
+                }
+                ActiveFileKind::PgCopy(uploader)
+            }
+            S3SinkFormat::Parquet => {
+                let row_group_size_bytes =
+                    self.max_file_size * u64::cast_from(self.params.parquet_row_group_ratio) / 100;
+                let arrow_builder_buffer_bytes = row_group_size_bytes
+                    * u64::cast_from(self.params.arrow_builder_buffer_ratio)
+                    / 100;
+                let file = ParquetFile::new(
+                    bucket,
+                    key.clone(),
+                    Arc::clone(&self.desc),
+                    Arc::clone(&self.sdk_config),
+                    arrow_builder_buffer_bytes,
+                    row_group_size_bytes,
+                    part_size_limit,
+                )
+                .run_in_task(|| "S3SinkParquetFile::new")
+                .await?;
+                ActiveFileKind::Parquet(file)
+            }
+        };
+        self.active_file = Some(ActiveFile {
+            key,
+            records: 0,
+            kind,
+        });
+        Ok(())
+    }
+}
@@ -69,6 +69,8 @@ fn main() {
                 "storage-types/src/sources/encoding.proto",
                 "storage-types/src/sources/envelope.proto",
                 "storage-types/src/sources/kafka.proto",
+                "storage-types/src/sources/kinesis.proto",
+                "storage-types/src/sources/mongodb.proto",
                 "storage-types/src/sources/mysql.proto",
                 "storage-types/src/sources/postgres.proto",
                 "storage-types/src/sources/sql_server.proto",
@@ -201,6 +201,7 @@ pub enum Connection<C: ConnectionAccess = InlinedConnection> {
     AwsPrivatelink(AwsPrivatelinkConnection),
     MySql(MySqlConnection<C>),
     SqlServer(SqlServerConnectionDetails<C>),
+    MongoDb(MongoDbConnection),
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
@@ -218,6 +219,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
             Connection::SqlServer(sql_server) => {
                 Connection::SqlServer(sql_server.into_inline_connection(r))
             }
+            Connection::MongoDb(mongodb) => Connection::MongoDb(mongodb),
         }
     }
 }
@@ -234,6 +236,7 @@ impl<C: ConnectionAccess> Connection<C> {
             Connection::AwsPrivatelink(conn) => conn.validate_by_default(),
             Connection::MySql(conn) => conn.validate_by_default(),
             Connection::SqlServer(conn) => conn.validate_by_default(),
+            Connection::MongoDb(conn) => conn.validate_by_default(),
         }
     }
 }
@@ -254,6 +257,7 @@ impl Connection<InlinedConnection> {
             Connection::AwsPrivatelink(conn) => conn.validate(id, storage_configuration).await?,
             Connection::MySql(conn) => conn.validate(id, storage_configuration).await?,
             Connection::SqlServer(conn) => conn.validate(id, storage_configuration).await?,
+            Connection::MongoDb(conn) => conn.validate(id, storage_configuration).await?,
         }
         Ok(())
     }
@@ -286,6 +290,13 @@ impl Connection<InlinedConnection> {
         }
     }
 
+    pub fn unwrap_mongodb(self) -> <InlinedConnection as ConnectionAccess>::MongoDb {
+        match self {
+            Self::MongoDb(conn) => conn,
+            o => unreachable!("{o:?} is not a MongoDB connection"),
+        }
+    }
+
     pub fn unwrap_aws(self) -> <InlinedConnection as ConnectionAccess>::Aws {
         match self {
             Self::Aws(conn) => conn,
@@ -345,6 +356,7 @@ impl<C: ConnectionAccess> AlterCompatible for Connection<C> {
             (Self::Kafka(s), Self::Kafka(o)) => s.alter_compatible(id, o),
             (Self::Postgres(s), Self::Postgres(o)) => s.alter_compatible(id, o),
             (Self::MySql(s), Self::MySql(o)) => s.alter_compatible(id, o),
+            (Self::MongoDb(s), Self::MongoDb(o)) => s.alter_compatible(id, o),
             _ => {
                 tracing::warn!(
                     "Connection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
@@ -2298,6 +2310,136 @@ impl RustType<ProtoSqlServerEncryptionLevel> for mz_sql_server_util::config::Enc
     }
 }
 
+/// Details how to connect to a MongoDB deployment.
+///
+/// The connection is always made directly to the configured member, rather
+/// than discovering the rest of the replica set, so that the node we read
+/// a snapshot from is the same node we tail the change stream from.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MongoDbConnection {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The username to authenticate as, if authentication is enabled.
+    pub user: Option<StringOrSecret>,
+    /// Password used for authentication.
+    pub password: Option<CatalogItemId>,
+    /// Whether to connect over TLS, verifying the server's certificate
+    /// against the system's root certificates.
+    pub tls: bool,
+}
+
+impl MongoDbConnection {
+    fn validate_by_default(&self) -> bool {
+        true
+    }
+
+    /// Opens a client to the upstream MongoDB deployment.
+    ///
+    /// The provided [`InTask`] argument determines whether any secrets are
+    /// read in an [`mz_ore::task`]. The driver itself always runs its I/O on
+    /// the ambient tokio runtime.
+    pub async fn connect(
+        &self,
+        secrets_reader: &Arc<dyn SecretsReader>,
+        storage_configuration: &StorageConfiguration,
+        in_task: InTask,
+    ) -> Result<mongodb::Client, anyhow::Error> {
+        use mongodb::options::{ClientOptions, Credential, ServerAddress, Tls, TlsOptions};
+
+        // Prevent users from probing our internal network ports by trying to
+        // connect to localhost, or another non-external IP.
+        resolve_address(
+            &self.host,
+            ENFORCE_EXTERNAL_ADDRESSES.get(storage_configuration.config_set()),
+        )
+        .await?;
+
+        let mut options = ClientOptions::default();
+        options.hosts = vec![ServerAddress::Tcp {
+            host: self.host.clone(),
+            port: Some(self.port),
+        }];
+        options.direct_connection = Some(true);
+        options.app_name = Some("materialize".into());
+        if self.tls {
+            options.tls = Some(Tls::Enabled(TlsOptions::default()));
+        }
+        if let Some(user) = &self.user {
+            let mut credential = Credential::default();
+            credential.username = Some(
+                user.get_string(in_task, secrets_reader)
+                    .await
+                    .context("username")?,
+            );
+            if let Some(password) = self.password {
+                credential.password = Some(
+                    secrets_reader
+                        .read_string_in_task_if(in_task, password)
+                        .await
+                        .context("password")?,
+                );
+            }
+            options.credential = Some(credential);
+        }
+
+        Ok(mongodb::Client::with_options(options)?)
+    }
+
+    /// Attempts to open a connection to the upstream MongoDB deployment.
+    async fn validate(
+        &self,
+        _id: CatalogItemId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(), anyhow::Error> {
+        let client = self
+            .connect(
+                &storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+                InTask::No,
+            )
+            .await?;
+        // The driver connects lazily, so issue a command to force a round
+        // trip (and authentication) to the server.
+        client
+            .database("admin")
+            .run_command(mongodb::bson::doc! { "ping": 1 })
+            .await?;
+        Ok(())
+    }
+}
+
+impl AlterCompatible for MongoDbConnection {
+    fn alter_compatible(&self, _id: GlobalId, _other: &Self) -> Result<(), AlterError> {
+        // Every element of the MongoDB connection is configurable. Sources
+        // verify that they are still reading from the same replica set.
+        Ok(())
+    }
+}
+
+impl RustType<ProtoMongoDbConnection> for MongoDbConnection {
+    fn into_proto(&self) -> ProtoMongoDbConnection {
+        ProtoMongoDbConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            tls: self.tls,
+        }
+    }
+
+    fn from_proto(proto: ProtoMongoDbConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MongoDbConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            tls: proto.tls,
+        })
+    }
+}
+
 /// A connection to an SSH tunnel.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct SshConnection {
@@ -120,6 +120,15 @@ pub trait ConnectionAccess:
         + Serialize
         + for<'a> Deserialize<'a>
         + AlterCompatible;
+    type MongoDb: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>
+        + AlterCompatible;
 }
 
 /// Expresses that the struct contains references to connections. Use a
@@ -136,6 +145,7 @@ impl ConnectionAccess for ReferencedConnection {
     type Csr = CatalogItemId;
     type MySql = CatalogItemId;
     type SqlServer = CatalogItemId;
+    type MongoDb = CatalogItemId;
 }
 
 /// Expresses that the struct contains an inlined definition of a connection.
@@ -150,4 +160,5 @@ impl ConnectionAccess for InlinedConnection {
     type Csr = super::CsrConnection;
     type MySql = super::MySqlConnection;
     type SqlServer = super::SqlServerConnectionDetails;
+    type MongoDb = super::MongoDbConnection;
 }
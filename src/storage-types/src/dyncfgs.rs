@@ -129,6 +129,104 @@ pub const KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS: Config<usize> = Config
         most this number of elements.",
 );
 
+// Iceberg
+
+/// The maximum amount of time an Iceberg sink waits before committing a
+/// snapshot that only advances its frontier.
+pub const ICEBERG_SINK_PROGRESS_COMMIT_INTERVAL: Config<Duration> = Config::new(
+    "iceberg_sink_progress_commit_interval",
+    Duration::from_secs(10),
+    "The minimum amount of time between Iceberg snapshots that only record the progress of the \
+    sink. Snapshots that contain data are always committed immediately.",
+);
+
+/// The size at which an Iceberg sink rolls over to a new data file.
+pub const ICEBERG_SINK_TARGET_FILE_SIZE: Config<usize> = Config::new(
+    "iceberg_sink_target_file_size",
+    128 * 1024 * 1024,
+    "The approximate size in bytes at which an Iceberg sink closes a Parquet file and starts a \
+    new one.",
+);
+
+/// The number of manifests of the same kind after which an Iceberg sink merges
+/// them into a single manifest.
+pub const ICEBERG_SINK_MANIFEST_MERGE_MIN_COUNT: Config<usize> = Config::new(
+    "iceberg_sink_manifest_merge_min_count",
+    100,
+    "The number of manifests of the same kind that an Iceberg table may accumulate before the \
+    sink merges them into a single manifest.",
+);
+
+// S3 sinks
+
+/// The minimum amount of time between writes of the progress file of an S3
+/// sink that only advance its frontier.
+pub const S3_SINK_PROGRESS_COMMIT_INTERVAL: Config<Duration> = Config::new(
+    "s3_sink_progress_commit_interval",
+    Duration::from_secs(10),
+    "The minimum amount of time between writes of the progress file of an S3 sink when no \
+    timestamps with data were closed. Progress is always recorded after writing a manifest.",
+);
+
+/// The ratio (defined as a percentage) of row-group size to max-file-size.
+pub const S3_SINK_PARQUET_ROW_GROUP_FILE_RATIO: Config<usize> = Config::new(
+    "s3_sink_parquet_row_group_file_ratio",
+    20,
+    "The ratio (defined as a percentage) of row-group size to max-file-size for Parquet files \
+    written by S3 sinks. Must be <= 100.",
+);
+
+/// The ratio (defined as a percentage) of arrow-builder size to row-group size.
+pub const S3_SINK_ARROW_BUILDER_BUFFER_RATIO: Config<usize> = Config::new(
+    "s3_sink_arrow_builder_buffer_ratio",
+    150,
+    "The ratio (defined as a percentage) of arrow-builder size to row-group size for Parquet \
+    files written by S3 sinks. Must be >= 100.",
+);
+
+/// The size of each part in a multipart upload to S3.
+pub const S3_SINK_MULTIPART_PART_SIZE_BYTES: Config<usize> = Config::new(
+    "s3_sink_multipart_part_size_bytes",
+    1024 * 1024 * 8,
+    "The size of each part in a multipart upload of an S3 sink.",
+);
+
+// Postgres sinks
+
+/// The minimum amount of time between commits of a Postgres sink that only
+/// advance its frontier.
+pub const POSTGRES_SINK_PROGRESS_COMMIT_INTERVAL: Config<Duration> = Config::new(
+    "postgres_sink_progress_commit_interval",
+    Duration::from_secs(10),
+    "The minimum amount of time between updates of the progress table of a Postgres sink when \
+    there are no changes to commit. Changes are always committed as soon as they are complete.",
+);
+
+// HTTP sinks
+
+/// The maximum number of changes sent in a single request of an HTTP sink.
+pub const HTTP_SINK_MAX_BATCH_SIZE: Config<usize> = Config::new(
+    "http_sink_max_batch_size",
+    10_000,
+    "The maximum number of changes sent in a single request of an HTTP sink. The changes at a \
+    timestamp are split across as many requests as necessary.",
+);
+
+/// The timeout of each request of an HTTP sink.
+pub const HTTP_SINK_REQUEST_TIMEOUT: Config<Duration> = Config::new(
+    "http_sink_request_timeout",
+    Duration::from_secs(30),
+    "The timeout of each request of an HTTP sink.",
+);
+
+/// The number of times an HTTP sink retries a failed request.
+pub const HTTP_SINK_MAX_RETRIES: Config<usize> = Config::new(
+    "http_sink_max_retries",
+    10,
+    "The number of times an HTTP sink retries a failed request, with exponential backoff, \
+    before restarting the sink.",
+);
+
 // MySQL
 
 /// Replication heartbeat interval requested from the MySQL server.
@@ -292,6 +390,17 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&CLUSTER_SHUTDOWN_GRACE_PERIOD)
         .add(&DELAY_SOURCES_PAST_REHYDRATION)
         .add(&ENFORCE_EXTERNAL_ADDRESSES)
+        .add(&ICEBERG_SINK_MANIFEST_MERGE_MIN_COUNT)
+        .add(&ICEBERG_SINK_PROGRESS_COMMIT_INTERVAL)
+        .add(&ICEBERG_SINK_TARGET_FILE_SIZE)
+        .add(&S3_SINK_PROGRESS_COMMIT_INTERVAL)
+        .add(&S3_SINK_PARQUET_ROW_GROUP_FILE_RATIO)
+        .add(&S3_SINK_ARROW_BUILDER_BUFFER_RATIO)
+        .add(&S3_SINK_MULTIPART_PART_SIZE_BYTES)
+        .add(&POSTGRES_SINK_PROGRESS_COMMIT_INTERVAL)
+        .add(&HTTP_SINK_MAX_BATCH_SIZE)
+        .add(&HTTP_SINK_REQUEST_TIMEOUT)
+        .add(&HTTP_SINK_MAX_RETRIES)
         .add(&KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS)
         .add(&KAFKA_CLIENT_ID_ENRICHMENT_RULES)
         .add(&KAFKA_DEFAULT_AWS_PRIVATELINK_ENDPOINT_IDENTIFICATION_ALGORITHM)
@@ -325,4 +434,10 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&crate::sources::sql_server::SNAPSHOT_MAX_LSN_WAIT)
         .add(&crate::sources::sql_server::SNAPSHOT_PROGRESS_REPORT_INTERVAL)
         .add(&crate::sources::sql_server::OFFSET_KNOWN_INTERVAL)
+        .add(&crate::sources::mongodb::SNAPSHOT_PROGRESS_REPORT_INTERVAL)
+        .add(&crate::sources::mongodb::CHANGE_STREAM_MAX_AWAIT_TIME)
+        .add(&crate::sources::mongodb::OFFSET_KNOWN_INTERVAL)
+        .add(&crate::sources::kinesis::POLL_INTERVAL)
+        .add(&crate::sources::kinesis::SHARD_REFRESH_INTERVAL)
+        .add(&crate::sources::kinesis::GET_RECORDS_LIMIT)
 }
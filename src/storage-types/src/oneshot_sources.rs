@@ -90,6 +90,11 @@ pub enum ContentSource {
         connection_id: CatalogItemId,
         uri: String,
     },
+    /// Path on the local filesystem of the cluster, possibly containing glob
+    /// patterns. Only intended for testing.
+    LocalFile {
+        path: String,
+    },
 }
 
 impl RustType<proto_oneshot_ingestion_request::Source> for ContentSource {
@@ -109,6 +114,11 @@ impl RustType<proto_oneshot_ingestion_request::Source> for ContentSource {
                 connection_id: Some(connection_id.into_proto()),
                 uri: uri.to_string(),
             }),
+            ContentSource::LocalFile { path } => {
+                proto_oneshot_ingestion_request::Source::LocalFile(ProtoLocalFileSource {
+                    path: path.clone(),
+                })
+            }
         }
     }
 
@@ -131,6 +141,9 @@ impl RustType<proto_oneshot_ingestion_request::Source> for ContentSource {
                     uri: source.uri,
                 })
             }
+            proto_oneshot_ingestion_request::Source::LocalFile(source) => {
+                Ok(ContentSource::LocalFile { path: source.path })
+            }
         }
     }
 }
@@ -139,6 +152,10 @@ impl RustType<proto_oneshot_ingestion_request::Source> for ContentSource {
 pub enum ContentFormat {
     Csv(CopyCsvFormatParams<'static>),
     Parquet,
+    /// Newline-delimited JSON objects, columns are mapped by field name.
+    Json,
+    /// Avro object container files, columns are mapped by field name.
+    Avro,
 }
 
 impl RustType<proto_oneshot_ingestion_request::Format> for ContentFormat {
@@ -152,6 +169,12 @@ impl RustType<proto_oneshot_ingestion_request::Format> for ContentFormat {
             ContentFormat::Parquet => proto_oneshot_ingestion_request::Format::Parquet(
                 ProtoParquetContentFormat::default(),
             ),
+            ContentFormat::Json => {
+                proto_oneshot_ingestion_request::Format::Json(ProtoJsonContentFormat::default())
+            }
+            ContentFormat::Avro => {
+                proto_oneshot_ingestion_request::Format::Avro(ProtoAvroContentFormat::default())
+            }
         }
     }
 
@@ -166,6 +189,12 @@ impl RustType<proto_oneshot_ingestion_request::Format> for ContentFormat {
             proto_oneshot_ingestion_request::Format::Parquet(ProtoParquetContentFormat {}) => {
                 Ok(ContentFormat::Parquet)
             }
+            proto_oneshot_ingestion_request::Format::Json(ProtoJsonContentFormat {}) => {
+                Ok(ContentFormat::Json)
+            }
+            proto_oneshot_ingestion_request::Format::Avro(ProtoAvroContentFormat {}) => {
+                Ok(ContentFormat::Avro)
+            }
         }
     }
 }
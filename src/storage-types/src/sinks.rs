@@ -26,15 +26,19 @@ use timely::PartialOrder;
 use timely::progress::frontier::Antichain;
 
 use crate::AlterCompatible;
+use crate::connections::aws::AwsConnectionReference;
 use crate::connections::inline::{
     ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
     ReferencedConnection,
 };
+use crate::connections::string_or_secret::StringOrSecret;
 use crate::connections::{ConnectionContext, KafkaConnection, KafkaTopicOptions};
 use crate::controller::{AlterError, CollectionMetadata};
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage_types.sinks.rs"));
 
+pub mod iceberg;
+pub mod s3;
 pub mod s3_oneshot_sink;
 
 /// A sink for updates to a relational collection.
@@ -241,6 +245,10 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSinkConnection<C>),
+    Iceberg(IcebergSinkConnection<C>),
+    S3(S3SinkConnection<C>),
+    Postgres(PostgresSinkConnection<C>),
+    Http(HttpSinkConnection),
 }
 
 impl<C: ConnectionAccess> StorageSinkConnection<C> {
@@ -260,6 +268,26 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
             (StorageSinkConnection::Kafka(s), StorageSinkConnection::Kafka(o)) => {
                 s.alter_compatible(id, o)?
             }
+            (StorageSinkConnection::Iceberg(s), StorageSinkConnection::Iceberg(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            (StorageSinkConnection::S3(s), StorageSinkConnection::S3(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            (StorageSinkConnection::Postgres(s), StorageSinkConnection::Postgres(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            (StorageSinkConnection::Http(s), StorageSinkConnection::Http(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            _ => {
+                tracing::warn!(
+                    "StorageSinkConnection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+                return Err(AlterError { id });
+            }
         }
 
         Ok(())
@@ -272,6 +300,10 @@ impl<R: ConnectionResolver> IntoInlineConnection<StorageSinkConnection, R>
     fn into_inline_connection(self, r: R) -> StorageSinkConnection {
         match self {
             Self::Kafka(conn) => StorageSinkConnection::Kafka(conn.into_inline_connection(r)),
+            Self::Iceberg(conn) => StorageSinkConnection::Iceberg(conn.into_inline_connection(r)),
+            Self::S3(conn) => StorageSinkConnection::S3(conn.into_inline_connection(r)),
+            Self::Postgres(conn) => StorageSinkConnection::Postgres(conn.into_inline_connection(r)),
+            Self::Http(conn) => StorageSinkConnection::Http(conn),
         }
     }
 }
@@ -283,6 +315,10 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 Self::Kafka(conn) => KafkaV2(conn.into_proto()),
+                Self::Iceberg(conn) => Iceberg(conn.into_proto()),
+                Self::S3(conn) => S3(conn.into_proto()),
+                Self::Postgres(conn) => Postgres(conn.into_proto()),
+                Self::Http(conn) => Http(conn.into_proto()),
             }),
         }
     }
@@ -295,6 +331,10 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
 
         Ok(match kind {
             KafkaV2(proto) => Self::Kafka(proto.into_rust()?),
+            Iceberg(proto) => Self::Iceberg(proto.into_rust()?),
+            S3(proto) => Self::S3(proto.into_rust()?),
+            Postgres(proto) => Self::Postgres(proto.into_rust()?),
+            Http(proto) => Self::Http(proto.into_rust()?),
         })
     }
 }
@@ -305,6 +345,12 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Iceberg(IcebergSinkConnection { aws_connection, .. }) => {
+                aws_connection.as_ref().map(|aws| aws.connection_id)
+            }
+            S3(S3SinkConnection { aws_connection, .. }) => Some(aws_connection.connection_id),
+            Postgres(PostgresSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Http(_) => None,
         }
     }
 
@@ -313,6 +359,10 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(_) => "kafka",
+            Iceberg(_) => "iceberg",
+            S3(_) => "s3",
+            Postgres(_) => "postgres",
+            Http(_) => "http",
         }
     }
 }
@@ -712,6 +762,19 @@ pub enum KafkaSinkFormatType<C: ConnectionAccess = InlinedConnection> {
         compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
         csr_connection: C::Csr,
     },
+    Protobuf {
+        /// The fully qualified name of the message that rows are encoded as.
+        message_name: String,
+        /// The encoded `FileDescriptorSet` that describes the message.
+        descriptors: Vec<u8>,
+        /// The `.proto` source of the schema. Only set if the schema is
+        /// published to `csr_connection`.
+        schema: Option<String>,
+        compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+        /// The schema registry that the schema is published to, if any.
+        /// Messages are framed in the Confluent wire format if set.
+        csr_connection: Option<C::Csr>,
+    },
     Json,
     Text,
     Bytes,
@@ -721,6 +784,7 @@ impl<C: ConnectionAccess> KafkaSinkFormatType<C> {
     pub fn get_format_name(&self) -> &str {
         match self {
             Self::Avro { .. } => "avro",
+            Self::Protobuf { .. } => "protobuf",
             Self::Json => "json",
             Self::Text => "text",
             Self::Bytes => "bytes",
@@ -739,6 +803,9 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                 (KafkaSinkFormatType::Avro { .. }, KafkaSinkFormatType::Avro { .. }) => {
                     "avro".into()
                 }
+                (KafkaSinkFormatType::Protobuf { .. }, KafkaSinkFormatType::Protobuf { .. }) => {
+                    "protobuf".into()
+                }
                 (KafkaSinkFormatType::Json, KafkaSinkFormatType::Json) => "json".into(),
                 (keyf, valuef) => format!(
                     "key-{}-value-{}",
@@ -782,6 +849,40 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     return Err(AlterError { id });
                 }
             }
+            (
+                KafkaSinkFormatType::Protobuf {
+                    message_name,
+                    descriptors,
+                    schema,
+                    compatibility_level: _,
+                    csr_connection,
+                },
+                KafkaSinkFormatType::Protobuf {
+                    message_name: other_message_name,
+                    descriptors: other_descriptors,
+                    schema: other_schema,
+                    compatibility_level: _,
+                    csr_connection: other_csr_connection,
+                },
+            ) => {
+                let csr_connection_compatible = match (csr_connection, other_csr_connection) {
+                    (Some(c), Some(o)) => c.alter_compatible(id, o).is_ok(),
+                    (c, o) => c.is_none() && o.is_none(),
+                };
+                if message_name != other_message_name
+                    || descriptors != other_descriptors
+                    || schema != other_schema
+                    || !csr_connection_compatible
+                {
+                    tracing::warn!(
+                        "KafkaSinkFormat::Protobuf incompatible at value_format:\nself:\n{:#?}\n\nother\n{:#?}",
+                        self,
+                        other
+                    );
+
+                    return Err(AlterError { id });
+                }
+            }
             (s, o) => {
                 if s != o {
                     tracing::warn!(
@@ -821,6 +922,40 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     return Err(AlterError { id });
                 }
             }
+            (
+                Some(KafkaSinkFormatType::Protobuf {
+                    message_name,
+                    descriptors,
+                    schema,
+                    compatibility_level: _,
+                    csr_connection,
+                }),
+                Some(KafkaSinkFormatType::Protobuf {
+                    message_name: other_message_name,
+                    descriptors: other_descriptors,
+                    schema: other_schema,
+                    compatibility_level: _,
+                    csr_connection: other_csr_connection,
+                }),
+            ) => {
+                let csr_connection_compatible = match (csr_connection, other_csr_connection) {
+                    (Some(c), Some(o)) => c.alter_compatible(id, o).is_ok(),
+                    (c, o) => c.is_none() && o.is_none(),
+                };
+                if message_name != other_message_name
+                    || descriptors != other_descriptors
+                    || schema != other_schema
+                    || !csr_connection_compatible
+                {
+                    tracing::warn!(
+                        "KafkaSinkFormat::Protobuf incompatible at key_format:\nself:\n{:#?}\n\nother\n{:#?}",
+                        self,
+                        other
+                    );
+
+                    return Err(AlterError { id });
+                }
+            }
             (s, o) => {
                 if s != o {
                     tracing::warn!(
@@ -862,6 +997,20 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormatType, R>
                 compatibility_level,
                 csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
             },
+            KafkaSinkFormatType::Protobuf {
+                message_name,
+                descriptors,
+                schema,
+                compatibility_level,
+                csr_connection,
+            } => KafkaSinkFormatType::Protobuf {
+                message_name,
+                descriptors,
+                schema,
+                compatibility_level,
+                csr_connection: csr_connection
+                    .map(|csr_connection| r.resolve_connection(csr_connection).unwrap_csr()),
+            },
             KafkaSinkFormatType::Json => KafkaSinkFormatType::Json,
             KafkaSinkFormatType::Text => KafkaSinkFormatType::Text,
             KafkaSinkFormatType::Bytes => KafkaSinkFormatType::Bytes,
@@ -883,6 +1032,19 @@ impl RustType<ProtoKafkaSinkFormatType> for KafkaSinkFormatType {
                     compatibility_level: csr_compat_level_to_proto(compatibility_level),
                     csr_connection: Some(csr_connection.into_proto()),
                 }),
+                Self::Protobuf {
+                    message_name,
+                    descriptors,
+                    schema,
+                    compatibility_level,
+                    csr_connection,
+                } => Type::Protobuf(proto_kafka_sink_format_type::ProtoKafkaSinkProtobufFormat {
+                    message_name: message_name.clone(),
+                    descriptors: descriptors.clone(),
+                    schema: schema.clone(),
+                    compatibility_level: csr_compat_level_to_proto(compatibility_level),
+                    csr_connection: csr_connection.into_proto(),
+                }),
                 Self::Json => Type::Json(()),
                 Self::Text => Type::Text(()),
                 Self::Bytes => Type::Bytes(()),
@@ -904,6 +1066,13 @@ impl RustType<ProtoKafkaSinkFormatType> for KafkaSinkFormatType {
                     .csr_connection
                     .into_rust_if_some("ProtoKafkaSinkFormatType::csr_connection")?,
             },
+            Type::Protobuf(proto) => Self::Protobuf {
+                message_name: proto.message_name,
+                descriptors: proto.descriptors,
+                schema: proto.schema,
+                compatibility_level: csr_compat_level_from_proto(proto.compatibility_level),
+                csr_connection: proto.csr_connection.into_rust()?,
+            },
             Type::Json(()) => Self::Json,
             Type::Text(()) => Self::Text,
             Type::Bytes(()) => Self::Bytes,
@@ -965,6 +1134,494 @@ fn csr_compat_level_from_proto(val: i32) -> Option<mz_ccsr::CompatibilityLevel>
     }
 }
 
+/// A sink that continuously commits the sinked relation to an Apache Iceberg
+/// table.
+///
+/// The table is stored in a warehouse following the Hadoop catalog layout, i.e.
+/// the table lives at `<warehouse>/<namespace>/<table>` and its current
+/// metadata version is tracked by the `metadata/version-hint.text` file.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IcebergSinkConnection<C: ConnectionAccess = InlinedConnection> {
+    /// The AWS connection used to access the warehouse, if the warehouse lives
+    /// in S3. Warehouses without a connection live on the local filesystem.
+    pub aws_connection: Option<AwsConnectionReference<C>>,
+    /// The URI of the warehouse, e.g. `s3://bucket/path` or `file:///path`.
+    pub warehouse: String,
+    /// The namespace of the table within the warehouse.
+    pub namespace: String,
+    /// The name of the table within the namespace.
+    pub table: String,
+    /// The user-specified key for the sink.
+    pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> IcebergSinkConnection<C> {
+    /// Returns the URI of the table's root directory.
+    pub fn table_location(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.warehouse.trim_end_matches('/'),
+            self.namespace,
+            self.table
+        )
+    }
+
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &IcebergSinkConnection<C>,
+    ) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+        let IcebergSinkConnection {
+            aws_connection,
+            warehouse,
+            namespace,
+            table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (
+                aws_connection.as_ref().map(|aws| aws.connection_id)
+                    == other.aws_connection.as_ref().map(|aws| aws.connection_id),
+                "aws_connection",
+            ),
+            (warehouse == &other.warehouse, "warehouse"),
+            (namespace == &other.namespace, "namespace"),
+            (table == &other.table, "table"),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "IcebergSinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<IcebergSinkConnection, R>
+    for IcebergSinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> IcebergSinkConnection {
+        let IcebergSinkConnection {
+            aws_connection,
+            warehouse,
+            namespace,
+            table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+        IcebergSinkConnection {
+            aws_connection: aws_connection.map(|aws| aws.into_inline_connection(&r)),
+            warehouse,
+            namespace,
+            table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoIcebergSinkConnection> for IcebergSinkConnection {
+    fn into_proto(&self) -> ProtoIcebergSinkConnection {
+        ProtoIcebergSinkConnection {
+            aws_connection: self.aws_connection.into_proto(),
+            warehouse: self.warehouse.clone(),
+            namespace: self.namespace.clone(),
+            table: self.table.clone(),
+            key_desc_and_indices: self.key_desc_and_indices.into_proto(),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoIcebergSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(IcebergSinkConnection {
+            aws_connection: proto.aws_connection.into_rust()?,
+            warehouse: proto.warehouse,
+            namespace: proto.namespace,
+            table: proto.table,
+            key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoIcebergSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// A sink that applies the changes to the sinked relation to a table in
+/// PostgreSQL.
+///
+/// Changes are applied as upserts keyed on the user-specified key of the sink.
+/// The frontier the table is complete up to is tracked in a progress table in
+/// the same schema, which is updated in the same transaction as the changes.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresSinkConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: CatalogItemId,
+    pub connection: C::Pg,
+    /// The schema of the target table and the progress table.
+    pub schema: String,
+    /// The name of the target table.
+    pub table: String,
+    /// The name of the table recording the progress of the sink.
+    pub progress_table: String,
+    /// The user-specified key for the sink.
+    pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> PostgresSinkConnection<C> {
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &PostgresSinkConnection<C>,
+    ) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+        let PostgresSinkConnection {
+            connection_id,
+            connection,
+            schema,
+            table,
+            progress_table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                connection.alter_compatible(id, &other.connection).is_ok(),
+                "connection",
+            ),
+            (schema == &other.schema, "schema"),
+            (table == &other.table, "table"),
+            (progress_table == &other.progress_table, "progress_table"),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "PostgresSinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<PostgresSinkConnection, R>
+    for PostgresSinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> PostgresSinkConnection {
+        let PostgresSinkConnection {
+            connection_id,
+            connection,
+            schema,
+            table,
+            progress_table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+        PostgresSinkConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_pg(),
+            schema,
+            table,
+            progress_table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoPostgresSinkConnection> for PostgresSinkConnection {
+    fn into_proto(&self) -> ProtoPostgresSinkConnection {
+        ProtoPostgresSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            progress_table: self.progress_table.clone(),
+            key_desc_and_indices: self.key_desc_and_indices.into_proto(),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPostgresSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(PostgresSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection")?,
+            schema: proto.schema,
+            table: proto.table,
+            progress_table: proto.progress_table,
+            key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoPostgresSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// A sink that delivers the changes at each closed timestamp of the sinked
+/// relation to an HTTP endpoint.
+///
+/// Changes are delivered at least once. Every request carries an idempotency
+/// key derived from the timestamp of its changes, so that the endpoint can
+/// discard requests it has already processed.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HttpSinkConnection {
+    /// The URL to `POST` changes to.
+    pub url: String,
+    /// The value of the `Authorization` header of each request, if any.
+    pub authorization: Option<StringOrSecret>,
+    /// The user-specified key for the sink.
+    pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+}
+
+impl HttpSinkConnection {
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &HttpSinkConnection,
+    ) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+        tracing::warn!(
+            "HttpSinkConnection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
+            self,
+            other
+        );
+        Err(AlterError { id })
+    }
+}
+
+impl RustType<ProtoHttpSinkConnection> for HttpSinkConnection {
+    fn into_proto(&self) -> ProtoHttpSinkConnection {
+        ProtoHttpSinkConnection {
+            url: self.url.clone(),
+            authorization: self.authorization.into_proto(),
+            key_desc_and_indices: self.key_desc_and_indices.into_proto(),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoHttpSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(HttpSinkConnection {
+            url: proto.url,
+            authorization: proto.authorization.into_rust()?,
+            key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoHttpSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// A sink that continuously writes the changes to the sinked relation to
+/// files in S3.
+///
+/// See [`s3`] for the layout of the files written by the sink.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct S3SinkConnection<C: ConnectionAccess = InlinedConnection> {
+    /// The AWS connection used to access the bucket.
+    pub aws_connection: AwsConnectionReference<C>,
+    /// The `s3://bucket/prefix` URI under which all files are written.
+    pub uri: String,
+    /// The encoding of the data files.
+    pub format: S3SinkFormat,
+    /// The max size of each data file.
+    pub max_file_size: u64,
+    /// The user-specified key for the sink.
+    pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> S3SinkConnection<C> {
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &S3SinkConnection<C>,
+    ) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+        let S3SinkConnection {
+            aws_connection,
+            uri,
+            format,
+            max_file_size,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (
+                aws_connection.connection_id == other.aws_connection.connection_id,
+                "aws_connection",
+            ),
+            (uri == &other.uri, "uri"),
+            (format == &other.format, "format"),
+            (max_file_size == &other.max_file_size, "max_file_size"),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "S3SinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<S3SinkConnection, R>
+    for S3SinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> S3SinkConnection {
+        let S3SinkConnection {
+            aws_connection,
+            uri,
+            format,
+            max_file_size,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+        S3SinkConnection {
+            aws_connection: aws_connection.into_inline_connection(&r),
+            uri,
+            format,
+            max_file_size,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoS3SinkConnection> for S3SinkConnection {
+    fn into_proto(&self) -> ProtoS3SinkConnection {
+        ProtoS3SinkConnection {
+            aws_connection: Some(self.aws_connection.into_proto()),
+            uri: self.uri.clone(),
+            format: Some(self.format.into_proto()),
+            max_file_size: self.max_file_size,
+            key_desc_and_indices: self.key_desc_and_indices.into_proto(),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(S3SinkConnection {
+            aws_connection: proto
+                .aws_connection
+                .into_rust_if_some("ProtoS3SinkConnection::aws_connection")?,
+            uri: proto.uri,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoS3SinkConnection::format")?,
+            max_file_size: proto.max_file_size,
+            key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoS3SinkConnection::value_desc")?,
+        })
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum S3SinkFormat {
     /// Encoded using the PG `COPY` protocol, with one of its supported formats.
@@ -973,6 +1630,19 @@ pub enum S3SinkFormat {
     Parquet,
 }
 
+impl S3SinkFormat {
+    pub fn get_format_name(&self) -> &str {
+        match self {
+            Self::PgCopy(CopyFormatParams::Csv(_)) => "csv",
+            Self::PgCopy(CopyFormatParams::Text(_)) => "text",
+            Self::PgCopy(CopyFormatParams::Binary) => "binary",
+            Self::PgCopy(CopyFormatParams::Parquet) | Self::Parquet => "parquet",
+            Self::PgCopy(CopyFormatParams::Json) => "json",
+            Self::PgCopy(CopyFormatParams::Avro) => "avro",
+        }
+    }
+}
+
 impl RustType<ProtoS3SinkFormat> for S3SinkFormat {
     fn into_proto(&self) -> ProtoS3SinkFormat {
         use proto_s3_sink_format::Kind;
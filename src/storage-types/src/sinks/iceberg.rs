@@ -0,0 +1,457 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types describing the on-disk format of Apache Iceberg tables written by
+//! Iceberg sinks.
+//!
+//! Only the subset of the [Iceberg table spec] that the sink produces is
+//! modeled: format version 2, unpartitioned and unsorted tables, with a single
+//! schema. Tables are laid out following the Hadoop catalog convention, where
+//! the current metadata version is recorded in `metadata/version-hint.text`.
+//!
+//! [Iceberg table spec]: https://iceberg.apache.org/spec/
+
+use std::collections::BTreeMap;
+
+use anyhow::bail;
+use mz_repr::{GlobalId, RelationDesc, ScalarType};
+use serde::{Deserialize, Serialize};
+
+/// The table property recording the ID of the sink that owns the table. Sinks
+/// refuse to write to tables owned by another sink.
+pub const SINK_ID_PROPERTY: &str = "materialize.sink-id";
+
+/// The snapshot summary key recording the frontier of the sinked collection
+/// that the snapshot is complete up to.
+pub const FRONTIER_SUMMARY_KEY: &str = "materialize.frontier";
+
+/// The name of the file that points at the current metadata version.
+pub const VERSION_HINT_FILE: &str = "version-hint.text";
+
+/// The Iceberg format version written by the sink.
+pub const FORMAT_VERSION: u8 = 2;
+
+/// Returns the path, relative to the table location, of the metadata file for
+/// the given version.
+pub fn metadata_file_path(version: u64) -> String {
+    format!("metadata/v{version}.metadata.json")
+}
+
+/// Returns the Iceberg primitive type that columns of type `scalar_type` are
+/// written as, or an error if the type is not supported.
+///
+/// The mapping mirrors the Arrow mapping used when writing Parquet files, so
+/// that the physical Parquet types agree with the declared Iceberg types.
+pub fn iceberg_type(scalar_type: &ScalarType) -> Result<String, anyhow::Error> {
+    let ty = match scalar_type {
+        ScalarType::Bool => "boolean".into(),
+        ScalarType::Int16 | ScalarType::Int32 => "int".into(),
+        ScalarType::Int64 => "long".into(),
+        ScalarType::Float32 => "float".into(),
+        ScalarType::Float64 => "double".into(),
+        // Matches the Decimal128 representation used by the Arrow encoder,
+        // which defaults to a scale of 10 when the column has no max scale.
+        ScalarType::Numeric { max_scale } => {
+            let scale = max_scale.map_or(10, |s| s.into_u8());
+            if scale > 38 {
+                bail!("numeric scale {scale} exceeds the maximum Iceberg decimal scale of 38");
+            }
+            format!("decimal(38, {scale})")
+        }
+        ScalarType::Date => "date".into(),
+        ScalarType::Time => "time".into(),
+        ScalarType::Timestamp { .. } => "timestamp".into(),
+        ScalarType::TimestampTz { .. } => "timestamptz".into(),
+        ScalarType::String
+        | ScalarType::Char { .. }
+        | ScalarType::VarChar { .. }
+        | ScalarType::Jsonb => "string".into(),
+        ScalarType::Bytes | ScalarType::HllSketch | ScalarType::QuantileSketch => "binary".into(),
+        ScalarType::Uuid => "uuid".into(),
+        other => bail!("type {other:?} cannot be written to Iceberg"),
+    };
+    Ok(ty)
+}
+
+/// Validates that every column of `desc` can be written to an Iceberg table.
+pub fn validate_desc(desc: &RelationDesc) -> Result<(), anyhow::Error> {
+    let mut errs = vec![];
+    for (name, typ) in desc.iter() {
+        if let Err(e) = iceberg_type(&typ.scalar_type) {
+            errs.push(format!("{name}: {e}"));
+        }
+    }
+    if !errs.is_empty() {
+        bail!(
+            "cannot write the following columns to Iceberg: {}",
+            errs.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// A column of an Iceberg schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NestedField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
+/// An Iceberg table schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Schema {
+    /// Always `"struct"`.
+    #[serde(rename = "type")]
+    pub schema_type: String,
+    pub schema_id: i32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub identifier_field_ids: Vec<i32>,
+    pub fields: Vec<NestedField>,
+}
+
+impl Schema {
+    /// Builds the schema for a sink writing `desc`, keyed by `key_indices`.
+    ///
+    /// Field IDs are assigned in column order starting at 1. The key columns
+    /// are recorded as the schema's identifier fields when they are all
+    /// non-nullable, as required by the spec.
+    pub fn for_desc(desc: &RelationDesc, key_indices: &[usize]) -> Result<Self, anyhow::Error> {
+        let mut fields = Vec::with_capacity(desc.arity());
+        let mut seen = BTreeMap::new();
+        for (i, (name, typ)) in desc.iter().enumerate() {
+            if let Some(prev) = seen.insert(name.as_str(), i) {
+                bail!(
+                    "column name {} appears at positions {} and {}; Iceberg requires unique column names",
+                    name,
+                    prev + 1,
+                    i + 1
+                );
+            }
+            fields.push(NestedField {
+                id: field_id(i),
+                name: name.to_string(),
+                required: !typ.nullable,
+                field_type: iceberg_type(&typ.scalar_type)?,
+            });
+        }
+        let identifier_field_ids = if key_indices.iter().all(|i| fields[*i].required) {
+            key_indices.iter().map(|i| field_id(*i)).collect()
+        } else {
+            vec![]
+        };
+        Ok(Schema {
+            schema_type: "struct".into(),
+            schema_id: 0,
+            identifier_field_ids,
+            fields,
+        })
+    }
+
+    /// Returns the largest field ID in use by the schema.
+    pub fn last_column_id(&self) -> i32 {
+        self.fields.iter().map(|f| f.id).max().unwrap_or(0)
+    }
+
+    /// Reports whether `self` and `other` describe the same columns, ignoring
+    /// schema IDs.
+    pub fn same_columns(&self, other: &Schema) -> bool {
+        self.fields == other.fields
+    }
+}
+
+/// Returns the Iceberg field ID of the column at `index`.
+pub fn field_id(index: usize) -> i32 {
+    i32::try_from(index + 1).expect("relation arity fits in i32")
+}
+
+/// A partition spec. Sinks always write unpartitioned tables.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionSpec {
+    pub spec_id: i32,
+    pub fields: Vec<serde_json::Value>,
+}
+
+/// A sort order. Sinks always write unsorted tables.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SortOrder {
+    pub order_id: i32,
+    pub fields: Vec<serde_json::Value>,
+}
+
+/// A snapshot of the table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Snapshot {
+    pub snapshot_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_snapshot_id: Option<i64>,
+    pub sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub manifest_list: String,
+    pub summary: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<i32>,
+}
+
+impl Snapshot {
+    /// Returns the sinked collection's frontier recorded in the snapshot, if
+    /// the snapshot was written by a sink.
+    pub fn frontier(&self) -> Option<Option<u64>> {
+        let value = self.summary.get(FRONTIER_SUMMARY_KEY)?;
+        if value.is_empty() {
+            Some(None)
+        } else {
+            value.parse().ok().map(Some)
+        }
+    }
+}
+
+/// An entry of the snapshot log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotLogEntry {
+    pub snapshot_id: i64,
+    pub timestamp_ms: i64,
+}
+
+/// An entry of the metadata log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MetadataLogEntry {
+    pub metadata_file: String,
+    pub timestamp_ms: i64,
+}
+
+/// A named reference to a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotRef {
+    pub snapshot_id: i64,
+    #[serde(rename = "type")]
+    pub ref_type: String,
+}
+
+/// The contents of a table metadata file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadata {
+    pub format_version: u8,
+    pub table_uuid: String,
+    pub location: String,
+    pub last_sequence_number: i64,
+    pub last_updated_ms: i64,
+    pub last_column_id: i32,
+    pub schemas: Vec<Schema>,
+    pub current_schema_id: i32,
+    pub partition_specs: Vec<PartitionSpec>,
+    pub default_spec_id: i32,
+    pub last_partition_id: i32,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub snapshot_log: Vec<SnapshotLogEntry>,
+    #[serde(default)]
+    pub metadata_log: Vec<MetadataLogEntry>,
+    pub sort_orders: Vec<SortOrder>,
+    pub default_sort_order_id: i32,
+    #[serde(default)]
+    pub refs: BTreeMap<String, SnapshotRef>,
+}
+
+impl TableMetadata {
+    /// Creates the metadata for a new, empty table owned by `sink_id`.
+    pub fn new(
+        sink_id: GlobalId,
+        location: String,
+        table_uuid: String,
+        schema: Schema,
+        now_ms: i64,
+    ) -> Self {
+        TableMetadata {
+            format_version: FORMAT_VERSION,
+            table_uuid,
+            location,
+            last_sequence_number: 0,
+            last_updated_ms: now_ms,
+            last_column_id: schema.last_column_id(),
+            current_schema_id: schema.schema_id,
+            schemas: vec![schema],
+            partition_specs: vec![PartitionSpec {
+                spec_id: 0,
+                fields: vec![],
+            }],
+            default_spec_id: 0,
+            // Per the spec, the last partition ID of a table without
+            // partition fields is 999.
+            last_partition_id: 999,
+            properties: BTreeMap::from([(SINK_ID_PROPERTY.into(), sink_id.to_string())]),
+            current_snapshot_id: None,
+            snapshots: vec![],
+            snapshot_log: vec![],
+            metadata_log: vec![],
+            sort_orders: vec![SortOrder {
+                order_id: 0,
+                fields: vec![],
+            }],
+            default_sort_order_id: 0,
+            refs: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the table's current schema.
+    pub fn current_schema(&self) -> Option<&Schema> {
+        self.schemas
+            .iter()
+            .find(|s| s.schema_id == self.current_schema_id)
+    }
+
+    /// Returns the table's current snapshot, if any.
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        let id = self.current_snapshot_id?;
+        self.snapshots.iter().find(|s| s.snapshot_id == id)
+    }
+
+    /// Returns the ID of the sink that owns the table, if any.
+    pub fn owning_sink(&self) -> Option<&str> {
+        self.properties.get(SINK_ID_PROPERTY).map(|s| s.as_str())
+    }
+
+    /// Adds `snapshot` to the table and makes it the current snapshot.
+    ///
+    /// Only the most recent `max_snapshots` snapshots are retained in the
+    /// metadata. Files referenced only by expired snapshots are not removed.
+    pub fn push_snapshot(&mut self, snapshot: Snapshot, max_snapshots: usize) {
+        self.last_sequence_number = snapshot.sequence_number;
+        self.last_updated_ms = snapshot.timestamp_ms;
+        self.current_snapshot_id = Some(snapshot.snapshot_id);
+        self.refs.insert(
+            "main".into(),
+            SnapshotRef {
+                snapshot_id: snapshot.snapshot_id,
+                ref_type: "branch".into(),
+            },
+        );
+        self.snapshot_log.push(SnapshotLogEntry {
+            snapshot_id: snapshot.snapshot_id,
+            timestamp_ms: snapshot.timestamp_ms,
+        });
+        self.snapshots.push(snapshot);
+        if self.snapshots.len() > max_snapshots {
+            let excess = self.snapshots.len() - max_snapshots;
+            self.snapshots.drain(..excess);
+        }
+        if self.snapshot_log.len() > max_snapshots {
+            let excess = self.snapshot_log.len() - max_snapshots;
+            self.snapshot_log.drain(..excess);
+        }
+    }
+
+    /// Records that `metadata_file` was the previous metadata file of the
+    /// table, retaining at most `max_entries` entries.
+    pub fn push_metadata_log(
+        &mut self,
+        metadata_file: String,
+        timestamp_ms: i64,
+        max_entries: usize,
+    ) {
+        self.metadata_log.push(MetadataLogEntry {
+            metadata_file,
+            timestamp_ms,
+        });
+        if self.metadata_log.len() > max_entries {
+            let excess = self.metadata_log.len() - max_entries;
+            self.metadata_log.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{RelationDesc, ScalarType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn schema_for_desc() {
+        let desc = RelationDesc::builder()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true))
+            .finish();
+        let schema = Schema::for_desc(&desc, &[0]).unwrap();
+        assert_eq!(schema.identifier_field_ids, vec![1]);
+        assert_eq!(schema.last_column_id(), 2);
+        assert_eq!(schema.fields[0].field_type, "long");
+        assert!(schema.fields[0].required);
+        assert!(!schema.fields[1].required);
+
+        // Nullable key columns cannot be identifier fields.
+        let schema = Schema::for_desc(&desc, &[0, 1]).unwrap();
+        assert!(schema.identifier_field_ids.is_empty());
+
+        let desc = RelationDesc::builder()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("a", ScalarType::Int32.nullable(false))
+            .finish();
+        assert!(Schema::for_desc(&desc, &[]).is_err());
+    }
+
+    #[mz_ore::test]
+    fn metadata_roundtrip() {
+        let desc = RelationDesc::builder()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .finish();
+        let schema = Schema::for_desc(&desc, &[0]).unwrap();
+        let mut metadata = TableMetadata::new(
+            GlobalId::User(1),
+            "file:///tmp/wh/db/t".into(),
+            "00000000-0000-0000-0000-000000000000".into(),
+            schema,
+            0,
+        );
+        for i in 0..5 {
+            metadata.push_snapshot(
+                Snapshot {
+                    snapshot_id: i,
+                    parent_snapshot_id: i.checked_sub(1),
+                    sequence_number: i + 1,
+                    timestamp_ms: i,
+                    manifest_list: format!("snap-{i}.avro"),
+                    summary: BTreeMap::from([
+                        ("operation".into(), "append".into()),
+                        (FRONTIER_SUMMARY_KEY.into(), i.to_string()),
+                    ]),
+                    schema_id: Some(0),
+                },
+                3,
+            );
+        }
+        assert_eq!(metadata.snapshots.len(), 3);
+        assert_eq!(
+            metadata.current_snapshot().unwrap().frontier(),
+            Some(Some(4))
+        );
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(json.contains("\"format-version\":2"));
+        let parsed: TableMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, metadata);
+        assert_eq!(parsed.owning_sink(), Some("u1"));
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The layout of the files written by a continuous S3 sink.
+//!
+//! All files live under the `s3://bucket/prefix` URI of the sink:
+//!
+//! ```text
+//! <prefix>/data/<timestamp>/part-<n>.<ext>   changes at <timestamp>
+//! <prefix>/manifests/<timestamp>.json        the data files of <timestamp>
+//! <prefix>/progress.json                     the frontier the sink is complete up to
+//! ```
+//!
+//! Every data file contains the `mz_timestamp` and `mz_diff` of each change,
+//! followed by the columns of the sinked relation, just like the output of
+//! `SUBSCRIBE`. Data files are only written for timestamps at which the sinked
+//! relation changed, and a timestamp is considered closed once its manifest
+//! has been written. Readers must only consume the data files listed in a
+//! manifest: a sink that restarts may rewrite the files of timestamps beyond
+//! its last recorded progress, possibly as a different number of files.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use http::Uri;
+use mz_repr::{ColumnName, GlobalId, RelationDesc, ScalarType};
+use serde::{Deserialize, Serialize};
+
+/// The name of the column holding the timestamp of a change.
+pub const TIMESTAMP_COLUMN: &str = "mz_timestamp";
+/// The name of the column holding the diff of a change.
+pub const DIFF_COLUMN: &str = "mz_diff";
+
+/// Returns the description of the rows in the data files of a sink of
+/// `value_desc`.
+pub fn file_desc(value_desc: &RelationDesc) -> Result<RelationDesc, anyhow::Error> {
+    for name in [TIMESTAMP_COLUMN, DIFF_COLUMN] {
+        if value_desc.iter_names().any(|n| n.as_str() == name) {
+            bail!("column name {name} is reserved by S3 sinks");
+        }
+    }
+    let metadata = RelationDesc::builder()
+        .with_column(
+            ColumnName::from(TIMESTAMP_COLUMN),
+            ScalarType::MzTimestamp.nullable(false),
+        )
+        .with_column(
+            ColumnName::from(DIFF_COLUMN),
+            ScalarType::Int64.nullable(false),
+        )
+        .finish();
+    Ok(metadata.concat(value_desc.clone()))
+}
+
+/// Provides the object keys of the files written by a sink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3SinkLayout {
+    pub bucket: String,
+    /// The key prefix of all files, without a trailing slash. May be empty.
+    pub prefix: String,
+}
+
+impl S3SinkLayout {
+    /// Parses the `s3://bucket/prefix` URI of a sink.
+    pub fn new(uri: &str) -> Result<Self, anyhow::Error> {
+        let parsed = Uri::from_str(uri).map_err(|e| anyhow!("invalid S3 URI {uri}: {e}"))?;
+        if parsed.scheme_str() != Some("s3") {
+            bail!("expected an s3:// URI, got {uri}");
+        }
+        let bucket = match parsed.host() {
+            Some(bucket) if !bucket.is_empty() => bucket.to_string(),
+            _ => bail!("S3 URI {uri} does not specify a bucket"),
+        };
+        let prefix = parsed.path().trim_matches('/').to_string();
+        Ok(S3SinkLayout { bucket, prefix })
+    }
+
+    fn key(&self, path: &str) -> String {
+        if self.prefix.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", self.prefix, path)
+        }
+    }
+
+    /// The key of the `index`th data file of `timestamp`.
+    pub fn data_key(&self, timestamp: u64, index: usize, extension: &str) -> String {
+        self.key(&format!("data/{timestamp}/part-{index:04}.{extension}"))
+    }
+
+    /// The key of the manifest of `timestamp`.
+    ///
+    /// Timestamps are zero-padded so that listing the manifests returns them
+    /// in timestamp order.
+    pub fn manifest_key(&self, timestamp: u64) -> String {
+        self.key(&format!("manifests/{timestamp:020}.json"))
+    }
+
+    /// The key of the file recording the progress of the sink.
+    pub fn progress_key(&self) -> String {
+        self.key("progress.json")
+    }
+}
+
+/// A data file listed in a [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub key: String,
+    /// The number of changes in the file.
+    pub records: u64,
+    /// The size of the file in bytes.
+    pub bytes: u64,
+}
+
+/// The data files holding all changes at a timestamp.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub sink_id: String,
+    pub timestamp: u64,
+    /// The format of the data files, e.g. `parquet` or `csv`.
+    pub format: String,
+    pub files: Vec<ManifestFile>,
+}
+
+/// The frontier that a sink has written all manifests up to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+    pub sink_id: String,
+    /// The frontier, or `None` if the sinked relation is closed and the sink
+    /// has written all of it.
+    pub frontier: Option<u64>,
+}
+
+impl Progress {
+    /// Checks that the progress was recorded by `sink_id`.
+    pub fn check_owner(&self, sink_id: GlobalId) -> Result<(), anyhow::Error> {
+        if self.sink_id != sink_id.to_string() {
+            bail!(
+                "S3 path is already in use by sink {}, refusing to write to it from sink {}",
+                self.sink_id,
+                sink_id
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn layout() {
+        let layout = S3SinkLayout::new("s3://bucket/a/b/").unwrap();
+        assert_eq!(layout.bucket, "bucket");
+        assert_eq!(layout.data_key(7, 1, "csv"), "a/b/data/7/part-0001.csv");
+        assert_eq!(
+            layout.manifest_key(7),
+            "a/b/manifests/00000000000000000007.json"
+        );
+        assert_eq!(layout.progress_key(), "a/b/progress.json");
+
+        let layout = S3SinkLayout::new("s3://bucket").unwrap();
+        assert_eq!(layout.progress_key(), "progress.json");
+
+        assert!(S3SinkLayout::new("file:///tmp").is_err());
+        assert!(S3SinkLayout::new("s3:///path").is_err());
+    }
+
+    #[mz_ore::test]
+    fn reserved_columns() {
+        let desc = RelationDesc::builder()
+            .with_column("a", ScalarType::String.nullable(true))
+            .finish();
+        let file = file_desc(&desc).unwrap();
+        let names: Vec<_> = file.iter_names().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["mz_timestamp", "mz_diff", "a"]);
+
+        let desc = RelationDesc::builder()
+            .with_column("mz_diff", ScalarType::Int64.nullable(true))
+            .finish();
+        assert!(file_desc(&desc).is_err());
+    }
+}
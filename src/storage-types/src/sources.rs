@@ -30,8 +30,8 @@ use mz_persist_types::Codec;
 use mz_persist_types::arrow::ArrayOrd;
 use mz_persist_types::columnar::{ColumnDecoder, ColumnEncoder, Schema};
 use mz_persist_types::stats::{
-    ColumnNullStats, ColumnStatKinds, ColumnarStats, ColumnarStatsBuilder, PrimitiveStats,
-    StructStats,
+    BloomFilter, BloomFilterColumns, ColumnNullStats, ColumnStatKinds, ColumnarStats,
+    ColumnarStatsBuilder, PrimitiveStats, StructStats,
 };
 use mz_proto::{IntoRustIfSome, ProtoMapEntry, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{
@@ -56,20 +56,26 @@ use crate::connections::inline::{
 use crate::controller::{AlterError, CollectionMetadata};
 use crate::errors::{DataflowError, ProtoDataflowError};
 use crate::instances::StorageInstanceId;
+use crate::sources::kinesis::KinesisSourceExportDetails;
+use crate::sources::mongodb::MongoDbSourceExportDetails;
 use crate::sources::proto_ingestion_description::{ProtoSourceExport, ProtoSourceImport};
 use crate::sources::sql_server::SqlServerSourceExportDetails;
 
 pub mod encoding;
 pub mod envelope;
 pub mod kafka;
+pub mod kinesis;
 pub mod load_generator;
+pub mod mongodb;
 pub mod mysql;
 pub mod postgres;
 pub mod sql_server;
 
 pub use crate::sources::envelope::SourceEnvelope;
 pub use crate::sources::kafka::KafkaSourceConnection;
+pub use crate::sources::kinesis::KinesisSourceConnection;
 pub use crate::sources::load_generator::LoadGeneratorSourceConnection;
+pub use crate::sources::mongodb::MongoDbSourceConnection;
 pub use crate::sources::mysql::{MySqlSourceConnection, MySqlSourceExportDetails};
 pub use crate::sources::postgres::{PostgresSourceConnection, PostgresSourceExportDetails};
 pub use crate::sources::sql_server::{SqlServerSource, SqlServerSourceExtras};
@@ -778,10 +784,14 @@ impl<C: ConnectionAccess> SourceExportDataConfig<C> {
                     GenericSourceConnection::MySql(_) => false,
                     // SQL Server can produce retractions (deletes).
                     GenericSourceConnection::SqlServer(_) => false,
+                    // MongoDB can produce retractions (deletes).
+                    GenericSourceConnection::MongoDb(_) => false,
                     // Whether or not a Loadgen source can produce retractions varies.
                     GenericSourceConnection::LoadGenerator(g) => g.load_generator.is_monotonic(),
                     // Kafka exports with `None` envelope are append-only.
                     GenericSourceConnection::Kafka(_) => true,
+                    // Kinesis exports with `None` envelope are append-only.
+                    GenericSourceConnection::Kinesis(_) => true,
                 }
             }
         }
@@ -912,9 +922,11 @@ impl SourceDesc<InlinedConnection> {
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum GenericSourceConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSourceConnection<C>),
+    Kinesis(KinesisSourceConnection<C>),
     Postgres(PostgresSourceConnection<C>),
     MySql(MySqlSourceConnection<C>),
     SqlServer(SqlServerSource<C>),
+    MongoDb(MongoDbSourceConnection<C>),
     LoadGenerator(LoadGeneratorSourceConnection),
 }
 
@@ -924,6 +936,12 @@ impl<C: ConnectionAccess> From<KafkaSourceConnection<C>> for GenericSourceConnec
     }
 }
 
+impl<C: ConnectionAccess> From<KinesisSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: KinesisSourceConnection<C>) -> Self {
+        Self::Kinesis(conn)
+    }
+}
+
 impl<C: ConnectionAccess> From<PostgresSourceConnection<C>> for GenericSourceConnection<C> {
     fn from(conn: PostgresSourceConnection<C>) -> Self {
         Self::Postgres(conn)
@@ -942,6 +960,12 @@ impl<C: ConnectionAccess> From<SqlServerSource<C>> for GenericSourceConnection<C
     }
 }
 
+impl<C: ConnectionAccess> From<MongoDbSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: MongoDbSourceConnection<C>) -> Self {
+        Self::MongoDb(conn)
+    }
+}
+
 impl<C: ConnectionAccess> From<LoadGeneratorSourceConnection> for GenericSourceConnection<C> {
     fn from(conn: LoadGeneratorSourceConnection) -> Self {
         Self::LoadGenerator(conn)
@@ -956,6 +980,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<GenericSourceConnection, R>
             GenericSourceConnection::Kafka(kafka) => {
                 GenericSourceConnection::Kafka(kafka.into_inline_connection(r))
             }
+            GenericSourceConnection::Kinesis(kinesis) => {
+                GenericSourceConnection::Kinesis(kinesis.into_inline_connection(r))
+            }
             GenericSourceConnection::Postgres(pg) => {
                 GenericSourceConnection::Postgres(pg.into_inline_connection(r))
             }
@@ -965,6 +992,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<GenericSourceConnection, R>
             GenericSourceConnection::SqlServer(sql_server) => {
                 GenericSourceConnection::SqlServer(sql_server.into_inline_connection(r))
             }
+            GenericSourceConnection::MongoDb(mongodb) => {
+                GenericSourceConnection::MongoDb(mongodb.into_inline_connection(r))
+            }
             GenericSourceConnection::LoadGenerator(lg) => {
                 GenericSourceConnection::LoadGenerator(lg)
             }
@@ -976,9 +1006,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn name(&self) -> &'static str {
         match self {
             Self::Kafka(conn) => conn.name(),
+            Self::Kinesis(conn) => conn.name(),
             Self::Postgres(conn) => conn.name(),
             Self::MySql(conn) => conn.name(),
             Self::SqlServer(conn) => conn.name(),
+            Self::MongoDb(conn) => conn.name(),
             Self::LoadGenerator(conn) => conn.name(),
         }
     }
@@ -986,9 +1018,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn external_reference(&self) -> Option<&str> {
         match self {
             Self::Kafka(conn) => conn.external_reference(),
+            Self::Kinesis(conn) => conn.external_reference(),
             Self::Postgres(conn) => conn.external_reference(),
             Self::MySql(conn) => conn.external_reference(),
             Self::SqlServer(conn) => conn.external_reference(),
+            Self::MongoDb(conn) => conn.external_reference(),
             Self::LoadGenerator(conn) => conn.external_reference(),
         }
     }
@@ -996,9 +1030,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn default_key_desc(&self) -> RelationDesc {
         match self {
             Self::Kafka(conn) => conn.default_key_desc(),
+            Self::Kinesis(conn) => conn.default_key_desc(),
             Self::Postgres(conn) => conn.default_key_desc(),
             Self::MySql(conn) => conn.default_key_desc(),
             Self::SqlServer(conn) => conn.default_key_desc(),
+            Self::MongoDb(conn) => conn.default_key_desc(),
             Self::LoadGenerator(conn) => conn.default_key_desc(),
         }
     }
@@ -1006,9 +1042,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn default_value_desc(&self) -> RelationDesc {
         match self {
             Self::Kafka(conn) => conn.default_value_desc(),
+            Self::Kinesis(conn) => conn.default_value_desc(),
             Self::Postgres(conn) => conn.default_value_desc(),
             Self::MySql(conn) => conn.default_value_desc(),
             Self::SqlServer(conn) => conn.default_value_desc(),
+            Self::MongoDb(conn) => conn.default_value_desc(),
             Self::LoadGenerator(conn) => conn.default_value_desc(),
         }
     }
@@ -1016,9 +1054,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn timestamp_desc(&self) -> RelationDesc {
         match self {
             Self::Kafka(conn) => conn.timestamp_desc(),
+            Self::Kinesis(conn) => conn.timestamp_desc(),
             Self::Postgres(conn) => conn.timestamp_desc(),
             Self::MySql(conn) => conn.timestamp_desc(),
             Self::SqlServer(conn) => conn.timestamp_desc(),
+            Self::MongoDb(conn) => conn.timestamp_desc(),
             Self::LoadGenerator(conn) => conn.timestamp_desc(),
         }
     }
@@ -1026,9 +1066,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn connection_id(&self) -> Option<CatalogItemId> {
         match self {
             Self::Kafka(conn) => conn.connection_id(),
+            Self::Kinesis(conn) => conn.connection_id(),
             Self::Postgres(conn) => conn.connection_id(),
             Self::MySql(conn) => conn.connection_id(),
             Self::SqlServer(conn) => conn.connection_id(),
+            Self::MongoDb(conn) => conn.connection_id(),
             Self::LoadGenerator(conn) => conn.connection_id(),
         }
     }
@@ -1036,9 +1078,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn primary_export_details(&self) -> SourceExportDetails {
         match self {
             Self::Kafka(conn) => conn.primary_export_details(),
+            Self::Kinesis(conn) => conn.primary_export_details(),
             Self::Postgres(conn) => conn.primary_export_details(),
             Self::MySql(conn) => conn.primary_export_details(),
             Self::SqlServer(conn) => conn.primary_export_details(),
+            Self::MongoDb(conn) => conn.primary_export_details(),
             Self::LoadGenerator(conn) => conn.primary_export_details(),
         }
     }
@@ -1046,9 +1090,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn supports_read_only(&self) -> bool {
         match self {
             GenericSourceConnection::Kafka(conn) => conn.supports_read_only(),
+            GenericSourceConnection::Kinesis(conn) => conn.supports_read_only(),
             GenericSourceConnection::Postgres(conn) => conn.supports_read_only(),
             GenericSourceConnection::MySql(conn) => conn.supports_read_only(),
             GenericSourceConnection::SqlServer(conn) => conn.supports_read_only(),
+            GenericSourceConnection::MongoDb(conn) => conn.supports_read_only(),
             GenericSourceConnection::LoadGenerator(conn) => conn.supports_read_only(),
         }
     }
@@ -1056,9 +1102,11 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
     fn prefers_single_replica(&self) -> bool {
         match self {
             GenericSourceConnection::Kafka(conn) => conn.prefers_single_replica(),
+            GenericSourceConnection::Kinesis(conn) => conn.prefers_single_replica(),
             GenericSourceConnection::Postgres(conn) => conn.prefers_single_replica(),
             GenericSourceConnection::MySql(conn) => conn.prefers_single_replica(),
             GenericSourceConnection::SqlServer(conn) => conn.prefers_single_replica(),
+            GenericSourceConnection::MongoDb(conn) => conn.prefers_single_replica(),
             GenericSourceConnection::LoadGenerator(conn) => conn.prefers_single_replica(),
         }
     }
@@ -1070,9 +1118,11 @@ impl<C: ConnectionAccess> crate::AlterCompatible for GenericSourceConnection<C>
         }
         let r = match (self, other) {
             (Self::Kafka(conn), Self::Kafka(other)) => conn.alter_compatible(id, other),
+            (Self::Kinesis(conn), Self::Kinesis(other)) => conn.alter_compatible(id, other),
             (Self::Postgres(conn), Self::Postgres(other)) => conn.alter_compatible(id, other),
             (Self::MySql(conn), Self::MySql(other)) => conn.alter_compatible(id, other),
             (Self::SqlServer(conn), Self::SqlServer(other)) => conn.alter_compatible(id, other),
+            (Self::MongoDb(conn), Self::MongoDb(other)) => conn.alter_compatible(id, other),
             (Self::LoadGenerator(conn), Self::LoadGenerator(other)) => {
                 conn.alter_compatible(id, other)
             }
@@ -1097,6 +1147,7 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
         ProtoSourceConnection {
             kind: Some(match self {
                 GenericSourceConnection::Kafka(kafka) => Kind::Kafka(kafka.into_proto()),
+                GenericSourceConnection::Kinesis(kinesis) => Kind::Kinesis(kinesis.into_proto()),
                 GenericSourceConnection::Postgres(postgres) => {
                     Kind::Postgres(postgres.into_proto())
                 }
@@ -1104,6 +1155,7 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
                 GenericSourceConnection::SqlServer(sql_server) => {
                     Kind::SqlServer(sql_server.into_proto())
                 }
+                GenericSourceConnection::MongoDb(mongodb) => Kind::Mongodb(mongodb.into_proto()),
                 GenericSourceConnection::LoadGenerator(loadgen) => {
                     Kind::Loadgen(loadgen.into_proto())
                 }
@@ -1118,11 +1170,13 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoSourceConnection::kind"))?;
         Ok(match kind {
             Kind::Kafka(kafka) => GenericSourceConnection::Kafka(kafka.into_rust()?),
+            Kind::Kinesis(kinesis) => GenericSourceConnection::Kinesis(kinesis.into_rust()?),
             Kind::Postgres(postgres) => GenericSourceConnection::Postgres(postgres.into_rust()?),
             Kind::Mysql(mysql) => GenericSourceConnection::MySql(mysql.into_rust()?),
             Kind::SqlServer(sql_server) => {
                 GenericSourceConnection::SqlServer(sql_server.into_rust()?)
             }
+            Kind::Mongodb(mongodb) => GenericSourceConnection::MongoDb(mongodb.into_rust()?),
             Kind::Loadgen(loadgen) => GenericSourceConnection::LoadGenerator(loadgen.into_rust()?),
         })
     }
@@ -1136,9 +1190,11 @@ pub enum SourceExportDetails {
     /// output to.
     None,
     Kafka(KafkaSourceExportDetails),
+    Kinesis(KinesisSourceExportDetails),
     Postgres(PostgresSourceExportDetails),
     MySql(MySqlSourceExportDetails),
     SqlServer(SqlServerSourceExportDetails),
+    MongoDb(MongoDbSourceExportDetails),
     LoadGenerator(LoadGeneratorSourceExportDetails),
 }
 
@@ -1150,8 +1206,10 @@ impl crate::AlterCompatible for SourceExportDetails {
         let r = match (self, other) {
             (Self::None, Self::None) => Ok(()),
             (Self::Kafka(s), Self::Kafka(o)) => s.alter_compatible(id, o),
+            (Self::Kinesis(s), Self::Kinesis(o)) => s.alter_compatible(id, o),
             (Self::Postgres(s), Self::Postgres(o)) => s.alter_compatible(id, o),
             (Self::MySql(s), Self::MySql(o)) => s.alter_compatible(id, o),
+            (Self::MongoDb(s), Self::MongoDb(o)) => s.alter_compatible(id, o),
             (Self::LoadGenerator(s), Self::LoadGenerator(o)) => s.alter_compatible(id, o),
             _ => Err(AlterError { id }),
         };
@@ -1175,6 +1233,7 @@ impl RustType<ProtoSourceExportDetails> for SourceExportDetails {
             kind: match self {
                 SourceExportDetails::None => None,
                 SourceExportDetails::Kafka(details) => Some(Kind::Kafka(details.into_proto())),
+                SourceExportDetails::Kinesis(details) => Some(Kind::Kinesis(details.into_proto())),
                 SourceExportDetails::Postgres(details) => {
                     Some(Kind::Postgres(details.into_proto()))
                 }
@@ -1182,6 +1241,7 @@ impl RustType<ProtoSourceExportDetails> for SourceExportDetails {
                 SourceExportDetails::SqlServer(details) => {
                     Some(Kind::SqlServer(details.into_proto()))
                 }
+                SourceExportDetails::MongoDb(details) => Some(Kind::Mongodb(details.into_proto())),
                 SourceExportDetails::LoadGenerator(details) => {
                     Some(Kind::Loadgen(details.into_proto()))
                 }
@@ -1194,9 +1254,11 @@ impl RustType<ProtoSourceExportDetails> for SourceExportDetails {
         Ok(match proto.kind {
             None => SourceExportDetails::None,
             Some(Kind::Kafka(details)) => SourceExportDetails::Kafka(details.into_rust()?),
+            Some(Kind::Kinesis(details)) => SourceExportDetails::Kinesis(details.into_rust()?),
             Some(Kind::Postgres(details)) => SourceExportDetails::Postgres(details.into_rust()?),
             Some(Kind::Mysql(details)) => SourceExportDetails::MySql(details.into_rust()?),
             Some(Kind::SqlServer(details)) => SourceExportDetails::SqlServer(details.into_rust()?),
+            Some(Kind::Mongodb(details)) => SourceExportDetails::MongoDb(details.into_rust()?),
             Some(Kind::Loadgen(details)) => {
                 SourceExportDetails::LoadGenerator(details.into_rust()?)
             }
@@ -1221,10 +1283,15 @@ pub enum SourceExportStatementDetails {
         table: mz_sql_server_util::desc::SqlServerTableDesc,
         capture_instance: Arc<str>,
     },
+    MongoDb {
+        database: String,
+        collection: String,
+    },
     LoadGenerator {
         output: LoadGeneratorOutput,
     },
     Kafka {},
+    Kinesis {},
 }
 
 impl RustType<ProtoSourceExportStatementDetails> for SourceExportStatementDetails {
@@ -1259,6 +1326,17 @@ impl RustType<ProtoSourceExportStatementDetails> for SourceExportStatementDetail
                     },
                 )),
             },
+            SourceExportStatementDetails::MongoDb {
+                database,
+                collection,
+            } => ProtoSourceExportStatementDetails {
+                kind: Some(proto_source_export_statement_details::Kind::Mongodb(
+                    mongodb::ProtoMongoDbSourceExportStatementDetails {
+                        database: database.clone(),
+                        collection: collection.clone(),
+                    },
+                )),
+            },
             SourceExportStatementDetails::LoadGenerator { output } => {
                 ProtoSourceExportStatementDetails {
                     kind: Some(proto_source_export_statement_details::Kind::Loadgen(
@@ -1273,6 +1351,11 @@ impl RustType<ProtoSourceExportStatementDetails> for SourceExportStatementDetail
                     kafka::ProtoKafkaSourceExportStatementDetails {},
                 )),
             },
+            SourceExportStatementDetails::Kinesis {} => ProtoSourceExportStatementDetails {
+                kind: Some(proto_source_export_statement_details::Kind::Kinesis(
+                    kinesis::ProtoKinesisSourceExportStatementDetails {},
+                )),
+            },
         }
     }
 
@@ -1297,12 +1380,17 @@ impl RustType<ProtoSourceExportStatementDetails> for SourceExportStatementDetail
                     .into_rust_if_some("ProtoSqlServerSourceExportStatementDetails::table")?,
                 capture_instance: details.capture_instance.into(),
             },
+            Some(Kind::Mongodb(details)) => SourceExportStatementDetails::MongoDb {
+                database: details.database,
+                collection: details.collection,
+            },
             Some(Kind::Loadgen(details)) => SourceExportStatementDetails::LoadGenerator {
                 output: details
                     .output
                     .into_rust_if_some("ProtoLoadGeneratorSourceExportStatementDetails::output")?,
             },
             Some(Kind::Kafka(_details)) => SourceExportStatementDetails::Kafka {},
+            Some(Kind::Kinesis(_details)) => SourceExportStatementDetails::Kinesis {},
             None => {
                 return Err(TryFromProtoError::missing_field(
                     "ProtoSourceExportStatementDetails::kind",
@@ -1863,6 +1951,15 @@ impl ColumnDecoder<SourceData> for SourceDataColumnarDecoder {
             cols: stats.into_iter().map(|(name, s)| (name, s)).collect(),
         }
     }
+
+    fn bloom_filters(&self, cols: &BloomFilterColumns) -> BTreeMap<String, BloomFilter> {
+        // The filters are keyed by the names of the columns of the `ok` rows,
+        // which is how readers refer to them.
+        match &self.row_decoder {
+            SourceDataRowColumnarDecoder::Row(decoder) => decoder.bloom_filters(cols),
+            SourceDataRowColumnarDecoder::EmptyRow => BTreeMap::new(),
+        }
+    }
 }
 
 /// An encoder for [`Row`]s within [`SourceData`].
@@ -2116,7 +2213,10 @@ mod tests {
         let stats = RelationPartStats {
             name: "test",
             metrics: &stats_metrics,
-            stats: &PartStats { key: stats },
+            stats: &PartStats {
+                key: stats,
+                blooms: BTreeMap::new(),
+            },
             desc: read_desc,
         };
         let mut datum_vec = DatumVec::new();
@@ -0,0 +1,479 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to Kinesis sources
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use mz_dyncfg::Config;
+use mz_ore::future::InTask;
+use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
+use mz_repr::{CatalogItemId, Datum, GlobalId, RelationDesc, Row, ScalarType};
+use mz_timely_util::order::Partitioned;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use timely::order::{PartialOrder, TotalOrder};
+use timely::progress::timestamp::{PathSummary, Refines, Timestamp};
+
+use crate::AlterCompatible;
+use crate::connections::ConnectionContext;
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+use crate::controller::AlterError;
+use crate::sources::{SourceConnection, SourceExportDetails, SourceTimestamp};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_types.sources.kinesis.rs"
+));
+
+pub const POLL_INTERVAL: Config<Duration> = Config::new(
+    "kinesis_poll_interval",
+    Duration::from_secs(1),
+    "Interval at which an idle Kinesis source polls its shards for new records. Kinesis allows \
+    five reads per second per shard, shared across all consumers.",
+);
+
+pub const SHARD_REFRESH_INTERVAL: Config<Duration> = Config::new(
+    "kinesis_shard_refresh_interval",
+    Duration::from_secs(30),
+    "Interval at which a Kinesis source lists the shards of its stream to discover splits \
+    and merges.",
+);
+
+pub const GET_RECORDS_LIMIT: Config<usize> = Config::new(
+    "kinesis_get_records_limit",
+    10_000,
+    "Maximum number of records a Kinesis source requests from a shard in a single call.",
+);
+
+/// The number of 64-bit limbs in a [`SequenceNumber`].
+const SEQUENCE_NUMBER_LIMBS: usize = 7;
+
+/// A Kinesis sequence number.
+///
+/// Sequence numbers are opaque decimal strings of up to 128 digits that
+/// increase within a shard. They are stored as a fixed-width big-endian
+/// integer so that they are `Copy` and order numerically.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct SequenceNumber([u64; SEQUENCE_NUMBER_LIMBS]);
+
+impl SequenceNumber {
+    pub const MIN: SequenceNumber = SequenceNumber([0; SEQUENCE_NUMBER_LIMBS]);
+    pub const MAX: SequenceNumber = SequenceNumber([u64::MAX; SEQUENCE_NUMBER_LIMBS]);
+
+    /// Returns the sequence number immediately after this one, if any.
+    pub fn successor(&self) -> Option<Self> {
+        let mut limbs = self.0;
+        for limb in limbs.iter_mut().rev() {
+            let (value, overflow) = limb.overflowing_add(1);
+            *limb = value;
+            if !overflow {
+                return Some(SequenceNumber(limbs));
+            }
+        }
+        None
+    }
+
+    /// Returns the sequence number immediately before this one, if any.
+    pub fn predecessor(&self) -> Option<Self> {
+        let mut limbs = self.0;
+        for limb in limbs.iter_mut().rev() {
+            let (value, overflow) = limb.overflowing_sub(1);
+            *limb = value;
+            if !overflow {
+                return Some(SequenceNumber(limbs));
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for SequenceNumber {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            bail!("empty sequence number");
+        }
+        let mut limbs = [0; SEQUENCE_NUMBER_LIMBS];
+        for c in s.chars() {
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| anyhow!("invalid sequence number: {s}"))?;
+            let mut carry = u128::from(digit);
+            for limb in limbs.iter_mut().rev() {
+                let value = u128::from(*limb) * 10 + carry;
+                *limb = u64::try_from(value & u128::from(u64::MAX)).expect("masked into range");
+                carry = value >> 64;
+            }
+            if carry != 0 {
+                bail!("sequence number out of range: {s}");
+            }
+        }
+        Ok(SequenceNumber(limbs))
+    }
+}
+
+impl fmt::Display for SequenceNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Repeatedly divide by the largest power of ten that fits in a `u64`
+        // and print the remainders from most to least significant.
+        const CHUNK: u128 = 10_000_000_000_000_000_000;
+        let mut limbs = self.0;
+        let mut chunks = vec![];
+        loop {
+            let mut rem = 0u128;
+            for limb in limbs.iter_mut() {
+                let cur = (rem << 64) | u128::from(*limb);
+                *limb = u64::try_from(cur / CHUNK).expect("quotient fits in a limb");
+                rem = cur % CHUNK;
+            }
+            chunks.push(rem);
+            if limbs.iter().all(|limb| *limb == 0) {
+                break;
+            }
+        }
+        let mut chunks = chunks.into_iter().rev();
+        write!(f, "{}", chunks.next().expect("at least one chunk"))?;
+        for chunk in chunks {
+            write!(f, "{chunk:019}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Timestamp for SequenceNumber {
+    // No need to describe complex summaries
+    type Summary = ();
+
+    fn minimum() -> Self {
+        SequenceNumber::MIN
+    }
+}
+
+impl TotalOrder for SequenceNumber {}
+
+impl PartialOrder for SequenceNumber {
+    fn less_equal(&self, other: &Self) -> bool {
+        self <= other
+    }
+}
+
+impl PathSummary<SequenceNumber> for () {
+    fn results_in(&self, src: &SequenceNumber) -> Option<SequenceNumber> {
+        Some(*src)
+    }
+
+    fn followed_by(&self, _other: &Self) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Refines<()> for SequenceNumber {
+    fn to_inner(_other: ()) -> Self {
+        Self::minimum()
+    }
+
+    fn to_outer(self) -> () {}
+
+    fn summarize(_path: Self::Summary) -> <() as Timestamp>::Summary {}
+}
+
+/// A "moment in time" perceivable in Kinesis––for each shard, the next
+/// sequence number that might be read from it.
+///
+/// Shards are partitioned by the numeric suffix of their shard ID, which
+/// Kinesis assigns in increasing order as shards are split and merged. A
+/// shard that has been closed and read to its end is absent from the
+/// frontier, while the range above the highest known shard stays at the
+/// minimum so that future children start from their beginning.
+pub type KinesisTimestamp = Partitioned<u64, SequenceNumber>;
+
+/// Returns the partition of the shard with the given ID, e.g. `7` for
+/// `shardId-000000000007`.
+pub fn shard_index(shard_id: &str) -> Option<u64> {
+    shard_id.strip_prefix("shardId-")?.parse().ok()
+}
+
+impl SourceTimestamp for KinesisTimestamp {
+    fn encode_row(&self) -> Row {
+        let sequence_number = self.timestamp().to_string();
+        let ts = if *self.timestamp() == SequenceNumber::MIN {
+            Datum::Null
+        } else {
+            Datum::String(&sequence_number)
+        };
+        Row::pack(&[
+            Datum::UInt64(self.interval().lower),
+            Datum::UInt64(self.interval().upper),
+            ts,
+        ])
+    }
+
+    fn decode_row(row: &Row) -> Self {
+        let mut datums = row.iter();
+        match (datums.next(), datums.next(), datums.next(), datums.next()) {
+            (
+                Some(Datum::UInt64(lower)),
+                Some(Datum::UInt64(upper)),
+                Some(Datum::String(ts)),
+                None,
+            ) => {
+                let ts = ts.parse().expect("valid sequence number");
+                Partitioned::new_range(lower, upper, ts)
+            }
+            (Some(Datum::UInt64(lower)), Some(Datum::UInt64(upper)), Some(Datum::Null), None) => {
+                Partitioned::new_range(lower, upper, SequenceNumber::MIN)
+            }
+            _ => panic!("invalid row {row:?}"),
+        }
+    }
+}
+
+pub static KINESIS_PROGRESS_DESC: LazyLock<RelationDesc> = LazyLock::new(|| {
+    RelationDesc::builder()
+        .with_column("shard_lower", ScalarType::UInt64.nullable(false))
+        .with_column("shard_upper", ScalarType::UInt64.nullable(false))
+        .with_column("sequence_number", ScalarType::String.nullable(true))
+        .finish()
+});
+
+/// Details about how to create a Materialize source that reads from a Kinesis
+/// data stream.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct KinesisSourceConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: CatalogItemId,
+    pub connection: C::Aws,
+    /// The name of the upstream stream.
+    pub stream: String,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<KinesisSourceConnection, R>
+    for KinesisSourceConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> KinesisSourceConnection {
+        let KinesisSourceConnection {
+            connection_id,
+            connection,
+            stream,
+        } = self;
+
+        KinesisSourceConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_aws(),
+            stream,
+        }
+    }
+}
+
+impl KinesisSourceConnection {
+    /// Returns a Kinesis client authenticated through this source's AWS
+    /// connection.
+    pub async fn client(
+        &self,
+        connection_context: &ConnectionContext,
+        in_task: InTask,
+    ) -> Result<mz_aws_util::kinesis::Client, anyhow::Error> {
+        let sdk_config = self
+            .connection
+            .load_sdk_config(connection_context, self.connection_id, in_task)
+            .await?;
+        Ok(mz_aws_util::kinesis::new_client(&sdk_config))
+    }
+}
+
+impl<C: ConnectionAccess> SourceConnection for KinesisSourceConnection<C> {
+    fn name(&self) -> &'static str {
+        "kinesis"
+    }
+
+    fn external_reference(&self) -> Option<&str> {
+        Some(self.stream.as_str())
+    }
+
+    fn default_key_desc(&self) -> RelationDesc {
+        RelationDesc::empty()
+    }
+
+    fn default_value_desc(&self) -> RelationDesc {
+        RelationDesc::builder()
+            .with_column("data", ScalarType::Bytes.nullable(false))
+            .finish()
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        KINESIS_PROGRESS_DESC.clone()
+    }
+
+    fn connection_id(&self) -> Option<CatalogItemId> {
+        Some(self.connection_id)
+    }
+
+    fn primary_export_details(&self) -> SourceExportDetails {
+        SourceExportDetails::Kinesis(KinesisSourceExportDetails {})
+    }
+
+    fn supports_read_only(&self) -> bool {
+        true
+    }
+
+    fn prefers_single_replica(&self) -> bool {
+        // Every replica reading a shard counts against the same per-shard read
+        // throughput limit.
+        true
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for KinesisSourceConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+
+        let KinesisSourceConnection {
+            connection_id,
+            connection,
+            stream,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                connection.alter_compatible(id, &other.connection).is_ok(),
+                "connection",
+            ),
+            (stream == &other.stream, "stream"),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "KinesisSourceConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RustType<ProtoKinesisSourceConnection> for KinesisSourceConnection {
+    fn into_proto(&self) -> ProtoKinesisSourceConnection {
+        ProtoKinesisSourceConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            stream: self.stream.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoKinesisSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(KinesisSourceConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoKinesisSourceConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoKinesisSourceConnection::connection")?,
+            stream: proto.stream,
+        })
+    }
+}
+
+/// The details of a source export from a Kinesis source.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct KinesisSourceExportDetails {}
+
+impl RustType<ProtoKinesisSourceExportDetails> for KinesisSourceExportDetails {
+    fn into_proto(&self) -> ProtoKinesisSourceExportDetails {
+        ProtoKinesisSourceExportDetails {}
+    }
+
+    fn from_proto(_proto: ProtoKinesisSourceExportDetails) -> Result<Self, TryFromProtoError> {
+        Ok(KinesisSourceExportDetails {})
+    }
+}
+
+impl AlterCompatible for KinesisSourceExportDetails {
+    fn alter_compatible(&self, _id: GlobalId, _other: &Self) -> Result<(), AlterError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn sequence_number_roundtrip() {
+        let values = [
+            "0",
+            "1",
+            "18446744073709551615",
+            "18446744073709551616",
+            "49590338271490256608559692538361571095921575989136588898",
+            &"9".repeat(128),
+        ];
+        for value in values {
+            let parsed: SequenceNumber = value.parse().unwrap();
+            assert_eq!(parsed.to_string(), value);
+        }
+
+        assert!("".parse::<SequenceNumber>().is_err());
+        assert!("12a".parse::<SequenceNumber>().is_err());
+        assert!("9".repeat(140).parse::<SequenceNumber>().is_err());
+    }
+
+    #[mz_ore::test]
+    fn sequence_number_order() {
+        let small: SequenceNumber = "99999999999999999999".parse().unwrap();
+        let large: SequenceNumber = "100000000000000000000".parse().unwrap();
+        assert!(small < large);
+        assert_eq!(small.successor(), Some(large));
+        assert_eq!(large.predecessor(), Some(small));
+        assert_eq!(SequenceNumber::MIN.predecessor(), None);
+        assert_eq!(SequenceNumber::MAX.successor(), None);
+    }
+
+    #[mz_ore::test]
+    fn timestamp_row_roundtrip() {
+        let timestamps = [
+            KinesisTimestamp::minimum(),
+            Partitioned::new_singleton(
+                3,
+                "49590338271490256608559692538361571095921575989136588898"
+                    .parse()
+                    .unwrap(),
+            ),
+            Partitioned::new_range(4, u64::MAX, SequenceNumber::MIN),
+        ];
+        for ts in timestamps {
+            assert_eq!(KinesisTimestamp::decode_row(&ts.encode_row()), ts);
+        }
+    }
+
+    #[mz_ore::test]
+    fn shard_indexes() {
+        assert_eq!(shard_index("shardId-000000000000"), Some(0));
+        assert_eq!(shard_index("shardId-000000000017"), Some(17));
+        assert_eq!(shard_index("shard-1"), None);
+    }
+}
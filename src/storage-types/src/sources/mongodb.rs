@@ -0,0 +1,380 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to MongoDB sources
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use mongodb::bson::{Timestamp as ClusterTime, doc};
+use mz_dyncfg::Config;
+use mz_ore::future::InTask;
+use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
+use mz_repr::{CatalogItemId, GlobalId, RelationDesc, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use timely::progress::Antichain;
+
+use crate::AlterCompatible;
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+use crate::controller::AlterError;
+use crate::sources::envelope::{SourceEnvelope, UpsertEnvelope, UpsertStyle};
+use crate::sources::{MzOffset, SourceConnection, SourceExportDetails};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_types.sources.mongodb.rs"
+));
+
+pub const SNAPSHOT_PROGRESS_REPORT_INTERVAL: Config<Duration> = Config::new(
+    "mongodb_snapshot_progress_report_interval",
+    Duration::from_secs(2),
+    "Interval at which we'll report progress for currently running snapshots.",
+);
+
+pub const CHANGE_STREAM_MAX_AWAIT_TIME: Config<Duration> = Config::new(
+    "mongodb_change_stream_max_await_time",
+    Duration::from_secs(1),
+    "Maximum amount of time the server waits for new changes before returning an empty \
+    change stream batch, which bounds how often an idle source reports progress.",
+);
+
+pub const OFFSET_KNOWN_INTERVAL: Config<Duration> = Config::new(
+    "mongodb_offset_known_interval",
+    Duration::from_secs(10),
+    "Interval to fetch `offset_known`, from the `operationTime` of a `hello` command.",
+);
+
+pub static MONGODB_PROGRESS_DESC: LazyLock<RelationDesc> = LazyLock::new(|| {
+    RelationDesc::builder()
+        .with_column("cluster_time", ScalarType::UInt64.nullable(true))
+        .finish()
+});
+
+/// The shape of every collection ingested by a MongoDB source.
+///
+/// Documents are schemaless, so each one is exported whole as `jsonb`
+/// alongside its `_id`, which MongoDB guarantees is unique within a collection
+/// and which we use as the upsert key.
+pub static MONGODB_COLLECTION_DESC: LazyLock<RelationDesc> = LazyLock::new(|| {
+    RelationDesc::builder()
+        .with_column("_id", ScalarType::Jsonb.nullable(false))
+        .with_column("document", ScalarType::Jsonb.nullable(false))
+        .with_key(vec![0])
+        .finish()
+});
+
+/// The envelope every MongoDB export is ingested with.
+///
+/// Change streams do not carry the prior version of a document, so updates
+/// are applied as upserts on `_id`: each message carries the full document as
+/// a record at index 0, or `NULL` for a deletion.
+pub fn collection_envelope() -> SourceEnvelope {
+    SourceEnvelope::Upsert(UpsertEnvelope {
+        source_arity: MONGODB_COLLECTION_DESC.arity(),
+        style: UpsertStyle::Debezium { after_idx: 0 },
+        key_indices: vec![0],
+    })
+}
+
+/// Converts a MongoDB cluster time into the offset used to timestamp updates.
+///
+/// Cluster times are totally ordered by `(time, increment)`, which packs
+/// losslessly into a `u64` with the seconds in the upper half.
+pub fn cluster_time_to_offset(ts: ClusterTime) -> MzOffset {
+    MzOffset::from((u64::from(ts.time) << 32) | u64::from(ts.increment))
+}
+
+/// The inverse of [`cluster_time_to_offset`].
+pub fn offset_to_cluster_time(offset: MzOffset) -> ClusterTime {
+    let time = u32::try_from(offset.offset >> 32).expect("shifted into range");
+    let increment = u32::try_from(offset.offset & u64::from(u32::MAX)).expect("masked into range");
+    ClusterTime { time, increment }
+}
+
+/// Returns the cluster time of the latest operation the connected node is
+/// aware of, as reported by the `hello` command.
+pub async fn operation_time(client: &mongodb::Client) -> Result<ClusterTime, anyhow::Error> {
+    let reply = client
+        .database("admin")
+        .run_command(doc! { "hello": 1 })
+        .await?;
+    Ok(reply.get_timestamp("operationTime")?)
+}
+
+/// Returns the name of the replica set the connected node belongs to, or
+/// `None` if it is a standalone server, which does not support change streams.
+pub async fn replica_set_name(client: &mongodb::Client) -> Result<Option<String>, anyhow::Error> {
+    let reply = client
+        .database("admin")
+        .run_command(doc! { "hello": 1 })
+        .await?;
+    Ok(reply.get_str("setName").ok().map(|name| name.to_string()))
+}
+
+/// Details about how to create a Materialize source that reads from MongoDB.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct MongoDbSourceConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: CatalogItemId,
+    pub connection: C::MongoDb,
+    pub details: MongoDbSourceDetails,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<MongoDbSourceConnection, R>
+    for MongoDbSourceConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> MongoDbSourceConnection {
+        let MongoDbSourceConnection {
+            connection_id,
+            connection,
+            details,
+        } = self;
+
+        MongoDbSourceConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_mongodb(),
+            details,
+        }
+    }
+}
+
+impl MongoDbSourceConnection {
+    pub async fn fetch_write_frontier(
+        self,
+        storage_configuration: &crate::configuration::StorageConfiguration,
+    ) -> Result<Antichain<MzOffset>, anyhow::Error> {
+        let client = self
+            .connection
+            .connect(
+                &storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+                InTask::No,
+            )
+            .await?;
+
+        let current = operation_time(&client).await?;
+        Ok(Antichain::from_elem(cluster_time_to_offset(current) + 1))
+    }
+}
+
+impl<C: ConnectionAccess> SourceConnection for MongoDbSourceConnection<C> {
+    fn name(&self) -> &'static str {
+        "mongodb"
+    }
+
+    fn external_reference(&self) -> Option<&str> {
+        None
+    }
+
+    fn default_key_desc(&self) -> RelationDesc {
+        RelationDesc::empty()
+    }
+
+    fn default_value_desc(&self) -> RelationDesc {
+        // The MongoDB source only outputs data to its subsources. The catalog object
+        // representing the source itself is just an empty relation with no columns
+        RelationDesc::empty()
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        MONGODB_PROGRESS_DESC.clone()
+    }
+
+    fn connection_id(&self) -> Option<CatalogItemId> {
+        Some(self.connection_id)
+    }
+
+    fn primary_export_details(&self) -> SourceExportDetails {
+        SourceExportDetails::None
+    }
+
+    fn supports_read_only(&self) -> bool {
+        false
+    }
+
+    fn prefers_single_replica(&self) -> bool {
+        true
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for MongoDbSourceConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+
+        let MongoDbSourceConnection {
+            connection_id,
+            connection,
+            details,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                connection.alter_compatible(id, &other.connection).is_ok(),
+                "connection",
+            ),
+            (
+                details.alter_compatible(id, &other.details).is_ok(),
+                "details",
+            ),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "MongoDbSourceConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RustType<ProtoMongoDbSourceConnection> for MongoDbSourceConnection {
+    fn into_proto(&self) -> ProtoMongoDbSourceConnection {
+        ProtoMongoDbSourceConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            details: Some(self.details.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoMongoDbSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MongoDbSourceConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoMongoDbSourceConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoMongoDbSourceConnection::connection")?,
+            details: proto
+                .details
+                .into_rust_if_some("ProtoMongoDbSourceConnection::details")?,
+        })
+    }
+}
+
+/// MongoDB-specific details for a source, serialized as an option in the
+/// `CREATE SOURCE` statement.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct MongoDbSourceDetails {
+    /// The name of the replica set observed during purification. Cluster times
+    /// are only comparable within a single replica set, so the source refuses
+    /// to resume against a different one.
+    pub replica_set: String,
+}
+
+impl RustType<ProtoMongoDbSourceDetails> for MongoDbSourceDetails {
+    fn into_proto(&self) -> ProtoMongoDbSourceDetails {
+        ProtoMongoDbSourceDetails {
+            replica_set: self.replica_set.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMongoDbSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(MongoDbSourceDetails {
+            replica_set: proto.replica_set,
+        })
+    }
+}
+
+impl AlterCompatible for MongoDbSourceDetails {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self.replica_set != other.replica_set {
+            tracing::warn!(
+                "MongoDbSourceDetails incompatible at replica_set:\nself:\n{:#?}\n\nother\n{:#?}",
+                self,
+                other
+            );
+            return Err(AlterError { id });
+        }
+        Ok(())
+    }
+}
+
+/// Specifies the details of a MongoDB source export.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct MongoDbSourceExportDetails {
+    /// The database containing the upstream collection.
+    pub database: String,
+    /// The name of the upstream collection.
+    pub collection: String,
+}
+
+impl RustType<ProtoMongoDbSourceExportDetails> for MongoDbSourceExportDetails {
+    fn into_proto(&self) -> ProtoMongoDbSourceExportDetails {
+        ProtoMongoDbSourceExportDetails {
+            database: self.database.clone(),
+            collection: self.collection.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMongoDbSourceExportDetails) -> Result<Self, TryFromProtoError> {
+        Ok(MongoDbSourceExportDetails {
+            database: proto.database,
+            collection: proto.collection,
+        })
+    }
+}
+
+impl AlterCompatible for MongoDbSourceExportDetails {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self != other {
+            return Err(AlterError { id });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn cluster_time_offset_roundtrip() {
+        let times = [
+            ClusterTime {
+                time: 0,
+                increment: 0,
+            },
+            ClusterTime {
+                time: 1_700_000_000,
+                increment: 7,
+            },
+            ClusterTime {
+                time: u32::MAX,
+                increment: u32::MAX,
+            },
+        ];
+        for ts in times {
+            assert_eq!(offset_to_cluster_time(cluster_time_to_offset(ts)), ts);
+        }
+
+        // Offsets must order the same way as cluster times do.
+        let earlier = cluster_time_to_offset(ClusterTime {
+            time: 10,
+            increment: u32::MAX,
+        });
+        let later = cluster_time_to_offset(ClusterTime {
+            time: 11,
+            increment: 0,
+        });
+        assert!(earlier < later);
+    }
+}
@@ -9,12 +9,28 @@
 
 //! Types and traits that connect up our mz-repr types with the stats that persist maintains.
 
-use mz_expr::{ColumnSpecs, Interpreter, MapFilterProject, ResultSpec, UnmaterializableFunc};
+use mz_expr::{
+    BinaryFunc, ColumnSpecs, Interpreter, MapFilterProject, MirScalarExpr, ResultSpec,
+    UnmaterializableFunc, VariadicFunc,
+};
 use mz_persist_types::stats::{
     BytesStats, ColumnStatKinds, JsonStats, PartStats, PartStatsMetrics,
 };
+use mz_repr::stats::{bloom_filter_key, bloom_filter_supported};
 use mz_repr::{ColumnIndex, ColumnType, Datum, RelationDesc, RowArena, ScalarType};
 
+/// Whether the stats of a part show that it can be skipped for some filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartFilterResult {
+    /// The part may contain rows that pass the filter.
+    MayMatch,
+    /// The min/max stats of the part show that no row passes the filter.
+    FilteredByStats,
+    /// The min/max stats couldn't rule the part out, but its Bloom filters
+    /// show that it doesn't contain a value that the filter requires.
+    FilteredByBloomFilters,
+}
+
 /// Bundles together a relation desc with the stats for a specific part, and translates between
 /// Persist's stats representation and the `ResultSpec`s that are used for eg. filter pushdown.
 #[derive(Debug)]
@@ -43,13 +59,23 @@ impl<'a> RelationPartStats<'a> {
 
 impl RelationPartStats<'_> {
     pub fn may_match_mfp<'a>(&'a self, time_range: ResultSpec<'a>, mfp: &MapFilterProject) -> bool {
+        self.filter_mfp(time_range, mfp) == PartFilterResult::MayMatch
+    }
+
+    /// Like [Self::may_match_mfp], but also reports which stats ruled the part
+    /// out.
+    pub fn filter_mfp<'a>(
+        &'a self,
+        time_range: ResultSpec<'a>,
+        mfp: &MapFilterProject,
+    ) -> PartFilterResult {
         let arena = RowArena::new();
         let mut ranges = ColumnSpecs::new(self.desc.typ(), &arena);
         ranges.push_unmaterializable(UnmaterializableFunc::MzNow, time_range);
 
         if self.err_count().into_iter().any(|count| count > 0) {
             // If the error collection is nonempty, we always keep the part.
-            return true;
+            return PartFilterResult::MayMatch;
         }
 
         for (pos, (idx, _name, _typ)) in self.desc.iter_all().enumerate() {
@@ -57,7 +83,90 @@ impl RelationPartStats<'_> {
             ranges.push_column(pos, result_spec);
         }
         let result = ranges.mfp_filter(mfp).range;
-        result.may_contain(Datum::True) || result.may_fail()
+        if result.may_fail() {
+            PartFilterResult::MayMatch
+        } else if !result.may_contain(Datum::True) {
+            PartFilterResult::FilteredByStats
+        } else if !self.may_match_bloom_filters(mfp) {
+            PartFilterResult::FilteredByBloomFilters
+        } else {
+            PartFilterResult::MayMatch
+        }
+    }
+
+    /// Returns false if the part's Bloom filters show that no row in it can
+    /// pass the predicates of `mfp`.
+    ///
+    /// Only predicates that compare an input column to a literal for equality
+    /// (or a disjunction of them, as produced by `IN` lists) are considered.
+    /// Callers are responsible for keeping parts on which evaluating the
+    /// predicates may error.
+    pub fn may_match_bloom_filters(&self, mfp: &MapFilterProject) -> bool {
+        if self.stats.blooms.is_empty() {
+            return true;
+        }
+        let may_match = mfp
+            .predicates
+            .iter()
+            .all(|(_, predicate)| self.may_match_bloom_predicate(mfp.input_arity, predicate));
+        if !may_match {
+            self.metrics.bloom_filtered_count.inc();
+        }
+        may_match
+    }
+
+    fn may_match_bloom_predicate(&self, input_arity: usize, predicate: &MirScalarExpr) -> bool {
+        let disjuncts = match predicate {
+            MirScalarExpr::CallVariadic {
+                func: VariadicFunc::Or,
+                exprs,
+            } => exprs.as_slice(),
+            predicate => std::slice::from_ref(predicate),
+        };
+        let mut lookups = Vec::with_capacity(disjuncts.len());
+        for disjunct in disjuncts {
+            let MirScalarExpr::CallBinary {
+                func: BinaryFunc::Eq,
+                expr1,
+                expr2,
+            } = disjunct
+            else {
+                return true;
+            };
+            let (col, literal) = match (&**expr1, &**expr2) {
+                (MirScalarExpr::Column(col, _), literal)
+                | (literal, MirScalarExpr::Column(col, _))
+                    if *col < input_arity =>
+                {
+                    (*col, literal)
+                }
+                _ => return true,
+            };
+            match literal.as_literal() {
+                Some(Ok(datum)) => lookups.push((col, datum)),
+                _ => return true,
+            }
+        }
+        lookups
+            .into_iter()
+            .any(|(col, datum)| self.bloom_may_contain(col, datum))
+    }
+
+    fn bloom_may_contain(&self, pos: usize, datum: Datum) -> bool {
+        if datum.is_null() {
+            // Nothing is equal to null.
+            return false;
+        }
+        let Some((_idx, name, typ)) = self.desc.iter_all().nth(pos) else {
+            return true;
+        };
+        if !bloom_filter_supported(&typ.scalar_type) {
+            return true;
+        }
+        match self.stats.blooms.get(name.as_str()) {
+            Some(filter) => filter.may_contain(bloom_filter_key(datum).data()),
+            None => true,
+        }
     }
 
     fn json_spec<'a>(len: usize, stats: &'a JsonStats, arena: &'a RowArena) -> ResultSpec<'a> {
@@ -226,7 +335,7 @@ mod tests {
     use mz_persist_types::codec_impls::UnitSchema;
     use mz_persist_types::columnar::{ColumnDecoder, Schema};
     use mz_persist_types::part::PartBuilder;
-    use mz_persist_types::stats::PartStats;
+    use mz_persist_types::stats::{BloomFilterColumns, PartStats};
     use mz_repr::{ColumnType, Datum, RelationDesc, Row, RowArena, ScalarType};
     use mz_repr::{RelationType, arb_datum_for_column};
     use proptest::prelude::*;
@@ -252,12 +361,20 @@ mod tests {
         let decoder = <RelationDesc as Schema<SourceData>>::decoder(&schema, key_col.clone())
             .expect("success");
         let key_stats = decoder.stats();
+        let bloom_cols = BloomFilterColumns {
+            names: vec!["col".to_owned()],
+            max_bytes: 64,
+        };
+        let key_blooms = decoder.bloom_filters(&bloom_cols);
 
         let metrics = PartStatsMetrics::new(&MetricsRegistry::new());
         let stats = RelationPartStats {
             name: "test",
             metrics: &metrics,
-            stats: &PartStats { key: key_stats },
+            stats: &PartStats {
+                key: key_stats,
+                blooms: key_blooms,
+            },
             desc: &schema,
         };
         let arena = RowArena::default();
@@ -266,11 +383,92 @@ mod tests {
         for datum in datums {
             let spec = stats.col_stats(&ColumnIndex::from_raw(0), &arena);
             assert!(spec.may_contain(*datum));
+            if !datum.is_null() {
+                assert!(stats.bloom_may_contain(0, *datum));
+            }
         }
 
         Ok(())
     }
 
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn bloom_filter_pushdown() {
+        let schema = RelationDesc::builder()
+            .with_column("id", ScalarType::Int32.nullable(false))
+            .with_column("val", ScalarType::Int32.nullable(false))
+            .finish();
+
+        // Only even ids, so that the min/max stats can't rule out odd ones.
+        let mut builder = PartBuilder::new(&schema, &UnitSchema);
+        for id in (0..200).step_by(2) {
+            let row = Row::pack_slice(&[Datum::Int32(id), Datum::Int32(id)]);
+            builder.push(&SourceData(Ok(row)), &(), 1u64, 1i64);
+        }
+        let part = builder.finish();
+        let decoder =
+            <RelationDesc as Schema<SourceData>>::decoder(&schema, part.key.as_struct().clone())
+                .expect("success");
+        let bloom_cols = BloomFilterColumns {
+            names: vec!["ID".to_owned()],
+            max_bytes: 1024,
+        };
+        let part_stats = PartStats {
+            key: decoder.stats(),
+            blooms: decoder.bloom_filters(&bloom_cols),
+        };
+        assert_eq!(part_stats.blooms.keys().collect::<Vec<_>>(), vec!["id"]);
+
+        let metrics = PartStatsMetrics::new(&MetricsRegistry::new());
+        let stats = RelationPartStats::new("test", &metrics, &schema, &part_stats);
+        let eq = |col: usize, id: i32| {
+            MirScalarExpr::column(col).call_binary(
+                MirScalarExpr::literal_ok(Datum::Int32(id), ScalarType::Int32),
+                BinaryFunc::Eq,
+            )
+        };
+        let filter = |predicate: MirScalarExpr| {
+            let mfp = MapFilterProject::new(2).filter([predicate]);
+            stats.filter_mfp(ResultSpec::value_all(), &mfp)
+        };
+
+        // No false negatives...
+        for id in (0..200).step_by(2) {
+            assert_eq!(filter(eq(0, id)), PartFilterResult::MayMatch);
+        }
+        // ...few false positives...
+        let filtered = (1..200)
+            .step_by(2)
+            .filter(|id| filter(eq(0, *id)) == PartFilterResult::FilteredByBloomFilters)
+            .count();
+        assert!(filtered > 90, "only {} of 100 parts filtered", filtered);
+        // ...and the min/max stats still come first.
+        assert_eq!(filter(eq(0, 1000)), PartFilterResult::FilteredByStats);
+
+        // An IN list can only match if one of its values can.
+        let find_absent = |from: i32| {
+            (from..200)
+                .step_by(2)
+                .find(|id| filter(eq(0, *id)) == PartFilterResult::FilteredByBloomFilters)
+                .expect("some absent id is filtered")
+        };
+        let a = find_absent(1);
+        let b = find_absent(a + 2);
+        let absent = MirScalarExpr::CallVariadic {
+            func: VariadicFunc::Or,
+            exprs: vec![eq(0, a), eq(0, b)],
+        };
+        assert_eq!(filter(absent), PartFilterResult::FilteredByBloomFilters);
+        let present = MirScalarExpr::CallVariadic {
+            func: VariadicFunc::Or,
+            exprs: vec![eq(0, a), eq(0, 2)],
+        };
+        assert_eq!(filter(present), PartFilterResult::MayMatch);
+
+        // Columns without a filter are never ruled out by one.
+        assert_eq!(filter(eq(1, a)), PartFilterResult::MayMatch);
+    }
+
     fn scalar_type_stats_roundtrip(scalar_type: ScalarType) {
         // Non-nullable version of the column.
         let column_type = scalar_type.clone().nullable(false);
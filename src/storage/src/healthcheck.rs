@@ -48,9 +48,14 @@ pub enum StatusNamespace {
     /// `Running`.
     Generator,
     Kafka,
+    Kinesis,
     Postgres,
     MySql,
     SqlServer,
+    MongoDb,
+    Iceberg,
+    S3,
+    Http,
     Ssh,
     Upsert,
     Decode,
@@ -69,9 +74,14 @@ impl fmt::Display for StatusNamespace {
         match self {
             Generator => write!(f, "generator"),
             Kafka => write!(f, "kafka"),
+            Kinesis => write!(f, "kinesis"),
             Postgres => write!(f, "postgres"),
             MySql => write!(f, "mysql"),
             SqlServer => write!(f, "sql-server"),
+            MongoDb => write!(f, "mongodb"),
+            Iceberg => write!(f, "iceberg"),
+            S3 => write!(f, "s3"),
+            Http => write!(f, "http"),
             Ssh => write!(f, "ssh"),
             Upsert => write!(f, "upsert"),
             Decode => write!(f, "decode"),
@@ -308,6 +308,15 @@ pub fn build_ingestion_dataflow<A: Allocate>(
                     storage_state,
                     base_source_config,
                 ),
+                GenericSourceConnection::Kinesis(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    c,
+                    description.clone(),
+                    &feedback,
+                    storage_state,
+                    base_source_config,
+                ),
                 GenericSourceConnection::Postgres(c) => crate::render::sources::render_source(
                     mz_scope,
                     &debug_name,
@@ -335,6 +344,15 @@ pub fn build_ingestion_dataflow<A: Allocate>(
                     storage_state,
                     base_source_config,
                 ),
+                GenericSourceConnection::MongoDb(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    c,
+                    description.clone(),
+                    &feedback,
+                    storage_state,
+                    base_source_config,
+                ),
                 GenericSourceConnection::LoadGenerator(c) => crate::render::sources::render_source(
                     mz_scope,
                     &debug_name,
@@ -9,4 +9,234 @@
 
 //! Moving data to external systems
 
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use mz_dyncfg::ConfigValHandle;
+use mz_ore::vec::VecExt;
+use mz_persist_client::write::WriteHandle;
+use mz_repr::Timestamp;
+use mz_storage_types::StorageDiff;
+use mz_storage_types::sources::SourceData;
+use mz_timely_util::antichain::AntichainExt;
+use timely::PartialOrder;
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{error, info};
+
+mod http;
+mod iceberg;
 mod kafka;
+mod postgres;
+mod s3;
+
+/// The progress of a sink that writes its updates in timestamp order and
+/// resumes from a frontier recorded in the external system.
+///
+/// Buffers updates until the input frontier passes their timestamp and
+/// advances the upper of the sink's persist shard, and the write frontier
+/// reported to the controller, to the frontier the sink has committed.
+///
+/// This relies on `Timestamp` being totally ordered, so it is deliberately
+/// not generic over the timestamp.
+pub(crate) struct SinkProgress<U> {
+    as_of: Antichain<Timestamp>,
+    resume_upper: Antichain<Timestamp>,
+    /// The smallest time that has not been committed.
+    upper: Timestamp,
+    write_handle: WriteHandle<SourceData, (), Timestamp, StorageDiff>,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    buffer_min_capacity: ConfigValHandle<usize>,
+    deferred: Vec<(U, Timestamp)>,
+    ready: Vec<(U, Timestamp)>,
+}
+
+impl<U> SinkProgress<U> {
+    /// Starts tracking the progress of a sink that has committed everything
+    /// before `resume_upper`.
+    ///
+    /// Returns an error if the input has been compacted past `resume_upper`,
+    /// and `None` if the sink has already committed its final frontier.
+    pub fn new(
+        name: &str,
+        as_of: Antichain<Timestamp>,
+        resume_upper: Antichain<Timestamp>,
+        write_handle: WriteHandle<SourceData, (), Timestamp, StorageDiff>,
+        write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+        buffer_min_capacity: ConfigValHandle<usize>,
+    ) -> Result<Option<Self>, anyhow::Error> {
+        // The input has overcompacted if
+        let overcompacted =
+            // ..we have made some progress in the past
+            *resume_upper != [Timestamp::minimum()] &&
+            // ..but the since frontier is now beyond that
+            !PartialOrder::less_equal(&as_of, &resume_upper);
+        if overcompacted {
+            let err = format!(
+                "{name}: input compacted past resume upper: as_of {}, resume_upper: {}",
+                as_of.pretty(),
+                resume_upper.pretty()
+            );
+            // This would normally be an assertion but because it can happen after a
+            // Materialize backup/restore we log an error so that it appears on Sentry but
+            // leaves the rest of the objects in the cluster unaffected.
+            error!("{err}");
+            return Err(anyhow!("{err}"));
+        }
+
+        info!(
+            "{name}: as_of: {}, resume upper: {}",
+            as_of.pretty(),
+            resume_upper.pretty()
+        );
+
+        let Some(upper) = resume_upper.as_option().copied() else {
+            write_frontier.borrow_mut().clear();
+            return Ok(None);
+        };
+        Ok(Some(SinkProgress {
+            as_of,
+            resume_upper,
+            upper,
+            write_handle,
+            write_frontier,
+            buffer_min_capacity,
+            deferred: Vec::new(),
+            ready: Vec::new(),
+        }))
+    }
+
+    /// Accepts an update from the input, returning it if it can be written
+    /// out immediately.
+    ///
+    /// We want to publish updates in time order and we know that we have
+    /// already committed all times not beyond the upper. Therefore, if an
+    /// update happens *exactly* at the upper then it is the minimum pending
+    /// time and so emitting it now will not violate the timestamp publish
+    /// order. This is load bearing because it is the mechanism by which sinks
+    /// incrementally stream out the initial snapshot instead of buffering it
+    /// all in memory first. This argument doesn't hold for partially ordered
+    /// time because many different timestamps can be *exactly* at the upper
+    /// but we can't know ahead of time which one will be advanced in the next
+    /// progress message.
+    ///
+    /// Updates beyond the upper are buffered until the input frontier passes
+    /// them, and updates before it have already been committed and are
+    /// dropped.
+    pub fn accept(&mut self, update: U, time: Timestamp) -> Option<U> {
+        match self.upper.cmp(&time) {
+            Ordering::Less => {
+                self.deferred.push((update, time));
+                None
+            }
+            Ordering::Equal => Some(update),
+            Ordering::Greater => None,
+        }
+    }
+
+    /// Buffers an update from the input until the input frontier passes it,
+    /// for sinks that cannot write out updates at the upper immediately.
+    ///
+    /// Returns whether the update was buffered, rather than dropped because it
+    /// has already been committed.
+    pub fn defer(&mut self, update: U, time: Timestamp) -> bool {
+        if time < self.upper {
+            return false;
+        }
+        self.deferred.push((update, time));
+        true
+    }
+
+    /// Returns the buffered updates the input `frontier` has passed, sorted
+    /// by time, or `None` if the sink must not commit `frontier`.
+    ///
+    /// The caller must write out all of the returned updates before committing
+    /// `frontier`.
+    pub fn ready(&mut self, frontier: &Antichain<Timestamp>) -> Option<&mut Vec<(U, Timestamp)>> {
+        // Ignore progress updates before our resumption frontier
+        if !PartialOrder::less_equal(&self.resume_upper, frontier) {
+            return None;
+        }
+        // Also ignore progress updates until we are past the as_of frontier. This
+        // is to avoid the following pathological scenario:
+        // 1. Sink gets instantiated with an as_of = {10}, resume_upper = {0}.
+        //    `frontier` initially jumps at {10}, then the snapshot appears at time
+        //    10.
+        // 2. `frontier` would normally advance to say {11} and we would commit the
+        //    snapshot but clusterd crashes instead.
+        // 3. A new cluster restarts the sink with an earlier as_of, say {5}. This
+        //    is valid, the earlier as_of has strictly more information. The
+        //    snapshot now appears at time 5.
+        //
+        // If we were to commit an empty transaction in step 1 and advanced the
+        // resume_upper to {10} then in step 3 we would ignore the snapshot that
+        // now appears at 5 completely. So it is important to only start committing
+        // transactions after we're strictly beyond the as_of.
+        // TODO(petrosagg): is this logic an indication of us holding something
+        // wrong elsewhere? Investigate.
+        // Note: !PartialOrder::less_than(as_of, frontier) would not be equivalent
+        // nor correct for partially ordered times.
+        if !self.as_of.iter().all(|t| !frontier.less_equal(t)) {
+            return None;
+        }
+
+        // N.B. Shrinking the Vecs here is important because when starting the sink
+        // we might buffer a ton of updates into them, e.g. if the recorded progress
+        // was lost and the resume upper is 0, and we don't want to keep around a
+        // massively oversized Vec.
+        let min_capacity = self.buffer_min_capacity.get();
+        self.deferred.shrink_to(min_capacity);
+        self.ready.clear();
+        self.ready.shrink_to(min_capacity);
+        self.ready.extend(
+            self.deferred
+                .drain_filter_swapping(|(_, time)| !frontier.less_equal(time)),
+        );
+        self.ready.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+        Some(&mut self.ready)
+    }
+
+    /// Records that the sink has committed everything before `committed`,
+    /// after the input frontier advanced to `frontier`.
+    ///
+    /// Returns whether the input can still produce updates.
+    pub async fn advance(
+        &mut self,
+        frontier: &Antichain<Timestamp>,
+        committed: &Antichain<Timestamp>,
+    ) -> bool {
+        let mut expect_upper = self.write_handle.shared_upper();
+        loop {
+            if PartialOrder::less_equal(committed, &expect_upper) {
+                // The frontier has already been advanced as far as necessary.
+                break;
+            }
+            // TODO(sinks): include the high water mark in the output topic for
+            // the messages we've published, if and when we allow reads to the sink
+            // directly, to allow monitoring the progress of the sink in terms of
+            // the output system.
+            const EMPTY: &[((SourceData, ()), Timestamp, StorageDiff)] = &[];
+            match self
+                .write_handle
+                .compare_and_append(EMPTY, expect_upper, committed.clone())
+                .await
+                .expect("valid usage")
+            {
+                Ok(()) => break,
+                Err(mismatch) => {
+                    expect_upper = mismatch.current;
+                }
+            }
+        }
+        self.write_frontier.borrow_mut().clone_from(committed);
+
+        match frontier.as_option() {
+            Some(upper) => {
+                self.upper = *upper;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,598 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of an [`HttpSinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which `POST`s the changes at
+//! each closed timestamp to the endpoint of the sink as JSON:
+//!
+//! ```text
+//! {"timestamp": 1700000000000, "updates": [{"key": {...}, "value": {...}}, ...]}
+//! ```
+//!
+//! With `ENVELOPE UPSERT` the value of an update is the new row, or `null` if
+//! the key was deleted. With `ENVELOPE DEBEZIUM` the value holds the `before`
+//! and `after` rows. Timestamps with more changes than fit in a single request
+//! are split across several requests.
+//!
+//! # At least once
+//!
+//! Once all changes before a frontier have been delivered, the sink advances
+//! the upper of its persist shard to that frontier, and resumes from that upper
+//! on restart. A restarted sink therefore redelivers any timestamp whose
+//! delivery was in flight. The changes at a timestamp are always split into
+//! requests in the same way, and every request carries an `Idempotency-Key`
+//! header naming the sink, the timestamp, and the index of the request within
+//! the timestamp, which allows the endpoint to discard requests it has already
+//! processed.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use differential_dataflow::{Collection, Hashable};
+use mz_interchange::avro::DiffPair;
+use mz_interchange::envelopes::dbz_format;
+use mz_interchange::json::JsonEncoder;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_ore::retry::{Retry, RetryResult};
+use mz_persist_client::Diagnostics;
+use mz_persist_client::write::WriteHandle;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_storage_types::StorageDiff;
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::{
+    HTTP_SINK_MAX_BATCH_SIZE, HTTP_SINK_MAX_RETRIES, HTTP_SINK_REQUEST_TIMEOUT,
+    KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS,
+};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::{HttpSinkConnection, SinkEnvelope, StorageSinkDesc};
+use mz_storage_types::sources::SourceData;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use reqwest::StatusCode;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde_json::json;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concatenate, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{debug, info, warn};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::sink::SinkProgress;
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+/// The header carrying the idempotency key of a request.
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// The header carrying the timestamp of the changes in a request.
+const TIMESTAMP_HEADER: &str = "Materialize-Timestamp";
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for HttpSinkConnection {
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_desc_and_indices
+            .as_ref()
+            .map(|(_desc, indices)| indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_handle = {
+            let persist = Arc::clone(&storage_state.persist_clients);
+            let shard_meta = sink.to_storage_metadata.clone();
+            async move {
+                let client = persist.open(shard_meta.persist_location).await?;
+                let handle = client
+                    .open_writer(
+                        shard_meta.data_shard,
+                        Arc::new(shard_meta.relation_desc),
+                        Arc::new(UnitSchema),
+                        Diagnostics::from_purpose("sink handle"),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        };
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let (sink_status, sink_token) = sink_collection(
+            format!("http-{sink_id}-sink"),
+            &input,
+            sink_id,
+            self.clone(),
+            storage_state.storage_configuration.clone(),
+            sink,
+            statistics,
+            write_handle,
+            write_frontier,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            id: None,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Http,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Sinks a collection of changes to an HTTP endpoint.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// given sink `id`.
+fn sink_collection<G: Scope<Timestamp = Timestamp>>(
+    name: String,
+    input: &Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+    sink_id: GlobalId,
+    connection: HttpSinkConnection,
+    storage_configuration: StorageConfiguration,
+    sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+    statistics: SinkStatistics,
+    write_handle: impl Future<
+        Output = anyhow::Result<WriteHandle<SourceData, (), Timestamp, StorageDiff>>,
+    > + 'static,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to deliver the changes.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+    let config_set = storage_configuration.config_set();
+    let buffer_min_capacity = KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS.handle(config_set);
+    let max_batch_size = HTTP_SINK_MAX_BATCH_SIZE.handle(config_set);
+    let request_timeout = HTTP_SINK_REQUEST_TIMEOUT.get(config_set);
+    let max_retries = HTTP_SINK_MAX_RETRIES.get(config_set);
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let as_of = sink.as_of.clone();
+    let envelope = sink.envelope;
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let mut write_handle = write_handle.await?;
+
+            let authorization = match &connection.authorization {
+                Some(authorization) => Some(
+                    authorization
+                        .get_string(
+                            InTask::Yes,
+                            &storage_configuration.connection_context.secrets_reader,
+                        )
+                        .await?,
+                ),
+                None => None,
+            };
+            let deliverer = Deliverer::new(
+                connection.url.clone(),
+                authorization,
+                format!(
+                    "materialize-{}-{}",
+                    storage_configuration.connection_context.environment_id, sink_id
+                ),
+                request_timeout,
+                max_retries,
+            )?;
+            let encoder = ChangeEncoder::new(&connection, envelope);
+
+            // The upper of the sink's shard records the frontier that all
+            // changes have been delivered up to.
+            let resume_upper = write_handle.fetch_recent_upper().await.clone();
+
+            info!("{name}: delivering to {}", connection.url);
+            let Some(mut progress) = SinkProgress::new(
+                &name,
+                as_of,
+                resume_upper,
+                write_handle,
+                write_frontier,
+                buffer_min_capacity,
+            )?
+            else {
+                return Ok(());
+            };
+
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for (update, time, _diff) in batch {
+                            // Changes before the upper have already been delivered. All other
+                            // changes are buffered until their timestamp closes, so that they
+                            // can be split into requests deterministically.
+                            if progress.defer(update, time) {
+                                statistics.inc_messages_staged_by(1);
+                            }
+                        }
+                    }
+                    Event::Progress(frontier) => {
+                        let Some(ready) = progress.ready(&frontier) else {
+                            continue;
+                        };
+                        sort_updates(ready);
+                        for updates in ready.chunk_by(|(_, a), (_, b)| a == b) {
+                            let time = updates[0].1;
+                            let bodies = encoder.encode(time, updates, max_batch_size.get());
+                            let batches = bodies.len();
+                            for (index, body) in bodies.into_iter().enumerate() {
+                                let bytes = u64::cast_from(body.len());
+                                deliverer.deliver(time, index, body).await?;
+                                statistics.inc_bytes_staged_by(bytes);
+                                statistics.inc_bytes_committed_by(bytes);
+                            }
+                            statistics.inc_messages_committed_by(u64::cast_from(updates.len()));
+                            debug!(
+                                "{name}: delivered {} changes at {time} in {batches} requests",
+                                updates.len()
+                            );
+                        }
+                        ready.clear();
+
+                        if !progress.advance(&frontier, &frontier).await {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    });
+
+    let statuses = errors.map(|error: Rc<anyhow::Error>| HealthStatusMessage {
+        id: None,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace: StatusNamespace::Http,
+    });
+
+    (statuses, button.press_on_drop())
+}
+
+/// Sorts updates by timestamp, and the updates at each timestamp into a
+/// deterministic order.
+fn sort_updates(updates: &mut [((Option<Row>, DiffPair<Row>), Timestamp)]) {
+    updates.sort_unstable_by(|((ka, va), ta), ((kb, vb), tb)| {
+        (ta, ka, &va.before, &va.after).cmp(&(tb, kb, &vb.before, &vb.after))
+    });
+}
+
+/// Encodes the changes at a timestamp as the bodies of requests.
+struct ChangeEncoder {
+    key_encoder: Option<JsonEncoder>,
+    value_encoder: JsonEncoder,
+    envelope: SinkEnvelope,
+}
+
+impl ChangeEncoder {
+    fn new(connection: &HttpSinkConnection, envelope: SinkEnvelope) -> Self {
+        let debezium = matches!(envelope, SinkEnvelope::Debezium);
+        ChangeEncoder {
+            key_encoder: connection
+                .key_desc_and_indices
+                .as_ref()
+                .map(|(desc, _indices)| JsonEncoder::new(desc.clone(), false)),
+            value_encoder: JsonEncoder::new(connection.value_desc.clone(), debezium),
+            envelope,
+        }
+    }
+
+    /// Encodes `updates`, which must all be at `time`, as the bodies of one or
+    /// more requests of at most `max_batch_size` changes each.
+    fn encode(
+        &self,
+        time: Timestamp,
+        updates: &[((Option<Row>, DiffPair<Row>), Timestamp)],
+        max_batch_size: usize,
+    ) -> Vec<Vec<u8>> {
+        let mut row_buf = Row::default();
+        updates
+            .chunks(max_batch_size.max(1))
+            .map(|batch| {
+                let changes: Vec<_> = batch
+                    .iter()
+                    .map(|((key, value), _time)| {
+                        let key = match (&self.key_encoder, key) {
+                            (Some(encoder), Some(key)) => encoder.encode_json(key),
+                            _ => serde_json::Value::Null,
+                        };
+                        let value = match self.envelope {
+                            SinkEnvelope::Upsert => value.after.as_ref(),
+                            SinkEnvelope::Debezium => {
+                                dbz_format(&mut row_buf.packer(), value.clone());
+                                Some(&row_buf)
+                            }
+                        };
+                        let value = value.map_or(serde_json::Value::Null, |value| {
+                            self.value_encoder.encode_json(value)
+                        });
+                        json!({ "key": key, "value": value })
+                    })
+                    .collect();
+                let body = json!({ "timestamp": u64::from(time), "updates": changes });
+                serde_json::to_vec(&body).expect("JSON values serialize")
+            })
+            .collect()
+    }
+}
+
+/// Delivers requests to the endpoint of a sink.
+struct Deliverer {
+    client: reqwest::Client,
+    url: String,
+    /// The value of the `Authorization` header, if any.
+    authorization: Option<String>,
+    /// The prefix of the idempotency key of each request.
+    idempotency_prefix: String,
+    max_retries: usize,
+}
+
+impl Deliverer {
+    fn new(
+        url: String,
+        authorization: Option<String>,
+        idempotency_prefix: String,
+        request_timeout: Duration,
+        max_retries: usize,
+    ) -> Result<Self, anyhow::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()?;
+        Ok(Deliverer {
+            client,
+            url,
+            authorization,
+            idempotency_prefix,
+            max_retries,
+        })
+    }
+
+    /// Delivers the `index`th request of the changes at `time`, retrying
+    /// transient failures.
+    async fn deliver(
+        &self,
+        time: Timestamp,
+        index: usize,
+        body: Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        let idempotency_key = format!("{}-{time}-{index}", self.idempotency_prefix);
+        let body = Bytes::from(body);
+        Retry::default()
+            .max_tries(self.max_retries + 1)
+            .retry_async(|state| {
+                let mut request = self
+                    .client
+                    .post(&self.url)
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(IDEMPOTENCY_KEY_HEADER, &idempotency_key)
+                    .header(TIMESTAMP_HEADER, time.to_string())
+                    .body(body.clone());
+                if let Some(authorization) = &self.authorization {
+                    request = request.header(AUTHORIZATION, authorization);
+                }
+                let url = &self.url;
+                let idempotency_key = &idempotency_key;
+                async move {
+                    if state.i > 0 {
+                        warn!(
+                            "retrying request {idempotency_key} to {url}, attempt {}",
+                            state.i
+                        );
+                    }
+                    match request.send().await {
+                        Ok(response) if response.status().is_success() => RetryResult::Ok(()),
+                        Ok(response) => {
+                            let status = response.status();
+                            let err = anyhow!("{url} responded with {status}");
+                            if is_retryable(status) {
+                                RetryResult::RetryableErr(err)
+                            } else {
+                                RetryResult::FatalErr(err)
+                            }
+                        }
+                        Err(err) => RetryResult::RetryableErr(
+                            anyhow::Error::new(err).context(format!("sending request to {url}")),
+                        ),
+                    }
+                }
+            })
+            .await
+    }
+}
+
+/// Whether a request that failed with `status` may succeed if retried.
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::sync::Mutex;
+
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use mz_repr::{Datum, RelationDesc, ScalarType};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn connection(key: bool) -> HttpSinkConnection {
+        let value_desc = RelationDesc::builder()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true))
+            .finish();
+        let key_desc_and_indices = key.then(|| {
+            let key_desc = RelationDesc::builder()
+                .with_column("a", ScalarType::Int64.nullable(false))
+                .finish();
+            (key_desc, vec![0])
+        });
+        HttpSinkConnection {
+            url: "http://localhost".into(),
+            authorization: None,
+            key_desc_and_indices,
+            relation_key_indices: None,
+            value_desc,
+        }
+    }
+
+    fn update(
+        a: i64,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> ((Option<Row>, DiffPair<Row>), Timestamp) {
+        let row = |b: &str| Row::pack_slice(&[Datum::Int64(a), Datum::String(b)]);
+        let key = Row::pack_slice(&[Datum::Int64(a)]);
+        let value = DiffPair {
+            before: before.map(row),
+            after: after.map(row),
+        };
+        ((Some(key), value), Timestamp::from(7))
+    }
+
+    #[mz_ore::test]
+    fn encode_upsert() {
+        let encoder = ChangeEncoder::new(&connection(true), SinkEnvelope::Upsert);
+        let mut updates = vec![update(2, Some("x"), None), update(1, None, Some("y"))];
+        sort_updates(&mut updates);
+
+        let bodies = encoder.encode(Timestamp::from(7), &updates, 1);
+        let bodies: Vec<serde_json::Value> = bodies
+            .iter()
+            .map(|body| serde_json::from_slice(body).unwrap())
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                json!({"timestamp": 7, "updates": [{"key": {"a": 1}, "value": {"a": 1, "b": "y"}}]}),
+                json!({"timestamp": 7, "updates": [{"key": {"a": 2}, "value": null}]}),
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn encode_debezium() {
+        let encoder = ChangeEncoder::new(&connection(false), SinkEnvelope::Debezium);
+        let updates = vec![update(1, Some("x"), Some("y"))];
+
+        let bodies = encoder.encode(Timestamp::from(7), &updates, 10);
+        let body: serde_json::Value = serde_json::from_slice(&bodies[0]).unwrap();
+        assert_eq!(
+            body,
+            json!({"timestamp": 7, "updates": [{
+                "key": null,
+                "value": {"before": {"a": 1, "b": "x"}, "after": {"a": 1, "b": "y"}},
+            }]})
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn deliver_retries() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let app = Router::new().route(
+            "/",
+            post({
+                let requests = Arc::clone(&requests);
+                move |headers: HeaderMap, body: String| async move {
+                    let mut requests = requests.lock().unwrap();
+                    let header = |name| headers[name].to_str().unwrap().to_string();
+                    requests.push((header("Idempotency-Key"), header("Authorization"), body));
+                    // Fail the first attempt with a transient error.
+                    if requests.len() == 1 {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+
+        // Use port 0 to get a dynamically assigned port.
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let tcp = TcpListener::bind(addr).await.expect("able to bind");
+        let addr = tcp.local_addr().expect("valid addr");
+        mz_ore::task::spawn(|| "test-server", async move {
+            axum::serve(tcp, app.into_make_service()).await.unwrap();
+        });
+
+        let deliverer = Deliverer::new(
+            format!("http://{addr}/"),
+            Some("Bearer token".into()),
+            "materialize-env-u1".into(),
+            Duration::from_secs(10),
+            3,
+        )
+        .unwrap();
+        deliverer
+            .deliver(Timestamp::from(7), 1, b"{}".to_vec())
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for (key, authorization, body) in requests.iter() {
+            assert_eq!(key, "materialize-env-u1-7-1");
+            assert_eq!(authorization, "Bearer token");
+            assert_eq!(body, "{}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn retryable_statuses() {
+        assert!(is_retryable(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable(reqwest::StatusCode::BAD_REQUEST));
+        assert!(!is_retryable(reqwest::StatusCode::UNAUTHORIZED));
+    }
+}
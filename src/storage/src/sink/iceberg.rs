@@ -0,0 +1,482 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of an [`IcebergSinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which writes them to Parquet
+//! files and commits those files to the table as Iceberg snapshots.
+//!
+//! # Snapshots
+//!
+//! Every timestamp of the sinked collection that contains updates is committed
+//! as its own Iceberg snapshot. Within a snapshot, the `before` half of each
+//! update is written as an equality delete on the sink key and the `after`
+//! half as a new row. Equality deletes only apply to rows of earlier
+//! snapshots, so committing each timestamp separately guarantees that a key
+//! updated at one timestamp is deleted and re-inserted exactly once.
+//!
+//! Updates at exactly the current upper are streamed straight into the open
+//! Parquet files, which is how the initial snapshot is written out without
+//! first buffering it in memory. Updates beyond the upper are buffered until
+//! the input frontier passes them.
+//!
+//! # Progress
+//!
+//! Each snapshot records the frontier of the sinked collection that it is
+//! complete up to in its summary. When the sink restarts it resumes from the
+//! frontier of the table's current snapshot, which makes the sink exactly
+//! once: files written by an uncommitted attempt are never referenced by the
+//! table. Snapshots that only advance the frontier are committed at most once
+//! per `ICEBERG_SINK_PROGRESS_COMMIT_INTERVAL`.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{Context, anyhow};
+use differential_dataflow::{Collection, Hashable};
+use mz_interchange::avro::DiffPair;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::now::NowFn;
+use mz_persist_client::Diagnostics;
+use mz_persist_client::write::WriteHandle;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
+use mz_storage_operators::iceberg::io::FileIo;
+use mz_storage_operators::iceberg::manifest::{DataFile, DataFileContent};
+use mz_storage_operators::iceberg::parquet::{ParquetFileWriter, file_size};
+use mz_storage_operators::iceberg::{
+    SnapshotUpdate, Table, frontier_summary, load_or_create_table,
+};
+use mz_storage_types::StorageDiff;
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::{
+    ICEBERG_SINK_MANIFEST_MERGE_MIN_COUNT, ICEBERG_SINK_PROGRESS_COMMIT_INTERVAL,
+    ICEBERG_SINK_TARGET_FILE_SIZE, KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS,
+};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::iceberg::{Schema, field_id};
+use mz_storage_types::sinks::{IcebergSinkConnection, StorageSinkDesc};
+use mz_storage_types::sources::SourceData;
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concatenate, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{debug, info};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::sink::SinkProgress;
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for IcebergSinkConnection {
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_desc_and_indices
+            .as_ref()
+            .map(|(_desc, indices)| indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_handle = {
+            let persist = Arc::clone(&storage_state.persist_clients);
+            let shard_meta = sink.to_storage_metadata.clone();
+            async move {
+                let client = persist.open(shard_meta.persist_location).await?;
+                let handle = client
+                    .open_writer(
+                        shard_meta.data_shard,
+                        Arc::new(shard_meta.relation_desc),
+                        Arc::new(UnitSchema),
+                        Diagnostics::from_purpose("sink handle"),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        };
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let (sink_status, sink_token) = sink_collection(
+            format!("iceberg-{sink_id}-sink"),
+            &input,
+            sink_id,
+            self.clone(),
+            storage_state.storage_configuration.clone(),
+            sink,
+            statistics,
+            storage_state.now.clone(),
+            write_handle,
+            write_frontier,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            id: None,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Iceberg,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Sinks a collection of keyed updates to an Iceberg table.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// given sink `id`.
+fn sink_collection<G: Scope<Timestamp = Timestamp>>(
+    name: String,
+    input: &Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+    sink_id: GlobalId,
+    connection: IcebergSinkConnection,
+    storage_configuration: StorageConfiguration,
+    sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+    statistics: SinkStatistics,
+    now: NowFn,
+    write_handle: impl Future<
+        Output = anyhow::Result<WriteHandle<SourceData, (), Timestamp, StorageDiff>>,
+    > + 'static,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to commit to the table.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+    let config_set = storage_configuration.config_set();
+    let buffer_min_capacity = KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS.handle(config_set);
+    let progress_commit_interval = ICEBERG_SINK_PROGRESS_COMMIT_INTERVAL.handle(config_set);
+    let target_file_size = ICEBERG_SINK_TARGET_FILE_SIZE.handle(config_set);
+    let manifest_merge_min_count = ICEBERG_SINK_MANIFEST_MERGE_MIN_COUNT.handle(config_set);
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let as_of = sink.as_of.clone();
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let write_handle = write_handle.await?;
+
+            let location = connection.table_location();
+            let key_indices = connection
+                .key_desc_and_indices
+                .as_ref()
+                .map(|(_desc, indices)| indices.clone())
+                .ok_or_else(|| anyhow!("iceberg sinks require a key"))?;
+            let schema = Schema::for_desc(&connection.value_desc, &key_indices)?;
+            let io = FileIo::for_sink(&connection, &storage_configuration.connection_context)
+                .await
+                .with_context(|| format!("connecting to warehouse {}", connection.warehouse))?;
+            let mut table =
+                load_or_create_table(io, location.clone(), sink_id, schema, now_ms(&now)).await?;
+
+            let resume_upper = match table.frontier() {
+                None => Antichain::from_elem(Timestamp::minimum()),
+                Some(None) => Antichain::new(),
+                Some(Some(t)) => Antichain::from_elem(Timestamp::from(t)),
+            };
+
+            info!("{name}: writing to table {location}");
+            let Some(mut progress) = SinkProgress::new(
+                &name,
+                as_of,
+                resume_upper.clone(),
+                write_handle,
+                write_frontier,
+                buffer_min_capacity,
+            )?
+            else {
+                return Ok(());
+            };
+
+            let mut writer = SnapshotWriter::new(&connection, &key_indices, statistics)?;
+            let mut committed = resume_upper;
+            let mut last_commit = Instant::now();
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for (update, time, _diff) in batch {
+                            // Updates at the upper belong to the snapshot that will be
+                            // committed next and can be written out immediately.
+                            if let Some(update) = progress.accept(update, time) {
+                                writer.add(&table, update, target_file_size.get()).await?;
+                            }
+                        }
+                    }
+                    Event::Progress(frontier) => {
+                        let Some(ready) = progress.ready(&frontier) else {
+                            continue;
+                        };
+
+                        // Commit one snapshot per timestamp with updates. Each snapshot is
+                        // complete up to the next timestamp with updates, or the new frontier
+                        // for the last one.
+                        let mut ready = ready.drain(..).peekable();
+                        loop {
+                            let next_time = ready.peek().map(|(_, time)| *time);
+                            if writer.has_updates() {
+                                let upper = match next_time {
+                                    Some(time) => Antichain::from_elem(time),
+                                    None => frontier.clone(),
+                                };
+                                writer
+                                    .commit(
+                                        &mut table,
+                                        &upper,
+                                        now_ms(&now),
+                                        manifest_merge_min_count.get(),
+                                    )
+                                    .await?;
+                                committed = upper;
+                                last_commit = Instant::now();
+                            }
+                            let Some(time) = next_time else {
+                                break;
+                            };
+                            while let Some((update, _time)) = ready.next_if(|(_, t)| *t == time) {
+                                writer.add(&table, update, target_file_size.get()).await?;
+                            }
+                        }
+                        drop(ready);
+
+                        // Commit a snapshot that only records progress, unless we have done so
+                        // recently. The final frontier is always committed.
+                        if committed != frontier
+                            && (frontier.is_empty()
+                                || last_commit.elapsed() >= progress_commit_interval.get())
+                        {
+                            debug!("{name}: committing progress {}", frontier.pretty());
+                            writer
+                                .commit(
+                                    &mut table,
+                                    &frontier,
+                                    now_ms(&now),
+                                    manifest_merge_min_count.get(),
+                                )
+                                .await?;
+                            committed = frontier.clone();
+                            last_commit = Instant::now();
+                        }
+
+                        if !progress.advance(&frontier, &committed).await {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    });
+
+    let statuses = errors.map(|error: Rc<anyhow::Error>| HealthStatusMessage {
+        id: None,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace: StatusNamespace::Iceberg,
+    });
+
+    (statuses, button.press_on_drop())
+}
+
+/// Accumulates the files of the next snapshot.
+struct SnapshotWriter {
+    value_desc: RelationDesc,
+    value_field_ids: Vec<i32>,
+    key_desc: RelationDesc,
+    key_field_ids: Vec<i32>,
+    data: Option<ParquetFileWriter>,
+    deletes: Option<ParquetFileWriter>,
+    update: SnapshotUpdate,
+    statistics: SinkStatistics,
+    staged_messages: u64,
+    staged_bytes: u64,
+}
+
+impl SnapshotWriter {
+    fn new(
+        connection: &IcebergSinkConnection,
+        key_indices: &[usize],
+        statistics: SinkStatistics,
+    ) -> Result<Self, anyhow::Error> {
+        let (key_desc, _) = connection
+            .key_desc_and_indices
+            .clone()
+            .ok_or_else(|| anyhow!("iceberg sinks require a key"))?;
+        Ok(SnapshotWriter {
+            value_field_ids: (0..connection.value_desc.arity()).map(field_id).collect(),
+            value_desc: connection.value_desc.clone(),
+            key_field_ids: key_indices.iter().copied().map(field_id).collect(),
+            key_desc,
+            data: None,
+            deletes: None,
+            update: SnapshotUpdate::default(),
+            statistics,
+            staged_messages: 0,
+            staged_bytes: 0,
+        })
+    }
+
+    /// Reports whether any updates have been added since the last commit.
+    fn has_updates(&self) -> bool {
+        self.data.is_some()
+            || self.deletes.is_some()
+            || !self.update.data_files.is_empty()
+            || !self.update.delete_files.is_empty()
+    }
+
+    /// Adds an update to the next snapshot.
+    async fn add(
+        &mut self,
+        table: &Table,
+        (key, value): (Option<Row>, DiffPair<Row>),
+        target_file_size: usize,
+    ) -> Result<(), anyhow::Error> {
+        if value.before.is_some() {
+            let key = key.ok_or_else(|| anyhow!("iceberg sink update without a key"))?;
+            let deletes = match &mut self.deletes {
+                Some(deletes) => deletes,
+                None => self.deletes.insert(ParquetFileWriter::new(
+                    self.key_desc.clone(),
+                    &self.key_field_ids,
+                )?),
+            };
+            deletes.add_row(&key)?;
+            if deletes.size_estimate() >= target_file_size {
+                self.flush_deletes(table).await?;
+            }
+        }
+        if let Some(after) = value.after {
+            let data = match &mut self.data {
+                Some(data) => data,
+                None => self.data.insert(ParquetFileWriter::new(
+                    self.value_desc.clone(),
+                    &self.value_field_ids,
+                )?),
+            };
+            data.add_row(&after)?;
+            let size = u64::cast_from(after.byte_len());
+            self.statistics.inc_messages_staged_by(1);
+            self.statistics.inc_bytes_staged_by(size);
+            self.staged_messages += 1;
+            self.staged_bytes += size;
+            if data.size_estimate() >= target_file_size {
+                self.flush_data(table).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Commits all added updates as a new snapshot that is complete up to
+    /// `frontier`.
+    async fn commit(
+        &mut self,
+        table: &mut Table,
+        frontier: &Antichain<Timestamp>,
+        now_ms: i64,
+        manifest_merge_min_count: usize,
+    ) -> Result<(), anyhow::Error> {
+        self.flush_data(table).await?;
+        self.flush_deletes(table).await?;
+        let mut update = std::mem::take(&mut self.update);
+        update.summary = frontier_summary(frontier.as_option().map(|t| u64::from(*t)));
+        table
+            .commit(update, now_ms, manifest_merge_min_count)
+            .await?;
+        self.statistics
+            .inc_messages_committed_by(self.staged_messages);
+        self.statistics.inc_bytes_committed_by(self.staged_bytes);
+        self.staged_messages = 0;
+        self.staged_bytes = 0;
+        Ok(())
+    }
+
+    async fn flush_data(&mut self, table: &Table) -> Result<(), anyhow::Error> {
+        if let Some(data) = self.data.take() {
+            let file = write_file(table, data, DataFileContent::Data, vec![]).await?;
+            self.update.data_files.push(file);
+        }
+        Ok(())
+    }
+
+    async fn flush_deletes(&mut self, table: &Table) -> Result<(), anyhow::Error> {
+        if let Some(deletes) = self.deletes.take() {
+            let file = write_file(
+                table,
+                deletes,
+                DataFileContent::EqualityDeletes,
+                self.key_field_ids.clone(),
+            )
+            .await?;
+            self.update.delete_files.push(file);
+        }
+        Ok(())
+    }
+}
+
+/// Finishes `writer` and uploads it as a new file of the table.
+async fn write_file(
+    table: &Table,
+    writer: ParquetFileWriter,
+    content: DataFileContent,
+    equality_ids: Vec<i32>,
+) -> Result<DataFile, anyhow::Error> {
+    let record_count = writer.record_count();
+    let bytes = writer.finish()?;
+    let file_path = table.new_data_file_uri();
+    let file_size_in_bytes = file_size(&bytes);
+    table.write_file(&file_path, bytes).await?;
+    Ok(DataFile {
+        content,
+        file_path,
+        record_count,
+        file_size_in_bytes,
+        equality_ids,
+    })
+}
+
+fn now_ms(now: &NowFn) -> i64 {
+    i64::try_from(now()).expect("timestamp fits in i64")
+}
@@ -71,7 +71,6 @@
 //! [1]: https://github.com/confluentinc/librdkafka/blob/master/INTRODUCTION.md#message-reliability
 
 use std::cell::RefCell;
-use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::future::Future;
 use std::rc::Rc;
@@ -82,6 +81,7 @@ use std::time::Duration;
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
 use crate::metrics::sink::kafka::KafkaSinkMetrics;
 use crate::render::sinks::SinkRender;
+use crate::sink::SinkProgress;
 use crate::statistics::SinkStatistics;
 use crate::storage_state::StorageState;
 use anyhow::{Context, anyhow, bail};
@@ -93,6 +93,7 @@ use mz_interchange::avro::{AvroEncoder, DiffPair};
 use mz_interchange::encode::Encode;
 use mz_interchange::envelopes::dbz_format;
 use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::Encoder as ProtobufEncoder;
 use mz_interchange::text_binary::{BinaryEncoder, TextEncoder};
 use mz_kafka_util::admin::EnsureTopicConfig;
 use mz_kafka_util::client::{
@@ -104,7 +105,6 @@ use mz_ore::collections::CollectionExt;
 use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
 use mz_ore::task::{self, AbortOnDropHandle};
-use mz_ore::vec::VecExt;
 use mz_persist_client::Diagnostics;
 use mz_persist_client::write::WriteHandle;
 use mz_persist_types::codec_impls::UnitSchema;
@@ -112,6 +112,7 @@ use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_storage_client::sink::progress_key::ProgressKey;
 use mz_storage_types::StorageDiff;
 use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::connections::CsrConnection;
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::dyncfgs::{
     KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS, SINK_ENSURE_TOPIC_CONFIG, SINK_PROGRESS_SEARCH,
@@ -139,7 +140,7 @@ use timely::dataflow::{Scope, Stream};
 use timely::progress::{Antichain, Timestamp as _};
 use tokio::sync::watch;
 use tokio::time::{self, MissedTickBehavior};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, info, warn};
 
 impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for KafkaSinkConnection {
     fn get_key_indices(&self) -> Option<&[usize]> {
@@ -663,7 +664,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                 ContextCreationError::Other(anyhow::anyhow!("synthetic error"))
             ));
 
-            let mut write_handle = write_handle.await?;
+            let write_handle = write_handle.await?;
 
             let metrics = Arc::new(metrics);
 
@@ -677,41 +678,18 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
             )
             .await?;
 
-            // The input has overcompacted if
-            let overcompacted =
-                // ..we have made some progress in the past
-                *resume_upper != [Timestamp::minimum()] &&
-                // ..but the since frontier is now beyond that
-                !PartialOrder::less_equal(&as_of, &resume_upper);
-            if overcompacted {
-                let err = format!(
-                    "{name}: input compacted past resume upper: as_of {}, resume_upper: {}",
-                    as_of.pretty(),
-                    resume_upper.pretty()
-                );
-                // This would normally be an assertion but because it can happen after a
-                // Materialize backup/restore we log an error so that it appears on Sentry but
-                // leaves the rest of the objects in the cluster unaffected.
-                error!("{err}");
-                return Err(anyhow!("{err}").into());
-            }
-
-            info!(
-                "{name}: as_of: {}, resume upper: {}",
-                as_of.pretty(),
-                resume_upper.pretty()
-            );
-
-            // The section below relies on TotalOrder for correctness so we'll work with timestamps
-            // directly to make sure this doesn't compile if someone attempts to make this operator
-            // generic over partial orders in the future.
-            let Some(mut upper) = resume_upper.clone().into_option() else {
-                write_frontier.borrow_mut().clear();
+            let Some(mut progress) = SinkProgress::new(
+                &name,
+                as_of,
+                resume_upper,
+                write_handle,
+                write_frontier,
+                buffer_min_capacity,
+            )?
+            else {
                 return Ok(());
             };
 
-            let mut deferred_updates = vec![];
-            let mut extra_updates = vec![];
             // We must wait until we have data to commit before starting a transaction because
             // Kafka doesn't have a heartbeating mechanism to keep a transaction open indefinitely.
             // This flag tracks whether we have started the transaction.
@@ -720,106 +698,31 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                 match event {
                     Event::Data(_cap, batch) => {
                         for (message, time, diff) in batch {
-                            // We want to publish updates in time order and we know that we have
-                            // already committed all times not beyond `upper`. Therefore, if this
-                            // update happens *exactly* at upper then it is the minimum pending
-                            // time and so emitting it now will not violate the timestamp publish
-                            // order. This optimization is load bearing because it is the mechanism
-                            // by which we incrementally stream the initial snapshot out to Kafka
-                            // instead of buffering it all in memory first. This argument doesn't
-                            // hold for partially ordered time because many different timestamps
-                            // can be *exactly* at upper but we can't know ahead of time which one
-                            // will be advanced in the next progress message.
-                            match upper.cmp(&time) {
-                                Ordering::Less => deferred_updates.push((message, time, diff)),
-                                Ordering::Equal => {
-                                    if !transaction_begun {
-                                        producer.begin_transaction().await?;
-                                        transaction_begun = true;
-                                    }
-                                    producer.send(&message, time, diff)?;
+                            if let Some((message, diff)) = progress.accept((message, diff), time) {
+                                if !transaction_begun {
+                                    producer.begin_transaction().await?;
+                                    transaction_begun = true;
                                 }
-                                Ordering::Greater => continue,
+                                producer.send(&message, time, diff)?;
                             }
                         }
                     }
-                    Event::Progress(progress) => {
-                        // Ignore progress updates before our resumption frontier
-                        if !PartialOrder::less_equal(&resume_upper, &progress) {
-                            continue;
-                        }
-                        // Also ignore progress updates until we are past the as_of frontier. This
-                        // is to avoid the following pathological scenario:
-                        // 1. Sink gets instantiated with an as_of = {10}, resume_upper = {0}.
-                        //    `progress` initially jumps at {10}, then the snapshot appears at time
-                        //    10.
-                        // 2. `progress` would normally advance to say {11} and we would commit the
-                        //    snapshot but clusterd crashes instead.
-                        // 3. A new cluster restarts the sink with an earlier as_of, say {5}. This
-                        //    is valid, the earlier as_of has strictly more information. The
-                        //    snapshot now appears at time 5.
-                        //
-                        // If we were to commit an empty transaction in step 1 and advanced the
-                        // resume_upper to {10} then in step 3 we would ignore the snapshot that
-                        // now appears at 5 completely. So it is important to only start committing
-                        // transactions after we're strictly beyond the as_of.
-                        // TODO(petrosagg): is this logic an indication of us holding something
-                        // wrong elsewhere? Investigate.
-                        // Note: !PartialOrder::less_than(as_of, progress) would not be equivalent
-                        // nor correct for partially ordered times.
-                        if !as_of.iter().all(|t| !progress.less_equal(t)) {
+                    Event::Progress(frontier) => {
+                        let Some(ready) = progress.ready(&frontier) else {
                             continue;
-                        }
+                        };
                         if !transaction_begun {
                             producer.begin_transaction().await?;
                         }
-
-                        // N.B. Shrinking the Vec here is important because when starting the Sink
-                        // we might buffer a ton of updates into these collections, e.g. if someone
-                        // deleted the progress topic and the resume upper is 0, and we don't want
-                        // to keep around a massively oversized VEc.
-                        deferred_updates.shrink_to(buffer_min_capacity.get());
-                        extra_updates.extend(
-                            deferred_updates
-                                .drain_filter_swapping(|(_, time, _)| !progress.less_equal(time)),
-                        );
-                        extra_updates.sort_unstable_by(|a, b| a.1.cmp(&b.1));
-
-                        // N.B. See the comment above.
-                        extra_updates.shrink_to(buffer_min_capacity.get());
-                        for (message, time, diff) in extra_updates.drain(..) {
+                        for ((message, diff), time) in ready.drain(..) {
                             producer.send(&message, time, diff)?;
                         }
 
-                        debug!("{name}: committing transaction for {}", progress.pretty());
-                        producer.commit_transaction(progress.clone()).await?;
+                        debug!("{name}: committing transaction for {}", frontier.pretty());
+                        producer.commit_transaction(frontier.clone()).await?;
                         transaction_begun = false;
-                        let mut expect_upper = write_handle.shared_upper();
-                        loop {
-                            if PartialOrder::less_equal(&progress, &expect_upper) {
-                                // The frontier has already been advanced as far as necessary.
-                                break;
-                            }
-                            // TODO(sinks): include the high water mark in the output topic for
-                            // the messages we've published, if and when we allow reads to the sink
-                            // directly, to allow monitoring the progress of the sink in terms of
-                            // the output system.
-                            const EMPTY: &[((SourceData, ()), Timestamp, StorageDiff)] = &[];
-                            match write_handle
-                                .compare_and_append(EMPTY, expect_upper, progress.clone())
-                                .await
-                                .expect("valid usage")
-                            {
-                                Ok(()) => break,
-                                Err(mismatch) => {
-                                    expect_upper = mismatch.current;
-                                }
-                            }
-                        }
-                        write_frontier.borrow_mut().clone_from(&progress);
-                        match progress.into_option() {
-                            Some(new_upper) => upper = new_upper,
-                            None => break,
+                        if !progress.advance(&frontier, &frontier).await {
+                            break;
                         }
                     }
                 }
@@ -1353,6 +1256,36 @@ async fn fetch_partition_count_loop<F>(
     }
 }
 
+/// Publishes the schema of a Protobuf format to its schema registry, if it has
+/// one, returning the ID of the schema.
+///
+/// Note that where this lies in the rendering cycle means that we will publish
+/// the schemas each time the sink is rendered.
+async fn publish_protobuf_schema(
+    storage_configuration: &StorageConfiguration,
+    subject: String,
+    schema: Option<String>,
+    compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+    csr_connection: Option<CsrConnection>,
+) -> Result<Option<i32>, anyhow::Error> {
+    let (Some(schema), Some(csr_connection)) = (schema, csr_connection) else {
+        return Ok(None);
+    };
+    let ccsr = csr_connection
+        .connect(storage_configuration, InTask::Yes)
+        .await?;
+    let schema_id = mz_storage_client::sink::publish_kafka_schema(
+        ccsr,
+        subject,
+        schema,
+        mz_ccsr::SchemaType::Protobuf,
+        compatibility_level,
+    )
+    .await
+    .context("error publishing kafka schemas for sink")?;
+    Ok(Some(schema_id))
+}
+
 /// Encodes a stream of `(Option<Row>, Option<Row>)` updates using the specified encoder.
 ///
 /// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
@@ -1417,6 +1350,30 @@ fn encode_collection<G: Scope>(
 
                         Some(Box::new(AvroEncoder::new(desc, false, &schema, schema_id)))
                     }
+                    (Some(desc), Some(KafkaSinkFormatType::Protobuf {
+                        message_name,
+                        descriptors,
+                        schema,
+                        compatibility_level,
+                        csr_connection,
+                    })) => {
+                        let schema_id = publish_protobuf_schema(
+                            &storage_configuration,
+                            format!("{}-key", connection.topic),
+                            schema,
+                            compatibility_level,
+                            csr_connection,
+                        )
+                        .await?;
+
+                        Some(Box::new(ProtobufEncoder::new(
+                            desc,
+                            false,
+                            &descriptors,
+                            &message_name,
+                            schema_id,
+                        )?))
+                    }
                     (None, None) => None,
                     (desc, format) => {
                         return Err(anyhow!(
@@ -1459,6 +1416,30 @@ fn encode_collection<G: Scope>(
 
                     Box::new(AvroEncoder::new(value_desc, debezium, &schema, schema_id))
                 }
+                KafkaSinkFormatType::Protobuf {
+                    message_name,
+                    descriptors,
+                    schema,
+                    compatibility_level,
+                    csr_connection,
+                } => {
+                    let schema_id = publish_protobuf_schema(
+                        &storage_configuration,
+                        format!("{}-value", connection.topic),
+                        schema,
+                        compatibility_level,
+                        csr_connection,
+                    )
+                    .await?;
+
+                    Box::new(ProtobufEncoder::new(
+                        value_desc,
+                        debezium,
+                        &descriptors,
+                        &message_name,
+                        schema_id,
+                    )?)
+                }
             };
 
             // !IMPORTANT!
@@ -0,0 +1,513 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of a [`PostgresSinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which applies them to the
+//! target table as logical upserts: every change deletes the rows with the
+//! key of the change and, unless the change is a deletion, inserts the new
+//! row. The target table must already exist and contain a column for each
+//! column of the sinked relation. Values are sent as text and cast to the
+//! type of their target column.
+//!
+//! # Exactly once
+//!
+//! The sink records the frontier it has applied all changes up to in a
+//! progress table, which is created if it does not exist. Changes and the
+//! progress they result in are committed in the same transaction, so the
+//! target table is always consistent with the recorded frontier. On restart
+//! the sink resumes from that frontier.
+//!
+//! The progress is only updated if it still holds the frontier the sink
+//! expects, which fences out any other instance of the same sink that
+//! attempts to write concurrently.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{Context, anyhow, bail};
+use bytes::BytesMut;
+use differential_dataflow::{Collection, Hashable};
+use mz_interchange::avro::DiffPair;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_persist_client::Diagnostics;
+use mz_persist_client::write::WriteHandle;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_postgres_util::Client;
+use mz_repr::{Diff, GlobalId, RelationType, Row, Timestamp};
+use mz_sql_parser::ast::{Ident, display::AstDisplay};
+use mz_storage_types::StorageDiff;
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::{
+    KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS, POSTGRES_SINK_PROGRESS_COMMIT_INTERVAL,
+};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::{PostgresSinkConnection, StorageSinkDesc};
+use mz_storage_types::sources::SourceData;
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concatenate, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tokio_postgres::Statement;
+use tokio_postgres::types::ToSql;
+use tracing::{debug, info};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::sink::SinkProgress;
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for PostgresSinkConnection {
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_desc_and_indices
+            .as_ref()
+            .map(|(_desc, indices)| indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_handle = {
+            let persist = Arc::clone(&storage_state.persist_clients);
+            let shard_meta = sink.to_storage_metadata.clone();
+            async move {
+                let client = persist.open(shard_meta.persist_location).await?;
+                let handle = client
+                    .open_writer(
+                        shard_meta.data_shard,
+                        Arc::new(shard_meta.relation_desc),
+                        Arc::new(UnitSchema),
+                        Diagnostics::from_purpose("sink handle"),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        };
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let (sink_status, sink_token) = sink_collection(
+            format!("postgres-{sink_id}-sink"),
+            &input,
+            sink_id,
+            self.clone(),
+            storage_state.storage_configuration.clone(),
+            sink,
+            statistics,
+            write_handle,
+            write_frontier,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            id: None,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Postgres,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Sinks a collection of changes to a table in PostgreSQL.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// given sink `id`.
+fn sink_collection<G: Scope<Timestamp = Timestamp>>(
+    name: String,
+    input: &Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+    sink_id: GlobalId,
+    connection: PostgresSinkConnection,
+    storage_configuration: StorageConfiguration,
+    sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+    statistics: SinkStatistics,
+    write_handle: impl Future<
+        Output = anyhow::Result<WriteHandle<SourceData, (), Timestamp, StorageDiff>>,
+    > + 'static,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to write to the table.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+    let buffer_min_capacity =
+        KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS.handle(storage_configuration.config_set());
+    let progress_commit_interval =
+        POSTGRES_SINK_PROGRESS_COMMIT_INTERVAL.handle(storage_configuration.config_set());
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let as_of = sink.as_of.clone();
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let write_handle = write_handle.await?;
+
+            let config = connection
+                .connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    &storage_configuration,
+                    InTask::Yes,
+                )
+                .await?;
+            let client = config
+                .connect(
+                    &name,
+                    &storage_configuration.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+            let progress_id = format!(
+                "materialize-{}-{}",
+                storage_configuration.connection_context.environment_id, sink_id
+            );
+            let mut writer = TableWriter::new(client, &connection, progress_id, statistics)
+                .await
+                .with_context(|| {
+                    format!(
+                        "preparing to write to {}.{}",
+                        connection.schema, connection.table
+                    )
+                })?;
+
+            let resume_upper = writer.committed.clone();
+            info!(
+                "{name}: writing to table {}.{}",
+                connection.schema, connection.table
+            );
+            let Some(mut progress) = SinkProgress::new(
+                &name,
+                as_of,
+                resume_upper,
+                write_handle,
+                write_frontier,
+                buffer_min_capacity,
+            )?
+            else {
+                return Ok(());
+            };
+
+            let mut last_commit = Instant::now();
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for (update, time, _diff) in batch {
+                            // Changes at the upper belong to the next transaction to be
+                            // committed and can be applied immediately.
+                            if let Some(update) = progress.accept(update, time) {
+                                writer.apply(update).await?;
+                            }
+                        }
+                    }
+                    Event::Progress(frontier) => {
+                        let Some(ready) = progress.ready(&frontier) else {
+                            continue;
+                        };
+                        for (update, _time) in ready.drain(..) {
+                            writer.apply(update).await?;
+                        }
+
+                        // Commit as soon as there are changes to commit, and otherwise only
+                        // periodically record progress. The final frontier is always recorded.
+                        if writer.committed != frontier
+                            && (writer.in_transaction
+                                || frontier.is_empty()
+                                || last_commit.elapsed() >= progress_commit_interval.get())
+                        {
+                            debug!("{name}: committing up to {}", frontier.pretty());
+                            writer.commit(&frontier).await?;
+                            last_commit = Instant::now();
+                        }
+
+                        if !progress.advance(&frontier, &writer.committed).await {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    });
+
+    let statuses = errors.map(|error: Rc<anyhow::Error>| HealthStatusMessage {
+        id: None,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace: StatusNamespace::Postgres,
+    });
+
+    (statuses, button.press_on_drop())
+}
+
+/// Applies changes to the target table and records the progress of the sink.
+struct TableWriter {
+    client: Client,
+    /// The identifier of the sink in the progress table.
+    progress_id: String,
+    delete: Statement,
+    insert: Statement,
+    update_progress: Statement,
+    key_typ: RelationType,
+    value_typ: RelationType,
+    /// Whether a transaction with uncommitted changes is open.
+    in_transaction: bool,
+    /// The frontier recorded in the progress table.
+    committed: Antichain<Timestamp>,
+    statistics: SinkStatistics,
+    staged_messages: u64,
+    staged_bytes: u64,
+}
+
+impl TableWriter {
+    /// Prepares to write to the target table, creating the progress table and
+    /// the progress of the sink if they do not exist.
+    async fn new(
+        client: Client,
+        connection: &PostgresSinkConnection,
+        progress_id: String,
+        statistics: SinkStatistics,
+    ) -> Result<Self, anyhow::Error> {
+        let Some((key_desc, _)) = &connection.key_desc_and_indices else {
+            bail!("Postgres sinks require a key");
+        };
+        let quote = |name: &str| Ident::new_unchecked(name).to_ast_string_simple();
+        let schema = quote(&connection.schema);
+        let table = format!("{schema}.{}", quote(&connection.table));
+        let progress_table = format!("{schema}.{}", quote(&connection.progress_table));
+
+        client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {progress_table} \
+                 (sink_id text PRIMARY KEY, frontier bigint)"
+            ))
+            .await?;
+        client
+            .execute(
+                &format!(
+                    "INSERT INTO {progress_table} (sink_id, frontier) VALUES ($1, 0) \
+                     ON CONFLICT (sink_id) DO NOTHING"
+                ),
+                &[&progress_id],
+            )
+            .await?;
+        let frontier: Option<i64> = client
+            .query_one(
+                &format!("SELECT frontier FROM {progress_table} WHERE sink_id = $1"),
+                &[&progress_id],
+            )
+            .await?
+            .get(0);
+        let committed = match frontier {
+            Some(frontier) => {
+                let frontier = u64::try_from(frontier)
+                    .map_err(|_| anyhow!("invalid frontier in {progress_table}: {frontier}"))?;
+                Antichain::from_elem(Timestamp::from(frontier))
+            }
+            None => Antichain::new(),
+        };
+
+        // Cast each value to the type of its target column, so that the
+        // target table is free to use any type with a compatible text
+        // representation.
+        let column_types: BTreeMap<String, String> = client
+            .query(
+                "SELECT a.attname::text, format_type(a.atttypid, a.atttypmod) \
+                 FROM pg_catalog.pg_attribute a \
+                 WHERE a.attrelid = $1::regclass AND a.attnum > 0 AND NOT a.attisdropped",
+                &[&table],
+            )
+            .await?
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect();
+        let target = |name: &str, index: usize| -> Result<String, anyhow::Error> {
+            let typ = column_types
+                .get(name)
+                .ok_or_else(|| anyhow!("column {} does not exist in {table}", quote(name)))?;
+            Ok(format!("${index}::text::{typ}"))
+        };
+
+        let key_predicates = key_desc
+            .iter()
+            .enumerate()
+            .map(|(i, (name, typ))| {
+                let value = target(name.as_str(), i + 1)?;
+                // `IS NOT DISTINCT FROM` cannot use an index, so only use it
+                // where the key can actually be null.
+                let op = if typ.nullable {
+                    "IS NOT DISTINCT FROM"
+                } else {
+                    "="
+                };
+                Ok(format!("{} {op} {value}", quote(name.as_str())))
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        let delete = format!("DELETE FROM {table} WHERE {}", key_predicates.join(" AND "));
+
+        let (columns, values): (Vec<_>, Vec<_>) = connection
+            .value_desc
+            .iter_names()
+            .enumerate()
+            .map(|(i, name)| Ok((quote(name.as_str()), target(name.as_str(), i + 1)?)))
+            .collect::<Result<Vec<_>, anyhow::Error>>()?
+            .into_iter()
+            .unzip();
+        let insert = format!(
+            "INSERT INTO {table} ({}) VALUES ({})",
+            columns.join(", "),
+            values.join(", ")
+        );
+
+        let update_progress = format!(
+            "UPDATE {progress_table} SET frontier = $2 \
+             WHERE sink_id = $1 AND frontier IS NOT DISTINCT FROM $3"
+        );
+
+        Ok(TableWriter {
+            delete: client.prepare(&delete).await?,
+            insert: client.prepare(&insert).await?,
+            update_progress: client.prepare(&update_progress).await?,
+            client,
+            progress_id,
+            key_typ: key_desc.typ().clone(),
+            value_typ: connection.value_desc.typ().clone(),
+            in_transaction: false,
+            committed,
+            statistics,
+            staged_messages: 0,
+            staged_bytes: 0,
+        })
+    }
+
+    /// Applies a change within the open transaction, opening one if needed.
+    async fn apply(
+        &mut self,
+        (key, value): (Option<Row>, DiffPair<Row>),
+    ) -> Result<(), anyhow::Error> {
+        let key = key.expect("Postgres sinks always have a key");
+        if !self.in_transaction {
+            self.client.batch_execute("BEGIN").await?;
+            self.in_transaction = true;
+        }
+
+        let params = encode_text(&key, &self.key_typ);
+        self.client
+            .execute(&self.delete, &as_params(&params))
+            .await?;
+        if let Some(after) = &value.after {
+            let params = encode_text(after, &self.value_typ);
+            self.client
+                .execute(&self.insert, &as_params(&params))
+                .await?;
+        }
+
+        let size = u64::cast_from(value.after.as_ref().map_or(key.byte_len(), Row::byte_len));
+        self.statistics.inc_messages_staged_by(1);
+        self.statistics.inc_bytes_staged_by(size);
+        self.staged_messages += 1;
+        self.staged_bytes += size;
+        Ok(())
+    }
+
+    /// Records `frontier` as the progress of the sink, committing any changes
+    /// applied since the last commit.
+    async fn commit(&mut self, frontier: &Antichain<Timestamp>) -> Result<(), anyhow::Error> {
+        let encode = |frontier: &Antichain<Timestamp>| {
+            frontier
+                .as_option()
+                .map(|t| i64::try_from(u64::from(*t)))
+                .transpose()
+                .map_err(|_| anyhow!("frontier {} out of range", frontier.pretty()))
+        };
+        let new = encode(frontier)?;
+        let old = encode(&self.committed)?;
+
+        if !self.in_transaction {
+            self.client.batch_execute("BEGIN").await?;
+            self.in_transaction = true;
+        }
+        let updated = self
+            .client
+            .execute(&self.update_progress, &[&self.progress_id, &new, &old])
+            .await?;
+        if updated != 1 {
+            bail!(
+                "progress of {} changed concurrently, another instance of the sink may be running",
+                self.progress_id
+            );
+        }
+        self.client.batch_execute("COMMIT").await?;
+        self.in_transaction = false;
+
+        self.committed.clone_from(frontier);
+        self.statistics
+            .inc_messages_committed_by(self.staged_messages);
+        self.statistics.inc_bytes_committed_by(self.staged_bytes);
+        self.staged_messages = 0;
+        self.staged_bytes = 0;
+        Ok(())
+    }
+}
+
+/// Encodes each datum of `row` in the PostgreSQL text format.
+fn encode_text(row: &Row, typ: &RelationType) -> Vec<Option<String>> {
+    row.iter()
+        .zip(typ.column_types.iter())
+        .map(|(datum, typ)| {
+            mz_pgrepr::Value::from_datum(datum, &typ.scalar_type).map(|value| {
+                let mut buf = BytesMut::new();
+                value.encode_text(&mut buf);
+                String::from_utf8(buf.to_vec()).expect("text encoding is valid UTF-8")
+            })
+        })
+        .collect()
+}
+
+fn as_params(values: &[Option<String>]) -> Vec<&(dyn ToSql + Sync)> {
+    values.iter().map(|v| v as &(dyn ToSql + Sync)).collect()
+}
@@ -0,0 +1,386 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of an [`S3SinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which writes the changes at
+//! each timestamp to data files and closes the timestamp by writing its
+//! manifest. See [`mz_storage_types::sinks::s3`] for the layout of the files.
+//!
+//! # Exactly once
+//!
+//! After closing one or more timestamps the sink records the frontier it has
+//! written all manifests up to in its progress file. On restart the sink
+//! resumes from that frontier. Any timestamp beyond it is written again from
+//! scratch, and because data files and manifests are named after their
+//! timestamp, a rewrite replaces the files of the previous attempt instead of
+//! duplicating them. The progress file also records the sink that owns the
+//! path, so that two sinks never write to the same path.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Context;
+use differential_dataflow::{Collection, Hashable};
+use mz_interchange::avro::DiffPair;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_persist_client::Diagnostics;
+use mz_persist_client::write::WriteHandle;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, Timestamp};
+use mz_storage_operators::s3_oneshot_sink::CopyToParameters;
+use mz_storage_operators::s3_sink::{S3SinkClient, TimestampWriter};
+use mz_storage_types::StorageDiff;
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::{
+    KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS, S3_SINK_ARROW_BUILDER_BUFFER_RATIO,
+    S3_SINK_MULTIPART_PART_SIZE_BYTES, S3_SINK_PARQUET_ROW_GROUP_FILE_RATIO,
+    S3_SINK_PROGRESS_COMMIT_INTERVAL,
+};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::s3::{Manifest, Progress, S3SinkLayout, file_desc};
+use mz_storage_types::sinks::{S3SinkConnection, StorageSinkDesc};
+use mz_storage_types::sources::SourceData;
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concatenate, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{debug, info};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::sink::SinkProgress;
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for S3SinkConnection {
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_desc_and_indices
+            .as_ref()
+            .map(|(_desc, indices)| indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_handle = {
+            let persist = Arc::clone(&storage_state.persist_clients);
+            let shard_meta = sink.to_storage_metadata.clone();
+            async move {
+                let client = persist.open(shard_meta.persist_location).await?;
+                let handle = client
+                    .open_writer(
+                        shard_meta.data_shard,
+                        Arc::new(shard_meta.relation_desc),
+                        Arc::new(UnitSchema),
+                        Diagnostics::from_purpose("sink handle"),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        };
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let (sink_status, sink_token) = sink_collection(
+            format!("s3-{sink_id}-sink"),
+            &input,
+            sink_id,
+            self.clone(),
+            storage_state.storage_configuration.clone(),
+            sink,
+            statistics,
+            write_handle,
+            write_frontier,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            id: None,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::S3,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Sinks a collection of changes to files in S3.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// given sink `id`.
+fn sink_collection<G: Scope<Timestamp = Timestamp>>(
+    name: String,
+    input: &Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
+    sink_id: GlobalId,
+    connection: S3SinkConnection,
+    storage_configuration: StorageConfiguration,
+    sink: &StorageSinkDesc<CollectionMetadata, Timestamp>,
+    statistics: SinkStatistics,
+    write_handle: impl Future<
+        Output = anyhow::Result<WriteHandle<SourceData, (), Timestamp, StorageDiff>>,
+    > + 'static,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to write the files of the sink.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+    let config_set = storage_configuration.config_set();
+    let buffer_min_capacity = KAFKA_BUFFERED_EVENT_RESIZE_THRESHOLD_ELEMENTS.handle(config_set);
+    let progress_commit_interval = S3_SINK_PROGRESS_COMMIT_INTERVAL.handle(config_set);
+    let params = CopyToParameters {
+        parquet_row_group_ratio: S3_SINK_PARQUET_ROW_GROUP_FILE_RATIO.get(config_set),
+        arrow_builder_buffer_ratio: S3_SINK_ARROW_BUILDER_BUFFER_RATIO.get(config_set),
+        s3_multipart_part_size_bytes: S3_SINK_MULTIPART_PART_SIZE_BYTES.get(config_set),
+    };
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let as_of = sink.as_of.clone();
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let write_handle = write_handle.await?;
+
+            let layout = S3SinkLayout::new(&connection.uri)?;
+            let sdk_config = connection
+                .aws_connection
+                .connection
+                .load_sdk_config(
+                    &storage_configuration.connection_context,
+                    connection.aws_connection.connection_id,
+                    InTask::Yes,
+                )
+                .await?;
+            let client = S3SinkClient::new(sdk_config, layout);
+            let claimed = client
+                .claim(sink_id)
+                .await
+                .with_context(|| format!("claiming {}", connection.uri))?;
+
+            let resume_upper = Antichain::from_iter(claimed.frontier.map(Timestamp::from));
+
+            info!("{name}: writing to {}", connection.uri);
+            let Some(mut progress) = SinkProgress::new(
+                &name,
+                as_of,
+                resume_upper.clone(),
+                write_handle,
+                write_frontier,
+                buffer_min_capacity,
+            )?
+            else {
+                return Ok(());
+            };
+
+            let mut writer = ChangeWriter {
+                sink_id,
+                client,
+                desc: Arc::new(file_desc(&connection.value_desc)?),
+                connection,
+                params,
+                statistics,
+                open: None,
+                staged_messages: 0,
+                staged_bytes: 0,
+                row_buf: Row::default(),
+            };
+            let mut committed = resume_upper;
+            let mut last_commit = Instant::now();
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for (update, time, _diff) in batch {
+                            // Changes at the upper can be written out immediately, as they
+                            // belong to the next timestamp to be closed.
+                            if let Some(update) = progress.accept(update, time) {
+                                writer.add(time, update).await?;
+                            }
+                        }
+                    }
+                    Event::Progress(frontier) => {
+                        let Some(ready) = progress.ready(&frontier) else {
+                            continue;
+                        };
+
+                        // Close the timestamp at the old upper, followed by every buffered
+                        // timestamp that the frontier has passed.
+                        let mut closed_any = writer.close().await?;
+                        for (update, time) in ready.drain(..) {
+                            if writer.open_timestamp().is_some_and(|open| open != time) {
+                                closed_any |= writer.close().await?;
+                            }
+                            writer.add(time, update).await?;
+                        }
+                        closed_any |= writer.close().await?;
+
+                        // Record progress after closing any timestamps, and otherwise only
+                        // periodically. The final frontier is always recorded.
+                        if committed != frontier
+                            && (closed_any
+                                || frontier.is_empty()
+                                || last_commit.elapsed() >= progress_commit_interval.get())
+                        {
+                            debug!("{name}: recording progress {}", frontier.pretty());
+                            writer.record_progress(&frontier).await?;
+                            committed = frontier.clone();
+                            last_commit = Instant::now();
+                        }
+
+                        if !progress.advance(&frontier, &committed).await {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    });
+
+    let statuses = errors.map(|error: Rc<anyhow::Error>| HealthStatusMessage {
+        id: None,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace: StatusNamespace::S3,
+    });
+
+    (statuses, button.press_on_drop())
+}
+
+/// Writes the changes of one timestamp at a time.
+struct ChangeWriter {
+    sink_id: GlobalId,
+    client: S3SinkClient,
+    /// The description of the rows in the data files.
+    desc: Arc<RelationDesc>,
+    connection: S3SinkConnection,
+    params: CopyToParameters,
+    statistics: SinkStatistics,
+    /// The timestamp whose files are currently being written, if any.
+    open: Option<(Timestamp, TimestampWriter)>,
+    staged_messages: u64,
+    staged_bytes: u64,
+    row_buf: Row,
+}
+
+impl ChangeWriter {
+    /// Returns the timestamp whose files are currently being written, if any.
+    fn open_timestamp(&self) -> Option<Timestamp> {
+        self.open.as_ref().map(|(time, _)| *time)
+    }
+
+    /// Writes the retraction and the insertion of `update` at `time`.
+    async fn add(
+        &mut self,
+        time: Timestamp,
+        (_key, value): (Option<Row>, DiffPair<Row>),
+    ) -> Result<(), anyhow::Error> {
+        let writer = match &mut self.open {
+            Some((open, writer)) => {
+                assert_eq!(*open, time, "changes must be added in timestamp order");
+                writer
+            }
+            None => {
+                let writer = self.client.timestamp_writer(
+                    Arc::clone(&self.desc),
+                    self.connection.format.clone(),
+                    self.connection.max_file_size,
+                    self.params.clone(),
+                    u64::from(time),
+                )?;
+                &mut self.open.insert((time, writer)).1
+            }
+        };
+        for (row, diff) in [(value.before, -1), (value.after, 1)] {
+            let Some(row) = row else { continue };
+            let mut packer = self.row_buf.packer();
+            packer.push(Datum::MzTimestamp(time));
+            packer.push(Datum::Int64(diff));
+            packer.extend_by_row(&row);
+            writer.append_row(&self.row_buf).await?;
+
+            let size = u64::cast_from(row.byte_len());
+            self.statistics.inc_messages_staged_by(1);
+            self.statistics.inc_bytes_staged_by(size);
+            self.staged_messages += 1;
+            self.staged_bytes += size;
+        }
+        Ok(())
+    }
+
+    /// Finishes the files of the open timestamp and writes its manifest.
+    ///
+    /// Returns whether a timestamp was closed.
+    async fn close(&mut self) -> Result<bool, anyhow::Error> {
+        let Some((time, writer)) = self.open.take() else {
+            return Ok(false);
+        };
+        let files = writer.finish().await?;
+        let manifest = Manifest {
+            sink_id: self.sink_id.to_string(),
+            timestamp: u64::from(time),
+            format: self.connection.format.get_format_name().to_string(),
+            files,
+        };
+        self.client.write_manifest(&manifest).await?;
+        self.statistics
+            .inc_messages_committed_by(self.staged_messages);
+        self.statistics.inc_bytes_committed_by(self.staged_bytes);
+        self.staged_messages = 0;
+        self.staged_bytes = 0;
+        Ok(true)
+    }
+
+    /// Records that all manifests up to `frontier` have been written.
+    async fn record_progress(&self, frontier: &Antichain<Timestamp>) -> Result<(), anyhow::Error> {
+        let progress = Progress {
+            sink_id: self.sink_id.to_string(),
+            frontier: frontier.as_option().map(|t| u64::from(*t)),
+        };
+        self.client.write_progress(&progress).await
+    }
+}
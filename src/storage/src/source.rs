@@ -35,6 +35,8 @@ pub mod types;
 
 pub mod generator;
 mod kafka;
+mod kinesis;
+mod mongodb;
 mod mysql;
 mod postgres;
 mod sql_server;
@@ -0,0 +1,630 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the ingestion dataflow of a [`KinesisSourceConnection`].
+//!
+//! # Shards
+//!
+//! A Kinesis stream is made up of shards, each of which is an ordered log of records identified
+//! by increasing sequence numbers. Resharding closes shards and opens new ones: a split closes
+//! one parent and opens two children, and a merge closes two parents and opens one child. Shard
+//! IDs end in a counter that Kinesis increments for every new shard, which we use as the
+//! partition of a [`KinesisTimestamp`].
+//!
+//! # Reading
+//!
+//! A single worker reads every shard. A shard is only read once all of its parents have been
+//! read to their end, so that records with the same partition key are emitted in the order in
+//! which they were written. The shard list is refreshed periodically to discover resharding.
+//!
+//! # Progress
+//!
+//! The frontier of an open shard is the sequence number after the last record read from it. A
+//! closed shard that has been read to its end is dropped from the frontier, and every partition
+//! above the highest known shard is held at the minimum so that future shards are read from
+//! their beginning. When resuming, shards that are not covered by the resume upper have already
+//! been read to their end and are skipped.
+//!
+//! # Error handling
+//!
+//! [`DefiniteError`]s, like the stream being deleted, are written into all outputs and are
+//! permanent. [`TransientError`]s are reported in the source status and cause the dataflow to
+//! restart.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use aws_sdk_kinesis::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_kinesis::types::ShardIteratorType;
+use differential_dataflow::AsCollection;
+use differential_dataflow::containers::TimelyStack;
+use itertools::Itertools;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_repr::{Datum, Diff, GlobalId, Row};
+use mz_storage_types::errors::{DataflowError, SourceError, SourceErrorDetails};
+use mz_storage_types::sources::kinesis::{
+    GET_RECORDS_LIMIT, KinesisTimestamp, POLL_INTERVAL, SHARD_REFRESH_INTERVAL, SequenceNumber,
+    shard_index,
+};
+use mz_storage_types::sources::{
+    KinesisSourceConnection, SourceExport, SourceExportDetails, SourceTimestamp,
+};
+use mz_timely_util::builder_async::{
+    AsyncOutputHandle, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use mz_timely_util::containers::stack::AccountedStackBuilder;
+use mz_timely_util::order::Partitioned;
+use timely::PartialOrder;
+use timely::container::CapacityContainerBuilder;
+use timely::dataflow::channels::pushers::Tee;
+use timely::dataflow::operators::core::Partition;
+use timely::dataflow::operators::{CapabilitySet, Concat, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::RawSourceCreationConfig;
+use crate::source::types::{
+    Probe, ProgressStatisticsUpdate, SignaledFuture, SourceMessage, SourceRender, StackedCollection,
+};
+
+/// Used as a partition ID to determine the worker that is responsible for
+/// reading from Kinesis.
+static READER: &str = "reader";
+
+impl SourceRender for KinesisSourceConnection {
+    type Time = KinesisTimestamp;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::Kinesis;
+
+    /// Render the ingestion dataflow. This function only connects things together and contains no
+    /// actual processing logic.
+    fn render<G: Scope<Timestamp = KinesisTimestamp>>(
+        self,
+        scope: &mut G,
+        config: &RawSourceCreationConfig,
+        _resume_uppers: impl futures::Stream<Item = Antichain<KinesisTimestamp>> + 'static,
+        _start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        BTreeMap<GlobalId, StackedCollection<G, Result<SourceMessage, DataflowError>>>,
+        Stream<G, Infallible>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Option<Stream<G, Probe<KinesisTimestamp>>>,
+        Vec<PressOnDropButton>,
+    ) {
+        // Collect the source outputs that we will be exporting.
+        let mut outputs = vec![];
+        for (output_index, (id, export)) in config.source_exports.iter().enumerate() {
+            let SourceExport {
+                details,
+                storage_metadata: _,
+                data_config: _,
+            } = export;
+
+            match details {
+                SourceExportDetails::Kinesis(_) => {}
+                // This is an export that doesn't need any data output to it.
+                SourceExportDetails::None => continue,
+                other => unreachable!("unexpected source export details: {other:?}"),
+            }
+
+            let resume_upper = config
+                .source_resume_uppers
+                .get(id)
+                .expect("missing resume upper")
+                .iter()
+                .map(KinesisTimestamp::decode_row);
+
+            outputs.push(SourceOutputInfo {
+                output_index,
+                resume_upper: Antichain::from_iter(resume_upper),
+            });
+        }
+
+        let (data, uppers, errors, stats, token) =
+            render_reader(scope.clone(), config.clone(), self, outputs);
+
+        let partition_count = u64::cast_from(config.source_exports.len());
+        let data_streams: Vec<_> = data.inner.partition::<CapacityContainerBuilder<_>, _, _>(
+            partition_count,
+            |((output, data), time, diff): &(
+                (usize, Result<SourceMessage, DataflowError>),
+                KinesisTimestamp,
+                Diff,
+            )| {
+                let output = u64::cast_from(*output);
+                (output, (data.clone(), time.clone(), diff.clone()))
+            },
+        );
+        let mut data_collections = BTreeMap::new();
+        for (id, data_stream) in config.source_exports.keys().zip_eq(data_streams) {
+            data_collections.insert(*id, data_stream.as_collection());
+        }
+
+        let health_init = std::iter::once(HealthStatusMessage {
+            id: None,
+            namespace: Self::STATUS_NAMESPACE,
+            update: HealthStatusUpdate::Running,
+        })
+        .to_stream(scope);
+
+        let health_errs = errors.map(move |err| {
+            // This update will cause the dataflow to restart
+            let err_string = err.display_with_causes().to_string();
+            let update = HealthStatusUpdate::halting(err_string, None);
+
+            HealthStatusMessage {
+                id: None,
+                namespace: Self::STATUS_NAMESPACE,
+                update,
+            }
+        });
+        let health = health_init.concat(&health_errs);
+
+        (data_collections, uppers, health, stats, None, vec![token])
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SourceOutputInfo {
+    output_index: usize,
+    resume_upper: Antichain<KinesisTimestamp>,
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ReplicationError {
+    #[error(transparent)]
+    Transient(#[from] Rc<TransientError>),
+    #[error(transparent)]
+    Definite(#[from] Rc<DefiniteError>),
+}
+
+/// A transient error that never ends up in the collection of a specific table.
+#[derive(Debug, thiserror::Error)]
+pub enum TransientError {
+    #[error("shard {0} is missing a shard iterator")]
+    MissingShardIterator(String),
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+}
+
+/// A definite error that always ends up in the collection of a specific table.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DefiniteError {
+    #[error("stream was deleted: {0}")]
+    StreamDeleted(String),
+    #[error("shard {0} expired from the stream before it was read to its end")]
+    ShardExpired(u64),
+}
+
+impl From<DefiniteError> for DataflowError {
+    fn from(err: DefiniteError) -> Self {
+        let m = err.to_string().into();
+        DataflowError::SourceError(Box::new(SourceError {
+            error: SourceErrorDetails::Other(m),
+        }))
+    }
+}
+
+/// The read state of a single shard.
+#[derive(Debug)]
+struct ShardState {
+    /// The ID of the shard, e.g. `shardId-000000000003`.
+    id: String,
+    /// The partitions of the shards this shard was split or merged from.
+    parents: Vec<u64>,
+    /// The sequence number after the last record read from this shard.
+    upper: SequenceNumber,
+    /// Whether the shard is closed and has been read to its end.
+    finished: bool,
+    /// The iterator to continue reading the shard with, if one was obtained.
+    iterator: Option<String>,
+}
+
+type StackedAsyncOutputHandle<T, D> = AsyncOutputHandle<
+    T,
+    AccountedStackBuilder<CapacityContainerBuilder<TimelyStack<(D, T, Diff)>>>,
+    Tee<T, TimelyStack<(D, T, Diff)>>,
+>;
+
+fn render_reader<G: Scope<Timestamp = KinesisTimestamp>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: KinesisSourceConnection,
+    outputs: Vec<SourceOutputInfo>,
+) -> (
+    StackedCollection<G, (usize, Result<SourceMessage, DataflowError>)>,
+    Stream<G, Infallible>,
+    Stream<G, ReplicationError>,
+    Stream<G, ProgressStatisticsUpdate>,
+    PressOnDropButton,
+) {
+    let op_name = format!("KinesisReader({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (data_output, data_stream) = builder.new_output::<AccountedStackBuilder<_>>();
+    let (_upper_output, upper_stream) = builder.new_output::<CapacityContainerBuilder<_>>();
+    // Kinesis does not expose the latest sequence number of a shard without reading it, so
+    // there are no offsets to report.
+    let (_stats_output, stats_stream) = builder.new_output::<CapacityContainerBuilder<_>>();
+
+    // Captures DefiniteErrors that affect the entire source, including all outputs
+    let (definite_error_handle, definite_errors) =
+        builder.new_output::<CapacityContainerBuilder<_>>();
+
+    let (button, transient_errors) = builder.build_fallible(move |caps| {
+        let busy_signal = Arc::clone(&config.busy_signal);
+        Box::pin(SignaledFuture::new(busy_signal, async move {
+            let [
+                data_cap_set,
+                upper_cap_set,
+                _stats_cap,
+                definite_error_cap_set,
+            ]: &mut [_; 4] = caps.try_into().unwrap();
+
+            // Only run the reader on the worker responsible for it.
+            if !config.responsible_for(READER) {
+                return Ok::<_, TransientError>(());
+            }
+
+            let client = connection
+                .client(&config.config.connection_context, InTask::Yes)
+                .await?;
+
+            let output_indexes: Vec<_> = outputs.iter().map(|o| o.output_index).collect();
+            let resume_upper = Antichain::from_iter(
+                outputs
+                    .iter()
+                    .flat_map(|output| output.resume_upper.iter().cloned()),
+            );
+
+            let config_set = config.config.config_set();
+            let poll_interval = POLL_INTERVAL.handle(config_set);
+            let refresh_interval = SHARD_REFRESH_INTERVAL.handle(config_set);
+            let records_limit = GET_RECORDS_LIMIT.handle(config_set);
+
+            let mut shards: BTreeMap<u64, ShardState> = BTreeMap::new();
+            let mut last_refresh: Option<Instant> = None;
+
+            loop {
+                if last_refresh.is_none_or(|t| t.elapsed() >= refresh_interval.get()) {
+                    let listed = match mz_aws_util::kinesis::list_shards(
+                        &client,
+                        &connection.stream,
+                    )
+                    .await
+                    {
+                        Ok(listed) => listed,
+                        Err(err) if is_not_found(&err) => {
+                            let err = DefiniteError::StreamDeleted(connection.stream.clone());
+                            return Ok(return_definite_error(
+                                err,
+                                &output_indexes,
+                                &data_output,
+                                data_cap_set,
+                                &definite_error_handle,
+                                definite_error_cap_set,
+                            )
+                            .await);
+                        }
+                        Err(err) => return Err(anyhow::Error::from(err).into()),
+                    };
+
+                    let is_first_listing = last_refresh.is_none();
+                    let max_known = shards.last_key_value().map(|(idx, _)| *idx);
+                    for shard in listed {
+                        let idx = shard_index(shard.shard_id()).ok_or_else(|| {
+                            anyhow::anyhow!("unexpected shard ID {}", shard.shard_id())
+                        })?;
+                        // Kinesis assigns increasing IDs to new shards, so only shards above
+                        // the highest known one are new.
+                        if max_known.is_some_and(|max| idx <= max) {
+                            continue;
+                        }
+                        let parents = [shard.parent_shard_id(), shard.adjacent_parent_shard_id()]
+                            .into_iter()
+                            .flatten()
+                            .filter_map(shard_index)
+                            .collect();
+                        // Shards discovered after startup have never been read, while shards
+                        // discovered at startup continue where the resume upper left off.
+                        let upper = if is_first_listing {
+                            resume_position(&resume_upper, idx)
+                        } else {
+                            Some(SequenceNumber::MIN)
+                        };
+                        shards.insert(
+                            idx,
+                            ShardState {
+                                id: shard.shard_id().to_string(),
+                                parents,
+                                upper: upper.unwrap_or(SequenceNumber::MIN),
+                                finished: upper.is_none(),
+                                iterator: None,
+                            },
+                        );
+                    }
+
+                    if is_first_listing {
+                        // A shard that was partially read but no longer exists has had some
+                        // of its records trimmed before we could read them.
+                        for ts in resume_upper.elements() {
+                            if let Some(idx) = ts.interval().singleton() {
+                                if *ts.timestamp() != SequenceNumber::MIN
+                                    && !shards.contains_key(idx)
+                                {
+                                    return Ok(return_definite_error(
+                                        DefiniteError::ShardExpired(*idx),
+                                        &output_indexes,
+                                        &data_output,
+                                        data_cap_set,
+                                        &definite_error_handle,
+                                        definite_error_cap_set,
+                                    )
+                                    .await);
+                                }
+                            }
+                        }
+                        tracing::info!(%config.id, ?shards, "starting kinesis reader");
+                    }
+                    last_refresh = Some(Instant::now());
+                }
+
+                let mut records_read: u64 = 0;
+                let ready: Vec<u64> = shards
+                    .iter()
+                    .filter(|(_, shard)| !shard.finished)
+                    .filter(|(_, shard)| {
+                        shard
+                            .parents
+                            .iter()
+                            .all(|parent| shards.get(parent).is_none_or(|p| p.finished))
+                    })
+                    .map(|(idx, _)| *idx)
+                    .collect();
+                for idx in ready {
+                    let shard = shards.get_mut(&idx).expect("known shard");
+
+                    let iterator = match shard.iterator.take() {
+                        Some(iterator) => iterator,
+                        None => {
+                            let request = client
+                                .get_shard_iterator()
+                                .stream_name(&connection.stream)
+                                .shard_id(&shard.id);
+                            let request = match shard.upper.predecessor() {
+                                None => request.shard_iterator_type(ShardIteratorType::TrimHorizon),
+                                Some(last) => request
+                                    .shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+                                    .starting_sequence_number(last.to_string()),
+                            };
+                            match request.send().await {
+                                Ok(response) => response
+                                    .shard_iterator()
+                                    .ok_or_else(|| {
+                                        TransientError::MissingShardIterator(shard.id.clone())
+                                    })?
+                                    .to_string(),
+                                Err(err) if is_not_found(&err) => {
+                                    let err =
+                                        DefiniteError::StreamDeleted(connection.stream.clone());
+                                    return Ok(return_definite_error(
+                                        err,
+                                        &output_indexes,
+                                        &data_output,
+                                        data_cap_set,
+                                        &definite_error_handle,
+                                        definite_error_cap_set,
+                                    )
+                                    .await);
+                                }
+                                Err(err) => return Err(anyhow::Error::from(err).into()),
+                            }
+                        }
+                    };
+
+                    let limit = i32::try_from(records_limit.get()).unwrap_or(i32::MAX);
+                    let response = client
+                        .get_records()
+                        .shard_iterator(&iterator)
+                        .limit(limit)
+                        .send()
+                        .await;
+                    let response = match response {
+                        Ok(response) => response,
+                        Err(err) => match err.code() {
+                            // Iterators expire after five minutes, so obtain a new one next time.
+                            Some("ExpiredIteratorException") => continue,
+                            // The shard is being read too quickly, so back off until next time.
+                            Some("ProvisionedThroughputExceededException") => {
+                                shard.iterator = Some(iterator);
+                                continue;
+                            }
+                            Some("ResourceNotFoundException") => {
+                                let err = DefiniteError::StreamDeleted(connection.stream.clone());
+                                return Ok(return_definite_error(
+                                    err,
+                                    &output_indexes,
+                                    &data_output,
+                                    data_cap_set,
+                                    &definite_error_handle,
+                                    definite_error_cap_set,
+                                )
+                                .await);
+                            }
+                            _ => return Err(anyhow::Error::from(err).into()),
+                        },
+                    };
+
+                    for record in response.records() {
+                        let sequence_number: SequenceNumber = record.sequence_number().parse()?;
+                        if sequence_number < shard.upper {
+                            continue;
+                        }
+                        let time = Partitioned::new_singleton(idx, sequence_number);
+                        let message = SourceMessage {
+                            key: Row::default(),
+                            value: Row::pack([Datum::Bytes(record.data().as_ref())]),
+                            metadata: Row::default(),
+                        };
+                        let cap = data_cap_set
+                            .iter()
+                            .find(|cap| cap.time().less_equal(&time))
+                            .expect("holding a capability for every unfinished shard");
+                        for output in &outputs {
+                            if output.resume_upper.less_equal(&time) {
+                                let update =
+                                    ((output.output_index, Ok(message.clone())), time, Diff::ONE);
+                                data_output.give_fueled(cap, update).await;
+                            }
+                        }
+                        shard.upper = sequence_number
+                            .successor()
+                            .expect("sequence numbers have at most 128 digits");
+                        records_read += 1;
+                    }
+
+                    match response.next_shard_iterator() {
+                        Some(next) => shard.iterator = Some(next.to_string()),
+                        // The shard was closed by resharding and has been read to its end.
+                        None => {
+                            tracing::info!(%config.id, shard = %shard.id, "finished closed shard");
+                            shard.finished = true;
+                        }
+                    }
+                }
+
+                let upper = shards_frontier(&shards);
+                data_cap_set.downgrade(upper.iter());
+                upper_cap_set.downgrade(upper.iter());
+
+                if records_read == 0 {
+                    tokio::time::sleep(poll_interval.get()).await;
+                }
+            }
+        }))
+    });
+
+    let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
+
+    (
+        data_stream.as_collection(),
+        upper_stream,
+        errors,
+        stats_stream,
+        button.press_on_drop(),
+    )
+}
+
+/// Returns the position in shard `idx` from which reading should resume, or `None` if the resume
+/// upper does not cover the shard because it has already been read to its end.
+fn resume_position(resume_upper: &Antichain<KinesisTimestamp>, idx: u64) -> Option<SequenceNumber> {
+    resume_upper
+        .iter()
+        .filter(|ts| ts.interval().contains(&idx))
+        .map(|ts| *ts.timestamp())
+        .min()
+}
+
+/// Returns the frontier of the stream given the read state of its shards.
+fn shards_frontier(shards: &BTreeMap<u64, ShardState>) -> Antichain<KinesisTimestamp> {
+    let mut frontier = Antichain::new();
+    for (idx, shard) in shards {
+        if !shard.finished {
+            frontier.insert(Partitioned::new_singleton(*idx, shard.upper));
+        }
+    }
+    let future = shards.last_key_value().map_or(0, |(idx, _)| idx + 1);
+    frontier.insert(Partitioned::new_range(
+        future,
+        u64::MAX,
+        SequenceNumber::MIN,
+    ));
+    frontier
+}
+
+fn is_not_found<E: ProvideErrorMetadata>(err: &SdkError<E>) -> bool {
+    err.code() == Some("ResourceNotFoundException")
+}
+
+/// Writes a definite error that affects the whole source into all of its outputs and reports it
+/// upstream.
+async fn return_definite_error(
+    err: DefiniteError,
+    outputs: &[usize],
+    data_handle: &StackedAsyncOutputHandle<
+        KinesisTimestamp,
+        (usize, Result<SourceMessage, DataflowError>),
+    >,
+    data_cap_set: &CapabilitySet<KinesisTimestamp>,
+    definite_error_handle: &AsyncOutputHandle<
+        KinesisTimestamp,
+        CapacityContainerBuilder<Vec<ReplicationError>>,
+        Tee<KinesisTimestamp, Vec<ReplicationError>>,
+    >,
+    definite_error_cap_set: &CapabilitySet<KinesisTimestamp>,
+) {
+    for output_index in outputs {
+        let time = data_cap_set[0].time().clone();
+        let update = ((*output_index, Err(err.clone().into())), time, Diff::ONE);
+        data_handle.give_fueled(&data_cap_set[0], update).await;
+    }
+    definite_error_handle.give(
+        &definite_error_cap_set[0],
+        ReplicationError::Definite(Rc::new(err)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shard(upper: u64, finished: bool) -> ShardState {
+        ShardState {
+            id: String::new(),
+            parents: vec![],
+            upper: upper.to_string().parse().unwrap(),
+            finished,
+            iterator: None,
+        }
+    }
+
+    #[mz_ore::test]
+    fn frontier_after_resharding() {
+        // Shard 0 was split into shards 1 and 2, which were merged into shard 3.
+        let shards = BTreeMap::from([
+            (0, shard(10, true)),
+            (1, shard(20, true)),
+            (2, shard(30, false)),
+            (3, shard(0, false)),
+        ]);
+        let frontier = shards_frontier(&shards);
+        let seq = |n: u64| -> SequenceNumber { n.to_string().parse().unwrap() };
+        assert_eq!(
+            frontier,
+            Antichain::from_iter([
+                Partitioned::new_singleton(2, seq(30)),
+                Partitioned::new_singleton(3, SequenceNumber::MIN),
+                Partitioned::new_range(4, u64::MAX, SequenceNumber::MIN),
+            ])
+        );
+
+        // Finished shards are not covered by the frontier, while shards in the future range
+        // start at their beginning.
+        assert_eq!(resume_position(&frontier, 0), None);
+        assert_eq!(resume_position(&frontier, 2), Some(seq(30)));
+        assert_eq!(resume_position(&frontier, 7), Some(SequenceNumber::MIN));
+    }
+}
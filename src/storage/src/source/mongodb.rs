@@ -0,0 +1,275 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the ingestion dataflow of a [`MongoDbSourceConnection`].
+//!
+//! This dataflow is split into Replication and Statistics operators.
+//!
+//! # Replication
+//!
+//! The replication operator snapshots every collection that has not yet been ingested at a single
+//! cluster time and then tails a change stream over all of the source's collections, starting
+//! from the earliest point any of them still needs. Updates are timestamped with the cluster time
+//! of the change that produced them, which is a total order over the replica set's history.
+//!
+//! See the [replication] module for more information.
+//!
+//! # Statistics
+//!
+//! The statistics operator periodically probes the replica set for its latest operation time and
+//! reports it alongside the durably committed frontier.
+//!
+//! # Error handling
+//!
+//! [`DefiniteError`]s are errors that will reoccur no matter how often the source restarts, like
+//! a collection being dropped. They are written into the affected collections and are permanent.
+//!
+//! [`TransientError`]s are any errors that can happen for reasons that are unrelated to the data
+//! itself, like connection failures. These are reported in the source status and cause the
+//! dataflow to restart.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use differential_dataflow::AsCollection;
+use differential_dataflow::containers::TimelyStack;
+use itertools::Itertools;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_repr::{Diff, GlobalId};
+use mz_storage_types::errors::{DataflowError, SourceError, SourceErrorDetails};
+use mz_storage_types::sources::{
+    MongoDbSourceConnection, MzOffset, SourceExport, SourceExportDetails, SourceTimestamp,
+};
+use mz_timely_util::builder_async::{AsyncOutputHandle, PressOnDropButton};
+use mz_timely_util::containers::stack::AccountedStackBuilder;
+use timely::container::CapacityContainerBuilder;
+use timely::dataflow::channels::pushers::Tee;
+use timely::dataflow::operators::core::Partition;
+use timely::dataflow::operators::{CapabilitySet, Concat, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::RawSourceCreationConfig;
+use crate::source::types::{
+    Probe, ProgressStatisticsUpdate, SourceMessage, SourceRender, StackedCollection,
+};
+
+mod replication;
+mod statistics;
+
+impl SourceRender for MongoDbSourceConnection {
+    type Time = MzOffset;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::MongoDb;
+
+    /// Render the ingestion dataflow. This function only connects things together and contains no
+    /// actual processing logic.
+    fn render<G: Scope<Timestamp = MzOffset>>(
+        self,
+        scope: &mut G,
+        config: &RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<MzOffset>> + 'static,
+        _start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        BTreeMap<GlobalId, StackedCollection<G, Result<SourceMessage, DataflowError>>>,
+        Stream<G, Infallible>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Option<Stream<G, Probe<MzOffset>>>,
+        Vec<PressOnDropButton>,
+    ) {
+        // Collect the source outputs that we will be exporting.
+        let mut source_outputs = BTreeMap::new();
+        for (output_index, (id, export)) in config.source_exports.iter().enumerate() {
+            let SourceExport {
+                details,
+                storage_metadata: _,
+                data_config: _,
+            } = export;
+
+            let details = match details {
+                SourceExportDetails::MongoDb(details) => details,
+                // This is an export that doesn't need any data output to it.
+                SourceExportDetails::None => continue,
+                other => unreachable!("unexpected source export details: {other:?}"),
+            };
+
+            let resume_upper = config
+                .source_resume_uppers
+                .get(id)
+                .expect("missing resume upper")
+                .iter()
+                .map(MzOffset::decode_row);
+
+            let output_info = SourceOutputInfo {
+                output_index,
+                database: details.database.clone(),
+                collection: details.collection.clone(),
+                resume_upper: Antichain::from_iter(resume_upper),
+            };
+            source_outputs.insert(*id, output_info);
+        }
+
+        let (repl_updates, uppers, repl_err, snapshot_stats, repl_token) =
+            replication::render(scope.clone(), config.clone(), self.clone(), source_outputs);
+
+        let (stats_stream, stats_err, probe_stream, stats_token) =
+            statistics::render(scope.clone(), config.clone(), self, resume_uppers);
+
+        let stats_stream = stats_stream.concat(&snapshot_stats);
+
+        let partition_count = u64::cast_from(config.source_exports.len());
+        let data_streams: Vec<_> = repl_updates
+            .inner
+            .partition::<CapacityContainerBuilder<_>, _, _>(
+                partition_count,
+                |((output, data), time, diff): &(
+                    (usize, Result<SourceMessage, DataflowError>),
+                    MzOffset,
+                    Diff,
+                )| {
+                    let output = u64::cast_from(*output);
+                    (output, (data.clone(), time.clone(), diff.clone()))
+                },
+            );
+        let mut data_collections = BTreeMap::new();
+        for (id, data_stream) in config.source_exports.keys().zip_eq(data_streams) {
+            data_collections.insert(*id, data_stream.as_collection());
+        }
+
+        let health_init = std::iter::once(HealthStatusMessage {
+            id: None,
+            namespace: Self::STATUS_NAMESPACE,
+            update: HealthStatusUpdate::Running,
+        })
+        .to_stream(scope);
+
+        let health_errs = repl_err.concat(&stats_err).map(move |err| {
+            // This update will cause the dataflow to restart
+            let err_string = err.display_with_causes().to_string();
+            let update = HealthStatusUpdate::halting(err_string, None);
+
+            HealthStatusMessage {
+                id: None,
+                namespace: Self::STATUS_NAMESPACE,
+                update,
+            }
+        });
+        let health = health_init.concat(&health_errs);
+
+        (
+            data_collections,
+            uppers,
+            health,
+            stats_stream,
+            Some(probe_stream),
+            vec![repl_token, stats_token],
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SourceOutputInfo {
+    output_index: usize,
+    /// The database containing the upstream collection.
+    database: String,
+    /// The name of the upstream collection.
+    collection: String,
+    resume_upper: Antichain<MzOffset>,
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ReplicationError {
+    #[error(transparent)]
+    Transient(#[from] Rc<TransientError>),
+    #[error(transparent)]
+    Definite(#[from] Rc<DefiniteError>),
+}
+
+/// A transient error that never ends up in the collection of a specific table.
+#[derive(Debug, thiserror::Error)]
+pub enum TransientError {
+    #[error("stream ended prematurely")]
+    ReplicationEOF,
+    #[error("change stream event is missing its {0}")]
+    MalformedEvent(&'static str),
+    #[error(transparent)]
+    MongoDb(#[from] mongodb::error::Error),
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+}
+
+/// A definite error that always ends up in the collection of a specific table.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DefiniteError {
+    #[error("unable to decode: {0}")]
+    ValueDecodeError(String),
+    #[error("collection was dropped: {0}")]
+    CollectionDropped(String),
+    #[error("collection was renamed: {0}")]
+    CollectionRenamed(String),
+    #[error("source was created against replica set {expected:?} but is connected to {actual:?}")]
+    ReplicaSetChanged { expected: String, actual: String },
+    #[error("the oplog no longer contains the change stream resume point {0}")]
+    ChangeStreamHistoryLost(MzOffset),
+}
+
+impl From<DefiniteError> for DataflowError {
+    fn from(err: DefiniteError) -> Self {
+        let m = err.to_string().into();
+        DataflowError::SourceError(Box::new(SourceError {
+            error: match &err {
+                DefiniteError::ValueDecodeError(_)
+                | DefiniteError::CollectionDropped(_)
+                | DefiniteError::CollectionRenamed(_) => SourceErrorDetails::Other(m),
+                DefiniteError::ReplicaSetChanged { .. }
+                | DefiniteError::ChangeStreamHistoryLost(_) => {
+                    SourceErrorDetails::Initialization(m)
+                }
+            },
+        }))
+    }
+}
+
+type StackedAsyncOutputHandle<T, D> = AsyncOutputHandle<
+    T,
+    AccountedStackBuilder<CapacityContainerBuilder<TimelyStack<(D, T, Diff)>>>,
+    Tee<T, TimelyStack<(D, T, Diff)>>,
+>;
+
+/// Writes a definite error that affects the whole source into all of its outputs and reports it
+/// upstream.
+async fn return_definite_error(
+    err: DefiniteError,
+    outputs: &[usize],
+    data_handle: &StackedAsyncOutputHandle<MzOffset, (usize, Result<SourceMessage, DataflowError>)>,
+    data_cap_set: &CapabilitySet<MzOffset>,
+    definite_error_handle: &AsyncOutputHandle<
+        MzOffset,
+        CapacityContainerBuilder<Vec<ReplicationError>>,
+        Tee<MzOffset, Vec<ReplicationError>>,
+    >,
+    definite_error_cap_set: &CapabilitySet<MzOffset>,
+) {
+    for output_index in outputs {
+        let update = (
+            (*output_index, Err(err.clone().into())),
+            MzOffset::from(u64::MAX),
+            Diff::ONE,
+        );
+        data_handle.give_fueled(&data_cap_set[0], update).await;
+    }
+    definite_error_handle.give(
+        &definite_error_cap_set[0],
+        ReplicationError::Definite(Rc::new(err)),
+    );
+}
@@ -0,0 +1,544 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the replication side of the [`MongoDbSourceConnection`] ingestion dataflow.
+//!
+//! # Snapshot
+//!
+//! Collections whose resume upper is still the minimum offset have never been ingested. The
+//! operator reads the replica set's current operation time `t0` and scans each such collection
+//! with a snapshot read concern at exactly `t0`, emitting every document at `offset(t0)`.
+//!
+//! # Change stream
+//!
+//! A single deployment-wide change stream, filtered down to the namespaces of the source's
+//! collections, is opened at the earliest cluster time any collection still needs: the resume
+//! upper of collections that have made progress and `offset(t0) + 1` for those that were just
+//! snapshotted. Events before a collection's own lower bound are skipped, so every collection
+//! observes each change exactly once.
+//!
+//! MongoDB does not report the previous version of a document, so every change is emitted as an
+//! upsert keyed by the document's `_id`: inserts, replacements and updates carry the current
+//! version of the document, and deletions carry `NULL`.
+//!
+//! # Progress
+//!
+//! The change stream is totally ordered by cluster time, so receiving an event at offset `t`
+//! proves that all events before `t` have been seen. When the server returns an empty batch, its
+//! post batch resume token carries the cluster time up to which the oplog was scanned, which lets
+//! idle sources make progress as well.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+
+use differential_dataflow::AsCollection;
+use futures::TryStreamExt;
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::change_stream::event::{OperationType, ResumeToken};
+use mongodb::error::ErrorKind;
+use mongodb::options::FullDocumentType;
+use mz_ore::future::InTask;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::{Datum, Diff, GlobalId, Row};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sources::mongodb::{
+    CHANGE_STREAM_MAX_AWAIT_TIME, SNAPSHOT_PROGRESS_REPORT_INTERVAL, cluster_time_to_offset,
+    offset_to_cluster_time, operation_time, replica_set_name,
+};
+use mz_storage_types::sources::{MongoDbSourceConnection, MzOffset};
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+use mz_timely_util::containers::stack::AccountedStackBuilder;
+use timely::container::CapacityContainerBuilder;
+use timely::dataflow::operators::{CapabilitySet, Concat, Map};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+
+use crate::source::RawSourceCreationConfig;
+use crate::source::mongodb::{
+    DefiniteError, ReplicationError, SourceOutputInfo, StackedAsyncOutputHandle, TransientError,
+    return_definite_error,
+};
+use crate::source::types::{
+    ProgressStatisticsUpdate, SignaledFuture, SourceMessage, StackedCollection,
+};
+
+/// Used as a partition ID to determine the worker that is responsible for
+/// reading data from MongoDB.
+static REPL_READER: &str = "reader";
+
+/// The server error code reported when a change stream cannot be resumed
+/// because the oplog no longer contains the requested cluster time.
+const CHANGE_STREAM_HISTORY_LOST: i32 = 286;
+
+pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: MongoDbSourceConnection,
+    outputs: BTreeMap<GlobalId, SourceOutputInfo>,
+) -> (
+    StackedCollection<G, (usize, Result<SourceMessage, DataflowError>)>,
+    Stream<G, Infallible>,
+    Stream<G, ReplicationError>,
+    Stream<G, ProgressStatisticsUpdate>,
+    PressOnDropButton,
+) {
+    let op_name = format!("MongoDbReplicationReader({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (data_output, data_stream) = builder.new_output::<AccountedStackBuilder<_>>();
+    let (_upper_output, upper_stream) = builder.new_output::<CapacityContainerBuilder<_>>();
+    let (stats_output, stats_stream) = builder.new_output::<CapacityContainerBuilder<_>>();
+
+    // Captures DefiniteErrors that affect the entire source, including all outputs
+    let (definite_error_handle, definite_errors) =
+        builder.new_output::<CapacityContainerBuilder<_>>();
+
+    let (button, transient_errors) = builder.build_fallible(move |caps| {
+        let busy_signal = Arc::clone(&config.busy_signal);
+        Box::pin(SignaledFuture::new(busy_signal, async move {
+            let [
+                data_cap_set,
+                upper_cap_set,
+                stats_cap,
+                definite_error_cap_set,
+            ]: &mut [_; 4] = caps.try_into().unwrap();
+
+            // Only run the replication reader on the worker responsible for it.
+            if !config.responsible_for(REPL_READER) {
+                return Ok::<_, TransientError>(());
+            }
+
+            let client = connection
+                .connection
+                .connect(
+                    &config.config.connection_context.secrets_reader,
+                    &config.config,
+                    InTask::Yes,
+                )
+                .await?;
+
+            let output_indexes: Vec<_> = outputs.values().map(|o| o.output_index).collect();
+
+            // Cluster times are only meaningful within the replica set that produced them, so
+            // refuse to resume against any other deployment.
+            let replica_set = replica_set_name(&client).await?;
+            if replica_set.as_deref() != Some(connection.details.replica_set.as_str()) {
+                let err = DefiniteError::ReplicaSetChanged {
+                    expected: connection.details.replica_set.clone(),
+                    actual: replica_set.unwrap_or_else(|| "a standalone server".into()),
+                };
+                return Ok(return_definite_error(
+                    err,
+                    &output_indexes,
+                    &data_output,
+                    data_cap_set,
+                    &definite_error_handle,
+                    definite_error_cap_set,
+                )
+                .await);
+            }
+
+            // Collections that have never made progress need to be snapshotted.
+            let needs_snapshot: Vec<_> = outputs
+                .values()
+                .filter(|output| *output.resume_upper == [MzOffset::minimum()])
+                .collect();
+
+            // Small helper closure.
+            let emit_stats = |cap, known: u64, staged: u64| {
+                let update = ProgressStatisticsUpdate::Snapshot {
+                    records_known: known,
+                    records_staged: staged,
+                };
+                tracing::debug!(?config.id, %known, %staged, "snapshot progress");
+                stats_output.give(cap, update);
+            };
+
+            let snapshot_offset = if needs_snapshot.is_empty() {
+                None
+            } else {
+                let snapshot_time = operation_time(&client).await?;
+                let snapshot_offset = cluster_time_to_offset(snapshot_time);
+                let snapshot_cap = data_cap_set.delayed(&snapshot_offset);
+                tracing::info!(%config.id, %snapshot_offset, "starting snapshot");
+
+                emit_stats(&stats_cap[0], 0, 0);
+
+                // The estimated count comes from collection metadata and is cheap to obtain, but
+                // may not match the number of documents visible at the snapshot time.
+                let mut records_known: u64 = 0;
+                for output in &needs_snapshot {
+                    let count = client
+                        .database(&output.database)
+                        .collection::<Document>(&output.collection)
+                        .estimated_document_count()
+                        .await?;
+                    records_known = records_known.saturating_add(count);
+                }
+                emit_stats(&stats_cap[0], records_known, 0);
+
+                let report_interval =
+                    SNAPSHOT_PROGRESS_REPORT_INTERVAL.handle(config.config.config_set());
+                let mut last_report = Instant::now();
+                let mut records_staged: u64 = 0;
+
+                for output in &needs_snapshot {
+                    let mut cursor = client
+                        .database(&output.database)
+                        .run_cursor_command(doc! {
+                            "find": output.collection.as_str(),
+                            "readConcern": {
+                                "level": "snapshot",
+                                "atClusterTime": snapshot_time,
+                            },
+                        })
+                        .await?;
+
+                    while let Some(document) = cursor.try_next().await? {
+                        records_staged = records_staged.saturating_add(1);
+                        if last_report.elapsed() > report_interval.get() {
+                            last_report = Instant::now();
+                            emit_stats(&stats_cap[0], records_known, records_staged);
+                        }
+
+                        let message = match document.get("_id") {
+                            Some(id) => {
+                                let id = id.clone();
+                                pack_upsert(&id, Some(document))
+                            }
+                            None => Err(missing_id(&document)),
+                        };
+                        let update = ((output.output_index, message), snapshot_offset, Diff::ONE);
+                        data_output.give_fueled(&snapshot_cap, update).await;
+                    }
+                }
+                emit_stats(&stats_cap[0], records_known, records_staged);
+
+                Some(snapshot_offset)
+            };
+
+            // The first offset each output needs to observe from the change stream, by namespace.
+            let mut namespaces: BTreeMap<(String, String), Vec<(usize, MzOffset)>> =
+                BTreeMap::new();
+            for output in outputs.values() {
+                let lower = match output.resume_upper.as_option() {
+                    Some(upper) if *upper == MzOffset::minimum() => {
+                        snapshot_offset.expect("snapshotted above") + 1
+                    }
+                    Some(upper) => *upper,
+                    None => unreachable!("empty resume upper?"),
+                };
+                namespaces
+                    .entry((output.database.clone(), output.collection.clone()))
+                    .or_default()
+                    .push((output.output_index, lower));
+            }
+
+            let Some(start_offset) = namespaces.values().flatten().map(|(_, lower)| *lower).min()
+            else {
+                // There is nothing to replicate, so hold the frontier where it is.
+                return Ok(std::future::pending::<()>().await);
+            };
+            data_cap_set.downgrade(&[start_offset]);
+            upper_cap_set.downgrade(&[start_offset]);
+
+            let filter: Vec<Bson> = namespaces
+                .keys()
+                .map(|(db, coll)| Bson::Document(doc! { "db": db.as_str(), "coll": coll.as_str() }))
+                .collect();
+            let stream = client
+                .watch()
+                .pipeline([doc! { "$match": { "ns": { "$in": filter } } }])
+                .full_document(FullDocumentType::UpdateLookup)
+                .start_at_operation_time(offset_to_cluster_time(start_offset))
+                .max_await_time(CHANGE_STREAM_MAX_AWAIT_TIME.get(config.config.config_set()))
+                .await;
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(err) if is_history_lost(&err) => {
+                    return Ok(return_definite_error(
+                        DefiniteError::ChangeStreamHistoryLost(start_offset),
+                        &output_indexes,
+                        &data_output,
+                        data_cap_set,
+                        &definite_error_handle,
+                        definite_error_cap_set,
+                    )
+                    .await);
+                }
+                Err(err) => return Err(err.into()),
+            };
+            tracing::info!(%config.id, %start_offset, "started change stream");
+
+            // Outputs whose collection was dropped or renamed and will not receive any more data.
+            let mut errored_outputs = BTreeSet::new();
+
+            // All changes made by a transaction share its commit's cluster time, but the upsert
+            // envelope requires at most one update per key and timestamp. Changes are therefore
+            // buffered until the cluster time advances, keeping only the last change to each
+            // document, which is its state when the transaction committed.
+            let mut pending_offset: Option<MzOffset> = None;
+            let mut pending: BTreeMap<(usize, Row), Row> = BTreeMap::new();
+
+            loop {
+                let event = match stream.next_if_any().await {
+                    Ok(event) => event,
+                    Err(err) if is_history_lost(&err) => {
+                        let resume_offset = *data_cap_set[0].time();
+                        return Ok(return_definite_error(
+                            DefiniteError::ChangeStreamHistoryLost(resume_offset),
+                            &output_indexes,
+                            &data_output,
+                            data_cap_set,
+                            &definite_error_handle,
+                            definite_error_cap_set,
+                        )
+                        .await);
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+
+                let Some(event) = event else {
+                    // The batch was empty, so the post batch resume token reflects how far the
+                    // server scanned the oplog without finding anything relevant.
+                    let Some(scanned) = stream.resume_token().and_then(|t| resume_token_offset(&t))
+                    else {
+                        continue;
+                    };
+                    let Some(pending_time) = pending_offset.take() else {
+                        if *data_cap_set[0].time() < scanned {
+                            data_cap_set.downgrade(&[scanned]);
+                            upper_cap_set.downgrade(&[scanned]);
+                        }
+                        continue;
+                    };
+                    if pending_time < scanned {
+                        flush_pending(&mut pending, pending_time, &data_output, data_cap_set).await;
+                        data_cap_set.downgrade(&[scanned]);
+                        upper_cap_set.downgrade(&[scanned]);
+                    } else {
+                        pending_offset = Some(pending_time);
+                    }
+                    continue;
+                };
+
+                let cluster_time = event
+                    .cluster_time
+                    .ok_or(TransientError::MalformedEvent("cluster time"))?;
+                let offset = cluster_time_to_offset(cluster_time);
+
+                if let Some(pending_time) = pending_offset.filter(|t| *t != offset) {
+                    flush_pending(&mut pending, pending_time, &data_output, data_cap_set).await;
+                    // Events arrive in cluster time order, so nothing before this event's
+                    // cluster time remains to be seen.
+                    data_cap_set.downgrade(&[offset]);
+                    upper_cap_set.downgrade(&[offset]);
+                }
+                pending_offset = Some(offset);
+
+                let Some(ns) = &event.ns else {
+                    return Err(TransientError::MalformedEvent("namespace"));
+                };
+                let Some(coll) = &ns.coll else {
+                    // Database-wide events are preceded by events for each collection.
+                    continue;
+                };
+                let Some(targets) = namespaces.get(&(ns.db.clone(), coll.clone())) else {
+                    continue;
+                };
+                let targets: Vec<usize> = targets
+                    .iter()
+                    .filter(|(output_index, lower)| {
+                        *lower <= offset && !errored_outputs.contains(output_index)
+                    })
+                    .map(|(output_index, _)| *output_index)
+                    .collect();
+                if targets.is_empty() {
+                    continue;
+                }
+
+                let qualified = format!("{}.{}", ns.db, coll);
+                let message = match event.operation_type {
+                    OperationType::Insert | OperationType::Update | OperationType::Replace => {
+                        // An update to a document that has since been deleted has no full
+                        // document. Its deletion follows later in the stream, so treating it
+                        // as a deletion now is equivalent.
+                        let document = event.full_document;
+                        event_key(event.document_key.as_ref())
+                            .and_then(|id| pack_upsert(&id, document))
+                    }
+                    OperationType::Delete => {
+                        event_key(event.document_key.as_ref()).and_then(|id| pack_upsert(&id, None))
+                    }
+                    OperationType::Drop | OperationType::Rename => {
+                        let err = match event.operation_type {
+                            OperationType::Drop => DefiniteError::CollectionDropped(qualified),
+                            _ => DefiniteError::CollectionRenamed(qualified),
+                        };
+                        tracing::warn!(%config.id, %err, "collection can no longer be replicated");
+                        for output_index in targets {
+                            let update =
+                                ((output_index, Err(err.clone().into())), offset, Diff::ONE);
+                            data_output.give_fueled(&data_cap_set[0], update).await;
+                            errored_outputs.insert(output_index);
+                        }
+                        continue;
+                    }
+                    // Any other event does not affect the contents of the collection.
+                    _ => continue,
+                };
+
+                match message {
+                    Ok(message) => {
+                        for output_index in targets {
+                            pending
+                                .insert((output_index, message.key.clone()), message.value.clone());
+                        }
+                    }
+                    Err(err) => {
+                        for output_index in targets {
+                            let update = ((output_index, Err(err.clone())), offset, Diff::ONE);
+                            data_output.give_fueled(&data_cap_set[0], update).await;
+                        }
+                    }
+                }
+            }
+        }))
+    });
+
+    let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
+
+    (
+        data_stream.as_collection(),
+        upper_stream,
+        errors,
+        stats_stream,
+        button.press_on_drop(),
+    )
+}
+
+/// Emits all buffered changes at the cluster time they were made.
+async fn flush_pending(
+    pending: &mut BTreeMap<(usize, Row), Row>,
+    time: MzOffset,
+    data_output: &StackedAsyncOutputHandle<MzOffset, (usize, Result<SourceMessage, DataflowError>)>,
+    data_cap_set: &CapabilitySet<MzOffset>,
+) {
+    for ((output_index, key), value) in std::mem::take(pending) {
+        let message = SourceMessage {
+            key,
+            value,
+            metadata: Row::default(),
+        };
+        let update = ((output_index, Ok(message)), time, Diff::ONE);
+        data_output.give_fueled(&data_cap_set[0], update).await;
+    }
+}
+
+fn is_history_lost(err: &mongodb::error::Error) -> bool {
+    matches!(&*err.kind, ErrorKind::Command(e) if e.code == CHANGE_STREAM_HISTORY_LOST)
+}
+
+/// Returns the `_id` from the document key of a change event.
+fn event_key(document_key: Option<&Document>) -> Result<Bson, DataflowError> {
+    let document_key = document_key.ok_or_else(|| {
+        DataflowError::from(DefiniteError::ValueDecodeError(
+            "change event is missing its document key".into(),
+        ))
+    })?;
+    match document_key.get("_id") {
+        Some(id) => Ok(id.clone()),
+        None => Err(missing_id(document_key)),
+    }
+}
+
+fn missing_id(document: &Document) -> DataflowError {
+    DefiniteError::ValueDecodeError(format!("document has no _id: {document}")).into()
+}
+
+/// Packs a document into the key and value the upsert envelope expects: the key is the `_id` and
+/// the value is a record of the `_id` and the document, or `NULL` for a deletion.
+fn pack_upsert(id: &Bson, document: Option<Document>) -> Result<SourceMessage, DataflowError> {
+    let to_decode_error = |err: serde_json::Error| {
+        DataflowError::from(DefiniteError::ValueDecodeError(err.to_string()))
+    };
+    let id = id.clone().into_relaxed_extjson();
+
+    let mut key = Row::default();
+    JsonbPacker::new(&mut key.packer())
+        .pack_serde_json(id.clone())
+        .map_err(to_decode_error)?;
+
+    let mut value = Row::default();
+    let mut packer = value.packer();
+    match document {
+        Some(document) => {
+            let document = Bson::Document(document).into_relaxed_extjson();
+            packer
+                .push_list_with(|packer| {
+                    JsonbPacker::new(packer).pack_serde_json(id)?;
+                    JsonbPacker::new(packer).pack_serde_json(document)
+                })
+                .map_err(to_decode_error)?;
+        }
+        None => packer.push(Datum::Null),
+    }
+
+    Ok(SourceMessage {
+        key,
+        value,
+        metadata: Row::default(),
+    })
+}
+
+/// Extracts the cluster time from a resume token.
+///
+/// Resume tokens are opaque, but their `_data` field is a hex encoded key string whose first
+/// component is the cluster time of the event, or of the point in the oplog the server scanned up
+/// to for tokens that are not associated with an event. A key string encodes a timestamp as the
+/// type byte `0x82` followed by the timestamp as a big endian integer, which is exactly how
+/// [`cluster_time_to_offset`] orders cluster times.
+fn resume_token_offset(token: &ResumeToken) -> Option<MzOffset> {
+    let token = mongodb::bson::to_document(token).ok()?;
+    resume_token_data_offset(token.get_str("_data").ok()?)
+}
+
+fn resume_token_data_offset(data: &str) -> Option<MzOffset> {
+    let bytes = hex::decode(data.get(..18)?).ok()?;
+    let (ty, ts) = bytes.split_first()?;
+    if *ty != 0x82 {
+        return None;
+    }
+    let ts = <[u8; 8]>::try_from(ts).ok()?;
+    Some(MzOffset::from(u64::from_be_bytes(ts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::Timestamp as ClusterTime;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn resume_token_cluster_time() {
+        // The `_data` of a resume token for an event at cluster time (1700000000, 7).
+        let data = "826553F100000000072B022C0100296E5A1004";
+        let expected = cluster_time_to_offset(ClusterTime {
+            time: 1_700_000_000,
+            increment: 7,
+        });
+        assert_eq!(resume_token_data_offset(data), Some(expected));
+
+        assert_eq!(resume_token_data_offset("82"), None);
+        assert_eq!(resume_token_data_offset("3C6553F1000000000700"), None);
+        assert_eq!(resume_token_data_offset("not hex at all, definitely"), None);
+    }
+}
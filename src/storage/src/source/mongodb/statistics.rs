@@ -0,0 +1,143 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the statistics collection of the [`MongoDbSourceConnection`] ingestion dataflow.
+
+use std::cell::{Cell, RefCell};
+
+use futures::StreamExt;
+use mz_ore::future::InTask;
+use mz_storage_types::sources::mongodb::{
+    OFFSET_KNOWN_INTERVAL, cluster_time_to_offset, operation_time,
+};
+use mz_storage_types::sources::{MongoDbSourceConnection, MzOffset};
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+use timely::dataflow::operators::Map;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use crate::source::types::{Probe, ProgressStatisticsUpdate};
+use crate::source::{RawSourceCreationConfig, probe};
+
+use super::{ReplicationError, TransientError};
+
+static STATISTICS: &str = "statistics";
+
+/// Renders the statistics dataflow.
+pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: MongoDbSourceConnection,
+    resume_uppers: impl futures::Stream<Item = Antichain<MzOffset>> + 'static,
+) -> (
+    Stream<G, ProgressStatisticsUpdate>,
+    Stream<G, ReplicationError>,
+    Stream<G, Probe<MzOffset>>,
+    PressOnDropButton,
+) {
+    let op_name = format!("MongoDbStatistics({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (stats_output, stats_stream) = builder.new_output();
+    let (probe_output, probe_stream) = builder.new_output();
+
+    let (button, transient_errors) = builder.build_fallible::<TransientError, _>(move |caps| {
+        Box::pin(async move {
+            let [stats_cap, probe_cap]: &mut [_; 2] = caps.try_into().unwrap();
+
+            // Only run the statistics collection on the worker responsible for it.
+            if !config.responsible_for(STATISTICS) {
+                // Emit 0, to mark this worker as having started up correctly.
+                stats_output.give(
+                    &stats_cap[0],
+                    ProgressStatisticsUpdate::SteadyState {
+                        offset_known: 0,
+                        offset_committed: 0,
+                    },
+                );
+                return Ok(());
+            }
+
+            let client = connection
+                .connection
+                .connect(
+                    &config.config.connection_context.secrets_reader,
+                    &config.config,
+                    InTask::Yes,
+                )
+                .await?;
+
+            tokio::pin!(resume_uppers);
+
+            let prev_offset_known = Cell::new(None);
+            let prev_offset_committed = Cell::new(None);
+            let stats_output = RefCell::new(stats_output);
+
+            let mut probe_ticker = probe::Ticker::new(
+                || OFFSET_KNOWN_INTERVAL.get(config.config.config_set()),
+                config.now_fn,
+            );
+            let probe_loop = async {
+                loop {
+                    let probe_ts = probe_ticker.tick().await;
+
+                    // Every change up to and including the latest operation has happened, so the
+                    // upstream frontier is just past it.
+                    let known = cluster_time_to_offset(operation_time(&client).await?);
+                    let offset_known = known.offset;
+                    if let Some(offset_committed) = prev_offset_committed.get() {
+                        stats_output.borrow_mut().give(
+                            &stats_cap[0],
+                            ProgressStatisticsUpdate::SteadyState {
+                                offset_known,
+                                offset_committed,
+                            },
+                        );
+                    }
+                    prev_offset_known.set(Some(offset_known));
+
+                    probe_output.give(
+                        &probe_cap[0],
+                        Probe {
+                            probe_ts,
+                            upstream_frontier: Antichain::from_elem(known + 1),
+                        },
+                    );
+                }
+            };
+            let commit_loop = async {
+                while let Some(committed_frontier) = resume_uppers.next().await {
+                    let Some(committed) = committed_frontier.as_option() else {
+                        continue;
+                    };
+                    let offset_committed = committed.offset;
+                    if let Some(offset_known) = prev_offset_known.get() {
+                        stats_output.borrow_mut().give(
+                            &stats_cap[0],
+                            ProgressStatisticsUpdate::SteadyState {
+                                offset_known,
+                                offset_committed,
+                            },
+                        );
+                    }
+                    prev_offset_committed.set(Some(offset_committed));
+                }
+            };
+
+            futures::future::join(probe_loop, commit_loop).await.0
+        })
+    });
+
+    (
+        stats_stream,
+        transient_errors.map(ReplicationError::from),
+        probe_stream,
+        button.press_on_drop(),
+    )
+}
@@ -28,9 +28,10 @@ use mz_repr::{GlobalId, Row, TimestampManipulation};
 use mz_storage_types::StorageDiff;
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::sources::{
-    GenericSourceConnection, IngestionDescription, KafkaSourceConnection,
-    LoadGeneratorSourceConnection, MySqlSourceConnection, PostgresSourceConnection,
-    SourceConnection, SourceData, SourceEnvelope, SourceTimestamp, SqlServerSource,
+    GenericSourceConnection, IngestionDescription, KafkaSourceConnection, KinesisSourceConnection,
+    LoadGeneratorSourceConnection, MongoDbSourceConnection, MySqlSourceConnection,
+    PostgresSourceConnection, SourceConnection, SourceData, SourceEnvelope, SourceTimestamp,
+    SqlServerSource,
 };
 use timely::order::PartialOrder;
 use timely::progress::frontier::MutableAntichain;
@@ -344,6 +345,17 @@ impl<T: Timestamp + TimestampManipulation + Lattice + Codec64 + Display + Sync>
                                 .await;
                                 to_vec_row(uppers)
                             }
+                            GenericSourceConnection::Kinesis(_) => {
+                                let uppers = reclock_resume_uppers::<KinesisSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
                             GenericSourceConnection::Postgres(_) => {
                                 let uppers = reclock_resume_uppers::<PostgresSourceConnection, _>(
                                     &id,
@@ -377,6 +389,17 @@ impl<T: Timestamp + TimestampManipulation + Lattice + Codec64 + Display + Sync>
                                 .await;
                                 to_vec_row(uppers)
                             }
+                            GenericSourceConnection::MongoDb(_) => {
+                                let uppers = reclock_resume_uppers::<MongoDbSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
                             GenericSourceConnection::LoadGenerator(_) => {
                                 let uppers =
                                     reclock_resume_uppers::<LoadGeneratorSourceConnection, _>(
@@ -7,6 +7,15 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Duration;
+
+use anyhow::{Context, bail};
+use aws_sdk_kinesis::types::StreamStatus;
+use mz_aws_util::kinesis::Client;
+use mz_ore::retry::Retry;
+
+use crate::action::State;
+
 mod create_stream;
 mod ingest;
 mod update_shards;
@@ -16,3 +25,38 @@ pub use create_stream::run_create_stream;
 pub use ingest::run_ingest;
 pub use update_shards::run_update_shards;
 pub use verify::run_verify;
+
+/// Returns the name of the stream that testdrive uses for `stream`.
+///
+/// Like Kafka topics, every testdrive run uses a fresh stream so that runs
+/// never observe each other's records.
+fn stream_name(stream: &str, state: &State) -> String {
+    format!("testdrive-{}-{}", stream, state.seed)
+}
+
+/// Waits until `stream` is `ACTIVE`, which Kinesis requires before records can
+/// be written to it or its shards can be updated.
+async fn wait_for_active_stream(
+    client: &Client,
+    stream: &str,
+    timeout: Duration,
+) -> Result<(), anyhow::Error> {
+    Retry::default()
+        .max_duration(timeout)
+        .retry_async(|_| async {
+            let summary = client
+                .describe_stream_summary()
+                .stream_name(stream)
+                .send()
+                .await
+                .context("describing stream")?;
+            let status = summary
+                .stream_description_summary()
+                .map(|s| s.stream_status().clone());
+            match status {
+                Some(StreamStatus::Active) => Ok(()),
+                status => bail!("stream {stream} is not active: {status:?}"),
+            }
+        })
+        .await
+}
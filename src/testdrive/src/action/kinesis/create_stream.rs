@@ -0,0 +1,36 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Context;
+
+use crate::action::kinesis::{stream_name, wait_for_active_stream};
+use crate::action::{ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+pub async fn run_create_stream(
+    mut cmd: BuiltinCommand,
+    state: &State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let stream = stream_name(&cmd.args.string("stream")?, state);
+    let shards: i32 = cmd.args.opt_parse("shards")?.unwrap_or(1);
+    cmd.args.done()?;
+
+    println!("Creating Kinesis stream {stream} with {shards} shards");
+
+    let client = mz_aws_util::kinesis::new_client(&state.aws_config);
+    client
+        .create_stream()
+        .stream_name(&stream)
+        .shard_count(shards)
+        .send()
+        .await
+        .context("creating stream")?;
+    wait_for_active_stream(&client, &stream, state.default_timeout).await?;
+    Ok(ControlFlow::Continue)
+}
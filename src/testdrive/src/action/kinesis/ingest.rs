@@ -0,0 +1,48 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Context;
+use aws_sdk_kinesis::primitives::Blob;
+
+use crate::action::kinesis::stream_name;
+use crate::action::{ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+/// Writes every input line as one record to a Kinesis stream.
+///
+/// Records are routed by `partition-key` if one is given, and by their own
+/// contents otherwise. Records with the same partition key land on the same
+/// shard in the order in which they appear in the input.
+pub async fn run_ingest(
+    mut cmd: BuiltinCommand,
+    state: &State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let stream = stream_name(&cmd.args.string("stream")?, state);
+    let partition_key = cmd.args.opt_string("partition-key");
+    let repeat: usize = cmd.args.opt_parse("repeat")?.unwrap_or(1);
+    cmd.args.done()?;
+
+    println!("Ingesting data into Kinesis stream {stream}");
+
+    let client = mz_aws_util::kinesis::new_client(&state.aws_config);
+    for _ in 0..repeat {
+        for line in &cmd.input {
+            let key = partition_key.as_deref().unwrap_or(line);
+            client
+                .put_record()
+                .stream_name(&stream)
+                .partition_key(key)
+                .data(Blob::new(line.as_bytes()))
+                .send()
+                .await
+                .with_context(|| format!("putting record {line:?}"))?;
+        }
+    }
+    Ok(ControlFlow::Continue)
+}
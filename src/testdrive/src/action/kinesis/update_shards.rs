@@ -0,0 +1,42 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Context;
+use aws_sdk_kinesis::types::ScalingType;
+
+use crate::action::kinesis::{stream_name, wait_for_active_stream};
+use crate::action::{ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+/// Reshards a Kinesis stream to `shards` open shards.
+///
+/// Kinesis implements this by splitting or merging the existing shards, which
+/// closes them and opens their children.
+pub async fn run_update_shards(
+    mut cmd: BuiltinCommand,
+    state: &State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let stream = stream_name(&cmd.args.string("stream")?, state);
+    let shards: i32 = cmd.args.parse("shards")?;
+    cmd.args.done()?;
+
+    println!("Updating Kinesis stream {stream} to {shards} shards");
+
+    let client = mz_aws_util::kinesis::new_client(&state.aws_config);
+    client
+        .update_shard_count()
+        .stream_name(&stream)
+        .target_shard_count(shards)
+        .scaling_type(ScalingType::UniformScaling)
+        .send()
+        .await
+        .context("updating shard count")?;
+    wait_for_active_stream(&client, &stream, state.default_timeout).await?;
+    Ok(ControlFlow::Continue)
+}
@@ -0,0 +1,58 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::{Context, bail};
+use mz_ore::retry::Retry;
+
+use crate::action::kinesis::stream_name;
+use crate::action::{ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+/// Verifies the number of open and closed shards of a Kinesis stream.
+pub async fn run_verify(
+    mut cmd: BuiltinCommand,
+    state: &State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let stream = stream_name(&cmd.args.string("stream")?, state);
+    let expected_open: usize = cmd.args.parse("open-shards")?;
+    let expected_closed: Option<usize> = cmd.args.opt_parse("closed-shards")?;
+    cmd.args.done()?;
+
+    println!("Verifying shards of Kinesis stream {stream}");
+
+    let client = mz_aws_util::kinesis::new_client(&state.aws_config);
+    Retry::default()
+        .max_duration(state.default_timeout)
+        .retry_async(|_| async {
+            let shards = mz_aws_util::kinesis::list_shards(&client, &stream)
+                .await
+                .context("listing shards")?;
+            let (closed, open): (Vec<_>, Vec<_>) = shards.iter().partition(|s| {
+                s.sequence_number_range()
+                    .is_some_and(|r| r.ending_sequence_number().is_some())
+            });
+            if open.len() != expected_open {
+                bail!(
+                    "stream {stream} has {} open shards, expected {expected_open}",
+                    open.len()
+                );
+            }
+            if let Some(expected_closed) = expected_closed {
+                if closed.len() != expected_closed {
+                    bail!(
+                        "stream {stream} has {} closed shards, expected {expected_closed}",
+                        closed.len()
+                    );
+                }
+            }
+            Ok(())
+        })
+        .await?;
+    Ok(ControlFlow::Continue)
+}
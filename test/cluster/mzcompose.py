@@ -3567,6 +3567,7 @@ def workflow_statement_logging(c: Composition, parser: WorkflowArgumentParser) -
         )
 
         c.run_testdrive_files("statement-logging/statement-logging.td")
+        c.run_testdrive_files("statement-logging/peek-result-cache.td")
 
 
 def workflow_blue_green_deployment(
@@ -0,0 +1,68 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""
+Native Kinesis source tests, functional, against LocalStack.
+"""
+
+import random
+
+from materialize.mzcompose.composition import Composition
+from materialize.mzcompose.services.localstack import Localstack
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.mz import Mz
+from materialize.mzcompose.services.testdrive import Testdrive
+
+AWS_ENDPOINT_URL = "http://localstack:4566"
+AWS_REGION = "us-east-1"
+AWS_ACCESS_KEY_ID = "LSIAQAAAAAAVNCBMPNSG"
+AWS_SECRET_ACCESS_KEY = "secret"
+
+SERVICES = [
+    Localstack(),
+    Mz(app_password=""),
+    Materialized(depends_on=["localstack"]),
+    Testdrive(
+        aws_endpoint=AWS_ENDPOINT_URL,
+        aws_access_key_id=AWS_ACCESS_KEY_ID,
+        aws_secret_access_key=AWS_SECRET_ACCESS_KEY,
+        default_timeout="60s",
+    ),
+]
+
+
+#
+# Test that Kinesis ingestion works, including across restarts and reshards.
+#
+def workflow_default(c: Composition) -> None:
+    # Start with a fresh state
+    c.kill("materialized")
+    c.rm("materialized")
+
+    c.up("localstack", "materialized")
+    seed = random.getrandbits(16)
+
+    def run(file: str) -> None:
+        c.run_testdrive_files(
+            "--no-reset",
+            "--max-errors=1",
+            f"--seed={seed}",
+            f"--var=aws-region={AWS_REGION}",
+            f"--var=default-replica-size={Materialized.Size.DEFAULT_SIZE}-{Materialized.Size.DEFAULT_SIZE}",
+            file,
+        )
+
+    run("10-create-source.td")
+    run("20-reshard.td")
+
+    # Records written while Materialize is down must be picked up from where
+    # the source left off.
+    c.kill("materialized")
+    c.up("materialized")
+    run("30-resumption.td")
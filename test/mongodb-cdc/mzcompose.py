@@ -0,0 +1,113 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""
+Native MongoDB source tests, functional.
+"""
+
+import random
+
+from materialize.mzcompose.composition import Composition
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.mongodb import MongoDb
+from materialize.mzcompose.services.mz import Mz
+from materialize.mzcompose.services.testdrive import Testdrive
+
+SERVICES = [
+    Mz(app_password=""),
+    Materialized(),
+    Testdrive(),
+    MongoDb(),
+]
+
+
+def mongosh(c: Composition, script: str) -> None:
+    """Runs a script against the `test` database of the MongoDB replica set.
+
+    Testdrive cannot talk to MongoDB, so upstream changes are made from here,
+    in between the testdrive files that observe them.
+    """
+    c.exec("mongodb", "mongosh", "--quiet", "test", "--eval", script)
+
+
+#
+# Test that MongoDB ingestion works
+#
+def workflow_default(c: Composition) -> None:
+    # Start with a fresh state
+    c.kill("mongodb")
+    c.rm("mongodb")
+    c.kill("materialized")
+    c.rm("materialized")
+
+    c.up("materialized", "mongodb")
+    seed = random.getrandbits(16)
+
+    def run(file: str) -> None:
+        c.run_testdrive_files(
+            "--no-reset",
+            "--max-errors=1",
+            f"--seed={seed}",
+            f"--var=default-replica-size={Materialized.Size.DEFAULT_SIZE}-{Materialized.Size.DEFAULT_SIZE}",
+            file,
+        )
+
+    mongosh(
+        c,
+        """
+        db.dropDatabase();
+        db.people.insertMany([
+            { _id: NumberInt(1), name: "alice", tags: ["a", "b"] },
+            { _id: NumberInt(2), name: "bob", address: { city: "Berlin" } },
+            { _id: NumberInt(3), name: "carol" },
+        ]);
+        db.events.insertOne({ _id: NumberInt(1), kind: "created" });
+        db.system_ignored.insertOne({ _id: NumberInt(1) });
+        """,
+    )
+    run("10-create-source.td")
+
+    mongosh(
+        c,
+        """
+        db.people.insertOne({ _id: NumberInt(4), name: "dave" });
+        db.people.updateOne({ _id: NumberInt(1) }, { $set: { name: "alicia" } });
+        db.people.replaceOne({ _id: NumberInt(2) }, { name: "robert" });
+        db.people.deleteOne({ _id: NumberInt(3) });
+        db.events.insertOne({ _id: NumberInt(2), kind: "updated" });
+
+        // Changes made by a transaction share a cluster time, so only the
+        // final state of each document may be observed.
+        const session = db.getMongo().startSession();
+        const people = session.getDatabase("test").people;
+        session.startTransaction();
+        people.insertOne({ _id: NumberInt(5), name: "erin" });
+        people.updateOne({ _id: NumberInt(5) }, { $set: { name: "erin2" } });
+        people.deleteOne({ _id: NumberInt(4) });
+        people.insertOne({ _id: NumberInt(4), name: "dave2" });
+        session.commitTransaction();
+        """,
+    )
+    run("20-replication.td")
+
+    # Changes made while Materialize is down must be picked up from where the
+    # source left off.
+    c.kill("materialized")
+    mongosh(
+        c,
+        """
+        db.people.insertOne({ _id: NumberInt(6), name: "frank" });
+        db.people.deleteOne({ _id: NumberInt(5) });
+        """,
+    )
+    c.up("materialized")
+    run("30-resumption.td")
+
+    mongosh(c, "db.events.drop();")
+    run("40-drop-collection.td")
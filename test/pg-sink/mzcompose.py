@@ -0,0 +1,55 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""
+Native Postgres sink tests, functional.
+"""
+
+from materialize.mzcompose.composition import Composition
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.mz import Mz
+from materialize.mzcompose.services.postgres import Postgres
+from materialize.mzcompose.services.testdrive import Testdrive
+
+SERVICES = [
+    Mz(app_password=""),
+    Materialized(depends_on=["postgres"]),
+    Testdrive(default_timeout="60s"),
+    Postgres(),
+]
+
+
+#
+# Test that the Postgres sink applies inserts, updates and deletes, resumes
+# from its progress table across restarts and is fenced out by other writers.
+#
+def workflow_default(c: Composition) -> None:
+    # Start with a fresh state
+    c.kill("materialized")
+    c.rm("materialized")
+
+    c.up("postgres", "materialized")
+
+    def run(file: str) -> None:
+        c.run_testdrive_files(
+            "--no-reset",
+            "--max-errors=1",
+            f"--var=default-replica-size={Materialized.Size.DEFAULT_SIZE}-{Materialized.Size.DEFAULT_SIZE}",
+            file,
+        )
+
+    run("10-create-sink.td")
+
+    # Changes made while Materialize is down must be written once the sink
+    # resumes, and changes committed before the restart must not be replayed.
+    c.kill("materialized")
+    c.up("materialized")
+    run("20-resumption.td")
+
+    run("30-fencing.td")